@@ -147,11 +147,14 @@ pub fn dump_database(storage: &mut SledStorage, dump_path: PathBuf) -> Result<()
                     table_name: schema.table_name.clone(),
                     columns: Vec::new(),
                     source: gluesql_core::ast::Query {
+                        with: None,
                         body: SetExpr::Values(Values(exprs_list)),
                         order_by: Vec::new(),
                         limit: None,
                         offset: None,
                     },
+                    on_conflict: None,
+                    returning: Vec::new(),
                 }
                 .to_sql();
 
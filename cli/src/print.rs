@@ -106,6 +106,11 @@ impl<'a, W: Write> Print<W> {
             Payload::Create => self.writeln("Table created")?,
             Payload::DropTable => self.writeln("Table dropped")?,
             Payload::DropFunction => self.writeln("Function dropped")?,
+            Payload::DropSequence => self.writeln("Sequence dropped")?,
+            Payload::DropView => self.writeln("View dropped")?,
+            Payload::DropTrigger => self.writeln("Trigger dropped")?,
+            Payload::DropProcedure => self.writeln("Procedure dropped")?,
+            Payload::Call => self.writeln("Procedure called")?,
             Payload::AlterTable => self.writeln("Table altered")?,
             Payload::CreateIndex => self.writeln("Index created")?,
             Payload::DropIndex => self.writeln("Index dropped")?,
@@ -113,6 +118,8 @@ impl<'a, W: Write> Print<W> {
             Payload::Rollback => self.writeln("Rollback completed")?,
             Payload::StartTransaction => self.writeln("Transaction started")?,
             Payload::Insert(n) => affected(*n, "inserted")?,
+            Payload::Explain(plan) => self.write(plan)?,
+            Payload::ExplainAnalyze(analyzed) => self.write(analyzed)?,
             Payload::Delete(n) => affected(*n, "deleted")?,
             Payload::Update(n) => affected(*n, "updated")?,
             Payload::ShowVariable(PayloadVariable::Version(v)) => self.writeln(format!("v{v}"))?,
@@ -132,6 +139,14 @@ impl<'a, W: Write> Print<W> {
                 let table = self.build_table(table);
                 self.writeln(table)?;
             }
+            Payload::ShowVariable(PayloadVariable::Views(names)) => {
+                let mut table = self.get_table(["views"]);
+                for name in names {
+                    table.add_record([name]);
+                }
+                let table = self.build_table(table);
+                self.writeln(table)?;
+            }
             Payload::ShowColumns(columns) => {
                 let mut table = self.get_table(vec!["Field", "Type"]);
                 for (field, field_type) in columns {
@@ -357,9 +372,13 @@ mod tests {
 
     #[test]
     fn print_payload() {
-        use gluesql_core::{
-            ast::DataType,
-            prelude::{Payload, PayloadVariable, Value},
+        use {
+            gluesql_core::{
+                ast::DataType,
+                executor::{AnalyzedPlan, PlanNode, PlanStats, StorageCalls},
+                prelude::{Payload, PayloadVariable, Value},
+            },
+            std::time::Duration,
         };
 
         let mut print = Print::new(Vec::new(), None, Default::default());
@@ -384,8 +403,53 @@ mod tests {
         test!(Payload::DropTable, "Table dropped");
         test!(Payload::AlterTable, "Table altered");
         test!(Payload::CreateIndex, "Index created");
+        test!(
+            Payload::Explain(PlanNode::new(
+                "Limit",
+                Some("LIMIT 3".to_owned()),
+                vec![PlanNode::new(
+                    "Hash Join",
+                    Some("INNER, HASH KEY t.id = p.team_id".to_owned()),
+                    vec![
+                        PlanNode::new("Table Scan", Some("Player AS p".to_owned()), Vec::new()),
+                        PlanNode::new("Table Scan", Some("Team AS t".to_owned()), Vec::new()),
+                    ],
+                )],
+            )),
+            "
+Limit: LIMIT 3
+└─ Hash Join: INNER, HASH KEY t.id = p.team_id
+   ├─ Table Scan: Player AS p
+   └─ Table Scan: Team AS t"
+        );
+        test!(
+            Payload::ExplainAnalyze(AnalyzedPlan {
+                plan: PlanNode::new("Table Scan", Some("Item".to_owned()), Vec::new()).with_stats(
+                    Some(PlanStats {
+                        rows_in: 0,
+                        rows_out: 2,
+                        elapsed: Duration::from_micros(20),
+                    })
+                ),
+                storage_calls: StorageCalls {
+                    scan_data: 1,
+                    scan_indexed_data: 0,
+                    fetch_data: 0,
+                },
+                elapsed: Duration::from_micros(1500),
+            }),
+            "
+Table Scan: Item (rows: 2, time: 20µs)
+Storage Calls: scan_data=1, scan_indexed_data=0, fetch_data=0
+Execution Time: 1.5ms"
+        );
         test!(Payload::DropIndex, "Index dropped");
         test!(Payload::DropFunction, "Function dropped");
+        test!(Payload::DropSequence, "Sequence dropped");
+        test!(Payload::DropView, "View dropped");
+        test!(Payload::DropTrigger, "Trigger dropped");
+        test!(Payload::DropProcedure, "Procedure dropped");
+        test!(Payload::Call, "Procedure called");
         test!(Payload::Commit, "Commit completed");
         test!(Payload::Rollback, "Rollback completed");
         test!(Payload::StartTransaction, "Transaction started");
@@ -407,6 +471,13 @@ mod tests {
             Payload::ShowVariable(PayloadVariable::Functions(Vec::new())),
             "
 | functions |"
+        );
+        test!(
+            Payload::ShowVariable(PayloadVariable::Views(vec!["Adult".to_owned()])),
+            "
+| views |
+|-------|
+| Adult |"
         );
         test!(
             Payload::ShowVariable(PayloadVariable::Tables(
@@ -19,7 +19,7 @@ impl ToSql for AstLiteral {
         match self {
             AstLiteral::Boolean(b) => b.to_string().to_uppercase(),
             AstLiteral::Number(n) => n.to_string(),
-            AstLiteral::QuotedString(qs) => format!("'{qs}'"),
+            AstLiteral::QuotedString(qs) => format!("'{}'", qs.replace('\'', "''")),
             AstLiteral::HexString(hs) => format!("'{hs}'"),
             AstLiteral::Null => "NULL".to_owned(),
         }
@@ -60,6 +60,10 @@ mod tests {
             "'hello'",
             AstLiteral::QuotedString("hello".to_owned()).to_sql()
         );
+        assert_eq!(
+            "'it''s'",
+            AstLiteral::QuotedString("it's".to_owned()).to_sql()
+        );
         assert_eq!("NULL", AstLiteral::Null.to_sql());
     }
 }
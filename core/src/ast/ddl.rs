@@ -1,5 +1,5 @@
 use {
-    super::{DataType, Expr},
+    super::{Assignment, DataType, Expr, Query, Statement},
     crate::ast::ToSql,
     serde::{Deserialize, Serialize},
 };
@@ -20,6 +20,25 @@ pub enum AlterTableOperation {
     },
     /// `RENAME TO <table_name>`
     RenameTable { table_name: String },
+    /// `ALTER [ COLUMN ] <column_name> <operation>`
+    AlterColumn {
+        column_name: String,
+        operation: AlterColumnOperation,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AlterColumnOperation {
+    /// `[ SET DATA ] TYPE <data_type>`
+    SetDataType(DataType),
+    /// `SET DEFAULT <expr>`
+    SetDefault(Expr),
+    /// `DROP DEFAULT`
+    DropDefault,
+    /// `SET NOT NULL`
+    SetNotNull,
+    /// `DROP NOT NULL`
+    DropNotNull,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -38,6 +57,79 @@ pub struct ColumnUniqueOption {
     pub is_primary: bool,
 }
 
+/// `[ CONSTRAINT <name> ] FOREIGN KEY (<column>) REFERENCES <table> (<column>)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ForeignKey {
+    pub name: String,
+    pub referencing_column_name: String,
+    pub referenced_table_name: String,
+    pub referenced_column_name: String,
+    pub on_delete: ReferentialAction,
+    pub on_update: ReferentialAction,
+}
+
+/// `[ CONSTRAINT <name> ] { PRIMARY KEY | UNIQUE } (<column>, ...)` on several columns
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UniqueKey {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub is_primary: bool,
+}
+
+/// `[ CONSTRAINT <name> ] CHECK (<expr>)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Check {
+    pub name: String,
+    pub expr: Expr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReferentialAction {
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+}
+
+/// Option of `CREATE SEQUENCE`, evaluated when the sequence is created
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SequenceOption {
+    IncrementBy(Expr),
+    /// `MINVALUE <expr>`, or `NO MINVALUE` when `None`
+    MinValue(Option<Expr>),
+    /// `MAXVALUE <expr>`, or `NO MAXVALUE` when `None`
+    MaxValue(Option<Expr>),
+    StartWith(Expr),
+    Cycle(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TriggerTiming {
+    Before,
+    After,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TriggerEvent {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// Action run by a trigger for each row, which can refer to the row as `NEW` and `OLD`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TriggerAction {
+    /// `EXECUTE FUNCTION <name>(<args>)`, a `BEFORE` trigger skips the row when it returns FALSE
+    Function {
+        name: String,
+        args: Vec<Expr>,
+    },
+    Statement(Box<Statement>),
+    /// `SET NEW.<column> = <expr> [, ..]`, changes the row of a `BEFORE INSERT` or
+    /// `BEFORE UPDATE` trigger before it is validated
+    SetNew(Vec<Assignment>),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct OperateFunctionArg {
     pub name: String,
@@ -46,6 +138,53 @@ pub struct OperateFunctionArg {
     pub default: Option<Expr>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FunctionBody {
+    /// `RETURN <expr>`
+    Return(Expr),
+    /// `BEGIN <procedural> .. END`
+    Block(Vec<Procedural>),
+    /// `RETURNS TABLE (<columns>) RETURN <query>`
+    Table {
+        columns: Vec<ColumnDef>,
+        query: Box<Query>,
+    },
+}
+
+/// Statement of a `BEGIN .. END` block of a function or a procedure
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Procedural {
+    /// `DECLARE <name> <data_type> [DEFAULT <expr>]`
+    Declare {
+        name: String,
+        data_type: DataType,
+        default: Option<Expr>,
+    },
+    /// `SET <name> = <expr>`
+    Set { name: String, value: Expr },
+    /// `IF <expr> THEN .. [ELSEIF <expr> THEN ..] [ELSE ..] END IF`,
+    /// `CASE` statements are translated into `IF` as well
+    If {
+        branches: Vec<(Expr, Vec<Procedural>)>,
+        else_body: Option<Vec<Procedural>>,
+    },
+    /// `WHILE <expr> DO .. END WHILE`
+    While {
+        condition: Expr,
+        body: Vec<Procedural>,
+    },
+    /// `LOOP .. END LOOP`
+    Loop(Vec<Procedural>),
+    /// `EXIT [WHEN <expr>]`, leaves the innermost loop
+    Exit(Option<Expr>),
+    /// `CONTINUE [WHEN <expr>]`, starts the next iteration of the innermost loop
+    Continue(Option<Expr>),
+    /// `RETURN [<expr>]`
+    Return(Option<Expr>),
+    /// SQL statement, only allowed in procedures
+    Statement(Box<Statement>),
+}
+
 impl ToSql for AlterTableOperation {
     fn to_sql(&self) -> String {
         match self {
@@ -66,6 +205,22 @@ impl ToSql for AlterTableOperation {
             AlterTableOperation::RenameTable { table_name } => {
                 format!(r#"RENAME TO "{table_name}""#)
             }
+            AlterTableOperation::AlterColumn {
+                column_name,
+                operation,
+            } => format!(r#"ALTER COLUMN "{column_name}" {}"#, operation.to_sql()),
+        }
+    }
+}
+
+impl ToSql for AlterColumnOperation {
+    fn to_sql(&self) -> String {
+        match self {
+            AlterColumnOperation::SetDataType(data_type) => format!("SET DATA TYPE {data_type}"),
+            AlterColumnOperation::SetDefault(expr) => format!("SET DEFAULT {}", expr.to_sql()),
+            AlterColumnOperation::DropDefault => "DROP DEFAULT".to_owned(),
+            AlterColumnOperation::SetNotNull => "SET NOT NULL".to_owned(),
+            AlterColumnOperation::DropNotNull => "DROP NOT NULL".to_owned(),
         }
     }
 }
@@ -99,6 +254,72 @@ impl ToSql for ColumnDef {
     }
 }
 
+impl ToSql for SequenceOption {
+    fn to_sql(&self) -> String {
+        match self {
+            SequenceOption::IncrementBy(expr) => format!("INCREMENT BY {}", expr.to_sql()),
+            SequenceOption::MinValue(Some(expr)) => format!("MINVALUE {}", expr.to_sql()),
+            SequenceOption::MinValue(None) => "NO MINVALUE".to_owned(),
+            SequenceOption::MaxValue(Some(expr)) => format!("MAXVALUE {}", expr.to_sql()),
+            SequenceOption::MaxValue(None) => "NO MAXVALUE".to_owned(),
+            SequenceOption::StartWith(expr) => format!("START WITH {}", expr.to_sql()),
+            SequenceOption::Cycle(true) => "CYCLE".to_owned(),
+            SequenceOption::Cycle(false) => "NO CYCLE".to_owned(),
+        }
+    }
+}
+
+impl ToSql for TriggerTiming {
+    fn to_sql(&self) -> String {
+        match self {
+            TriggerTiming::Before => "BEFORE",
+            TriggerTiming::After => "AFTER",
+        }
+        .to_owned()
+    }
+}
+
+impl ToSql for TriggerEvent {
+    fn to_sql(&self) -> String {
+        match self {
+            TriggerEvent::Insert => "INSERT",
+            TriggerEvent::Update => "UPDATE",
+            TriggerEvent::Delete => "DELETE",
+        }
+        .to_owned()
+    }
+}
+
+impl ToSql for TriggerAction {
+    fn to_sql(&self) -> String {
+        match self {
+            TriggerAction::Function { name, args } => {
+                let args = args
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("EXECUTE FUNCTION {name}({args})")
+            }
+            TriggerAction::Statement(statement) => {
+                let sql = statement.to_sql();
+
+                sql.strip_suffix(';').map(str::to_owned).unwrap_or(sql)
+            }
+            TriggerAction::SetNew(assignments) => {
+                let assignments = assignments
+                    .iter()
+                    .map(|assignment| format!(r#""NEW".{}"#, assignment.to_sql()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("SET {assignments}")
+            }
+        }
+    }
+}
+
 impl ToSql for ColumnUniqueOption {
     fn to_sql(&self) -> String {
         if self.is_primary {
@@ -110,6 +331,66 @@ impl ToSql for ColumnUniqueOption {
     }
 }
 
+impl ToSql for ForeignKey {
+    fn to_sql(&self) -> String {
+        let ForeignKey {
+            name,
+            referencing_column_name,
+            referenced_table_name,
+            referenced_column_name,
+            on_delete,
+            on_update,
+        } = self;
+
+        format!(
+            r#"CONSTRAINT "{name}" FOREIGN KEY ("{referencing_column_name}") REFERENCES "{referenced_table_name}" ("{referenced_column_name}") ON DELETE {} ON UPDATE {}"#,
+            on_delete.to_sql(),
+            on_update.to_sql(),
+        )
+    }
+}
+
+impl ToSql for UniqueKey {
+    fn to_sql(&self) -> String {
+        let UniqueKey {
+            name,
+            columns,
+            is_primary,
+        } = self;
+        let unique = ColumnUniqueOption {
+            is_primary: *is_primary,
+        }
+        .to_sql();
+        let columns = columns
+            .iter()
+            .map(|column| format!(r#""{column}""#))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(r#"CONSTRAINT "{name}" {unique} ({columns})"#)
+    }
+}
+
+impl ToSql for Check {
+    fn to_sql(&self) -> String {
+        let Check { name, expr } = self;
+
+        format!(r#"CONSTRAINT "{name}" CHECK ({})"#, expr.to_sql())
+    }
+}
+
+impl ToSql for ReferentialAction {
+    fn to_sql(&self) -> String {
+        match self {
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+        }
+        .to_owned()
+    }
+}
+
 impl ToSql for OperateFunctionArg {
     fn to_sql(&self) -> String {
         let OperateFunctionArg {
@@ -125,10 +406,98 @@ impl ToSql for OperateFunctionArg {
     }
 }
 
+impl ToSql for FunctionBody {
+    fn to_sql(&self) -> String {
+        match self {
+            FunctionBody::Return(expr) => format!("RETURN {}", expr.to_sql()),
+            FunctionBody::Block(body) => format!("BEGIN {}END", block_to_sql(body)),
+            FunctionBody::Table { columns, query } => {
+                let columns = columns
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("RETURNS TABLE ({columns}) RETURN {}", query.to_sql())
+            }
+        }
+    }
+}
+
+impl ToSql for Procedural {
+    fn to_sql(&self) -> String {
+        let when = |condition: &Option<Expr>| {
+            condition
+                .as_ref()
+                .map(|expr| format!(" WHEN {}", expr.to_sql()))
+                .unwrap_or_default()
+        };
+
+        match self {
+            Procedural::Declare {
+                name,
+                data_type,
+                default,
+            } => {
+                let default = default
+                    .as_ref()
+                    .map(|expr| format!(" DEFAULT {}", expr.to_sql()))
+                    .unwrap_or_default();
+
+                format!(r#"DECLARE "{name}" {data_type}{default};"#)
+            }
+            Procedural::Set { name, value } => format!(r#"SET "{name}" = {};"#, value.to_sql()),
+            Procedural::If {
+                branches,
+                else_body,
+            } => {
+                let branches = branches
+                    .iter()
+                    .map(|(condition, body)| {
+                        format!("{} THEN {}", condition.to_sql(), block_to_sql(body))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("ELSEIF ");
+                let else_body = else_body
+                    .as_ref()
+                    .map(|body| format!("ELSE {}", block_to_sql(body)))
+                    .unwrap_or_default();
+
+                format!("IF {branches}{else_body}END IF;")
+            }
+            Procedural::While { condition, body } => {
+                format!(
+                    "WHILE {} DO {}END WHILE;",
+                    condition.to_sql(),
+                    block_to_sql(body)
+                )
+            }
+            Procedural::Loop(body) => format!("LOOP {}END LOOP;", block_to_sql(body)),
+            Procedural::Exit(condition) => format!("EXIT{};", when(condition)),
+            Procedural::Continue(condition) => format!("CONTINUE{};", when(condition)),
+            Procedural::Return(None) => "RETURN;".to_owned(),
+            Procedural::Return(Some(expr)) => format!("RETURN {};", expr.to_sql()),
+            Procedural::Statement(statement) => statement.to_sql(),
+        }
+    }
+}
+
+/// Renders each statement of a block followed by a space
+fn block_to_sql(body: &[Procedural]) -> String {
+    body.iter()
+        .map(|procedural| format!("{} ", procedural.to_sql()))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::ast::{
-        AstLiteral, ColumnDef, ColumnUniqueOption, DataType, Expr, OperateFunctionArg, ToSql,
+    use {
+        crate::ast::{
+            AstLiteral, BinaryOperator, Check, ColumnDef, ColumnUniqueOption, DataType, Expr,
+            ForeignKey, FunctionBody, OperateFunctionArg, Procedural, Query, ReferentialAction,
+            Select, SelectItem, SetExpr, TableFactor, TableWithJoins, ToSql, UniqueKey,
+        },
+        bigdecimal::BigDecimal,
     };
 
     #[test]
@@ -194,6 +563,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_sql_foreign_key() {
+        assert_eq!(
+            r#"CONSTRAINT "FK_user_id-User_id" FOREIGN KEY ("user_id") REFERENCES "User" ("id") ON DELETE CASCADE ON UPDATE NO ACTION"#,
+            ForeignKey {
+                name: "FK_user_id-User_id".to_owned(),
+                referencing_column_name: "user_id".to_owned(),
+                referenced_table_name: "User".to_owned(),
+                referenced_column_name: "id".to_owned(),
+                on_delete: ReferentialAction::Cascade,
+                on_update: ReferentialAction::NoAction,
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_unique_key() {
+        assert_eq!(
+            r#"CONSTRAINT "PK_tenant_id_order_id" PRIMARY KEY ("tenant_id", "order_id")"#,
+            UniqueKey {
+                name: "PK_tenant_id_order_id".to_owned(),
+                columns: vec!["tenant_id".to_owned(), "order_id".to_owned()],
+                is_primary: true,
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"CONSTRAINT "UQ_a_b" UNIQUE ("a", "b")"#,
+            UniqueKey {
+                name: "UQ_a_b".to_owned(),
+                columns: vec!["a".to_owned(), "b".to_owned()],
+                is_primary: false,
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_check() {
+        assert_eq!(
+            r#"CONSTRAINT "CK_price" CHECK ("price" > 0)"#,
+            Check {
+                name: "CK_price".to_owned(),
+                expr: Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier("price".to_owned())),
+                    op: BinaryOperator::Gt,
+                    right: Box::new(Expr::Literal(AstLiteral::Number(BigDecimal::from(0)))),
+                },
+            }
+            .to_sql()
+        );
+    }
+
     #[test]
     fn to_sql_operate_function_arg() {
         assert_eq!(
@@ -216,4 +640,108 @@ mod tests {
             .to_sql()
         );
     }
+
+    #[test]
+    fn to_sql_function_body() {
+        let num = || Expr::Identifier("num".to_owned());
+        let number = |n: i64| Expr::Literal(AstLiteral::Number(BigDecimal::from(n)));
+
+        assert_eq!(r#"RETURN "num""#, FunctionBody::Return(num()).to_sql());
+
+        assert_eq!(
+            r#"BEGIN DECLARE "num" INT DEFAULT 0; WHILE "num" < 3 DO SET "num" = "num" + 1; EXIT WHEN "num" = 2; END WHILE; IF "num" = 1 THEN RETURN 1; ELSEIF "num" = 2 THEN RETURN 2; ELSE RETURN; END IF; LOOP CONTINUE; END LOOP; END"#,
+            FunctionBody::Block(vec![
+                Procedural::Declare {
+                    name: "num".to_owned(),
+                    data_type: DataType::Int,
+                    default: Some(number(0)),
+                },
+                Procedural::While {
+                    condition: Expr::BinaryOp {
+                        left: Box::new(num()),
+                        op: BinaryOperator::Lt,
+                        right: Box::new(number(3)),
+                    },
+                    body: vec![
+                        Procedural::Set {
+                            name: "num".to_owned(),
+                            value: Expr::BinaryOp {
+                                left: Box::new(num()),
+                                op: BinaryOperator::Plus,
+                                right: Box::new(number(1)),
+                            },
+                        },
+                        Procedural::Exit(Some(Expr::BinaryOp {
+                            left: Box::new(num()),
+                            op: BinaryOperator::Eq,
+                            right: Box::new(number(2)),
+                        })),
+                    ],
+                },
+                Procedural::If {
+                    branches: vec![
+                        (
+                            Expr::BinaryOp {
+                                left: Box::new(num()),
+                                op: BinaryOperator::Eq,
+                                right: Box::new(number(1)),
+                            },
+                            vec![Procedural::Return(Some(number(1)))],
+                        ),
+                        (
+                            Expr::BinaryOp {
+                                left: Box::new(num()),
+                                op: BinaryOperator::Eq,
+                                right: Box::new(number(2)),
+                            },
+                            vec![Procedural::Return(Some(number(2)))],
+                        ),
+                    ],
+                    else_body: Some(vec![Procedural::Return(None)]),
+                },
+                Procedural::Loop(vec![Procedural::Continue(None)]),
+            ])
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"RETURNS TABLE ("id" INT NULL) RETURN SELECT * FROM "Item" WHERE "id" > "num""#,
+            FunctionBody::Table {
+                columns: vec![ColumnDef {
+                    name: "id".to_owned(),
+                    data_type: DataType::Int,
+                    nullable: true,
+                    default: None,
+                    unique: None,
+                }],
+                query: Box::new(Query {
+                    with: None,
+                    body: SetExpr::Select(Box::new(Select {
+                        distinct: None,
+                        projection: vec![SelectItem::Wildcard],
+                        from: TableWithJoins {
+                            relation: TableFactor::Table {
+                                name: "Item".to_owned(),
+                                alias: None,
+                                index: None,
+                            },
+                            joins: Vec::new(),
+                        },
+                        selection: Some(Expr::BinaryOp {
+                            left: Box::new(Expr::Identifier("id".to_owned())),
+                            op: BinaryOperator::Gt,
+                            right: Box::new(num()),
+                        }),
+                        group_by: Vec::new(),
+                        grouping_sets: Vec::new(),
+                        having: None,
+                    })),
+                    order_by: Vec::new(),
+                    limit: None,
+                    offset: None,
+                }),
+            }
+            .to_sql()
+        );
+    }
 }
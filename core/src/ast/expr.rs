@@ -1,7 +1,7 @@
 use {
     super::{
         Aggregate, AstLiteral, BinaryOperator, DataType, DateTimeField, Function, Query, ToSql,
-        ToSqlUnquoted, UnaryOperator,
+        ToSqlUnquoted, UnaryOperator, Window,
     },
     serde::{Deserialize, Serialize},
 };
@@ -52,12 +52,15 @@ pub enum Expr {
     },
     Nested(Box<Expr>),
     Literal(AstLiteral),
+    /// Bind parameter such as `$1` or `?`
+    Placeholder(String),
     TypedString {
         data_type: DataType,
         value: String,
     },
     Function(Box<Function>),
     Aggregate(Box<Aggregate>),
+    Window(Box<Window>),
     Exists {
         subquery: Box<Query>,
         negated: bool,
@@ -178,7 +181,10 @@ impl Expr {
             },
             Expr::Nested(expr) => format!("({})", expr.to_sql_with(quoted)),
             Expr::Literal(s) => s.to_sql(),
-            Expr::TypedString { data_type, value } => format!("{data_type} '{value}'"),
+            Expr::Placeholder(placeholder) => placeholder.to_owned(),
+            Expr::TypedString { data_type, value } => {
+                format!("{data_type} '{}'", value.replace('\'', "''"))
+            }
             Expr::Case {
                 operand,
                 when_then,
@@ -213,6 +219,7 @@ impl Expr {
                 }
             }
             Expr::Aggregate(a) => a.to_sql(),
+            Expr::Window(window) => window.to_sql(),
             Expr::Function(func) => func.to_sql(),
             Expr::InSubquery {
                 expr,
@@ -330,6 +337,16 @@ mod tests {
             }
             .to_sql()
         );
+        assert_eq!(
+            "TEXT 'it''s'",
+            Expr::TypedString {
+                data_type: DataType::Text,
+                value: "it's".to_owned()
+            }
+            .to_sql()
+        );
+
+        assert_eq!("$1", Expr::Placeholder("$1".to_owned()).to_sql());
 
         assert_eq!(
             r#"("id")"#,
@@ -431,7 +448,9 @@ mod tests {
             Expr::InSubquery {
                 expr: Box::new(Expr::Identifier("id".to_owned())),
                 subquery: Box::new(Query {
+                    with: None,
                     body: SetExpr::Select(Box::new(Select {
+                        distinct: None,
                         projection: vec![SelectItem::Wildcard],
                         from: TableWithJoins {
                             relation: TableFactor::Table {
@@ -443,6 +462,7 @@ mod tests {
                         },
                         selection: None,
                         group_by: Vec::new(),
+                        grouping_sets: Vec::new(),
                         having: None,
                     })),
                     order_by: Vec::new(),
@@ -459,7 +479,9 @@ mod tests {
             Expr::InSubquery {
                 expr: Box::new(Expr::Identifier("id".to_owned())),
                 subquery: Box::new(Query {
+                    with: None,
                     body: SetExpr::Select(Box::new(Select {
+                        distinct: None,
                         projection: vec![SelectItem::Wildcard],
                         from: TableWithJoins {
                             relation: TableFactor::Table {
@@ -471,6 +493,7 @@ mod tests {
                         },
                         selection: None,
                         group_by: Vec::new(),
+                        grouping_sets: Vec::new(),
                         having: None,
                     })),
                     order_by: Vec::new(),
@@ -486,7 +509,9 @@ mod tests {
             r#"EXISTS(SELECT * FROM "FOO")"#,
             Expr::Exists {
                 subquery: Box::new(Query {
+                    with: None,
                     body: SetExpr::Select(Box::new(Select {
+                        distinct: None,
                         projection: vec![SelectItem::Wildcard],
                         from: TableWithJoins {
                             relation: TableFactor::Table {
@@ -498,6 +523,7 @@ mod tests {
                         },
                         selection: None,
                         group_by: Vec::new(),
+                        grouping_sets: Vec::new(),
                         having: None,
                     })),
                     order_by: Vec::new(),
@@ -513,7 +539,9 @@ mod tests {
             r#"NOT EXISTS(SELECT * FROM "FOO")"#,
             Expr::Exists {
                 subquery: Box::new(Query {
+                    with: None,
                     body: SetExpr::Select(Box::new(Select {
+                        distinct: None,
                         projection: vec![SelectItem::Wildcard],
                         from: TableWithJoins {
                             relation: TableFactor::Table {
@@ -525,6 +553,7 @@ mod tests {
                         },
                         selection: None,
                         group_by: Vec::new(),
+                        grouping_sets: Vec::new(),
                         having: None,
                     })),
                     order_by: Vec::new(),
@@ -539,7 +568,9 @@ mod tests {
         assert_eq!(
             r#"(SELECT * FROM "FOO")"#,
             Expr::Subquery(Box::new(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
+                    distinct: None,
                     projection: vec![SelectItem::Wildcard],
                     from: TableWithJoins {
                         relation: TableFactor::Table {
@@ -551,6 +582,7 @@ mod tests {
                     },
                     selection: None,
                     group_by: Vec::new(),
+                    grouping_sets: Vec::new(),
                     having: None,
                 })),
                 order_by: Vec::new(),
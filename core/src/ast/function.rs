@@ -1,5 +1,5 @@
 use {
-    super::{ast_literal::TrimWhereField, DataType, DateTimeField, Expr},
+    super::{ast_literal::TrimWhereField, DataType, DateTimeField, Expr, OrderByExpr},
     crate::ast::ToSql,
     serde::{Deserialize, Serialize},
     strum_macros::Display,
@@ -198,6 +198,8 @@ pub enum Function {
     IsEmpty(Expr),
     Length(Expr),
     Values(Expr),
+    Nextval(Expr),
+    Currval(Expr),
 }
 
 impl ToSql for Function {
@@ -455,12 +457,25 @@ impl ToSql for Function {
             Function::Length(e) => format!("LENGTH({})", e.to_sql()),
             Function::Values(e) => format!("VALUES({})", e.to_sql()),
             Function::Entries(e) => format!("ENTRIES({})", e.to_sql()),
+            Function::Nextval(e) => format!("NEXTVAL({})", e.to_sql()),
+            Function::Currval(e) => format!("CURRVAL({})", e.to_sql()),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum Aggregate {
+pub struct Aggregate {
+    pub func: AggregateFunction,
+    /// DISTINCT, each distinct value of the argument is aggregated once
+    pub distinct: bool,
+    /// FILTER (WHERE expr), only the rows passing the filter are aggregated
+    pub filter: Option<Expr>,
+    /// ORDER BY inside the call, the order in which the rows are aggregated
+    pub order_by: Vec<OrderByExpr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AggregateFunction {
     Count(CountArgExpr),
     Sum(Expr),
     Max(Expr),
@@ -468,18 +483,88 @@ pub enum Aggregate {
     Avg(Expr),
     Variance(Expr),
     Stdev(Expr),
+    /// GROUPING(exprs), bit mask of the exprs left out of the grouping set of the row
+    Grouping(Vec<Expr>),
+    /// ARRAY_AGG(expr), list of the values
+    ArrayAgg(Expr),
+    /// STRING_AGG(expr, separator), non-NULL values concatenated with the separators
+    StringAgg {
+        expr: Expr,
+        separator: Expr,
+    },
+    /// JSON_OBJECT_AGG(key, value), map of the key-value pairs
+    JsonObjectAgg {
+        key: Expr,
+        value: Expr,
+    },
+}
+
+impl From<AggregateFunction> for Aggregate {
+    fn from(func: AggregateFunction) -> Self {
+        Aggregate {
+            func,
+            distinct: false,
+            filter: None,
+            order_by: Vec::new(),
+        }
+    }
 }
 
 impl ToSql for Aggregate {
     fn to_sql(&self) -> String {
-        match self {
-            Aggregate::Count(cae) => format!("COUNT({})", cae.to_sql()),
-            Aggregate::Sum(e) => format!("SUM({})", e.to_sql()),
-            Aggregate::Max(e) => format!("MAX({})", e.to_sql()),
-            Aggregate::Min(e) => format!("MIN({})", e.to_sql()),
-            Aggregate::Avg(e) => format!("AVG({})", e.to_sql()),
-            Aggregate::Variance(e) => format!("VARIANCE({})", e.to_sql()),
-            Aggregate::Stdev(e) => format!("STDEV({})", e.to_sql()),
+        let Aggregate {
+            func,
+            distinct,
+            filter,
+            order_by,
+        } = self;
+
+        let (name, args) = match func {
+            AggregateFunction::Count(cae) => ("COUNT", cae.to_sql()),
+            AggregateFunction::Sum(e) => ("SUM", e.to_sql()),
+            AggregateFunction::Max(e) => ("MAX", e.to_sql()),
+            AggregateFunction::Min(e) => ("MIN", e.to_sql()),
+            AggregateFunction::Avg(e) => ("AVG", e.to_sql()),
+            AggregateFunction::Variance(e) => ("VARIANCE", e.to_sql()),
+            AggregateFunction::Stdev(e) => ("STDEV", e.to_sql()),
+            AggregateFunction::Grouping(exprs) => {
+                let exprs = exprs
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                ("GROUPING", exprs)
+            }
+            AggregateFunction::ArrayAgg(e) => ("ARRAY_AGG", e.to_sql()),
+            AggregateFunction::StringAgg { expr, separator } => (
+                "STRING_AGG",
+                format!("{}, {}", expr.to_sql(), separator.to_sql()),
+            ),
+            AggregateFunction::JsonObjectAgg { key, value } => (
+                "JSON_OBJECT_AGG",
+                format!("{}, {}", key.to_sql(), value.to_sql()),
+            ),
+        };
+        let distinct = if *distinct { "DISTINCT " } else { "" };
+        let order_by = match order_by.is_empty() {
+            true => "".to_owned(),
+            false => {
+                let order_by = order_by
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!(" ORDER BY {order_by}")
+            }
+        };
+
+        match filter {
+            Some(filter) => format!(
+                "{name}({distinct}{args}{order_by}) FILTER (WHERE {})",
+                filter.to_sql()
+            ),
+            None => format!("{name}({distinct}{args}{order_by})"),
         }
     }
 }
@@ -503,8 +588,8 @@ impl ToSql for CountArgExpr {
 mod tests {
     use {
         crate::ast::{
-            Aggregate, AstLiteral, CountArgExpr, DataType, DateTimeField, Expr, Function, ToSql,
-            TrimWhereField,
+            Aggregate, AggregateFunction, AstLiteral, BinaryOperator, CountArgExpr, DataType,
+            DateTimeField, Expr, Function, OrderByExpr, ToSql, TrimWhereField,
         },
         bigdecimal::BigDecimal,
         std::str::FromStr,
@@ -1286,49 +1371,149 @@ mod tests {
             ))))
             .to_sql()
         );
+
+        assert_eq!(
+            "NEXTVAL('Seq')",
+            &Expr::Function(Box::new(Function::Nextval(Expr::Literal(
+                AstLiteral::QuotedString("Seq".to_owned())
+            ))))
+            .to_sql()
+        );
+
+        assert_eq!(
+            "CURRVAL('Seq')",
+            &Expr::Function(Box::new(Function::Currval(Expr::Literal(
+                AstLiteral::QuotedString("Seq".to_owned())
+            ))))
+            .to_sql()
+        );
     }
 
     #[test]
     fn to_sql_aggregate() {
         assert_eq!(
             r#"MAX("id")"#,
-            Expr::Aggregate(Box::new(Aggregate::Max(Expr::Identifier("id".to_owned())))).to_sql()
+            Expr::Aggregate(Box::new(
+                AggregateFunction::Max(Expr::Identifier("id".to_owned())).into()
+            ))
+            .to_sql()
         );
 
         assert_eq!(
             "COUNT(*)",
-            Expr::Aggregate(Box::new(Aggregate::Count(CountArgExpr::Wildcard))).to_sql()
+            Expr::Aggregate(Box::new(
+                AggregateFunction::Count(CountArgExpr::Wildcard).into()
+            ))
+            .to_sql()
         );
 
         assert_eq!(
             r#"MIN("id")"#,
-            Expr::Aggregate(Box::new(Aggregate::Min(Expr::Identifier("id".to_owned())))).to_sql()
+            Expr::Aggregate(Box::new(
+                AggregateFunction::Min(Expr::Identifier("id".to_owned())).into()
+            ))
+            .to_sql()
         );
 
         assert_eq!(
             r#"SUM("price")"#,
-            &Expr::Aggregate(Box::new(Aggregate::Sum(Expr::Identifier(
-                "price".to_owned()
-            ))))
+            &Expr::Aggregate(Box::new(
+                AggregateFunction::Sum(Expr::Identifier("price".to_owned())).into()
+            ))
             .to_sql()
         );
 
         assert_eq!(
             r#"AVG("pay")"#,
-            &Expr::Aggregate(Box::new(Aggregate::Avg(Expr::Identifier("pay".to_owned())))).to_sql()
+            &Expr::Aggregate(Box::new(
+                AggregateFunction::Avg(Expr::Identifier("pay".to_owned())).into()
+            ))
+            .to_sql()
         );
         assert_eq!(
             r#"VARIANCE("pay")"#,
-            &Expr::Aggregate(Box::new(Aggregate::Variance(Expr::Identifier(
-                "pay".to_owned()
-            ))))
+            &Expr::Aggregate(Box::new(
+                AggregateFunction::Variance(Expr::Identifier("pay".to_owned())).into()
+            ))
             .to_sql()
         );
         assert_eq!(
             r#"STDEV("total")"#,
-            &Expr::Aggregate(Box::new(Aggregate::Stdev(Expr::Identifier(
-                "total".to_owned()
-            ))))
+            &Expr::Aggregate(Box::new(
+                AggregateFunction::Stdev(Expr::Identifier("total".to_owned())).into()
+            ))
+            .to_sql()
+        );
+        assert_eq!(
+            r#"GROUPING("name", "city")"#,
+            &Expr::Aggregate(Box::new(
+                AggregateFunction::Grouping(vec![
+                    Expr::Identifier("name".to_owned()),
+                    Expr::Identifier("city".to_owned())
+                ])
+                .into()
+            ))
+            .to_sql()
+        );
+        assert_eq!(
+            r#"COUNT(DISTINCT "id")"#,
+            &Expr::Aggregate(Box::new(Aggregate {
+                func: AggregateFunction::Count(CountArgExpr::Expr(Expr::Identifier(
+                    "id".to_owned()
+                ))),
+                distinct: true,
+                filter: None,
+                order_by: Vec::new(),
+            }))
+            .to_sql()
+        );
+        assert_eq!(
+            r#"SUM("price") FILTER (WHERE "paid" = TRUE)"#,
+            &Expr::Aggregate(Box::new(Aggregate {
+                func: AggregateFunction::Sum(Expr::Identifier("price".to_owned())),
+                distinct: false,
+                filter: Some(Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier("paid".to_owned())),
+                    op: BinaryOperator::Eq,
+                    right: Box::new(Expr::Literal(AstLiteral::Boolean(true))),
+                }),
+                order_by: Vec::new(),
+            }))
+            .to_sql()
+        );
+        assert_eq!(
+            r#"ARRAY_AGG("name" ORDER BY "id" DESC)"#,
+            &Expr::Aggregate(Box::new(Aggregate {
+                func: AggregateFunction::ArrayAgg(Expr::Identifier("name".to_owned())),
+                distinct: false,
+                filter: None,
+                order_by: vec![OrderByExpr {
+                    expr: Expr::Identifier("id".to_owned()),
+                    asc: Some(false),
+                }],
+            }))
+            .to_sql()
+        );
+        assert_eq!(
+            r#"STRING_AGG("name", ', ')"#,
+            &Expr::Aggregate(Box::new(
+                AggregateFunction::StringAgg {
+                    expr: Expr::Identifier("name".to_owned()),
+                    separator: Expr::Literal(AstLiteral::QuotedString(", ".to_owned())),
+                }
+                .into()
+            ))
+            .to_sql()
+        );
+        assert_eq!(
+            r#"JSON_OBJECT_AGG("name", "score")"#,
+            &Expr::Aggregate(Box::new(
+                AggregateFunction::JsonObjectAgg {
+                    key: Expr::Identifier("name".to_owned()),
+                    value: Expr::Identifier("score".to_owned()),
+                }
+                .into()
+            ))
             .to_sql()
         );
     }
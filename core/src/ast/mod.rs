@@ -5,15 +5,17 @@ mod expr;
 mod function;
 mod operator;
 mod query;
+mod window;
 
 pub use {
     ast_literal::{AstLiteral, DateTimeField, TrimWhereField},
     data_type::DataType,
     ddl::*,
     expr::Expr,
-    function::{Aggregate, CountArgExpr, Function},
+    function::{Aggregate, AggregateFunction, CountArgExpr, Function},
     operator::*,
     query::*,
+    window::{Window, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunction, WindowSpec},
 };
 
 use serde::{Deserialize, Serialize};
@@ -41,6 +43,10 @@ pub enum Statement {
         columns: Vec<String>,
         /// A SQL query that specifies what to insert
         source: Query,
+        /// ON CONFLICT
+        on_conflict: Option<OnConflict>,
+        /// RETURNING, empty when not specified
+        returning: Vec<SelectItem>,
     },
     /// UPDATE
     Update {
@@ -48,15 +54,23 @@ pub enum Statement {
         table_name: String,
         /// Column assignments
         assignments: Vec<Assignment>,
+        /// FROM, the target table as `relation` cross joined with the FROM tables
+        from: Option<TableWithJoins>,
         /// WHERE
         selection: Option<Expr>,
+        /// RETURNING, empty when not specified
+        returning: Vec<SelectItem>,
     },
     /// DELETE
     Delete {
         /// FROM
         table_name: String,
+        /// USING, the target table as `relation` cross joined with the USING tables
+        using: Option<TableWithJoins>,
         /// WHERE
         selection: Option<Expr>,
+        /// RETURNING, empty when not specified
+        returning: Vec<SelectItem>,
     },
     /// CREATE TABLE
     CreateTable {
@@ -67,6 +81,12 @@ pub enum Statement {
         columns: Option<Vec<ColumnDef>>,
         source: Option<Box<Query>>,
         engine: Option<String>,
+        /// FOREIGN KEY constraints, including column-level REFERENCES
+        foreign_keys: Vec<ForeignKey>,
+        /// CHECK constraints, including column-level CHECK
+        checks: Vec<Check>,
+        /// PRIMARY KEY and UNIQUE constraints on several columns
+        unique_keys: Vec<UniqueKey>,
     },
     /// CREATE FUNCTION
     CreateFunction {
@@ -74,7 +94,19 @@ pub enum Statement {
         name: String,
         /// Optional schema
         args: Vec<OperateFunctionArg>,
-        return_: Expr,
+        body: FunctionBody,
+    },
+    /// CREATE PROCEDURE
+    CreateProcedure {
+        or_replace: bool,
+        name: String,
+        args: Vec<OperateFunctionArg>,
+        body: Vec<Procedural>,
+    },
+    /// CALL
+    Call {
+        name: String,
+        args: Vec<Expr>,
     },
     /// ALTER TABLE
     AlterTable {
@@ -96,11 +128,58 @@ pub enum Statement {
         /// One or more objects to drop. (ANSI SQL requires exactly one.)
         names: Vec<String>,
     },
+    /// DROP PROCEDURE
+    DropProcedure {
+        if_exists: bool,
+        names: Vec<String>,
+    },
+    /// CREATE VIEW
+    CreateView {
+        or_replace: bool,
+        name: String,
+        /// Column aliases, empty when not specified
+        columns: Vec<String>,
+        query: Box<Query>,
+    },
+    /// DROP VIEW
+    DropView {
+        if_exists: bool,
+        names: Vec<String>,
+    },
+    /// CREATE TRIGGER
+    CreateTrigger {
+        or_replace: bool,
+        name: String,
+        table_name: String,
+        timing: TriggerTiming,
+        /// Events joined by `OR`
+        events: Vec<TriggerEvent>,
+        action: TriggerAction,
+    },
+    /// DROP TRIGGER
+    DropTrigger {
+        if_exists: bool,
+        names: Vec<String>,
+    },
+    /// CREATE SEQUENCE
+    CreateSequence {
+        if_not_exists: bool,
+        name: String,
+        options: Vec<SequenceOption>,
+    },
+    /// DROP SEQUENCE
+    DropSequence {
+        if_exists: bool,
+        names: Vec<String>,
+    },
     /// CREATE INDEX
     CreateIndex {
         name: String,
         table_name: String,
-        column: OrderByExpr,
+        columns: Vec<OrderByExpr>,
+        unique: bool,
+        /// Only rows matching the predicate are indexed
+        predicate: Option<Expr>,
     },
     /// DROP INDEX
     DropIndex {
@@ -116,6 +195,11 @@ pub enum Statement {
     /// SHOW VARIABLE
     ShowVariable(Variable),
     ShowIndexes(String),
+    /// EXPLAIN
+    Explain {
+        statement: Box<Statement>,
+        analyze: bool,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -124,10 +208,29 @@ pub struct Assignment {
     pub value: Expr,
 }
 
+/// `ON CONFLICT [(column, ..)] { DO NOTHING | DO UPDATE SET .. [WHERE ..] }`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OnConflict {
+    /// Primary key or unique column, empty when any conflict is handled
+    pub target: Vec<String>,
+    pub action: OnConflictAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OnConflictAction {
+    DoNothing,
+    /// The row proposed for insertion is available as `EXCLUDED`
+    DoUpdate {
+        assignments: Vec<Assignment>,
+        selection: Option<Expr>,
+    },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Variable {
     Tables,
     Functions,
+    Views,
     Version,
 }
 
@@ -141,47 +244,75 @@ impl ToSql for Statement {
                 table_name,
                 columns,
                 source,
+                on_conflict,
+                returning,
             } => {
                 let columns = match columns.is_empty() {
                     true => "".to_owned(),
                     false => format!("({}) ", columns.join(", ")),
                 };
+                let on_conflict = on_conflict
+                    .as_ref()
+                    .map(|on_conflict| format!(" {}", on_conflict.to_sql()))
+                    .unwrap_or_default();
+                let returning = returning_to_sql(returning);
 
-                format!("INSERT INTO {table_name} {columns}{};", source.to_sql())
+                format!(
+                    "INSERT INTO {table_name} {columns}{}{on_conflict}{returning};",
+                    source.to_sql()
+                )
             }
             Statement::Update {
                 table_name,
                 assignments,
+                from,
                 selection,
+                returning,
             } => {
                 let assignments = assignments
                     .iter()
                     .map(ToSql::to_sql)
                     .collect::<Vec<_>>()
                     .join(", ");
+                let from = joined_to_sql("FROM", from.as_ref());
+                let returning = returning_to_sql(returning);
                 match selection {
                     Some(expr) => {
                         format!(
-                            r#"UPDATE "{table_name}" SET {assignments} WHERE {};"#,
+                            r#"UPDATE "{table_name}" SET {assignments}{from} WHERE {}{returning};"#,
                             expr.to_sql()
                         )
                     }
-                    None => format!(r#"UPDATE "{table_name}" SET {assignments};"#),
+                    None => {
+                        format!(r#"UPDATE "{table_name}" SET {assignments}{from}{returning};"#)
+                    }
                 }
             }
             Statement::Delete {
                 table_name,
+                using,
                 selection,
-            } => match selection {
-                Some(expr) => format!(r#"DELETE FROM "{table_name}" WHERE {};"#, expr.to_sql()),
-                None => format!(r#"DELETE FROM "{table_name}";"#),
-            },
+                returning,
+            } => {
+                let using = joined_to_sql("USING", using.as_ref());
+                let returning = returning_to_sql(returning);
+                match selection {
+                    Some(expr) => format!(
+                        r#"DELETE FROM "{table_name}"{using} WHERE {}{returning};"#,
+                        expr.to_sql()
+                    ),
+                    None => format!(r#"DELETE FROM "{table_name}"{using}{returning};"#),
+                }
+            }
             Statement::CreateTable {
                 if_not_exists,
                 name,
                 columns,
                 source,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
             } => {
                 let if_not_exists = if_not_exists.then_some("IF NOT EXISTS");
                 let body = match source {
@@ -189,15 +320,14 @@ impl ToSql for Statement {
                     None if columns.is_none() => None,
                     None => {
                         let columns = columns
-                            .as_ref()
-                            .map(|columns| {
-                                columns
-                                    .iter()
-                                    .map(ToSql::to_sql)
-                                    .collect::<Vec<_>>()
-                                    .join(", ")
-                            })
-                            .unwrap_or_else(|| "".to_owned());
+                            .iter()
+                            .flatten()
+                            .map(ToSql::to_sql)
+                            .chain(unique_keys.iter().map(ToSql::to_sql))
+                            .chain(foreign_keys.iter().map(ToSql::to_sql))
+                            .chain(checks.iter().map(ToSql::to_sql))
+                            .collect::<Vec<_>>()
+                            .join(", ");
 
                         Some(format!("({columns})"))
                     }
@@ -221,7 +351,7 @@ impl ToSql for Statement {
                 or_replace,
                 name,
                 args,
-                return_,
+                body,
                 ..
             } => {
                 let or_replace = or_replace.then_some(" OR REPLACE").unwrap_or("");
@@ -230,8 +360,31 @@ impl ToSql for Statement {
                     .map(ToSql::to_sql)
                     .collect::<Vec<_>>()
                     .join(", ");
-                let return_ = format!(" RETURN {}", return_.to_sql());
-                format!("CREATE{or_replace} FUNCTION {name}({args}){return_};")
+                let body = body.to_sql();
+                format!("CREATE{or_replace} FUNCTION {name}({args}) {body};")
+            }
+            Statement::CreateProcedure {
+                or_replace,
+                name,
+                args,
+                body,
+            } => {
+                let or_replace = or_replace.then_some(" OR REPLACE").unwrap_or("");
+                let args = args
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let body = FunctionBody::Block(body.clone()).to_sql();
+                format!("CREATE{or_replace} PROCEDURE {name}({args}) {body};")
+            }
+            Statement::Call { name, args } => {
+                let args = args
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("CALL {name}({args});")
             }
             Statement::AlterTable { name, operation } => {
                 format!(r#"ALTER TABLE "{name}" {};"#, operation.to_sql())
@@ -254,14 +407,131 @@ impl ToSql for Statement {
                     false => format!("DROP FUNCTION {};", names),
                 }
             }
+            Statement::DropProcedure { if_exists, names } => {
+                let names = names.join(", ");
+                match if_exists {
+                    true => format!("DROP PROCEDURE IF EXISTS {};", names),
+                    false => format!("DROP PROCEDURE {};", names),
+                }
+            }
+            Statement::CreateView {
+                or_replace,
+                name,
+                columns,
+                query,
+            } => {
+                let or_replace = or_replace.then_some(" OR REPLACE").unwrap_or("");
+                let columns = match columns.is_empty() {
+                    true => "".to_owned(),
+                    false => {
+                        let columns = columns
+                            .iter()
+                            .map(|column| format!(r#""{column}""#))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+
+                        format!(" ({columns})")
+                    }
+                };
+
+                format!(
+                    r#"CREATE{or_replace} VIEW "{name}"{columns} AS {};"#,
+                    query.to_sql()
+                )
+            }
+            Statement::DropView { if_exists, names } => {
+                let names = names
+                    .iter()
+                    .map(|name| format!(r#""{name}""#))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                match if_exists {
+                    true => format!("DROP VIEW IF EXISTS {names};"),
+                    false => format!("DROP VIEW {names};"),
+                }
+            }
+            Statement::CreateTrigger {
+                or_replace,
+                name,
+                table_name,
+                timing,
+                events,
+                action,
+            } => {
+                let or_replace = or_replace.then_some(" OR REPLACE").unwrap_or("");
+                let events = events
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(" OR ");
+
+                format!(
+                    r#"CREATE{or_replace} TRIGGER "{name}" {} {events} ON "{table_name}" FOR EACH ROW {};"#,
+                    timing.to_sql(),
+                    action.to_sql()
+                )
+            }
+            Statement::DropTrigger { if_exists, names } => {
+                let names = names
+                    .iter()
+                    .map(|name| format!(r#""{name}""#))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                match if_exists {
+                    true => format!("DROP TRIGGER IF EXISTS {names};"),
+                    false => format!("DROP TRIGGER {names};"),
+                }
+            }
+            Statement::CreateSequence {
+                if_not_exists,
+                name,
+                options,
+            } => {
+                let if_not_exists = if_not_exists.then_some("IF NOT EXISTS");
+                let name = format!(r#""{name}""#);
+                let options = options.iter().map(ToSql::to_sql);
+
+                let sql = ["CREATE SEQUENCE".to_owned()]
+                    .into_iter()
+                    .chain(if_not_exists.map(str::to_owned))
+                    .chain(Some(name))
+                    .chain(options)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                format!("{sql};")
+            }
+            Statement::DropSequence { if_exists, names } => {
+                let names = names
+                    .iter()
+                    .map(|name| format!(r#""{name}""#))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                match if_exists {
+                    true => format!("DROP SEQUENCE IF EXISTS {names};"),
+                    false => format!("DROP SEQUENCE {names};"),
+                }
+            }
             Statement::CreateIndex {
                 name,
                 table_name,
-                column,
+                columns,
+                unique,
+                predicate,
             } => {
+                let unique = if *unique { "UNIQUE " } else { "" };
+                let columns = columns
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let predicate = predicate
+                    .as_ref()
+                    .map(|predicate| format!(" WHERE {}", predicate.to_sql()))
+                    .unwrap_or_default();
+
                 format!(
-                    r#"CREATE INDEX "{name}" ON "{table_name}" ({});"#,
-                    column.to_sql()
+                    r#"CREATE {unique}INDEX "{name}" ON "{table_name}" ({columns}){predicate};"#
                 )
             }
             Statement::DropIndex { name, table_name } => {
@@ -273,8 +543,13 @@ impl ToSql for Statement {
             Statement::ShowVariable(variable) => match variable {
                 Variable::Tables => "SHOW TABLES;".to_owned(),
                 Variable::Functions => "SHOW FUNCTIONS;".to_owned(),
+                Variable::Views => "SHOW VIEWS;".to_owned(),
                 Variable::Version => "SHOW VERSIONS;".to_owned(),
             },
+            Statement::Explain { statement, analyze } => match analyze {
+                true => format!("EXPLAIN ANALYZE {}", statement.to_sql()),
+                false => format!("EXPLAIN {}", statement.to_sql()),
+            },
             Statement::ShowIndexes(object_name) => {
                 format!(r#"SHOW INDEXES FROM "{object_name}";"#)
             }
@@ -289,18 +564,92 @@ impl ToSql for Assignment {
     }
 }
 
+impl ToSql for OnConflict {
+    fn to_sql(&self) -> String {
+        let OnConflict { target, action } = self;
+        let target = match target.is_empty() {
+            true => "".to_owned(),
+            false => format!(" ({})", target.join(", ")),
+        };
+
+        match action {
+            OnConflictAction::DoNothing => format!("ON CONFLICT{target} DO NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                let assignments = assignments
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                match selection {
+                    Some(expr) => format!(
+                        "ON CONFLICT{target} DO UPDATE SET {assignments} WHERE {}",
+                        expr.to_sql()
+                    ),
+                    None => format!("ON CONFLICT{target} DO UPDATE SET {assignments}"),
+                }
+            }
+        }
+    }
+}
+
+/// Renders the tables joined after the target table, the leading cross join is the first
+/// FROM or USING table.
+fn joined_to_sql(keyword: &str, table_with_joins: Option<&TableWithJoins>) -> String {
+    match table_with_joins.map(|TableWithJoins { joins, .. }| joins.split_first()) {
+        Some(Some((Join { relation, .. }, joins))) => {
+            let table_with_joins = TableWithJoins {
+                relation: relation.clone(),
+                joins: joins.to_vec(),
+            };
+
+            format!(" {keyword} {}", table_with_joins.to_sql())
+        }
+        _ => "".to_owned(),
+    }
+}
+
+fn returning_to_sql(returning: &[SelectItem]) -> String {
+    match returning.is_empty() {
+        true => "".to_owned(),
+        false => {
+            let items = returning
+                .iter()
+                .map(ToSql::to_sql)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!(" RETURNING {items}")
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
         crate::ast::{
-            AlterTableOperation, Assignment, AstLiteral, BinaryOperator, ColumnDef, DataType, Expr,
-            OperateFunctionArg, OrderByExpr, Query, Select, SelectItem, SetExpr, Statement,
-            TableFactor, TableWithJoins, ToSql, Values, Variable,
+            AlterColumnOperation, AlterTableOperation, Assignment, AstLiteral, BinaryOperator,
+            Check, ColumnDef, DataType, Expr, ForeignKey, FunctionBody, Join, JoinConstraint,
+            JoinExecutor, JoinOperator, OnConflict, OnConflictAction, OperateFunctionArg,
+            OrderByExpr, Procedural, Query, ReferentialAction, Select, SelectItem, SequenceOption,
+            SetExpr, Statement, TableFactor, TableWithJoins, ToSql, TriggerAction, TriggerEvent,
+            TriggerTiming, Values, Variable,
         },
         bigdecimal::BigDecimal,
         std::str::FromStr,
     };
 
+    fn table(name: &str) -> TableFactor {
+        TableFactor::Table {
+            name: name.to_owned(),
+            alias: None,
+            index: None,
+        }
+    }
+
     #[test]
     fn to_sql_show_columns() {
         assert_eq!(
@@ -320,6 +669,7 @@ mod tests {
                 table_name: "Test".into(),
                 columns: vec!["id".to_owned(), "num".to_owned(), "name".to_owned()],
                 source: Query {
+                    with: None,
                     body: SetExpr::Values(Values(vec![vec![
                         Expr::Literal(AstLiteral::Number(BigDecimal::from_str("1").unwrap())),
                         Expr::Literal(AstLiteral::Number(BigDecimal::from_str("2").unwrap())),
@@ -328,7 +678,71 @@ mod tests {
                     order_by: vec![],
                     limit: None,
                     offset: None
-                }
+                },
+                on_conflict: None,
+                returning: vec![],
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            "INSERT INTO Test (id) VALUES (1) ON CONFLICT DO NOTHING;",
+            Statement::Insert {
+                table_name: "Test".into(),
+                columns: vec!["id".to_owned()],
+                source: Query {
+                    with: None,
+                    body: SetExpr::Values(Values(vec![vec![Expr::Literal(AstLiteral::Number(
+                        BigDecimal::from_str("1").unwrap()
+                    ))]])),
+                    order_by: vec![],
+                    limit: None,
+                    offset: None
+                },
+                on_conflict: Some(OnConflict {
+                    target: vec![],
+                    action: OnConflictAction::DoNothing,
+                }),
+                returning: vec![],
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"INSERT INTO Test (id, num) VALUES (1, 2) ON CONFLICT (id) DO UPDATE SET "num" = "EXCLUDED"."num" WHERE "num" < 10;"#,
+            Statement::Insert {
+                table_name: "Test".into(),
+                columns: vec!["id".to_owned(), "num".to_owned()],
+                source: Query {
+                    with: None,
+                    body: SetExpr::Values(Values(vec![vec![
+                        Expr::Literal(AstLiteral::Number(BigDecimal::from_str("1").unwrap())),
+                        Expr::Literal(AstLiteral::Number(BigDecimal::from_str("2").unwrap())),
+                    ]])),
+                    order_by: vec![],
+                    limit: None,
+                    offset: None
+                },
+                on_conflict: Some(OnConflict {
+                    target: vec!["id".to_owned()],
+                    action: OnConflictAction::DoUpdate {
+                        assignments: vec![Assignment {
+                            id: "num".to_owned(),
+                            value: Expr::CompoundIdentifier {
+                                alias: "EXCLUDED".to_owned(),
+                                ident: "num".to_owned(),
+                            },
+                        }],
+                        selection: Some(Expr::BinaryOp {
+                            left: Box::new(Expr::Identifier("num".to_owned())),
+                            op: BinaryOperator::Lt,
+                            right: Box::new(Expr::Literal(AstLiteral::Number(
+                                BigDecimal::from_str("10").unwrap()
+                            ))),
+                        }),
+                    },
+                }),
+                returning: vec![],
             }
             .to_sql()
         );
@@ -352,7 +766,9 @@ mod tests {
                         value: Expr::Literal(AstLiteral::QuotedString("blue".to_owned()))
                     }
                 ],
-                selection: None
+                from: None,
+                selection: None,
+                returning: vec![],
             }
             .to_sql()
         );
@@ -365,14 +781,94 @@ mod tests {
                     id: "name".to_owned(),
                     value: Expr::Literal(AstLiteral::QuotedString("first".to_owned()))
                 }],
+                from: None,
                 selection: Some(Expr::BinaryOp {
                     left: Box::new(Expr::Identifier("a".to_owned())),
                     op: BinaryOperator::Gt,
                     right: Box::new(Expr::Identifier("b".to_owned()))
-                })
+                }),
+                returning: vec![],
             }
             .to_sql()
-        )
+        );
+
+        assert_eq!(
+            r#"UPDATE "Foo" SET "name" = 'first' RETURNING "id", "name" AS "new_name";"#,
+            Statement::Update {
+                table_name: "Foo".into(),
+                assignments: vec![Assignment {
+                    id: "name".to_owned(),
+                    value: Expr::Literal(AstLiteral::QuotedString("first".to_owned()))
+                }],
+                from: None,
+                selection: None,
+                returning: vec![
+                    SelectItem::Expr {
+                        expr: Expr::Identifier("id".to_owned()),
+                        label: "".to_owned(),
+                    },
+                    SelectItem::Expr {
+                        expr: Expr::Identifier("name".to_owned()),
+                        label: "new_name".to_owned(),
+                    },
+                ],
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"UPDATE "Foo" SET "price" = "Bar"."price" FROM "Bar" INNER JOIN "Baz" ON "Bar"."id" = "Baz"."id" WHERE "Foo"."id" = "Bar"."id";"#,
+            Statement::Update {
+                table_name: "Foo".into(),
+                assignments: vec![Assignment {
+                    id: "price".to_owned(),
+                    value: Expr::CompoundIdentifier {
+                        alias: "Bar".to_owned(),
+                        ident: "price".to_owned(),
+                    },
+                }],
+                from: Some(TableWithJoins {
+                    relation: table("Foo"),
+                    joins: vec![
+                        Join {
+                            relation: table("Bar"),
+                            join_operator: JoinOperator::Cross,
+                            join_executor: JoinExecutor::NestedLoop,
+                        },
+                        Join {
+                            relation: table("Baz"),
+                            join_operator: JoinOperator::Inner(JoinConstraint::On(
+                                Expr::BinaryOp {
+                                    left: Box::new(Expr::CompoundIdentifier {
+                                        alias: "Bar".to_owned(),
+                                        ident: "id".to_owned(),
+                                    }),
+                                    op: BinaryOperator::Eq,
+                                    right: Box::new(Expr::CompoundIdentifier {
+                                        alias: "Baz".to_owned(),
+                                        ident: "id".to_owned(),
+                                    }),
+                                }
+                            )),
+                            join_executor: JoinExecutor::NestedLoop,
+                        },
+                    ],
+                }),
+                selection: Some(Expr::BinaryOp {
+                    left: Box::new(Expr::CompoundIdentifier {
+                        alias: "Foo".to_owned(),
+                        ident: "id".to_owned(),
+                    }),
+                    op: BinaryOperator::Eq,
+                    right: Box::new(Expr::CompoundIdentifier {
+                        alias: "Bar".to_owned(),
+                        ident: "id".to_owned(),
+                    }),
+                }),
+                returning: vec![],
+            }
+            .to_sql()
+        );
     }
 
     #[test]
@@ -381,7 +877,9 @@ mod tests {
             r#"DELETE FROM "Foo";"#,
             Statement::Delete {
                 table_name: "Foo".into(),
-                selection: None
+                using: None,
+                selection: None,
+                returning: vec![],
             }
             .to_sql()
         );
@@ -390,11 +888,52 @@ mod tests {
             r#"DELETE FROM "Foo" WHERE "item" = 'glue';"#,
             Statement::Delete {
                 table_name: "Foo".into(),
+                using: None,
                 selection: Some(Expr::BinaryOp {
                     left: Box::new(Expr::Identifier("item".to_owned())),
                     op: BinaryOperator::Eq,
                     right: Box::new(Expr::Literal(AstLiteral::QuotedString("glue".to_owned())))
-                })
+                }),
+                returning: vec![],
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"DELETE FROM "Foo" RETURNING *;"#,
+            Statement::Delete {
+                table_name: "Foo".into(),
+                using: None,
+                selection: None,
+                returning: vec![SelectItem::Wildcard],
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"DELETE FROM "Foo" USING "Bar" WHERE "Foo"."id" = "Bar"."id";"#,
+            Statement::Delete {
+                table_name: "Foo".into(),
+                using: Some(TableWithJoins {
+                    relation: table("Foo"),
+                    joins: vec![Join {
+                        relation: table("Bar"),
+                        join_operator: JoinOperator::Cross,
+                        join_executor: JoinExecutor::NestedLoop,
+                    }],
+                }),
+                selection: Some(Expr::BinaryOp {
+                    left: Box::new(Expr::CompoundIdentifier {
+                        alias: "Foo".to_owned(),
+                        ident: "id".to_owned(),
+                    }),
+                    op: BinaryOperator::Eq,
+                    right: Box::new(Expr::CompoundIdentifier {
+                        alias: "Bar".to_owned(),
+                        ident: "id".to_owned(),
+                    }),
+                }),
+                returning: vec![],
             }
             .to_sql()
         );
@@ -410,6 +949,9 @@ mod tests {
                 columns: None,
                 source: None,
                 engine: None,
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                unique_keys: Vec::new(),
             }
             .to_sql()
         );
@@ -422,6 +964,9 @@ mod tests {
                 columns: None,
                 source: None,
                 engine: None,
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                unique_keys: Vec::new(),
             }
             .to_sql()
         );
@@ -440,6 +985,9 @@ mod tests {
                 },]),
                 source: None,
                 engine: None,
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                unique_keys: Vec::new(),
             }
             .to_sql()
         );
@@ -474,6 +1022,53 @@ mod tests {
                 ]),
                 source: None,
                 engine: None,
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                unique_keys: Vec::new(),
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"CREATE TABLE "Bar" ("id" INT NOT NULL, "foo_id" INT NULL, CONSTRAINT "FK_foo_id-Foo_id" FOREIGN KEY ("foo_id") REFERENCES "Foo" ("id") ON DELETE SET NULL ON UPDATE RESTRICT, CONSTRAINT "CK_id" CHECK ("id" > 0));"#,
+            Statement::CreateTable {
+                if_not_exists: false,
+                name: "Bar".into(),
+                columns: Some(vec![
+                    ColumnDef {
+                        name: "id".to_owned(),
+                        data_type: DataType::Int,
+                        nullable: false,
+                        default: None,
+                        unique: None,
+                    },
+                    ColumnDef {
+                        name: "foo_id".to_owned(),
+                        data_type: DataType::Int,
+                        nullable: true,
+                        default: None,
+                        unique: None,
+                    },
+                ]),
+                source: None,
+                engine: None,
+                foreign_keys: vec![ForeignKey {
+                    name: "FK_foo_id-Foo_id".to_owned(),
+                    referencing_column_name: "foo_id".to_owned(),
+                    referenced_table_name: "Foo".to_owned(),
+                    referenced_column_name: "id".to_owned(),
+                    on_delete: ReferentialAction::SetNull,
+                    on_update: ReferentialAction::Restrict,
+                }],
+                checks: vec![Check {
+                    name: "CK_id".to_owned(),
+                    expr: Expr::BinaryOp {
+                        left: Box::new(Expr::Identifier("id".to_owned())),
+                        op: BinaryOperator::Gt,
+                        right: Box::new(Expr::Literal(AstLiteral::Number(BigDecimal::from(0)))),
+                    },
+                }],
+                unique_keys: Vec::new(),
             }
             .to_sql()
         );
@@ -488,7 +1083,9 @@ mod tests {
                 name: "Foo".into(),
                 columns: None,
                 source: Some(Box::new(Query {
+                    with: None,
                     body: SetExpr::Select(Box::new(Select {
+                        distinct: None,
                         projection: vec![
                             SelectItem::Expr {
                                 expr: Expr::Identifier("id".to_owned()),
@@ -509,6 +1106,7 @@ mod tests {
                         },
                         selection: None,
                         group_by: vec![],
+                        grouping_sets: Vec::new(),
                         having: None
                     })),
                     order_by: vec![],
@@ -516,6 +1114,9 @@ mod tests {
                     offset: None
                 })),
                 engine: None,
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                unique_keys: Vec::new(),
             }
             .to_sql()
         );
@@ -527,6 +1128,7 @@ mod tests {
                 name: "Foo".into(),
                 columns: None,
                 source: Some(Box::new(Query {
+                    with: None,
                     body: SetExpr::Values(Values(vec![vec![Expr::Literal(AstLiteral::Boolean(
                         true
                     ))]])),
@@ -535,6 +1137,9 @@ mod tests {
                     offset: None
                 })),
                 engine: None,
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                unique_keys: Vec::new(),
             }
             .to_sql()
         );
@@ -550,6 +1155,9 @@ mod tests {
                 columns: None,
                 source: None,
                 engine: Some("MEMORY".to_owned()),
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                unique_keys: Vec::new(),
             }
             .to_sql()
         );
@@ -568,6 +1176,9 @@ mod tests {
                 },]),
                 source: None,
                 engine: Some("SLED".to_owned()),
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                unique_keys: Vec::new(),
             }
             .to_sql()
         );
@@ -587,7 +1198,7 @@ mod tests {
                         BigDecimal::from_str("0").unwrap()
                     ))),
                 }],
-                return_: Expr::Identifier("num".to_owned())
+                body: FunctionBody::Return(Expr::Identifier("num".to_owned()))
             }
             .to_sql()
         );
@@ -597,7 +1208,67 @@ mod tests {
                 or_replace: true,
                 name: "add".into(),
                 args: vec![],
-                return_: Expr::Literal(AstLiteral::Number(BigDecimal::from_str("1").unwrap()))
+                body: FunctionBody::Return(Expr::Literal(AstLiteral::Number(
+                    BigDecimal::from_str("1").unwrap()
+                )))
+            }
+            .to_sql()
+        );
+        assert_eq!(
+            r#"CREATE FUNCTION add() BEGIN DECLARE "num" INT; RETURN "num"; END;"#,
+            Statement::CreateFunction {
+                or_replace: false,
+                name: "add".into(),
+                args: vec![],
+                body: FunctionBody::Block(vec![
+                    Procedural::Declare {
+                        name: "num".to_owned(),
+                        data_type: DataType::Int,
+                        default: None,
+                    },
+                    Procedural::Return(Some(Expr::Identifier("num".to_owned()))),
+                ])
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_create_procedure() {
+        assert_eq!(
+            r#"CREATE OR REPLACE PROCEDURE clear("id" INT) BEGIN DELETE FROM "Foo" WHERE "id" = "id"; END;"#,
+            Statement::CreateProcedure {
+                or_replace: true,
+                name: "clear".into(),
+                args: vec![OperateFunctionArg {
+                    name: "id".into(),
+                    data_type: DataType::Int,
+                    default: None,
+                }],
+                body: vec![Procedural::Statement(Box::new(Statement::Delete {
+                    table_name: "Foo".into(),
+                    using: None,
+                    selection: Some(Expr::BinaryOp {
+                        left: Box::new(Expr::Identifier("id".to_owned())),
+                        op: BinaryOperator::Eq,
+                        right: Box::new(Expr::Identifier("id".to_owned())),
+                    }),
+                    returning: vec![],
+                }))],
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_call() {
+        assert_eq!(
+            "CALL clear(1);",
+            Statement::Call {
+                name: "clear".into(),
+                args: vec![Expr::Literal(AstLiteral::Number(
+                    BigDecimal::from_str("1").unwrap()
+                ))],
             }
             .to_sql()
         );
@@ -670,6 +1341,44 @@ mod tests {
             }
             .to_sql()
         );
+
+        assert_eq!(
+            r#"ALTER TABLE "Foo" ALTER COLUMN "id" SET DATA TYPE TEXT;"#,
+            Statement::AlterTable {
+                name: "Foo".to_owned(),
+                operation: AlterTableOperation::AlterColumn {
+                    column_name: "id".to_owned(),
+                    operation: AlterColumnOperation::SetDataType(DataType::Text),
+                }
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"ALTER TABLE "Foo" ALTER COLUMN "num" SET DEFAULT 1;"#,
+            Statement::AlterTable {
+                name: "Foo".to_owned(),
+                operation: AlterTableOperation::AlterColumn {
+                    column_name: "num".to_owned(),
+                    operation: AlterColumnOperation::SetDefault(Expr::Literal(AstLiteral::Number(
+                        BigDecimal::from_str("1").unwrap()
+                    ))),
+                }
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"ALTER TABLE "Foo" ALTER COLUMN "num" DROP NOT NULL;"#,
+            Statement::AlterTable {
+                name: "Foo".to_owned(),
+                operation: AlterTableOperation::AlterColumn {
+                    column_name: "num".to_owned(),
+                    operation: AlterColumnOperation::DropNotNull,
+                }
+            }
+            .to_sql()
+        );
     }
 
     #[test]
@@ -732,6 +1441,224 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_sql_drop_procedure() {
+        assert_eq!(
+            "DROP PROCEDURE IF EXISTS Foo, Bar;",
+            Statement::DropProcedure {
+                if_exists: true,
+                names: vec!["Foo".into(), "Bar".into()]
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_create_view() {
+        let query = |table_name: &str| Query {
+            with: None,
+            body: SetExpr::Select(Box::new(Select {
+                distinct: None,
+                projection: vec![SelectItem::Wildcard],
+                from: TableWithJoins {
+                    relation: TableFactor::Table {
+                        name: table_name.to_owned(),
+                        alias: None,
+                        index: None,
+                    },
+                    joins: vec![],
+                },
+                selection: None,
+                group_by: vec![],
+                grouping_sets: Vec::new(),
+                having: None,
+            })),
+            order_by: vec![],
+            limit: None,
+            offset: None,
+        };
+
+        assert_eq!(
+            r#"CREATE VIEW "Foo" AS SELECT * FROM "Bar";"#,
+            Statement::CreateView {
+                or_replace: false,
+                name: "Foo".into(),
+                columns: Vec::new(),
+                query: Box::new(query("Bar")),
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"CREATE OR REPLACE VIEW "Foo" ("a", "b") AS SELECT * FROM "Bar";"#,
+            Statement::CreateView {
+                or_replace: true,
+                name: "Foo".into(),
+                columns: vec!["a".to_owned(), "b".to_owned()],
+                query: Box::new(query("Bar")),
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_drop_view() {
+        assert_eq!(
+            r#"DROP VIEW "Foo";"#,
+            Statement::DropView {
+                if_exists: false,
+                names: vec!["Foo".into()]
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"DROP VIEW IF EXISTS "Foo", "Bar";"#,
+            Statement::DropView {
+                if_exists: true,
+                names: vec!["Foo".into(), "Bar".into()]
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_create_trigger() {
+        assert_eq!(
+            r#"CREATE TRIGGER "Audit" AFTER INSERT OR DELETE ON "Foo" FOR EACH ROW DELETE FROM "Log";"#,
+            Statement::CreateTrigger {
+                or_replace: false,
+                name: "Audit".into(),
+                table_name: "Foo".into(),
+                timing: TriggerTiming::After,
+                events: vec![TriggerEvent::Insert, TriggerEvent::Delete],
+                action: TriggerAction::Statement(Box::new(Statement::Delete {
+                    table_name: "Log".into(),
+                    using: None,
+                    selection: None,
+                    returning: Vec::new(),
+                })),
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"CREATE OR REPLACE TRIGGER "Check" BEFORE UPDATE ON "Foo" FOR EACH ROW EXECUTE FUNCTION is_valid("NEW"."id");"#,
+            Statement::CreateTrigger {
+                or_replace: true,
+                name: "Check".into(),
+                table_name: "Foo".into(),
+                timing: TriggerTiming::Before,
+                events: vec![TriggerEvent::Update],
+                action: TriggerAction::Function {
+                    name: "is_valid".into(),
+                    args: vec![Expr::CompoundIdentifier {
+                        alias: "NEW".into(),
+                        ident: "id".into(),
+                    }],
+                },
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"CREATE TRIGGER "Fill" BEFORE INSERT OR UPDATE ON "Foo" FOR EACH ROW SET "NEW"."name" = 'none', "NEW"."id" = 0;"#,
+            Statement::CreateTrigger {
+                or_replace: false,
+                name: "Fill".into(),
+                table_name: "Foo".into(),
+                timing: TriggerTiming::Before,
+                events: vec![TriggerEvent::Insert, TriggerEvent::Update],
+                action: TriggerAction::SetNew(vec![
+                    Assignment {
+                        id: "name".into(),
+                        value: Expr::Literal(AstLiteral::QuotedString("none".into())),
+                    },
+                    Assignment {
+                        id: "id".into(),
+                        value: Expr::Literal(AstLiteral::Number(
+                            BigDecimal::from_str("0").unwrap()
+                        )),
+                    },
+                ]),
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_drop_trigger() {
+        assert_eq!(
+            r#"DROP TRIGGER "Audit";"#,
+            Statement::DropTrigger {
+                if_exists: false,
+                names: vec!["Audit".into()]
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"DROP TRIGGER IF EXISTS "Audit", "Check";"#,
+            Statement::DropTrigger {
+                if_exists: true,
+                names: vec!["Audit".into(), "Check".into()]
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_create_sequence() {
+        assert_eq!(
+            r#"CREATE SEQUENCE "Seq";"#,
+            Statement::CreateSequence {
+                if_not_exists: false,
+                name: "Seq".into(),
+                options: Vec::new(),
+            }
+            .to_sql()
+        );
+
+        let number = |n: &str| Expr::Literal(AstLiteral::Number(BigDecimal::from_str(n).unwrap()));
+
+        assert_eq!(
+            r#"CREATE SEQUENCE IF NOT EXISTS "Seq" INCREMENT BY 2 MINVALUE 1 NO MAXVALUE START WITH 10 CYCLE;"#,
+            Statement::CreateSequence {
+                if_not_exists: true,
+                name: "Seq".into(),
+                options: vec![
+                    SequenceOption::IncrementBy(number("2")),
+                    SequenceOption::MinValue(Some(number("1"))),
+                    SequenceOption::MaxValue(None),
+                    SequenceOption::StartWith(number("10")),
+                    SequenceOption::Cycle(true),
+                ],
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_drop_sequence() {
+        assert_eq!(
+            r#"DROP SEQUENCE "Seq";"#,
+            Statement::DropSequence {
+                if_exists: false,
+                names: vec!["Seq".into()]
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"DROP SEQUENCE IF EXISTS "Foo", "Bar";"#,
+            Statement::DropSequence {
+                if_exists: true,
+                names: vec!["Foo".into(), "Bar".into()]
+            }
+            .to_sql()
+        );
+    }
+
     #[test]
     fn to_sql_create_index() {
         assert_eq!(
@@ -739,10 +1666,52 @@ mod tests {
             Statement::CreateIndex {
                 name: "idx_name".into(),
                 table_name: "Test".into(),
-                column: OrderByExpr {
+                columns: vec![OrderByExpr {
                     expr: Expr::Identifier("LastName".to_owned()),
                     asc: None
-                }
+                }],
+                unique: false,
+                predicate: None,
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"CREATE UNIQUE INDEX "idx_customer" ON "Orders" ("customer_id", "created_at" DESC);"#,
+            Statement::CreateIndex {
+                name: "idx_customer".into(),
+                table_name: "Orders".into(),
+                columns: vec![
+                    OrderByExpr {
+                        expr: Expr::Identifier("customer_id".to_owned()),
+                        asc: None
+                    },
+                    OrderByExpr {
+                        expr: Expr::Identifier("created_at".to_owned()),
+                        asc: Some(false)
+                    }
+                ],
+                unique: true,
+                predicate: None,
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"CREATE INDEX "idx_active" ON "Orders" ("customer_id") WHERE "status" = 'active';"#,
+            Statement::CreateIndex {
+                name: "idx_active".into(),
+                table_name: "Orders".into(),
+                columns: vec![OrderByExpr {
+                    expr: Expr::Identifier("customer_id".to_owned()),
+                    asc: None
+                }],
+                unique: false,
+                predicate: Some(Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier("status".to_owned())),
+                    op: BinaryOperator::Eq,
+                    right: Box::new(Expr::Literal(AstLiteral::QuotedString("active".to_owned())))
+                }),
             }
             .to_sql()
         );
@@ -777,6 +1746,10 @@ mod tests {
             "SHOW FUNCTIONS;",
             Statement::ShowVariable(Variable::Functions).to_sql()
         );
+        assert_eq!(
+            "SHOW VIEWS;",
+            Statement::ShowVariable(Variable::Views).to_sql()
+        );
         assert_eq!(
             "SHOW VERSIONS;",
             Statement::ShowVariable(Variable::Version).to_sql()
@@ -791,6 +1764,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_sql_explain() {
+        assert_eq!(
+            r#"EXPLAIN SHOW INDEXES FROM "Test";"#,
+            Statement::Explain {
+                statement: Box::new(Statement::ShowIndexes("Test".into())),
+                analyze: false,
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"EXPLAIN ANALYZE SHOW INDEXES FROM "Test";"#,
+            Statement::Explain {
+                statement: Box::new(Statement::ShowIndexes("Test".into())),
+                analyze: true,
+            }
+            .to_sql()
+        );
+    }
+
     #[test]
     fn to_sql_assignment() {
         assert_eq!(
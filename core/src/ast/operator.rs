@@ -1,6 +1,7 @@
 use {
     crate::ast::ToSql,
     serde::{Deserialize, Serialize},
+    std::cmp::Ordering,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -90,6 +91,19 @@ impl IndexOperator {
             Eq => Eq,
         }
     }
+
+    /// Whether a value which compares to the operand as `ordering` satisfies the operator
+    pub fn matches(&self, ordering: Ordering) -> bool {
+        use IndexOperator::*;
+
+        match self {
+            Gt => ordering == Ordering::Greater,
+            Lt => ordering == Ordering::Less,
+            GtEq => ordering != Ordering::Less,
+            LtEq => ordering != Ordering::Greater,
+            Eq => ordering == Ordering::Equal,
+        }
+    }
 }
 
 impl From<IndexOperator> for BinaryOperator {
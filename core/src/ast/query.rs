@@ -8,28 +8,79 @@ use {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Query {
+    pub with: Option<With>,
     pub body: SetExpr,
     pub order_by: Vec<OrderByExpr>,
     pub limit: Option<Expr>,
     pub offset: Option<Expr>,
 }
 
+/// WITH clause, a list of common table expressions
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct With {
+    pub recursive: bool,
+    pub cte_tables: Vec<Cte>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Cte {
+    pub alias: TableAlias,
+    pub query: Query,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SetExpr {
     Select(Box<Select>),
     Values(Values),
+    /// UNION, INTERSECT or EXCEPT of two set expressions
+    SetOperation {
+        op: SetOperator,
+        /// `true` for ALL, `false` for DISTINCT (the default)
+        all: bool,
+        left: Box<SetExpr>,
+        right: Box<SetExpr>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum SetOperator {
+    Union,
+    Intersect,
+    Except,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Select {
+    pub distinct: Option<Distinct>,
     pub projection: Vec<SelectItem>,
     pub from: TableWithJoins,
     /// WHERE
     pub selection: Option<Expr>,
     pub group_by: Vec<Expr>,
+    /// GROUPING SETS, ROLLUP and CUBE of GROUP BY, crossed with `group_by` and each other
+    pub grouping_sets: Vec<GroupingSets>,
     pub having: Option<Expr>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GroupingSets {
+    /// GROUPING SETS ((a, b), (a), ())
+    Sets(Vec<Vec<Expr>>),
+    /// ROLLUP (a, b), the sets (a, b), (a) and ()
+    Rollup(Vec<Vec<Expr>>),
+    /// CUBE (a, b), the sets (a, b), (a), (b) and ()
+    Cube(Vec<Vec<Expr>>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Distinct {
+    /// DISTINCT
+    Distinct,
+    /// DISTINCT ON (exprs)
+    On(Vec<Expr>),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SelectItem {
     /// An expression
@@ -49,9 +100,18 @@ pub struct TableWithJoins {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum IndexItem {
     PrimaryKey(Expr),
+    /// Leading columns of a composite primary key, `full_key` when all of them are covered
+    CompositePrimaryKey {
+        exprs: Vec<Expr>,
+        full_key: bool,
+        /// Compares the key column next to the leading ones
+        cmp_expr: Option<(IndexOperator, Expr)>,
+    },
     NonClustered {
         name: String,
         asc: Option<bool>,
+        /// Equality values of the leading columns of a composite index, `cmp_expr` compares the next one
+        prefix: Vec<Expr>,
         cmp_expr: Option<(IndexOperator, Expr)>,
     },
 }
@@ -76,6 +136,12 @@ pub enum TableFactor {
         dict: Dictionary,
         alias: TableAlias,
     },
+    /// Table-valued custom function, `<name>(<args>)`
+    Function {
+        name: String,
+        args: Vec<Expr>,
+        alias: TableAlias,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
@@ -85,6 +151,8 @@ pub enum Dictionary {
     GlueTableColumns,
     GlueIndexes,
     GlueObjects,
+    GlueTableConstraints,
+    GlueViews,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -114,6 +182,9 @@ pub enum JoinExecutor {
 pub enum JoinOperator {
     Inner(JoinConstraint),
     LeftOuter(JoinConstraint),
+    RightOuter(JoinConstraint),
+    FullOuter(JoinConstraint),
+    Cross,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -151,12 +222,15 @@ impl Query {
         };
 
         let Query {
+            with,
             body,
             order_by,
             limit,
             offset,
         } = self;
 
+        let with = with.as_ref().map(|with| with.to_sql_with(quoted));
+
         let order_by = if order_by.is_empty() {
             "".to_owned()
         } else {
@@ -184,14 +258,77 @@ impl Query {
             .filter(|sql| !sql.is_empty())
             .join(" ");
 
-        if string.is_empty() {
+        let query = if string.is_empty() {
             body.to_sql_with(quoted)
         } else {
             format!("{} {}", body.to_sql_with(quoted), string)
+        };
+
+        match with {
+            Some(with) => format!("{with} {query}"),
+            None => query,
         }
     }
 }
 
+impl ToSql for With {
+    fn to_sql(&self) -> String {
+        self.to_sql_with(true)
+    }
+}
+
+impl ToSqlUnquoted for With {
+    fn to_sql_unquoted(&self) -> String {
+        self.to_sql_with(false)
+    }
+}
+
+impl With {
+    fn to_sql_with(&self, quoted: bool) -> String {
+        let With {
+            recursive,
+            cte_tables,
+        } = self;
+
+        let recursive = if *recursive { " RECURSIVE" } else { "" };
+        let cte_tables = cte_tables
+            .iter()
+            .map(|cte| cte.to_sql_with(quoted))
+            .join(", ");
+
+        format!("WITH{recursive} {cte_tables}")
+    }
+}
+
+impl Cte {
+    fn to_sql_with(&self, quoted: bool) -> String {
+        let Cte {
+            alias: TableAlias { name, columns },
+            query,
+        } = self;
+
+        let name = match quoted {
+            true => format!(r#""{name}""#),
+            false => name.to_owned(),
+        };
+        let name = if columns.is_empty() {
+            name
+        } else {
+            let columns = columns
+                .iter()
+                .map(|column| match quoted {
+                    true => format!(r#""{column}""#),
+                    false => column.to_owned(),
+                })
+                .join(", ");
+
+            format!("{name} ({columns})")
+        };
+
+        format!("{name} AS ({})", query.to_sql_with(quoted))
+    }
+}
+
 impl ToSql for SetExpr {
     fn to_sql(&self) -> String {
         self.to_sql_with(true)
@@ -211,6 +348,23 @@ impl SetExpr {
             (SetExpr::Select(select), false) => select.to_sql_unquoted(),
             (SetExpr::Values(values), true) => format!("VALUES {}", values.to_sql()),
             (SetExpr::Values(values), false) => format!("VALUES {}", values.to_sql_unquoted()),
+            (
+                SetExpr::SetOperation {
+                    op,
+                    all,
+                    left,
+                    right,
+                },
+                _,
+            ) => {
+                let operand = |set_expr: &SetExpr| match set_expr {
+                    SetExpr::SetOperation { .. } => format!("({})", set_expr.to_sql_with(quoted)),
+                    SetExpr::Select(_) | SetExpr::Values(_) => set_expr.to_sql_with(quoted),
+                };
+                let all = if *all { " ALL" } else { "" };
+
+                format!("{} {op}{all} {}", operand(left), operand(right))
+            }
         }
     }
 }
@@ -235,28 +389,46 @@ impl Select {
         };
 
         let Select {
+            distinct,
             projection,
             from,
             selection,
             group_by,
+            grouping_sets,
             having,
         } = self;
         let projection = projection
             .iter()
             .map(|item| item.to_sql_with(quoted))
             .join(", ");
+        let projection = match distinct {
+            Some(Distinct::Distinct) => format!("DISTINCT {projection}"),
+            Some(Distinct::On(exprs)) => format!(
+                "DISTINCT ON ({}) {projection}",
+                exprs.iter().map(|expr| to_sql(expr)).join(", ")
+            ),
+            None => projection,
+        };
 
         let selection = match selection {
             Some(expr) => format!("WHERE {}", to_sql(expr)),
             None => "".to_owned(),
         };
 
-        let group_by = if group_by.is_empty() {
+        let group_by = if group_by.is_empty() && grouping_sets.is_empty() {
             "".to_owned()
         } else {
+            let grouping_sets = grouping_sets
+                .iter()
+                .map(|grouping_sets| grouping_sets.to_sql_with(quoted));
+
             format!(
                 "GROUP BY {}",
-                group_by.iter().map(|item| to_sql(item)).join(", ")
+                group_by
+                    .iter()
+                    .map(|item| to_sql(item))
+                    .chain(grouping_sets)
+                    .join(", ")
             )
         };
 
@@ -281,6 +453,41 @@ impl Select {
     }
 }
 
+impl ToSql for GroupingSets {
+    fn to_sql(&self) -> String {
+        self.to_sql_with(true)
+    }
+}
+
+impl ToSqlUnquoted for GroupingSets {
+    fn to_sql_unquoted(&self) -> String {
+        self.to_sql_with(false)
+    }
+}
+
+impl GroupingSets {
+    fn to_sql_with(&self, quoted: bool) -> String {
+        let to_sql = |expr: &Expr| match quoted {
+            true => expr.to_sql(),
+            false => expr.to_sql_unquoted(),
+        };
+        let to_sql_sets = |sets: &[Vec<Expr>], lift_singleton: bool| {
+            sets.iter()
+                .map(|set| match set.as_slice() {
+                    [expr] if lift_singleton => to_sql(expr),
+                    _ => format!("({})", set.iter().map(to_sql).join(", ")),
+                })
+                .join(", ")
+        };
+
+        match self {
+            GroupingSets::Sets(sets) => format!("GROUPING SETS ({})", to_sql_sets(sets, false)),
+            GroupingSets::Rollup(sets) => format!("ROLLUP ({})", to_sql_sets(sets, true)),
+            GroupingSets::Cube(sets) => format!("CUBE ({})", to_sql_sets(sets, true)),
+        }
+    }
+}
+
 impl ToSql for SelectItem {
     fn to_sql(&self) -> String {
         self.to_sql_with(true)
@@ -390,6 +597,15 @@ impl TableFactor {
             (TableFactor::Dictionary { dict, alias }, false) => {
                 format!("{dict} {}", alias.to_sql_with(quoted))
             }
+            (TableFactor::Function { name, args, alias }, _) => {
+                let args = args.iter().map(to_sql).collect::<Vec<_>>().join(", ");
+                let name = match quoted {
+                    true => format!(r#""{name}""#),
+                    false => name.to_owned(),
+                };
+
+                format!("{name}({args}) {}", alias.to_sql_with(quoted))
+            }
         }
     }
 }
@@ -438,14 +654,24 @@ impl Join {
         } = self;
 
         let (join_operator, join_constraint) = match join_operator {
-            JoinOperator::Inner(join_constraint) => ("INNER JOIN", join_constraint),
-            JoinOperator::LeftOuter(join_constraint) => ("LEFT OUTER JOIN", join_constraint),
+            JoinOperator::Inner(join_constraint) => ("INNER JOIN", Some(join_constraint)),
+            JoinOperator::LeftOuter(join_constraint) => ("LEFT OUTER JOIN", Some(join_constraint)),
+            JoinOperator::RightOuter(join_constraint) => {
+                ("RIGHT OUTER JOIN", Some(join_constraint))
+            }
+            JoinOperator::FullOuter(join_constraint) => ("FULL OUTER JOIN", Some(join_constraint)),
+            JoinOperator::Cross => ("CROSS JOIN", None),
         };
 
         let (join_constraint, join_executor) = match quoted {
-            true => (join_constraint.to_sql(), join_executor.to_sql()),
+            true => (
+                join_constraint.map(ToSql::to_sql).unwrap_or_default(),
+                join_executor.to_sql(),
+            ),
             false => (
-                join_constraint.to_sql_unquoted(),
+                join_constraint
+                    .map(ToSqlUnquoted::to_sql_unquoted)
+                    .unwrap_or_default(),
                 join_executor.to_sql_unquoted(),
             ),
         };
@@ -590,9 +816,10 @@ mod tests {
     use {
         crate::{
             ast::{
-                AstLiteral, BinaryOperator, Dictionary, Expr, Join, JoinConstraint, JoinExecutor,
-                JoinOperator, OrderByExpr, Query, Select, SelectItem, SetExpr, TableAlias,
-                TableFactor, TableWithJoins, ToSql, ToSqlUnquoted, Values,
+                AstLiteral, BinaryOperator, Cte, Dictionary, Distinct, Expr, GroupingSets, Join,
+                JoinConstraint, JoinExecutor, JoinOperator, OrderByExpr, Query, Select, SelectItem,
+                SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, ToSql,
+                ToSqlUnquoted, Values, With,
             },
             parse_sql::parse_expr,
             translate::translate_expr,
@@ -616,7 +843,9 @@ mod tests {
         let actual =
             r#"SELECT * FROM "FOO" AS "F" ORDER BY "name" ASC LIMIT 10 OFFSET 3"#.to_owned();
         let expected = Query {
+            with: None,
             body: SetExpr::Select(Box::new(Select {
+                distinct: None,
                 projection: vec![SelectItem::Wildcard],
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -631,6 +860,7 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             })),
             order_by,
@@ -653,7 +883,9 @@ mod tests {
         }];
         let actual = "SELECT * FROM FOO AS F ORDER BY name ASC LIMIT 10 OFFSET 3".to_owned();
         let expected = Query {
+            with: None,
             body: SetExpr::Select(Box::new(Select {
+                distinct: None,
                 projection: vec![SelectItem::Wildcard],
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -668,6 +900,7 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             })),
             order_by,
@@ -686,6 +919,7 @@ mod tests {
     fn to_sql_set_expr() {
         let actual = r#"SELECT * FROM "FOO" AS "F" INNER JOIN "PlayerItem""#.to_owned();
         let expected = SetExpr::Select(Box::new(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -708,6 +942,7 @@ mod tests {
             },
             selection: None,
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         }))
         .to_sql();
@@ -728,12 +963,33 @@ mod tests {
         ]))
         .to_sql();
         assert_eq!(actual, expected);
+
+        let values = |n: &str| {
+            SetExpr::Values(Values(vec![vec![Expr::Literal(AstLiteral::Number(
+                BigDecimal::from_str(n).unwrap(),
+            ))]]))
+        };
+        let actual = "VALUES (1) UNION ALL (VALUES (2) EXCEPT VALUES (3))".to_owned();
+        let expected = SetExpr::SetOperation {
+            op: SetOperator::Union,
+            all: true,
+            left: Box::new(values("1")),
+            right: Box::new(SetExpr::SetOperation {
+                op: SetOperator::Except,
+                all: false,
+                left: Box::new(values("2")),
+                right: Box::new(values("3")),
+            }),
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
     }
 
     #[test]
     fn to_sql_unquoted_set_expr() {
         let actual = "SELECT * FROM FOO AS F INNER JOIN PlayerItem".to_owned();
         let expected = SetExpr::Select(Box::new(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -756,6 +1012,7 @@ mod tests {
             },
             selection: None,
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         }))
         .to_sql_unquoted();
@@ -797,6 +1054,7 @@ mod tests {
         let actual =
             r#"SELECT * FROM "FOO" AS "F" GROUP BY "name" HAVING "name" = 'glue'"#.to_owned();
         let expected = Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -811,6 +1069,7 @@ mod tests {
             },
             selection: None,
             group_by: vec![Expr::Identifier("name".to_owned())],
+            grouping_sets: Vec::new(),
             having: Some(Expr::BinaryOp {
                 left: Box::new(Expr::Identifier("name".to_owned())),
                 op: BinaryOperator::Eq,
@@ -820,8 +1079,38 @@ mod tests {
         .to_sql();
         assert_eq!(actual, expected);
 
+        let actual = r#"SELECT * FROM "FOO" GROUP BY "city", ROLLUP ("name", ("a", "b")), CUBE ("a"), GROUPING SETS (("a", "b"), ("name"), ())"#.to_owned();
+        let ident = |name: &str| Expr::Identifier(name.to_owned());
+        let expected = Select {
+            distinct: None,
+            projection: vec![SelectItem::Wildcard],
+            from: TableWithJoins {
+                relation: TableFactor::Table {
+                    name: "FOO".to_owned(),
+                    alias: None,
+                    index: None,
+                },
+                joins: Vec::new(),
+            },
+            selection: None,
+            group_by: vec![ident("city")],
+            grouping_sets: vec![
+                GroupingSets::Rollup(vec![vec![ident("name")], vec![ident("a"), ident("b")]]),
+                GroupingSets::Cube(vec![vec![ident("a")]]),
+                GroupingSets::Sets(vec![
+                    vec![ident("a"), ident("b")],
+                    vec![ident("name")],
+                    Vec::new(),
+                ]),
+            ],
+            having: None,
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
         let actual = r#"SELECT * FROM "FOO" WHERE "name" = 'glue'"#.to_owned();
         let expected = Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -837,6 +1126,53 @@ mod tests {
                 right: Box::new(Expr::Literal(AstLiteral::QuotedString("glue".to_owned()))),
             }),
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
+            having: None,
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
+        let actual = r#"SELECT DISTINCT "name" AS "name" FROM "FOO""#.to_owned();
+        let expected = Select {
+            distinct: Some(Distinct::Distinct),
+            projection: vec![SelectItem::Expr {
+                expr: Expr::Identifier("name".to_owned()),
+                label: "name".to_owned(),
+            }],
+            from: TableWithJoins {
+                relation: TableFactor::Table {
+                    name: "FOO".to_owned(),
+                    alias: None,
+                    index: None,
+                },
+                joins: Vec::new(),
+            },
+            selection: None,
+            group_by: Vec::new(),
+            grouping_sets: Vec::new(),
+            having: None,
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
+        let actual = r#"SELECT DISTINCT ON ("name", "age") * FROM "FOO""#.to_owned();
+        let expected = Select {
+            distinct: Some(Distinct::On(vec![
+                Expr::Identifier("name".to_owned()),
+                Expr::Identifier("age".to_owned()),
+            ])),
+            projection: vec![SelectItem::Wildcard],
+            from: TableWithJoins {
+                relation: TableFactor::Table {
+                    name: "FOO".to_owned(),
+                    alias: None,
+                    index: None,
+                },
+                joins: Vec::new(),
+            },
+            selection: None,
+            group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         }
         .to_sql();
@@ -847,6 +1183,7 @@ mod tests {
     fn to_sql_unquoted_select() {
         let actual = "SELECT * FROM FOO AS F GROUP BY name HAVING name = 'glue'".to_owned();
         let expected = Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -861,6 +1198,7 @@ mod tests {
             },
             selection: None,
             group_by: vec![Expr::Identifier("name".to_owned())],
+            grouping_sets: Vec::new(),
             having: Some(Expr::BinaryOp {
                 left: Box::new(Expr::Identifier("name".to_owned())),
                 op: BinaryOperator::Eq,
@@ -872,6 +1210,7 @@ mod tests {
 
         let actual = "SELECT * FROM FOO WHERE name = 'glue'".to_owned();
         let expected = Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -887,12 +1226,66 @@ mod tests {
                 right: Box::new(Expr::Literal(AstLiteral::QuotedString("glue".to_owned()))),
             }),
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         }
         .to_sql_unquoted();
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn to_sql_query_with() {
+        let with = With {
+            recursive: true,
+            cte_tables: vec![Cte {
+                alias: TableAlias {
+                    name: "T".to_owned(),
+                    columns: vec!["n".to_owned()],
+                },
+                query: Query {
+                    with: None,
+                    body: SetExpr::Values(Values(vec![vec![Expr::Literal(AstLiteral::Number(
+                        BigDecimal::from_str("1").unwrap(),
+                    ))]])),
+                    order_by: Vec::new(),
+                    limit: None,
+                    offset: None,
+                },
+            }],
+        };
+        let query = Query {
+            with: Some(with),
+            body: SetExpr::Select(Box::new(Select {
+                distinct: None,
+                projection: vec![SelectItem::Wildcard],
+                from: TableWithJoins {
+                    relation: TableFactor::Table {
+                        name: "T".to_owned(),
+                        alias: None,
+                        index: None,
+                    },
+                    joins: Vec::new(),
+                },
+                selection: None,
+                group_by: Vec::new(),
+                grouping_sets: Vec::new(),
+                having: None,
+            })),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+        };
+
+        assert_eq!(
+            r#"WITH RECURSIVE "T" ("n") AS (VALUES (1)) SELECT * FROM "T""#,
+            query.to_sql()
+        );
+        assert_eq!(
+            "WITH RECURSIVE T (n) AS (VALUES (1)) SELECT * FROM T",
+            query.to_sql_unquoted()
+        );
+    }
+
     #[test]
     fn to_sql_select_item() {
         let actual = r#""name" AS "n""#.to_owned();
@@ -980,7 +1373,9 @@ mod tests {
         let actual = r#"(SELECT * FROM "FOO") AS "F""#;
         let expected = TableFactor::Derived {
             subquery: Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
+                    distinct: None,
                     projection: vec![SelectItem::Wildcard],
                     from: TableWithJoins {
                         relation: TableFactor::Table {
@@ -992,6 +1387,7 @@ mod tests {
                     },
                     selection: None,
                     group_by: Vec::new(),
+                    grouping_sets: Vec::new(),
                     having: None,
                 })),
                 order_by: Vec::new(),
@@ -1027,6 +1423,21 @@ mod tests {
         }
         .to_sql();
         assert_eq!(actual, expected);
+
+        let actual = r#""items"(1, 'a') AS "i""#;
+        let expected = TableFactor::Function {
+            name: "items".to_owned(),
+            args: vec![
+                Expr::Literal(AstLiteral::Number(BigDecimal::from_str("1").unwrap())),
+                Expr::Literal(AstLiteral::QuotedString("a".to_owned())),
+            ],
+            alias: TableAlias {
+                name: "i".to_owned(),
+                columns: Vec::new(),
+            },
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
     }
 
     #[test]
@@ -1046,7 +1457,9 @@ mod tests {
         let actual = "(SELECT * FROM FOO) AS F";
         let expected = TableFactor::Derived {
             subquery: Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
+                    distinct: None,
                     projection: vec![SelectItem::Wildcard],
                     from: TableWithJoins {
                         relation: TableFactor::Table {
@@ -1058,6 +1471,7 @@ mod tests {
                     },
                     selection: None,
                     group_by: Vec::new(),
+                    grouping_sets: Vec::new(),
                     having: None,
                 })),
                 order_by: Vec::new(),
@@ -1093,6 +1507,20 @@ mod tests {
         }
         .to_sql_unquoted();
         assert_eq!(actual, expected);
+
+        let actual = "items(1) AS i";
+        let expected = TableFactor::Function {
+            name: "items".to_owned(),
+            args: vec![Expr::Literal(AstLiteral::Number(
+                BigDecimal::from_str("1").unwrap(),
+            ))],
+            alias: TableAlias {
+                name: "i".to_owned(),
+                columns: Vec::new(),
+            },
+        }
+        .to_sql_unquoted();
+        assert_eq!(actual, expected);
     }
 
     #[test]
@@ -1197,6 +1625,51 @@ mod tests {
         }
         .to_sql();
         assert_eq!(actual, expected);
+
+        let actual = r#"RIGHT OUTER JOIN "PlayerItem" ON "PlayerItem"."user_id" = "Player"."id""#;
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::RightOuter(JoinConstraint::On(expr(
+                r#""PlayerItem"."user_id" = "Player"."id""#,
+            ))),
+            join_executor: JoinExecutor::NestedLoop,
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
+        let actual = r#"FULL OUTER JOIN "PlayerItem" ON "PlayerItem"."user_id" = "Player"."id""#;
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::FullOuter(JoinConstraint::None),
+            join_executor: JoinExecutor::Hash {
+                key_expr: expr("PlayerItem.user_id"),
+                value_expr: expr("Player.id"),
+                where_clause: None,
+            },
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
+        let actual = r#"CROSS JOIN "PlayerItem""#;
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::Cross,
+            join_executor: JoinExecutor::NestedLoop,
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
     }
 
     #[test]
@@ -1283,6 +1756,47 @@ mod tests {
         }
         .to_sql_unquoted();
         assert_eq!(actual, expected);
+
+        let actual = "RIGHT OUTER JOIN PlayerItem";
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::RightOuter(JoinConstraint::None),
+            join_executor: JoinExecutor::NestedLoop,
+        }
+        .to_sql_unquoted();
+        assert_eq!(actual, expected);
+
+        let actual = "FULL OUTER JOIN PlayerItem ON PlayerItem.user_id = Player.id";
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::FullOuter(JoinConstraint::On(expr(
+                "PlayerItem.user_id = Player.id",
+            ))),
+            join_executor: JoinExecutor::NestedLoop,
+        }
+        .to_sql_unquoted();
+        assert_eq!(actual, expected);
+
+        let actual = "CROSS JOIN PlayerItem";
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::Cross,
+            join_executor: JoinExecutor::NestedLoop,
+        }
+        .to_sql_unquoted();
+        assert_eq!(actual, expected);
     }
 
     #[test]
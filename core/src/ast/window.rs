@@ -0,0 +1,262 @@
+use {
+    super::{Aggregate, Expr, OrderByExpr, ToSql},
+    serde::{Deserialize, Serialize},
+    strum_macros::Display,
+};
+
+/// Function call evaluated over a window, e.g. `RANK() OVER (ORDER BY score DESC)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Window {
+    pub function: WindowFunction,
+    pub spec: WindowSpec,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum WindowFunction {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag {
+        expr: Expr,
+        offset: Option<Expr>,
+        default: Option<Expr>,
+    },
+    Lead {
+        expr: Expr,
+        offset: Option<Expr>,
+        default: Option<Expr>,
+    },
+    FirstValue(Expr),
+    LastValue(Expr),
+    Aggregate(Aggregate),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WindowSpec {
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<OrderByExpr>,
+    pub frame: Option<WindowFrame>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start: WindowFrameBound,
+    pub end: WindowFrameBound,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WindowFrameBound {
+    CurrentRow,
+    /// `None` represents `UNBOUNDED PRECEDING`
+    Preceding(Option<Expr>),
+    /// `None` represents `UNBOUNDED FOLLOWING`
+    Following(Option<Expr>),
+}
+
+impl ToSql for Window {
+    fn to_sql(&self) -> String {
+        let Window { function, spec } = self;
+
+        format!("{} OVER ({})", function.to_sql(), spec.to_sql())
+    }
+}
+
+impl ToSql for WindowFunction {
+    fn to_sql(&self) -> String {
+        let name = self.to_string();
+
+        match self {
+            WindowFunction::RowNumber | WindowFunction::Rank | WindowFunction::DenseRank => {
+                format!("{name}()")
+            }
+            WindowFunction::Lag {
+                expr,
+                offset,
+                default,
+            }
+            | WindowFunction::Lead {
+                expr,
+                offset,
+                default,
+            } => {
+                let args = std::iter::once(expr)
+                    .chain(offset)
+                    .chain(default)
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("{name}({args})")
+            }
+            WindowFunction::FirstValue(expr) | WindowFunction::LastValue(expr) => {
+                format!("{name}({})", expr.to_sql())
+            }
+            WindowFunction::Aggregate(aggregate) => aggregate.to_sql(),
+        }
+    }
+}
+
+impl ToSql for WindowSpec {
+    fn to_sql(&self) -> String {
+        let WindowSpec {
+            partition_by,
+            order_by,
+            frame,
+        } = self;
+
+        let partition_by = (!partition_by.is_empty()).then(|| {
+            let exprs = partition_by
+                .iter()
+                .map(ToSql::to_sql)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("PARTITION BY {exprs}")
+        });
+        let order_by = (!order_by.is_empty()).then(|| {
+            let exprs = order_by
+                .iter()
+                .map(ToSql::to_sql)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("ORDER BY {exprs}")
+        });
+        let frame = frame.as_ref().map(ToSql::to_sql);
+
+        [partition_by, order_by, frame]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl ToSql for WindowFrame {
+    fn to_sql(&self) -> String {
+        let WindowFrame { units, start, end } = self;
+
+        format!("{units} BETWEEN {} AND {}", start.to_sql(), end.to_sql())
+    }
+}
+
+impl ToSql for WindowFrameBound {
+    fn to_sql(&self) -> String {
+        match self {
+            WindowFrameBound::CurrentRow => "CURRENT ROW".to_owned(),
+            WindowFrameBound::Preceding(None) => "UNBOUNDED PRECEDING".to_owned(),
+            WindowFrameBound::Preceding(Some(expr)) => format!("{} PRECEDING", expr.to_sql()),
+            WindowFrameBound::Following(None) => "UNBOUNDED FOLLOWING".to_owned(),
+            WindowFrameBound::Following(Some(expr)) => format!("{} FOLLOWING", expr.to_sql()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{
+        AggregateFunction, AstLiteral, Expr, OrderByExpr, ToSql, Window, WindowFrame,
+        WindowFrameBound, WindowFrameUnits, WindowFunction, WindowSpec,
+    };
+
+    fn number(n: &str) -> Expr {
+        Expr::Literal(AstLiteral::Number(n.parse().unwrap()))
+    }
+
+    #[test]
+    fn to_sql() {
+        let actual = Expr::Window(Box::new(Window {
+            function: WindowFunction::RowNumber,
+            spec: WindowSpec {
+                partition_by: Vec::new(),
+                order_by: Vec::new(),
+                frame: None,
+            },
+        }));
+        assert_eq!("ROW_NUMBER() OVER ()", actual.to_sql());
+
+        let actual = Expr::Window(Box::new(Window {
+            function: WindowFunction::DenseRank,
+            spec: WindowSpec {
+                partition_by: vec![Expr::Identifier("category".to_owned())],
+                order_by: vec![OrderByExpr {
+                    expr: Expr::Identifier("price".to_owned()),
+                    asc: Some(false),
+                }],
+                frame: None,
+            },
+        }));
+        assert_eq!(
+            r#"DENSE_RANK() OVER (PARTITION BY "category" ORDER BY "price" DESC)"#,
+            actual.to_sql()
+        );
+
+        let actual = Expr::Window(Box::new(Window {
+            function: WindowFunction::Lag {
+                expr: Expr::Identifier("price".to_owned()),
+                offset: Some(number("2")),
+                default: Some(number("0")),
+            },
+            spec: WindowSpec {
+                partition_by: Vec::new(),
+                order_by: vec![OrderByExpr {
+                    expr: Expr::Identifier("id".to_owned()),
+                    asc: None,
+                }],
+                frame: None,
+            },
+        }));
+        assert_eq!(
+            r#"LAG("price", 2, 0) OVER (ORDER BY "id")"#,
+            actual.to_sql()
+        );
+
+        let actual = Expr::Window(Box::new(Window {
+            function: WindowFunction::Aggregate(
+                AggregateFunction::Sum(Expr::Identifier("price".to_owned())).into(),
+            ),
+            spec: WindowSpec {
+                partition_by: Vec::new(),
+                order_by: vec![OrderByExpr {
+                    expr: Expr::Identifier("id".to_owned()),
+                    asc: Some(true),
+                }],
+                frame: Some(WindowFrame {
+                    units: WindowFrameUnits::Rows,
+                    start: WindowFrameBound::Preceding(Some(number("1"))),
+                    end: WindowFrameBound::Following(None),
+                }),
+            },
+        }));
+        assert_eq!(
+            r#"SUM("price") OVER (ORDER BY "id" ASC ROWS BETWEEN 1 PRECEDING AND UNBOUNDED FOLLOWING)"#,
+            actual.to_sql()
+        );
+
+        let actual = Expr::Window(Box::new(Window {
+            function: WindowFunction::LastValue(Expr::Identifier("name".to_owned())),
+            spec: WindowSpec {
+                partition_by: Vec::new(),
+                order_by: Vec::new(),
+                frame: Some(WindowFrame {
+                    units: WindowFrameUnits::Range,
+                    start: WindowFrameBound::CurrentRow,
+                    end: WindowFrameBound::CurrentRow,
+                }),
+            },
+        }));
+        assert_eq!(
+            r#"LAST_VALUE("name") OVER (RANGE BETWEEN CURRENT ROW AND CURRENT ROW)"#,
+            actual.to_sql()
+        );
+    }
+}
@@ -52,6 +52,9 @@ impl Build for CreateTableNode {
             columns,
             source: None,
             engine: None,
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            unique_keys: Vec::new(),
         })
     }
 }
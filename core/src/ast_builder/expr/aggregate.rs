@@ -1,7 +1,7 @@
 use {
     super::ExprNode,
     crate::{
-        ast::{Aggregate, CountArgExpr},
+        ast::{Aggregate, AggregateFunction, CountArgExpr},
         parse_sql::parse_expr,
         result::{Error, Result},
         translate::translate_expr,
@@ -9,7 +9,13 @@ use {
 };
 
 #[derive(Clone, Debug)]
-pub enum AggregateNode<'a> {
+pub struct AggregateNode<'a> {
+    func: AggregateFunctionNode<'a>,
+    distinct: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum AggregateFunctionNode<'a> {
     Count(CountArgExprNode<'a>),
     Sum(ExprNode<'a>),
     Min(ExprNode<'a>),
@@ -17,6 +23,15 @@ pub enum AggregateNode<'a> {
     Avg(ExprNode<'a>),
     Variance(ExprNode<'a>),
     Stdev(ExprNode<'a>),
+    ArrayAgg(ExprNode<'a>),
+    StringAgg {
+        expr: ExprNode<'a>,
+        separator: ExprNode<'a>,
+    },
+    JsonObjectAgg {
+        key: ExprNode<'a>,
+        value: ExprNode<'a>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -53,24 +68,75 @@ impl<'a> TryFrom<CountArgExprNode<'a>> for CountArgExpr {
     }
 }
 
-impl<'a> TryFrom<AggregateNode<'a>> for Aggregate {
+impl<'a> TryFrom<AggregateFunctionNode<'a>> for AggregateFunction {
     type Error = Error;
 
-    fn try_from(aggr_node: AggregateNode<'a>) -> Result<Self> {
-        match aggr_node {
-            AggregateNode::Count(count_arg_expr_node) => {
-                count_arg_expr_node.try_into().map(Aggregate::Count)
+    fn try_from(func_node: AggregateFunctionNode<'a>) -> Result<Self> {
+        match func_node {
+            AggregateFunctionNode::Count(count_arg_expr_node) => {
+                count_arg_expr_node.try_into().map(AggregateFunction::Count)
+            }
+            AggregateFunctionNode::Sum(expr_node) => {
+                expr_node.try_into().map(AggregateFunction::Sum)
+            }
+            AggregateFunctionNode::Min(expr_node) => {
+                expr_node.try_into().map(AggregateFunction::Min)
+            }
+            AggregateFunctionNode::Max(expr_node) => {
+                expr_node.try_into().map(AggregateFunction::Max)
+            }
+            AggregateFunctionNode::Avg(expr_node) => {
+                expr_node.try_into().map(AggregateFunction::Avg)
+            }
+            AggregateFunctionNode::Variance(expr_node) => {
+                expr_node.try_into().map(AggregateFunction::Variance)
+            }
+            AggregateFunctionNode::Stdev(expr_node) => {
+                expr_node.try_into().map(AggregateFunction::Stdev)
+            }
+            AggregateFunctionNode::ArrayAgg(expr_node) => {
+                expr_node.try_into().map(AggregateFunction::ArrayAgg)
+            }
+            AggregateFunctionNode::StringAgg { expr, separator } => {
+                Ok(AggregateFunction::StringAgg {
+                    expr: expr.try_into()?,
+                    separator: separator.try_into()?,
+                })
+            }
+            AggregateFunctionNode::JsonObjectAgg { key, value } => {
+                Ok(AggregateFunction::JsonObjectAgg {
+                    key: key.try_into()?,
+                    value: value.try_into()?,
+                })
             }
-            AggregateNode::Sum(expr_node) => expr_node.try_into().map(Aggregate::Sum),
-            AggregateNode::Min(expr_node) => expr_node.try_into().map(Aggregate::Min),
-            AggregateNode::Max(expr_node) => expr_node.try_into().map(Aggregate::Max),
-            AggregateNode::Avg(expr_node) => expr_node.try_into().map(Aggregate::Avg),
-            AggregateNode::Variance(expr_node) => expr_node.try_into().map(Aggregate::Variance),
-            AggregateNode::Stdev(expr_node) => expr_node.try_into().map(Aggregate::Stdev),
         }
     }
 }
 
+impl<'a> TryFrom<AggregateNode<'a>> for Aggregate {
+    type Error = Error;
+
+    fn try_from(aggr_node: AggregateNode<'a>) -> Result<Self> {
+        let AggregateNode { func, distinct } = aggr_node;
+
+        Ok(Aggregate {
+            func: func.try_into()?,
+            distinct,
+            filter: None,
+            order_by: Vec::new(),
+        })
+    }
+}
+
+impl<'a> From<AggregateFunctionNode<'a>> for ExprNode<'a> {
+    fn from(func: AggregateFunctionNode<'a>) -> Self {
+        ExprNode::Aggregate(Box::new(AggregateNode {
+            func,
+            distinct: false,
+        }))
+    }
+}
+
 impl<'a> ExprNode<'a> {
     pub fn count(self) -> Self {
         count(self)
@@ -99,39 +165,121 @@ impl<'a> ExprNode<'a> {
     pub fn stdev(self) -> Self {
         stdev(self)
     }
+
+    pub fn array_agg(self) -> Self {
+        array_agg(self)
+    }
+
+    pub fn string_agg<T: Into<ExprNode<'a>>>(self, separator: T) -> Self {
+        string_agg(self, separator)
+    }
+
+    pub fn count_distinct(self) -> Self {
+        count_distinct(self)
+    }
+
+    pub fn sum_distinct(self) -> Self {
+        sum_distinct(self)
+    }
+
+    pub fn avg_distinct(self) -> Self {
+        avg_distinct(self)
+    }
+
+    /// Only the rows passing `expr` are aggregated, e.g. `SUM(price) FILTER (WHERE paid)`
+    pub fn filter<T: Into<ExprNode<'a>>>(self, expr: T) -> Self {
+        ExprNode::AggregateFilter {
+            aggregate: Box::new(self),
+            filter: Box::new(expr.into()),
+        }
+    }
 }
 
 pub fn count<'a, T: Into<CountArgExprNode<'a>>>(expr: T) -> ExprNode<'a> {
-    ExprNode::Aggregate(Box::new(AggregateNode::Count(expr.into())))
+    AggregateFunctionNode::Count(expr.into()).into()
 }
 
 pub fn sum<'a, T: Into<ExprNode<'a>>>(expr: T) -> ExprNode<'a> {
-    ExprNode::Aggregate(Box::new(AggregateNode::Sum(expr.into())))
+    AggregateFunctionNode::Sum(expr.into()).into()
 }
 
 pub fn min<'a, T: Into<ExprNode<'a>>>(expr: T) -> ExprNode<'a> {
-    ExprNode::Aggregate(Box::new(AggregateNode::Min(expr.into())))
+    AggregateFunctionNode::Min(expr.into()).into()
 }
 
 pub fn max<'a, T: Into<ExprNode<'a>>>(expr: T) -> ExprNode<'a> {
-    ExprNode::Aggregate(Box::new(AggregateNode::Max(expr.into())))
+    AggregateFunctionNode::Max(expr.into()).into()
 }
 
 pub fn avg<'a, T: Into<ExprNode<'a>>>(expr: T) -> ExprNode<'a> {
-    ExprNode::Aggregate(Box::new(AggregateNode::Avg(expr.into())))
+    AggregateFunctionNode::Avg(expr.into()).into()
 }
 
 pub fn variance<'a, T: Into<ExprNode<'a>>>(expr: T) -> ExprNode<'a> {
-    ExprNode::Aggregate(Box::new(AggregateNode::Variance(expr.into())))
+    AggregateFunctionNode::Variance(expr.into()).into()
 }
 
 pub fn stdev<'a, T: Into<ExprNode<'a>>>(expr: T) -> ExprNode<'a> {
-    ExprNode::Aggregate(Box::new(AggregateNode::Stdev(expr.into())))
+    AggregateFunctionNode::Stdev(expr.into()).into()
+}
+
+pub fn array_agg<'a, T: Into<ExprNode<'a>>>(expr: T) -> ExprNode<'a> {
+    AggregateFunctionNode::ArrayAgg(expr.into()).into()
+}
+
+pub fn string_agg<'a, T: Into<ExprNode<'a>>, U: Into<ExprNode<'a>>>(
+    expr: T,
+    separator: U,
+) -> ExprNode<'a> {
+    AggregateFunctionNode::StringAgg {
+        expr: expr.into(),
+        separator: separator.into(),
+    }
+    .into()
+}
+
+pub fn json_object_agg<'a, T: Into<ExprNode<'a>>, U: Into<ExprNode<'a>>>(
+    key: T,
+    value: U,
+) -> ExprNode<'a> {
+    AggregateFunctionNode::JsonObjectAgg {
+        key: key.into(),
+        value: value.into(),
+    }
+    .into()
+}
+
+fn distinct(func: AggregateFunctionNode) -> ExprNode {
+    ExprNode::Aggregate(Box::new(AggregateNode {
+        func,
+        distinct: true,
+    }))
+}
+
+pub fn count_distinct<'a, T: Into<ExprNode<'a>>>(expr: T) -> ExprNode<'a> {
+    distinct(AggregateFunctionNode::Count(CountArgExprNode::Expr(
+        expr.into(),
+    )))
+}
+
+pub fn sum_distinct<'a, T: Into<ExprNode<'a>>>(expr: T) -> ExprNode<'a> {
+    distinct(AggregateFunctionNode::Sum(expr.into()))
+}
+
+pub fn avg_distinct<'a, T: Into<ExprNode<'a>>>(expr: T) -> ExprNode<'a> {
+    distinct(AggregateFunctionNode::Avg(expr.into()))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ast_builder::{avg, col, count, max, min, stdev, sum, test_expr, variance};
+    use crate::{
+        ast::{Expr, ToSql},
+        ast_builder::{
+            array_agg, avg, avg_distinct, col, count, count_distinct, expr, json_object_agg, max,
+            min, stdev, string_agg, sum, sum_distinct, test_expr, text, variance, ExprNode,
+        },
+        translate::TranslateError,
+    };
 
     #[test]
     fn aggregate() {
@@ -193,5 +341,70 @@ mod tests {
         let actual = stdev("scatterplot");
         let expected = "STDEV(scatterplot)";
         test_expr(actual, expected);
+
+        let actual = col("name").array_agg();
+        let expected = "ARRAY_AGG(name)";
+        test_expr(actual, expected);
+
+        let actual = array_agg("name");
+        let expected = "ARRAY_AGG(name)";
+        test_expr(actual, expected);
+
+        let actual = col("name").string_agg(text(", "));
+        let expected = "STRING_AGG(name, ', ')";
+        test_expr(actual, expected);
+
+        let actual = string_agg("name", text(", "));
+        let expected = "STRING_AGG(name, ', ')";
+        test_expr(actual, expected);
+
+        let actual = json_object_agg("name", "score");
+        let expected = "JSON_OBJECT_AGG(name, score)";
+        test_expr(actual, expected);
+
+        let actual = col("id").count_distinct();
+        let expected = "COUNT(DISTINCT id)";
+        test_expr(actual, expected);
+
+        let actual = count_distinct("id");
+        let expected = "COUNT(DISTINCT id)";
+        test_expr(actual, expected);
+
+        let actual = col("amount").sum_distinct();
+        let expected = "SUM(DISTINCT amount)";
+        test_expr(actual, expected);
+
+        let actual = sum_distinct("amount");
+        let expected = "SUM(DISTINCT amount)";
+        test_expr(actual, expected);
+
+        let actual = col("grade").avg_distinct();
+        let expected = "AVG(DISTINCT grade)";
+        test_expr(actual, expected);
+
+        let actual = avg_distinct("grade");
+        let expected = "AVG(DISTINCT grade)";
+        test_expr(actual, expected);
+    }
+
+    #[test]
+    fn aggregate_filter() {
+        let to_sql = |expr_node: ExprNode| Expr::try_from(expr_node).map(|expr| expr.to_sql());
+
+        let actual = sum("amount").filter(col("paid").eq(true));
+        let expected = r#"SUM("amount") FILTER (WHERE "paid" = TRUE)"#;
+        assert_eq!(to_sql(actual), Ok(expected.to_owned()));
+
+        let actual = col("id").count_distinct().filter("id > 3");
+        let expected = r#"COUNT(DISTINCT "id") FILTER (WHERE "id" > 3)"#;
+        assert_eq!(to_sql(actual), Ok(expected.to_owned()));
+
+        let actual = expr("MAX(score)").filter("grade = 'A'");
+        let expected = r#"MAX("score") FILTER (WHERE "grade" = 'A')"#;
+        assert_eq!(to_sql(actual), Ok(expected.to_owned()));
+
+        let actual = col("amount").filter("paid");
+        let expected = TranslateError::FilterOnNonAggregateFunction("amount".to_owned());
+        assert_eq!(Expr::try_from(actual), Err(expected.into()));
     }
 }
@@ -152,6 +152,7 @@ mod test {
                 },
             };
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -163,10 +164,12 @@ mod test {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             let query = Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
@@ -22,12 +22,15 @@ pub use {
 
 use {
     crate::{
-        ast::{Aggregate, AstLiteral, BinaryOperator, Expr, Function, Query, UnaryOperator},
+        ast::{
+            Aggregate, AstLiteral, BinaryOperator, Expr, Function, Query, ToSqlUnquoted,
+            UnaryOperator,
+        },
         ast_builder::QueryNode,
         parse_sql::{parse_comma_separated_exprs, parse_expr, parse_query},
         prelude::DataType,
         result::{Error, Result},
-        translate::{translate_expr, translate_query},
+        translate::{translate_expr, translate_query, TranslateError},
     },
     aggregate::AggregateNode,
     bigdecimal::BigDecimal,
@@ -83,6 +86,10 @@ pub enum ExprNode<'a> {
     Nested(Box<ExprNode<'a>>),
     Function(Box<FunctionNode<'a>>),
     Aggregate(Box<AggregateNode<'a>>),
+    AggregateFilter {
+        aggregate: Box<ExprNode<'a>>,
+        filter: Box<ExprNode<'a>>,
+    },
     Exists {
         subquery: Box<QueryNode<'a>>,
         negated: bool,
@@ -243,6 +250,15 @@ impl<'a> TryFrom<ExprNode<'a>> for Expr {
             ExprNode::Aggregate(aggr_expr) => Aggregate::try_from(*aggr_expr)
                 .map(Box::new)
                 .map(Expr::Aggregate),
+            ExprNode::AggregateFilter { aggregate, filter } => match Expr::try_from(*aggregate)? {
+                Expr::Aggregate(aggregate) => Ok(Expr::Aggregate(Box::new(Aggregate {
+                    filter: Some(Expr::try_from(*filter)?),
+                    ..*aggregate
+                }))),
+                expr => {
+                    Err(TranslateError::FilterOnNonAggregateFunction(expr.to_sql_unquoted()).into())
+                }
+            },
             ExprNode::Exists { subquery, negated } => Query::try_from(*subquery)
                 .map(Box::new)
                 .map(|subquery| Expr::Exists { subquery, negated }),
@@ -1,37 +1,59 @@
 use {
-    super::Build,
-    crate::{ast::Statement, result::Result},
+    super::{Build, ExprNode},
+    crate::{
+        ast::{Expr, Statement},
+        result::Result,
+    },
 };
 
-use super::OrderByExprNode;
+use super::OrderByExprList;
 
 #[derive(Clone, Debug)]
 pub struct CreateIndexNode<'a> {
     name: String,
     table_name: String,
-    column: OrderByExprNode<'a>,
+    columns: OrderByExprList<'a>,
+    unique: bool,
+    filter_expr: Option<ExprNode<'a>>,
 }
 
 impl<'a> CreateIndexNode<'a> {
-    pub fn new(table_name: String, name: String, column: OrderByExprNode<'a>) -> Self {
+    pub fn new(
+        table_name: String,
+        name: String,
+        columns: OrderByExprList<'a>,
+        unique: bool,
+    ) -> Self {
         Self {
             table_name,
             name,
-            column,
+            columns,
+            unique,
+            filter_expr: None,
         }
     }
+
+    pub fn filter<T: Into<ExprNode<'a>>>(mut self, expr: T) -> Self {
+        self.filter_expr = Some(expr.into());
+
+        self
+    }
 }
 
 impl<'a> Build for CreateIndexNode<'a> {
     fn build(self) -> Result<Statement> {
         let table_name = self.table_name;
         let name = self.name;
-        let column = self.column.try_into()?;
+        let columns = self.columns.try_into()?;
+        let unique = self.unique;
+        let predicate = self.filter_expr.map(Expr::try_from).transpose()?;
 
         Ok(Statement::CreateIndex {
             name,
             table_name,
-            column,
+            columns,
+            unique,
+            predicate,
         })
     }
 }
@@ -70,6 +92,19 @@ mod tests {
         let actual = table("Foo").create_index("nameIndex", "name desc").build();
         let expected = "CREATE INDEX nameIndex ON Foo (name Desc)";
         test(actual, expected);
+
+        let actual = table("Foo")
+            .create_unique_index("idIndex", vec!["id", "name desc"])
+            .build();
+        let expected = "CREATE UNIQUE INDEX idIndex ON Foo (id, name Desc)";
+        test(actual, expected);
+
+        let actual = table("Foo")
+            .create_index("activeIndex", "id")
+            .filter("status = 'active'")
+            .build();
+        let expected = "CREATE INDEX activeIndex ON Foo (id) WHERE status = 'active'";
+        test(actual, expected);
     }
 
     #[test]
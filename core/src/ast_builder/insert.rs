@@ -59,6 +59,8 @@ impl<'a> Build for InsertSourceNode<'a> {
             table_name,
             columns,
             source,
+            on_conflict: None,
+            returning: Vec::new(),
         })
     }
 }
@@ -46,12 +46,14 @@ pub use {
     select::{
         select, values, FilterNode, GroupByNode, HashJoinNode, HavingNode, JoinConstraintNode,
         JoinNode, LimitNode, OffsetLimitNode, OffsetNode, OrderByNode, ProjectNode, SelectNode,
+        SetOperationNode,
     },
     select_item::SelectItemNode,
     select_item_list::SelectItemList,
     show_columns::ShowColumnsNode,
     table_factor::{
-        glue_indexes, glue_objects, glue_table_columns, glue_tables, series, TableFactorNode,
+        glue_indexes, glue_objects, glue_table_columns, glue_table_constraints, glue_tables,
+        glue_views, series, TableFactorNode,
     },
     table_name::table,
     update::UpdateNode,
@@ -71,7 +73,10 @@ pub use {index::CreateIndexNode, index::DropIndexNode};
 
 /// Available aggregate or normal SQL functions
 pub use expr::{
-    aggregate::{avg, count, max, min, stdev, sum, variance, AggregateNode},
+    aggregate::{
+        array_agg, avg, avg_distinct, count, count_distinct, json_object_agg, max, min, stdev,
+        string_agg, sum, sum_distinct, variance, AggregateFunctionNode, AggregateNode,
+    },
     function::{
         abs, acos, ascii, asin, atan, calc_distance, cast, ceil, chr, coalesce, concat, concat_ws,
         cos, degrees, divide, entries, exp, extract, find_idx, floor, format, gcd, generate_uuid,
@@ -26,6 +26,12 @@ impl<'a> From<Vec<&str>> for OrderByExprList<'a> {
     }
 }
 
+impl<'a> From<OrderByExprNode<'a>> for OrderByExprList<'a> {
+    fn from(order_by_expr: OrderByExprNode<'a>) -> Self {
+        OrderByExprList::OrderByExprs(vec![order_by_expr])
+    }
+}
+
 impl<'a> From<ExprNode<'a>> for OrderByExprList<'a> {
     fn from(expr_node: ExprNode<'a>) -> Self {
         OrderByExprList::OrderByExprs(vec![expr_node.into()])
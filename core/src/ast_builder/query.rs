@@ -4,7 +4,7 @@ use {
         table_factor::TableType,
         ExprList, FilterNode, GroupByNode, HashJoinNode, HavingNode, JoinConstraintNode, JoinNode,
         LimitNode, OffsetLimitNode, OffsetNode, OrderByNode, ProjectNode, SelectNode,
-        TableFactorNode,
+        SetOperationNode, TableFactorNode,
     },
     crate::{
         ast::{Expr, Query, SetExpr, Values},
@@ -31,6 +31,7 @@ pub enum QueryNode<'a> {
     FilterNode(FilterNode<'a>),
     ProjectNode(ProjectNode<'a>),
     OrderByNode(OrderByNode<'a>),
+    SetOperationNode(SetOperationNode<'a>),
 }
 
 impl<'a> QueryNode<'a> {
@@ -79,6 +80,8 @@ impl_from_select_nodes!(OffsetNode);
 impl_from_select_nodes!(OffsetLimitNode);
 impl_from_select_nodes!(ProjectNode);
 impl_from_select_nodes!(OrderByNode);
+impl_from_select_nodes!(ValuesNode);
+impl_from_select_nodes!(SetOperationNode);
 
 impl<'a> TryFrom<QueryNode<'a>> for Query {
     type Error = Error;
@@ -95,6 +98,7 @@ impl<'a> TryFrom<QueryNode<'a>> for Query {
                     .collect::<Result<Vec<_>>>()?;
 
                 Ok(Query {
+                    with: None,
                     body: SetExpr::Values(Values(values)),
                     order_by: Vec::new(),
                     limit: None,
@@ -114,6 +118,7 @@ impl<'a> TryFrom<QueryNode<'a>> for Query {
             QueryNode::OffsetLimitNode(node) => node.prebuild(),
             QueryNode::ProjectNode(node) => node.prebuild(),
             QueryNode::OrderByNode(node) => node.prebuild(),
+            QueryNode::SetOperationNode(node) => node.prebuild(),
         }
     }
 }
@@ -128,8 +133,8 @@ mod test {
                 TableFactor, TableWithJoins,
             },
             ast_builder::{
-                col, glue_indexes, glue_objects, glue_table_columns, glue_tables, series, table,
-                test_query, SelectItemList,
+                col, glue_indexes, glue_objects, glue_table_columns, glue_table_constraints,
+                glue_tables, glue_views, series, table, test_query, SelectItemList,
             },
         },
         pretty_assertions::assert_eq,
@@ -173,6 +178,7 @@ mod test {
                 },
             };
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -184,10 +190,12 @@ mod test {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
@@ -255,6 +263,14 @@ mod test {
         let expected = "SELECT * FROM GLUE_TABLE_COLUMNS";
         test_query(actual, expected);
 
+        let actual = glue_table_constraints().select().into();
+        let expected = "SELECT * FROM GLUE_TABLE_CONSTRAINTS";
+        test_query(actual, expected);
+
+        let actual = glue_views().select().into();
+        let expected = "SELECT * FROM GLUE_VIEWS";
+        test_query(actual, expected);
+
         let actual = series("1 + 2").select().into();
         let expected = "SELECT * FROM SERIES(1 + 2)";
         test_query(actual, expected);
@@ -1,11 +1,11 @@
 use {
     super::Prebuild,
     crate::{
-        ast::Select,
+        ast::{Select, SetOperator},
         ast_builder::{
             ExprList, ExprNode, GroupByNode, HashJoinNode, JoinConstraintNode, JoinNode, LimitNode,
             OffsetNode, OrderByExprList, OrderByNode, ProjectNode, QueryNode, SelectItemList,
-            SelectNode, TableFactorNode,
+            SelectNode, SetOperationNode, TableFactorNode,
         },
         result::Result,
     },
@@ -96,6 +96,30 @@ impl<'a> FilterNode<'a> {
     pub fn alias_as(self, table_alias: &'a str) -> TableFactorNode {
         QueryNode::FilterNode(self).alias_as(table_alias)
     }
+
+    pub fn union<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, false, query)
+    }
+
+    pub fn union_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, true, query)
+    }
+
+    pub fn intersect<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, false, query)
+    }
+
+    pub fn intersect_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, true, query)
+    }
+
+    pub fn except<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, false, query)
+    }
+
+    pub fn except_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, true, query)
+    }
 }
 
 impl<'a> Prebuild<Select> for FilterNode<'a> {
@@ -217,6 +241,7 @@ mod tests {
                 },
             };
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -228,10 +253,12 @@ mod tests {
                 },
                 selection: Some(expr("PlayerItem.amount > 10").try_into().unwrap()),
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
@@ -203,6 +203,7 @@ mod tests {
                 },
             };
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -214,10 +215,12 @@ mod tests {
                 },
                 selection: None,
                 group_by: vec![col("PlayerItem.category").try_into().unwrap()],
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
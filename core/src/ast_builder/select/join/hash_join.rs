@@ -74,6 +74,45 @@ impl<'a> HashJoinNode<'a> {
         )
     }
 
+    pub fn right_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Right)
+    }
+
+    pub fn right_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Right,
+        )
+    }
+
+    pub fn full_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Full)
+    }
+
+    pub fn full_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Full,
+        )
+    }
+
+    pub fn cross_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Cross)
+    }
+
+    pub fn cross_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Cross,
+        )
+    }
+
     pub fn project<T: Into<SelectItemList<'a>>>(self, select_items: T) -> ProjectNode<'a> {
         ProjectNode::new(self, select_items)
     }
@@ -176,6 +215,7 @@ mod tests {
                 },
             };
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -187,10 +227,12 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
@@ -225,6 +267,7 @@ mod tests {
                 },
             };
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -236,10 +279,12 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
@@ -273,6 +318,7 @@ mod tests {
             };
 
             let subquery = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -284,14 +330,17 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Derived {
                         subquery: Query {
+                            with: None,
                             body: SetExpr::Select(Box::new(subquery)),
                             order_by: Vec::new(),
                             limit: None,
@@ -306,10 +355,12 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
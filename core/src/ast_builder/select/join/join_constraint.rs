@@ -78,6 +78,45 @@ impl<'a> JoinConstraintNode<'a> {
         )
     }
 
+    pub fn right_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Right)
+    }
+
+    pub fn right_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Right,
+        )
+    }
+
+    pub fn full_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Full)
+    }
+
+    pub fn full_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Full,
+        )
+    }
+
+    pub fn cross_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Cross)
+    }
+
+    pub fn cross_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Cross,
+        )
+    }
+
     pub fn project<T: Into<SelectItemList<'a>>>(self, select_items: T) -> ProjectNode<'a> {
         ProjectNode::new(self, select_items)
     }
@@ -119,12 +158,18 @@ impl<'a> Prebuild<Select> for JoinConstraintNode<'a> {
         select.from.joins.push(Join {
             relation,
             join_operator: match operator_type {
-                JoinOperatorType::Inner => {
+                JoinOperatorType::Inner | JoinOperatorType::Cross => {
                     JoinOperator::Inner(JoinConstraint::On(self.expr.try_into()?))
                 }
                 JoinOperatorType::Left => {
                     JoinOperator::LeftOuter(JoinConstraint::On(self.expr.try_into()?))
                 }
+                JoinOperatorType::Right => {
+                    JoinOperator::RightOuter(JoinConstraint::On(self.expr.try_into()?))
+                }
+                JoinOperatorType::Full => {
+                    JoinOperator::FullOuter(JoinConstraint::On(self.expr.try_into()?))
+                }
             },
             join_executor,
         });
@@ -208,6 +253,7 @@ mod tests {
                 },
             };
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -219,10 +265,12 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
@@ -119,6 +119,45 @@ impl<'a> JoinNode<'a> {
         )
     }
 
+    pub fn right_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Right)
+    }
+
+    pub fn right_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Right,
+        )
+    }
+
+    pub fn full_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Full)
+    }
+
+    pub fn full_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Full,
+        )
+    }
+
+    pub fn cross_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Cross)
+    }
+
+    pub fn cross_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Cross,
+        )
+    }
+
     pub fn hash_executor<T: Into<ExprNode<'a>>, U: Into<ExprNode<'a>>>(
         self,
         key_expr: T,
@@ -366,6 +405,74 @@ mod tests {
         test(actual, expected);
     }
 
+    #[test]
+    fn right_full_join() {
+        // select node -> right join node -> join constraint node
+        let actual = table("player")
+            .select()
+            .right_join("item")
+            .on("player.id = item.id")
+            .project(vec!["player.id", "item.id"])
+            .build();
+        let expected = "
+            SELECT player.id, item.id
+            FROM player
+            RIGHT JOIN item
+            ON player.id = item.id
+        ";
+        test(actual, expected);
+
+        // select node -> full join node -> join constraint node -> right join node
+        let actual = table("Item")
+            .select()
+            .full_join_as("Player", "p1")
+            .on("p1.id = Item.player_id")
+            .right_join_as("Player", "p2")
+            .on("p2.id = Item.player_id")
+            .filter("p1.id = 1")
+            .build();
+        let expected = "
+            SELECT * FROM Item
+            FULL JOIN Player p1 ON p1.id = Item.player_id
+            RIGHT JOIN Player p2 ON p2.id = Item.player_id
+            WHERE p1.id = 1;
+        ";
+        test(actual, expected);
+
+        // select node -> full join node -> build
+        let actual = table("Foo").select().full_join("Bar").build();
+        let expected = "SELECT * FROM Foo FULL JOIN Bar";
+        test(actual, expected);
+    }
+
+    #[test]
+    fn cross_join() {
+        // select node -> cross join node -> cross join node
+        let actual = table("Foo")
+            .select()
+            .cross_join("Bar")
+            .cross_join_as("Baz", "B")
+            .build();
+        let expected = "
+            SELECT * FROM Foo
+            CROSS JOIN Bar
+            CROSS JOIN Baz B
+            ";
+        test(actual, expected);
+
+        // select node -> cross join node -> project node
+        let actual = table("Orders")
+            .select()
+            .cross_join("Customers")
+            .project(vec!["Orders.OrderID", "Customers.CustomerName"])
+            .build();
+        let expected = "
+            SELECT Orders.OrderID, Customers.CustomerName
+            FROM Orders CROSS JOIN Customers
+        ";
+        test(actual, expected);
+    }
+
     #[test]
     fn join_join() {
         // join - join
@@ -590,6 +697,7 @@ mod tests {
                 },
             };
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -601,10 +709,12 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
@@ -5,7 +5,7 @@ use {
         ast_builder::{
             ExprNode, FilterNode, GroupByNode, HashJoinNode, HavingNode, JoinConstraintNode,
             JoinNode, LimitNode, OffsetNode, OrderByExprList, ProjectNode, QueryNode, SelectNode,
-            TableFactorNode,
+            SetOperationNode, TableFactorNode,
         },
         result::Result,
     },
@@ -22,6 +22,7 @@ pub enum PrevNode<'a> {
     HashJoin(Box<HashJoinNode<'a>>),
     ProjectNode(Box<ProjectNode<'a>>),
     Values(ValuesNode<'a>),
+    SetOperation(Box<SetOperationNode<'a>>),
 }
 
 impl<'a> Prebuild<Query> for PrevNode<'a> {
@@ -36,6 +37,7 @@ impl<'a> Prebuild<Query> for PrevNode<'a> {
             Self::HashJoin(node) => node.prebuild(),
             Self::ProjectNode(node) => node.prebuild(),
             Self::Values(node) => node.prebuild(),
+            Self::SetOperation(node) => node.prebuild(),
         }
     }
 }
@@ -94,6 +96,12 @@ impl<'a> From<ValuesNode<'a>> for PrevNode<'a> {
     }
 }
 
+impl<'a> From<SetOperationNode<'a>> for PrevNode<'a> {
+    fn from(node: SetOperationNode<'a>) -> Self {
+        PrevNode::SetOperation(Box::new(node))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct OrderByNode<'a> {
     prev_node: PrevNode<'a>,
@@ -272,6 +280,7 @@ mod tests {
                 },
             };
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("*").try_into().unwrap(),
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -283,10 +292,12 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: OrderByExprList::from("Player.score DESC")
                     .try_into()
@@ -1,11 +1,11 @@
 use {
     super::Prebuild,
     crate::{
-        ast::Select,
+        ast::{Distinct, Select, SetOperator},
         ast_builder::{
-            ExprNode, FilterNode, GroupByNode, HashJoinNode, HavingNode, JoinConstraintNode,
-            JoinNode, LimitNode, OffsetNode, OrderByExprList, OrderByNode, QueryNode,
-            SelectItemList, SelectNode, TableFactorNode,
+            ExprList, ExprNode, FilterNode, GroupByNode, HashJoinNode, HavingNode,
+            JoinConstraintNode, JoinNode, LimitNode, OffsetNode, OrderByExprList, OrderByNode,
+            QueryNode, SelectItemList, SelectNode, SetOperationNode, TableFactorNode,
         },
         result::Result,
     },
@@ -18,7 +18,7 @@ pub enum PrevNode<'a> {
     Having(HavingNode<'a>),
     Join(Box<JoinNode<'a>>),
     JoinConstraint(Box<JoinConstraintNode<'a>>),
-    HashJoin(HashJoinNode<'a>),
+    HashJoin(Box<HashJoinNode<'a>>),
     Filter(FilterNode<'a>),
 }
 
@@ -68,7 +68,7 @@ impl<'a> From<JoinConstraintNode<'a>> for PrevNode<'a> {
 
 impl<'a> From<HashJoinNode<'a>> for PrevNode<'a> {
     fn from(node: HashJoinNode<'a>) -> Self {
-        PrevNode::HashJoin(node)
+        PrevNode::HashJoin(Box::new(node))
     }
 }
 
@@ -78,10 +78,17 @@ impl<'a> From<FilterNode<'a>> for PrevNode<'a> {
     }
 }
 
+#[derive(Clone, Debug)]
+enum DistinctNode<'a> {
+    Distinct,
+    On(ExprList<'a>),
+}
+
 #[derive(Clone, Debug)]
 pub struct ProjectNode<'a> {
     prev_node: PrevNode<'a>,
     select_items_list: Vec<SelectItemList<'a>>,
+    distinct: Option<DistinctNode<'a>>,
 }
 
 impl<'a> ProjectNode<'a> {
@@ -92,9 +99,22 @@ impl<'a> ProjectNode<'a> {
         Self {
             prev_node: prev_node.into(),
             select_items_list: vec![select_items.into()],
+            distinct: None,
         }
     }
 
+    pub fn distinct(mut self) -> Self {
+        self.distinct = Some(DistinctNode::Distinct);
+
+        self
+    }
+
+    pub fn distinct_on<T: Into<ExprList<'a>>>(mut self, expr_list: T) -> Self {
+        self.distinct = Some(DistinctNode::On(expr_list.into()));
+
+        self
+    }
+
     pub fn project<T: Into<SelectItemList<'a>>>(mut self, select_items: T) -> Self {
         self.select_items_list.push(select_items.into());
 
@@ -116,6 +136,30 @@ impl<'a> ProjectNode<'a> {
     pub fn limit<T: Into<ExprNode<'a>>>(self, expr: T) -> LimitNode<'a> {
         LimitNode::new(self, expr)
     }
+
+    pub fn union<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, false, query)
+    }
+
+    pub fn union_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, true, query)
+    }
+
+    pub fn intersect<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, false, query)
+    }
+
+    pub fn intersect_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, true, query)
+    }
+
+    pub fn except<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, false, query)
+    }
+
+    pub fn except_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, true, query)
+    }
 }
 
 impl<'a> Prebuild<Select> for ProjectNode<'a> {
@@ -129,6 +173,11 @@ impl<'a> Prebuild<Select> for ProjectNode<'a> {
             .into_iter()
             .flatten()
             .collect::<Vec<_>>();
+        query.distinct = match self.distinct {
+            Some(DistinctNode::Distinct) => Some(Distinct::Distinct),
+            Some(DistinctNode::On(expr_list)) => Some(Distinct::On(expr_list.try_into()?)),
+            None => None,
+        };
 
         Ok(query)
     }
@@ -187,6 +236,33 @@ mod tests {
         test(actual, expected);
     }
 
+    #[test]
+    fn distinct() {
+        // project node -> distinct -> build
+        let actual = table("Foo").select().project("name").distinct().build();
+        let expected = "SELECT DISTINCT name FROM Foo";
+        test(actual, expected);
+
+        // project node -> distinct on -> build
+        let actual = table("Foo")
+            .select()
+            .project("name, age")
+            .distinct_on("name")
+            .order_by("name, age DESC")
+            .build();
+        let expected = "SELECT DISTINCT ON (name) name, age FROM Foo ORDER BY name, age DESC";
+        test(actual, expected);
+
+        // project node -> distinct on multiple exprs -> build
+        let actual = table("Bar")
+            .select()
+            .project("city, age")
+            .distinct_on(vec!["city", "age % 10"])
+            .build();
+        let expected = "SELECT DISTINCT ON (city, age % 10) city, age FROM Bar";
+        test(actual, expected);
+    }
+
     #[test]
     fn prev_nodes() {
         // select node -> project node -> build
@@ -248,6 +324,7 @@ mod tests {
                 },
             };
             let select = Select {
+                distinct: None,
                 projection: SelectItemList::from("Player.name, PlayerItem.name")
                     .try_into()
                     .unwrap(),
@@ -261,10 +338,12 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
@@ -2,12 +2,13 @@ use {
     super::{join::JoinOperatorType, Prebuild},
     crate::{
         ast::{
-            AstLiteral, Expr, Query, Select, SelectItem, TableAlias, TableFactor, TableWithJoins,
+            AstLiteral, Expr, Query, Select, SelectItem, SetOperator, TableAlias, TableFactor,
+            TableWithJoins,
         },
         ast_builder::{
             table_factor::TableType, ExprList, ExprNode, FilterNode, GroupByNode, JoinNode,
             LimitNode, OffsetNode, OrderByExprList, OrderByNode, ProjectNode, QueryNode,
-            SelectItemList, TableFactorNode,
+            SelectItemList, SetOperationNode, TableFactorNode,
         },
         result::Result,
         translate::alias_or_name,
@@ -74,9 +75,72 @@ impl<'a> SelectNode<'a> {
         )
     }
 
+    pub fn right_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Right)
+    }
+
+    pub fn right_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Right,
+        )
+    }
+
+    pub fn full_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Full)
+    }
+
+    pub fn full_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Full,
+        )
+    }
+
+    pub fn cross_join(self, table_name: &str) -> JoinNode<'a> {
+        JoinNode::new(self, table_name.to_owned(), None, JoinOperatorType::Cross)
+    }
+
+    pub fn cross_join_as(self, table_name: &str, alias: &str) -> JoinNode<'a> {
+        JoinNode::new(
+            self,
+            table_name.to_owned(),
+            Some(alias.to_owned()),
+            JoinOperatorType::Cross,
+        )
+    }
+
     pub fn alias_as(self, table_alias: &'a str) -> TableFactorNode {
         QueryNode::SelectNode(self).alias_as(table_alias)
     }
+
+    pub fn union<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, false, query)
+    }
+
+    pub fn union_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, true, query)
+    }
+
+    pub fn intersect<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, false, query)
+    }
+
+    pub fn intersect_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, true, query)
+    }
+
+    pub fn except<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, false, query)
+    }
+
+    pub fn except_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, true, query)
+    }
 }
 
 impl<'a> Prebuild<Select> for SelectNode<'a> {
@@ -115,10 +179,12 @@ impl<'a> Prebuild<Select> for SelectNode<'a> {
         };
 
         Ok(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from,
             selection: None,
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         })
     }
@@ -0,0 +1,180 @@
+use {
+    super::Prebuild,
+    crate::{
+        ast::{Query, SetExpr, SetOperator},
+        ast_builder::{
+            ExprNode, LimitNode, OffsetNode, OrderByExprList, OrderByNode, QueryNode,
+            TableFactorNode,
+        },
+        result::Result,
+    },
+};
+
+#[derive(Clone, Debug)]
+pub struct SetOperationNode<'a> {
+    left: Box<QueryNode<'a>>,
+    op: SetOperator,
+    all: bool,
+    right: Box<QueryNode<'a>>,
+}
+
+impl<'a> SetOperationNode<'a> {
+    pub fn new<L: Into<QueryNode<'a>>, R: Into<QueryNode<'a>>>(
+        left: L,
+        op: SetOperator,
+        all: bool,
+        right: R,
+    ) -> Self {
+        Self {
+            left: Box::new(left.into()),
+            op,
+            all,
+            right: Box::new(right.into()),
+        }
+    }
+
+    pub fn union<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, false, query)
+    }
+
+    pub fn union_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, true, query)
+    }
+
+    pub fn intersect<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, false, query)
+    }
+
+    pub fn intersect_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, true, query)
+    }
+
+    pub fn except<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, false, query)
+    }
+
+    pub fn except_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, true, query)
+    }
+
+    pub fn order_by<T: Into<OrderByExprList<'a>>>(self, order_by_exprs: T) -> OrderByNode<'a> {
+        OrderByNode::new(self, order_by_exprs)
+    }
+
+    pub fn offset<T: Into<ExprNode<'a>>>(self, expr: T) -> OffsetNode<'a> {
+        OffsetNode::new(self, expr)
+    }
+
+    pub fn limit<T: Into<ExprNode<'a>>>(self, expr: T) -> LimitNode<'a> {
+        LimitNode::new(self, expr)
+    }
+
+    pub fn alias_as(self, table_alias: &'a str) -> TableFactorNode {
+        QueryNode::SetOperationNode(self).alias_as(table_alias)
+    }
+}
+
+impl<'a> Prebuild<Query> for SetOperationNode<'a> {
+    fn prebuild(self) -> Result<Query> {
+        let left = Query::try_from(*self.left)?.body;
+        let right = Query::try_from(*self.right)?.body;
+        let body = SetExpr::SetOperation {
+            op: self.op,
+            all: self.all,
+            left: Box::new(left),
+            right: Box::new(right),
+        };
+
+        Ok(Query {
+            with: None,
+            body,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast_builder::{table, test, values, Build};
+
+    #[test]
+    fn set_operation() {
+        // project node -> union -> build
+        let actual = table("Foo")
+            .select()
+            .project("id")
+            .union(table("Bar").select().project("id"))
+            .build();
+        let expected = "SELECT id FROM Foo UNION SELECT id FROM Bar";
+        test(actual, expected);
+
+        // select node -> union all -> build
+        let actual = table("Foo")
+            .select()
+            .union_all(table("Bar").select())
+            .build();
+        let expected = "SELECT * FROM Foo UNION ALL SELECT * FROM Bar";
+        test(actual, expected);
+
+        // filter node -> intersect -> build
+        let actual = table("Foo")
+            .select()
+            .filter("id > 1")
+            .intersect("SELECT * FROM Bar")
+            .build();
+        let expected = "SELECT * FROM Foo WHERE id > 1 INTERSECT SELECT * FROM Bar";
+        test(actual, expected);
+
+        // values node -> except all -> build
+        let actual = values(vec!["1", "2"])
+            .except_all(table("Bar").select().project("id"))
+            .build();
+        let expected = "VALUES (1), (2) EXCEPT ALL SELECT id FROM Bar";
+        test(actual, expected);
+
+        // set operation node -> set operation node -> order by -> limit -> build
+        let actual = table("Foo")
+            .select()
+            .project("id")
+            .union(table("Bar").select().project("id"))
+            .except(table("Baz").select().project("id"))
+            .order_by("id DESC")
+            .limit(3)
+            .build();
+        let expected = "
+            SELECT id FROM Foo
+            UNION SELECT id FROM Bar
+            EXCEPT SELECT id FROM Baz
+            ORDER BY id DESC
+            LIMIT 3
+        ";
+        test(actual, expected);
+
+        // set operation node -> intersect all -> derived subquery
+        let actual = table("Foo")
+            .select()
+            .project("id")
+            .intersect_all(table("Bar").select().project("id"))
+            .alias_as("Sub")
+            .select()
+            .build();
+        let expected = "
+            SELECT * FROM (
+                SELECT id FROM Foo
+                INTERSECT ALL SELECT id FROM Bar
+            ) Sub
+        ";
+        test(actual, expected);
+
+        // set operation node -> offset -> build
+        let actual = table("Foo")
+            .select()
+            .union(table("Bar").select())
+            .offset(1)
+            .build();
+        let expected = "SELECT * FROM Foo UNION SELECT * FROM Bar OFFSET 1";
+        test(actual, expected);
+    }
+}
@@ -1,10 +1,10 @@
 use {
     crate::ast_builder::{
         select::Prebuild, ExprList, ExprNode, LimitNode, OffsetNode, OrderByExprList, OrderByNode,
-        QueryNode, TableFactorNode,
+        QueryNode, SetOperationNode, TableFactorNode,
     },
     crate::{
-        ast::{Expr, Query, SetExpr, Values},
+        ast::{Expr, Query, SetExpr, SetOperator, Values},
         result::Result,
     },
 };
@@ -30,6 +30,30 @@ impl<'a> ValuesNode<'a> {
     pub fn alias_as(self, table_alias: &'a str) -> TableFactorNode {
         QueryNode::ValuesNode(self).alias_as(table_alias)
     }
+
+    pub fn union<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, false, query)
+    }
+
+    pub fn union_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, true, query)
+    }
+
+    pub fn intersect<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, false, query)
+    }
+
+    pub fn intersect_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, true, query)
+    }
+
+    pub fn except<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, false, query)
+    }
+
+    pub fn except_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, true, query)
+    }
 }
 
 impl<'a> Prebuild<Query> for ValuesNode<'a> {
@@ -43,6 +67,7 @@ impl<'a> Prebuild<Query> for ValuesNode<'a> {
         let body = SetExpr::Values(Values(values));
 
         Ok(Query {
+            with: None,
             body,
             order_by: Vec::new(),
             limit: None,
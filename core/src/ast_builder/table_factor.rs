@@ -59,6 +59,22 @@ pub fn glue_table_columns() -> TableFactorNode<'static> {
     }
 }
 
+pub fn glue_table_constraints() -> TableFactorNode<'static> {
+    TableFactorNode {
+        table_name: "GLUE_TABLE_CONSTRAINTS".to_owned(),
+        table_type: TableType::Dictionary(Dictionary::GlueTableConstraints),
+        table_alias: None,
+    }
+}
+
+pub fn glue_views() -> TableFactorNode<'static> {
+    TableFactorNode {
+        table_name: "GLUE_VIEWS".to_owned(),
+        table_type: TableType::Dictionary(Dictionary::GlueViews),
+        table_alias: None,
+    }
+}
+
 pub fn series<'a, T: Into<ExprNode<'a>>>(args: T) -> TableFactorNode<'a> {
     TableFactorNode {
         table_name: "SERIES".to_owned(),
@@ -1,6 +1,6 @@
 use super::{
     table_factor::TableType, AlterTableNode, CreateIndexNode, CreateTableNode, DeleteNode,
-    DropIndexNode, DropTableNode, InsertNode, OrderByExprNode, SelectNode, ShowColumnsNode,
+    DropIndexNode, DropTableNode, InsertNode, OrderByExprList, SelectNode, ShowColumnsNode,
     TableFactorNode, UpdateNode,
 };
 
@@ -64,12 +64,20 @@ impl<'a> TableNameNode {
         DropIndexNode::new(self.table_name, name.to_owned())
     }
 
-    pub fn create_index<T: Into<OrderByExprNode<'a>>>(
+    pub fn create_index<T: Into<OrderByExprList<'a>>>(
         self,
         name: &str,
-        column: T,
+        columns: T,
     ) -> CreateIndexNode<'a> {
-        CreateIndexNode::new(self.table_name, name.to_owned(), column.into())
+        CreateIndexNode::new(self.table_name, name.to_owned(), columns.into(), false)
+    }
+
+    pub fn create_unique_index<T: Into<OrderByExprList<'a>>>(
+        self,
+        name: &str,
+        columns: T,
+    ) -> CreateIndexNode<'a> {
+        CreateIndexNode::new(self.table_name, name.to_owned(), columns.into(), true)
     }
 
     pub fn alter_table(self) -> AlterTableNode {
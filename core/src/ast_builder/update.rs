@@ -46,7 +46,9 @@ impl<'a> Build for UpdateNode<'a> {
         Ok(Statement::Update {
             table_name,
             assignments,
+            from: None,
             selection,
+            returning: Vec::new(),
         })
     }
 }
@@ -1,13 +1,23 @@
 use {
-    crate::ast::{Expr, OperateFunctionArg},
+    crate::ast::{FunctionBody, OperateFunctionArg},
     serde::{Deserialize, Serialize},
 };
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FunctionKind {
+    #[default]
+    Function,
+    /// Procedure run by `CALL`, which cannot be used in expressions
+    Procedure,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CustomFunction {
     pub func_name: String,
     pub args: Vec<OperateFunctionArg>,
-    pub body: Expr,
+    pub body: FunctionBody,
+    #[serde(default)]
+    pub kind: FunctionKind,
 }
 
 impl CustomFunction {
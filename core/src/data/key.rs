@@ -50,6 +50,8 @@ pub enum Key {
     Interval(Interval),
     Uuid(u128),
     Inet(IpAddr),
+    /// Composite primary key or combined unique columns
+    Composite(Vec<Key>),
     None,
 }
 
@@ -78,6 +80,7 @@ impl Ord for Key {
             (Key::Interval(l), Key::Interval(r)) => l.cmp(r),
             (Key::Uuid(l), Key::Uuid(r)) => l.cmp(r),
             (Key::Inet(l), Key::Inet(r)) => l.cmp(r),
+            (Key::Composite(l), Key::Composite(r)) => l.cmp(r),
             (Key::None, Key::None) => Ordering::Equal,
             (Key::None, _) => Ordering::Greater,
             (_, Key::None) => Ordering::Less,
@@ -115,6 +118,7 @@ impl PartialOrd for Key {
             (Key::Time(l), Key::Time(r)) => Some(l.cmp(r)),
             (Key::Interval(l), Key::Interval(r)) => l.partial_cmp(r),
             (Key::Uuid(l), Key::Uuid(r)) => Some(l.cmp(r)),
+            (Key::Composite(l), Key::Composite(r)) => l.partial_cmp(r),
             _ => None,
         };
         Some(self.cmp(other))
@@ -191,6 +195,7 @@ impl From<Key> for Value {
             Key::Time(v) => Value::Time(v),
             Key::Interval(v) => Value::Interval(v),
             Key::Uuid(v) => Value::Uuid(v),
+            Key::Composite(keys) => Value::List(keys.into_iter().map(Value::from).collect()),
             Key::None => Value::Null,
         }
     }
@@ -199,7 +204,26 @@ impl From<Key> for Value {
 const VALUE: u8 = 0;
 const NONE: u8 = 1;
 
+/// Each element of a composite key escapes `0x00` as `0x00 0xFF` and ends with `0x00 0x00`,
+/// so the encoded key of a prefix is also a byte prefix and the ordering is preserved.
+const ESCAPE: u8 = 0xFF;
+const TERMINATOR: [u8; 2] = [0, 0];
+
 impl Key {
+    /// Builds the key of a row from the values on `indexes`, composite on several columns.
+    /// Returns `None` when the row has no value on one of `indexes`.
+    pub fn from_row(values: &[Value], indexes: &[usize]) -> Result<Option<Key>> {
+        let keys = indexes
+            .iter()
+            .map(|i| values.get(*i).map(Key::try_from).transpose())
+            .collect::<Result<Option<Vec<_>>>>()?;
+
+        Ok(keys.map(|mut keys| match keys.len() {
+            1 => keys.remove(0),
+            _ => Key::Composite(keys),
+        }))
+    }
+
     /// Key to Big-Endian for comparison purpose
     pub fn to_cmp_be_bytes(&self) -> Result<Vec<u8>> {
         Ok(match self {
@@ -359,6 +383,22 @@ impl Key {
                 .chain(v.to_be_bytes().iter())
                 .copied()
                 .collect::<Vec<_>>(),
+            Key::Composite(keys) => {
+                let mut bytes = Vec::new();
+
+                for key in keys {
+                    for byte in key.to_cmp_be_bytes()? {
+                        match byte {
+                            0 => bytes.extend([0, ESCAPE]),
+                            byte => bytes.push(byte),
+                        }
+                    }
+
+                    bytes.extend(TERMINATOR);
+                }
+
+                bytes
+            }
             Key::None => vec![NONE],
         })
     }
@@ -387,7 +427,8 @@ impl Key {
             Key::Interval(_) => 20,
             Key::Uuid(_) => 21,
             Key::Inet(_) => 22,
-            Key::None => 23,
+            Key::Composite(_) => 23,
+            Key::None => 24,
         }
     }
 }
@@ -578,6 +619,11 @@ mod tests {
 
         assert_eq!(Key::None.partial_cmp(&Key::None), Some(Ordering::Equal));
         assert!(Key::None > Key::I8(100));
+
+        let composite = |keys: &[i64]| Key::Composite(keys.iter().copied().map(Key::I64).collect());
+        assert!(composite(&[1, 2]) > composite(&[1]));
+        assert!(composite(&[1, 2]) < composite(&[2, 0]));
+        assert!(composite(&[1, 2]) == composite(&[1, 2]));
     }
 
     #[test]
@@ -844,6 +890,21 @@ mod tests {
             F64(12.34.into()).to_cmp_be_bytes(),
             Err(KeyError::FloatToCmpBigEndianNotSupported.into())
         );
+
+        let c1 = Composite(vec![I64(1), Str("a".to_owned())]).to_cmp_be_bytes();
+        let c2 = Composite(vec![I64(1), Str("ab".to_owned())]).to_cmp_be_bytes();
+        let c3 = Composite(vec![I64(2), Str("".to_owned())]).to_cmp_be_bytes();
+        let c4 = Composite(vec![Str("a".to_owned()), I64(1)]).to_cmp_be_bytes();
+        let c5 = Composite(vec![Str("a\0".to_owned()), I64(0)]).to_cmp_be_bytes();
+        assert_eq!(cmp(&c1, &c1), Ordering::Equal);
+        assert_eq!(cmp(&c1, &c2), Ordering::Less);
+        assert_eq!(cmp(&c2, &c3), Ordering::Less);
+        assert_eq!(cmp(&c4, &c5), Ordering::Less);
+
+        let prefix = Composite(vec![I64(1)]).to_cmp_be_bytes().unwrap();
+        assert!(c1.as_ref().unwrap().starts_with(&prefix));
+        assert!(c2.as_ref().unwrap().starts_with(&prefix));
+        assert!(!c3.as_ref().unwrap().starts_with(&prefix));
     }
 
     #[test]
@@ -908,6 +969,25 @@ mod tests {
                     .as_u128()
             )
         );
+        assert_eq!(
+            Value::from(Key::Composite(vec![Key::I64(1), Key::Str("a".to_owned())])),
+            Value::List(vec![Value::I64(1), Value::Str("a".to_owned())])
+        );
         matches!(Value::from(Key::None), Value::Null);
     }
+
+    #[test]
+    fn from_row() {
+        let values = [Value::I64(1), Value::Str("a".to_owned()), Value::Null];
+
+        assert_eq!(Key::from_row(&values, &[0]), Ok(Some(Key::I64(1))));
+        assert_eq!(
+            Key::from_row(&values, &[1, 2]),
+            Ok(Some(Key::Composite(vec![
+                Key::Str("a".to_owned()),
+                Key::None
+            ])))
+        );
+        assert_eq!(Key::from_row(&values, &[0, 3]), Ok(None));
+    }
 }
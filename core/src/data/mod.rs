@@ -5,22 +5,28 @@ mod key;
 mod literal;
 mod point;
 mod row;
+mod sequence;
 mod string_ext;
 mod table;
+mod trigger;
+mod view;
 
 pub mod schema;
 pub mod value;
 
 pub use {
     bigdecimal_ext::BigDecimalExt,
-    function::CustomFunction,
+    function::{CustomFunction, FunctionKind},
     interval::{Interval, IntervalError},
     key::{Key, KeyError},
     literal::{Literal, LiteralError},
     point::Point,
     row::{Row, RowError},
     schema::{Schema, SchemaIndex, SchemaIndexOrd, SchemaParseError},
+    sequence::{Sequence, SequenceError},
     string_ext::{StringExt, StringExtError},
     table::{get_alias, get_index, TableError},
+    trigger::Trigger,
     value::{HashMapJsonExt, NumericBinaryOperator, Value, ValueError},
+    view::View,
 };
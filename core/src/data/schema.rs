@@ -1,6 +1,9 @@
 use {
     crate::{
-        ast::{ColumnDef, Expr, OrderByExpr, Statement, ToSql},
+        ast::{
+            Check, ColumnDef, ColumnUniqueOption, Expr, ForeignKey, OrderByExpr, Statement, ToSql,
+            UniqueKey,
+        },
         prelude::{parse, translate},
         result::Result,
     },
@@ -22,27 +25,67 @@ pub enum SchemaIndexOrd {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SchemaIndex {
     pub name: String,
-    pub expr: Expr,
+    /// Key columns, the sort direction of each column applies to composite indexes
+    pub columns: Vec<OrderByExpr>,
     pub order: SchemaIndexOrd,
+    pub unique: bool,
+    /// Only rows matching the predicate are indexed
+    pub predicate: Option<Expr>,
     pub created: NaiveDateTime,
 }
 
+impl SchemaIndex {
+    pub fn exprs(&self) -> impl Iterator<Item = &Expr> {
+        self.columns.iter().map(|OrderByExpr { expr, .. }| expr)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Schema {
     pub table_name: String,
     pub column_defs: Option<Vec<ColumnDef>>,
     pub indexes: Vec<SchemaIndex>,
     pub engine: Option<String>,
+    pub foreign_keys: Vec<ForeignKey>,
+    pub checks: Vec<Check>,
+    pub unique_keys: Vec<UniqueKey>,
 }
 
 impl Schema {
+    /// Column indexes of the primary key, several when the primary key is composite
+    pub fn primary_key_indexes(&self) -> Option<Vec<usize>> {
+        let column_defs = self.column_defs.as_ref()?;
+
+        let column_index = |column_name: &String| {
+            column_defs
+                .iter()
+                .position(|ColumnDef { name, .. }| name == column_name)
+        };
+
+        match self
+            .unique_keys
+            .iter()
+            .find(|UniqueKey { is_primary, .. }| *is_primary)
+        {
+            Some(UniqueKey { columns, .. }) => columns.iter().map(column_index).collect(),
+            None => column_defs
+                .iter()
+                .position(|ColumnDef { unique, .. }| {
+                    unique == &Some(ColumnUniqueOption { is_primary: true })
+                })
+                .map(|i| vec![i]),
+        }
+    }
+
     pub fn to_ddl(&self) -> String {
         let Schema {
             table_name,
             column_defs,
             indexes,
             engine,
-            ..
+            foreign_keys,
+            checks,
+            unique_keys,
         } = self;
 
         let create_table = Statement::CreateTable {
@@ -51,14 +94,30 @@ impl Schema {
             columns: column_defs.to_owned(),
             engine: engine.to_owned(),
             source: None,
+            foreign_keys: foreign_keys.to_owned(),
+            checks: checks.to_owned(),
+            unique_keys: unique_keys.to_owned(),
         }
         .to_sql();
 
-        let create_indexes = indexes.iter().map(|SchemaIndex { name, expr, .. }| {
-            let expr = expr.to_sql();
-
-            format!(r#"CREATE INDEX "{name}" ON "{table_name}" ({expr});"#)
-        });
+        let create_indexes = indexes.iter().map(
+            |SchemaIndex {
+                 name,
+                 columns,
+                 unique,
+                 predicate,
+                 ..
+             }| {
+                Statement::CreateIndex {
+                    name: name.to_owned(),
+                    table_name: table_name.to_owned(),
+                    columns: columns.to_owned(),
+                    unique: *unique,
+                    predicate: predicate.to_owned(),
+                }
+                .to_sql()
+            },
+        );
 
         iter::once(create_table)
             .chain(create_indexes)
@@ -78,17 +137,24 @@ impl Schema {
                 match create_index {
                     Statement::CreateIndex {
                         name,
-                        column: OrderByExpr { expr, asc },
+                        columns,
+                        unique,
+                        predicate,
                         ..
                     } => {
-                        let order = asc
-                            .and_then(|bool| bool.then_some(SchemaIndexOrd::Asc))
-                            .unwrap_or(SchemaIndexOrd::Both);
+                        let order = match columns.as_slice() {
+                            [OrderByExpr {
+                                asc: Some(true), ..
+                            }] => SchemaIndexOrd::Asc,
+                            _ => SchemaIndexOrd::Both,
+                        };
 
                         let index = SchemaIndex {
                             name,
-                            expr,
+                            columns,
                             order,
+                            unique,
+                            predicate,
                             created,
                         };
 
@@ -107,12 +173,18 @@ impl Schema {
                 name,
                 columns,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
                 ..
             } => Ok(Schema {
                 table_name: name,
                 column_defs: columns,
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
             }),
             _ => Err(SchemaParseError::CannotParseDDL.into()),
         }
@@ -130,7 +202,10 @@ mod tests {
     use {
         super::SchemaParseError,
         crate::{
-            ast::{AstLiteral, ColumnDef, ColumnUniqueOption, Expr},
+            ast::{
+                AstLiteral, BinaryOperator, Check, ColumnDef, ColumnUniqueOption, Expr, ForeignKey,
+                OrderByExpr, ReferentialAction, UniqueKey,
+            },
             chrono::Utc,
             data::{Schema, SchemaIndex, SchemaIndexOrd},
             prelude::DataType,
@@ -143,7 +218,9 @@ mod tests {
             column_defs,
             indexes,
             engine,
-            ..
+            foreign_keys,
+            checks,
+            unique_keys,
         } = actual;
 
         let Schema {
@@ -151,12 +228,17 @@ mod tests {
             column_defs: column_defs_e,
             indexes: indexes_e,
             engine: engine_e,
-            ..
+            foreign_keys: foreign_keys_e,
+            checks: checks_e,
+            unique_keys: unique_keys_e,
         } = expected;
 
         assert_eq!(table_name, table_name_e);
         assert_eq!(column_defs, column_defs_e);
         assert_eq!(engine, engine_e);
+        assert_eq!(foreign_keys, foreign_keys_e);
+        assert_eq!(checks, checks_e);
+        assert_eq!(unique_keys, unique_keys_e);
         indexes
             .into_iter()
             .zip(indexes_e)
@@ -165,18 +247,27 @@ mod tests {
 
     fn assert_index(actual: SchemaIndex, expected: SchemaIndex) {
         let SchemaIndex {
-            name, expr, order, ..
+            name,
+            columns,
+            order,
+            unique,
+            predicate,
+            ..
         } = actual;
         let SchemaIndex {
             name: name_e,
-            expr: expr_e,
+            columns: columns_e,
             order: order_e,
+            unique: unique_e,
+            predicate: predicate_e,
             ..
         } = expected;
 
         assert_eq!(name, name_e);
-        assert_eq!(expr, expr_e);
+        assert_eq!(columns, columns_e);
         assert_eq!(order, order_e);
+        assert_eq!(unique, unique_e);
+        assert_eq!(predicate, predicate_e);
     }
 
     #[test]
@@ -201,6 +292,9 @@ mod tests {
             ]),
             indexes: Vec::new(),
             engine: None,
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            unique_keys: Vec::new(),
         };
 
         let ddl = r#"CREATE TABLE "User" ("id" INT NOT NULL, "name" TEXT NULL DEFAULT 'glue');"#;
@@ -214,6 +308,9 @@ mod tests {
             column_defs: None,
             indexes: Vec::new(),
             engine: None,
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            unique_keys: Vec::new(),
         };
         let ddl = r#"CREATE TABLE "Test";"#;
         assert_eq!(schema.to_ddl(), ddl);
@@ -235,8 +332,13 @@ mod tests {
             }]),
             indexes: Vec::new(),
             engine: None,
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            unique_keys: Vec::new(),
         };
 
+        assert_eq!(schema.primary_key_indexes(), Some(vec![0]));
+
         let ddl = r#"CREATE TABLE "User" ("id" INT NOT NULL PRIMARY KEY);"#;
         assert_eq!(schema.to_ddl(), ddl);
 
@@ -244,6 +346,121 @@ mod tests {
         assert_schema(actual, schema);
     }
 
+    #[test]
+    fn table_with_foreign_key() {
+        let schema = Schema {
+            table_name: "Post".to_owned(),
+            column_defs: Some(vec![
+                ColumnDef {
+                    name: "id".to_owned(),
+                    data_type: DataType::Int,
+                    nullable: false,
+                    default: None,
+                    unique: Some(ColumnUniqueOption { is_primary: true }),
+                },
+                ColumnDef {
+                    name: "user_id".to_owned(),
+                    data_type: DataType::Int,
+                    nullable: true,
+                    default: None,
+                    unique: None,
+                },
+            ]),
+            indexes: Vec::new(),
+            engine: None,
+            foreign_keys: vec![ForeignKey {
+                name: "FK_user_id-User_id".to_owned(),
+                referencing_column_name: "user_id".to_owned(),
+                referenced_table_name: "User".to_owned(),
+                referenced_column_name: "id".to_owned(),
+                on_delete: ReferentialAction::Cascade,
+                on_update: ReferentialAction::NoAction,
+            }],
+            checks: Vec::new(),
+            unique_keys: Vec::new(),
+        };
+
+        let ddl = r#"CREATE TABLE "Post" ("id" INT NOT NULL PRIMARY KEY, "user_id" INT NULL, CONSTRAINT "FK_user_id-User_id" FOREIGN KEY ("user_id") REFERENCES "User" ("id") ON DELETE CASCADE ON UPDATE NO ACTION);"#;
+        assert_eq!(schema.to_ddl(), ddl);
+
+        let actual = Schema::from_ddl(ddl).unwrap();
+        assert_schema(actual, schema);
+    }
+
+    #[test]
+    fn table_with_check() {
+        let schema = Schema {
+            table_name: "Product".to_owned(),
+            column_defs: Some(vec![ColumnDef {
+                name: "price".to_owned(),
+                data_type: DataType::Int,
+                nullable: true,
+                default: None,
+                unique: None,
+            }]),
+            indexes: Vec::new(),
+            engine: None,
+            foreign_keys: Vec::new(),
+            checks: vec![Check {
+                name: "CK_price".to_owned(),
+                expr: Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier("price".to_owned())),
+                    op: BinaryOperator::Gt,
+                    right: Box::new(Expr::Literal(AstLiteral::Number(0.into()))),
+                },
+            }],
+            unique_keys: Vec::new(),
+        };
+
+        let ddl = r#"CREATE TABLE "Product" ("price" INT NULL, CONSTRAINT "CK_price" CHECK ("price" > 0));"#;
+        assert_eq!(schema.to_ddl(), ddl);
+
+        let actual = Schema::from_ddl(ddl).unwrap();
+        assert_schema(actual, schema);
+    }
+
+    #[test]
+    fn table_with_composite_primary_key() {
+        let column_def = |name: &str| ColumnDef {
+            name: name.to_owned(),
+            data_type: DataType::Int,
+            nullable: false,
+            default: None,
+            unique: None,
+        };
+        let schema = Schema {
+            table_name: "Order".to_owned(),
+            column_defs: Some(vec![
+                column_def("tenant_id"),
+                column_def("order_id"),
+                column_def("seq"),
+            ]),
+            indexes: Vec::new(),
+            engine: None,
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            unique_keys: vec![
+                UniqueKey {
+                    name: "PK_tenant_id_order_id".to_owned(),
+                    columns: vec!["tenant_id".to_owned(), "order_id".to_owned()],
+                    is_primary: true,
+                },
+                UniqueKey {
+                    name: "UQ_tenant_id_seq".to_owned(),
+                    columns: vec!["tenant_id".to_owned(), "seq".to_owned()],
+                    is_primary: false,
+                },
+            ],
+        };
+        assert_eq!(schema.primary_key_indexes(), Some(vec![0, 1]));
+
+        let ddl = r#"CREATE TABLE "Order" ("tenant_id" INT NOT NULL, "order_id" INT NOT NULL, "seq" INT NOT NULL, CONSTRAINT "PK_tenant_id_order_id" PRIMARY KEY ("tenant_id", "order_id"), CONSTRAINT "UQ_tenant_id_seq" UNIQUE ("tenant_id", "seq"));"#;
+        assert_eq!(schema.to_ddl(), ddl);
+
+        let actual = Schema::from_ddl(ddl).unwrap();
+        assert_schema(actual, schema);
+    }
+
     #[test]
     fn invalid_ddl() {
         // Only Statement::CreateTable is supported
@@ -275,22 +492,55 @@ mod tests {
             indexes: vec![
                 SchemaIndex {
                     name: "User_id".to_owned(),
-                    expr: Expr::Identifier("id".to_owned()),
+                    columns: vec![OrderByExpr {
+                        expr: Expr::Identifier("id".to_owned()),
+                        asc: None,
+                    }],
                     order: SchemaIndexOrd::Both,
+                    unique: false,
+                    predicate: None,
                     created: Utc::now().naive_utc(),
                 },
                 SchemaIndex {
                     name: "User_name".to_owned(),
-                    expr: Expr::Identifier("name".to_owned()),
+                    columns: vec![OrderByExpr {
+                        expr: Expr::Identifier("name".to_owned()),
+                        asc: None,
+                    }],
+                    order: SchemaIndexOrd::Both,
+                    unique: false,
+                    predicate: None,
+                    created: Utc::now().naive_utc(),
+                },
+                SchemaIndex {
+                    name: "User_id_name".to_owned(),
+                    columns: vec![
+                        OrderByExpr {
+                            expr: Expr::Identifier("id".to_owned()),
+                            asc: None,
+                        },
+                        OrderByExpr {
+                            expr: Expr::Identifier("name".to_owned()),
+                            asc: Some(false),
+                        },
+                    ],
                     order: SchemaIndexOrd::Both,
+                    unique: true,
+                    predicate: Some(Expr::IsNotNull(Box::new(Expr::Identifier(
+                        "name".to_owned(),
+                    )))),
                     created: Utc::now().naive_utc(),
                 },
             ],
             engine: None,
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            unique_keys: Vec::new(),
         };
         let ddl = r#"CREATE TABLE "User" ("id" INT NOT NULL, "name" TEXT NOT NULL);
 CREATE INDEX "User_id" ON "User" ("id");
-CREATE INDEX "User_name" ON "User" ("name");"#;
+CREATE INDEX "User_name" ON "User" ("name");
+CREATE UNIQUE INDEX "User_id_name" ON "User" ("id", "name" DESC) WHERE "name" IS NOT NULL;"#;
         assert_eq!(schema.to_ddl(), ddl);
 
         let actual = Schema::from_ddl(ddl).unwrap();
@@ -324,11 +574,19 @@ CREATE TABLE "User" ("id" INT NOT NULL, "name" TEXT NOT NULL);"#;
             ]),
             indexes: vec![SchemaIndex {
                 name: ".".to_owned(),
-                expr: Expr::Identifier(";".to_owned()),
+                columns: vec![OrderByExpr {
+                    expr: Expr::Identifier(";".to_owned()),
+                    asc: None,
+                }],
                 order: SchemaIndexOrd::Both,
+                unique: false,
+                predicate: None,
                 created: Utc::now().naive_utc(),
             }],
             engine: None,
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            unique_keys: Vec::new(),
         };
         let ddl = r#"CREATE TABLE "1" ("2" INT NULL, ";" INT NULL);
 CREATE INDEX "." ON "1" (";");"#;
@@ -0,0 +1,153 @@
+use {
+    crate::result::Result,
+    serde::{Deserialize, Serialize},
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum SequenceError {
+    #[error("sequence already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("sequence not found: {0}")]
+    NotFound(String),
+
+    #[error("sequence {name} is used by the identity column of table {table_name}")]
+    OwnedByTable { name: String, table_name: String },
+
+    #[error("sequence {name} is used by the default of column {table_name}.{column_name}")]
+    UsedByColumnDefault {
+        name: String,
+        table_name: String,
+        column_name: String,
+    },
+
+    #[error("INCREMENT must not be zero: {0}")]
+    ZeroIncrement(String),
+
+    #[error("MINVALUE {min_value} must be less than MAXVALUE {max_value}: {name}")]
+    InvalidRange {
+        name: String,
+        min_value: i64,
+        max_value: i64,
+    },
+
+    #[error("START value {start} is out of the range of sequence {name}")]
+    StartOutOfRange { name: String, start: i64 },
+
+    #[error("sequence {0} reached its maximum value")]
+    MaxValueReached(String),
+
+    #[error("sequence {0} reached its minimum value")]
+    MinValueReached(String),
+
+    #[error("CURRVAL of sequence {0} is not yet defined, call NEXTVAL first")]
+    CurrentValueNotDefined(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sequence {
+    pub name: String,
+    pub increment: i64,
+    pub min_value: i64,
+    pub max_value: i64,
+    pub start: i64,
+    pub cycle: bool,
+    /// Table of the identity column the sequence was created for, dropped along with it
+    pub owned_by: Option<String>,
+    /// Value most recently returned by `NEXTVAL`
+    pub last_value: Option<i64>,
+}
+
+impl Sequence {
+    /// Name of the sequence backing an identity column, as PostgreSQL names it for `SERIAL`.
+    pub fn identity_name(table_name: &str, column_name: &str) -> String {
+        format!("{table_name}_{column_name}_seq")
+    }
+
+    /// Moves the sequence one step forward and returns the new value.
+    pub fn advance(&mut self) -> Result<i64> {
+        let Self {
+            name,
+            increment,
+            min_value,
+            max_value,
+            start,
+            cycle,
+            last_value,
+            ..
+        } = self;
+
+        let value = match last_value.map(|value| value.checked_add(*increment)) {
+            None => *start,
+            Some(Some(value)) if (*min_value..=*max_value).contains(&value) => value,
+            Some(_) if *cycle && *increment > 0 => *min_value,
+            Some(_) if *cycle => *max_value,
+            Some(_) if *increment > 0 => {
+                return Err(SequenceError::MaxValueReached(name.to_owned()).into());
+            }
+            Some(_) => {
+                return Err(SequenceError::MinValueReached(name.to_owned()).into());
+            }
+        };
+
+        *last_value = Some(value);
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Sequence, SequenceError};
+
+    fn sequence(increment: i64, cycle: bool) -> Sequence {
+        Sequence {
+            name: "Seq".to_owned(),
+            increment,
+            min_value: 1,
+            max_value: 5,
+            start: if increment > 0 { 1 } else { 5 },
+            cycle,
+            owned_by: None,
+            last_value: None,
+        }
+    }
+
+    #[test]
+    fn advance() {
+        let mut seq = sequence(2, false);
+        assert_eq!(seq.advance(), Ok(1));
+        assert_eq!(seq.advance(), Ok(3));
+        assert_eq!(seq.advance(), Ok(5));
+        assert_eq!(
+            seq.advance(),
+            Err(SequenceError::MaxValueReached("Seq".to_owned()).into())
+        );
+        assert_eq!(seq.last_value, Some(5));
+
+        let mut seq = sequence(-3, false);
+        assert_eq!(seq.advance(), Ok(5));
+        assert_eq!(seq.advance(), Ok(2));
+        assert_eq!(
+            seq.advance(),
+            Err(SequenceError::MinValueReached("Seq".to_owned()).into())
+        );
+
+        let mut seq = sequence(2, true);
+        seq.last_value = Some(5);
+        assert_eq!(seq.advance(), Ok(1));
+
+        let mut seq = sequence(-2, true);
+        seq.last_value = Some(2);
+        assert_eq!(seq.advance(), Ok(5));
+
+        let mut seq = sequence(1, false);
+        seq.max_value = i64::MAX;
+        seq.last_value = Some(i64::MAX);
+        assert_eq!(
+            seq.advance(),
+            Err(SequenceError::MaxValueReached("Seq".to_owned()).into())
+        );
+    }
+}
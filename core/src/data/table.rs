@@ -31,6 +31,10 @@ pub fn get_alias(table_factor: &TableFactor) -> &String {
         | TableFactor::Dictionary {
             alias: TableAlias { name, .. },
             ..
+        }
+        | TableFactor::Function {
+            alias: TableAlias { name, .. },
+            ..
         } => name,
     }
 }
@@ -40,6 +44,7 @@ pub fn get_index(table_factor: &TableFactor) -> Option<&IndexItem> {
         TableFactor::Table { index, .. } => index.as_ref(),
         TableFactor::Derived { .. }
         | TableFactor::Series { .. }
-        | TableFactor::Dictionary { .. } => None,
+        | TableFactor::Dictionary { .. }
+        | TableFactor::Function { .. } => None,
     }
 }
@@ -0,0 +1,13 @@
+use {
+    crate::ast::{TriggerAction, TriggerEvent, TriggerTiming},
+    serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Trigger {
+    pub name: String,
+    pub table_name: String,
+    pub timing: TriggerTiming,
+    pub events: Vec<TriggerEvent>,
+    pub action: TriggerAction,
+}
@@ -243,7 +243,9 @@ impl Value {
             | (DataType::Timestamp, Value::Timestamp(_))
             | (DataType::Time, Value::Time(_))
             | (DataType::Interval, Value::Interval(_))
-            | (DataType::Uuid, Value::Uuid(_)) => Ok(self.clone()),
+            | (DataType::Uuid, Value::Uuid(_))
+            | (DataType::Map, Value::Map(_))
+            | (DataType::List, Value::List(_)) => Ok(self.clone()),
 
             (_, Value::Null) => Ok(Value::Null),
 
@@ -856,7 +858,7 @@ mod tests {
         crate::data::{point::Point, value::uuid::parse_uuid, NumericBinaryOperator, ValueError},
         chrono::{NaiveDate, NaiveTime},
         rust_decimal::Decimal,
-        std::{net::IpAddr, str::FromStr},
+        std::{collections::HashMap, net::IpAddr, str::FromStr},
     };
 
     fn time(hour: u32, min: u32, sec: u32) -> NaiveTime {
@@ -2066,6 +2068,8 @@ mod tests {
         cast!(F32(1.0_f32)              => Float32        , F32(1.0_f32));
         cast!(F64(1.0)              => Float        , F64(1.0));
         cast!(Value::Uuid(123)      => Uuid         , Value::Uuid(123));
+        cast!(Value::List(vec![I64(1)])    => List    , Value::List(vec![I64(1)]));
+        cast!(Value::Map(HashMap::new())   => Map     , Value::Map(HashMap::new()));
 
         // Boolean
         cast!(Str("TRUE".to_owned())    => Boolean, Bool(true));
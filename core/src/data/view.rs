@@ -0,0 +1,12 @@
+use {
+    crate::ast::Query,
+    serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct View {
+    pub view_name: String,
+    /// Column aliases given by `CREATE VIEW name (a, b) AS ..`, empty if not specified
+    pub columns: Vec<String>,
+    pub query: Query,
+}
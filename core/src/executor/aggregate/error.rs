@@ -4,4 +4,10 @@ use {serde::Serialize, std::fmt::Debug, thiserror::Error};
 pub enum AggregateError {
     #[error("unreachable rc unwrap failure")]
     UnreachableRcUnwrapFailure,
+
+    #[error("argument of GROUPING is not an expression of GROUP BY: {0}")]
+    GroupingArgNotInGroupBy(String),
+
+    #[error("key of JSON_OBJECT_AGG must not be NULL")]
+    NullJsonObjectAggKey,
 }
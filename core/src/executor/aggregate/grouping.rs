@@ -0,0 +1,152 @@
+use {
+    super::AggregateError,
+    crate::{
+        ast::{Expr, GroupingSets, ToSql},
+        data::{Key, Value},
+        executor::context::RowContext,
+        result::Result,
+    },
+    std::rc::Rc,
+};
+
+/// Grouping sets of GROUP BY, every row is grouped once for each of the sets
+pub struct Grouping<'a> {
+    /// Distinct expressions of GROUP BY, evaluated once for each row
+    exprs: Vec<&'a Expr>,
+    /// Whether each of `exprs` is grouped, for each grouping set
+    sets: Vec<Vec<bool>>,
+}
+
+impl<'a> Grouping<'a> {
+    pub fn new(group_by: &'a [Expr], grouping_sets: &'a [GroupingSets]) -> Self {
+        let mut exprs: Vec<&'a Expr> = Vec::new();
+        let mut position = |expr: &'a Expr| match exprs.iter().position(|item| *item == expr) {
+            Some(position) => position,
+            None => {
+                exprs.push(expr);
+                exprs.len() - 1
+            }
+        };
+
+        let group_by = group_by.iter().map(&mut position).collect::<Vec<_>>();
+        let sets = grouping_sets
+            .iter()
+            .fold(vec![group_by], |sets, grouping_sets| {
+                let (GroupingSets::Sets(items)
+                | GroupingSets::Rollup(items)
+                | GroupingSets::Cube(items)) = grouping_sets;
+                let items = items
+                    .iter()
+                    .map(|item| item.iter().map(&mut position).collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+
+                let expanded = match grouping_sets {
+                    GroupingSets::Sets(_) => items,
+                    GroupingSets::Rollup(_) => (0..=items.len())
+                        .rev()
+                        .map(|len| items[..len].concat())
+                        .collect(),
+                    GroupingSets::Cube(_) => (0..1_usize << items.len())
+                        .rev()
+                        .map(|included| {
+                            items
+                                .iter()
+                                .enumerate()
+                                .filter(|(i, _)| included & (1 << (items.len() - 1 - i)) != 0)
+                                .flat_map(|(_, item)| item.iter().copied())
+                                .collect()
+                        })
+                        .collect(),
+                };
+
+                sets.iter()
+                    .flat_map(|set| {
+                        expanded
+                            .iter()
+                            .map(move |item| [set.as_slice(), item].concat())
+                    })
+                    .collect()
+            });
+
+        let sets = sets
+            .into_iter()
+            .map(|set| (0..exprs.len()).map(|i| set.contains(&i)).collect())
+            .collect();
+
+        Self { exprs, sets }
+    }
+
+    pub fn exprs(&self) -> &[&'a Expr] {
+        &self.exprs
+    }
+
+    /// Group keys of the row for each grouping set, from the keys of `exprs`
+    pub fn groups<'b>(&'b self, keys: &'b [Key]) -> impl Iterator<Item = (usize, Vec<Key>)> + 'b {
+        let single = self.sets.len() == 1;
+
+        self.sets.iter().enumerate().map(move |(set, grouped)| {
+            if single {
+                return (set, keys.to_vec());
+            }
+
+            let keys = keys
+                .iter()
+                .zip(grouped)
+                .map(|(key, grouped)| match grouped {
+                    true => key.clone(),
+                    false => Key::None,
+                });
+
+            (
+                set,
+                std::iter::once(Key::I64(set as i64)).chain(keys).collect(),
+            )
+        })
+    }
+
+    /// Context of a group, with the expressions left out of its grouping set read as NULL
+    pub fn context(&self, set: usize, next: Rc<RowContext<'a>>) -> Rc<RowContext<'a>> {
+        let mut columns = Vec::new();
+        let mut exprs = Vec::new();
+
+        for (expr, grouped) in self.exprs.iter().zip(&self.sets[set]) {
+            match (expr, grouped) {
+                (_, true) => {}
+                (Expr::Identifier(ident), false) => {
+                    if let Some(table_alias) = next.get_alias(ident) {
+                        columns.push((table_alias, ident.as_str()));
+                    }
+                }
+                (Expr::CompoundIdentifier { alias, ident }, false) => {
+                    columns.push((alias.as_str(), ident.as_str()));
+                }
+                (_, false) => exprs.push(*expr),
+            }
+        }
+
+        match columns.is_empty() && exprs.is_empty() {
+            true => next,
+            false => Rc::new(RowContext::Grouping {
+                columns,
+                exprs,
+                next,
+            }),
+        }
+    }
+
+    /// Value of `GROUPING(args)`, a bit mask of the args left out of the grouping set,
+    /// with the last arg as the least significant bit
+    pub fn mask(&self, set: usize, args: &[Expr]) -> Result<Value> {
+        args.iter()
+            .try_fold(0, |mask, arg| {
+                let position = self
+                    .exprs
+                    .iter()
+                    .position(|expr| *expr == arg)
+                    .ok_or_else(|| AggregateError::GroupingArgNotInGroupBy(arg.to_sql()))?;
+
+                Ok((mask << 1) | i64::from(!self.sets[set][position]))
+            })
+            .map(Value::I64)
+    }
+}
@@ -1,15 +1,16 @@
 mod error;
+mod grouping;
 mod state;
 
 use {
-    self::state::State,
+    self::{grouping::Grouping, state::State},
     super::{
-        context::{AggregateContext, RowContext},
+        context::{AggregateContext, ExecuteContext, RowContext},
         evaluate::{evaluate, Evaluated},
         filter::check_expr,
     },
     crate::{
-        ast::{Expr, SelectItem},
+        ast::{Expr, GroupingSets, SelectItem},
         data::Key,
         result::Result,
         store::GStore,
@@ -19,12 +20,15 @@ use {
     std::{convert::identity, rc::Rc},
 };
 
-pub use error::AggregateError;
+pub use {error::AggregateError, state::AggrValue};
 
 pub struct Aggregator<'a, T: GStore> {
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     fields: &'a [SelectItem],
     group_by: &'a [Expr],
+    grouping_sets: &'a [GroupingSets],
+    grouping: Rc<Grouping<'a>>,
     having: Option<&'a Expr>,
     filter_context: Option<Rc<RowContext<'a>>>,
 }
@@ -38,15 +42,20 @@ enum S<T1, T2> {
 impl<'a, T: GStore> Aggregator<'a, T> {
     pub fn new(
         storage: &'a T,
+        ctx: &'a ExecuteContext,
         fields: &'a [SelectItem],
         group_by: &'a [Expr],
+        grouping_sets: &'a [GroupingSets],
         having: Option<&'a Expr>,
         filter_context: Option<Rc<RowContext<'a>>>,
     ) -> Self {
         Self {
             storage,
+            ctx,
             fields,
             group_by,
+            grouping_sets,
+            grouping: Rc::new(Grouping::new(group_by, grouping_sets)),
             having,
             filter_context,
         }
@@ -69,8 +78,8 @@ impl<'a, T: GStore> Aggregator<'a, T> {
             .enumerate()
             .map(|(i, row)| row.map(|row| (i, row)))
             .try_fold(
-                State::new(self.storage),
-                |state, (index, project_context)| async move {
+                State::new(self.storage, self.ctx, Rc::clone(&self.grouping)),
+                |mut state, (index, project_context)| async move {
                     let filter_context = match &self.filter_context {
                         Some(filter_context) => Rc::new(RowContext::concat(
                             Rc::clone(&project_context),
@@ -80,34 +89,38 @@ impl<'a, T: GStore> Aggregator<'a, T> {
                     };
                     let filter_context = Some(filter_context);
 
-                    let evaluated: Vec<Evaluated<'_>> = stream::iter(self.group_by.iter())
+                    let evaluated: Vec<Evaluated<'_>> = stream::iter(self.grouping.exprs())
                         .then(|expr| {
                             let filter_clone = filter_context.as_ref().map(Rc::clone);
-                            async move { evaluate(self.storage, filter_clone, None, expr).await }
+                            async move {
+                                evaluate(self.storage, self.ctx, filter_clone, None, expr).await
+                            }
                         })
                         .try_collect::<Vec<_>>()
                         .await?;
 
-                    let group = evaluated
+                    let keys = evaluated
                         .iter()
                         .map(Key::try_from)
                         .collect::<Result<Vec<Key>>>()?;
 
-                    let state = state.apply(index, group, Rc::clone(&project_context));
-                    let state = stream::iter(self.fields)
-                        .fold(Ok(state), |state, field| {
-                            let filter_clone = filter_context.as_ref().map(Rc::clone);
+                    for (set, group) in self.grouping.groups(&keys) {
+                        state = state.apply(index, set, group, Rc::clone(&project_context));
+                        state = stream::iter(self.fields)
+                            .fold(Ok(state), |state, field| {
+                                let filter_clone = filter_context.as_ref().map(Rc::clone);
 
-                            async move {
-                                match field {
-                                    SelectItem::Expr { expr, .. } => {
-                                        aggregate(state?, filter_clone, expr).await
+                                async move {
+                                    match field {
+                                        SelectItem::Expr { expr, .. } => {
+                                            aggregate(state?, filter_clone, expr).await
+                                        }
+                                        _ => state,
                                     }
-                                    _ => state,
                                 }
-                            }
-                        })
-                        .await?;
+                            })
+                            .await?;
+                    }
 
                     Ok(state)
                 },
@@ -122,6 +135,7 @@ impl<'a, T: GStore> Aggregator<'a, T> {
         state: State<'a, T>,
     ) -> Result<impl Stream<Item = Result<AggregateContext<'a>>>> {
         let storage = self.storage;
+        let ctx = self.ctx;
         let filter_context = self.filter_context.as_ref().map(Rc::clone);
         let having = self.having;
         let rows = state
@@ -149,6 +163,7 @@ impl<'a, T: GStore> Aggregator<'a, T> {
 
                             check_expr(
                                 storage,
+                                ctx,
                                 filter_context,
                                 aggregated.as_ref().map(Rc::clone),
                                 having,
@@ -172,18 +187,27 @@ impl<'a, T: GStore> Aggregator<'a, T> {
     }
 
     fn check_aggregate(&self) -> bool {
-        if !self.group_by.is_empty() {
-            return true;
-        }
+        is_aggregate(self.fields, self.group_by, self.grouping_sets)
+    }
+}
 
-        self.fields
-            .iter()
-            .map(|field| match field {
-                SelectItem::Expr { expr, .. } => check(expr),
-                _ => false,
-            })
-            .any(identity)
+/// Whether rows are aggregated, either grouped or projected with aggregate functions
+pub fn is_aggregate(
+    fields: &[SelectItem],
+    group_by: &[Expr],
+    grouping_sets: &[GroupingSets],
+) -> bool {
+    if !group_by.is_empty() || !grouping_sets.is_empty() {
+        return true;
     }
+
+    fields
+        .iter()
+        .map(|field| match field {
+            SelectItem::Expr { expr, .. } => check(expr),
+            _ => false,
+        })
+        .any(identity)
 }
 
 #[async_recursion(?Send)]
@@ -236,6 +260,14 @@ async fn aggregate<'a, T: GStore>(
                 .await
         }
         Expr::Aggregate(aggr) => state.accumulate(filter_context, aggr.as_ref()).await,
+        Expr::Window(window) => {
+            stream::iter(window.as_exprs())
+                .fold(
+                    Ok(state),
+                    |state, expr| async move { aggr(state?, expr).await },
+                )
+                .await
+        }
         _ => Ok(state),
     }
 }
@@ -264,6 +296,7 @@ fn check(expr: &Expr) -> bool {
                     .unwrap_or(false)
         }
         Expr::Aggregate(_) => true,
+        Expr::Window(window) => window.as_exprs().any(check),
         _ => false,
     }
 }
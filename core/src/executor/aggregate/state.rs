@@ -1,23 +1,33 @@
 use {
+    super::{grouping::Grouping, AggregateError},
     crate::{
-        ast::{Aggregate, CountArgExpr, DataType},
+        ast::{Aggregate, AggregateFunction, CountArgExpr, DataType, OrderByExpr},
         data::{Key, Value},
-        executor::{context::RowContext, evaluate::evaluate},
+        executor::{
+            context::{ExecuteContext, RowContext},
+            distinct::distinct_key,
+            evaluate::evaluate,
+            filter::check_expr,
+            sort::sort_by,
+        },
         result::Result,
         store::GStore,
     },
     futures::stream::{self, StreamExt, TryStreamExt},
     im_rc::{HashMap, HashSet},
     itertools::Itertools,
-    std::{cmp::Ordering, rc::Rc},
+    std::{cmp::Ordering, collections::HashMap as StdHashMap, rc::Rc},
     utils::{IndexMap, Vector},
 };
 
 type Group = Rc<Vec<Key>>;
 type ValuesMap<'a> = HashMap<&'a Aggregate, Value>;
 type Context<'a> = Rc<RowContext<'a>>;
+/// Arguments of a row with the keys of the ORDER BY inside the call
+type Item = (Vec<(Key, Option<bool>)>, Vec<Value>);
+type Items = im_rc::Vector<Item>;
 
-enum AggrValue {
+pub enum AggrValue {
     Count {
         wildcard: bool,
         count: i64,
@@ -39,42 +49,90 @@ enum AggrValue {
         sum: Value,
         count: i64,
     },
+    Grouping(Value),
+    ArrayAgg(Items),
+    StringAgg(Items),
+    JsonObjectAgg(Items),
 }
 
 impl AggrValue {
-    fn new(aggr: &Aggregate, value: &Value) -> Result<Self> {
+    pub fn new(aggr: &Aggregate, value: &Value) -> Result<Self> {
         let value = value.clone();
 
-        Ok(match aggr {
-            Aggregate::Count(CountArgExpr::Wildcard) => AggrValue::Count {
+        Ok(match &aggr.func {
+            AggregateFunction::Count(CountArgExpr::Wildcard) => AggrValue::Count {
                 wildcard: true,
                 count: 1,
             },
-            Aggregate::Count(CountArgExpr::Expr(_)) => AggrValue::Count {
+            AggregateFunction::Count(CountArgExpr::Expr(_)) => AggrValue::Count {
                 wildcard: false,
                 count: i64::from(!value.is_null()),
             },
-            Aggregate::Sum(_) => AggrValue::Sum(value),
-            Aggregate::Min(_) => AggrValue::Min(value),
-            Aggregate::Max(_) => AggrValue::Max(value),
-            Aggregate::Avg(_) => AggrValue::Avg {
+            AggregateFunction::Sum(_) => AggrValue::Sum(value),
+            AggregateFunction::Min(_) => AggrValue::Min(value),
+            AggregateFunction::Max(_) => AggrValue::Max(value),
+            AggregateFunction::Avg(_) => AggrValue::Avg {
                 sum: value,
                 count: 1,
             },
-            Aggregate::Variance(_) => AggrValue::Variance {
+            AggregateFunction::Variance(_) => AggrValue::Variance {
                 sum_square: value.multiply(&value)?,
                 sum: value,
                 count: 1,
             },
-            Aggregate::Stdev(_) => AggrValue::Stdev {
+            AggregateFunction::Stdev(_) => AggrValue::Stdev {
                 sum_square: value.multiply(&value)?,
                 sum: value,
                 count: 1,
             },
+            AggregateFunction::Grouping(_) => AggrValue::Grouping(value),
+            AggregateFunction::ArrayAgg(_) => {
+                AggrValue::ArrayAgg(Items::unit((Vec::new(), vec![value])))
+            }
+            AggregateFunction::StringAgg { .. } => {
+                AggrValue::StringAgg(Items::unit((Vec::new(), vec![value])))
+            }
+            AggregateFunction::JsonObjectAgg { .. } => {
+                AggrValue::JsonObjectAgg(Items::unit((Vec::new(), vec![value])))
+            }
         })
     }
 
-    fn accumulate(&self, new_value: &Value) -> Result<Option<Self>> {
+    /// Empty collection of ARRAY_AGG, STRING_AGG or JSON_OBJECT_AGG, `None` for the others
+    fn collection(aggr: &Aggregate) -> Option<Self> {
+        match aggr.func {
+            AggregateFunction::ArrayAgg(_) => Some(AggrValue::ArrayAgg(Items::new())),
+            AggregateFunction::StringAgg { .. } => Some(AggrValue::StringAgg(Items::new())),
+            AggregateFunction::JsonObjectAgg { .. } => Some(AggrValue::JsonObjectAgg(Items::new())),
+            _ => None,
+        }
+    }
+
+    /// Adds the arguments of a row to the collection, `None` unless it is a collection
+    fn push(&self, item: Item) -> Option<Self> {
+        let push = |items: &Items| {
+            let mut items = items.clone();
+            items.push_back(item);
+            items
+        };
+
+        match self {
+            Self::ArrayAgg(items) => Some(Self::ArrayAgg(push(items))),
+            Self::StringAgg(items) => Some(Self::StringAgg(push(items))),
+            Self::JsonObjectAgg(items) => Some(Self::JsonObjectAgg(push(items))),
+            _ => None,
+        }
+    }
+
+    /// Result of an aggregate over no rows, e.g. when none of them pass its FILTER
+    pub fn empty(aggr: &Aggregate) -> Value {
+        match aggr.func {
+            AggregateFunction::Count(_) => Value::I64(0),
+            _ => Value::Null,
+        }
+    }
+
+    pub fn accumulate(&self, new_value: &Value) -> Result<Option<Self>> {
         match self {
             Self::Count { wildcard, count } => {
                 let wildcard = *wildcard;
@@ -119,10 +177,14 @@ impl AggrValue {
                 sum: sum.add(new_value)?,
                 count: count + 1,
             })),
+            Self::Grouping(_) => Ok(None),
+            Self::ArrayAgg(_) | Self::StringAgg(_) | Self::JsonObjectAgg(_) => {
+                Ok(self.push((Vec::new(), vec![new_value.clone()])))
+            }
         }
     }
 
-    async fn export(self) -> Result<Value> {
+    pub async fn export(self) -> Result<Value> {
         let variance = |sum_square: Value, sum: Value, count: i64| async move {
             let count = Value::I64(count);
             let sum_expr1 = sum_square.multiply(&count)?;
@@ -134,7 +196,9 @@ impl AggrValue {
 
         match self {
             Self::Count { count, .. } => Ok(Value::I64(count)),
-            Self::Sum(value) | Self::Min(value) | Self::Max(value) => Ok(value),
+            Self::Sum(value) | Self::Min(value) | Self::Max(value) | Self::Grouping(value) => {
+                Ok(value)
+            }
             Self::Avg { sum, count } => {
                 let sum = sum.cast(&DataType::Float)?;
 
@@ -150,44 +214,99 @@ impl AggrValue {
                 sum,
                 count,
             } => variance(sum_square, sum, count).await?.sqrt(),
+            Self::ArrayAgg(items) => {
+                let values = sorted(items).map(|mut args| args.swap_remove(0)).collect();
+
+                Ok(Value::List(values))
+            }
+            Self::StringAgg(items) => {
+                let mut items = sorted(items).filter(|args| !args[0].is_null());
+                let joined = items.next().map(|args| {
+                    items.fold(String::from(&args[0]), |joined, args| {
+                        let separator = match args.get(1) {
+                            Some(separator) if !separator.is_null() => String::from(separator),
+                            _ => String::new(),
+                        };
+
+                        joined + &separator + &String::from(&args[0])
+                    })
+                });
+
+                Ok(joined.map(Value::Str).unwrap_or(Value::Null))
+            }
+            Self::JsonObjectAgg(items) => sorted(items)
+                .map(|mut args| {
+                    let value = args.pop().unwrap_or(Value::Null);
+                    let key = args.swap_remove(0);
+
+                    match key.is_null() {
+                        true => Err(AggregateError::NullJsonObjectAggKey.into()),
+                        false => Ok((String::from(&key), value)),
+                    }
+                })
+                .collect::<Result<StdHashMap<_, _>>>()
+                .map(Value::Map),
         }
     }
 }
 
+/// Arguments of the collected rows, in the order of the ORDER BY inside the call
+fn sorted(items: Items) -> impl Iterator<Item = Vec<Value>> {
+    let mut items = items.into_iter().collect::<Vec<_>>();
+    items.sort_by(|(keys_a, _), (keys_b, _)| sort_by(keys_a, keys_b));
+
+    items.into_iter().map(|(_, args)| args)
+}
+
 pub struct State<'a, T: GStore> {
     storage: &'a T,
+    ctx: &'a ExecuteContext,
+    grouping: Rc<Grouping<'a>>,
     index: usize,
+    set: usize,
     group: Group,
-    values: IndexMap<(Group, &'a Aggregate), (usize, AggrValue)>,
+    values: IndexMap<(Group, &'a Aggregate), (usize, Option<AggrValue>)>,
+    distinct_values: HashSet<(Group, &'a Aggregate, Key)>,
     groups: HashSet<Group>,
     contexts: Vector<Rc<RowContext<'a>>>,
 }
 
 impl<'a, T: GStore> State<'a, T> {
-    pub fn new(storage: &'a T) -> Self {
+    pub fn new(storage: &'a T, ctx: &'a ExecuteContext, grouping: Rc<Grouping<'a>>) -> Self {
         State {
             storage,
+            ctx,
+            grouping,
             index: 0,
+            set: 0,
             group: Rc::new(vec![Key::None]),
             values: IndexMap::new(),
+            distinct_values: HashSet::new(),
             groups: HashSet::new(),
             contexts: Vector::new(),
         }
     }
 
-    pub fn apply(self, index: usize, group: Vec<Key>, context: Rc<RowContext<'a>>) -> Self {
+    pub fn apply(
+        self,
+        index: usize,
+        set: usize,
+        group: Vec<Key>,
+        context: Rc<RowContext<'a>>,
+    ) -> Self {
         let group = Rc::new(group);
         let (groups, contexts) = if self.groups.contains(&group) {
             (self.groups, self.contexts)
         } else {
             (
                 self.groups.update(Rc::clone(&group)),
-                self.contexts.push(context),
+                self.contexts.push(self.grouping.context(set, context)),
             )
         };
 
         Self {
             index,
+            set,
             group,
             groups,
             contexts,
@@ -195,13 +314,13 @@ impl<'a, T: GStore> State<'a, T> {
         }
     }
 
-    fn update(self, aggr: &'a Aggregate, value: AggrValue) -> Self {
+    fn update(self, aggr: &'a Aggregate, value: Option<AggrValue>) -> Self {
         let key = (Rc::clone(&self.group), aggr);
         let (values, _) = self.values.insert(key, (self.index, value));
         Self { values, ..self }
     }
 
-    fn get(&self, aggr: &'a Aggregate) -> Option<&(usize, AggrValue)> {
+    fn get(&self, aggr: &'a Aggregate) -> Option<&(usize, Option<AggrValue>)> {
         let group = Rc::clone(&self.group);
 
         self.values.get(&(group, aggr))
@@ -236,7 +355,11 @@ impl<'a, T: GStore> State<'a, T> {
             async move {
                 let aggregated = stream::iter(entries)
                     .then(|((_, aggr), (_, aggr_value))| async move {
-                        aggr_value.export().await.map(|value| (aggr, value))
+                        match aggr_value {
+                            Some(aggr_value) => aggr_value.export().await,
+                            None => Ok(AggrValue::empty(aggr)),
+                        }
+                        .map(|value| (aggr, value))
                     })
                     .try_collect::<HashMap<&'a Aggregate, Value>>()
                     .await?;
@@ -253,27 +376,99 @@ impl<'a, T: GStore> State<'a, T> {
         filter_context: Option<Rc<RowContext<'a>>>,
         aggr: &'a Aggregate,
     ) -> Result<State<'a, T>> {
-        let value = match aggr {
-            Aggregate::Count(CountArgExpr::Wildcard) => Value::Null,
-            Aggregate::Count(CountArgExpr::Expr(expr))
-            | Aggregate::Sum(expr)
-            | Aggregate::Min(expr)
-            | Aggregate::Max(expr)
-            | Aggregate::Avg(expr)
-            | Aggregate::Variance(expr)
-            | Aggregate::Stdev(expr) => evaluate(self.storage, filter_context, None, expr)
-                .await?
-                .try_into()?,
+        let accumulated = self.get(aggr);
+
+        if matches!(accumulated, Some((index, _)) if self.index <= *index) {
+            return Ok(self);
+        }
+
+        if let Some(filter) = &aggr.filter {
+            if !check_expr(self.storage, self.ctx, filter_context.clone(), None, filter).await? {
+                return Ok(match accumulated {
+                    Some(_) => self,
+                    None => self.update(aggr, None),
+                });
+            }
+        }
+
+        let value = match &aggr.func {
+            AggregateFunction::Count(CountArgExpr::Wildcard) => Value::Null,
+            AggregateFunction::Count(CountArgExpr::Expr(expr))
+            | AggregateFunction::Sum(expr)
+            | AggregateFunction::Min(expr)
+            | AggregateFunction::Max(expr)
+            | AggregateFunction::Avg(expr)
+            | AggregateFunction::Variance(expr)
+            | AggregateFunction::Stdev(expr)
+            | AggregateFunction::ArrayAgg(expr)
+            | AggregateFunction::StringAgg { expr, .. }
+            | AggregateFunction::JsonObjectAgg { key: expr, .. } => {
+                evaluate(self.storage, self.ctx, filter_context.clone(), None, expr)
+                    .await?
+                    .try_into()?
+            }
+            AggregateFunction::Grouping(exprs) => self.grouping.mask(self.set, exprs)?,
+        };
+
+        let state = match aggr.distinct {
+            true => {
+                let distinct_value = (Rc::clone(&self.group), aggr, distinct_key(&value)?);
+
+                if self.distinct_values.contains(&distinct_value) {
+                    return Ok(self);
+                }
+
+                Self {
+                    distinct_values: self.distinct_values.update(distinct_value),
+                    ..self
+                }
+            }
+            false => self,
         };
-        let aggr_value = match self.get(aggr) {
-            Some((index, _)) if self.index <= *index => None,
-            Some((_, aggr_value)) => aggr_value.accumulate(&value)?,
-            None => Some(AggrValue::new(aggr, &value)?),
+
+        let args = match &aggr.func {
+            AggregateFunction::ArrayAgg(_) => vec![value],
+            AggregateFunction::StringAgg {
+                separator: expr, ..
+            }
+            | AggregateFunction::JsonObjectAgg { value: expr, .. } => {
+                let arg = evaluate(state.storage, state.ctx, filter_context.clone(), None, expr)
+                    .await?
+                    .try_into()?;
+
+                vec![value, arg]
+            }
+            _ => {
+                let aggr_value = match state.get(aggr) {
+                    Some((_, Some(aggr_value))) => aggr_value.accumulate(&value)?,
+                    _ => Some(AggrValue::new(aggr, &value)?),
+                };
+
+                return match aggr_value {
+                    Some(aggr_value) => Ok(state.update(aggr, Some(aggr_value))),
+                    None => Ok(state),
+                };
+            }
+        };
+
+        let mut keys = Vec::with_capacity(aggr.order_by.len());
+        for OrderByExpr { expr, asc } in &aggr.order_by {
+            let value: Value =
+                evaluate(state.storage, state.ctx, filter_context.clone(), None, expr)
+                    .await?
+                    .try_into()?;
+
+            keys.push((Key::try_from(value)?, *asc));
+        }
+
+        let aggr_value = match state.get(aggr) {
+            Some((_, Some(aggr_value))) => aggr_value.push((keys, args)),
+            _ => AggrValue::collection(aggr).and_then(|collection| collection.push((keys, args))),
         };
 
         match aggr_value {
-            Some(aggr_value) => Ok(self.update(aggr, aggr_value)),
-            None => Ok(self),
+            Some(aggr_value) => Ok(state.update(aggr, Some(aggr_value))),
+            None => Ok(state),
         }
     }
 }
@@ -1,10 +1,18 @@
 use {
-    super::{validate, AlterError},
+    super::{
+        fetch_owned_sequences, fetch_table_triggers, sequence::default_sequence, validate,
+        AlterError,
+    },
     crate::{
-        ast::{AlterTableOperation, Expr, Function},
-        data::{Schema, SchemaIndex},
+        ast::{
+            AlterColumnOperation, AlterTableOperation, Check, ColumnDef, ColumnUniqueOption, Expr,
+            ForeignKey, Function, UniqueKey,
+        },
+        data::{Schema, SchemaIndex, Sequence, Trigger},
+        executor::evaluate_stateless,
+        executor::ValidateError,
         result::Result,
-        store::{GStore, GStoreMut},
+        store::{AlterTableError, DataRow, GStore, GStoreMut},
     },
 };
 
@@ -13,10 +21,38 @@ pub async fn alter_table<T: GStore + GStoreMut>(
     table_name: &str,
     operation: &AlterTableOperation,
 ) -> Result<()> {
+    validate_constraints(storage, table_name, operation).await?;
+
     match operation {
         AlterTableOperation::RenameTable {
             table_name: new_table_name,
-        } => storage.rename_schema(table_name, new_table_name).await,
+        } => {
+            let column_defs = fetch_column_defs(storage, table_name).await?;
+            let sequences = fetch_owned_sequences(storage, table_name, &column_defs).await?;
+            let triggers = fetch_table_triggers(storage, table_name).await?;
+
+            storage.rename_schema(table_name, new_table_name).await?;
+
+            for sequence in sequences {
+                let sequence = Sequence {
+                    owned_by: Some(new_table_name.to_owned()),
+                    ..sequence
+                };
+
+                storage.insert_sequence(sequence).await?;
+            }
+
+            for trigger in triggers {
+                let trigger = Trigger {
+                    table_name: new_table_name.to_owned(),
+                    ..trigger
+                };
+
+                storage.insert_trigger(trigger).await?;
+            }
+
+            Ok(())
+        }
         AlterTableOperation::RenameColumn {
             old_column_name,
             new_column_name,
@@ -34,8 +70,12 @@ pub async fn alter_table<T: GStore + GStoreMut>(
             column_name,
             if_exists,
         } => {
-            let indexes = match storage.fetch_schema(table_name).await? {
-                Some(Schema { indexes, .. }) => indexes,
+            let (indexes, column_defs) = match storage.fetch_schema(table_name).await? {
+                Some(Schema {
+                    indexes,
+                    column_defs,
+                    ..
+                }) => (indexes, column_defs.unwrap_or_default()),
                 None => {
                     return Err(AlterError::TableNotFound(table_name.to_owned()).into());
                 }
@@ -43,26 +83,263 @@ pub async fn alter_table<T: GStore + GStoreMut>(
 
             let indexes = indexes
                 .iter()
-                .filter(|SchemaIndex { expr, .. }| find_column(expr, column_name))
+                .filter(|index| {
+                    index
+                        .exprs()
+                        .chain(&index.predicate)
+                        .any(|expr| find_column(expr, column_name))
+                })
                 .map(|SchemaIndex { name, .. }| name);
 
             for index_name in indexes {
                 storage.drop_index(table_name, index_name).await?;
             }
 
+            let column_defs = column_defs
+                .into_iter()
+                .filter(|column_def| &column_def.name == column_name)
+                .collect::<Vec<_>>();
+            let sequences = fetch_owned_sequences(storage, table_name, &column_defs).await?;
+
             storage
                 .drop_column(table_name, column_name, *if_exists)
-                .await
+                .await?;
+
+            for Sequence { name, .. } in sequences {
+                storage.delete_sequence(&name).await?;
+            }
+
+            Ok(())
         }
+        AlterTableOperation::AlterColumn {
+            column_name,
+            operation,
+        } => alter_column(storage, table_name, column_name, operation).await,
     }
 }
 
-fn find_column(expr: &Expr, column_name: &str) -> bool {
+async fn alter_column<T: GStore + GStoreMut>(
+    storage: &mut T,
+    table_name: &str,
+    column_name: &str,
+    operation: &AlterColumnOperation,
+) -> Result<()> {
+    let schema = storage
+        .fetch_schema(table_name)
+        .await?
+        .ok_or_else(|| AlterError::TableNotFound(table_name.to_owned()))?;
+    let column_defs = schema
+        .column_defs
+        .as_ref()
+        .ok_or_else(|| AlterTableError::SchemalessTableFound(table_name.to_owned()))?;
+    let column_index = column_defs
+        .iter()
+        .position(|column_def| column_def.name == column_name)
+        .ok_or_else(|| AlterTableError::AlteringColumnNotFound(column_name.to_owned()))?;
+    let is_primary = schema
+        .primary_key_indexes()
+        .map(|indexes| indexes.contains(&column_index))
+        .unwrap_or(false);
+
+    // indexes on the column are rebuilt with the converted values
+    let indexes = schema
+        .indexes
+        .iter()
+        .filter(|index| {
+            index
+                .exprs()
+                .chain(&index.predicate)
+                .any(|expr| find_column(expr, column_name))
+        })
+        .collect::<Vec<_>>();
+
+    let mut column_def = column_defs[column_index].clone();
+    match operation {
+        AlterColumnOperation::SetDataType(data_type) => {
+            // a lossy conversion could leave duplicates in UNIQUE indexes, unique keys are
+            // checked by validate_constraints
+            let unique = is_primary
+                || matches!(column_def.unique, Some(ColumnUniqueOption { .. }))
+                || indexes.iter().any(|index| index.unique);
+
+            if unique {
+                return Err(
+                    AlterError::DataTypeChangeOnUniqueColumn(column_name.to_owned()).into(),
+                );
+            }
+
+            column_def.data_type = data_type.clone();
+        }
+        AlterColumnOperation::SetDefault(expr) => column_def.default = Some(expr.clone()),
+        AlterColumnOperation::DropDefault => column_def.default = None,
+        AlterColumnOperation::SetNotNull => column_def.nullable = false,
+        AlterColumnOperation::DropNotNull if is_primary => {
+            return Err(AlterError::DropNotNullOnPrimaryKey(column_name.to_owned()).into());
+        }
+        AlterColumnOperation::DropNotNull => column_def.nullable = true,
+    }
+
+    validate(&column_def).await?;
+    if let (Some(expr), None) = (&column_def.default, default_sequence(&column_def)) {
+        evaluate_stateless(None, expr)
+            .await?
+            .try_into_value(&column_def.data_type, column_def.nullable)?;
+    }
+
+    if !matches!(operation, AlterColumnOperation::SetDataType(_)) {
+        return storage.alter_column(table_name, &column_def).await;
+    }
+
+    // rows are converted only after the indexes are dropped, so a value failing to convert
+    // must be found before dropping them
+    if !indexes.is_empty() {
+        for item in storage.scan_data(table_name).await? {
+            let value = match item? {
+                (_, DataRow::Vec(values)) => values[column_index].cast(&column_def.data_type)?,
+                (_, DataRow::Map(_)) => {
+                    return Err(ValidateError::ConflictOnUnexpectedSchemalessRowFound.into());
+                }
+            };
+
+            value.validate_null(column_def.nullable)?;
+        }
+    }
+
+    for SchemaIndex { name, .. } in indexes.iter() {
+        storage.drop_index(table_name, name).await?;
+    }
+
+    storage.alter_column(table_name, &column_def).await?;
+
+    // UNIQUE indexes were rejected above, rebuilding the others cannot fail on duplicates
+    for SchemaIndex {
+        name,
+        columns,
+        unique,
+        predicate,
+        ..
+    } in indexes
+    {
+        storage
+            .create_index(table_name, name, columns, *unique, predicate.as_ref())
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn fetch_column_defs<T: GStore>(storage: &T, table_name: &str) -> Result<Vec<ColumnDef>> {
+    let column_defs = storage
+        .fetch_schema(table_name)
+        .await?
+        .and_then(|schema| schema.column_defs)
+        .unwrap_or_default();
+
+    Ok(column_defs)
+}
+
+async fn validate_constraints<T: GStore>(
+    storage: &T,
+    table_name: &str,
+    operation: &AlterTableOperation,
+) -> Result<()> {
+    let column_name = match operation {
+        AlterTableOperation::RenameTable { .. } => None,
+        AlterTableOperation::RenameColumn {
+            old_column_name, ..
+        } => Some(old_column_name),
+        AlterTableOperation::DropColumn { column_name, .. } => Some(column_name),
+        AlterTableOperation::AlterColumn {
+            column_name,
+            operation: AlterColumnOperation::SetDataType(_),
+        } => Some(column_name),
+        AlterTableOperation::AddColumn { .. } | AlterTableOperation::AlterColumn { .. } => {
+            return Ok(())
+        }
+    };
+
+    for Schema {
+        table_name: referencing_table_name,
+        foreign_keys,
+        checks,
+        unique_keys,
+        ..
+    } in storage.fetch_all_schemas().await?
+    {
+        if let (Some(column_name), true) = (column_name, referencing_table_name == table_name) {
+            if let Some(UniqueKey { name, .. }) = unique_keys
+                .into_iter()
+                .find(|UniqueKey { columns, .. }| columns.contains(column_name))
+            {
+                return Err(AlterError::ColumnReferencedByUniqueKey {
+                    column_name: column_name.to_owned(),
+                    unique_key: name,
+                }
+                .into());
+            }
+
+            if let Some(Check { name, .. }) = checks
+                .into_iter()
+                .find(|Check { expr, .. }| find_column(expr, column_name))
+            {
+                return Err(AlterError::ColumnReferencedByCheck {
+                    column_name: column_name.to_owned(),
+                    check: name,
+                }
+                .into());
+            }
+        }
+
+        for ForeignKey {
+            name,
+            referencing_column_name,
+            referenced_table_name,
+            referenced_column_name,
+            ..
+        } in foreign_keys
+        {
+            match column_name {
+                None if referenced_table_name == table_name => {
+                    return Err(AlterError::TableReferencedByForeignKey {
+                        table_name: table_name.to_owned(),
+                        foreign_key: name,
+                    }
+                    .into());
+                }
+                Some(column_name)
+                    if (referencing_table_name == table_name
+                        && &referencing_column_name == column_name)
+                        || (referenced_table_name == table_name
+                            && &referenced_column_name == column_name) =>
+                {
+                    return Err(AlterError::ColumnReferencedByForeignKey {
+                        column_name: column_name.to_owned(),
+                        foreign_key: name,
+                    }
+                    .into());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn find_column(expr: &Expr, column_name: &str) -> bool {
     let find = |expr| find_column(expr, column_name);
 
     match expr {
         Expr::Identifier(ident) => ident == column_name,
         Expr::Nested(expr) => find(expr),
+        Expr::IsNull(expr) | Expr::IsNotNull(expr) => find(expr),
+        Expr::InList { expr, list, .. } => find(expr) || list.iter().any(find),
+        Expr::Between {
+            expr, low, high, ..
+        } => find(expr) || find(low) || find(high),
+        Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+            find(expr) || find(pattern)
+        }
         Expr::BinaryOp { left, right, .. } => find(left) || find(right),
         Expr::UnaryOp { expr, .. } => find(expr),
         Expr::Function(func) => match func.as_ref() {
@@ -17,6 +17,36 @@ pub enum AlterError {
     #[error("function does not exist: {0}")]
     FunctionNotFound(String),
 
+    // CREATE PROCEDURE, DROP PROCEDURE
+    #[error("procedure already exists: {0}")]
+    ProcedureAlreadyExists(String),
+
+    #[error("procedure does not exist: {0}")]
+    ProcedureNotFound(String),
+
+    // CREATE VIEW, DROP VIEW
+    #[error("view already exists: {0}")]
+    ViewAlreadyExists(String),
+
+    #[error("view does not exist: {0}")]
+    ViewNotFound(String),
+
+    #[error("table '{table_name}' is read by view '{view_name}'")]
+    TableReadByView {
+        table_name: String,
+        view_name: String,
+    },
+
+    // CREATE TRIGGER, DROP TRIGGER
+    #[error("trigger already exists: {0}")]
+    TriggerAlreadyExists(String),
+
+    #[error("trigger does not exist: {0}")]
+    TriggerNotFound(String),
+
+    #[error("trigger {0} can set NEW only BEFORE INSERT or UPDATE")]
+    TriggerSetNewNotBefore(String),
+
     // CREATE INDEX, DROP TABLE
     #[error("table does not exist: {0}")]
     TableNotFound(String),
@@ -24,6 +54,61 @@ pub enum AlterError {
     #[error("CTAS source table does not exist: {0}")]
     CtasSourceTableNotFound(String),
 
+    // validate foreign key
+    #[error("referencing column of foreign key does not exist: {0}")]
+    ReferencingColumnNotFound(String),
+
+    #[error("referenced table of foreign key does not exist: {0}")]
+    ReferencedTableNotFound(String),
+
+    #[error("referenced column of foreign key does not exist: {0}")]
+    ReferencedColumnNotFound(String),
+
+    #[error("referenced column of foreign key must be PRIMARY KEY or UNIQUE: {0}")]
+    ReferencedColumnNotUnique(String),
+
+    #[error("foreign key '{foreign_key}' has mismatched data types: referencing '{referencing:?}', referenced '{referenced:?}'")]
+    ForeignKeyDataTypeMismatch {
+        foreign_key: String,
+        referencing: DataType,
+        referenced: DataType,
+    },
+
+    #[error("foreign key '{0}' cannot SET NULL on NOT NULL column")]
+    SetNullOnNotNullColumn(String),
+
+    #[error("foreign key '{0}' cannot CASCADE or SET NULL on update of a primary key column")]
+    UpdateActionOnPrimaryKeyColumn(String),
+
+    // DROP TABLE, ALTER TABLE
+    #[error("table '{table_name}' is referenced by foreign key '{foreign_key}'")]
+    TableReferencedByForeignKey {
+        table_name: String,
+        foreign_key: String,
+    },
+
+    #[error("column '{column_name}' is used by foreign key '{foreign_key}'")]
+    ColumnReferencedByForeignKey {
+        column_name: String,
+        foreign_key: String,
+    },
+
+    #[error("column '{column_name}' is used by check constraint '{check}'")]
+    ColumnReferencedByCheck { column_name: String, check: String },
+
+    #[error("column '{column_name}' is used by unique key '{unique_key}'")]
+    ColumnReferencedByUniqueKey {
+        column_name: String,
+        unique_key: String,
+    },
+
+    // ALTER COLUMN
+    #[error("data type of PRIMARY KEY, UNIQUE or UNIQUE index column cannot be changed: {0}")]
+    DataTypeChangeOnUniqueColumn(String),
+
+    #[error("NOT NULL of PRIMARY KEY column cannot be dropped: {0}")]
+    DropNotNullOnPrimaryKey(String),
+
     // validate column def
     #[error("column '{0}' of data type '{1:?}' is unsupported for unique constraint")]
     UnsupportedDataTypeForUniqueColumn(String, DataType),
@@ -32,6 +117,9 @@ pub enum AlterError {
     #[error("unsupported index expr: {0:#?}")]
     UnsupportedIndexExpr(Expr),
 
+    #[error("unsupported index predicate: {0:#?}")]
+    UnsupportedIndexPredicate(Expr),
+
     // validate index expr
     #[error("unsupported unnamed argument")]
     UnsupportedUnnamedArg,
@@ -1,8 +1,8 @@
 use {
     super::{validate_arg_names, validate_default_args, AlterError},
     crate::{
-        ast::{Expr, OperateFunctionArg},
-        data::CustomFunction,
+        ast::{FunctionBody, OperateFunctionArg, Procedural},
+        data::{CustomFunction, FunctionKind},
         result::Result,
         store::{GStore, GStoreMut},
     },
@@ -13,39 +13,103 @@ pub async fn insert_function<T: GStore + GStoreMut>(
     func_name: &str,
     args: &Vec<OperateFunctionArg>,
     or_replace: bool,
-    body: &Expr,
+    body: &FunctionBody,
 ) -> Result<()> {
-    validate_arg_names(args)?;
-    validate_default_args(args).await?;
-
-    if storage.fetch_function(func_name).await?.is_none() || or_replace {
-        storage.delete_function(func_name).await?;
-        storage
-            .insert_function(CustomFunction {
-                func_name: func_name.to_owned(),
-                args: args.to_owned(),
-                body: body.to_owned(),
-            })
-            .await?;
-        Ok(())
-    } else {
-        Err(AlterError::FunctionAlreadyExists(func_name.to_owned()).into())
+    let function = CustomFunction {
+        func_name: func_name.to_owned(),
+        args: args.to_owned(),
+        body: body.to_owned(),
+        kind: FunctionKind::Function,
+    };
+
+    insert(storage, function, or_replace).await
+}
+
+pub async fn insert_procedure<T: GStore + GStoreMut>(
+    storage: &mut T,
+    name: &str,
+    args: &Vec<OperateFunctionArg>,
+    or_replace: bool,
+    body: &[Procedural],
+) -> Result<()> {
+    let procedure = CustomFunction {
+        func_name: name.to_owned(),
+        args: args.to_owned(),
+        body: FunctionBody::Block(body.to_vec()),
+        kind: FunctionKind::Procedure,
+    };
+
+    insert(storage, procedure, or_replace).await
+}
+
+/// Functions and procedures share their names, `OR REPLACE` only replaces the same kind
+async fn insert<T: GStore + GStoreMut>(
+    storage: &mut T,
+    function: CustomFunction,
+    or_replace: bool,
+) -> Result<()> {
+    validate_arg_names(&function.args)?;
+    validate_default_args(&function.args).await?;
+
+    let func_name = &function.func_name;
+    let replaceable = match storage.fetch_function(func_name).await? {
+        Some(stored) => or_replace && stored.kind == function.kind,
+        None => true,
+    };
+
+    if !replaceable {
+        return Err(match function.kind {
+            FunctionKind::Function => AlterError::FunctionAlreadyExists(func_name.to_owned()),
+            FunctionKind::Procedure => AlterError::ProcedureAlreadyExists(func_name.to_owned()),
+        }
+        .into());
     }
+
+    storage.delete_function(func_name).await?;
+    storage.insert_function(function).await
 }
 
 pub async fn delete_function<T: GStore + GStoreMut>(
     storage: &mut T,
     func_names: &[String],
     if_exists: bool,
+) -> Result<()> {
+    delete(storage, func_names, if_exists, FunctionKind::Function).await
+}
+
+pub async fn delete_procedure<T: GStore + GStoreMut>(
+    storage: &mut T,
+    names: &[String],
+    if_exists: bool,
+) -> Result<()> {
+    delete(storage, names, if_exists, FunctionKind::Procedure).await
+}
+
+async fn delete<T: GStore + GStoreMut>(
+    storage: &mut T,
+    func_names: &[String],
+    if_exists: bool,
+    kind: FunctionKind,
 ) -> Result<()> {
     for func_name in func_names {
-        let function = storage.fetch_function(func_name).await?;
+        let exists = storage
+            .fetch_function(func_name)
+            .await?
+            .map(|function| function.kind == kind)
+            .unwrap_or(false);
 
-        if !if_exists {
-            function.ok_or_else(|| AlterError::FunctionNotFound(func_name.to_owned()))?;
+        match (exists, if_exists) {
+            (true, _) => storage.delete_function(func_name).await?,
+            (false, true) => {}
+            (false, false) => {
+                return Err(match kind {
+                    FunctionKind::Function => AlterError::FunctionNotFound(func_name.to_owned()),
+                    FunctionKind::Procedure => AlterError::ProcedureNotFound(func_name.to_owned()),
+                }
+                .into())
+            }
         }
-
-        storage.delete_function(func_name).await?;
     }
+
     Ok(())
 }
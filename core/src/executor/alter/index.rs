@@ -3,36 +3,87 @@ use {
     crate::{
         ast::{ColumnDef, Expr, Function, OrderByExpr},
         data::Schema,
+        executor::validate::{
+            evaluate_index_predicate, evaluate_index_values, index_key, ValidateError,
+        },
         result::Result,
-        store::{GStore, GStoreMut},
+        store::{DataRow, GStore, GStoreMut},
     },
+    im_rc::HashSet,
+    std::rc::Rc,
 };
 
 pub async fn create_index<T: GStore + GStoreMut>(
     storage: &mut T,
     table_name: &str,
     index_name: &str,
-    column: &OrderByExpr,
+    columns: &[OrderByExpr],
+    unique: bool,
+    predicate: Option<&Expr>,
 ) -> Result<()> {
-    let expr = &column.expr;
     let Schema { column_defs, .. } = storage
         .fetch_schema(table_name)
         .await?
         .ok_or_else(|| AlterError::TableNotFound(table_name.to_owned()))?;
-    let columns = column_defs
+    let column_defs = column_defs
         .unwrap_or_default()
         .into_iter()
         .map(|ColumnDef { name, .. }| name)
-        .collect::<Vec<_>>();
+        .collect::<Rc<[String]>>();
 
-    let (valid, has_ident) = validate_index_expr(&columns, expr);
-    if !valid {
-        return Err(AlterError::UnsupportedIndexExpr(expr.clone()).into());
-    } else if !has_ident {
-        return Err(AlterError::IdentifierNotFound(expr.clone()).into());
+    for OrderByExpr { expr, .. } in columns {
+        let (valid, has_ident) = validate_index_expr(&column_defs, expr);
+        if !valid {
+            return Err(AlterError::UnsupportedIndexExpr(expr.clone()).into());
+        } else if !has_ident {
+            return Err(AlterError::IdentifierNotFound(expr.clone()).into());
+        }
+    }
+
+    if let Some(predicate) = predicate {
+        if !validate_index_predicate(&column_defs, predicate) {
+            return Err(AlterError::UnsupportedIndexPredicate(predicate.clone()).into());
+        }
     }
 
-    storage.create_index(table_name, index_name, column).await
+    if unique {
+        let rows = storage
+            .scan_data(table_name)
+            .await?
+            .collect::<Result<Vec<_>>>()?;
+        let mut keys = HashSet::new();
+        for (_, row) in rows {
+            let values = match row {
+                DataRow::Vec(values) => values,
+                DataRow::Map(_) => {
+                    return Err(ValidateError::ConflictOnUnexpectedSchemalessRowFound.into());
+                }
+            };
+            if !evaluate_index_predicate(table_name, &column_defs, predicate, &values).await? {
+                continue;
+            }
+
+            let values = evaluate_index_values(table_name, &column_defs, columns, &values).await?;
+            let key = match index_key(&values)? {
+                Some(key) => key,
+                None => continue,
+            };
+
+            if keys.contains(&key) {
+                return Err(ValidateError::DuplicateEntryOnUniqueIndex {
+                    index_name: index_name.to_owned(),
+                    key,
+                }
+                .into());
+            }
+
+            keys.insert(key);
+        }
+    }
+
+    storage
+        .create_index(table_name, index_name, columns, unique, predicate)
+        .await
 }
 
 fn validate_index_expr(columns: &[String], expr: &Expr) -> (bool, bool) {
@@ -56,3 +107,23 @@ fn validate_index_expr(columns: &[String], expr: &Expr) -> (bool, bool) {
         _ => (false, false),
     }
 }
+
+fn validate_index_predicate(columns: &[String], expr: &Expr) -> bool {
+    let validate = |expr| validate_index_predicate(columns, expr);
+
+    match expr {
+        Expr::Nested(expr)
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::UnaryOp { expr, .. } => validate(expr),
+        Expr::BinaryOp { left, right, .. } => validate(left) && validate(right),
+        Expr::InList { expr, list, .. } => validate(expr) && list.iter().all(validate),
+        Expr::Between {
+            expr, low, high, ..
+        } => validate(expr) && validate(low) && validate(high),
+        Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+            validate(expr) && validate(pattern)
+        }
+        _ => validate_index_expr(columns, expr).0,
+    }
+}
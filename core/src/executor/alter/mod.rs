@@ -2,15 +2,25 @@ mod alter_table;
 mod error;
 mod function;
 mod index;
+mod sequence;
 mod table;
+mod trigger;
 mod validate;
+mod view;
 
-use validate::{validate, validate_arg_names, validate_column_names, validate_default_args};
+use {
+    sequence::{create_identity_sequences, default_sequence, fetch_owned_sequences},
+    trigger::fetch_table_triggers,
+    validate::{validate, validate_arg_names, validate_column_names, validate_default_args},
+};
 
 pub use {
-    alter_table::alter_table,
+    alter_table::{alter_table, find_column},
     error::AlterError,
-    function::{delete_function, insert_function},
+    function::{delete_function, delete_procedure, insert_function, insert_procedure},
     index::create_index,
-    table::{create_table, drop_table},
+    sequence::{create_sequence, drop_sequence},
+    table::{create_table, drop_table, TableConstraints},
+    trigger::{create_trigger, drop_trigger, TriggerDefinition},
+    view::{create_view, drop_view},
 };
@@ -0,0 +1,207 @@
+use crate::{
+    ast::{AstLiteral, ColumnDef, DataType, Expr, Function, SequenceOption},
+    data::{Sequence, SequenceError},
+    executor::evaluate_stateless,
+    result::Result,
+    store::{GStore, GStoreMut},
+};
+
+pub async fn create_sequence<T: GStore + GStoreMut>(
+    storage: &mut T,
+    name: &str,
+    options: &[SequenceOption],
+    if_not_exists: bool,
+) -> Result<()> {
+    if storage.fetch_sequence(name).await?.is_some() {
+        return match if_not_exists {
+            true => Ok(()),
+            false => Err(SequenceError::AlreadyExists(name.to_owned()).into()),
+        };
+    }
+
+    let sequence = build_sequence(name, options, None).await?;
+
+    storage.insert_sequence(sequence).await
+}
+
+pub async fn drop_sequence<T: GStore + GStoreMut>(
+    storage: &mut T,
+    names: &[String],
+    if_exists: bool,
+) -> Result<()> {
+    for name in names {
+        let sequence = match storage.fetch_sequence(name).await? {
+            Some(sequence) => sequence,
+            None if if_exists => continue,
+            None => return Err(SequenceError::NotFound(name.to_owned()).into()),
+        };
+
+        if let Some(table_name) = sequence.owned_by {
+            if storage.fetch_schema(&table_name).await?.is_some() {
+                return Err(SequenceError::OwnedByTable {
+                    name: name.to_owned(),
+                    table_name,
+                }
+                .into());
+            }
+        }
+
+        for schema in storage.fetch_all_schemas().await? {
+            let column_def = schema
+                .column_defs
+                .iter()
+                .flatten()
+                .find(|column_def| default_sequence(column_def) == Some(name.as_str()));
+
+            if let Some(column_def) = column_def {
+                return Err(SequenceError::UsedByColumnDefault {
+                    name: name.to_owned(),
+                    table_name: schema.table_name,
+                    column_name: column_def.name.to_owned(),
+                }
+                .into());
+            }
+        }
+
+        storage.delete_sequence(name).await?;
+    }
+
+    Ok(())
+}
+
+/// Creates the sequences of identity columns, which are named by [`Sequence::identity_name`]
+/// and owned by the table. Any other sequence used by a column default must already exist.
+pub async fn create_identity_sequences<T: GStore + GStoreMut>(
+    storage: &mut T,
+    table_name: &str,
+    column_defs: &[ColumnDef],
+) -> Result<()> {
+    for column_def in column_defs {
+        let sequence_name = match default_sequence(column_def) {
+            Some(sequence_name) => sequence_name,
+            None => continue,
+        };
+
+        if storage.fetch_sequence(sequence_name).await?.is_some() {
+            continue;
+        } else if sequence_name != Sequence::identity_name(table_name, &column_def.name) {
+            return Err(SequenceError::NotFound(sequence_name.to_owned()).into());
+        }
+
+        let sequence = build_sequence(sequence_name, &[], Some(table_name)).await?;
+
+        storage.insert_sequence(sequence).await?;
+    }
+
+    Ok(())
+}
+
+/// Sequences owned by the table, found through the defaults of its columns.
+pub async fn fetch_owned_sequences<T: GStore>(
+    storage: &T,
+    table_name: &str,
+    column_defs: &[ColumnDef],
+) -> Result<Vec<Sequence>> {
+    let mut sequences = Vec::new();
+
+    for sequence_name in column_defs.iter().filter_map(default_sequence) {
+        match storage.fetch_sequence(sequence_name).await? {
+            Some(sequence) if sequence.owned_by.as_deref() == Some(table_name) => {
+                sequences.push(sequence);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(sequences)
+}
+
+/// Name of the sequence the column default draws from, if it is `NEXTVAL('<name>')`.
+pub fn default_sequence(column_def: &ColumnDef) -> Option<&str> {
+    match column_def.default.as_ref()? {
+        Expr::Function(func) => match func.as_ref() {
+            Function::Nextval(Expr::Literal(AstLiteral::QuotedString(name))) => Some(name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+async fn build_sequence(
+    name: &str,
+    options: &[SequenceOption],
+    owned_by: Option<&str>,
+) -> Result<Sequence> {
+    async fn evaluate(expr: &Expr) -> Result<i64> {
+        let value = evaluate_stateless(None, expr)
+            .await?
+            .try_into_value(&DataType::Int, false)?;
+
+        i64::try_from(&value)
+    }
+
+    let mut increment = 1;
+    let mut min_value = None;
+    let mut max_value = None;
+    let mut start = None;
+    let mut cycle = false;
+
+    for option in options {
+        match option {
+            SequenceOption::IncrementBy(expr) => {
+                increment = evaluate(expr).await?;
+            }
+            SequenceOption::MinValue(Some(expr)) => {
+                min_value = Some(evaluate(expr).await?);
+            }
+            SequenceOption::MaxValue(Some(expr)) => {
+                max_value = Some(evaluate(expr).await?);
+            }
+            SequenceOption::MinValue(None) | SequenceOption::MaxValue(None) => {}
+            SequenceOption::StartWith(expr) => {
+                start = Some(evaluate(expr).await?);
+            }
+            SequenceOption::Cycle(value) => {
+                cycle = *value;
+            }
+        }
+    }
+
+    if increment == 0 {
+        return Err(SequenceError::ZeroIncrement(name.to_owned()).into());
+    }
+
+    let ascending = increment > 0;
+    let min_value = min_value.unwrap_or(if ascending { 1 } else { i64::MIN });
+    let max_value = max_value.unwrap_or(if ascending { i64::MAX } else { -1 });
+
+    if min_value >= max_value {
+        return Err(SequenceError::InvalidRange {
+            name: name.to_owned(),
+            min_value,
+            max_value,
+        }
+        .into());
+    }
+
+    let start = start.unwrap_or(if ascending { min_value } else { max_value });
+
+    if !(min_value..=max_value).contains(&start) {
+        return Err(SequenceError::StartOutOfRange {
+            name: name.to_owned(),
+            start,
+        }
+        .into());
+    }
+
+    Ok(Sequence {
+        name: name.to_owned(),
+        increment,
+        min_value,
+        max_value,
+        start,
+        cycle,
+        owned_by: owned_by.map(str::to_owned),
+        last_value: None,
+    })
+}
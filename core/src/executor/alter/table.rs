@@ -1,9 +1,21 @@
 use {
-    super::{validate, validate_column_names, AlterError},
+    super::{
+        create_identity_sequences, fetch_owned_sequences, fetch_table_triggers, validate,
+        validate_column_names, AlterError,
+    },
     crate::{
-        ast::{ColumnDef, Query, SetExpr, TableFactor, Values},
-        data::{Schema, TableError},
-        executor::{evaluate_stateless, select::select},
+        ast::{
+            Check, ColumnDef, ColumnUniqueOption, ForeignKey, Query, ReferentialAction, SetExpr,
+            TableFactor, UniqueKey, Values,
+        },
+        data::{Schema, Sequence, TableError, Trigger, View},
+        executor::{
+            context::ExecuteContext,
+            evaluate::evaluate_stateless_with,
+            select::{leftmost_operand, select},
+            validate::validate_check_exprs,
+        },
+        plan::read_table_names,
         prelude::{DataType, Value},
         result::{Error, Result},
         store::{GStore, GStoreMut},
@@ -11,16 +23,26 @@ use {
     futures::stream::TryStreamExt,
 };
 
+/// Constraints of CREATE TABLE which are stored into the schema
+pub struct TableConstraints<'a> {
+    pub foreign_keys: &'a [ForeignKey],
+    pub checks: &'a [Check],
+    pub unique_keys: &'a [UniqueKey],
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn create_table<T: GStore + GStoreMut>(
     storage: &mut T,
+    ctx: &ExecuteContext,
     target_table_name: &str,
     column_defs: Option<&[ColumnDef]>,
+    constraints: TableConstraints<'_>,
     if_not_exists: bool,
     source: &Option<Box<Query>>,
     engine: &Option<String>,
 ) -> Result<()> {
     let target_columns_defs = match source.as_deref() {
-        Some(Query { body, .. }) => match body {
+        Some(Query { body, .. }) => match leftmost_operand(body) {
             SetExpr::Select(select_query) => match &select_query.from.relation {
                 TableFactor::Table { name, .. } => {
                     let schema = storage.fetch_schema(name).await?;
@@ -58,7 +80,7 @@ pub async fn create_table<T: GStore + GStoreMut>(
                             continue;
                         }
 
-                        column_types[i] = evaluate_stateless(None, expr)
+                        column_types[i] = evaluate_stateless_with(ctx, None, expr)
                             .await
                             .and_then(Value::try_from)
                             .map(|value| value.get_type())?;
@@ -87,6 +109,9 @@ pub async fn create_table<T: GStore + GStoreMut>(
 
                 Some(column_defs)
             }
+            SetExpr::SetOperation { .. } => {
+                return Err(Error::Table(TableError::Unreachable));
+            }
         },
         None if column_defs.is_some() => column_defs.map(<[ColumnDef]>::to_vec),
         None => None,
@@ -100,12 +125,62 @@ pub async fn create_table<T: GStore + GStoreMut>(
         }
     }
 
-    if storage.fetch_schema(target_table_name).await?.is_none() {
+    let TableConstraints {
+        foreign_keys,
+        checks,
+        unique_keys,
+    } = constraints;
+
+    for UniqueKey { columns, .. } in unique_keys {
+        let column_defs = target_columns_defs
+            .iter()
+            .flatten()
+            .filter(|ColumnDef { name, .. }| columns.contains(name));
+
+        for ColumnDef {
+            name, data_type, ..
+        } in column_defs
+        {
+            if matches!(data_type, DataType::Float | DataType::Map) {
+                return Err(AlterError::UnsupportedDataTypeForUniqueColumn(
+                    name.to_owned(),
+                    data_type.clone(),
+                )
+                .into());
+            }
+        }
+    }
+
+    if let Some(column_defs) = target_columns_defs.as_deref() {
+        validate_check_exprs(target_table_name, column_defs, checks).await?;
+    }
+
+    for foreign_key in foreign_keys {
+        validate_foreign_key(
+            storage,
+            target_table_name,
+            target_columns_defs.as_deref(),
+            unique_keys,
+            foreign_key,
+        )
+        .await?;
+    }
+
+    if storage.fetch_view(target_table_name).await?.is_some() {
+        return Err(AlterError::ViewAlreadyExists(target_table_name.to_owned()).into());
+    } else if storage.fetch_schema(target_table_name).await?.is_none() {
+        if let Some(column_defs) = target_columns_defs.as_deref() {
+            create_identity_sequences(storage, target_table_name, column_defs).await?;
+        }
+
         let schema = Schema {
             table_name: target_table_name.to_owned(),
             column_defs: target_columns_defs,
             indexes: vec![],
             engine: engine.clone(),
+            foreign_keys: foreign_keys.to_vec(),
+            checks: checks.to_vec(),
+            unique_keys: unique_keys.to_vec(),
         };
 
         storage.insert_schema(&schema).await?;
@@ -115,7 +190,7 @@ pub async fn create_table<T: GStore + GStoreMut>(
 
     match source {
         Some(query) => {
-            let rows = select(storage, query, None)
+            let rows = select(storage, ctx, query, None)
                 .await?
                 .map_ok(Into::into)
                 .try_collect()
@@ -139,10 +214,151 @@ pub async fn drop_table<T: GStore + GStoreMut>(
         let schema = storage.fetch_schema(table_name).await?;
 
         if !if_exists {
-            schema.ok_or_else(|| AlterError::TableNotFound(table_name.to_owned()))?;
+            schema
+                .as_ref()
+                .ok_or_else(|| AlterError::TableNotFound(table_name.to_owned()))?;
+        }
+
+        for Schema {
+            table_name: referencing_table_name,
+            foreign_keys,
+            ..
+        } in storage.fetch_all_schemas().await?
+        {
+            if table_names.contains(&referencing_table_name) {
+                continue;
+            }
+
+            if let Some(ForeignKey { name, .. }) = foreign_keys
+                .into_iter()
+                .find(|foreign_key| &foreign_key.referenced_table_name == table_name)
+            {
+                return Err(AlterError::TableReferencedByForeignKey {
+                    table_name: table_name.to_owned(),
+                    foreign_key: name,
+                }
+                .into());
+            }
+        }
+
+        if schema.is_some() {
+            for View {
+                view_name, query, ..
+            } in storage.fetch_all_views().await?
+            {
+                if read_table_names(&query).contains(table_name) {
+                    return Err(AlterError::TableReadByView {
+                        table_name: table_name.to_owned(),
+                        view_name,
+                    }
+                    .into());
+                }
+            }
         }
 
+        let column_defs = schema
+            .and_then(|schema| schema.column_defs)
+            .unwrap_or_default();
+        let sequences = fetch_owned_sequences(storage, table_name, &column_defs).await?;
+        let triggers = fetch_table_triggers(storage, table_name).await?;
+
         storage.delete_schema(table_name).await?;
+
+        for Sequence { name, .. } in sequences {
+            storage.delete_sequence(&name).await?;
+        }
+
+        for Trigger { name, .. } in triggers {
+            storage.delete_trigger(&name).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn validate_foreign_key<T: GStore>(
+    storage: &T,
+    target_table_name: &str,
+    target_column_defs: Option<&[ColumnDef]>,
+    target_unique_keys: &[UniqueKey],
+    foreign_key: &ForeignKey,
+) -> Result<()> {
+    let ForeignKey {
+        name,
+        referencing_column_name,
+        referenced_table_name,
+        referenced_column_name,
+        on_delete,
+        on_update,
+    } = foreign_key;
+
+    let referencing_column = target_column_defs
+        .and_then(|column_defs| {
+            column_defs
+                .iter()
+                .find(|column_def| &column_def.name == referencing_column_name)
+        })
+        .ok_or_else(|| AlterError::ReferencingColumnNotFound(referencing_column_name.to_owned()))?;
+
+    let referenced_schema = match referenced_table_name == target_table_name {
+        true => None,
+        false => Some(
+            storage
+                .fetch_schema(referenced_table_name)
+                .await?
+                .ok_or_else(|| {
+                    AlterError::ReferencedTableNotFound(referenced_table_name.to_owned())
+                })?,
+        ),
+    };
+    let referenced_column_defs = match &referenced_schema {
+        Some(Schema { column_defs, .. }) => column_defs.as_deref(),
+        None => target_column_defs,
+    };
+    let referenced_column = referenced_column_defs
+        .and_then(|column_defs| {
+            column_defs
+                .iter()
+                .find(|column_def| &column_def.name == referenced_column_name)
+        })
+        .ok_or_else(|| AlterError::ReferencedColumnNotFound(referenced_column_name.to_owned()))?;
+
+    if referenced_column.unique.is_none() {
+        return Err(
+            AlterError::ReferencedColumnNotUnique(referenced_column_name.to_owned()).into(),
+        );
+    }
+
+    if referencing_column.data_type != referenced_column.data_type {
+        return Err(AlterError::ForeignKeyDataTypeMismatch {
+            foreign_key: name.to_owned(),
+            referencing: referencing_column.data_type.clone(),
+            referenced: referenced_column.data_type.clone(),
+        }
+        .into());
+    }
+
+    let set_null = [on_delete, on_update]
+        .iter()
+        .any(|action| matches!(action, ReferentialAction::SetNull));
+    if set_null && !referencing_column.nullable {
+        return Err(AlterError::SetNullOnNotNullColumn(name.to_owned()).into());
+    }
+
+    let primary_key = referencing_column.unique == Some(ColumnUniqueOption { is_primary: true })
+        || target_unique_keys.iter().any(
+            |UniqueKey {
+                 columns,
+                 is_primary,
+                 ..
+             }| *is_primary && columns.contains(referencing_column_name),
+        );
+    let update_action = matches!(
+        on_update,
+        ReferentialAction::Cascade | ReferentialAction::SetNull
+    );
+    if primary_key && update_action {
+        return Err(AlterError::UpdateActionOnPrimaryKeyColumn(name.to_owned()).into());
     }
 
     Ok(())
@@ -0,0 +1,100 @@
+use {
+    super::AlterError,
+    crate::{
+        ast::{TriggerAction, TriggerEvent, TriggerTiming},
+        data::Trigger,
+        result::Result,
+        store::{GStore, GStoreMut},
+    },
+};
+
+pub struct TriggerDefinition<'a> {
+    pub table_name: &'a str,
+    pub timing: TriggerTiming,
+    pub events: &'a [TriggerEvent],
+    pub action: &'a TriggerAction,
+}
+
+pub async fn create_trigger<T: GStore + GStoreMut>(
+    storage: &mut T,
+    name: &str,
+    definition: TriggerDefinition<'_>,
+    or_replace: bool,
+) -> Result<()> {
+    let TriggerDefinition {
+        table_name,
+        timing,
+        events,
+        action,
+    } = definition;
+
+    if storage.fetch_schema(table_name).await?.is_none() {
+        return Err(AlterError::TableNotFound(table_name.to_owned()).into());
+    }
+
+    match action {
+        TriggerAction::Function {
+            name: func_name, ..
+        } => {
+            if storage.fetch_function(func_name).await?.is_none() {
+                return Err(AlterError::FunctionNotFound(func_name.to_owned()).into());
+            }
+        }
+        TriggerAction::SetNew(_)
+            if timing != TriggerTiming::Before || events.contains(&TriggerEvent::Delete) =>
+        {
+            return Err(AlterError::TriggerSetNewNotBefore(name.to_owned()).into());
+        }
+        TriggerAction::SetNew(_) | TriggerAction::Statement(_) => {}
+    }
+
+    if storage.fetch_trigger(name).await?.is_some() && !or_replace {
+        return Err(AlterError::TriggerAlreadyExists(name.to_owned()).into());
+    }
+
+    storage
+        .insert_trigger(Trigger {
+            name: name.to_owned(),
+            table_name: table_name.to_owned(),
+            timing,
+            events: events.to_vec(),
+            action: action.clone(),
+        })
+        .await
+}
+
+pub async fn drop_trigger<T: GStore + GStoreMut>(
+    storage: &mut T,
+    names: &[String],
+    if_exists: bool,
+) -> Result<()> {
+    for name in names {
+        if storage.fetch_trigger(name).await?.is_none() {
+            if if_exists {
+                continue;
+            }
+
+            return Err(AlterError::TriggerNotFound(name.to_owned()).into());
+        }
+
+        storage.delete_trigger(name).await?;
+    }
+
+    Ok(())
+}
+
+/// Fetches the triggers defined on the table, which follow the table when it is renamed
+/// or dropped
+pub async fn fetch_table_triggers<T: GStore>(
+    storage: &T,
+    table_name: &str,
+) -> Result<Vec<Trigger>> {
+    let triggers = storage
+        .fetch_all_triggers()
+        .await?
+        .into_iter()
+        .filter(|trigger| trigger.table_name == table_name)
+        .collect();
+
+    Ok(triggers)
+}
@@ -1,5 +1,5 @@
 use {
-    super::AlterError,
+    super::{default_sequence, AlterError},
     crate::{
         ast::{ColumnDef, ColumnUniqueOption, DataType, OperateFunctionArg},
         executor::evaluate_stateless,
@@ -27,8 +27,12 @@ pub async fn validate(column_def: &ColumnDef) -> Result<()> {
         .into());
     }
 
-    if let Some(expr) = default {
-        evaluate_stateless(None, expr).await?;
+    match default {
+        Some(_) if default_sequence(column_def).is_some() => {}
+        Some(expr) => {
+            evaluate_stateless(None, expr).await?;
+        }
+        None => {}
     }
 
     Ok(())
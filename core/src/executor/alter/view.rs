@@ -0,0 +1,65 @@
+use {
+    super::AlterError,
+    crate::{
+        ast::{Query, Statement},
+        data::{TableError, View},
+        executor::{
+            context::ExecuteContext, fetch::apply_column_aliases, select::select_with_labels,
+        },
+        plan::plan,
+        result::Result,
+        store::{GStore, GStoreMut},
+    },
+};
+
+pub async fn create_view<T: GStore + GStoreMut>(
+    storage: &mut T,
+    ctx: &ExecuteContext,
+    view_name: &str,
+    columns: &[String],
+    query: &Query,
+    or_replace: bool,
+) -> Result<()> {
+    if storage.fetch_schema(view_name).await?.is_some() {
+        return Err(AlterError::TableAlreadyExists(view_name.to_owned()).into());
+    } else if storage.fetch_view(view_name).await?.is_some() && !or_replace {
+        return Err(AlterError::ViewAlreadyExists(view_name.to_owned()).into());
+    }
+
+    // The query is planned and its columns are resolved without reading any row, so a query
+    // which reads an unknown table or has fewer columns than the view is rejected here.
+    let planned = match plan(&*storage, Statement::Query(query.clone())).await? {
+        Statement::Query(query) => query,
+        _ => return Err(TableError::Unreachable.into()),
+    };
+    let (labels, _) = select_with_labels(&*storage, ctx, &planned, None).await?;
+    apply_column_aliases(view_name, columns, labels)?;
+
+    storage
+        .insert_view(View {
+            view_name: view_name.to_owned(),
+            columns: columns.to_vec(),
+            query: query.clone(),
+        })
+        .await
+}
+
+pub async fn drop_view<T: GStore + GStoreMut>(
+    storage: &mut T,
+    view_names: &[String],
+    if_exists: bool,
+) -> Result<()> {
+    for view_name in view_names {
+        if storage.fetch_view(view_name).await?.is_none() {
+            if if_exists {
+                continue;
+            }
+
+            return Err(AlterError::ViewNotFound(view_name.to_owned()).into());
+        }
+
+        storage.delete_view(view_name).await?;
+    }
+
+    Ok(())
+}
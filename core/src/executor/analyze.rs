@@ -0,0 +1,395 @@
+use {
+    super::{
+        context::ExecuteContext,
+        execute::ExecuteError,
+        explain::{explain, PlanNode},
+        select::select_profiled,
+    },
+    crate::{
+        ast::{IndexOperator, SetExpr, Statement},
+        data::{
+            CustomFunction as StructCustomFunction, Key, Schema, Sequence as StructSequence,
+            Trigger as StructTrigger, Value, View as StructView,
+        },
+        result::Result,
+        store::{
+            CustomFunction, DataRow, GStore, Index, MetaIter, Metadata, RowIter, Sequence, Store,
+            Trigger, View,
+        },
+    },
+    async_trait::async_trait,
+    chrono::{DateTime, Utc},
+    futures::{
+        stream::{self, Stream, StreamExt, TryStreamExt},
+        Future,
+    },
+    serde::{Deserialize, Serialize},
+    std::{cell::RefCell, collections::HashMap, fmt, pin::Pin, task::Poll, time::Duration},
+};
+
+/// Plan returned by `EXPLAIN ANALYZE`, annotated with what happened while running it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AnalyzedPlan {
+    pub plan: PlanNode,
+    pub storage_calls: StorageCalls,
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for AnalyzedPlan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let StorageCalls {
+            scan_data,
+            scan_indexed_data,
+            fetch_data,
+        } = self.storage_calls;
+
+        write!(f, "{}", self.plan)?;
+        writeln!(
+            f,
+            "Storage Calls: scan_data={scan_data}, scan_indexed_data={scan_indexed_data}, fetch_data={fetch_data}"
+        )?;
+        writeln!(f, "Execution Time: {:?}", self.elapsed)
+    }
+}
+
+/// Runs the query of `statement` and explains it with the stats recorded meanwhile
+pub async fn explain_analyze<T: GStore>(
+    storage: &T,
+    ctx: &ExecuteContext,
+    statement: &Statement,
+) -> Result<AnalyzedPlan> {
+    let query = match statement {
+        Statement::Query(query) => query,
+        _ => return Err(ExecuteError::UnsupportedExplainAnalyze.into()),
+    };
+
+    let profiler = Profiler::default();
+    let storage = ProfiledStorage::new(storage, &profiler);
+    let started = Utc::now();
+
+    select_profiled(&storage, ctx, query, &profiler)
+        .await?
+        .try_for_each(|_| async { Ok(()) })
+        .await?;
+
+    let elapsed = elapsed(started);
+    let plan = explain(statement, Some(&profiler))?;
+
+    Ok(AnalyzedPlan {
+        plan,
+        storage_calls: profiler.storage_calls(),
+        elapsed,
+    })
+}
+
+/// Rows taken from the input, rows passed on and time spent by a plan operation,
+/// the time includes the time spent by its inputs
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlanStats {
+    pub rows_in: usize,
+    pub rows_out: usize,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StorageCalls {
+    pub scan_data: usize,
+    pub scan_indexed_data: usize,
+    pub fetch_data: usize,
+}
+
+/// Rows passing through a stage, boxed to keep the types of the select pipeline small
+pub type Rows<'a, T> = Pin<Box<dyn Stream<Item = Result<T>> + 'a>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Fetch,
+    Join(usize),
+    Filter,
+    Aggregate,
+    Sort,
+    Distinct,
+    Limit,
+}
+
+/// Collects the stats of the stages of each plan node executed by `EXPLAIN ANALYZE`
+#[derive(Default)]
+pub struct Profiler {
+    stages: RefCell<HashMap<(usize, Stage), PlanStats>>,
+    storage_calls: RefCell<StorageCalls>,
+}
+
+impl Profiler {
+    pub fn storage_calls(&self) -> StorageCalls {
+        *self.storage_calls.borrow()
+    }
+
+    fn update<F: FnOnce(&mut PlanStats)>(&self, node_id: usize, stage: Stage, f: F) {
+        f(self
+            .stages
+            .borrow_mut()
+            .entry((node_id, stage))
+            .or_default())
+    }
+
+    fn count_call<F: FnOnce(&mut StorageCalls)>(&self, f: F) {
+        f(&mut self.storage_calls.borrow_mut())
+    }
+}
+
+/// Records the stages of a single plan node, does nothing unless it has a `Profiler`.
+///
+/// Plan nodes are the `SetExpr`s of the profiled query numbered in pre-order, the body of
+/// the query is node 0, so `EXPLAIN` finds the stats of a node by walking the query alike.
+#[derive(Clone, Copy)]
+pub struct Probe<'a> {
+    profiler: Option<&'a Profiler>,
+    node_id: usize,
+}
+
+impl<'a> Probe<'a> {
+    /// Probe of the body of the profiled query
+    pub fn new(profiler: Option<&'a Profiler>) -> Self {
+        Self {
+            profiler,
+            node_id: 0,
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(None)
+    }
+
+    /// Probes of the left and the right operands of a set operation
+    pub fn operands(self, left: &SetExpr) -> (Self, Self) {
+        let left_id = self.node_id + 1;
+        let right_id = left_id + count_nodes(left);
+
+        (
+            Self {
+                node_id: left_id,
+                ..self
+            },
+            Self {
+                node_id: right_id,
+                ..self
+            },
+        )
+    }
+
+    pub fn stats(self, stage: Stage) -> Option<PlanStats> {
+        self.profiler?
+            .stages
+            .borrow()
+            .get(&(self.node_id, stage))
+            .copied()
+    }
+
+    pub fn rows(self, stage: Stage, rows_in: usize, rows_out: usize) {
+        if let Some(profiler) = self.profiler {
+            profiler.update(self.node_id, stage, |stats| {
+                stats.rows_in += rows_in;
+                stats.rows_out += rows_out;
+            });
+        }
+    }
+
+    /// Counts the rows taken by the stage
+    pub fn input<T: 'a>(
+        self,
+        stage: Stage,
+        rows: impl Stream<Item = Result<T>> + 'a,
+    ) -> Rows<'a, T> {
+        match self.profiler {
+            Some(_) => Box::pin(rows.inspect_ok(move |_| self.rows(stage, 1, 0))),
+            None => Box::pin(rows),
+        }
+    }
+
+    /// Counts the rows passed on by the stage and the time spent polling them
+    pub fn output<T: 'a>(
+        self,
+        stage: Stage,
+        rows: impl Stream<Item = Result<T>> + 'a,
+    ) -> Rows<'a, T> {
+        let profiler = match self.profiler {
+            Some(profiler) => profiler,
+            None => return Box::pin(rows),
+        };
+        let mut rows = Box::pin(rows);
+
+        Box::pin(stream::poll_fn(move |cx| {
+            let started = Utc::now();
+            let polled = rows.poll_next_unpin(cx);
+
+            profiler.update(self.node_id, stage, |stats| {
+                stats.elapsed += elapsed(started);
+
+                if let Poll::Ready(Some(Ok(_))) = polled {
+                    stats.rows_out += 1;
+                }
+            });
+
+            polled
+        }))
+    }
+
+    /// Adds the time spent preparing the stage, e.g. sorting all the rows before passing them on
+    pub async fn time<F: Future>(self, stage: Stage, future: F) -> F::Output {
+        if self.profiler.is_none() {
+            return future.await;
+        }
+
+        let started = Utc::now();
+        let output = future.await;
+
+        self.elapse(stage, started);
+
+        output
+    }
+
+    pub fn elapse(self, stage: Stage, started: DateTime<Utc>) {
+        if let Some(profiler) = self.profiler {
+            profiler.update(self.node_id, stage, |stats| {
+                stats.elapsed += elapsed(started);
+            });
+        }
+    }
+}
+
+/// Number of plan nodes in the operand tree of `set_expr`
+fn count_nodes(set_expr: &SetExpr) -> usize {
+    match set_expr {
+        SetExpr::SetOperation { left, right, .. } => 1 + count_nodes(left) + count_nodes(right),
+        SetExpr::Select(_) | SetExpr::Values(_) => 1,
+    }
+}
+
+pub fn elapsed(started: DateTime<Utc>) -> Duration {
+    (Utc::now() - started).to_std().unwrap_or_default()
+}
+
+/// Storage counting the calls made to fetch rows while running `EXPLAIN ANALYZE`
+pub struct ProfiledStorage<'a, T> {
+    storage: &'a T,
+    profiler: &'a Profiler,
+}
+
+impl<'a, T> ProfiledStorage<'a, T> {
+    pub fn new(storage: &'a T, profiler: &'a Profiler) -> Self {
+        Self { storage, profiler }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a, T: Store> Store for ProfiledStorage<'a, T> {
+    async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
+        self.storage.fetch_schema(table_name).await
+    }
+
+    async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
+        self.storage.fetch_all_schemas().await
+    }
+
+    async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
+        self.profiler.count_call(|calls| calls.fetch_data += 1);
+
+        self.storage.fetch_data(table_name, key).await
+    }
+
+    async fn scan_data(&self, table_name: &str) -> Result<RowIter> {
+        self.profiler.count_call(|calls| calls.scan_data += 1);
+
+        self.storage.scan_data(table_name).await
+    }
+
+    async fn scan_data_with_prefix(
+        &self,
+        table_name: &str,
+        prefix: &[Key],
+        cmp_key: Option<(&IndexOperator, Key)>,
+    ) -> Result<RowIter> {
+        self.profiler.count_call(|calls| calls.scan_data += 1);
+
+        self.storage
+            .scan_data_with_prefix(table_name, prefix, cmp_key)
+            .await
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a, T: Index> Index for ProfiledStorage<'a, T> {
+    async fn scan_indexed_data(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        asc: Option<bool>,
+        prefix: &[Value],
+        cmp_value: Option<(&IndexOperator, Value)>,
+    ) -> Result<RowIter> {
+        self.profiler
+            .count_call(|calls| calls.scan_indexed_data += 1);
+
+        self.storage
+            .scan_indexed_data(table_name, index_name, asc, prefix, cmp_value)
+            .await
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a, T: Metadata> Metadata for ProfiledStorage<'a, T> {
+    async fn scan_table_meta(&self) -> Result<MetaIter> {
+        self.storage.scan_table_meta().await
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a, T: CustomFunction> CustomFunction for ProfiledStorage<'a, T> {
+    async fn fetch_function<'b>(
+        &'b self,
+        func_name: &str,
+    ) -> Result<Option<&'b StructCustomFunction>> {
+        self.storage.fetch_function(func_name).await
+    }
+
+    async fn fetch_all_functions<'b>(&'b self) -> Result<Vec<&'b StructCustomFunction>> {
+        self.storage.fetch_all_functions().await
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a, T: Sequence> Sequence for ProfiledStorage<'a, T> {
+    async fn fetch_sequence(&self, name: &str) -> Result<Option<StructSequence>> {
+        self.storage.fetch_sequence(name).await
+    }
+
+    async fn fetch_all_sequences(&self) -> Result<Vec<StructSequence>> {
+        self.storage.fetch_all_sequences().await
+    }
+
+    async fn next_sequence_value(&self, name: &str) -> Result<Option<i64>> {
+        self.storage.next_sequence_value(name).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a, T: View> View for ProfiledStorage<'a, T> {
+    async fn fetch_view(&self, view_name: &str) -> Result<Option<StructView>> {
+        self.storage.fetch_view(view_name).await
+    }
+
+    async fn fetch_all_views(&self) -> Result<Vec<StructView>> {
+        self.storage.fetch_all_views().await
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a, T: Trigger> Trigger for ProfiledStorage<'a, T> {
+    async fn fetch_trigger(&self, trigger_name: &str) -> Result<Option<StructTrigger>> {
+        self.storage.fetch_trigger(trigger_name).await
+    }
+
+    async fn fetch_all_triggers(&self) -> Result<Vec<StructTrigger>> {
+        self.storage.fetch_all_triggers().await
+    }
+}
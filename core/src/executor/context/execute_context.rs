@@ -0,0 +1,138 @@
+use {
+    crate::{
+        data::Value,
+        executor::{trigger::TriggerError, ExecuteError},
+        result::Result,
+    },
+    std::collections::HashMap,
+};
+
+/// Rows which a statement run by a procedure, a table function or a trigger reads besides
+/// the rows of its tables.
+#[derive(Debug)]
+pub enum Scope {
+    /// Arguments and local variables, referenced by their names or qualified by the name
+    /// of the procedure. Columns of the same name take precedence over them.
+    Variables {
+        name: String,
+        values: HashMap<String, Value>,
+    },
+    /// `NEW` and `OLD` rows of the firing trigger
+    Trigger {
+        new: Option<HashMap<String, Value>>,
+        old: Option<HashMap<String, Value>>,
+    },
+}
+
+/// Limits of a statement execution, configured on `Glue`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum depth of triggers, custom functions and procedures running within each other.
+    /// Each level takes stack space, so threads with a small stack may need a lower limit.
+    pub max_nesting_depth: usize,
+    /// Maximum number of times the recursive term of a `WITH RECURSIVE` CTE runs
+    pub max_recursion: usize,
+}
+
+impl Limits {
+    pub const DEFAULT: Self = Self {
+        max_nesting_depth: 16,
+        max_recursion: 1000,
+    };
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// State of a statement execution, passed down to everything the statement evaluates.
+#[derive(Debug, Default)]
+pub struct ExecuteContext {
+    params: Vec<Value>,
+    scope: Option<Scope>,
+    limits: Limits,
+    depth: usize,
+}
+
+impl ExecuteContext {
+    pub const fn new(params: Vec<Value>, limits: Limits) -> Self {
+        Self {
+            params,
+            scope: None,
+            limits,
+            depth: 0,
+        }
+    }
+
+    pub const fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Context of a trigger, custom function or procedure run by the statement, one level
+    /// deeper. Fails once the maximum nesting depth is reached, so calls which keep running
+    /// each other fail instead of recursing without end.
+    pub fn nest(&self, name: &str) -> Result<Self> {
+        if self.depth >= self.limits.max_nesting_depth {
+            return Err(ExecuteError::NestingDepthExceeded(name.to_owned()).into());
+        }
+
+        Ok(Self {
+            params: Vec::new(),
+            scope: None,
+            limits: self.limits,
+            depth: self.depth + 1,
+        })
+    }
+
+    /// Context at the same depth which reads the rows of `scope`
+    pub fn with_scope(&self, scope: Scope) -> Self {
+        Self {
+            params: Vec::new(),
+            scope: Some(scope),
+            limits: self.limits,
+            depth: self.depth,
+        }
+    }
+
+    /// Parameter bound to the placeholder `$n`
+    pub fn param(&self, placeholder: &str) -> Option<&Value> {
+        let index = placeholder.strip_prefix('$')?.parse::<usize>().ok()?;
+
+        self.params.get(index.checked_sub(1)?)
+    }
+
+    /// Variable named by a bare identifier which is not a column
+    pub fn get_value(&self, ident: &str) -> Option<&Value> {
+        match &self.scope {
+            Some(Scope::Variables { values, .. }) => values.get(ident),
+            Some(Scope::Trigger { .. }) | None => None,
+        }
+    }
+
+    /// Variable qualified by the procedure name, or a column of the `NEW` or `OLD` row
+    pub fn get_alias_value(&self, alias: &str, ident: &str) -> Result<Option<&Value>> {
+        let (new, old) = match &self.scope {
+            Some(Scope::Variables { name, values }) if name == alias => {
+                return Ok(values.get(ident));
+            }
+            Some(Scope::Trigger { new, old }) => (new, old),
+            Some(Scope::Variables { .. }) | None => return Ok(None),
+        };
+
+        let row = if alias.eq_ignore_ascii_case("NEW") {
+            new
+        } else if alias.eq_ignore_ascii_case("OLD") {
+            old
+        } else {
+            return Ok(None);
+        };
+
+        row.as_ref()
+            .ok_or_else(|| TriggerError::RowNotAvailable(alias.to_uppercase()))?
+            .get(ident)
+            .map(Some)
+            .ok_or_else(|| TriggerError::ColumnNotFound(format!("{alias}.{ident}")).into())
+    }
+}
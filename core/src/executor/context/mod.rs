@@ -1,4 +1,9 @@
 mod aggregate_context;
+mod execute_context;
 mod row_context;
 
-pub use {aggregate_context::AggregateContext, row_context::RowContext};
+pub use {
+    aggregate_context::AggregateContext,
+    execute_context::{ExecuteContext, Limits, Scope},
+    row_context::RowContext,
+};
@@ -1,8 +1,16 @@
 use {
-    crate::data::{Row, Value},
+    crate::{
+        ast::{Expr, Window},
+        data::{Row, Value},
+    },
     std::{borrow::Cow, collections::HashMap, fmt::Debug, rc::Rc},
 };
 
+/// Column labels and rows of a common table expression
+type CteData<'b> = (Option<&'b [String]>, &'b Rc<[Row]>);
+
+static NULL: Value = Value::Null;
+
 #[derive(Debug)]
 pub enum RowContext<'a> {
     Data {
@@ -19,6 +27,26 @@ pub enum RowContext<'a> {
         left: Rc<RowContext<'a>>,
         right: Rc<RowContext<'a>>,
     },
+    /// Window function results computed for the row in `next`
+    Window {
+        values: HashMap<&'a Window, Value>,
+        next: Rc<RowContext<'a>>,
+    },
+    /// Rows of a common table expression, visible to the queries run within `next`
+    Cte {
+        name: &'a str,
+        columns: Option<Vec<String>>,
+        rows: Rc<[Row]>,
+        next: Option<Rc<RowContext<'a>>>,
+    },
+    /// Row of a grouping set, the expressions left out of the set read as NULL
+    Grouping {
+        /// Table alias and name of each column left out
+        columns: Vec<(&'a str, &'a str)>,
+        /// Computed expressions left out, which read as NULL as a whole
+        exprs: Vec<&'a Expr>,
+        next: Rc<RowContext<'a>>,
+    },
 }
 
 impl<'a> RowContext<'a> {
@@ -47,6 +75,19 @@ impl<'a> RowContext<'a> {
             Self::Bridge { left, right } => {
                 left.get_value(target).or_else(|| right.get_value(target))
             }
+            Self::Window { next, .. } => next.get_value(target),
+            Self::Grouping { columns, next, .. } => {
+                let left_out = next
+                    .get_alias(target)
+                    .map(|table_alias| left_out(columns, Some(table_alias), target))
+                    .unwrap_or(false);
+
+                match left_out {
+                    true => Some(&NULL),
+                    false => next.get_value(target),
+                }
+            }
+            Self::Cte { next, .. } => next.as_ref().and_then(|next| next.get_value(target)),
             Self::RefVecData { columns, values } => columns
                 .iter()
                 .position(|column| column == target)
@@ -78,6 +119,16 @@ impl<'a> RowContext<'a> {
             Self::Bridge { left, right } => left
                 .get_alias_value(target_table_alias, target)
                 .or_else(|| right.get_alias_value(target_table_alias, target)),
+            Self::Window { next, .. } => next.get_alias_value(target_table_alias, target),
+            Self::Grouping { columns, next, .. } => {
+                match left_out(columns, Some(target_table_alias), target) {
+                    true => Some(&NULL),
+                    false => next.get_alias_value(target_table_alias, target),
+                }
+            }
+            Self::Cte { next, .. } => next
+                .as_ref()
+                .and_then(|next| next.get_alias_value(target_table_alias, target)),
             _ => None,
         }
     }
@@ -94,6 +145,17 @@ impl<'a> RowContext<'a> {
             Self::Bridge { left, right } => left
                 .get_alias_entries(alias)
                 .or_else(|| right.get_alias_entries(alias)),
+            Self::Window { next, .. } => next.get_alias_entries(alias),
+            Self::Grouping { columns, next, .. } => next.get_alias_entries(alias).map(|entries| {
+                entries
+                    .into_iter()
+                    .map(|(key, value)| match left_out(columns, Some(alias), key) {
+                        true => (key, Value::Null),
+                        false => (key, value),
+                    })
+                    .collect()
+            }),
+            Self::Cte { next, .. } => next.as_ref().and_then(|next| next.get_alias_entries(alias)),
             _ => None,
         }
     }
@@ -115,7 +177,109 @@ impl<'a> RowContext<'a> {
             Self::Bridge { left, right } => {
                 [left.get_all_entries(), right.get_all_entries()].concat()
             }
+            Self::Window { next, .. } => next.get_all_entries(),
+            Self::Grouping { columns, next, .. } => next
+                .get_all_entries()
+                .into_iter()
+                .map(|(key, value)| match left_out(columns, None, key) {
+                    true => (key, Value::Null),
+                    false => (key, value),
+                })
+                .collect(),
+            Self::Cte {
+                next: Some(next), ..
+            } => next.get_all_entries(),
             _ => vec![],
         }
     }
+
+    /// Table alias of the row an unqualified column `target` is read from
+    pub fn get_alias(&self, target: &str) -> Option<&'a str> {
+        match self {
+            Self::Data {
+                table_alias,
+                row,
+                next,
+            } => match row.get_value(target) {
+                Some(_) => Some(table_alias),
+                None => next.as_ref().and_then(|next| next.get_alias(target)),
+            },
+            Self::Bridge { left, right } => {
+                left.get_alias(target).or_else(|| right.get_alias(target))
+            }
+            Self::Window { next, .. } | Self::Grouping { next, .. } => next.get_alias(target),
+            Self::Cte { next, .. } => next.as_ref().and_then(|next| next.get_alias(target)),
+            Self::RefVecData { .. } | Self::RefMapData(_) => None,
+        }
+    }
+
+    /// Whether `expr` is a computed expression left out of the grouping set of the row
+    pub fn is_left_out(&self, expr: &Expr) -> bool {
+        match self {
+            Self::Grouping { exprs, next, .. } => {
+                exprs.iter().any(|left_out| *left_out == expr) || next.is_left_out(expr)
+            }
+            Self::Data {
+                next: Some(next), ..
+            }
+            | Self::Cte {
+                next: Some(next), ..
+            } => next.is_left_out(expr),
+            Self::Window { next, .. } => next.is_left_out(expr),
+            // the right side holds the rows of outer queries, whose grouping sets do not apply
+            Self::Bridge { left, .. } => left.is_left_out(expr),
+            _ => false,
+        }
+    }
+
+    pub fn get_window_value(&'a self, target: &Window) -> Option<&'a Value> {
+        match self {
+            Self::Window { values, next } => {
+                values.get(target).or_else(|| next.get_window_value(target))
+            }
+            Self::Data {
+                next: Some(next), ..
+            } => next.get_window_value(target),
+            Self::Bridge { left, right } => left
+                .get_window_value(target)
+                .or_else(|| right.get_window_value(target)),
+            Self::Cte {
+                next: Some(next), ..
+            } => next.get_window_value(target),
+            Self::Grouping { next, .. } => next.get_window_value(target),
+            _ => None,
+        }
+    }
+
+    pub fn get_cte(&self, target: &str) -> Option<CteData<'_>> {
+        match self {
+            Self::Cte {
+                name,
+                columns,
+                rows,
+                ..
+            } if *name == target => Some((columns.as_deref(), rows)),
+            Self::Cte {
+                next: Some(next), ..
+            }
+            | Self::Data {
+                next: Some(next), ..
+            } => next.get_cte(target),
+            Self::Window { next, .. } | Self::Grouping { next, .. } => next.get_cte(target),
+            Self::Bridge { left, right } => left.get_cte(target).or_else(|| right.get_cte(target)),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the column `target` is one of the `columns` left out of a grouping set,
+/// any table matches when `target_table_alias` is not given
+fn left_out(columns: &[(&str, &str)], target_table_alias: Option<&str>, target: &str) -> bool {
+    columns.iter().any(|(table_alias, column)| {
+        let same_table = target_table_alias
+            .map(|target_table_alias| *table_alias == target_table_alias)
+            .unwrap_or(true);
+
+        same_table && *column == target
+    })
 }
@@ -0,0 +1,181 @@
+use {
+    super::{
+        context::{ExecuteContext, RowContext},
+        evaluate::evaluate,
+        sort::SortItem,
+    },
+    crate::{
+        ast::{Aggregate, Distinct as AstDistinct, Expr},
+        data::{Key, Row, Value},
+        result::Result,
+        store::GStore,
+    },
+    futures::{
+        future,
+        stream::{self, Stream, StreamExt, TryStreamExt},
+    },
+    im_rc::HashMap,
+    ordered_float::OrderedFloat,
+    std::{borrow::Cow, collections::HashSet, rc::Rc},
+};
+
+pub struct Distinct<'a, T: GStore> {
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    context: Option<Rc<RowContext<'a>>>,
+    distinct: Option<&'a AstDistinct>,
+}
+
+impl<'a, T: GStore> Distinct<'a, T> {
+    pub fn new(
+        storage: &'a T,
+        ctx: &'a ExecuteContext,
+        context: Option<Rc<RowContext<'a>>>,
+        distinct: Option<&'a AstDistinct>,
+    ) -> Self {
+        Self {
+            storage,
+            ctx,
+            context,
+            distinct,
+        }
+    }
+
+    pub fn apply(
+        self,
+        rows: impl Stream<Item = Result<SortItem<'a>>> + 'a,
+        table_alias: &'a str,
+    ) -> impl Stream<Item = Result<Row>> + 'a {
+        #[derive(futures_enum::Stream)]
+        enum Rows<I1, I2, I3> {
+            NonDistinct(I1),
+            Distinct(I2),
+            DistinctOn(I3),
+        }
+
+        match self.distinct {
+            None => Rows::NonDistinct(rows.map_ok(|(.., row)| row)),
+            Some(AstDistinct::Distinct) => {
+                let rows = rows
+                    .and_then(|(.., row)| future::ready(row_keys(&row).map(|keys| (keys, row))));
+
+                Rows::Distinct(Box::pin(dedup(rows)))
+            }
+            Some(AstDistinct::On(exprs)) => {
+                let storage = self.storage;
+                let ctx = self.ctx;
+                let context = self.context;
+                let rows = rows.and_then(move |(aggregated, next, row)| {
+                    let filter_context = match &context {
+                        Some(context) => {
+                            Rc::new(RowContext::concat(Rc::clone(&next), Rc::clone(context)))
+                        }
+                        None => next,
+                    };
+
+                    async move {
+                        let label_context =
+                            Rc::new(RowContext::new(table_alias, Cow::Borrowed(&row), None));
+                        let filter_context =
+                            Rc::new(RowContext::concat(filter_context, label_context));
+
+                        let keys =
+                            expr_keys(storage, ctx, &filter_context, aggregated, exprs).await?;
+
+                        drop(filter_context);
+
+                        Ok((keys, row))
+                    }
+                });
+
+                Rows::DistinctOn(Box::pin(dedup(rows)))
+            }
+        }
+    }
+}
+
+async fn expr_keys<'a, 'b, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    context: &Rc<RowContext<'b>>,
+    aggregated: Option<Rc<HashMap<&'a Aggregate, Value>>>,
+    exprs: &'a [Expr],
+) -> Result<Vec<Key>> {
+    stream::iter(exprs)
+        .then(|expr| {
+            let context = Some(Rc::clone(context));
+            let aggregated = aggregated.as_ref().map(Rc::clone);
+
+            async move {
+                let value: Value = evaluate(storage, ctx, context, aggregated, expr)
+                    .await?
+                    .try_into()?;
+
+                distinct_key(&value)
+            }
+        })
+        .try_collect()
+        .await
+}
+
+pub fn row_keys(row: &Row) -> Result<Vec<Key>> {
+    match row {
+        Row::Vec { values, .. } => values.iter().map(distinct_key).collect(),
+        Row::Map(values) => {
+            let mut entries = values.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(name, _)| *name);
+
+            entries
+                .into_iter()
+                .flat_map(|(name, value)| [Ok(Key::Str(name.to_owned())), distinct_key(value)])
+                .collect()
+        }
+    }
+}
+
+/// Key which is equal for equal values, including `MAP`, `LIST` and `POINT` values which
+/// cannot be converted into a [`Key`]. They become a composite key tagged by their type.
+pub fn distinct_key(value: &Value) -> Result<Key> {
+    const LIST: u8 = 0;
+    const MAP: u8 = 1;
+    const POINT: u8 = 2;
+
+    let (tag, keys) = match value {
+        Value::List(values) => (
+            LIST,
+            values.iter().map(distinct_key).collect::<Result<_>>()?,
+        ),
+        Value::Map(values) => {
+            let mut entries = values.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(name, _)| *name);
+
+            let keys = entries
+                .into_iter()
+                .map(|(name, value)| {
+                    distinct_key(value)
+                        .map(|key| Key::Composite(vec![Key::Str(name.to_owned()), key]))
+                })
+                .collect::<Result<_>>()?;
+
+            (MAP, keys)
+        }
+        Value::Point(point) => (
+            POINT,
+            vec![
+                Key::F64(OrderedFloat(point.x)),
+                Key::F64(OrderedFloat(point.y)),
+            ],
+        ),
+        _ => return Key::try_from(value),
+    };
+
+    Ok(Key::Composite(vec![Key::U8(tag), Key::Composite(keys)]))
+}
+
+fn dedup<'a>(
+    rows: impl Stream<Item = Result<(Vec<Key>, Row)>> + 'a,
+) -> impl Stream<Item = Result<Row>> + 'a {
+    let mut keys_set = HashSet::new();
+
+    rows.try_filter_map(move |(keys, row)| future::ok(keys_set.insert(keys).then_some(row)))
+}
@@ -77,9 +77,15 @@ pub enum EvaluateError {
     #[error("context is required for identifier evaluation: {}", .0.to_sql())]
     ContextRequiredForIdentEvaluation(Expr),
 
+    #[error("placeholder is not bound to a parameter: {0}")]
+    UnboundPlaceholder(String),
+
     #[error("unreachable empty aggregate value: {0:?}")]
     UnreachableEmptyAggregateValue(Aggregate),
 
+    #[error("window function is only allowed in SELECT and ORDER BY: {0}")]
+    WindowFunctionNotAllowed(String),
+
     #[error("incompatible bit operation between {0} and {1}")]
     IncompatibleBitOperation(String, String),
 
@@ -135,6 +141,9 @@ pub enum EvaluateError {
     #[error("unsupported custom function in subqueries")]
     UnsupportedCustomFunction,
 
+    #[error("table function cannot be used as an expression: {0}")]
+    TableFunctionInExpression(String),
+
     #[error(r#"The function "{function_name}" requires at least {required_minimum} argument(s), but {found} were provided."#)]
     FunctionRequiresMoreArguments {
         function_name: String,
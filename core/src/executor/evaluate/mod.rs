@@ -4,10 +4,14 @@ mod expr;
 mod function;
 
 use {
-    super::{context::RowContext, select::select},
+    super::{
+        context::{ExecuteContext, Limits, RowContext},
+        procedure::{run_function, validate_args_len},
+        select::select,
+    },
     crate::{
-        ast::{Aggregate, Expr, Function},
-        data::{CustomFunction, Interval, Literal, Row, Value},
+        ast::{Aggregate, Expr, Function, FunctionBody, ToSql},
+        data::{CustomFunction, FunctionKind, Interval, Literal, Row, SequenceError, Value},
         mock::MockStorage,
         result::{Error, Result},
         store::GStore,
@@ -27,26 +31,40 @@ pub use {error::EvaluateError, evaluated::Evaluated};
 #[async_recursion(?Send)]
 pub async fn evaluate<'a, 'b: 'a, 'c: 'a, T: GStore>(
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     context: Option<Rc<RowContext<'b>>>,
     aggregated: Option<Rc<HashMap<&'c Aggregate, Value>>>,
     expr: &'a Expr,
 ) -> Result<Evaluated<'a>> {
-    evaluate_inner(Some(storage), context, aggregated, expr).await
+    evaluate_inner(Some(storage), ctx, context, aggregated, expr).await
 }
 
+/// Stateless evaluation outside of any statement has no parameters bound
+static STATELESS: ExecuteContext = ExecuteContext::new(Vec::new(), Limits::DEFAULT);
+
 pub async fn evaluate_stateless<'a, 'b: 'a>(
     context: Option<RowContext<'b>>,
     expr: &'a Expr,
+) -> Result<Evaluated<'a>> {
+    evaluate_stateless_with(&STATELESS, context, expr).await
+}
+
+/// Evaluates without storage like `evaluate_stateless`, placeholders read the parameters of `ctx`
+pub async fn evaluate_stateless_with<'a, 'b: 'a>(
+    ctx: &'a ExecuteContext,
+    context: Option<RowContext<'b>>,
+    expr: &'a Expr,
 ) -> Result<Evaluated<'a>> {
     let context = context.map(Rc::new);
     let storage: Option<&MockStorage> = None;
 
-    evaluate_inner(storage, context, None, expr).await
+    evaluate_inner(storage, ctx, context, None, expr).await
 }
 
 #[async_recursion(?Send)]
 async fn evaluate_inner<'a, 'b: 'a, 'c: 'a, T: GStore>(
     storage: Option<&'a T>,
+    ctx: &'a ExecuteContext,
     context: Option<Rc<RowContext<'b>>>,
     aggregated: Option<Rc<HashMap<&'c Aggregate, Value>>>,
     expr: &'a Expr,
@@ -55,34 +73,62 @@ async fn evaluate_inner<'a, 'b: 'a, 'c: 'a, T: GStore>(
         let context = context.as_ref().map(Rc::clone);
         let aggregated = aggregated.as_ref().map(Rc::clone);
 
-        evaluate_inner(storage, context, aggregated, expr)
+        evaluate_inner(storage, ctx, context, aggregated, expr)
     };
 
+    let computed = !matches!(
+        expr,
+        Expr::Literal(_)
+            | Expr::Placeholder(_)
+            | Expr::TypedString { .. }
+            | Expr::Identifier(_)
+            | Expr::CompoundIdentifier { .. }
+    );
+    if computed && matches!(&context, Some(context) if context.is_left_out(expr)) {
+        return Ok(Evaluated::Value(Value::Null));
+    }
+
     match expr {
         Expr::Literal(ast_literal) => expr::literal(ast_literal),
+        Expr::Placeholder(placeholder) => match ctx.param(placeholder) {
+            Some(value) => Ok(Evaluated::Value(value.clone())),
+            None => Err(EvaluateError::UnboundPlaceholder(placeholder.to_owned()).into()),
+        },
         Expr::TypedString { data_type, value } => {
             expr::typed_string(data_type, Cow::Borrowed(value))
         }
         Expr::Identifier(ident) => {
-            let context = context
-                .ok_or_else(|| EvaluateError::ContextRequiredForIdentEvaluation(expr.clone()))?;
+            let value = match &context {
+                Some(context) => context.get_value(ident),
+                None => None,
+            };
 
-            match context.get_value(ident) {
+            match value.or_else(|| ctx.get_value(ident)) {
                 Some(value) => Ok(value.clone()),
+                None if context.is_none() => {
+                    Err(EvaluateError::ContextRequiredForIdentEvaluation(expr.clone()).into())
+                }
                 None => Err(EvaluateError::ValueNotFound(ident.to_owned()).into()),
             }
             .map(Evaluated::from)
         }
         Expr::Nested(expr) => eval(expr).await,
         Expr::CompoundIdentifier { alias, ident } => {
-            let table_alias = &alias;
-            let column = &ident;
-            let context = context
-                .ok_or_else(|| EvaluateError::ContextRequiredForIdentEvaluation(expr.clone()))?;
+            let value = match &context {
+                Some(context) => context.get_alias_value(alias, ident),
+                None => None,
+            };
+            let value = match value {
+                Some(value) => Some(value),
+                None => ctx.get_alias_value(alias, ident)?,
+            };
 
-            match context.get_alias_value(table_alias, column) {
+            match value {
                 Some(value) => Ok(value.clone()),
-                None => Err(EvaluateError::ValueNotFound(column.to_string()).into()),
+                None if context.is_none() => {
+                    Err(EvaluateError::ContextRequiredForIdentEvaluation(expr.clone()).into())
+                }
+                None => Err(EvaluateError::ValueNotFound(ident.to_owned()).into()),
             }
             .map(Evaluated::from)
         }
@@ -90,7 +136,7 @@ async fn evaluate_inner<'a, 'b: 'a, 'c: 'a, T: GStore>(
             let storage =
                 storage.ok_or_else(|| EvaluateError::UnsupportedStatelessExpr(expr.clone()))?;
 
-            let evaluations = select(storage, query, context.as_ref().map(Rc::clone))
+            let evaluations = select(storage, ctx, query, context.as_ref().map(Rc::clone))
                 .await?
                 .map(|row| {
                     let value = match row? {
@@ -143,11 +189,29 @@ async fn evaluate_inner<'a, 'b: 'a, 'c: 'a, T: GStore>(
             Some(value) => Ok(Evaluated::from(value.clone())),
             None => Err(EvaluateError::UnreachableEmptyAggregateValue(*aggr.clone()).into()),
         },
+        Expr::Window(window) => {
+            let value = context
+                .as_ref()
+                .and_then(|context| context.get_window_value(window))
+                .cloned();
+
+            match value {
+                Some(value) => Ok(Evaluated::from(value)),
+                None => Err(EvaluateError::WindowFunctionNotAllowed(window.to_sql()).into()),
+            }
+        }
         Expr::Function(func) => {
             let context = context.as_ref().map(Rc::clone);
             let aggregated = aggregated.as_ref().map(Rc::clone);
 
-            evaluate_function(storage, context, aggregated, func).await
+            // Custom functions bypass `evaluate_function`, so its large stack frame is not
+            // held at each level of functions calling each other.
+            match func.as_ref() {
+                Function::Custom { name, exprs } => {
+                    evaluate_custom_function(storage, ctx, context, aggregated, name, exprs).await
+                }
+                func => evaluate_function(storage, ctx, context, aggregated, func).await,
+            }
         }
         Expr::InList {
             expr,
@@ -175,7 +239,7 @@ async fn evaluate_inner<'a, 'b: 'a, 'c: 'a, T: GStore>(
                 storage.ok_or_else(|| EvaluateError::UnsupportedStatelessExpr(expr.clone()))?;
             let target = eval(target_expr).await?;
 
-            select(storage, subquery, context)
+            select(storage, ctx, subquery, context)
                 .await?
                 .map(|row| {
                     let value = match row? {
@@ -245,7 +309,7 @@ async fn evaluate_inner<'a, 'b: 'a, 'c: 'a, T: GStore>(
             let storage =
                 storage.ok_or_else(|| EvaluateError::UnsupportedStatelessExpr(expr.clone()))?;
 
-            select(storage, subquery, context)
+            select(storage, ctx, subquery, context)
                 .await?
                 .try_next()
                 .await
@@ -308,8 +372,73 @@ async fn evaluate_inner<'a, 'b: 'a, 'c: 'a, T: GStore>(
     }
 }
 
+/// Runs a custom function, arguments are evaluated with `context` and omitted arguments take
+/// their defaults.
+async fn evaluate_custom_function<'a, 'b: 'a, 'c: 'a, T: GStore>(
+    storage: Option<&'a T>,
+    ctx: &'a ExecuteContext,
+    context: Option<Rc<RowContext<'b>>>,
+    aggregated: Option<Rc<HashMap<&'c Aggregate, Value>>>,
+    name: &'b str,
+    exprs: &'b [Expr],
+) -> Result<Evaluated<'a>> {
+    let eval = |expr| {
+        let context = context.as_ref().map(Rc::clone);
+        let aggregated = aggregated.as_ref().map(Rc::clone);
+
+        evaluate_inner(storage, ctx, context, aggregated, expr)
+    };
+
+    let function = storage
+        .ok_or(EvaluateError::UnsupportedCustomFunction)?
+        .fetch_function(name)
+        .await?
+        .filter(|function| function.kind == FunctionKind::Function)
+        .ok_or_else(|| EvaluateError::UnsupportedFunction(name.to_owned()))?;
+
+    validate_args_len(function, exprs.len())?;
+
+    let CustomFunction {
+        func_name,
+        args,
+        body,
+        ..
+    } = function;
+    let exprs = exprs.iter().chain(
+        args.iter()
+            .skip(exprs.len())
+            .filter_map(|arg| arg.default.as_ref()),
+    );
+
+    let values: Vec<Value> = stream::iter(args.iter().zip(exprs))
+        .then(|(arg, expr)| async { eval(expr).await?.try_into_value(&arg.data_type, true) })
+        .try_collect()
+        .await?;
+
+    match body {
+        FunctionBody::Return(body) => {
+            let values = args.iter().map(|arg| arg.name.to_owned()).zip(values);
+            let row = Cow::Owned(Row::Map(values.collect()));
+            let context = RowContext::new(name, row, None);
+
+            evaluate_inner(storage, ctx, Some(Rc::new(context)), None, body).await
+        }
+        FunctionBody::Block(body) => {
+            let storage = storage.ok_or(EvaluateError::UnsupportedCustomFunction)?;
+
+            run_function(storage, ctx, func_name, args, values, body)
+                .await
+                .map(Evaluated::Value)
+        }
+        FunctionBody::Table { .. } => {
+            Err(EvaluateError::TableFunctionInExpression(func_name.to_owned()).into())
+        }
+    }
+}
+
 async fn evaluate_function<'a, 'b: 'a, 'c: 'a, T: GStore>(
     storage: Option<&'a T>,
+    ctx: &'a ExecuteContext,
     context: Option<Rc<RowContext<'b>>>,
     aggregated: Option<Rc<HashMap<&'c Aggregate, Value>>>,
     func: &'b Function,
@@ -320,7 +449,7 @@ async fn evaluate_function<'a, 'b: 'a, 'c: 'a, T: GStore>(
         let context = context.as_ref().map(Rc::clone);
         let aggregated = aggregated.as_ref().map(Rc::clone);
 
-        evaluate_inner(storage, context, aggregated, expr)
+        evaluate_inner(storage, ctx, context, aggregated, expr)
     };
 
     let name = func.to_string();
@@ -332,51 +461,7 @@ async fn evaluate_function<'a, 'b: 'a, 'c: 'a, T: GStore>(
             f::concat(exprs)
         }
         Function::Custom { name, exprs } => {
-            let CustomFunction {
-                func_name,
-                args,
-                body,
-            } = storage
-                .ok_or(EvaluateError::UnsupportedCustomFunction)?
-                .fetch_function(name)
-                .await?
-                .ok_or_else(|| EvaluateError::UnsupportedFunction(name.to_string()))?;
-
-            let min = args.iter().filter(|arg| arg.default.is_none()).count();
-            let max = args.len();
-
-            if !(min..=max).contains(&exprs.len()) {
-                return Err((EvaluateError::FunctionArgsLengthNotWithinRange {
-                    name: func_name.to_owned(),
-                    expected_minimum: min,
-                    expected_maximum: max,
-                    found: exprs.len(),
-                })
-                .into());
-            }
-
-            let exprs = exprs.iter().chain(
-                args.iter()
-                    .skip(exprs.len())
-                    .filter_map(|arg| arg.default.as_ref()),
-            );
-
-            let context = stream::iter(args.iter().zip(exprs))
-                .then(|(arg, expr)| async {
-                    eval(expr)
-                        .await?
-                        .try_into_value(&arg.data_type, true)
-                        .map(|value| (arg.name.to_owned(), value))
-                })
-                .try_collect()
-                .await
-                .map(|values| {
-                    let row = Cow::Owned(Row::Map(values));
-                    let context = RowContext::new(name, row, None);
-                    Some(Rc::new(context))
-                })?;
-
-            evaluate_inner(storage, context, None, body).await
+            evaluate_custom_function(storage, ctx, context, aggregated, name, exprs).await
         }
         Function::ConcatWs { separator, exprs } => {
             let separator = eval(separator).await?;
@@ -670,5 +755,31 @@ async fn evaluate_function<'a, 'b: 'a, 'c: 'a, T: GStore>(
             let expr = eval(expr).await?;
             f::values(expr)
         }
+        Function::Nextval(expr) | Function::Currval(expr) => {
+            let storage = storage.ok_or_else(|| {
+                EvaluateError::UnsupportedStatelessExpr(Expr::Function(Box::new(func.clone())))
+            })?;
+            let sequence_name = match eval(expr).await?.try_into()? {
+                Value::Str(sequence_name) => sequence_name,
+                Value::Null => return Ok(Evaluated::from(Value::Null)),
+                _ => return Err(EvaluateError::FunctionRequiresStringValue(name).into()),
+            };
+
+            let value = match func {
+                Function::Nextval(_) => storage.next_sequence_value(&sequence_name).await?,
+                _ => storage
+                    .fetch_sequence(&sequence_name)
+                    .await?
+                    .map(|sequence| {
+                        sequence.last_value.ok_or_else(|| {
+                            SequenceError::CurrentValueNotDefined(sequence_name.clone())
+                        })
+                    })
+                    .transpose()?,
+            }
+            .ok_or(SequenceError::NotFound(sequence_name))?;
+
+            Ok(Evaluated::from(Value::I64(value)))
+        }
     }
 }
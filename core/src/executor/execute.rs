@@ -1,22 +1,31 @@
 use {
     super::{
         alter::{
-            alter_table, create_index, create_table, delete_function, drop_table, insert_function,
+            alter_table, create_index, create_sequence, create_table, create_trigger, create_view,
+            delete_function, delete_procedure, drop_sequence, drop_table, drop_trigger, drop_view,
+            insert_function, insert_procedure, TableConstraints, TriggerDefinition,
         },
-        fetch::{fetch, fetch_columns},
+        analyze::{explain_analyze, AnalyzedPlan},
+        context::ExecuteContext,
+        explain::{explain, PlanNode},
+        fetch::{fetch, fetch_columns, fetch_joined},
         insert::insert,
+        procedure::call,
+        referential::ReferentialActions,
+        returning::project_returning,
         select::{select, select_with_labels},
+        trigger::Triggers,
         update::Update,
-        validate::{validate_unique, ColumnValidation},
+        validate::{validate_rows, ColumnValidation},
     },
     crate::{
         ast::{
             AstLiteral, BinaryOperator, DataType, Dictionary, Expr, Query, SelectItem, SetExpr,
-            Statement, TableAlias, TableFactor, TableWithJoins, Variable,
+            Statement, TableAlias, TableFactor, TableWithJoins, TriggerEvent, Variable,
         },
-        data::{Key, Row, Schema, Value},
+        data::{FunctionKind, Key, Row, Schema, Value},
         result::Result,
-        store::{GStore, GStoreMut},
+        store::{DataRow, GStore, GStoreMut},
     },
     futures::stream::{StreamExt, TryStreamExt},
     serde::{Deserialize, Serialize},
@@ -28,6 +37,15 @@ use {
 pub enum ExecuteError {
     #[error("table not found: {0}")]
     TableNotFound(String),
+
+    #[error("EXPLAIN supports only SELECT, INSERT, UPDATE and DELETE")]
+    UnsupportedExplain,
+
+    #[error("EXPLAIN ANALYZE supports only SELECT")]
+    UnsupportedExplainAnalyze,
+
+    #[error("{0} exceeds the maximum nesting depth")]
+    NestingDepthExceeded(String),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -44,6 +62,11 @@ pub enum Payload {
     Update(usize),
     DropTable,
     DropFunction,
+    DropSequence,
+    DropView,
+    DropTrigger,
+    DropProcedure,
+    Call,
     AlterTable,
     CreateIndex,
     DropIndex,
@@ -51,28 +74,40 @@ pub enum Payload {
     Commit,
     Rollback,
     ShowVariable(PayloadVariable),
+    Explain(PlanNode),
+    ExplainAnalyze(AnalyzedPlan),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PayloadVariable {
     Tables(Vec<String>),
     Functions(Vec<String>),
+    Views(Vec<String>),
     Version(String),
 }
 
 pub async fn execute<T: GStore + GStoreMut>(
     storage: &mut T,
     statement: &Statement,
+) -> Result<Payload> {
+    execute_with(storage, &ExecuteContext::default(), statement).await
+}
+
+/// Executes `statement` with the parameters of `ctx` bound to its placeholders
+pub async fn execute_with<T: GStore + GStoreMut>(
+    storage: &mut T,
+    ctx: &ExecuteContext,
+    statement: &Statement,
 ) -> Result<Payload> {
     if matches!(
         statement,
         Statement::StartTransaction | Statement::Rollback | Statement::Commit
     ) {
-        return execute_inner(storage, statement).await;
+        return execute_inner(storage, ctx, statement).await;
     }
 
     let autocommit = storage.begin(true).await?;
-    let result = execute_inner(storage, statement).await;
+    let result = execute_inner(storage, ctx, statement).await;
 
     if !autocommit {
         return result;
@@ -88,8 +123,9 @@ pub async fn execute<T: GStore + GStoreMut>(
     }
 }
 
-async fn execute_inner<T: GStore + GStoreMut>(
+pub(super) async fn execute_inner<T: GStore + GStoreMut>(
     storage: &mut T,
+    ctx: &ExecuteContext,
     statement: &Statement,
 ) -> Result<Payload> {
     match statement {
@@ -101,11 +137,19 @@ async fn execute_inner<T: GStore + GStoreMut>(
             if_not_exists,
             source,
             engine,
-            ..
+            foreign_keys,
+            checks,
+            unique_keys,
         } => create_table(
             storage,
+            ctx,
             name,
             columns.as_ref().map(Vec::as_slice),
+            TableConstraints {
+                foreign_keys,
+                checks,
+                unique_keys,
+            },
             *if_not_exists,
             source,
             engine,
@@ -123,10 +167,19 @@ async fn execute_inner<T: GStore + GStoreMut>(
         Statement::CreateIndex {
             name,
             table_name,
-            column,
-        } => create_index(storage, table_name, name, column)
-            .await
-            .map(|_| Payload::CreateIndex),
+            columns,
+            unique,
+            predicate,
+        } => create_index(
+            storage,
+            table_name,
+            name,
+            columns,
+            *unique,
+            predicate.as_ref(),
+        )
+        .await
+        .map(|_| Payload::CreateIndex),
         Statement::DropIndex { name, table_name } => storage
             .drop_index(table_name, name)
             .await
@@ -143,18 +196,36 @@ async fn execute_inner<T: GStore + GStoreMut>(
             table_name,
             columns,
             source,
-        } => insert(storage, table_name, columns, source)
+            on_conflict,
+            returning,
+        } => {
+            insert(
+                storage,
+                ctx,
+                table_name,
+                columns,
+                source,
+                on_conflict.as_ref(),
+                returning,
+            )
             .await
-            .map(Payload::Insert),
+        }
         Statement::Update {
             table_name,
-            selection,
             assignments,
+            from,
+            selection,
+            returning,
         } => {
-            let Schema { column_defs, .. } = storage
+            let schema = storage
                 .fetch_schema(table_name)
                 .await?
                 .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?;
+            let Schema {
+                column_defs,
+                unique_keys,
+                ..
+            } = &schema;
 
             let all_columns = column_defs.as_deref().map(|columns| {
                 columns
@@ -165,69 +236,199 @@ async fn execute_inner<T: GStore + GStoreMut>(
             let columns_to_update = assignments
                 .iter()
                 .map(|assignment| assignment.id.to_owned())
-                .collect();
+                .collect::<Vec<_>>();
 
-            let update = Update::new(storage, table_name, assignments, column_defs.as_deref())?;
+            let update = Update::new(
+                storage,
+                ctx,
+                table_name,
+                assignments,
+                column_defs.as_deref(),
+                unique_keys,
+            )?;
+            let triggers = Triggers::fetch(storage, table_name, TriggerEvent::Update).await?;
+            let old_row = |row: &Row| match triggers.is_empty() {
+                true => None,
+                false => Some(DataRow::from(row.clone())),
+            };
 
-            let rows = fetch(storage, table_name, all_columns, selection.as_ref())
-                .await?
-                .and_then(|item| {
-                    let update = &update;
-                    let (key, row) = item;
+            let (rows, old_rows): (Vec<(Key, Row)>, Vec<Option<DataRow>>) = match from {
+                Some(from) => {
+                    fetch_joined(
+                        storage,
+                        ctx,
+                        table_name,
+                        all_columns,
+                        from,
+                        selection.as_ref(),
+                    )
+                    .await?
+                    .and_then(|(key, row, context)| {
+                        let update = &update;
+                        let old_row = old_row(&row);
 
-                    async move {
-                        let row = update.apply(row).await?;
+                        async move {
+                            let row = update.apply(row, Some(context)).await?;
 
-                        Ok((key, row))
+                            Ok(((key, row), old_row))
+                        }
+                    })
+                    .try_collect::<Vec<_>>()
+                    .await?
+                }
+                None => {
+                    fetch(storage, ctx, table_name, all_columns, selection.as_ref())
+                        .await?
+                        .and_then(|item| {
+                            let update = &update;
+                            let (key, row) = item;
+                            let old_row = old_row(&row);
+
+                            async move {
+                                let row = update.apply(row, None).await?;
+
+                                Ok(((key, row), old_row))
+                            }
+                        })
+                        .try_collect::<Vec<_>>()
+                        .await?
+                }
+            }
+            .into_iter()
+            .unzip();
+
+            let rows = rows
+                .into_iter()
+                .map(|(key, row)| (key, row.into()))
+                .collect::<Vec<(Key, DataRow)>>();
+            let (rows, old_rows) = match triggers.is_empty() {
+                true => (rows, old_rows),
+                false => {
+                    let mut fired = (Vec::new(), Vec::new());
+                    for ((key, mut row), old_row) in rows.into_iter().zip(old_rows) {
+                        if triggers
+                            .fire_before(storage, ctx, Some(&mut row), old_row.as_ref())
+                            .await?
+                        {
+                            fired.0.push((key, row));
+                            fired.1.push(old_row);
+                        }
                     }
-                })
-                .try_collect::<Vec<(Key, Row)>>()
-                .await?;
+
+                    fired
+                }
+            };
 
             if let Some(column_defs) = column_defs {
-                let column_validation =
-                    ColumnValidation::SpecifiedColumns(&column_defs, columns_to_update);
                 let rows = rows.iter().filter_map(|(_, row)| match row {
-                    Row::Vec { values, .. } => Some(values.as_slice()),
-                    Row::Map(_) => None,
+                    DataRow::Vec(values) => Some(values.as_slice()),
+                    DataRow::Map(_) => None,
                 });
+                let columns_to_update = columns_to_update
+                    .into_iter()
+                    .chain(triggers.set_columns().cloned())
+                    .collect();
 
-                validate_unique(storage, table_name, column_validation, rows).await?;
+                let column_validation =
+                    ColumnValidation::SpecifiedColumns(column_defs, columns_to_update);
+                validate_rows(storage, &schema, column_validation, rows).await?;
             }
 
             let num_rows = rows.len();
-            let rows = rows
-                .into_iter()
-                .map(|(key, row)| (key, row.into()))
-                .collect();
+            let payload = match returning.is_empty() {
+                true => Payload::Update(num_rows),
+                false => {
+                    let rows = rows.iter().map(|(_, row)| row.clone()).collect();
 
-            storage
-                .insert_data(table_name, rows)
-                .await
-                .map(|_| Payload::Update(num_rows))
+                    project_returning(storage, ctx, table_name, returning, rows).await?
+                }
+            };
+            let referential_actions = ReferentialActions::plan(
+                storage,
+                table_name,
+                rows.iter().map(|(key, row)| (key, Some(row))),
+            )
+            .await?;
+            let updated = match triggers.is_empty() {
+                true => Vec::new(),
+                false => rows.iter().map(|(_, row)| row.clone()).collect(),
+            };
+
+            storage.insert_data(table_name, rows).await?;
+            referential_actions.apply(storage).await?;
+
+            for (row, old_row) in updated.iter().zip(old_rows) {
+                triggers
+                    .fire_after(storage, ctx, Some(row), old_row.as_ref())
+                    .await?;
+            }
+
+            Ok(payload)
         }
         Statement::Delete {
             table_name,
+            using,
             selection,
+            returning,
         } => {
             let columns = fetch_columns(storage, table_name).await?.map(Rc::from);
-            let keys = fetch(storage, table_name, columns, selection.as_ref())
-                .await?
-                .map_ok(|(key, _)| key)
-                .try_collect::<Vec<_>>()
-                .await?;
+            let rows = match using {
+                Some(using) => {
+                    fetch_joined(storage, ctx, table_name, columns, using, selection.as_ref())
+                        .await?
+                        .map_ok(|(key, row, _)| (key, row.into()))
+                        .try_collect::<Vec<_>>()
+                        .await?
+                }
+                None => {
+                    fetch(storage, ctx, table_name, columns, selection.as_ref())
+                        .await?
+                        .map_ok(|(key, row)| (key, row.into()))
+                        .try_collect::<Vec<_>>()
+                        .await?
+                }
+            };
+            let triggers = Triggers::fetch(storage, table_name, TriggerEvent::Delete).await?;
+            let rows = match triggers.is_empty() {
+                true => rows,
+                false => {
+                    let mut fired = Vec::new();
+                    for (key, row) in rows {
+                        if triggers.fire_before(storage, ctx, None, Some(&row)).await? {
+                            fired.push((key, row));
+                        }
+                    }
 
-            let num_keys = keys.len();
+                    fired
+                }
+            };
+            let (keys, rows): (Vec<_>, Vec<DataRow>) = rows.into_iter().unzip();
+            let deleted = match triggers.is_empty() {
+                true => Vec::new(),
+                false => rows.clone(),
+            };
 
-            storage
-                .delete_data(table_name, keys)
-                .await
-                .map(|_| Payload::Delete(num_keys))
+            let payload = match returning.is_empty() {
+                true => Payload::Delete(keys.len()),
+                false => project_returning(storage, ctx, table_name, returning, rows).await?,
+            };
+            let referential_actions =
+                ReferentialActions::plan(storage, table_name, keys.iter().map(|key| (key, None)))
+                    .await?;
+
+            storage.delete_data(table_name, keys).await?;
+            referential_actions.apply(storage).await?;
+
+            for row in &deleted {
+                triggers.fire_after(storage, ctx, None, Some(row)).await?;
+            }
+
+            Ok(payload)
         }
 
         //- Selection
         Statement::Query(query) => {
-            let (labels, rows) = select_with_labels(storage, query, None).await?;
+            let (labels, rows) = select_with_labels(storage, ctx, query, None).await?;
 
             match labels {
                 Some(labels) => rows
@@ -256,9 +457,21 @@ async fn execute_inner<T: GStore + GStoreMut>(
 
             Ok(Payload::ShowColumns(output))
         }
+        Statement::Explain {
+            statement,
+            analyze: false,
+        } => explain(statement, None).map(Payload::Explain),
+        Statement::Explain {
+            statement,
+            analyze: true,
+        } => explain_analyze(storage, ctx, statement)
+            .await
+            .map(Payload::ExplainAnalyze),
         Statement::ShowIndexes(table_name) => {
             let query = Query {
+                with: None,
                 body: SetExpr::Select(Box::new(crate::ast::Select {
+                    distinct: None,
                     projection: vec![SelectItem::Wildcard],
                     from: TableWithJoins {
                         relation: TableFactor::Dictionary {
@@ -278,6 +491,7 @@ async fn execute_inner<T: GStore + GStoreMut>(
                         ))),
                     }),
                     group_by: Vec::new(),
+                    grouping_sets: Vec::new(),
                     having: None,
                 })),
                 order_by: Vec::new(),
@@ -285,7 +499,7 @@ async fn execute_inner<T: GStore + GStoreMut>(
                 offset: None,
             };
 
-            let (labels, rows) = select_with_labels(storage, &query, None).await?;
+            let (labels, rows) = select_with_labels(storage, ctx, &query, None).await?;
             let labels = labels.unwrap_or_default();
             let rows = rows
                 .map(|row| row?.try_into_vec())
@@ -300,47 +514,25 @@ async fn execute_inner<T: GStore + GStoreMut>(
         }
         Statement::ShowVariable(variable) => match variable {
             Variable::Tables => {
-                let query = Query {
-                    body: SetExpr::Select(Box::new(crate::ast::Select {
-                        projection: vec![SelectItem::Expr {
-                            expr: Expr::Identifier("TABLE_NAME".to_owned()),
-                            label: "TABLE_NAME".to_owned(),
-                        }],
-                        from: TableWithJoins {
-                            relation: TableFactor::Dictionary {
-                                dict: Dictionary::GlueTables,
-                                alias: TableAlias {
-                                    name: "GLUE_TABLES".to_owned(),
-                                    columns: Vec::new(),
-                                },
-                            },
-                            joins: Vec::new(),
-                        },
-                        selection: None,
-                        group_by: Vec::new(),
-                        having: None,
-                    })),
-                    order_by: Vec::new(),
-                    limit: None,
-                    offset: None,
-                };
-
-                let table_names = select(storage, &query, None)
-                    .await?
-                    .map(|row| row?.try_into_vec())
-                    .try_collect::<Vec<Vec<Value>>>()
-                    .await?
-                    .iter()
-                    .flat_map(|values| values.iter().map(|value| value.into()))
-                    .collect::<Vec<_>>();
+                let table_names =
+                    fetch_dictionary_names(storage, ctx, Dictionary::GlueTables, "TABLE_NAME")
+                        .await?;
 
                 Ok(Payload::ShowVariable(PayloadVariable::Tables(table_names)))
             }
+            Variable::Views => {
+                let view_names =
+                    fetch_dictionary_names(storage, ctx, Dictionary::GlueViews, "VIEW_NAME")
+                        .await?;
+
+                Ok(Payload::ShowVariable(PayloadVariable::Views(view_names)))
+            }
             Variable::Functions => {
                 let mut function_desc: Vec<_> = storage
                     .fetch_all_functions()
                     .await?
                     .iter()
+                    .filter(|f| f.kind == FunctionKind::Function)
                     .map(|f| f.to_str())
                     .collect();
                 function_desc.sort();
@@ -360,12 +552,117 @@ async fn execute_inner<T: GStore + GStoreMut>(
             or_replace,
             name,
             args,
-            return_,
-        } => insert_function(storage, name, args, *or_replace, return_)
+            body,
+        } => insert_function(storage, name, args, *or_replace, body)
             .await
             .map(|_| Payload::Create),
         Statement::DropFunction { if_exists, names } => delete_function(storage, names, *if_exists)
             .await
             .map(|_| Payload::DropFunction),
+        Statement::CreateProcedure {
+            or_replace,
+            name,
+            args,
+            body,
+        } => insert_procedure(storage, name, args, *or_replace, body)
+            .await
+            .map(|_| Payload::Create),
+        Statement::Call { name, args } => {
+            call(storage, ctx, name, args).await.map(|_| Payload::Call)
+        }
+        Statement::DropProcedure { if_exists, names } => {
+            delete_procedure(storage, names, *if_exists)
+                .await
+                .map(|_| Payload::DropProcedure)
+        }
+        Statement::CreateView {
+            or_replace,
+            name,
+            columns,
+            query,
+        } => create_view(storage, ctx, name, columns, query, *or_replace)
+            .await
+            .map(|_| Payload::Create),
+        Statement::DropView { if_exists, names } => drop_view(storage, names, *if_exists)
+            .await
+            .map(|_| Payload::DropView),
+        Statement::CreateTrigger {
+            or_replace,
+            name,
+            table_name,
+            timing,
+            events,
+            action,
+        } => {
+            let definition = TriggerDefinition {
+                table_name,
+                timing: *timing,
+                events,
+                action,
+            };
+
+            create_trigger(storage, name, definition, *or_replace)
+                .await
+                .map(|_| Payload::Create)
+        }
+        Statement::DropTrigger { if_exists, names } => drop_trigger(storage, names, *if_exists)
+            .await
+            .map(|_| Payload::DropTrigger),
+        Statement::CreateSequence {
+            if_not_exists,
+            name,
+            options,
+        } => create_sequence(storage, name, options, *if_not_exists)
+            .await
+            .map(|_| Payload::Create),
+        Statement::DropSequence { if_exists, names } => drop_sequence(storage, names, *if_exists)
+            .await
+            .map(|_| Payload::DropSequence),
     }
 }
+
+async fn fetch_dictionary_names<T: GStore>(
+    storage: &T,
+    ctx: &ExecuteContext,
+    dict: Dictionary,
+    column: &str,
+) -> Result<Vec<String>> {
+    let query = Query {
+        with: None,
+        body: SetExpr::Select(Box::new(crate::ast::Select {
+            distinct: None,
+            projection: vec![SelectItem::Expr {
+                expr: Expr::Identifier(column.to_owned()),
+                label: column.to_owned(),
+            }],
+            from: TableWithJoins {
+                relation: TableFactor::Dictionary {
+                    alias: TableAlias {
+                        name: dict.to_string(),
+                        columns: Vec::new(),
+                    },
+                    dict,
+                },
+                joins: Vec::new(),
+            },
+            selection: None,
+            group_by: Vec::new(),
+            grouping_sets: Vec::new(),
+            having: None,
+        })),
+        order_by: Vec::new(),
+        limit: None,
+        offset: None,
+    };
+
+    let names = select(storage, ctx, &query, None)
+        .await?
+        .map(|row| row?.try_into_vec())
+        .try_collect::<Vec<Vec<Value>>>()
+        .await?
+        .iter()
+        .flat_map(|values| values.iter().map(|value| value.into()))
+        .collect();
+
+    Ok(names)
+}
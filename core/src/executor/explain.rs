@@ -0,0 +1,450 @@
+use {
+    super::{
+        aggregate::is_aggregate,
+        analyze::{PlanStats, Probe, Profiler, Stage},
+        execute::ExecuteError,
+    },
+    crate::{
+        ast::{
+            BinaryOperator, Cte, Distinct, Expr, IndexItem, Join, JoinConstraint, JoinExecutor,
+            JoinOperator, OrderByExpr, Query, Select, SetExpr, SetOperator, Statement, TableAlias,
+            TableFactor, TableWithJoins, ToSql, ToSqlUnquoted, Values, With,
+        },
+        result::Result,
+    },
+    serde::{Deserialize, Serialize},
+    std::fmt,
+};
+
+/// Operation of the plan returned by `EXPLAIN`, `children` are the inputs of the operation
+/// and `stats` are recorded only by `EXPLAIN ANALYZE`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlanNode {
+    pub operation: String,
+    pub detail: Option<String>,
+    pub children: Vec<PlanNode>,
+    pub stats: Option<PlanStats>,
+}
+
+impl PlanNode {
+    pub fn new(operation: &str, detail: Option<String>, children: Vec<PlanNode>) -> Self {
+        Self {
+            operation: operation.to_owned(),
+            detail,
+            children,
+            stats: None,
+        }
+    }
+
+    pub fn with_stats(self, stats: Option<PlanStats>) -> Self {
+        Self { stats, ..self }
+    }
+
+    fn fmt_tree(&self, f: &mut fmt::Formatter, indent: &str) -> fmt::Result {
+        match &self.detail {
+            Some(detail) => write!(f, "{}: {detail}", self.operation)?,
+            None => write!(f, "{}", self.operation)?,
+        }
+
+        match self.stats {
+            Some(PlanStats {
+                rows_out, elapsed, ..
+            }) if self.children.is_empty() => {
+                writeln!(f, " (rows: {rows_out}, time: {elapsed:?})")?
+            }
+            Some(PlanStats {
+                rows_in,
+                rows_out,
+                elapsed,
+            }) => writeln!(f, " (rows: {rows_in} -> {rows_out}, time: {elapsed:?})")?,
+            None => writeln!(f)?,
+        }
+
+        let last = self.children.len().saturating_sub(1);
+        for (i, child) in self.children.iter().enumerate() {
+            let (branch, next) = match i == last {
+                true => ("└─ ", "   "),
+                false => ("├─ ", "│  "),
+            };
+
+            write!(f, "{indent}{branch}")?;
+            child.fmt_tree(f, &format!("{indent}{next}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for PlanNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_tree(f, "")
+    }
+}
+
+/// Builds the plan of `statement`, annotated with the stats recorded by `profiler`
+/// while running it for `EXPLAIN ANALYZE`
+pub fn explain(statement: &Statement, profiler: Option<&Profiler>) -> Result<PlanNode> {
+    match statement {
+        Statement::Query(query) => Ok(explain_query(query, Probe::new(profiler))),
+        Statement::Insert {
+            table_name, source, ..
+        } => Ok(PlanNode::new(
+            "Insert",
+            Some(table_name.to_owned()),
+            vec![explain_query(source, Probe::new(profiler))],
+        )),
+        Statement::Update {
+            table_name,
+            from,
+            selection,
+            ..
+        } => {
+            let rows = match from {
+                Some(from) => explain_table_with_joins(from, Probe::disabled()),
+                None => PlanNode::new("Table Scan", Some(table_name.to_owned()), Vec::new()),
+            };
+
+            Ok(PlanNode::new(
+                "Update",
+                Some(table_name.to_owned()),
+                vec![explain_filter(selection.as_ref(), rows, None)],
+            ))
+        }
+        Statement::Delete {
+            table_name,
+            using,
+            selection,
+            ..
+        } => {
+            let rows = match using {
+                Some(using) => explain_table_with_joins(using, Probe::disabled()),
+                None => PlanNode::new("Table Scan", Some(table_name.to_owned()), Vec::new()),
+            };
+
+            Ok(PlanNode::new(
+                "Delete",
+                Some(table_name.to_owned()),
+                vec![explain_filter(selection.as_ref(), rows, None)],
+            ))
+        }
+        _ => Err(ExecuteError::UnsupportedExplain.into()),
+    }
+}
+
+fn explain_query(query: &Query, probe: Probe) -> PlanNode {
+    let Query {
+        with,
+        body,
+        order_by,
+        limit,
+        offset,
+    } = query;
+
+    let node = explain_set_expr(body, order_by, probe);
+    let node = match (limit, offset) {
+        (None, None) => node,
+        _ => {
+            let detail = [("LIMIT", limit), ("OFFSET", offset)]
+                .into_iter()
+                .filter_map(|(keyword, expr)| {
+                    expr.as_ref()
+                        .map(|expr| format!("{keyword} {}", expr.to_sql_unquoted()))
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            let stats = probe.stats(Stage::Limit);
+
+            PlanNode::new("Limit", Some(detail), vec![node]).with_stats(stats)
+        }
+    };
+
+    match with {
+        Some(With {
+            recursive,
+            cte_tables,
+        }) => {
+            let operation = match recursive {
+                true => "With Recursive",
+                false => "With",
+            };
+            let children = cte_tables
+                .iter()
+                .map(|Cte { alias, query }| {
+                    PlanNode::new(
+                        "CTE",
+                        Some(alias.name.to_owned()),
+                        vec![explain_query(query, Probe::disabled())],
+                    )
+                })
+                .chain([node])
+                .collect();
+
+            PlanNode::new(operation, None, children)
+        }
+        None => node,
+    }
+}
+
+fn explain_set_expr(set_expr: &SetExpr, order_by: &[OrderByExpr], probe: Probe) -> PlanNode {
+    let (node, distinct) = match set_expr {
+        SetExpr::Select(select) => (explain_select(select, probe), select.distinct.as_ref()),
+        SetExpr::Values(Values(values_list)) => {
+            let detail = format!("{} rows", values_list.len());
+            let node = PlanNode::new("Values", Some(detail), Vec::new())
+                .with_stats(probe.stats(Stage::Fetch));
+
+            (node, None)
+        }
+        SetExpr::SetOperation {
+            op,
+            all,
+            left,
+            right,
+        } => {
+            let operation = match op {
+                SetOperator::Union => "Union",
+                SetOperator::Intersect => "Intersect",
+                SetOperator::Except => "Except",
+            };
+            let detail = all.then(|| "ALL".to_owned());
+            let (left_probe, right_probe) = probe.operands(left);
+            let children = vec![
+                explain_set_expr(left, &[], left_probe),
+                explain_set_expr(right, &[], right_probe),
+            ];
+            let node =
+                PlanNode::new(operation, detail, children).with_stats(probe.stats(Stage::Fetch));
+
+            (node, None)
+        }
+    };
+
+    let node = match order_by.is_empty() {
+        true => node,
+        false => PlanNode::new("Sort", Some(join_sql(order_by)), vec![node])
+            .with_stats(probe.stats(Stage::Sort)),
+    };
+
+    let node = match distinct {
+        Some(Distinct::Distinct) => PlanNode::new("Distinct", None, vec![node]),
+        Some(Distinct::On(exprs)) => {
+            let detail = format!("ON ({})", join_sql(exprs));
+
+            PlanNode::new("Distinct", Some(detail), vec![node])
+        }
+        None => return node,
+    };
+
+    node.with_stats(probe.stats(Stage::Distinct))
+}
+
+fn explain_select(select: &Select, probe: Probe) -> PlanNode {
+    let Select {
+        projection,
+        from,
+        selection,
+        group_by,
+        grouping_sets,
+        having,
+        ..
+    } = select;
+
+    let node = explain_filter(
+        selection.as_ref(),
+        explain_table_with_joins(from, probe),
+        probe.stats(Stage::Filter),
+    );
+
+    if !is_aggregate(projection, group_by, grouping_sets) {
+        return node;
+    }
+
+    let group_by = [join_sql(group_by), join_sql(grouping_sets)]
+        .into_iter()
+        .filter(|sql| !sql.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let group_by = (!group_by.is_empty()).then(|| format!("GROUP BY {group_by}"));
+    let having = having
+        .as_ref()
+        .map(|having| format!("HAVING {}", having.to_sql_unquoted()));
+    let detail = group_by
+        .into_iter()
+        .chain(having)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let detail = (!detail.is_empty()).then_some(detail);
+
+    PlanNode::new("Aggregate", detail, vec![node]).with_stats(probe.stats(Stage::Aggregate))
+}
+
+fn explain_filter(selection: Option<&Expr>, node: PlanNode, stats: Option<PlanStats>) -> PlanNode {
+    match selection {
+        Some(expr) => {
+            PlanNode::new("Filter", Some(expr.to_sql_unquoted()), vec![node]).with_stats(stats)
+        }
+        None => node,
+    }
+}
+
+fn explain_table_with_joins(table_with_joins: &TableWithJoins, probe: Probe) -> PlanNode {
+    let TableWithJoins { relation, joins } = table_with_joins;
+    let node = explain_table_factor(relation).with_stats(probe.stats(Stage::Fetch));
+
+    joins.iter().enumerate().fold(node, |node, (i, join)| {
+        explain_join(join, node).with_stats(probe.stats(Stage::Join(i)))
+    })
+}
+fn explain_join(join: &Join, node: PlanNode) -> PlanNode {
+    let Join {
+        relation,
+        join_operator,
+        join_executor,
+    } = join;
+
+    let (operator, constraint) = match join_operator {
+        JoinOperator::Inner(constraint) => ("INNER", Some(constraint)),
+        JoinOperator::LeftOuter(constraint) => ("LEFT OUTER", Some(constraint)),
+        JoinOperator::RightOuter(constraint) => ("RIGHT OUTER", Some(constraint)),
+        JoinOperator::FullOuter(constraint) => ("FULL OUTER", Some(constraint)),
+        JoinOperator::Cross => ("CROSS", None),
+    };
+
+    let mut detail = vec![operator.to_owned()];
+    if let Some(JoinConstraint::On(expr)) = constraint {
+        detail.push(format!("ON {}", expr.to_sql_unquoted()));
+    }
+
+    let operation = match join_executor {
+        JoinExecutor::NestedLoop => "Nested Loop Join",
+        JoinExecutor::Hash {
+            key_expr,
+            value_expr,
+            where_clause,
+        } => {
+            detail.push(format!(
+                "HASH KEY {} = {}",
+                key_expr.to_sql_unquoted(),
+                value_expr.to_sql_unquoted()
+            ));
+
+            if let Some(expr) = where_clause {
+                detail.push(format!("HASH FILTER {}", expr.to_sql_unquoted()));
+            }
+
+            "Hash Join"
+        }
+    };
+
+    PlanNode::new(
+        operation,
+        Some(detail.join(", ")),
+        vec![node, explain_table_factor(relation)],
+    )
+}
+
+fn explain_table_factor(table_factor: &TableFactor) -> PlanNode {
+    match table_factor {
+        TableFactor::Table { name, alias, index } => {
+            let table = match alias {
+                Some(TableAlias { name: alias, .. }) => format!("{name} AS {alias}"),
+                None => name.to_owned(),
+            };
+
+            let (operation, detail) = match index {
+                None => ("Table Scan", table),
+                Some(IndexItem::PrimaryKey(expr)) => (
+                    "Primary Key Lookup",
+                    format!("{table}, KEY = {}", expr.to_sql_unquoted()),
+                ),
+                Some(IndexItem::CompositePrimaryKey {
+                    exprs,
+                    full_key,
+                    cmp_expr: Some((op, expr)),
+                }) if !full_key => {
+                    let mut detail = vec![table];
+
+                    if !exprs.is_empty() {
+                        detail.push(format!("PREFIX = ({})", join_sql(exprs)));
+                    }
+
+                    detail.push(format!(
+                        "KEY {} {}",
+                        BinaryOperator::from(op.clone()).to_sql(),
+                        expr.to_sql_unquoted()
+                    ));
+
+                    ("Primary Key Range Scan", detail.join(", "))
+                }
+                Some(IndexItem::CompositePrimaryKey {
+                    exprs, full_key, ..
+                }) => {
+                    let operation = match full_key {
+                        true => "Primary Key Lookup",
+                        false => "Primary Key Prefix Scan",
+                    };
+
+                    (operation, format!("{table}, KEY = ({})", join_sql(exprs)))
+                }
+                Some(IndexItem::NonClustered {
+                    name: index_name,
+                    asc,
+                    prefix,
+                    cmp_expr,
+                }) => {
+                    let mut detail = vec![format!("{table} USING {index_name}")];
+
+                    if !prefix.is_empty() {
+                        detail.push(format!("PREFIX = ({})", join_sql(prefix)));
+                    }
+
+                    if let Some((op, expr)) = cmp_expr {
+                        detail.push(format!(
+                            "KEY {} {}",
+                            BinaryOperator::from(op.clone()).to_sql(),
+                            expr.to_sql_unquoted()
+                        ));
+                    }
+
+                    match asc {
+                        Some(true) => detail.push("ASC".to_owned()),
+                        Some(false) => detail.push("DESC".to_owned()),
+                        None => {}
+                    }
+
+                    ("Index Scan", detail.join(", "))
+                }
+            };
+
+            PlanNode::new(operation, Some(detail), Vec::new())
+        }
+        TableFactor::Derived { subquery, alias } => PlanNode::new(
+            "Subquery",
+            Some(alias.name.to_owned()),
+            vec![explain_query(subquery, Probe::disabled())],
+        ),
+        TableFactor::Series { alias, size } => {
+            let detail = format!("{} ({})", alias.name, size.to_sql_unquoted());
+
+            PlanNode::new("Series", Some(detail), Vec::new())
+        }
+        TableFactor::Dictionary { dict, alias } => {
+            let detail = format!("{dict} AS {}", alias.name);
+
+            PlanNode::new("Dictionary Scan", Some(detail), Vec::new())
+        }
+        TableFactor::Function { name, args, alias } => {
+            let detail = format!("{name}({}) AS {}", join_sql(args), alias.name);
+
+            PlanNode::new("Function Scan", Some(detail), Vec::new())
+        }
+    }
+}
+
+fn join_sql<T: ToSqlUnquoted>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(ToSqlUnquoted::to_sql_unquoted)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,21 +1,37 @@
 use {
-    super::{context::RowContext, evaluate::evaluate_stateless, filter::check_expr},
+    super::{
+        analyze::Probe,
+        context::{ExecuteContext, RowContext, Scope},
+        evaluate::evaluate_stateless_with,
+        filter::{check_expr, Filter},
+        join,
+        procedure::validate_args_len,
+    },
     crate::{
         ast::{
             ToSql,
             {
-                ColumnDef, ColumnUniqueOption, Dictionary, Expr, IndexItem, Join, Query, Select,
-                SelectItem, SetExpr, TableAlias, TableFactor, TableWithJoins, ToSqlUnquoted,
-                Values,
+                Check, ColumnDef, Dictionary, Expr, ForeignKey, FunctionBody, IndexItem, Join,
+                Query, Select, SelectItem, SetExpr, Statement, TableAlias, TableFactor,
+                TableWithJoins, ToSqlUnquoted, UniqueKey, Values,
             },
         },
-        data::{get_alias, get_index, Key, Row, Value},
-        executor::{evaluate::evaluate, select::select},
+        data::{
+            get_alias, get_index, CustomFunction, FunctionKind, Key, Row, Schema, TableError, Value,
+        },
+        executor::{
+            evaluate::evaluate,
+            select::{leftmost_operand, select, select_with_labels},
+        },
+        plan::plan,
         result::Result,
-        store::{DataRow, GStore},
+        store::{DataRow, GStore, RowIter},
     },
     async_recursion::async_recursion,
-    futures::stream::{self, Stream, StreamExt, TryStreamExt},
+    futures::{
+        future::ready,
+        stream::{self, Stream, StreamExt, TryStreamExt},
+    },
     iter_enum::Iterator,
     itertools::Itertools,
     serde::Serialize,
@@ -36,10 +52,17 @@ pub enum FetchError {
 
     #[error("table '{0}' has {1} columns available but {2} column aliases specified")]
     TooManyColumnAliases(String, usize, usize),
+
+    #[error("table function not found: {0}")]
+    TableFunctionNotFound(String),
+
+    #[error("table function '{0}' returns {1} columns but its query selects {2} columns")]
+    TableFunctionColumnsMismatch(String, usize, usize),
 }
 
 pub async fn fetch<'a, T: GStore>(
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     table_name: &'a str,
     columns: Option<Rc<[String]>>,
     where_clause: Option<&'a Expr>,
@@ -68,7 +91,7 @@ pub async fn fetch<'a, T: GStore>(
 
                 let context = RowContext::new(table_name, Cow::Borrowed(&row), None);
 
-                check_expr(storage, Some(Rc::new(context)), None, expr)
+                check_expr(storage, ctx, Some(Rc::new(context)), None, expr)
                     .await
                     .map(|pass| pass.then_some((key, row)))
             }
@@ -77,21 +100,98 @@ pub async fn fetch<'a, T: GStore>(
     Ok(rows)
 }
 
+/// Fetches target rows of UPDATE ... FROM or DELETE ... USING, each with the first
+/// joined row context which satisfies the WHERE clause.
+///
+/// Target rows are joined in a single pass, so the joined tables are fetched and hashed
+/// once rather than once per target row.
+pub async fn fetch_joined<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    table_name: &'a str,
+    columns: Option<Rc<[String]>>,
+    from: &'a TableWithJoins,
+    where_clause: Option<&'a Expr>,
+) -> Result<impl Stream<Item = Result<(Key, Row, Rc<RowContext<'a>>)>> + 'a> {
+    let TableWithJoins { relation, joins } = from;
+    let columns = columns.unwrap_or_else(|| Rc::from([]));
+    let alias = get_alias(relation);
+    let targets = fetch_table_rows(storage, ctx, table_name, get_index(relation), &None)
+        .await?
+        .map_ok(|(key, data_row)| {
+            let row = match data_row {
+                DataRow::Vec(values) => Row::Vec {
+                    columns: Rc::clone(&columns),
+                    values,
+                },
+                DataRow::Map(values) => Row::Map(values),
+            };
+            let context = Rc::new(RowContext::new(alias, Cow::Owned(row.clone()), None));
+
+            (key, row, context)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let positions = targets
+        .iter()
+        .enumerate()
+        .map(|(position, (_, _, context))| (Rc::as_ptr(context), position))
+        .collect::<HashMap<_, _>>();
+    let mut matched: Vec<Option<Rc<RowContext<'a>>>> = vec![None; targets.len()];
+
+    let contexts = targets.iter().map(|(_, _, context)| Ok(Rc::clone(context)));
+    let mut joined = join::Join::new(storage, ctx, relation, joins, None, Probe::disabled())
+        .apply(stream::iter(contexts.collect::<Vec<_>>()))
+        .await?;
+    let filter = Filter::new(storage, ctx, where_clause, None, None);
+
+    while let Some(context) = joined.try_next().await? {
+        let position = match positions.get(&Rc::as_ptr(target_context(&context))) {
+            Some(&position) if matched[position].is_none() => position,
+            _ => continue,
+        };
+
+        if filter.check(Rc::clone(&context)).await? {
+            matched[position] = Some(context);
+        }
+    }
+
+    let rows = targets
+        .into_iter()
+        .zip(matched)
+        .filter_map(|((key, row, _), context)| context.map(|context| Ok((key, row, context))));
+
+    Ok(stream::iter(rows))
+}
+
+/// Returns the context of the target row a joined row context was built upon
+fn target_context<'a, 'b>(context: &'b Rc<RowContext<'a>>) -> &'b Rc<RowContext<'a>> {
+    match context.as_ref() {
+        RowContext::Data {
+            next: Some(next), ..
+        } => target_context(next),
+        _ => context,
+    }
+}
+
 #[derive(futures_enum::Stream)]
-pub enum Rows<I1, I2, I3, I4> {
+pub enum Rows<I1, I2, I3, I4, I5, I6> {
     Derived(I1),
     Table(I2),
     Series(I3),
     Dictionary(I4),
+    Cte(I5),
+    Function(I6),
 }
 
 pub async fn fetch_relation_rows<'a, T: GStore>(
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     table_factor: &'a TableFactor,
     filter_context: &Option<Rc<RowContext<'a>>>,
 ) -> Result<impl Stream<Item = Result<Row>> + 'a> {
     let columns = Rc::from(
-        fetch_relation_columns(storage, table_factor)
+        fetch_relation_columns(storage, ctx, table_factor, filter_context)
             .await?
             .unwrap_or_default(),
     );
@@ -99,98 +199,50 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
     match table_factor {
         TableFactor::Derived { subquery, .. } => {
             let filter_context = filter_context.as_ref().map(Rc::clone);
+            let rows = select(storage, ctx, subquery, filter_context)
+                .await?
+                .map_ok(move |row| match row {
+                    Row::Vec { values, .. } => Row::Vec {
+                        columns: Rc::clone(&columns),
+                        values,
+                    },
+                    Row::Map(values) => Row::Map(values),
+                });
+
+            Ok(Rows::Derived(rows))
+        }
+        TableFactor::Table { name, .. } => {
+            if let Some((_, rows)) = filter_context
+                .as_ref()
+                .and_then(|context| context.get_cte(name))
+            {
+                let rows = Rc::clone(rows);
+                let rows = (0..rows.len()).map(move |i| match &rows[i] {
+                    Row::Vec { values, .. } => Ok(Row::Vec {
+                        columns: Rc::clone(&columns),
+                        values: values.clone(),
+                    }),
+                    Row::Map(values) => Ok(Row::Map(values.clone())),
+                });
+
+                return Ok(Rows::Cte(stream::iter(rows)));
+            }
+
             let rows =
-                select(storage, subquery, filter_context)
+                fetch_table_rows(storage, ctx, name, get_index(table_factor), filter_context)
                     .await?
-                    .map_ok(move |row| match row {
-                        Row::Vec { values, .. } => Row::Vec {
+                    .map_ok(move |(_, data_row)| match data_row {
+                        DataRow::Vec(values) => Row::Vec {
                             columns: Rc::clone(&columns),
                             values,
                         },
-                        Row::Map(values) => Row::Map(values),
+                        DataRow::Map(values) => Row::Map(values),
                     });
 
-            Ok(Rows::Derived(rows))
-        }
-        TableFactor::Table { name, .. } => {
-            let rows = {
-                #[derive(Iterator)]
-                enum Rows<I1, I2, I3> {
-                    Indexed(I1),
-                    PrimaryKey(I2),
-                    FullScan(I3),
-                }
-
-                match get_index(table_factor) {
-                    Some(IndexItem::NonClustered {
-                        name: index_name,
-                        asc,
-                        cmp_expr,
-                    }) => {
-                        let cmp_value = match cmp_expr {
-                            Some((op, expr)) => {
-                                let evaluated = evaluate(storage, None, None, expr).await?;
-
-                                Some((op, evaluated.try_into()?))
-                            }
-                            None => None,
-                        };
-
-                        let rows = storage
-                            .scan_indexed_data(name, index_name, *asc, cmp_value)
-                            .await?
-                            .map_ok(move |(_, data_row)| match data_row {
-                                DataRow::Vec(values) => Row::Vec {
-                                    columns: Rc::clone(&columns),
-                                    values,
-                                },
-                                DataRow::Map(values) => Row::Map(values),
-                            });
-
-                        Rows::Indexed(rows)
-                    }
-                    Some(IndexItem::PrimaryKey(expr)) => {
-                        let filter_context = filter_context.as_ref().map(Rc::clone);
-                        let key = evaluate(storage, filter_context, None, expr)
-                            .await
-                            .and_then(Value::try_from)
-                            .and_then(Key::try_from)?;
-
-                        let rows = storage
-                            .fetch_data(name, &key)
-                            .await
-                            .transpose()
-                            .map(|row| vec![row])
-                            .unwrap_or_else(Vec::new);
-
-                        Rows::PrimaryKey(rows.into_iter().map_ok(move |data_row| match data_row {
-                            DataRow::Vec(values) => Row::Vec {
-                                columns: Rc::clone(&columns),
-                                values,
-                            },
-                            DataRow::Map(values) => Row::Map(values),
-                        }))
-                    }
-                    _ => {
-                        let rows = storage.scan_data(name).await?.map_ok(move |(_, data_row)| {
-                            match data_row {
-                                DataRow::Vec(values) => Row::Vec {
-                                    columns: Rc::clone(&columns),
-                                    values,
-                                },
-                                DataRow::Map(values) => Row::Map(values),
-                            }
-                        });
-
-                        Rows::FullScan(rows)
-                    }
-                }
-            };
-
             Ok(Rows::Table(stream::iter(rows)))
         }
         TableFactor::Series { size, .. } => {
-            let value: Value = evaluate_stateless(None, size).await?.try_into()?;
+            let value: Value = evaluate_stateless_with(ctx, None, size).await?.try_into()?;
             let size: i64 = value.try_into()?;
             let size = match size {
                 n if n >= 0 => size,
@@ -207,14 +259,29 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
 
             Ok(Rows::Series(stream::iter(rows)))
         }
+        TableFactor::Function { name, args, .. } => {
+            let rows = fetch_function_rows(storage, ctx, name, args, filter_context)
+                .await?
+                .into_iter()
+                .map(move |values| {
+                    Ok(Row::Vec {
+                        columns: Rc::clone(&columns),
+                        values,
+                    })
+                });
+
+            Ok(Rows::Function(stream::iter(rows)))
+        }
         TableFactor::Dictionary { dict, .. } => {
             let rows = {
                 #[derive(Iterator)]
-                enum Rows<I1, I2, I3, I4> {
+                enum Rows<I1, I2, I3, I4, I5, I6> {
                     Tables(I1),
                     TableColumns(I2),
                     Indexes(I3),
                     Objects(I4),
+                    TableConstraints(I5),
+                    Views(I6),
                 }
                 match dict {
                     Dictionary::GlueObjects => {
@@ -307,21 +374,23 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
                     Dictionary::GlueIndexes => {
                         let schemas = storage.fetch_all_schemas().await?;
                         let rows = schemas.into_iter().flat_map(move |schema| {
-                            let column_defs = schema.column_defs.unwrap_or_default();
-                            let primary_column = column_defs.iter().find_map(|column_def| {
-                                let ColumnDef { name, unique, .. } = column_def;
-
-                                (unique == &Some(ColumnUniqueOption { is_primary: true }))
-                                    .then_some(name)
+                            let primary_columns = schema.primary_key_indexes().map(|indexes| {
+                                let column_defs = schema.column_defs.as_deref().unwrap_or_default();
+
+                                indexes
+                                    .into_iter()
+                                    .filter_map(|i| column_defs.get(i))
+                                    .map(|ColumnDef { name, .. }| name.as_str())
+                                    .join(", ")
                             });
 
-                            let clustered = match primary_column {
-                                Some(column_name) => {
+                            let clustered = match primary_columns {
+                                Some(column_names) => {
                                     let values = vec![
                                         Value::Str(schema.table_name.clone()),
                                         Value::Str("PRIMARY".to_owned()),
                                         Value::Str("BOTH".to_owned()),
-                                        Value::Str(column_name.to_owned()),
+                                        Value::Str(column_names),
                                         Value::Bool(true),
                                     ];
 
@@ -337,12 +406,17 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
 
                             let columns = Rc::clone(&columns);
                             let non_clustered = schema.indexes.into_iter().map(move |index| {
+                                let expression = index
+                                    .columns
+                                    .iter()
+                                    .map(ToSqlUnquoted::to_sql_unquoted)
+                                    .join(", ");
                                 let values = vec![
                                     Value::Str(schema.table_name.clone()),
                                     Value::Str(index.name),
                                     Value::Str(index.order.to_string()),
-                                    Value::Str(index.expr.to_sql_unquoted()),
-                                    Value::Bool(false),
+                                    Value::Str(expression),
+                                    Value::Bool(index.unique),
                                 ];
 
                                 Ok(Row::Vec {
@@ -356,6 +430,100 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
 
                         Rows::Indexes(rows)
                     }
+                    Dictionary::GlueTableConstraints => {
+                        let schemas = storage.fetch_all_schemas().await?;
+                        let rows = schemas.into_iter().flat_map(move |schema| {
+                            let columns = Rc::clone(&columns);
+                            let Schema {
+                                table_name,
+                                foreign_keys,
+                                checks,
+                                unique_keys,
+                                ..
+                            } = schema;
+
+                            let unique_keys = unique_keys.into_iter().map(|unique_key| {
+                                let UniqueKey {
+                                    name,
+                                    columns,
+                                    is_primary,
+                                } = unique_key;
+                                let constraint_type = match is_primary {
+                                    true => "PRIMARY KEY",
+                                    false => "UNIQUE",
+                                };
+                                let definition =
+                                    format!("{constraint_type} ({})", columns.join(", "));
+
+                                (name, constraint_type, Value::Null, definition)
+                            });
+
+                            let foreign_keys = foreign_keys.into_iter().map(|foreign_key| {
+                                let ForeignKey {
+                                    name,
+                                    referencing_column_name,
+                                    referenced_table_name,
+                                    referenced_column_name,
+                                    on_delete,
+                                    on_update,
+                                } = foreign_key;
+                                let definition = format!(
+                                    "REFERENCES {referenced_table_name} ({referenced_column_name}) ON DELETE {} ON UPDATE {}",
+                                    on_delete.to_sql(),
+                                    on_update.to_sql(),
+                                );
+
+                                (
+                                    name,
+                                    "FOREIGN KEY",
+                                    Value::Str(referencing_column_name),
+                                    definition,
+                                )
+                            });
+                            let checks = checks.into_iter().map(|Check { name, expr }| {
+                                let definition = format!("CHECK ({})", expr.to_sql_unquoted());
+
+                                (name, "CHECK", Value::Null, definition)
+                            });
+
+                            unique_keys.chain(foreign_keys).chain(checks).map(
+                                move |(name, constraint_type, column_name, definition)| {
+                                    let values = vec![
+                                        Value::Str(table_name.clone()),
+                                        Value::Str(name),
+                                        Value::Str(constraint_type.to_owned()),
+                                        column_name,
+                                        Value::Str(definition),
+                                    ];
+
+                                    Ok(Row::Vec {
+                                        columns: Rc::clone(&columns),
+                                        values,
+                                    })
+                                },
+                            )
+                        });
+
+                        Rows::TableConstraints(rows)
+                    }
+                    Dictionary::GlueViews => {
+                        let mut views = storage.fetch_all_views().await?;
+                        views.sort_by(|a, b| a.view_name.cmp(&b.view_name));
+
+                        let rows = views.into_iter().map(move |view| {
+                            let values = vec![
+                                Value::Str(view.view_name),
+                                Value::Str(view.query.to_sql_unquoted()),
+                            ];
+
+                            Ok(Row::Vec {
+                                columns: Rc::clone(&columns),
+                                values,
+                            })
+                        });
+
+                        Rows::Views(rows)
+                    }
                 }
             };
 
@@ -364,6 +532,118 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
     }
 }
 
+/// Fetches keyed rows of a table, using the planned index when it exists.
+pub async fn fetch_table_rows<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    table_name: &str,
+    index: Option<&IndexItem>,
+    filter_context: &Option<Rc<RowContext<'a>>>,
+) -> Result<RowIter> {
+    match index {
+        Some(IndexItem::NonClustered {
+            name: index_name,
+            asc,
+            prefix,
+            cmp_expr,
+        }) => {
+            let prefix = stream::iter(prefix)
+                .then(|expr| async move {
+                    evaluate(storage, ctx, None, None, expr)
+                        .await
+                        .and_then(Value::try_from)
+                })
+                .try_collect::<Vec<_>>()
+                .await?;
+            let cmp_value = match cmp_expr {
+                Some((op, expr)) => {
+                    let evaluated = evaluate(storage, ctx, None, None, expr).await?;
+
+                    Some((op, evaluated.try_into()?))
+                }
+                None => None,
+            };
+
+            storage
+                .scan_indexed_data(table_name, index_name, *asc, &prefix, cmp_value)
+                .await
+        }
+        Some(IndexItem::PrimaryKey(expr)) => {
+            let filter_context = filter_context.as_ref().map(Rc::clone);
+            let key = evaluate(storage, ctx, filter_context, None, expr)
+                .await
+                .and_then(Value::try_from)
+                .and_then(Key::try_from)?;
+
+            let row = storage
+                .fetch_data(table_name, &key)
+                .await
+                .map(|row| row.map(|row| (key, row)))
+                .transpose();
+
+            Ok(Box::new(row.into_iter()))
+        }
+        Some(IndexItem::CompositePrimaryKey {
+            exprs,
+            full_key,
+            cmp_expr,
+        }) => {
+            let keys = stream::iter(exprs)
+                .then(|expr| {
+                    let filter_context = filter_context.as_ref().map(Rc::clone);
+
+                    async move {
+                        evaluate(storage, ctx, filter_context, None, expr)
+                            .await
+                            .and_then(Value::try_from)
+                            .and_then(Key::try_from)
+                    }
+                })
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            if !full_key {
+                let cmp_key = match cmp_expr {
+                    Some((op, expr)) => {
+                        let key = evaluate(
+                            storage,
+                            ctx,
+                            filter_context.as_ref().map(Rc::clone),
+                            None,
+                            expr,
+                        )
+                        .await
+                        .and_then(Value::try_from)
+                        .and_then(Key::try_from)?;
+
+                        // NULL compares with no key
+                        if key == Key::None {
+                            return Ok(Box::new(iter::empty()));
+                        }
+
+                        Some((op, key))
+                    }
+                    None => None,
+                };
+
+                return storage
+                    .scan_data_with_prefix(table_name, &keys, cmp_key)
+                    .await;
+            }
+
+            let key = Key::Composite(keys);
+            let row = storage
+                .fetch_data(table_name, &key)
+                .await
+                .map(|row| row.map(|row| (key, row)))
+                .transpose();
+
+            Ok(Box::new(row.into_iter()))
+        }
+        None => storage.scan_data(table_name).await,
+    }
+}
+
 pub async fn fetch_columns<T: GStore>(
     storage: &T,
     table_name: &str,
@@ -384,35 +664,37 @@ pub async fn fetch_columns<T: GStore>(
 }
 
 #[async_recursion(?Send)]
-pub async fn fetch_relation_columns<T: GStore>(
-    storage: &T,
-    table_factor: &TableFactor,
+pub async fn fetch_relation_columns<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    table_factor: &'a TableFactor,
+    filter_context: &Option<Rc<RowContext<'a>>>,
 ) -> Result<Option<Vec<String>>> {
     match table_factor {
         TableFactor::Table { name, alias, .. } => {
-            let columns = fetch_columns(storage, name).await?;
-            match (columns, alias) {
-                (columns, None) => Ok(columns),
-                (None, Some(_)) => Ok(None),
-                (Some(columns), Some(alias)) if alias.columns.len() > columns.len() => {
-                    Err(FetchError::TooManyColumnAliases(
-                        name.to_string(),
-                        columns.len(),
-                        alias.columns.len(),
-                    )
-                    .into())
-                }
-                (Some(columns), Some(alias)) => Ok(Some(
-                    alias
-                        .columns
-                        .iter()
-                        .cloned()
-                        .chain(columns[alias.columns.len()..columns.len()].to_vec())
-                        .collect(),
-                )),
+            let columns = match filter_context
+                .as_ref()
+                .and_then(|context| context.get_cte(name))
+            {
+                Some((columns, _)) => columns.map(<[String]>::to_vec),
+                None => fetch_columns(storage, name).await?,
+            };
+
+            match alias {
+                Some(alias) => apply_column_aliases(name, &alias.columns, columns),
+                None => Ok(columns),
             }
         }
         TableFactor::Series { .. } => Ok(Some(vec!["N".to_owned()])),
+        TableFactor::Function { name, alias, .. } => {
+            let (_, columns, _) = fetch_table_function(storage, name).await?;
+            let columns = columns
+                .iter()
+                .map(|column| column.name.to_owned())
+                .collect();
+
+            apply_column_aliases(&alias.name, &alias.columns, Some(columns))
+        }
         TableFactor::Dictionary { dict, .. } => Ok(Some(match dict {
             Dictionary::GlueObjects => vec![
                 "OBJECT_NAME".to_owned(),
@@ -435,7 +717,24 @@ pub async fn fetch_relation_columns<T: GStore>(
                 "EXPRESSION".to_owned(),
                 "UNIQUENESS".to_owned(),
             ],
+            Dictionary::GlueTableConstraints => vec![
+                "TABLE_NAME".to_owned(),
+                "CONSTRAINT_NAME".to_owned(),
+                "CONSTRAINT_TYPE".to_owned(),
+                "COLUMN_NAME".to_owned(),
+                "DEFINITION".to_owned(),
+            ],
+            Dictionary::GlueViews => vec!["VIEW_NAME".to_owned(), "DEFINITION".to_owned()],
         })),
+        TableFactor::Derived {
+            subquery: subquery @ Query { with: Some(_), .. },
+            alias,
+        } => {
+            let filter_context = filter_context.as_ref().map(Rc::clone);
+            let (labels, _) = select_with_labels(storage, ctx, subquery, filter_context).await?;
+
+            apply_column_aliases(&alias.name, &alias.columns, labels)
+        }
         TableFactor::Derived {
             subquery: Query { body, .. },
             alias:
@@ -443,7 +742,7 @@ pub async fn fetch_relation_columns<T: GStore>(
                     columns: alias_columns,
                     name,
                 },
-        } => match body {
+        } => match leftmost_operand(body) {
             SetExpr::Select(statement) => {
                 let Select {
                     from:
@@ -454,26 +753,10 @@ pub async fn fetch_relation_columns<T: GStore>(
                     ..
                 } = statement.as_ref();
 
-                let labels = fetch_labels(storage, relation, joins, projection).await?;
-                match labels {
-                    None => Ok(None),
-                    Some(labels) if alias_columns.is_empty() => Ok(Some(labels)),
-                    Some(labels) if alias_columns.len() > labels.len() => {
-                        Err(FetchError::TooManyColumnAliases(
-                            name.to_string(),
-                            labels.len(),
-                            alias_columns.len(),
-                        )
-                        .into())
-                    }
-                    Some(labels) => Ok(Some(
-                        alias_columns
-                            .iter()
-                            .cloned()
-                            .chain(labels[alias_columns.len()..labels.len()].to_vec())
-                            .collect(),
-                    )),
-                }
+                let labels =
+                    fetch_labels(storage, ctx, relation, joins, projection, filter_context).await?;
+
+                apply_column_aliases(name, alias_columns, labels)
             }
             SetExpr::Values(Values(values_list)) => {
                 let total_len = values_list[0].len();
@@ -495,20 +778,146 @@ pub async fn fetch_relation_columns<T: GStore>(
 
                 Ok(Some(labels))
             }
+            SetExpr::SetOperation { .. } => Err(TableError::Unreachable.into()),
         },
     }
 }
 
+/// Fetches a custom function which returns a table, with its columns and query
+async fn fetch_table_function<'a, T: GStore>(
+    storage: &'a T,
+    name: &str,
+) -> Result<(&'a CustomFunction, &'a [ColumnDef], &'a Query)> {
+    let function = storage
+        .fetch_function(name)
+        .await?
+        .filter(|function| function.kind == FunctionKind::Function);
+
+    match function {
+        Some(
+            function @ CustomFunction {
+                body: FunctionBody::Table { columns, query },
+                ..
+            },
+        ) => Ok((function, columns, query)),
+        _ => Err(FetchError::TableFunctionNotFound(name.to_owned()).into()),
+    }
+}
+
+/// Runs the query of a table function with its arguments bound, omitted arguments take
+/// their defaults and each selected value is cast to the type of its returned column.
+async fn fetch_function_rows<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    name: &str,
+    exprs: &[Expr],
+    filter_context: &Option<Rc<RowContext<'a>>>,
+) -> Result<Vec<Vec<Value>>> {
+    let (function, columns, query) = fetch_table_function(storage, name).await?;
+    validate_args_len(function, exprs.len())?;
+
+    let CustomFunction { args, .. } = function;
+    let exprs = exprs.iter().chain(
+        args.iter()
+            .skip(exprs.len())
+            .filter_map(|arg| arg.default.as_ref()),
+    );
+
+    let mut values = Vec::with_capacity(args.len());
+    for (arg, expr) in args.iter().zip(exprs) {
+        let context = filter_context.as_ref().map(Rc::clone);
+        let value = evaluate(storage, ctx, context, None, expr)
+            .await?
+            .try_into_value(&arg.data_type, true)?;
+
+        values.push(value);
+    }
+
+    let ctx = ctx
+        .nest(&format!("function {name}"))?
+        .with_scope(Scope::Variables {
+            name: name.to_owned(),
+            values: args
+                .iter()
+                .map(|arg| arg.name.to_owned())
+                .zip(values)
+                .collect(),
+        });
+    let query = match plan(storage, Statement::Query(query.clone())).await? {
+        Statement::Query(query) => query,
+        _ => return Err(TableError::Unreachable.into()),
+    };
+
+    let rows = select(storage, &ctx, &query, None)
+        .await?
+        .and_then(|row| {
+            let values = match row {
+                Row::Vec { values, .. } => values,
+                Row::Map(mut values) => columns
+                    .iter()
+                    .map(|column| values.remove(&column.name).unwrap_or(Value::Null))
+                    .collect(),
+            };
+
+            let result = match values.len() == columns.len() {
+                true => columns
+                    .iter()
+                    .zip(values)
+                    .map(|(column, value)| {
+                        value.validate_null(column.nullable)?;
+                        value.cast(&column.data_type)
+                    })
+                    .collect(),
+                false => Err(FetchError::TableFunctionColumnsMismatch(
+                    name.to_owned(),
+                    columns.len(),
+                    values.len(),
+                )
+                .into()),
+            };
+
+            ready(result)
+        })
+        .try_collect()
+        .await?;
+
+    Ok(rows)
+}
+
+/// Renames the leading columns of `name` to the given column aliases
+pub fn apply_column_aliases(
+    name: &str,
+    alias_columns: &[String],
+    columns: Option<Vec<String>>,
+) -> Result<Option<Vec<String>>> {
+    match columns {
+        None => Ok(None),
+        Some(columns) if alias_columns.len() > columns.len() => Err(
+            FetchError::TooManyColumnAliases(name.to_owned(), columns.len(), alias_columns.len())
+                .into(),
+        ),
+        Some(columns) => Ok(Some(
+            alias_columns
+                .iter()
+                .cloned()
+                .chain(columns[alias_columns.len()..columns.len()].to_vec())
+                .collect(),
+        )),
+    }
+}
+
 async fn fetch_join_columns<'a, T: GStore>(
-    storage: &T,
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
     joins: &'a [Join],
+    filter_context: &Option<Rc<RowContext<'a>>>,
 ) -> Result<Option<Vec<(&'a String, Vec<String>)>>> {
     let columns = stream::iter(joins)
         .filter_map(|join| async {
             let relation = &join.relation;
             let alias = get_alias(relation);
 
-            fetch_relation_columns(storage, relation)
+            fetch_relation_columns(storage, ctx, relation, filter_context)
                 .await
                 .map(|columns| Some((alias, columns?)))
                 .transpose()
@@ -519,15 +928,17 @@ async fn fetch_join_columns<'a, T: GStore>(
     Ok((columns.len() == joins.len()).then_some(columns))
 }
 
-pub async fn fetch_labels<T: GStore>(
-    storage: &T,
-    relation: &TableFactor,
-    joins: &[Join],
+pub async fn fetch_labels<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    relation: &'a TableFactor,
+    joins: &'a [Join],
     projection: &[SelectItem],
+    filter_context: &Option<Rc<RowContext<'a>>>,
 ) -> Result<Option<Vec<String>>> {
     let table_alias = get_alias(relation);
-    let columns = fetch_relation_columns(storage, relation).await?;
-    let join_columns = fetch_join_columns(storage, joins).await?;
+    let columns = fetch_relation_columns(storage, ctx, relation, filter_context).await?;
+    let join_columns = fetch_join_columns(storage, ctx, joins, filter_context).await?;
 
     if (columns.is_none() || join_columns.is_none())
         && projection.iter().any(|item| {
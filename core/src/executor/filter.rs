@@ -1,5 +1,8 @@
 use {
-    super::{context::RowContext, evaluate::evaluate},
+    super::{
+        context::{ExecuteContext, RowContext},
+        evaluate::evaluate,
+    },
     crate::{
         ast::{Aggregate, Expr},
         data::Value,
@@ -12,6 +15,7 @@ use {
 
 pub struct Filter<'a, T: GStore> {
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     where_clause: Option<&'a Expr>,
     context: Option<Rc<RowContext<'a>>>,
     aggregated: Option<Rc<HashMap<&'a Aggregate, Value>>>,
@@ -20,12 +24,14 @@ pub struct Filter<'a, T: GStore> {
 impl<'a, T: GStore> Filter<'a, T> {
     pub fn new(
         storage: &'a T,
+        ctx: &'a ExecuteContext,
         where_clause: Option<&'a Expr>,
         context: Option<Rc<RowContext<'a>>>,
         aggregated: Option<Rc<HashMap<&'a Aggregate, Value>>>,
     ) -> Self {
         Self {
             storage,
+            ctx,
             where_clause,
             context,
             aggregated,
@@ -44,7 +50,7 @@ impl<'a, T: GStore> Filter<'a, T> {
                 let context = Some(context);
                 let aggregated = self.aggregated.as_ref().map(Rc::clone);
 
-                check_expr(self.storage, context, aggregated, expr).await
+                check_expr(self.storage, self.ctx, context, aggregated, expr).await
             }
             None => Ok(true),
         }
@@ -53,11 +59,12 @@ impl<'a, T: GStore> Filter<'a, T> {
 
 pub async fn check_expr<'a, T: GStore>(
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     context: Option<Rc<RowContext<'a>>>,
     aggregated: Option<Rc<HashMap<&'a Aggregate, Value>>>,
     expr: &'a Expr,
 ) -> Result<bool> {
-    evaluate(storage, context, aggregated, expr)
+    evaluate(storage, ctx, context, aggregated, expr)
         .await
         .map(|evaluated| evaluated.try_into())?
 }
@@ -1,18 +1,31 @@
 use {
     super::{
+        context::{ExecuteContext, RowContext},
+        execute::Payload,
+        filter::check_expr,
+        referential::ReferentialActions,
+        returning::project_returning,
         select::select,
-        validate::{validate_unique, ColumnValidation},
+        trigger::Triggers,
+        update::Update,
+        validate::{validate_rows, ColumnValidation, ValidateError},
     },
     crate::{
-        ast::{ColumnDef, ColumnUniqueOption, Expr, Query, SetExpr, Values},
-        data::{Key, Row, Schema, Value},
-        executor::{evaluate::evaluate_stateless, limit::Limit},
+        ast::{
+            Assignment, ColumnDef, Expr, Function, OnConflict, OnConflictAction, Query, SelectItem,
+            SetExpr, TriggerEvent, UniqueKey, Values,
+        },
+        data::{Key, Row, Schema, SchemaIndex, Value},
+        executor::{
+            evaluate::{evaluate, evaluate_stateless_with},
+            limit::Limit,
+        },
         result::Result,
         store::{DataRow, GStore, GStoreMut},
     },
     futures::stream::{self, StreamExt, TryStreamExt},
     serde::Serialize,
-    std::{fmt::Debug, rc::Rc},
+    std::{borrow::Cow, fmt::Debug, iter::once, rc::Rc},
     thiserror::Error as ThisError,
 };
 
@@ -38,6 +51,12 @@ pub enum InsertError {
 
     #[error("map type required: {0}")]
     MapTypeValueRequired(String),
+
+    #[error("conflict target is not a primary key, unique column or unique key: {0}")]
+    ConflictTargetNotUnique(String),
+
+    #[error("ON CONFLICT is not supported for schemaless table: {0}")]
+    OnConflictOnSchemalessTableNotSupported(String),
 }
 
 enum RowsData {
@@ -47,57 +66,110 @@ enum RowsData {
 
 pub async fn insert<T: GStore + GStoreMut>(
     storage: &mut T,
+    ctx: &ExecuteContext,
     table_name: &str,
     columns: &[String],
     source: &Query,
-) -> Result<usize> {
-    let Schema { column_defs, .. } = storage
+    on_conflict: Option<&OnConflict>,
+    returning: &[SelectItem],
+) -> Result<Payload> {
+    let schema = storage
         .fetch_schema(table_name)
         .await?
         .ok_or_else(|| InsertError::TableNotFound(table_name.to_owned()))?;
 
-    let rows = match column_defs {
-        Some(column_defs) => {
-            fetch_vec_rows(storage, table_name, column_defs, columns, source).await
+    let rows = match (schema.column_defs.as_deref(), on_conflict) {
+        (Some(column_defs), Some(on_conflict)) => {
+            let rows = fetch_vec_rows(storage, ctx, column_defs, columns, source).await?;
+            let rows = upsert(storage, ctx, &schema, column_defs, on_conflict, rows).await?;
+
+            return match returning.is_empty() {
+                true => Ok(Payload::Insert(rows.len())),
+                false => project_returning(storage, ctx, table_name, returning, rows).await,
+            };
+        }
+        (Some(column_defs), None) => fetch_vec_rows(storage, ctx, column_defs, columns, source)
+            .await?
+            .into_iter()
+            .map(DataRow::Vec)
+            .collect(),
+        (None, Some(_)) => {
+            return Err(InsertError::OnConflictOnSchemalessTableNotSupported(
+                table_name.to_owned(),
+            )
+            .into());
         }
-        None => fetch_map_rows(storage, source).await.map(RowsData::Append),
-    }?;
+        (None, None) => fetch_map_rows(storage, ctx, source).await?,
+    };
 
-    match rows {
-        RowsData::Append(rows) => {
-            let num_rows = rows.len();
+    let triggers = Triggers::fetch(storage, table_name, TriggerEvent::Insert).await?;
+    let rows = match triggers.is_empty() {
+        true => rows,
+        false => {
+            let mut fired = Vec::new();
+            for mut row in rows {
+                if triggers
+                    .fire_before(storage, ctx, Some(&mut row), None)
+                    .await?
+                {
+                    fired.push(row);
+                }
+            }
+
+            fired
+        }
+    };
+
+    let rows = match schema.column_defs.as_deref() {
+        Some(column_defs) => into_rows_data(storage, &schema, column_defs, rows).await?,
+        None => RowsData::Append(rows),
+    };
 
-            storage
-                .append_data(table_name, rows)
-                .await
-                .map(|_| num_rows)
+    let payload = match (returning.is_empty(), &rows) {
+        (true, RowsData::Append(rows)) => Payload::Insert(rows.len()),
+        (true, RowsData::Insert(rows)) => Payload::Insert(rows.len()),
+        (false, RowsData::Append(rows)) => {
+            project_returning(storage, ctx, table_name, returning, rows.clone()).await?
         }
-        RowsData::Insert(rows) => {
-            let num_rows = rows.len();
+        (false, RowsData::Insert(rows)) => {
+            let rows = rows.iter().map(|(_, row)| row.clone()).collect();
 
-            storage
-                .insert_data(table_name, rows)
-                .await
-                .map(|_| num_rows)
+            project_returning(storage, ctx, table_name, returning, rows).await?
         }
+    };
+
+    let inserted = match (triggers.is_empty(), &rows) {
+        (true, _) => Vec::new(),
+        (false, RowsData::Append(rows)) => rows.clone(),
+        (false, RowsData::Insert(rows)) => rows.iter().map(|(_, row)| row.clone()).collect(),
+    };
+
+    match rows {
+        RowsData::Append(rows) => storage.append_data(table_name, rows).await?,
+        RowsData::Insert(rows) => storage.insert_data(table_name, rows).await?,
+    }
+
+    for row in &inserted {
+        triggers.fire_after(storage, ctx, Some(row), None).await?;
     }
+
+    Ok(payload)
 }
 
 async fn fetch_vec_rows<T: GStore>(
     storage: &T,
-    table_name: &str,
-    column_defs: Vec<ColumnDef>,
+    ctx: &ExecuteContext,
+    column_defs: &[ColumnDef],
     columns: &[String],
     source: &Query,
-) -> Result<RowsData> {
+) -> Result<Vec<Vec<Value>>> {
     let labels = Rc::from(
         column_defs
             .iter()
             .map(|column_def| column_def.name.to_owned())
             .collect::<Vec<_>>(),
     );
-    let column_defs = Rc::from(column_defs);
-    let column_validation = ColumnValidation::All(&column_defs);
+    let column_defs = Rc::<[ColumnDef]>::from(column_defs);
 
     #[derive(futures_enum::Stream)]
     enum Rows<I1, I2> {
@@ -107,7 +179,7 @@ async fn fetch_vec_rows<T: GStore>(
 
     let rows = match &source.body {
         SetExpr::Values(Values(values_list)) => {
-            let limit = Limit::new(source.limit.as_ref(), source.offset.as_ref()).await?;
+            let limit = Limit::new(ctx, source.limit.as_ref(), source.offset.as_ref()).await?;
             let rows = stream::iter(values_list).then(|values| {
                 let column_defs = Rc::clone(&column_defs);
                 let labels = Rc::clone(&labels);
@@ -115,7 +187,7 @@ async fn fetch_vec_rows<T: GStore>(
                 async move {
                     Ok(Row::Vec {
                         columns: labels,
-                        values: fill_values(&column_defs, columns, values).await?,
+                        values: fill_values(storage, ctx, &column_defs, columns, values).await?,
                     })
                 }
             });
@@ -124,8 +196,8 @@ async fn fetch_vec_rows<T: GStore>(
 
             Rows::Values(rows)
         }
-        SetExpr::Select(_) => {
-            let rows = select(storage, source, None).await?.map(|row| {
+        SetExpr::Select(_) | SetExpr::SetOperation { .. } => {
+            let rows = select(storage, ctx, source, None).await?.map(|row| {
                 let values = row?.try_into_vec()?;
 
                 column_defs
@@ -151,25 +223,34 @@ async fn fetch_vec_rows<T: GStore>(
     .try_collect::<Vec<Vec<Value>>>()
     .await?;
 
-    validate_unique(
+    Ok(rows)
+}
+
+async fn into_rows_data<T: GStore>(
+    storage: &T,
+    schema: &Schema,
+    column_defs: &[ColumnDef],
+    rows: Vec<DataRow>,
+) -> Result<RowsData> {
+    let rows = rows
+        .into_iter()
+        .map(into_values)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    validate_rows(
         storage,
-        table_name,
-        column_validation,
+        schema,
+        ColumnValidation::All(column_defs),
         rows.iter().map(|values| values.as_slice()),
     )
     .await?;
 
-    let primary_key = column_defs.iter().position(|ColumnDef { unique, .. }| {
-        unique == &Some(ColumnUniqueOption { is_primary: true })
-    });
-
-    match primary_key {
-        Some(i) => rows
+    match schema.primary_key_indexes() {
+        Some(indexes) => rows
             .into_iter()
             .filter_map(|values| {
-                values
-                    .get(i)
-                    .map(Key::try_from)
+                Key::from_row(&values, &indexes)
+                    .transpose()
                     .map(|result| result.map(|key| (key, values.into())))
             })
             .collect::<Result<Vec<_>>>()
@@ -178,7 +259,314 @@ async fn fetch_vec_rows<T: GStore>(
     }
 }
 
-async fn fetch_map_rows<T: GStore>(storage: &T, source: &Query) -> Result<Vec<DataRow>> {
+/// Inserts rows one by one, so a row conflicting with a stored or an earlier inserted row
+/// is either skipped or updated in place following `ON CONFLICT`.
+/// Insert triggers fire around each inserted row and update triggers around each updated row.
+/// Returns the inserted and updated rows.
+async fn upsert<T: GStore + GStoreMut>(
+    storage: &mut T,
+    ctx: &ExecuteContext,
+    schema: &Schema,
+    column_defs: &[ColumnDef],
+    on_conflict: &OnConflict,
+    rows: Vec<Vec<Value>>,
+) -> Result<Vec<DataRow>> {
+    let Schema {
+        table_name,
+        indexes,
+        unique_keys,
+        ..
+    } = schema;
+    let OnConflict { target, action } = on_conflict;
+    let target = conflict_target(column_defs, unique_keys, indexes, target)?;
+    let primary_key = schema.primary_key_indexes();
+    let labels = Rc::from(
+        column_defs
+            .iter()
+            .map(|column_def| column_def.name.to_owned())
+            .collect::<Vec<_>>(),
+    );
+
+    let insert_triggers = Triggers::fetch(storage, table_name, TriggerEvent::Insert).await?;
+    let update_triggers = Triggers::fetch(storage, table_name, TriggerEvent::Update).await?;
+
+    let mut affected = Vec::new();
+    for values in rows {
+        let conflict = find_conflict(
+            storage,
+            table_name,
+            primary_key.as_deref(),
+            &target,
+            &values,
+        )
+        .await?;
+        let (key, stored_values, assignments, selection) = match (conflict, action) {
+            (None, _) => {
+                let mut data_row = DataRow::Vec(values);
+                if !insert_triggers
+                    .fire_before(storage, ctx, Some(&mut data_row), None)
+                    .await?
+                {
+                    continue;
+                }
+
+                let values = into_values(data_row)?;
+                validate_rows(
+                    storage,
+                    schema,
+                    ColumnValidation::All(column_defs),
+                    once(values.as_slice()),
+                )
+                .await?;
+
+                let key = primary_key
+                    .as_deref()
+                    .map(|indexes| Key::from_row(&values, indexes))
+                    .transpose()?
+                    .flatten();
+                let data_row = DataRow::Vec(values);
+
+                match key {
+                    Some(key) => {
+                        storage
+                            .insert_data(table_name, vec![(key, data_row.clone())])
+                            .await?
+                    }
+                    None => {
+                        storage
+                            .append_data(table_name, vec![data_row.clone()])
+                            .await?
+                    }
+                }
+
+                insert_triggers
+                    .fire_after(storage, ctx, Some(&data_row), None)
+                    .await?;
+                affected.push(data_row);
+                continue;
+            }
+            (Some(_), OnConflictAction::DoNothing) => continue,
+            (
+                Some((key, stored_values)),
+                OnConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                },
+            ) => (key, stored_values, assignments, selection),
+        };
+
+        let old_row = DataRow::Vec(stored_values.clone());
+        let row = Row::Vec {
+            columns: Rc::clone(&labels),
+            values: stored_values,
+        };
+        let excluded = Row::Vec {
+            columns: Rc::clone(&labels),
+            values,
+        };
+        let excluded = Rc::new(RowContext::new("EXCLUDED", Cow::Borrowed(&excluded), None));
+
+        if let Some(expr) = selection {
+            let context =
+                RowContext::new(table_name, Cow::Borrowed(&row), Some(Rc::clone(&excluded)));
+
+            if !check_expr(storage, ctx, Some(Rc::new(context)), None, expr).await? {
+                continue;
+            }
+        }
+
+        let update = Update::new(
+            storage,
+            ctx,
+            table_name,
+            assignments,
+            Some(column_defs),
+            unique_keys,
+        )?;
+        let values = update.apply(row, Some(excluded)).await?.try_into_vec()?;
+
+        let mut data_row = DataRow::Vec(values);
+        if !update_triggers
+            .fire_before(storage, ctx, Some(&mut data_row), Some(&old_row))
+            .await?
+        {
+            continue;
+        }
+
+        let values = into_values(data_row)?;
+        let columns_to_update = assignments
+            .iter()
+            .map(|Assignment { id, .. }| id)
+            .chain(update_triggers.set_columns())
+            .cloned()
+            .collect::<Vec<_>>();
+        validate_rows(
+            storage,
+            schema,
+            ColumnValidation::SpecifiedColumns(column_defs, columns_to_update),
+            once(values.as_slice()),
+        )
+        .await?;
+
+        let data_row = DataRow::Vec(values);
+
+        let referential_actions =
+            ReferentialActions::plan(storage, table_name, once((&key, Some(&data_row)))).await?;
+
+        storage
+            .insert_data(table_name, vec![(key, data_row.clone())])
+            .await?;
+        referential_actions.apply(storage).await?;
+        update_triggers
+            .fire_after(storage, ctx, Some(&data_row), Some(&old_row))
+            .await?;
+        affected.push(data_row);
+    }
+
+    Ok(affected)
+}
+
+/// Resolves the conflict target into the column indexes of each unique constraint.
+/// A specified target must match the columns of a unique column, a unique key or a UNIQUE index
+/// which is neither partial nor on expressions. Without a target every such constraint is used.
+fn conflict_target(
+    column_defs: &[ColumnDef],
+    unique_keys: &[UniqueKey],
+    indexes: &[SchemaIndex],
+    target: &[String],
+) -> Result<Vec<Vec<usize>>> {
+    let column_indexes = |columns: &[String]| {
+        columns
+            .iter()
+            .map(|column| {
+                column_defs
+                    .iter()
+                    .position(|ColumnDef { name, .. }| name == column)
+                    .ok_or_else(|| InsertError::WrongColumnName(column.to_owned()).into())
+            })
+            .collect::<Result<Vec<_>>>()
+    };
+    let unique_index_columns = indexes
+        .iter()
+        .filter(
+            |SchemaIndex {
+                 unique, predicate, ..
+             }| *unique && predicate.is_none(),
+        )
+        .filter_map(|index| {
+            index
+                .exprs()
+                .map(|expr| match expr {
+                    Expr::Identifier(column) => Some(column.to_owned()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect::<Vec<_>>();
+
+    if target.is_empty() {
+        return column_defs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, ColumnDef { unique, .. })| unique.map(|_| Ok(vec![i])))
+            .chain(
+                unique_keys
+                    .iter()
+                    .map(|UniqueKey { columns, .. }| column_indexes(columns)),
+            )
+            .chain(
+                unique_index_columns
+                    .iter()
+                    .map(|columns| column_indexes(columns)),
+            )
+            .collect();
+    }
+
+    let indexes = column_indexes(target)?;
+    let same_columns = |columns: &[String]| {
+        columns.len() == target.len() && target.iter().all(|column| columns.contains(column))
+    };
+
+    if let Some(UniqueKey { columns, .. }) = unique_keys
+        .iter()
+        .find(|UniqueKey { columns, .. }| same_columns(columns))
+    {
+        return column_indexes(columns).map(|indexes| vec![indexes]);
+    }
+
+    let unique_column = match indexes.as_slice() {
+        [i] => column_defs[*i].unique.is_some(),
+        _ => false,
+    };
+
+    match unique_column
+        || unique_index_columns
+            .iter()
+            .any(|columns| same_columns(columns))
+    {
+        true => Ok(vec![indexes]),
+        false => Err(InsertError::ConflictTargetNotUnique(target.join(", ")).into()),
+    }
+}
+
+/// Finds the stored row which has the same key with `values` on one of `target` constraints.
+/// The primary key is looked up directly, the other constraints share a single scan.
+async fn find_conflict<T: GStore>(
+    storage: &T,
+    table_name: &str,
+    primary_key: Option<&[usize]>,
+    target: &[Vec<usize>],
+    values: &[Value],
+) -> Result<Option<(Key, Vec<Value>)>> {
+    let mut keys = Vec::new();
+    for indexes in target {
+        let key = match Key::from_row(values, indexes)? {
+            None | Some(Key::None) => continue,
+            Some(Key::Composite(keys)) if keys.contains(&Key::None) => continue,
+            Some(key) => key,
+        };
+
+        if primary_key == Some(indexes.as_slice()) {
+            if let Some(data_row) = storage.fetch_data(table_name, &key).await? {
+                return Ok(Some((key, into_values(data_row)?)));
+            }
+
+            continue;
+        }
+
+        keys.push((indexes, key));
+    }
+
+    if keys.is_empty() {
+        return Ok(None);
+    }
+
+    for item in storage.scan_data(table_name).await? {
+        let (stored_key, data_row) = item?;
+        let stored_values = into_values(data_row)?;
+
+        for (indexes, key) in &keys {
+            if Key::from_row(&stored_values, indexes)?.as_ref() == Some(key) {
+                return Ok(Some((stored_key, stored_values)));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn into_values(data_row: DataRow) -> Result<Vec<Value>, ValidateError> {
+    match data_row {
+        DataRow::Vec(values) => Ok(values),
+        DataRow::Map(_) => Err(ValidateError::ConflictOnUnexpectedSchemalessRowFound),
+    }
+}
+
+async fn fetch_map_rows<T: GStore>(
+    storage: &T,
+    ctx: &ExecuteContext,
+    source: &Query,
+) -> Result<Vec<DataRow>> {
     #[derive(futures_enum::Stream)]
     enum Rows<I1, I2> {
         Values(I1),
@@ -187,13 +575,13 @@ async fn fetch_map_rows<T: GStore>(storage: &T, source: &Query) -> Result<Vec<Da
 
     let rows = match &source.body {
         SetExpr::Values(Values(values_list)) => {
-            let limit = Limit::new(source.limit.as_ref(), source.offset.as_ref()).await?;
+            let limit = Limit::new(ctx, source.limit.as_ref(), source.offset.as_ref()).await?;
             let rows = stream::iter(values_list).then(|values| async move {
                 if values.len() > 1 {
                     return Err(InsertError::OnlySingleValueAcceptedForSchemalessRow.into());
                 }
 
-                evaluate_stateless(None, &values[0])
+                evaluate_stateless_with(ctx, None, &values[0])
                     .await?
                     .try_into()
                     .map(Row::Map)
@@ -203,8 +591,8 @@ async fn fetch_map_rows<T: GStore>(storage: &T, source: &Query) -> Result<Vec<Da
 
             Rows::Values(rows)
         }
-        SetExpr::Select(_) => {
-            let rows = select(storage, source, None).await?.map(|row| {
+        SetExpr::Select(_) | SetExpr::SetOperation { .. } => {
+            let rows = select(storage, ctx, source, None).await?.map(|row| {
                 let row = row?;
 
                 if let Row::Vec { values, .. } = &row {
@@ -227,7 +615,9 @@ async fn fetch_map_rows<T: GStore>(storage: &T, source: &Query) -> Result<Vec<Da
     Ok(rows)
 }
 
-async fn fill_values(
+async fn fill_values<T: GStore>(
+    storage: &T,
+    ctx: &ExecuteContext,
     column_defs: &[ColumnDef],
     columns: &[String],
     values: &[Expr],
@@ -278,9 +668,21 @@ async fn fill_values(
                     .map(|(_, value)| value);
 
                 match (value, &column_def.default, nullable) {
-                    (Some(&expr), _, _) | (None, Some(expr), _) => evaluate_stateless(None, expr)
-                        .await?
-                        .try_into_value(data_type, *nullable),
+                    (Some(&expr), _, _) | (None, Some(expr), _) => {
+                        let evaluated = match expr {
+                            Expr::Function(func)
+                                if matches!(
+                                    func.as_ref(),
+                                    Function::Nextval(_) | Function::Currval(_)
+                                ) =>
+                            {
+                                evaluate(storage, ctx, None, None, expr).await?
+                            }
+                            _ => evaluate_stateless_with(ctx, None, expr).await?,
+                        };
+
+                        evaluated.try_into_value(data_type, *nullable)
+                    }
                     (None, None, true) => Ok(Value::Null),
                     (None, None, false) => {
                         Err(InsertError::LackOfRequiredColumn(def_name.to_owned()).into())
@@ -1,28 +1,38 @@
 use {
-    super::fetch::{fetch_relation_columns, fetch_relation_rows},
+    super::{
+        analyze::{Probe, Stage},
+        fetch::{fetch_relation_columns, fetch_relation_rows},
+    },
     crate::{
         ast::{
             Expr, Join as AstJoin, JoinConstraint, JoinExecutor as AstJoinExecutor,
             JoinOperator as AstJoinOperator, TableFactor,
         },
         data::{get_alias, Key, Row, Value},
-        executor::{context::RowContext, evaluate::evaluate, filter::check_expr},
+        executor::{
+            context::{ExecuteContext, RowContext},
+            evaluate::evaluate,
+            filter::check_expr,
+        },
         result::Result,
         store::GStore,
     },
     futures::{
         future,
-        stream::{self, empty, once, Stream, StreamExt, TryStreamExt},
+        stream::{self, empty, Stream, StreamExt, TryStreamExt},
     },
     itertools::Itertools,
-    std::{borrow::Cow, collections::HashMap, pin::Pin, rc::Rc},
+    std::{borrow::Cow, collections::HashMap, iter::once, pin::Pin, rc::Rc},
     utils::OrStream,
 };
 
 pub struct Join<'a, T: GStore> {
     storage: &'a T,
+    ctx: &'a ExecuteContext,
+    relation: &'a TableFactor,
     join_clauses: &'a [AstJoin],
     filter_context: Option<Rc<RowContext<'a>>>,
+    probe: Probe<'a>,
 }
 
 type JoinItem<'a> = Rc<RowContext<'a>>;
@@ -31,28 +41,55 @@ type Joined<'a> = Pin<Box<dyn Stream<Item = Result<JoinItem<'a>>> + 'a>>;
 impl<'a, T: GStore> Join<'a, T> {
     pub fn new(
         storage: &'a T,
+        ctx: &'a ExecuteContext,
+        relation: &'a TableFactor,
         join_clauses: &'a [AstJoin],
         filter_context: Option<Rc<RowContext<'a>>>,
+        probe: Probe<'a>,
     ) -> Self {
         Self {
             storage,
+            ctx,
+            relation,
             join_clauses,
             filter_context,
+            probe,
         }
     }
 
     pub async fn apply(
         self,
-        rows: impl Stream<Item = Result<RowContext<'a>>> + 'a,
+        rows: impl Stream<Item = Result<JoinItem<'a>>> + 'a,
     ) -> Result<Joined<'a>> {
-        let init_rows: Joined = Box::pin(rows.map(|row| row.map(Rc::new)));
+        let init_rows: Joined = Box::pin(rows);
 
-        stream::iter(self.join_clauses)
+        stream::iter(self.join_clauses.iter().enumerate())
             .map(Ok)
-            .try_fold(init_rows, |rows, join_clause| {
+            .try_fold(init_rows, |rows, (i, join_clause)| {
                 let filter_context = self.filter_context.as_ref().map(Rc::clone);
+                let left_relations = once(self.relation)
+                    .chain(self.join_clauses[..i].iter().map(|join| &join.relation))
+                    .collect();
+                let probe = self.probe;
+                let rows = probe.input(Stage::Join(i), rows);
+
+                async move {
+                    let joined = probe
+                        .time(
+                            Stage::Join(i),
+                            join(
+                                self.storage,
+                                self.ctx,
+                                filter_context,
+                                left_relations,
+                                join_clause,
+                                rows,
+                            ),
+                        )
+                        .await?;
 
-                async move { join(self.storage, filter_context, join_clause, rows).await }
+                    Ok(probe.output(Stage::Join(i), joined))
+                }
             })
             .await
     }
@@ -60,7 +97,9 @@ impl<'a, T: GStore> Join<'a, T> {
 
 async fn join<'a, T: GStore>(
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     filter_context: Option<Rc<RowContext<'a>>>,
+    left_relations: Vec<&'a TableFactor>,
     ast_join: &'a AstJoin,
     left_rows: impl Stream<Item = Result<JoinItem<'a>>> + 'a,
 ) -> Result<Joined<'a>> {
@@ -73,6 +112,7 @@ async fn join<'a, T: GStore>(
     let table_alias = get_alias(relation);
     let join_executor = JoinExecutor::new(
         storage,
+        ctx,
         relation,
         filter_context.as_ref().map(Rc::clone),
         join_executor,
@@ -80,36 +120,128 @@ async fn join<'a, T: GStore>(
     .await
     .map(Rc::new)?;
 
-    let (join_operator, where_clause) = match join_operator {
-        AstJoinOperator::Inner(JoinConstraint::None) => (JoinOperator::Inner, None),
-        AstJoinOperator::Inner(JoinConstraint::On(where_clause)) => {
-            (JoinOperator::Inner, Some(where_clause))
+    let (join_operator, join_constraint) = match join_operator {
+        AstJoinOperator::Inner(join_constraint) => (JoinOperator::Inner, Some(join_constraint)),
+        AstJoinOperator::LeftOuter(join_constraint) => {
+            (JoinOperator::LeftOuter, Some(join_constraint))
+        }
+        AstJoinOperator::RightOuter(join_constraint) => {
+            (JoinOperator::RightOuter, Some(join_constraint))
         }
-        AstJoinOperator::LeftOuter(JoinConstraint::None) => (JoinOperator::LeftOuter, None),
-        AstJoinOperator::LeftOuter(JoinConstraint::On(where_clause)) => {
-            (JoinOperator::LeftOuter, Some(where_clause))
+        AstJoinOperator::FullOuter(join_constraint) => {
+            (JoinOperator::FullOuter, Some(join_constraint))
         }
+        AstJoinOperator::Cross => (JoinOperator::Inner, None),
+    };
+    let where_clause = match join_constraint {
+        Some(JoinConstraint::On(where_clause)) => Some(where_clause),
+        Some(JoinConstraint::None) | None => None,
     };
 
-    let columns = fetch_relation_columns(storage, relation)
+    let columns = fetch_relation_columns(storage, ctx, relation, &filter_context)
         .await?
         .map(Rc::from);
-    let rows = left_rows.and_then(move |project_context| {
-        let init_context = {
-            let init_row = match columns.as_ref() {
-                Some(columns) => Row::Vec {
-                    columns: Rc::clone(columns),
-                    values: columns.iter().map(|_| Value::Null).collect(),
-                },
-                None => Row::Map(HashMap::new()),
-            };
 
-            Rc::new(RowContext::new(
-                table_alias,
-                Cow::Owned(init_row),
-                Some(Rc::clone(&project_context)),
-            ))
+    if matches!(
+        join_operator,
+        JoinOperator::RightOuter | JoinOperator::FullOuter
+    ) {
+        let left_context = null_context(storage, ctx, left_relations, &filter_context).await?;
+        let right_rows = match join_executor.as_ref() {
+            JoinExecutor::NestedLoop => {
+                fetch_relation_rows(storage, ctx, relation, &filter_context)
+                    .await?
+                    .try_collect::<Vec<_>>()
+                    .await?
+            }
+            JoinExecutor::Hash { rows, .. } => rows.clone(),
         };
+        let left_rows = left_rows.try_collect::<Vec<_>>().await?;
+
+        let mut matched = vec![false; right_rows.len()];
+        let mut joined = Vec::new();
+
+        for project_context in left_rows {
+            let filter_context = match filter_context.as_ref() {
+                Some(filter_context) => Rc::new(RowContext::concat(
+                    Rc::clone(&project_context),
+                    Rc::clone(filter_context),
+                )),
+                None => Rc::clone(&project_context),
+            };
+
+            let indexes = match join_executor.as_ref() {
+                JoinExecutor::NestedLoop => (0..right_rows.len()).collect(),
+                JoinExecutor::Hash {
+                    rows_map,
+                    value_expr,
+                    ..
+                } => {
+                    let hash_key = evaluate(
+                        storage,
+                        ctx,
+                        Some(Rc::clone(&filter_context)),
+                        None,
+                        value_expr,
+                    )
+                    .await
+                    .map(Key::try_from)??;
+
+                    rows_map.get(&hash_key).cloned().unwrap_or_default()
+                }
+            };
+
+            let mut found = false;
+            for index in indexes {
+                let row = check_where_clause(
+                    storage,
+                    ctx,
+                    table_alias,
+                    Some(Rc::clone(&filter_context)),
+                    Some(Rc::clone(&project_context)),
+                    where_clause,
+                    Cow::Borrowed(&right_rows[index]),
+                )
+                .await?;
+
+                if let Some(row) = row {
+                    matched[index] = true;
+                    found = true;
+                    joined.push(row);
+                }
+            }
+
+            if !found && matches!(join_operator, JoinOperator::FullOuter) {
+                joined.push(Rc::new(RowContext::new(
+                    table_alias,
+                    Cow::Owned(null_row(columns.as_ref())),
+                    Some(project_context),
+                )));
+            }
+        }
+
+        let unmatched = right_rows
+            .into_iter()
+            .zip(matched)
+            .filter_map(|(row, matched)| (!matched).then_some(row))
+            .map(move |row| {
+                Rc::new(RowContext::new(
+                    table_alias,
+                    Cow::Owned(row),
+                    left_context.as_ref().map(Rc::clone),
+                ))
+            });
+        let rows = joined.into_iter().chain(unmatched).map(Ok);
+
+        return Ok(Box::pin(stream::iter(rows)));
+    }
+
+    let rows = left_rows.and_then(move |project_context| {
+        let init_context = Rc::new(RowContext::new(
+            table_alias,
+            Cow::Owned(null_row(columns.as_ref())),
+            Some(Rc::clone(&project_context)),
+        ));
         let filter_context = filter_context.as_ref().map(Rc::clone);
         let join_executor = Rc::clone(&join_executor);
 
@@ -131,12 +263,13 @@ async fn join<'a, T: GStore>(
             }
             let rows = match join_executor.as_ref() {
                 JoinExecutor::NestedLoop => {
-                    let rows = fetch_relation_rows(storage, relation, &filter_context)
+                    let rows = fetch_relation_rows(storage, ctx, relation, &filter_context)
                         .await?
                         .and_then(|row| future::ok(Cow::Owned(row)))
                         .try_filter_map(move |row| {
                             check_where_clause(
                                 storage,
+                                ctx,
                                 table_alias,
                                 filter_context.as_ref().map(Rc::clone),
                                 Some(&project_context).map(Rc::clone),
@@ -147,11 +280,13 @@ async fn join<'a, T: GStore>(
                     Rows::NestedLoop(rows)
                 }
                 JoinExecutor::Hash {
+                    rows,
                     rows_map,
                     value_expr,
                 } => {
-                    let rows = evaluate(
+                    let indexes = evaluate(
                         storage,
+                        ctx,
                         filter_context.as_ref().map(Rc::clone),
                         None,
                         value_expr,
@@ -160,17 +295,19 @@ async fn join<'a, T: GStore>(
                     .map(Key::try_from)?
                     .map(|hash_key| rows_map.get(&hash_key))?;
 
-                    match rows {
+                    match indexes {
                         None => Rows::Empty(empty()),
-                        Some(rows) => {
-                            let rows = stream::iter(rows)
-                                .filter_map(|row| {
+                        Some(indexes) => {
+                            let rows = stream::iter(indexes)
+                                .filter_map(|index| {
+                                    let row = &rows[*index];
                                     let filter_context = filter_context.as_ref().map(Rc::clone);
                                     let project_context = Some(&project_context).map(Rc::clone);
 
                                     async {
                                         check_where_clause(
                                             storage,
+                                            ctx,
                                             table_alias,
                                             filter_context,
                                             project_context,
@@ -191,12 +328,14 @@ async fn join<'a, T: GStore>(
             };
 
             let rows: Joined = match join_operator {
-                JoinOperator::Inner => Box::pin(rows),
                 JoinOperator::LeftOuter => {
-                    let init_rows = once(async { Ok(init_context) });
+                    let init_rows = stream::once(async { Ok(init_context) });
 
                     Box::pin(OrStream::new(rows, init_rows))
                 }
+                JoinOperator::Inner | JoinOperator::RightOuter | JoinOperator::FullOuter => {
+                    Box::pin(rows)
+                }
             };
 
             Ok(rows)
@@ -210,12 +349,16 @@ async fn join<'a, T: GStore>(
 enum JoinOperator {
     Inner,
     LeftOuter,
+    RightOuter,
+    FullOuter,
 }
 
 enum JoinExecutor<'a> {
     NestedLoop,
     Hash {
-        rows_map: HashMap<Key, Vec<Row>>,
+        rows: Vec<Row>,
+        /// Hash key to the positions of matching `rows`
+        rows_map: HashMap<Key, Vec<usize>>,
         value_expr: &'a Expr,
     },
 }
@@ -223,6 +366,7 @@ enum JoinExecutor<'a> {
 impl<'a> JoinExecutor<'a> {
     async fn new<T: GStore>(
         storage: &'a T,
+        ctx: &'a ExecuteContext,
         relation: &TableFactor,
         filter_context: Option<Rc<RowContext<'a>>>,
         ast_join_executor: &'a AstJoinExecutor,
@@ -236,20 +380,25 @@ impl<'a> JoinExecutor<'a> {
             } => (key_expr, value_expr, where_clause),
         };
 
-        let rows_map = fetch_relation_rows(storage, relation, &filter_context)
+        let rows = fetch_relation_rows(storage, ctx, relation, &filter_context)
             .await?
-            .try_filter_map(|row| {
+            .try_collect::<Vec<_>>()
+            .await?;
+        let rows_map = stream::iter(rows.iter().enumerate())
+            .map(Ok)
+            .try_filter_map(|(index, row)| {
                 let filter_context = filter_context.as_ref().map(Rc::clone);
 
                 async move {
                     let filter_context = Rc::new(RowContext::new(
                         get_alias(relation),
-                        Cow::Borrowed(&row),
+                        Cow::Borrowed(row),
                         filter_context,
                     ));
 
                     let hash_key: Key = evaluate(
                         storage,
+                        ctx,
                         Some(&filter_context).map(Rc::clone),
                         None,
                         key_expr,
@@ -262,10 +411,10 @@ impl<'a> JoinExecutor<'a> {
                     }
 
                     match where_clause {
-                        Some(expr) => check_expr(storage, Some(filter_context), None, expr)
+                        Some(expr) => check_expr(storage, ctx, Some(filter_context), None, expr)
                             .await
-                            .map(|pass| pass.then_some((hash_key, row))),
-                        None => Ok(Some((hash_key, row))),
+                            .map(|pass| pass.then_some((hash_key, index))),
+                        None => Ok(Some((hash_key, index))),
                     }
                 }
             })
@@ -274,14 +423,51 @@ impl<'a> JoinExecutor<'a> {
             .into_iter()
             .into_group_map();
         Ok(Self::Hash {
+            rows,
             rows_map,
             value_expr,
         })
     }
 }
 
+fn null_row(columns: Option<&Rc<[String]>>) -> Row {
+    match columns {
+        Some(columns) => Row::Vec {
+            columns: Rc::clone(columns),
+            values: columns.iter().map(|_| Value::Null).collect(),
+        },
+        None => Row::Map(HashMap::new()),
+    }
+}
+
+/// Builds the context of the already joined relations with every column set to NULL
+async fn null_context<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    relations: Vec<&'a TableFactor>,
+    filter_context: &Option<Rc<RowContext<'a>>>,
+) -> Result<Option<Rc<RowContext<'a>>>> {
+    let mut context = None;
+
+    for relation in relations {
+        let columns = fetch_relation_columns(storage, ctx, relation, filter_context)
+            .await?
+            .map(Rc::from);
+        let row = null_row(columns.as_ref());
+
+        context = Some(Rc::new(RowContext::new(
+            get_alias(relation),
+            Cow::Owned(row),
+            context,
+        )));
+    }
+
+    Ok(context)
+}
+
 async fn check_where_clause<'a, 'b, T: GStore>(
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     table_alias: &'a str,
     filter_context: Option<Rc<RowContext<'a>>>,
     project_context: Option<Rc<RowContext<'a>>>,
@@ -292,7 +478,7 @@ async fn check_where_clause<'a, 'b, T: GStore>(
     let filter_context = Some(Rc::new(filter_context));
 
     match where_clause {
-        Some(expr) => check_expr(storage, filter_context, None, expr).await?,
+        Some(expr) => check_expr(storage, ctx, filter_context, None, expr).await?,
         None => true,
     }
     .then(|| RowContext::new(table_alias, Cow::Owned(row.into_owned()), project_context))
@@ -1,5 +1,5 @@
 use {
-    super::evaluate::evaluate_stateless,
+    super::{context::ExecuteContext, evaluate::evaluate_stateless_with},
     crate::{
         ast::Expr,
         data::{Row, Value},
@@ -14,14 +14,18 @@ pub struct Limit {
 }
 
 impl Limit {
-    pub async fn new(limit: Option<&Expr>, offset: Option<&Expr>) -> Result<Self> {
+    pub async fn new(
+        ctx: &ExecuteContext,
+        limit: Option<&Expr>,
+        offset: Option<&Expr>,
+    ) -> Result<Self> {
         let eval = |expr| async move {
             let expr = match expr {
                 Some(expr) => expr,
                 None => return Ok(None),
             };
 
-            let evaluated = evaluate_stateless(None, expr).await?;
+            let evaluated = evaluate_stateless_with(ctx, None, expr).await?;
             let size: Result<usize> = Value::try_from(evaluated)?.try_into();
 
             size.map(Some)
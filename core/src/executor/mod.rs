@@ -1,28 +1,43 @@
 mod aggregate;
 mod alter;
+mod analyze;
 mod context;
+mod distinct;
 mod evaluate;
 mod execute;
+mod explain;
 mod fetch;
 mod filter;
 mod insert;
 mod join;
 mod limit;
+mod prepare;
+mod procedure;
+mod referential;
+mod returning;
 mod select;
 mod sort;
+mod trigger;
 mod update;
 mod validate;
+mod window;
 
 pub use {
     aggregate::AggregateError,
     alter::AlterError,
-    context::RowContext,
+    analyze::{AnalyzedPlan, PlanStats, StorageCalls},
+    context::{ExecuteContext, Limits, RowContext},
     evaluate::{evaluate_stateless, EvaluateError},
-    execute::{execute, ExecuteError, Payload, PayloadVariable},
+    execute::{execute, execute_with, ExecuteError, Payload, PayloadVariable},
+    explain::PlanNode,
     fetch::FetchError,
     insert::InsertError,
+    prepare::PrepareError,
+    procedure::ProcedureError,
     select::SelectError,
     sort::SortError,
+    trigger::TriggerError,
     update::UpdateError,
     validate::ValidateError,
+    window::WindowError,
 };
@@ -0,0 +1,16 @@
+use {serde::Serialize, std::fmt::Debug, thiserror::Error as ThisError};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum PrepareError {
+    #[error("only a single statement can be prepared, but {0} found")]
+    NotSingleStatement(usize),
+
+    #[error("unsupported placeholder: {0}")]
+    UnsupportedPlaceholder(String),
+
+    #[error("positional '?' and numbered '$n' placeholders cannot be mixed")]
+    MixedPlaceholders,
+
+    #[error("prepared statement expects {0} parameters but {1} given")]
+    ParameterCountMismatch(usize, usize),
+}
@@ -0,0 +1,365 @@
+use {
+    super::{
+        context::{ExecuteContext, RowContext, Scope},
+        evaluate::{evaluate, EvaluateError, Evaluated},
+        execute::execute_inner,
+    },
+    crate::{
+        ast::{DataType, Expr, FunctionBody, OperateFunctionArg, Procedural, Statement, ToSql},
+        data::{CustomFunction, FunctionKind, Row, Value},
+        plan::plan,
+        result::Result,
+        store::{GStore, GStoreMut},
+    },
+    async_recursion::async_recursion,
+    async_trait::async_trait,
+    serde::Serialize,
+    std::{borrow::Cow, fmt::Debug, rc::Rc},
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum ProcedureError {
+    #[error("procedure does not exist: {0}")]
+    ProcedureNotFound(String),
+
+    #[error("variable is not declared: {0}")]
+    VariableNotDeclared(String),
+
+    #[error("{0} is only allowed inside a loop")]
+    OutsideLoop(String),
+
+    #[error("SQL statement is not allowed in function: {0}")]
+    StatementInFunction(String),
+}
+
+/// Checks the number of arguments given to a custom function or a procedure,
+/// omitted arguments take their defaults.
+pub fn validate_args_len(function: &CustomFunction, found: usize) -> Result<()> {
+    let CustomFunction {
+        func_name, args, ..
+    } = function;
+    let min = args.iter().filter(|arg| arg.default.is_none()).count();
+    let max = args.len();
+
+    if !(min..=max).contains(&found) {
+        return Err(EvaluateError::FunctionArgsLengthNotWithinRange {
+            name: func_name.to_owned(),
+            expected_minimum: min,
+            expected_maximum: max,
+            found,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Runs the `BEGIN .. END` body of a custom function with the evaluated arguments,
+/// returns NULL when the body ends without `RETURN`.
+pub async fn run_function<T: GStore>(
+    storage: &T,
+    ctx: &ExecuteContext,
+    name: &str,
+    args: &[OperateFunctionArg],
+    values: Vec<Value>,
+    body: &[Procedural],
+) -> Result<Value> {
+    let ctx = ctx.nest(&format!("function {name}"))?;
+    let mut variables = Variables::new(name, args, values);
+    let mut runtime = FunctionRuntime { storage, ctx: &ctx };
+
+    match run(&mut runtime, &mut variables, body).await? {
+        Flow::Return(value) => Ok(value.unwrap_or(Value::Null)),
+        Flow::Next => Ok(Value::Null),
+        Flow::Exit => Err(ProcedureError::OutsideLoop("EXIT".to_owned()).into()),
+        Flow::Continue => Err(ProcedureError::OutsideLoop("CONTINUE".to_owned()).into()),
+    }
+}
+
+/// Runs `CALL`, statements of the procedure are executed in the transaction of the caller.
+pub async fn call<T: GStore + GStoreMut>(
+    storage: &mut T,
+    ctx: &ExecuteContext,
+    name: &str,
+    exprs: &[Expr],
+) -> Result<()> {
+    let procedure = storage
+        .fetch_function(name)
+        .await?
+        .filter(|function| function.kind == FunctionKind::Procedure)
+        .cloned()
+        .ok_or_else(|| ProcedureError::ProcedureNotFound(name.to_owned()))?;
+
+    validate_args_len(&procedure, exprs.len())?;
+
+    let CustomFunction {
+        func_name,
+        args,
+        body,
+        ..
+    } = &procedure;
+    let exprs = exprs.iter().chain(
+        args.iter()
+            .skip(exprs.len())
+            .filter_map(|arg| arg.default.as_ref()),
+    );
+
+    let mut values = Vec::with_capacity(args.len());
+    for (arg, expr) in args.iter().zip(exprs) {
+        let value = evaluate(&*storage, ctx, None, None, expr)
+            .await?
+            .try_into_value(&arg.data_type, true)?;
+
+        values.push(value);
+    }
+
+    let body = match body {
+        FunctionBody::Block(body) => body.as_slice(),
+        FunctionBody::Return(_) | FunctionBody::Table { .. } => &[],
+    };
+    let ctx = ctx.nest(&format!("procedure {name}"))?;
+    let mut variables = Variables::new(func_name, args, values);
+    let mut runtime = ProcedureRuntime { storage, ctx: &ctx };
+
+    match run(&mut runtime, &mut variables, body).await? {
+        Flow::Return(_) | Flow::Next => Ok(()),
+        Flow::Exit => Err(ProcedureError::OutsideLoop("EXIT".to_owned()).into()),
+        Flow::Continue => Err(ProcedureError::OutsideLoop("CONTINUE".to_owned()).into()),
+    }
+}
+
+/// Arguments and local variables of a running function or procedure
+struct Variables<'a> {
+    name: &'a str,
+    columns: Vec<String>,
+    data_types: Vec<DataType>,
+    values: Vec<Value>,
+}
+
+impl<'a> Variables<'a> {
+    fn new(name: &'a str, args: &[OperateFunctionArg], values: Vec<Value>) -> Self {
+        Self {
+            name,
+            columns: args.iter().map(|arg| arg.name.to_owned()).collect(),
+            data_types: args.iter().map(|arg| arg.data_type.clone()).collect(),
+            values,
+        }
+    }
+
+    /// Declares a variable, declaring an existing one again resets it
+    fn declare(&mut self, name: &str, data_type: &DataType, value: Value) -> Result<()> {
+        let value = value.cast(data_type)?;
+
+        match self.columns.iter().position(|column| column == name) {
+            Some(index) => {
+                self.data_types[index] = data_type.clone();
+                self.values[index] = value;
+            }
+            None => {
+                self.columns.push(name.to_owned());
+                self.data_types.push(data_type.clone());
+                self.values.push(value);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set(&mut self, name: &str, value: Value) -> Result<()> {
+        let index = self
+            .columns
+            .iter()
+            .position(|column| column == name)
+            .ok_or_else(|| ProcedureError::VariableNotDeclared(name.to_owned()))?;
+
+        self.values[index] = value.cast(&self.data_types[index])?;
+
+        Ok(())
+    }
+
+    /// Scope of the statements run with the variables
+    fn to_scope(&self) -> Scope {
+        Scope::Variables {
+            name: self.name.to_owned(),
+            values: self
+                .columns
+                .iter()
+                .cloned()
+                .zip(self.values.clone())
+                .collect(),
+        }
+    }
+
+    fn to_row(&self) -> Row {
+        Row::Vec {
+            columns: Rc::from(self.columns.clone()),
+            values: self.values.clone(),
+        }
+    }
+}
+
+/// What to do after running a statement of a block
+enum Flow {
+    Next,
+    Exit,
+    Continue,
+    Return(Option<Value>),
+}
+
+#[async_trait(?Send)]
+trait Runtime {
+    async fn evaluate(&self, variables: &Variables<'_>, expr: &Expr) -> Result<Value>;
+
+    async fn execute(&mut self, variables: &Variables<'_>, statement: &Statement) -> Result<()>;
+}
+
+struct FunctionRuntime<'a, T> {
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+}
+
+#[async_trait(?Send)]
+impl<T: GStore> Runtime for FunctionRuntime<'_, T> {
+    async fn evaluate(&self, variables: &Variables<'_>, expr: &Expr) -> Result<Value> {
+        evaluate_with(self.storage, self.ctx, variables, expr).await
+    }
+
+    async fn execute(&mut self, _: &Variables<'_>, statement: &Statement) -> Result<()> {
+        Err(ProcedureError::StatementInFunction(statement.to_sql()).into())
+    }
+}
+
+struct ProcedureRuntime<'a, T> {
+    storage: &'a mut T,
+    ctx: &'a ExecuteContext,
+}
+
+#[async_trait(?Send)]
+impl<T: GStore + GStoreMut> Runtime for ProcedureRuntime<'_, T> {
+    async fn evaluate(&self, variables: &Variables<'_>, expr: &Expr) -> Result<Value> {
+        evaluate_with(&*self.storage, self.ctx, variables, expr).await
+    }
+
+    async fn execute(&mut self, variables: &Variables<'_>, statement: &Statement) -> Result<()> {
+        let statement = plan(&*self.storage, statement.clone()).await?;
+        let ctx = self.ctx.with_scope(variables.to_scope());
+
+        execute_inner(self.storage, &ctx, &statement)
+            .await
+            .map(|_| ())
+    }
+}
+
+async fn evaluate_with<T: GStore>(
+    storage: &T,
+    ctx: &ExecuteContext,
+    variables: &Variables<'_>,
+    expr: &Expr,
+) -> Result<Value> {
+    let context = RowContext::new(variables.name, Cow::Owned(variables.to_row()), None);
+    let evaluated = evaluate(storage, ctx, Some(Rc::new(context)), None, expr).await?;
+
+    Value::try_from(evaluated)
+}
+
+/// Evaluates a condition, NULL is regarded as FALSE
+async fn check<R: Runtime>(runtime: &R, variables: &Variables<'_>, expr: &Expr) -> Result<bool> {
+    match runtime.evaluate(variables, expr).await? {
+        Value::Null => Ok(false),
+        value => bool::try_from(Evaluated::Value(value)),
+    }
+}
+
+#[async_recursion(?Send)]
+async fn run<R: Runtime>(
+    runtime: &mut R,
+    variables: &mut Variables<'_>,
+    body: &[Procedural],
+) -> Result<Flow> {
+    for procedural in body {
+        let flow = match procedural {
+            Procedural::Declare {
+                name,
+                data_type,
+                default,
+            } => {
+                let value = match default {
+                    Some(expr) => runtime.evaluate(variables, expr).await?,
+                    None => Value::Null,
+                };
+
+                variables.declare(name, data_type, value)?;
+                Flow::Next
+            }
+            Procedural::Set { name, value } => {
+                let value = runtime.evaluate(variables, value).await?;
+
+                variables.set(name, value)?;
+                Flow::Next
+            }
+            Procedural::If {
+                branches,
+                else_body,
+            } => {
+                let mut matched = else_body.as_deref();
+                for (condition, body) in branches {
+                    if check(runtime, variables, condition).await? {
+                        matched = Some(body);
+                        break;
+                    }
+                }
+
+                match matched {
+                    Some(body) => run(runtime, variables, body).await?,
+                    None => Flow::Next,
+                }
+            }
+            Procedural::While { condition, body } => loop {
+                if !check(runtime, variables, condition).await? {
+                    break Flow::Next;
+                }
+
+                match run(runtime, variables, body).await? {
+                    Flow::Exit => break Flow::Next,
+                    Flow::Return(value) => break Flow::Return(value),
+                    Flow::Next | Flow::Continue => {}
+                }
+            },
+            Procedural::Loop(body) => loop {
+                match run(runtime, variables, body).await? {
+                    Flow::Exit => break Flow::Next,
+                    Flow::Return(value) => break Flow::Return(value),
+                    Flow::Next | Flow::Continue => {}
+                }
+            },
+            Procedural::Exit(condition) | Procedural::Continue(condition) => {
+                let matched = match condition {
+                    Some(condition) => check(runtime, variables, condition).await?,
+                    None => true,
+                };
+
+                match (matched, procedural) {
+                    (false, _) => Flow::Next,
+                    (true, Procedural::Exit(_)) => Flow::Exit,
+                    (true, _) => Flow::Continue,
+                }
+            }
+            Procedural::Return(expr) => match expr {
+                Some(expr) => Flow::Return(Some(runtime.evaluate(variables, expr).await?)),
+                None => Flow::Return(None),
+            },
+            Procedural::Statement(statement) => {
+                runtime.execute(variables, statement).await?;
+
+                Flow::Next
+            }
+        };
+
+        if !matches!(flow, Flow::Next) {
+            return Ok(flow);
+        }
+    }
+
+    Ok(Flow::Next)
+}
@@ -0,0 +1,275 @@
+use {
+    super::validate::{find_column_index, validate_rows, ColumnValidation, ValidateError},
+    crate::{
+        ast::{ForeignKey, ReferentialAction},
+        data::{Key, Schema, Value},
+        result::Result,
+        store::{DataRow, GStore, GStoreMut},
+    },
+    async_recursion::async_recursion,
+    std::collections::{HashMap, HashSet},
+};
+
+/// Writes to referencing tables caused by `ON DELETE` and `ON UPDATE` actions of foreign keys,
+/// they are written directly without firing the triggers of the referencing tables, but the
+/// updated rows are still validated against the CHECKs and unique constraints of their tables.
+/// Updates never change a primary key, as `ON UPDATE CASCADE` and `SET NULL` are rejected on
+/// primary key columns when the table is created.
+#[derive(Default)]
+pub struct ReferentialActions {
+    /// Rows updated by the actions, with the changes of every foreign key merged
+    updates: HashMap<String, HashMap<Key, DataRow>>,
+    deletes: HashMap<String, Vec<Key>>,
+    /// Rows written by the statement itself, or deleted by the actions
+    written: HashSet<(String, Key)>,
+    /// Foreign keys which already acted on a row, as (table, foreign key, row key)
+    acted: HashSet<(String, String, Key)>,
+}
+
+impl ReferentialActions {
+    /// Plans actions for rows of `table_name` which are going to be deleted, or updated to the
+    /// given new rows. It must be called before the rows are written, and it fails without any
+    /// write when a `RESTRICT` or `NO ACTION` foreign key still references a changed row.
+    pub async fn plan<'a, T: GStore>(
+        storage: &T,
+        table_name: &str,
+        changes: impl IntoIterator<Item = (&'a Key, Option<&'a DataRow>)>,
+    ) -> Result<Self> {
+        let mut actions = Self::default();
+        let schemas = storage.fetch_all_schemas().await?;
+        let referenced = schemas.iter().any(|Schema { foreign_keys, .. }| {
+            foreign_keys
+                .iter()
+                .any(|foreign_key| foreign_key.referenced_table_name == table_name)
+        });
+
+        if !referenced {
+            return Ok(actions);
+        }
+
+        let mut rows = Vec::new();
+        for (key, new_row) in changes {
+            if let Some(old_row) = storage.fetch_data(table_name, key).await? {
+                rows.push((old_row, new_row.cloned()));
+            }
+
+            actions
+                .written
+                .insert((table_name.to_owned(), key.to_owned()));
+        }
+
+        plan_changes(storage, &schemas, &mut actions, table_name, rows).await?;
+
+        Ok(actions)
+    }
+
+    pub async fn apply<T: GStoreMut>(self, storage: &mut T) -> Result<()> {
+        for (table_name, rows) in self.updates {
+            storage
+                .insert_data(&table_name, rows.into_iter().collect())
+                .await?;
+        }
+
+        for (table_name, keys) in self.deletes {
+            storage.delete_data(&table_name, keys).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_recursion(?Send)]
+async fn plan_changes<T: GStore>(
+    storage: &T,
+    schemas: &[Schema],
+    actions: &mut ReferentialActions,
+    table_name: &str,
+    changes: Vec<(DataRow, Option<DataRow>)>,
+) -> Result<()> {
+    let column_defs = match schemas
+        .iter()
+        .find(|schema| schema.table_name == table_name)
+        .and_then(|schema| schema.column_defs.as_deref())
+    {
+        Some(column_defs) => column_defs,
+        None => return Ok(()),
+    };
+
+    for schema in schemas {
+        let Schema {
+            table_name: referencing_table_name,
+            column_defs: referencing_column_defs,
+            foreign_keys,
+            ..
+        } = schema;
+        let referencing_column_defs = match referencing_column_defs {
+            Some(column_defs) => column_defs,
+            None => continue,
+        };
+
+        for foreign_key in foreign_keys
+            .iter()
+            .filter(|foreign_key| foreign_key.referenced_table_name == table_name)
+        {
+            let ForeignKey {
+                name,
+                referencing_column_name,
+                referenced_column_name,
+                on_delete,
+                on_update,
+                ..
+            } = foreign_key;
+
+            let (referencing_index, referenced_index) = match (
+                find_column_index(referencing_column_defs, referencing_column_name),
+                find_column_index(column_defs, referenced_column_name),
+            ) {
+                (Some(referencing_index), Some(referenced_index)) => {
+                    (referencing_index, referenced_index)
+                }
+                _ => continue,
+            };
+
+            let mut changed = HashMap::new();
+            for (old_row, new_row) in changes.iter() {
+                let old_value = match get_value(old_row, referenced_index) {
+                    Some(value) if !value.is_null() => value,
+                    _ => continue,
+                };
+                let new_value = new_row
+                    .as_ref()
+                    .and_then(|new_row| get_value(new_row, referenced_index));
+
+                if new_value == Some(old_value) {
+                    continue;
+                }
+
+                changed.insert(Key::try_from(old_value)?, new_value.cloned());
+            }
+
+            if changed.is_empty() {
+                continue;
+            }
+
+            let mut referencing_changes = Vec::new();
+            let mut updated_rows = Vec::new();
+            for item in storage.scan_data(referencing_table_name).await? {
+                let (key, data_row) = item?;
+                let row_key = (referencing_table_name.to_owned(), key);
+                let acted_key = (
+                    referencing_table_name.to_owned(),
+                    name.to_owned(),
+                    row_key.1.clone(),
+                );
+
+                if actions.written.contains(&row_key) || actions.acted.contains(&acted_key) {
+                    continue;
+                }
+
+                let data_row = actions
+                    .updates
+                    .get(referencing_table_name)
+                    .and_then(|rows| rows.get(&row_key.1))
+                    .cloned()
+                    .unwrap_or(data_row);
+                let new_value = match get_value(&data_row, referencing_index) {
+                    Some(value) if !value.is_null() => changed.get(&Key::try_from(value)?),
+                    _ => None,
+                };
+                let new_value = match new_value {
+                    Some(new_value) => new_value,
+                    None => continue,
+                };
+                let action = match new_value {
+                    Some(_) => on_update,
+                    None => on_delete,
+                };
+                let new_value = match (action, new_value) {
+                    (ReferentialAction::NoAction | ReferentialAction::Restrict, _) => {
+                        return Err(ValidateError::ReferencingRowExists {
+                            foreign_key: name.to_owned(),
+                            referencing_table_name: referencing_table_name.to_owned(),
+                        }
+                        .into());
+                    }
+                    (ReferentialAction::Cascade, new_value) => new_value.clone(),
+                    (ReferentialAction::SetNull, _) => Some(Value::Null),
+                };
+                let new_row = new_value.map(|new_value| match &data_row {
+                    DataRow::Vec(values) => {
+                        let mut values = values.clone();
+                        values[referencing_index] = new_value;
+
+                        DataRow::Vec(values)
+                    }
+                    DataRow::Map(_) => data_row.clone(),
+                });
+
+                if let Some(DataRow::Vec(values)) = &new_row {
+                    updated_rows.push(values.clone());
+                }
+
+                actions.acted.insert(acted_key);
+
+                let updates = actions
+                    .updates
+                    .entry(referencing_table_name.to_owned())
+                    .or_default();
+                match &new_row {
+                    Some(new_row) => {
+                        updates.insert(row_key.1.clone(), new_row.clone());
+                    }
+                    None => {
+                        updates.remove(&row_key.1);
+                        actions
+                            .deletes
+                            .entry(referencing_table_name.to_owned())
+                            .or_default()
+                            .push(row_key.1.clone());
+                        actions.written.insert(row_key);
+                    }
+                }
+
+                referencing_changes.push((data_row, new_row));
+            }
+
+            if !updated_rows.is_empty() {
+                // the new value is the one written to the referenced row, so it is not looked up
+                let schema = Schema {
+                    foreign_keys: Vec::new(),
+                    ..schema.clone()
+                };
+                let column_validation = ColumnValidation::SpecifiedColumns(
+                    referencing_column_defs,
+                    vec![referencing_column_name.to_owned()],
+                );
+
+                validate_rows(
+                    storage,
+                    &schema,
+                    column_validation,
+                    updated_rows.iter().map(Vec::as_slice),
+                )
+                .await?;
+            }
+
+            plan_changes(
+                storage,
+                schemas,
+                actions,
+                referencing_table_name,
+                referencing_changes,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn get_value(data_row: &DataRow, index: usize) -> Option<&Value> {
+    match data_row {
+        DataRow::Vec(values) => values.get(index),
+        DataRow::Map(_) => None,
+    }
+}
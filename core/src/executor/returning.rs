@@ -0,0 +1,98 @@
+use {
+    super::{
+        context::{ExecuteContext, RowContext},
+        execute::Payload,
+        fetch::{fetch_columns, FetchError},
+        select::Project,
+    },
+    crate::{
+        ast::SelectItem,
+        data::Row,
+        result::Result,
+        store::{DataRow, GStore},
+    },
+    futures::stream::{self, StreamExt, TryStreamExt},
+    std::{borrow::Cow, rc::Rc},
+};
+
+/// Projects rows affected by INSERT, UPDATE or DELETE with RETURNING items,
+/// the same way as SELECT projection does.
+pub async fn project_returning<T: GStore>(
+    storage: &T,
+    ctx: &ExecuteContext,
+    table_name: &str,
+    returning: &[SelectItem],
+    rows: Vec<DataRow>,
+) -> Result<Payload> {
+    let columns = fetch_columns(storage, table_name).await?.map(Rc::from);
+    let labels = fetch_returning_labels(table_name, columns.as_deref(), returning)?.map(Rc::from);
+    let columns = columns.unwrap_or_else(|| Rc::from([]));
+
+    let project = Project::new(storage, ctx, None, returning);
+    let rows = stream::iter(rows)
+        .then(|data_row| {
+            let row = match data_row {
+                DataRow::Vec(values) => Row::Vec {
+                    columns: Rc::clone(&columns),
+                    values,
+                },
+                DataRow::Map(values) => Row::Map(values),
+            };
+            let context = Rc::new(RowContext::new(table_name, Cow::Owned(row), None));
+            let labels = labels.as_ref().map(Rc::clone);
+            let project = &project;
+
+            async move { project.apply(None, labels, context).await }
+        })
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    match labels {
+        Some(labels) => rows
+            .into_iter()
+            .map(Row::try_into_vec)
+            .collect::<Result<_>>()
+            .map(|rows| Payload::Select {
+                labels: labels.iter().cloned().collect(),
+                rows,
+            }),
+        None => rows
+            .into_iter()
+            .map(Row::try_into_map)
+            .collect::<Result<_>>()
+            .map(Payload::SelectMap),
+    }
+}
+
+fn fetch_returning_labels(
+    table_name: &str,
+    columns: Option<&[String]>,
+    returning: &[SelectItem],
+) -> Result<Option<Vec<String>>> {
+    let columns = match columns {
+        Some(columns) => columns,
+        None if returning.iter().any(|item| {
+            matches!(
+                item,
+                SelectItem::Wildcard | SelectItem::QualifiedWildcard(_)
+            )
+        }) =>
+        {
+            return Ok(None);
+        }
+        None => &[],
+    };
+
+    returning
+        .iter()
+        .map(|item| match item {
+            SelectItem::Wildcard => Ok(columns.to_vec()),
+            SelectItem::QualifiedWildcard(alias) if alias == table_name => Ok(columns.to_vec()),
+            SelectItem::QualifiedWildcard(alias) => {
+                Err(FetchError::TableAliasNotFound(alias.to_owned()).into())
+            }
+            SelectItem::Expr { label, .. } => Ok(vec![label.to_owned()]),
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(|labels| Some(labels.concat()))
+}
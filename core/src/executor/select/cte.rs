@@ -0,0 +1,182 @@
+use {
+    super::{
+        select_with_labels, set_expr_rows, set_operation::operand_keys, sort_stateless, SelectError,
+    },
+    crate::{
+        ast::{Cte, Query, SetExpr, SetOperator, TableAlias, With},
+        data::{Key, Row},
+        executor::{
+            analyze::Probe,
+            context::{ExecuteContext, RowContext},
+            fetch::apply_column_aliases,
+            limit::Limit,
+        },
+        plan::read_table_names,
+        result::Result,
+        store::GStore,
+    },
+    async_recursion::async_recursion,
+    futures::stream::{self, TryStreamExt},
+    std::{collections::HashSet, rc::Rc},
+};
+
+/// Materializes common table expressions in order and binds each of them to the row context,
+/// so the following CTEs and the main query can read it like a table.
+#[async_recursion(?Send)]
+pub async fn bind_ctes<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    with: &'a With,
+    filter_context: Option<Rc<RowContext<'a>>>,
+) -> Result<Option<Rc<RowContext<'a>>>> {
+    let With {
+        recursive,
+        cte_tables,
+    } = with;
+
+    let mut filter_context = filter_context;
+
+    for Cte { alias, query } in cte_tables {
+        let TableAlias { name, columns } = alias;
+        let next = filter_context.as_ref().map(Rc::clone);
+
+        let (columns, rows) = match &query.body {
+            SetExpr::SetOperation {
+                op: SetOperator::Union,
+                all,
+                left,
+                right,
+            } if *recursive && references(right, name) => {
+                let Query {
+                    order_by,
+                    limit,
+                    offset,
+                    ..
+                } = query;
+
+                let (columns, rows) =
+                    recursive_rows(storage, ctx, alias, *all, left, right, next).await?;
+                let rows = sort_stateless(ctx, rows, order_by, Probe::disabled()).await?;
+                let rows = Limit::new(ctx, limit.as_ref(), offset.as_ref())
+                    .await?
+                    .apply(stream::iter(rows.into_iter().map(Ok)))
+                    .try_collect()
+                    .await?;
+
+                (columns, rows)
+            }
+            _ => {
+                let (labels, rows) = select_with_labels(storage, ctx, query, next).await?;
+                let columns = apply_column_aliases(name, columns, labels)?;
+
+                (columns, rows.try_collect::<Vec<_>>().await?)
+            }
+        };
+
+        filter_context = Some(Rc::new(RowContext::Cte {
+            name,
+            columns,
+            rows: Rc::from(rows),
+            next: filter_context,
+        }));
+    }
+
+    Ok(filter_context)
+}
+
+/// Evaluates `WITH RECURSIVE` by running the recursive term against the rows found by the
+/// previous iteration until no new rows are produced.
+async fn recursive_rows<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    alias: &'a TableAlias,
+    all: bool,
+    anchor: &'a SetExpr,
+    recursive: &'a SetExpr,
+    filter_context: Option<Rc<RowContext<'a>>>,
+) -> Result<(Option<Vec<String>>, Vec<Row>)> {
+    let TableAlias { name, columns } = alias;
+    let (labels, rows) = set_expr_rows(
+        storage,
+        ctx,
+        anchor,
+        filter_context.as_ref().map(Rc::clone),
+        Probe::disabled(),
+    )
+    .await?;
+    let columns = apply_column_aliases(name, columns, labels)?;
+    let labels = columns.as_deref().map(Rc::<[String]>::from);
+    let relabel = |rows: Vec<Row>| -> Vec<Row> {
+        rows.into_iter()
+            .map(|row| match (row, &labels) {
+                (Row::Vec { values, .. }, Some(labels)) => Row::Vec {
+                    columns: Rc::clone(labels),
+                    values,
+                },
+                (row, _) => row,
+            })
+            .collect()
+    };
+
+    let mut keys_set = HashSet::new();
+    let mut working_rows = new_rows(relabel(rows), all, &mut keys_set)?;
+    let mut rows = working_rows.clone();
+    let max_recursion = ctx.limits().max_recursion;
+    let mut recursion = 0;
+
+    while !working_rows.is_empty() {
+        if recursion == max_recursion {
+            return Err(SelectError::RecursionLimitExceeded(name.to_owned(), max_recursion).into());
+        }
+
+        recursion += 1;
+        let context = RowContext::Cte {
+            name,
+            columns: columns.clone(),
+            rows: Rc::from(working_rows),
+            next: filter_context.as_ref().map(Rc::clone),
+        };
+
+        let (_, found) = set_expr_rows(
+            storage,
+            ctx,
+            recursive,
+            Some(Rc::new(context)),
+            Probe::disabled(),
+        )
+        .await?;
+
+        working_rows = new_rows(relabel(found), all, &mut keys_set)?;
+        rows.extend(working_rows.iter().cloned());
+    }
+
+    Ok((columns, rows))
+}
+
+/// Drops rows already seen by the recursion unless `UNION ALL` is used
+fn new_rows(rows: Vec<Row>, all: bool, keys_set: &mut HashSet<Vec<Key>>) -> Result<Vec<Row>> {
+    if all {
+        return Ok(rows);
+    }
+
+    rows.into_iter()
+        .map(|row| operand_keys(&row).map(|keys| (keys, row)))
+        .filter_map(|item| match item {
+            Ok((keys, row)) => keys_set.insert(keys).then_some(Ok(row)),
+            Err(error) => Some(Err(error)),
+        })
+        .collect()
+}
+
+/// Checks whether `name` is read as a table by `set_expr`, including its subqueries
+fn references(set_expr: &SetExpr, name: &str) -> bool {
+    let query = Query {
+        with: None,
+        body: set_expr.clone(),
+        order_by: Vec::new(),
+        limit: None,
+        offset: None,
+    };
+
+    read_table_names(&query).contains(name)
+}
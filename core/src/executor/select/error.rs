@@ -4,4 +4,10 @@ use {serde::Serialize, std::fmt::Debug, thiserror::Error};
 pub enum SelectError {
     #[error("VALUES lists must all be the same length")]
     NumberOfValuesDifferent,
+
+    #[error("each set operation query must have the same number of columns")]
+    NumberOfSetOperationColumnsDifferent,
+
+    #[error("recursive CTE {0} exceeds the maximum recursion of {1}")]
+    RecursionLimitExceeded(String, usize),
 }
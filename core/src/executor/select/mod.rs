@@ -1,19 +1,24 @@
+mod cte;
 mod error;
 mod project;
+mod set_operation;
 
-pub use error::SelectError;
+pub use {error::SelectError, project::Project};
 
 use {
-    self::project::Project,
+    self::{cte::bind_ctes, set_operation::SetOperation},
     super::{
         aggregate::Aggregator,
-        context::{AggregateContext, RowContext},
-        evaluate::evaluate_stateless,
+        analyze::{Probe, Profiler, Stage},
+        context::{AggregateContext, ExecuteContext, RowContext},
+        distinct::Distinct,
+        evaluate::evaluate_stateless_with,
         fetch::{fetch_labels, fetch_relation_rows},
         filter::Filter,
         join::Join,
         limit::Limit,
         sort::Sort,
+        window::Window,
     },
     crate::{
         ast::{Expr, OrderByExpr, Query, Select, SetExpr, TableWithJoins, Values},
@@ -22,12 +27,16 @@ use {
         store::GStore,
     },
     async_recursion::async_recursion,
+    chrono::Utc,
     futures::stream::{self, Stream, StreamExt, TryStreamExt},
     std::{borrow::Cow, rc::Rc},
     utils::Vector,
 };
 
-async fn rows_with_labels(exprs_list: &[Vec<Expr>]) -> Result<(Vec<Row>, Vec<String>)> {
+async fn rows_with_labels(
+    ctx: &ExecuteContext,
+    exprs_list: &[Vec<Expr>],
+) -> Result<(Vec<Row>, Vec<String>)> {
     let first_len = exprs_list[0].len();
     let labels = (1..=first_len)
         .map(|i| format!("column{}", i))
@@ -45,7 +54,7 @@ async fn rows_with_labels(exprs_list: &[Vec<Expr>]) -> Result<(Vec<Row>, Vec<Str
         let mut values = Vec::with_capacity(exprs.len());
 
         for (i, expr) in exprs.iter().enumerate() {
-            let evaluated = evaluate_stateless(None, expr).await?;
+            let evaluated = evaluate_stateless_with(ctx, None, expr).await?;
 
             let value = match column_types[i] {
                 Some(ref data_type) => evaluated.try_into_value(data_type, true)?,
@@ -69,7 +78,14 @@ async fn rows_with_labels(exprs_list: &[Vec<Expr>]) -> Result<(Vec<Row>, Vec<Str
     Ok((rows, labels))
 }
 
-async fn sort_stateless(rows: Vec<Row>, order_by: &[OrderByExpr]) -> Result<Vec<Row>> {
+async fn sort_stateless(
+    ctx: &ExecuteContext,
+    rows: Vec<Row>,
+    order_by: &[OrderByExpr],
+    probe: Probe<'_>,
+) -> Result<Vec<Row>> {
+    let started = Utc::now();
+    let rows_in = rows.len();
     let sorted = stream::iter(rows.into_iter())
         .then(|row| async move {
             stream::iter(order_by)
@@ -77,7 +93,7 @@ async fn sort_stateless(rows: Vec<Row>, order_by: &[OrderByExpr]) -> Result<Vec<
                     let row = Some(&row);
 
                     async move {
-                        evaluate_stateless(row.map(Row::as_context), expr)
+                        evaluate_stateless_with(ctx, row.map(Row::as_context), expr)
                             .await
                             .and_then(Value::try_from)
                             .and_then(Key::try_from)
@@ -96,72 +112,194 @@ async fn sort_stateless(rows: Vec<Row>, order_by: &[OrderByExpr]) -> Result<Vec<
         .map(|(_, row)| row)
         .collect::<Vec<_>>();
 
+    probe.rows(Stage::Sort, rows_in, sorted.len());
+    probe.elapse(Stage::Sort, started);
+
     Ok(sorted)
 }
 
-#[async_recursion(?Send)]
 pub async fn select_with_labels<'a, T: GStore>(
     storage: &'a T,
+    ctx: &'a ExecuteContext,
+    query: &'a Query,
+    filter_context: Option<Rc<RowContext<'a>>>,
+) -> Result<(Option<Vec<String>>, impl Stream<Item = Result<Row>> + 'a)> {
+    query_with_labels(storage, ctx, query, filter_context, Probe::disabled()).await
+}
+
+/// Selects while `profiler` records the rows and time of each stage for `EXPLAIN ANALYZE`
+pub async fn select_profiled<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    query: &'a Query,
+    profiler: &'a Profiler,
+) -> Result<impl Stream<Item = Result<Row>> + 'a> {
+    query_with_labels(storage, ctx, query, None, Probe::new(Some(profiler)))
+        .await
+        .map(|(_, rows)| rows)
+}
+
+async fn query_with_labels<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
     query: &'a Query,
     filter_context: Option<Rc<RowContext<'a>>>,
+    probe: Probe<'a>,
+) -> Result<(Option<Vec<String>>, impl Stream<Item = Result<Row>> + 'a)> {
+    let Query {
+        with,
+        body,
+        order_by,
+        limit,
+        offset,
+    } = query;
+    let filter_context = match with {
+        Some(with) => bind_ctes(storage, ctx, with, filter_context).await?,
+        None => filter_context,
+    };
+    let limit = Limit::new(ctx, limit.as_ref(), offset.as_ref()).await?;
+
+    set_expr_with_labels(storage, ctx, body, order_by, limit, filter_context, probe).await
+}
+
+pub fn leftmost_operand(set_expr: &SetExpr) -> &SetExpr {
+    match set_expr {
+        SetExpr::SetOperation { left, .. } => leftmost_operand(left),
+        SetExpr::Select(_) | SetExpr::Values(_) => set_expr,
+    }
+}
+
+#[async_recursion(?Send)]
+async fn set_expr_with_labels<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    set_expr: &'a SetExpr,
+    order_by: &'a [OrderByExpr],
+    limit: Limit,
+    filter_context: Option<Rc<RowContext<'a>>>,
+    probe: Probe<'a>,
 ) -> Result<(Option<Vec<String>>, impl Stream<Item = Result<Row>> + 'a)> {
     #[derive(futures_enum::Stream)]
-    enum Row<S1, S2> {
+    enum Row<S1, S2, S3> {
         Select(S2),
         Values(S1),
+        SetOperation(S3),
     }
 
     let Select {
+        distinct,
         from: table_with_joins,
         selection: where_clause,
         projection,
         group_by,
+        grouping_sets,
         having,
-    } = match &query.body {
+    } = match set_expr {
         SetExpr::Select(statement) => statement.as_ref(),
         SetExpr::Values(Values(values_list)) => {
-            let limit = Limit::new(query.limit.as_ref(), query.offset.as_ref()).await?;
-            let (rows, labels) = rows_with_labels(values_list).await?;
-            let rows = sort_stateless(rows, &query.order_by).await?;
+            let (rows, labels) = probe
+                .time(Stage::Fetch, rows_with_labels(ctx, values_list))
+                .await?;
+            probe.rows(Stage::Fetch, 0, rows.len());
+
+            let rows = sort_stateless(ctx, rows, order_by, probe).await?;
             let rows = stream::iter(rows.into_iter().map(Ok));
-            let rows = limit.apply(rows);
+            let rows = probe.output(Stage::Limit, limit.apply(probe.input(Stage::Limit, rows)));
 
             return Ok((Some(labels), Row::Values(rows)));
         }
+        SetExpr::SetOperation {
+            op,
+            all,
+            left,
+            right,
+        } => {
+            let started = Utc::now();
+            let (left_probe, right_probe) = probe.operands(left);
+            let (labels, left) = set_expr_rows(
+                storage,
+                ctx,
+                left,
+                filter_context.as_ref().map(Rc::clone),
+                left_probe,
+            )
+            .await?;
+            let (_, right) =
+                set_expr_rows(storage, ctx, right, filter_context, right_probe).await?;
+            let rows_in = left.len() + right.len();
+            let rows = SetOperation::new(*op, *all, labels.as_deref()).apply(left, right)?;
+            probe.rows(Stage::Fetch, rows_in, rows.len());
+            probe.elapse(Stage::Fetch, started);
+
+            let rows = sort_stateless(ctx, rows, order_by, probe).await?;
+            let rows = stream::iter(rows.into_iter().map(Ok));
+            let rows = probe.output(Stage::Limit, limit.apply(probe.input(Stage::Limit, rows)));
+
+            return Ok((labels, Row::SetOperation(rows)));
+        }
     };
 
     let TableWithJoins { relation, joins } = &table_with_joins;
-    let rows = fetch_relation_rows(storage, relation, &None)
+    let rows = probe
+        .time(
+            Stage::Fetch,
+            fetch_relation_rows(storage, ctx, relation, &filter_context),
+        )
         .await?
         .map(move |row| {
             let row = row?;
             let alias = get_alias(relation);
 
-            Ok(RowContext::new(alias, Cow::Owned(row), None))
+            Ok(Rc::new(RowContext::new(alias, Cow::Owned(row), None)))
         });
+    let rows = probe.output(Stage::Fetch, rows);
 
-    let join = Join::new(storage, joins, filter_context.as_ref().map(Rc::clone));
+    let join = Join::new(
+        storage,
+        ctx,
+        relation,
+        joins,
+        filter_context.as_ref().map(Rc::clone),
+        probe,
+    );
     let aggregate = Aggregator::new(
         storage,
+        ctx,
         projection,
         group_by,
+        grouping_sets,
         having.as_ref(),
         filter_context.as_ref().map(Rc::clone),
     );
     let filter = Rc::new(Filter::new(
         storage,
+        ctx,
         where_clause.as_ref(),
         filter_context.as_ref().map(Rc::clone),
         None,
     ));
-    let limit = Limit::new(query.limit.as_ref(), query.offset.as_ref()).await?;
+    let window = Window::new(
+        storage,
+        ctx,
+        projection,
+        order_by,
+        filter_context.as_ref().map(Rc::clone),
+    );
     let sort = Sort::new(
         storage,
+        ctx,
+        filter_context.as_ref().map(Rc::clone),
+        order_by,
+    );
+    let distinct = Distinct::new(
+        storage,
+        ctx,
         filter_context.as_ref().map(Rc::clone),
-        &query.order_by,
+        distinct.as_ref(),
     );
 
     let rows = join.apply(rows).await?;
+    let rows = probe.input(Stage::Filter, rows);
     let rows = rows.try_filter_map(move |project_context| {
         let filter = Rc::clone(&filter);
 
@@ -173,13 +311,18 @@ pub async fn select_with_labels<'a, T: GStore>(
         }
     });
 
-    let rows = aggregate.apply(rows).await?;
+    let rows = probe.output(Stage::Filter, rows);
 
-    let labels = fetch_labels(storage, relation, joins, projection)
+    let rows = probe.input(Stage::Aggregate, rows);
+    let rows = probe.time(Stage::Aggregate, aggregate.apply(rows)).await?;
+    let rows = probe.output(Stage::Aggregate, rows);
+    let rows = window.apply(rows).await?;
+
+    let labels = fetch_labels(storage, ctx, relation, joins, projection, &filter_context)
         .await?
         .map(Rc::from);
 
-    let project = Rc::new(Project::new(storage, filter_context, projection));
+    let project = Rc::new(Project::new(storage, ctx, filter_context, projection));
     let project_labels = labels.as_ref().map(Rc::clone);
     let rows = rows.and_then(move |aggregate_context| {
         let labels = project_labels.as_ref().map(Rc::clone);
@@ -196,19 +339,41 @@ pub async fn select_with_labels<'a, T: GStore>(
         }
     });
 
-    let rows = sort.apply(rows, get_alias(relation)).await?;
-    let rows = limit.apply(rows);
+    let rows = probe.input(Stage::Sort, rows);
+    let rows = probe
+        .time(Stage::Sort, sort.apply(rows, get_alias(relation)))
+        .await?;
+    let rows = probe.output(Stage::Sort, rows);
+    let rows = probe.input(Stage::Distinct, rows);
+    let rows = probe.output(Stage::Distinct, distinct.apply(rows, get_alias(relation)));
+    let rows = probe.output(Stage::Limit, limit.apply(probe.input(Stage::Limit, rows)));
     let labels = labels.map(|labels| labels.iter().cloned().collect());
 
     Ok((labels, Row::Select(rows)))
 }
 
+async fn set_expr_rows<'a, T: GStore>(
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    set_expr: &'a SetExpr,
+    filter_context: Option<Rc<RowContext<'a>>>,
+    probe: Probe<'a>,
+) -> Result<(Option<Vec<String>>, Vec<Row>)> {
+    let limit = Limit::new(ctx, None, None).await?;
+    let (labels, rows) =
+        set_expr_with_labels(storage, ctx, set_expr, &[], limit, filter_context, probe).await?;
+    let rows = rows.try_collect().await?;
+
+    Ok((labels, rows))
+}
+
 pub async fn select<'a, T: GStore>(
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     query: &'a Query,
     filter_context: Option<Rc<RowContext<'a>>>,
 ) -> Result<impl Stream<Item = Result<Row>> + 'a> {
-    select_with_labels(storage, query, filter_context)
+    select_with_labels(storage, ctx, query, filter_context)
         .await
         .map(|(_, rows)| rows)
 }
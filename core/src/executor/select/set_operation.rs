@@ -0,0 +1,211 @@
+use {
+    super::SelectError,
+    crate::{
+        ast::{DataType, SetOperator},
+        data::{Key, Row, Value},
+        executor::distinct::row_keys,
+        plan::widen_data_type,
+        result::Result,
+    },
+    ordered_float::OrderedFloat,
+    rust_decimal::prelude::ToPrimitive,
+    std::{
+        collections::{HashMap, HashSet},
+        rc::Rc,
+    },
+};
+
+pub struct SetOperation {
+    op: SetOperator,
+    all: bool,
+    columns: Option<Rc<[String]>>,
+}
+
+impl SetOperation {
+    pub fn new(op: SetOperator, all: bool, labels: Option<&[String]>) -> Self {
+        Self {
+            op,
+            all,
+            columns: labels.map(Rc::from),
+        }
+    }
+
+    pub fn apply(&self, left: Vec<Row>, right: Vec<Row>) -> Result<Vec<Row>> {
+        let left = self.relabel(left)?;
+        let right = self.relabel(right)?;
+        let data_types = widen_columns(&left, &right);
+        let left = cast_rows(left, &data_types)?;
+        let right = cast_rows(right, &data_types)?;
+
+        match (self.op, self.all) {
+            (SetOperator::Union, true) => Ok(left.into_iter().chain(right).collect()),
+            (SetOperator::Union, false) => {
+                let mut keys_set = HashSet::new();
+
+                left.into_iter()
+                    .chain(right)
+                    .map(|row| operand_keys(&row).map(|keys| (keys, row)))
+                    .filter_map(|item| match item {
+                        Ok((keys, row)) => keys_set.insert(keys).then_some(Ok(row)),
+                        Err(error) => Some(Err(error)),
+                    })
+                    .collect()
+            }
+            (SetOperator::Intersect, all) => {
+                let mut counts = count_keys(&right)?;
+
+                left.into_iter()
+                    .map(|row| operand_keys(&row).map(|keys| (keys, row)))
+                    .filter_map(|item| {
+                        let (keys, row) = match item {
+                            Ok(item) => item,
+                            Err(error) => return Some(Err(error)),
+                        };
+
+                        match counts.get_mut(&keys) {
+                            Some(count) if *count > 0 => {
+                                *count = if all { *count - 1 } else { 0 };
+
+                                Some(Ok(row))
+                            }
+                            _ => None,
+                        }
+                    })
+                    .collect()
+            }
+            (SetOperator::Except, all) => {
+                let mut counts = count_keys(&right)?;
+                let mut keys_set = HashSet::new();
+
+                left.into_iter()
+                    .map(|row| operand_keys(&row).map(|keys| (keys, row)))
+                    .filter_map(|item| {
+                        let (keys, row) = match item {
+                            Ok(item) => item,
+                            Err(error) => return Some(Err(error)),
+                        };
+
+                        let matched = match counts.get_mut(&keys) {
+                            Some(count) if *count > 0 => {
+                                if all {
+                                    *count -= 1;
+                                }
+
+                                true
+                            }
+                            _ => false,
+                        };
+
+                        (!matched && (all || keys_set.insert(keys))).then_some(Ok(row))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Rows of both operands take the column labels of the left operand.
+    fn relabel(&self, rows: Vec<Row>) -> Result<Vec<Row>> {
+        let columns = match &self.columns {
+            Some(columns) => columns,
+            None => return Ok(rows),
+        };
+
+        rows.into_iter()
+            .map(|row| match row {
+                Row::Vec { values, .. } if values.len() != columns.len() => {
+                    Err(SelectError::NumberOfSetOperationColumnsDifferent.into())
+                }
+                Row::Vec { values, .. } => Ok(Row::Vec {
+                    columns: Rc::clone(columns),
+                    values,
+                }),
+                Row::Map(_) => Ok(row),
+            })
+            .collect()
+    }
+}
+
+/// Types which the columns of both operands are read as, `None` for columns whose values
+/// are all NULL or whose types do not widen to a common type.
+fn widen_columns(left: &[Row], right: &[Row]) -> Vec<Option<DataType>> {
+    let mut data_types: Vec<Option<Option<DataType>>> = Vec::new();
+
+    for values in left.iter().chain(right).filter_map(|row| match row {
+        Row::Vec { values, .. } => Some(values),
+        Row::Map(_) => None,
+    }) {
+        if data_types.len() < values.len() {
+            data_types.resize(values.len(), None);
+        }
+
+        for (data_type, value) in data_types.iter_mut().zip(values) {
+            let value_type = match value.get_type() {
+                Some(value_type) => value_type,
+                None => continue,
+            };
+
+            *data_type = match data_type.take() {
+                None => Some(Some(value_type)),
+                Some(Some(data_type)) => Some(widen_data_type(&data_type, &value_type)),
+                Some(None) => Some(None),
+            };
+        }
+    }
+
+    data_types.into_iter().map(Option::flatten).collect()
+}
+
+fn cast_rows(rows: Vec<Row>, data_types: &[Option<DataType>]) -> Result<Vec<Row>> {
+    rows.into_iter()
+        .map(|row| match row {
+            Row::Vec { columns, values } => values
+                .into_iter()
+                .zip(data_types)
+                .map(|(value, data_type)| match data_type {
+                    Some(data_type) if value.get_type().as_ref() != Some(data_type) => {
+                        value.cast(data_type)
+                    }
+                    _ => Ok(value),
+                })
+                .collect::<Result<Vec<Value>>>()
+                .map(|values| Row::Vec { columns, values }),
+            Row::Map(_) => Ok(row),
+        })
+        .collect()
+}
+
+fn count_keys(rows: &[Row]) -> Result<HashMap<Vec<Key>, usize>> {
+    let mut counts = HashMap::new();
+
+    for row in rows {
+        *counts.entry(operand_keys(row)?).or_insert(0) += 1;
+    }
+
+    Ok(counts)
+}
+
+/// Keys of a row where numbers of different types are the same key when their values are
+/// equal, as operands may differ by numeric widening, e.g. `INT8` and `INTEGER` columns.
+pub fn operand_keys(row: &Row) -> Result<Vec<Key>> {
+    row_keys(row).map(|keys| keys.into_iter().map(widen_key).collect())
+}
+
+fn widen_key(key: Key) -> Key {
+    match key {
+        Key::I8(v) => Key::I128(v.into()),
+        Key::I16(v) => Key::I128(v.into()),
+        Key::I32(v) => Key::I128(v.into()),
+        Key::I64(v) => Key::I128(v.into()),
+        Key::U8(v) => Key::I128(v.into()),
+        Key::U16(v) => Key::I128(v.into()),
+        Key::U32(v) => Key::I128(v.into()),
+        Key::U64(v) => Key::I128(v.into()),
+        Key::U128(v) => i128::try_from(v).map_or(Key::U128(v), Key::I128),
+        Key::F32(v) => widen_key(Key::F64(OrderedFloat(v.0.into()))),
+        Key::F64(v) if v.fract() == 0.0 && v.0 >= i128::MIN as f64 && v.0 < i128::MAX as f64 => {
+            Key::I128(v.0 as i128)
+        }
+        Key::Decimal(v) if v.fract().is_zero() => v.to_i128().map_or(Key::Decimal(v), Key::I128),
+        key => key,
+    }
+}
@@ -1,5 +1,8 @@
 use {
-    super::{context::RowContext, evaluate::evaluate},
+    super::{
+        context::{ExecuteContext, RowContext},
+        evaluate::evaluate,
+    },
     crate::{
         ast::{Aggregate, AstLiteral, Expr, OrderByExpr, UnaryOperator},
         data::{Key, Row, Value},
@@ -23,8 +26,15 @@ pub enum SortError {
     Unreachable,
 }
 
+pub type SortItem<'a> = (
+    Option<Rc<HashMap<&'a Aggregate, Value>>>,
+    Rc<RowContext<'a>>,
+    Row,
+);
+
 pub struct Sort<'a, T: GStore> {
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     context: Option<Rc<RowContext<'a>>>,
     order_by: &'a [OrderByExpr],
 }
@@ -32,11 +42,13 @@ pub struct Sort<'a, T: GStore> {
 impl<'a, T: GStore> Sort<'a, T> {
     pub fn new(
         storage: &'a T,
+        ctx: &'a ExecuteContext,
         context: Option<Rc<RowContext<'a>>>,
         order_by: &'a [OrderByExpr],
     ) -> Self {
         Self {
             storage,
+            ctx,
             context,
             order_by,
         }
@@ -44,15 +56,9 @@ impl<'a, T: GStore> Sort<'a, T> {
 
     pub async fn apply(
         &self,
-        rows: impl Stream<
-                Item = Result<(
-                    Option<Rc<HashMap<&'a Aggregate, Value>>>,
-                    Rc<RowContext<'a>>,
-                    Row,
-                )>,
-            > + 'a,
+        rows: impl Stream<Item = Result<SortItem<'a>>> + 'a,
         table_alias: &'a str,
-    ) -> Result<impl Stream<Item = Result<Row>> + 'a> {
+    ) -> Result<impl Stream<Item = Result<SortItem<'a>>> + 'a> {
         #[derive(futures_enum::Stream)]
         enum Rows<I1, I2> {
             NonOrderBy(I1),
@@ -60,8 +66,6 @@ impl<'a, T: GStore> Sort<'a, T> {
         }
 
         if self.order_by.is_empty() {
-            let rows = rows.map_ok(|(.., row)| row);
-
             return Ok(Rows::NonOrderBy(Box::pin(rows)));
         }
 
@@ -132,7 +136,7 @@ impl<'a, T: GStore> Sort<'a, T> {
                                 match sort_type {
                                     SortType::Value(value) => value,
                                     SortType::Expr(expr) => {
-                                        evaluate(self.storage, context, aggregated, expr)
+                                        evaluate(self.storage, self.ctx, context, aggregated, expr)
                                             .await?
                                             .try_into()?
                                     }
@@ -147,15 +151,15 @@ impl<'a, T: GStore> Sort<'a, T> {
                     drop(label_context);
                     drop(filter_context);
 
-                    Ok((keys, row))
+                    Ok((keys, (aggregated, next, row)))
                 }
             })
-            .try_collect::<Vec<(Vec<(Key, Option<bool>)>, Row)>>()
+            .try_collect::<Vec<(Vec<(Key, Option<bool>)>, SortItem<'a>)>>()
             .await
             .map(Vector::from)?
             .sort_by(|(keys_a, ..), (keys_b, ..)| sort_by(keys_a, keys_b))
             .into_iter()
-            .map(|(.., row)| Ok(row));
+            .map(|(.., item)| Ok(item));
 
         Ok(Rows::OrderBy(stream::iter(rows)))
     }
@@ -0,0 +1,267 @@
+use {
+    super::{
+        context::{ExecuteContext, Scope},
+        evaluate::{evaluate, Evaluated},
+        execute::execute_inner,
+        fetch::FetchError,
+    },
+    crate::{
+        ast::{Assignment, ColumnDef, Expr, Function, TriggerAction, TriggerEvent, TriggerTiming},
+        data::{Trigger, Value},
+        plan::plan,
+        result::Result,
+        store::{DataRow, GStore, GStoreMut},
+    },
+    async_recursion::async_recursion,
+    serde::Serialize,
+    std::{collections::HashMap, fmt::Debug},
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum TriggerError {
+    #[error("{0} row is not available for the trigger event")]
+    RowNotAvailable(String),
+
+    #[error("trigger row column not found: {0}")]
+    ColumnNotFound(String),
+}
+
+/// Row-level triggers of a table fired by an event.
+///
+/// BEFORE triggers fire before the rows are validated, so the rows they change with `SET NEW`
+/// are validated and written, and AFTER triggers fire once all rows are written, both within
+/// the transaction of the statement.
+/// Rows changed by the `ON DELETE` and `ON UPDATE` actions of foreign keys do not fire triggers.
+pub struct Triggers {
+    column_defs: Option<Vec<ColumnDef>>,
+    before: Vec<Trigger>,
+    after: Vec<Trigger>,
+}
+
+impl Triggers {
+    /// Fetches the triggers and plans their statements once for all rows of the firing
+    /// statement, `NEW` and `OLD` are read from the scope of each row when the statements run
+    pub async fn fetch<T: GStore>(
+        storage: &T,
+        table_name: &str,
+        event: TriggerEvent,
+    ) -> Result<Self> {
+        let mut triggers = storage
+            .fetch_all_triggers()
+            .await?
+            .into_iter()
+            .filter(|trigger| trigger.table_name == table_name && trigger.events.contains(&event))
+            .collect::<Vec<_>>();
+        triggers.sort_by(|a, b| a.name.cmp(&b.name));
+
+        if triggers.is_empty() {
+            return Ok(Self {
+                column_defs: None,
+                before: Vec::new(),
+                after: Vec::new(),
+            });
+        }
+
+        let column_defs = storage
+            .fetch_schema(table_name)
+            .await?
+            .ok_or_else(|| FetchError::TableNotFound(table_name.to_owned()))?
+            .column_defs;
+
+        let mut planned = Vec::with_capacity(triggers.len());
+        for trigger in triggers {
+            let action = match trigger.action {
+                TriggerAction::Statement(statement) => {
+                    TriggerAction::Statement(Box::new(plan(storage, *statement).await?))
+                }
+                action => action,
+            };
+
+            planned.push(Trigger { action, ..trigger });
+        }
+
+        let (before, after) = planned
+            .into_iter()
+            .partition(|trigger| trigger.timing == TriggerTiming::Before);
+
+        Ok(Self {
+            column_defs,
+            before,
+            after,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+
+    /// Columns which the `SET NEW` actions of BEFORE triggers change
+    pub fn set_columns(&self) -> impl Iterator<Item = &String> {
+        self.before
+            .iter()
+            .flat_map(|trigger| match &trigger.action {
+                TriggerAction::SetNew(assignments) => assignments.as_slice(),
+                TriggerAction::Function { .. } | TriggerAction::Statement(_) => &[],
+            })
+            .map(|Assignment { id, .. }| id)
+    }
+
+    /// Fires BEFORE triggers for the row, returns `false` when one of them skips the row.
+    /// `SET NEW` actions change `new` in place.
+    pub async fn fire_before<T: GStore + GStoreMut>(
+        &self,
+        storage: &mut T,
+        ctx: &ExecuteContext,
+        new: Option<&mut DataRow>,
+        old: Option<&DataRow>,
+    ) -> Result<bool> {
+        if self.before.is_empty() {
+            return Ok(true);
+        }
+
+        let mut scope = self.trigger_scope(new.as_deref(), old);
+
+        for trigger in &self.before {
+            if !self.fire(storage, ctx, &mut scope, trigger).await? {
+                return Ok(false);
+            }
+        }
+
+        if let (Some(row), Some(mut values)) = (new, scope.new) {
+            *row = match row {
+                DataRow::Vec(_) => DataRow::Vec(
+                    self.columns()
+                        .map(|column| values.remove(column).unwrap_or(Value::Null))
+                        .collect(),
+                ),
+                DataRow::Map(_) => DataRow::Map(values),
+            };
+        }
+
+        Ok(true)
+    }
+
+    pub async fn fire_after<T: GStore + GStoreMut>(
+        &self,
+        storage: &mut T,
+        ctx: &ExecuteContext,
+        new: Option<&DataRow>,
+        old: Option<&DataRow>,
+    ) -> Result<()> {
+        let mut scope = self.trigger_scope(new, old);
+
+        for trigger in &self.after {
+            self.fire(storage, ctx, &mut scope, trigger).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs the trigger action, returns `false` when the trigger function returns FALSE.
+    ///
+    /// Triggers fired by the statements of trigger actions count towards the nesting depth,
+    /// so triggers which keep firing each other fail instead of recursing without end.
+    #[async_recursion(?Send)]
+    async fn fire<T: GStore + GStoreMut>(
+        &self,
+        storage: &mut T,
+        ctx: &ExecuteContext,
+        scope: &mut TriggerScope,
+        trigger: &Trigger,
+    ) -> Result<bool> {
+        let TriggerScope { new, old } = scope;
+        let ctx = &ctx
+            .nest(&format!("trigger {}", trigger.name))?
+            .with_scope(Scope::Trigger {
+                new: new.clone(),
+                old: old.clone(),
+            });
+
+        match &trigger.action {
+            TriggerAction::Function { name, args } => {
+                let expr = Expr::Function(Box::new(Function::Custom {
+                    name: name.to_owned(),
+                    exprs: args.clone(),
+                }));
+                let value = Value::try_from(evaluate(&*storage, ctx, None, None, &expr).await?)?;
+
+                Ok(value != Value::Bool(false))
+            }
+            TriggerAction::Statement(statement) => {
+                execute_inner(storage, ctx, statement).await?;
+
+                Ok(true)
+            }
+            TriggerAction::SetNew(assignments) => {
+                let mut values = Vec::with_capacity(assignments.len());
+                for Assignment { id, value } in assignments {
+                    let evaluated = evaluate(&*storage, ctx, None, None, value).await?;
+                    let value = match &self.column_defs {
+                        Some(column_defs) => {
+                            let ColumnDef {
+                                data_type,
+                                nullable,
+                                ..
+                            } = column_defs
+                                .iter()
+                                .find(|column_def| id == &column_def.name)
+                                .ok_or_else(|| TriggerError::ColumnNotFound(format!("NEW.{id}")))?;
+
+                            let value = match evaluated {
+                                Evaluated::Literal(v) => Value::try_from_literal(data_type, &v)?,
+                                Evaluated::Value(v) => {
+                                    v.validate_type(data_type)?;
+                                    v
+                                }
+                                Evaluated::StrSlice {
+                                    source: s,
+                                    range: r,
+                                } => Value::Str(s[r].to_owned()),
+                            };
+
+                            value.validate_null(*nullable)?;
+                            value
+                        }
+                        None => evaluated.try_into()?,
+                    };
+
+                    values.push((id.to_owned(), value));
+                }
+
+                new.as_mut()
+                    .ok_or_else(|| TriggerError::RowNotAvailable("NEW".to_owned()))?
+                    .extend(values);
+
+                Ok(true)
+            }
+        }
+    }
+
+    fn columns(&self) -> impl Iterator<Item = &String> {
+        self.column_defs
+            .iter()
+            .flatten()
+            .map(|ColumnDef { name, .. }| name)
+    }
+
+    fn trigger_scope(&self, new: Option<&DataRow>, old: Option<&DataRow>) -> TriggerScope {
+        let new = new.map(|row| self.to_values(row));
+        let old = old.map(|row| self.to_values(row));
+
+        TriggerScope { new, old }
+    }
+
+    fn to_values(&self, data_row: &DataRow) -> HashMap<String, Value> {
+        match data_row {
+            DataRow::Vec(values) => self.columns().cloned().zip(values.clone()).collect(),
+            DataRow::Map(values) => values.clone(),
+        }
+    }
+}
+
+/// `NEW` and `OLD` rows of a trigger event
+struct TriggerScope {
+    new: Option<HashMap<String, Value>>,
+    old: Option<HashMap<String, Value>>,
+}
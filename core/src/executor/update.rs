@@ -1,10 +1,10 @@
 use {
     super::{
-        context::RowContext,
+        context::{ExecuteContext, RowContext},
         evaluate::{evaluate, Evaluated},
     },
     crate::{
-        ast::{Assignment, ColumnDef, ColumnUniqueOption},
+        ast::{Assignment, ColumnDef, ColumnUniqueOption, UniqueKey},
         data::{Row, Value},
         result::{Error, Result},
         store::GStore,
@@ -30,6 +30,7 @@ pub enum UpdateError {
 
 pub struct Update<'a, T: GStore> {
     storage: &'a T,
+    ctx: &'a ExecuteContext,
     table_name: &'a str,
     fields: &'a [Assignment],
     column_defs: Option<&'a [ColumnDef]>,
@@ -38,9 +39,11 @@ pub struct Update<'a, T: GStore> {
 impl<'a, T: GStore> Update<'a, T> {
     pub fn new(
         storage: &'a T,
+        ctx: &'a ExecuteContext,
         table_name: &'a str,
         fields: &'a [Assignment],
         column_defs: Option<&'a [ColumnDef]>,
+        unique_keys: &[UniqueKey],
     ) -> Result<Self> {
         if let Some(column_defs) = column_defs {
             for assignment in fields.iter() {
@@ -50,7 +53,13 @@ impl<'a, T: GStore> Update<'a, T> {
                     return Err(UpdateError::ColumnNotFound(id.to_owned()).into());
                 } else if column_defs.iter().any(|ColumnDef { name, unique, .. }| {
                     name == id && matches!(unique, Some(ColumnUniqueOption { is_primary: true }))
-                }) {
+                }) || unique_keys.iter().any(
+                    |UniqueKey {
+                         columns,
+                         is_primary,
+                         ..
+                     }| { *is_primary && columns.contains(id) },
+                ) {
                     return Err(UpdateError::UpdateOnPrimaryKeyNotSupported(id.to_owned()).into());
                 }
             }
@@ -58,14 +67,15 @@ impl<'a, T: GStore> Update<'a, T> {
 
         Ok(Self {
             storage,
+            ctx,
             table_name,
             fields,
             column_defs,
         })
     }
 
-    pub async fn apply(&self, row: Row) -> Result<Row> {
-        let context = RowContext::new(self.table_name, Cow::Borrowed(&row), None);
+    pub async fn apply(&self, row: Row, next: Option<Rc<RowContext<'_>>>) -> Result<Row> {
+        let context = RowContext::new(self.table_name, Cow::Borrowed(&row), next);
         let context = Some(Rc::new(context));
 
         let assignments = stream::iter(self.fields.iter())
@@ -77,7 +87,8 @@ impl<'a, T: GStore> Update<'a, T> {
                 let context = context.as_ref().map(Rc::clone);
 
                 async move {
-                    let evaluated = evaluate(self.storage, context, None, value_expr).await?;
+                    let evaluated =
+                        evaluate(self.storage, self.ctx, context, None, value_expr).await?;
                     let value = match self.column_defs {
                         Some(column_defs) => {
                             let ColumnDef {
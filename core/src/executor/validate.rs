@@ -1,13 +1,17 @@
 use {
+    super::{alter::find_column, context::RowContext, evaluate::evaluate_stateless},
     crate::{
-        ast::{ColumnDef, ColumnUniqueOption},
-        data::{Key, Value},
-        result::Result,
-        store::{DataRow, Store},
+        ast::{
+            Check, ColumnDef, ColumnUniqueOption, Expr, ForeignKey, IndexOperator, OrderByExpr,
+            UniqueKey,
+        },
+        data::{Key, Row, Schema, SchemaIndex, Value},
+        result::{Error, Result},
+        store::{DataRow, GStore, Store},
     },
     im_rc::HashSet,
     serde::Serialize,
-    std::fmt::Debug,
+    std::{borrow::Cow, fmt::Debug, rc::Rc},
     thiserror::Error as ThisError,
     utils::Vector,
 };
@@ -25,8 +29,33 @@ pub enum ValidateError {
 
     #[error("duplicate entry '{0:?}' for primary_key field")]
     DuplicateEntryOnPrimaryKeyField(Key),
+
+    #[error("duplicate entry '{key:?}' for unique key '{unique_key}'")]
+    DuplicateEntryOnUniqueKey { unique_key: String, key: Key },
+
+    #[error("duplicate entry '{key:?}' for unique index '{index_name}'")]
+    DuplicateEntryOnUniqueIndex { index_name: String, key: Key },
+
+    #[error("referenced table not found: {0}")]
+    ReferencedTableNotFound(String),
+
+    #[error("referenced column not found: {0}")]
+    ReferencedColumnNotFound(String),
+
+    #[error("foreign key '{foreign_key}' violated: '{}' not found in referenced table", String::from(.value))]
+    ReferencedValueNotFound { foreign_key: String, value: Value },
+
+    #[error("foreign key '{foreign_key}' violated: row is still referenced from table '{referencing_table_name}'")]
+    ReferencingRowExists {
+        foreign_key: String,
+        referencing_table_name: String,
+    },
+
+    #[error("check constraint '{0}' violated")]
+    CheckConstraintViolated(String),
 }
 
+#[derive(Clone)]
 pub enum ColumnValidation<'column_def> {
     /// `INSERT`
     All(&'column_def [ColumnDef]),
@@ -209,3 +238,479 @@ fn fetch_specified_unique_columns(
         })
         .collect()
 }
+
+/// Checks the combined uniqueness of `unique_keys` among the validated rows and the stored rows,
+/// where a key having NULL on any of its columns is never a duplicate.
+pub async fn validate_unique_keys<T: Store>(
+    storage: &T,
+    table_name: &str,
+    unique_keys: &[UniqueKey],
+    column_validation: ColumnValidation<'_>,
+    row_iter: impl Iterator<Item = &[Value]> + Clone,
+) -> Result<()> {
+    let (column_defs, specified_columns) = match &column_validation {
+        ColumnValidation::All(column_defs) => (*column_defs, None),
+        ColumnValidation::SpecifiedColumns(column_defs, specified_columns) => {
+            (*column_defs, Some(specified_columns))
+        }
+    };
+
+    for unique_key in unique_keys {
+        let UniqueKey {
+            name,
+            columns,
+            is_primary,
+        } = unique_key;
+
+        if specified_columns.is_some_and(|specified| !columns.iter().any(|c| specified.contains(c)))
+        {
+            continue;
+        }
+
+        let indexes = match columns
+            .iter()
+            .map(|column| find_column_index(column_defs, column))
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(indexes) => indexes,
+            None => continue,
+        };
+        let duplicate = |key: Key| -> Error {
+            match is_primary {
+                true => ValidateError::DuplicateEntryOnPrimaryKeyField(key),
+                false => ValidateError::DuplicateEntryOnUniqueKey {
+                    unique_key: name.to_owned(),
+                    key,
+                },
+            }
+            .into()
+        };
+
+        let mut keys = HashSet::new();
+        for values in row_iter.clone() {
+            let key = match Key::from_row(values, &indexes)? {
+                Some(Key::Composite(keys)) if keys.contains(&Key::None) => continue,
+                Some(key) => key,
+                None => continue,
+            };
+
+            if keys.contains(&key) {
+                return Err(duplicate(key));
+            }
+
+            keys.insert(key);
+        }
+
+        if keys.is_empty() {
+            continue;
+        }
+
+        if *is_primary && specified_columns.is_none() {
+            for key in keys {
+                if storage.fetch_data(table_name, &key).await?.is_some() {
+                    return Err(duplicate(key));
+                }
+            }
+
+            continue;
+        }
+
+        for item in storage.scan_data(table_name).await? {
+            let values = match item? {
+                (_, DataRow::Vec(values)) => values,
+                (_, DataRow::Map(_)) => {
+                    return Err(ValidateError::ConflictOnUnexpectedSchemalessRowFound.into());
+                }
+            };
+
+            if let Some(key) = Key::from_row(&values, &indexes)? {
+                if keys.contains(&key) {
+                    return Err(duplicate(key));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the uniqueness of `UNIQUE` indexes among the validated rows and the stored rows,
+/// stored rows are looked up through the index itself.
+pub async fn validate_unique_indexes<T: GStore>(
+    storage: &T,
+    table_name: &str,
+    indexes: &[SchemaIndex],
+    column_validation: ColumnValidation<'_>,
+    row_iter: impl Iterator<Item = &[Value]> + Clone,
+) -> Result<()> {
+    let (column_defs, specified_columns) = match &column_validation {
+        ColumnValidation::All(column_defs) => (*column_defs, None),
+        ColumnValidation::SpecifiedColumns(column_defs, specified_columns) => {
+            (*column_defs, Some(specified_columns))
+        }
+    };
+    let columns = column_defs
+        .iter()
+        .map(|ColumnDef { name, .. }| name.to_owned())
+        .collect::<Rc<[String]>>();
+
+    for index in indexes.iter().filter(|index| index.unique) {
+        if specified_columns.is_some_and(|specified| {
+            !index
+                .exprs()
+                .chain(&index.predicate)
+                .any(|expr| specified.iter().any(|column| find_column(expr, column)))
+        }) {
+            continue;
+        }
+
+        let duplicate = |key: Key| -> Error {
+            ValidateError::DuplicateEntryOnUniqueIndex {
+                index_name: index.name.to_owned(),
+                key,
+            }
+            .into()
+        };
+
+        let mut keys = HashSet::new();
+        let mut validated = Vec::new();
+        for values in row_iter.clone() {
+            if !evaluate_index_predicate(table_name, &columns, index.predicate.as_ref(), values)
+                .await?
+            {
+                continue;
+            }
+
+            let values =
+                evaluate_index_values(table_name, &columns, &index.columns, values).await?;
+            let key = match index_key(&values)? {
+                Some(key) => key,
+                None => continue,
+            };
+
+            if keys.contains(&key) {
+                return Err(duplicate(key));
+            }
+
+            keys.insert(key.clone());
+            validated.push((key, values));
+        }
+
+        for (key, mut values) in validated {
+            let value = values.pop().unwrap_or(Value::Null);
+            let stored = storage
+                .scan_indexed_data(
+                    table_name,
+                    &index.name,
+                    None,
+                    &values,
+                    Some((&IndexOperator::Eq, value)),
+                )
+                .await?
+                .next()
+                .transpose()?;
+
+            if stored.is_some() {
+                return Err(duplicate(key));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Evaluates the key columns of an index on a row of `columns`
+pub async fn evaluate_index_values(
+    table_name: &str,
+    columns: &Rc<[String]>,
+    index_columns: &[OrderByExpr],
+    values: &[Value],
+) -> Result<Vec<Value>> {
+    let row = Row::Vec {
+        columns: Rc::clone(columns),
+        values: values.to_vec(),
+    };
+
+    let mut index_values = Vec::with_capacity(index_columns.len());
+    for OrderByExpr { expr, .. } in index_columns {
+        let context = RowContext::new(table_name, Cow::Borrowed(&row), None);
+        let value = evaluate_stateless(Some(context), expr)
+            .await
+            .and_then(Value::try_from)?;
+
+        index_values.push(value);
+    }
+
+    Ok(index_values)
+}
+
+/// Whether a row of `columns` is covered by an index with the `predicate`, NULL excludes it
+pub async fn evaluate_index_predicate(
+    table_name: &str,
+    columns: &Rc<[String]>,
+    predicate: Option<&Expr>,
+    values: &[Value],
+) -> Result<bool> {
+    let predicate = match predicate {
+        Some(predicate) => predicate,
+        None => return Ok(true),
+    };
+
+    let row = Row::Vec {
+        columns: Rc::clone(columns),
+        values: values.to_vec(),
+    };
+    let context = RowContext::new(table_name, Cow::Borrowed(&row), None);
+    let evaluated = evaluate_stateless(Some(context), predicate).await?;
+
+    Ok(!evaluated.is_null() && bool::try_from(evaluated)?)
+}
+
+/// Key of evaluated index values, `None` when any of them is NULL
+pub fn index_key(values: &[Value]) -> Result<Option<Key>> {
+    if values.iter().any(Value::is_null) {
+        return Ok(None);
+    }
+
+    let keys = values
+        .iter()
+        .map(Key::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(match <[Key; 1]>::try_from(keys) {
+        Ok([key]) => key,
+        Err(keys) => Key::Composite(keys),
+    }))
+}
+
+/// Checks that every non-null referencing value of `foreign_keys` exists in the referenced
+/// table, either stored or among the validated rows themselves for self-referencing keys.
+pub async fn validate_foreign_keys<T: Store>(
+    storage: &T,
+    table_name: &str,
+    foreign_keys: &[ForeignKey],
+    column_validation: ColumnValidation<'_>,
+    row_iter: impl Iterator<Item = &[Value]> + Clone,
+) -> Result<()> {
+    let (column_defs, specified_columns) = match &column_validation {
+        ColumnValidation::All(column_defs) => (*column_defs, None),
+        ColumnValidation::SpecifiedColumns(column_defs, specified_columns) => {
+            (*column_defs, Some(specified_columns))
+        }
+    };
+
+    for foreign_key in foreign_keys {
+        let ForeignKey {
+            name,
+            referencing_column_name,
+            referenced_table_name,
+            referenced_column_name,
+            ..
+        } = foreign_key;
+
+        if specified_columns.is_some_and(|columns| !columns.contains(referencing_column_name)) {
+            continue;
+        }
+
+        let referencing_index = match find_column_index(column_defs, referencing_column_name) {
+            Some(index) => index,
+            None => continue,
+        };
+        let referencing_values = row_iter
+            .clone()
+            .filter_map(|values| values.get(referencing_index))
+            .filter(|value| !value.is_null())
+            .collect::<Vec<_>>();
+
+        if referencing_values.is_empty() {
+            continue;
+        }
+
+        let Schema {
+            column_defs: referenced_column_defs,
+            ..
+        } = storage
+            .fetch_schema(referenced_table_name)
+            .await?
+            .ok_or_else(|| {
+                ValidateError::ReferencedTableNotFound(referenced_table_name.to_owned())
+            })?;
+        let referenced_column_defs = referenced_column_defs.unwrap_or_default();
+        let referenced_index = find_column_index(&referenced_column_defs, referenced_column_name)
+            .ok_or_else(|| {
+            ValidateError::ReferencedColumnNotFound(referenced_column_name.to_owned())
+        })?;
+        let is_primary = matches!(
+            referenced_column_defs[referenced_index].unique,
+            Some(ColumnUniqueOption { is_primary: true })
+        );
+
+        let validated_keys = match referenced_table_name == table_name {
+            true => row_iter
+                .clone()
+                .filter_map(|values| values.get(referenced_index))
+                .map(Key::try_from)
+                .collect::<Result<HashSet<Key>>>()?,
+            false => HashSet::new(),
+        };
+        let stored_keys = match is_primary {
+            true => HashSet::new(),
+            false => storage
+                .scan_data(referenced_table_name)
+                .await?
+                .filter_map(|result| match result {
+                    Ok((_, DataRow::Vec(values))) => {
+                        values.get(referenced_index).map(Key::try_from)
+                    }
+                    Ok((_, DataRow::Map(_))) => None,
+                    Err(error) => Some(Err(error)),
+                })
+                .collect::<Result<HashSet<Key>>>()?,
+        };
+
+        for value in referencing_values {
+            let key = Key::try_from(value)?;
+
+            if validated_keys.contains(&key) || stored_keys.contains(&key) {
+                continue;
+            }
+
+            if is_primary
+                && storage
+                    .fetch_data(referenced_table_name, &key)
+                    .await?
+                    .is_some()
+            {
+                continue;
+            }
+
+            return Err(ValidateError::ReferencedValueNotFound {
+                foreign_key: name.to_owned(),
+                value: value.clone(),
+            }
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates the rows to be written against every constraint of `schema`: CHECKs, unique columns,
+/// unique keys, unique indexes and foreign keys.
+pub async fn validate_rows<'a, T: GStore>(
+    storage: &T,
+    schema: &Schema,
+    column_validation: ColumnValidation<'_>,
+    row_iter: impl Iterator<Item = &'a [Value]> + Clone,
+) -> Result<()> {
+    let Schema {
+        table_name,
+        indexes,
+        foreign_keys,
+        checks,
+        unique_keys,
+        ..
+    } = schema;
+    let column_defs = match &column_validation {
+        ColumnValidation::All(column_defs) => *column_defs,
+        ColumnValidation::SpecifiedColumns(column_defs, _) => *column_defs,
+    };
+
+    validate_checks(table_name, column_defs, checks, row_iter.clone()).await?;
+    validate_unique(
+        storage,
+        table_name,
+        column_validation.clone(),
+        row_iter.clone(),
+    )
+    .await?;
+    validate_unique_keys(
+        storage,
+        table_name,
+        unique_keys,
+        column_validation.clone(),
+        row_iter.clone(),
+    )
+    .await?;
+    validate_unique_indexes(
+        storage,
+        table_name,
+        indexes,
+        column_validation.clone(),
+        row_iter.clone(),
+    )
+    .await?;
+    validate_foreign_keys(
+        storage,
+        table_name,
+        foreign_keys,
+        column_validation,
+        row_iter,
+    )
+    .await
+}
+
+/// Evaluates `checks` against each row, where only `FALSE` is a violation and `NULL` passes.
+pub async fn validate_checks<'a>(
+    table_name: &str,
+    column_defs: &[ColumnDef],
+    checks: &[Check],
+    row_iter: impl Iterator<Item = &'a [Value]>,
+) -> Result<()> {
+    if checks.is_empty() {
+        return Ok(());
+    }
+
+    let columns = column_defs
+        .iter()
+        .map(|ColumnDef { name, .. }| name.to_owned())
+        .collect::<Rc<[String]>>();
+
+    for values in row_iter {
+        let row = Row::Vec {
+            columns: Rc::clone(&columns),
+            values: values.to_vec(),
+        };
+
+        for Check { name, expr } in checks {
+            let context = RowContext::new(table_name, Cow::Borrowed(&row), None);
+            let evaluated = evaluate_stateless(Some(context), expr).await?;
+
+            if !evaluated.is_null() && !bool::try_from(evaluated)? {
+                return Err(ValidateError::CheckConstraintViolated(name.to_owned()).into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Evaluates each CHECK against a row of NULLs when the table is created, so a CHECK
+/// which refers to an unknown column fails then rather than at the first write.
+pub async fn validate_check_exprs(
+    table_name: &str,
+    column_defs: &[ColumnDef],
+    checks: &[Check],
+) -> Result<()> {
+    let row = Row::Vec {
+        columns: column_defs
+            .iter()
+            .map(|ColumnDef { name, .. }| name.to_owned())
+            .collect(),
+        values: vec![Value::Null; column_defs.len()],
+    };
+
+    for Check { expr, .. } in checks {
+        let context = RowContext::new(table_name, Cow::Borrowed(&row), None);
+
+        evaluate_stateless(Some(context), expr).await?;
+    }
+
+    Ok(())
+}
+
+pub fn find_column_index(column_defs: &[ColumnDef], column_name: &str) -> Option<usize> {
+    column_defs
+        .iter()
+        .position(|ColumnDef { name, .. }| name == column_name)
+}
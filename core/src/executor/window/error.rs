@@ -0,0 +1,19 @@
+use {serde::Serialize, std::fmt::Debug, thiserror::Error};
+
+#[derive(Error, Serialize, Debug, PartialEq, Eq)]
+pub enum WindowError {
+    #[error("window frame offset must not be negative: {0}")]
+    NegativeFrameOffset(i64),
+
+    #[error("window frame offset must not be null")]
+    NullFrameOffset,
+
+    #[error("RANGE with offset requires exactly one ORDER BY expression")]
+    RangeOffsetRequiresSingleOrderBy,
+
+    #[error("window frame start cannot be UNBOUNDED FOLLOWING")]
+    UnboundedFollowingFrameStart,
+
+    #[error("window frame end cannot be UNBOUNDED PRECEDING")]
+    UnboundedPrecedingFrameEnd,
+}
@@ -0,0 +1,522 @@
+mod error;
+
+use {
+    super::{
+        aggregate::AggrValue,
+        context::{AggregateContext, ExecuteContext, RowContext},
+        evaluate::evaluate,
+        sort::sort_by,
+    },
+    crate::{
+        ast::{
+            Aggregate, Expr, OrderByExpr, SelectItem, Window as AstWindow, WindowFrame,
+            WindowFrameBound, WindowFrameUnits, WindowFunction, WindowSpec,
+        },
+        data::{Key, Value},
+        result::Result,
+        store::GStore,
+    },
+    futures::stream::{self, Stream, TryStreamExt},
+    std::{cmp::Ordering, collections::HashMap, rc::Rc},
+};
+
+pub use error::WindowError;
+
+type Context<'a> = (
+    Rc<RowContext<'a>>,
+    Option<Rc<im_rc::HashMap<&'a Aggregate, Value>>>,
+);
+
+/// Range of sorted positions sharing the same ORDER BY keys within a partition
+#[derive(Clone, Copy)]
+struct PeerGroup {
+    start: usize,
+    end: usize,
+    dense_rank: usize,
+}
+
+struct Partition<'b> {
+    peers: Vec<PeerGroup>,
+    /// Function argument evaluated for each sorted position
+    arguments: Vec<Value>,
+    /// Values of the single ORDER BY expression, used by RANGE offsets
+    range_values: Option<(Vec<&'b Value>, bool)>,
+}
+
+pub struct Window<'a, T: GStore> {
+    storage: &'a T,
+    ctx: &'a ExecuteContext,
+    fields: &'a [SelectItem],
+    order_by: &'a [OrderByExpr],
+    filter_context: Option<Rc<RowContext<'a>>>,
+}
+
+#[derive(futures_enum::Stream)]
+enum S<T1, T2> {
+    NonWindow(T1),
+    Window(T2),
+}
+
+impl<'a, T: GStore> Window<'a, T> {
+    pub fn new(
+        storage: &'a T,
+        ctx: &'a ExecuteContext,
+        fields: &'a [SelectItem],
+        order_by: &'a [OrderByExpr],
+        filter_context: Option<Rc<RowContext<'a>>>,
+    ) -> Self {
+        Self {
+            storage,
+            ctx,
+            fields,
+            order_by,
+            filter_context,
+        }
+    }
+
+    pub async fn apply(
+        &self,
+        rows: impl Stream<Item = Result<AggregateContext<'a>>>,
+    ) -> Result<impl Stream<Item = Result<AggregateContext<'a>>>> {
+        let windows = self.windows();
+
+        if windows.is_empty() {
+            return Ok(S::NonWindow(rows));
+        }
+
+        let rows = rows.try_collect::<Vec<_>>().await?;
+        let contexts = rows
+            .iter()
+            .map(|AggregateContext { aggregated, next }| {
+                let filter_context = match &self.filter_context {
+                    Some(filter_context) => Rc::new(RowContext::concat(
+                        Rc::clone(next),
+                        Rc::clone(filter_context),
+                    )),
+                    None => Rc::clone(next),
+                };
+
+                (filter_context, aggregated.clone().map(Rc::new))
+            })
+            .collect::<Vec<_>>();
+
+        let mut values_list = vec![HashMap::new(); rows.len()];
+
+        for window in windows {
+            let evaluated = self.evaluate_window(window, &contexts).await?;
+
+            for (values, value) in values_list.iter_mut().zip(evaluated) {
+                values.insert(window, value);
+            }
+        }
+
+        drop(contexts);
+
+        let rows = rows.into_iter().zip(values_list).map(
+            |(AggregateContext { aggregated, next }, values)| {
+                let next = Rc::new(RowContext::Window { values, next });
+
+                Ok(AggregateContext { aggregated, next })
+            },
+        );
+
+        Ok(S::Window(stream::iter(rows)))
+    }
+
+    fn windows(&self) -> Vec<&'a AstWindow> {
+        let exprs = self
+            .fields
+            .iter()
+            .filter_map(|field| match field {
+                SelectItem::Expr { expr, .. } => Some(expr),
+                _ => None,
+            })
+            .chain(self.order_by.iter().map(|OrderByExpr { expr, .. }| expr));
+
+        let mut windows = Vec::new();
+
+        for window in exprs.flat_map(find_windows) {
+            if !windows.contains(&window) {
+                windows.push(window);
+            }
+        }
+
+        windows
+    }
+
+    async fn evaluate_window(
+        &self,
+        window: &'a AstWindow,
+        contexts: &[Context<'a>],
+    ) -> Result<Vec<Value>> {
+        let AstWindow {
+            function,
+            spec:
+                WindowSpec {
+                    partition_by,
+                    order_by,
+                    frame,
+                },
+        } = window;
+
+        let mut partitions: Vec<Vec<usize>> = Vec::new();
+        let mut partition_indexes: HashMap<Vec<Key>, usize> = HashMap::new();
+        let mut order_keys = Vec::with_capacity(contexts.len());
+        let mut order_values = Vec::with_capacity(contexts.len());
+
+        for (index, context) in contexts.iter().enumerate() {
+            let mut partition_key = Vec::with_capacity(partition_by.len());
+            for expr in partition_by {
+                let value = self.evaluate_value(context, expr).await?;
+
+                partition_key.push(Key::try_from(value)?);
+            }
+
+            let mut keys = Vec::with_capacity(order_by.len());
+            let mut values = Vec::with_capacity(order_by.len());
+            for OrderByExpr { expr, asc } in order_by {
+                let value = self.evaluate_value(context, expr).await?;
+
+                keys.push((Key::try_from(&value)?, *asc));
+                values.push(value);
+            }
+
+            match partition_indexes.get(&partition_key) {
+                Some(&i) => partitions[i].push(index),
+                None => {
+                    partition_indexes.insert(partition_key, partitions.len());
+                    partitions.push(vec![index]);
+                }
+            }
+
+            order_keys.push(keys);
+            order_values.push(values);
+        }
+
+        let argument = match function {
+            WindowFunction::RowNumber | WindowFunction::Rank | WindowFunction::DenseRank => None,
+            WindowFunction::Lag { expr, .. }
+            | WindowFunction::Lead { expr, .. }
+            | WindowFunction::FirstValue(expr)
+            | WindowFunction::LastValue(expr) => Some(expr),
+            WindowFunction::Aggregate(aggregate) => aggregate.as_expr(),
+        };
+
+        let mut results = vec![Value::Null; contexts.len()];
+
+        for mut rows in partitions {
+            rows.sort_by(|a, b| sort_by(&order_keys[*a], &order_keys[*b]));
+
+            let mut arguments = Vec::with_capacity(rows.len());
+            for index in rows.iter() {
+                let value = match argument {
+                    Some(expr) => self.evaluate_value(&contexts[*index], expr).await?,
+                    None => Value::Null,
+                };
+
+                arguments.push(value);
+            }
+
+            let range_values = match order_by.as_slice() {
+                [OrderByExpr { asc, .. }] => {
+                    let values = rows
+                        .iter()
+                        .map(|index| &order_values[*index][0])
+                        .collect::<Vec<_>>();
+
+                    Some((values, asc.unwrap_or(true)))
+                }
+                _ => None,
+            };
+            let partition = Partition {
+                peers: peer_groups(&rows, &order_keys),
+                arguments,
+                range_values,
+            };
+
+            for (position, index) in rows.iter().enumerate() {
+                let context = &contexts[*index];
+                let peer = partition.peers[position];
+
+                let value = match function {
+                    WindowFunction::RowNumber => Value::I64(position as i64 + 1),
+                    WindowFunction::Rank => Value::I64(peer.start as i64 + 1),
+                    WindowFunction::DenseRank => Value::I64(peer.dense_rank as i64 + 1),
+                    WindowFunction::Lag {
+                        offset, default, ..
+                    } => {
+                        let offset = self.shift_offset(context, offset.as_ref()).await?;
+                        let target = position as i64 - offset;
+
+                        self.shift(context, &partition.arguments, target, default)
+                            .await?
+                    }
+                    WindowFunction::Lead {
+                        offset, default, ..
+                    } => {
+                        let offset = self.shift_offset(context, offset.as_ref()).await?;
+                        let target = position as i64 + offset;
+
+                        self.shift(context, &partition.arguments, target, default)
+                            .await?
+                    }
+                    WindowFunction::FirstValue(_) => self
+                        .frame_values(context, frame, &partition, position)
+                        .await?
+                        .first()
+                        .cloned()
+                        .unwrap_or(Value::Null),
+                    WindowFunction::LastValue(_) => self
+                        .frame_values(context, frame, &partition, position)
+                        .await?
+                        .last()
+                        .cloned()
+                        .unwrap_or(Value::Null),
+                    WindowFunction::Aggregate(aggregate) => {
+                        let values = self
+                            .frame_values(context, frame, &partition, position)
+                            .await?;
+
+                        aggregate_frame(aggregate, values).await?
+                    }
+                };
+
+                results[*index] = value;
+            }
+        }
+
+        Ok(results)
+    }
+
+    async fn shift_offset(&self, context: &Context<'a>, offset: Option<&'a Expr>) -> Result<i64> {
+        match offset {
+            Some(expr) => i64::try_from(&self.evaluate_value(context, expr).await?),
+            None => Ok(1),
+        }
+    }
+
+    async fn shift(
+        &self,
+        context: &Context<'a>,
+        arguments: &[Value],
+        target: i64,
+        default: &'a Option<Expr>,
+    ) -> Result<Value> {
+        let value = usize::try_from(target)
+            .ok()
+            .and_then(|target| arguments.get(target));
+
+        match (value, default) {
+            (Some(value), _) => Ok(value.clone()),
+            (None, Some(default)) => self.evaluate_value(context, default).await,
+            (None, None) => Ok(Value::Null),
+        }
+    }
+
+    async fn frame_values<'b>(
+        &self,
+        context: &Context<'a>,
+        frame: &'a Option<WindowFrame>,
+        partition: &'b Partition<'_>,
+        position: usize,
+    ) -> Result<&'b [Value]> {
+        let Partition {
+            peers, arguments, ..
+        } = partition;
+
+        let frame = match frame {
+            Some(frame) => frame,
+            None => return Ok(&arguments[..=peers[position].end]),
+        };
+
+        let start = self
+            .frame_position(context, frame, &frame.start, true, partition, position)
+            .await?
+            .max(0);
+        let end = self
+            .frame_position(context, frame, &frame.end, false, partition, position)
+            .await?
+            .min(arguments.len() as i64 - 1);
+
+        Ok(match start <= end {
+            true => &arguments[start as usize..=end as usize],
+            false => &[],
+        })
+    }
+
+    async fn frame_position(
+        &self,
+        context: &Context<'a>,
+        frame: &'a WindowFrame,
+        bound: &'a WindowFrameBound,
+        is_start: bool,
+        partition: &Partition<'_>,
+        position: usize,
+    ) -> Result<i64> {
+        let peer = partition.peers[position];
+        let peer_bound = match is_start {
+            true => peer.start as i64,
+            false => peer.end as i64,
+        };
+
+        let (offset, following) = match (bound, is_start) {
+            (WindowFrameBound::CurrentRow, _) => {
+                return Ok(match frame.units {
+                    WindowFrameUnits::Rows => position as i64,
+                    WindowFrameUnits::Range => peer_bound,
+                });
+            }
+            (WindowFrameBound::Preceding(None), true) => return Ok(0),
+            (WindowFrameBound::Following(None), false) => return Ok(i64::MAX),
+            (WindowFrameBound::Preceding(None), false) => {
+                return Err(WindowError::UnboundedPrecedingFrameEnd.into());
+            }
+            (WindowFrameBound::Following(None), true) => {
+                return Err(WindowError::UnboundedFollowingFrameStart.into());
+            }
+            (WindowFrameBound::Preceding(Some(expr)), _) => (expr, false),
+            (WindowFrameBound::Following(Some(expr)), _) => (expr, true),
+        };
+
+        let offset = self.evaluate_value(context, offset).await?;
+
+        match frame.units {
+            WindowFrameUnits::Rows => {
+                let offset = i64::try_from(&offset)?;
+                if offset < 0 {
+                    return Err(WindowError::NegativeFrameOffset(offset).into());
+                }
+
+                Ok(match following {
+                    true => position as i64 + offset,
+                    false => position as i64 - offset,
+                })
+            }
+            WindowFrameUnits::Range => {
+                let (values, asc) = partition
+                    .range_values
+                    .as_ref()
+                    .ok_or(WindowError::RangeOffsetRequiresSingleOrderBy)?;
+                let asc = *asc;
+
+                if offset.is_null() {
+                    return Err(WindowError::NullFrameOffset.into());
+                }
+
+                let current = values[position];
+                if current.is_null() {
+                    return Ok(peer_bound);
+                }
+
+                let target = match following == asc {
+                    true => current.add(&offset)?,
+                    false => current.subtract(&offset)?,
+                };
+                let within = |value: &&Value| match value.evaluate_cmp(&target) {
+                    Some(Ordering::Equal) => true,
+                    Some(Ordering::Greater) => is_start == asc,
+                    Some(Ordering::Less) => is_start != asc,
+                    None => false,
+                };
+
+                Ok(match is_start {
+                    true => values.iter().position(within).unwrap_or(values.len()) as i64,
+                    false => values
+                        .iter()
+                        .rposition(within)
+                        .map(|position| position as i64)
+                        .unwrap_or(-1),
+                })
+            }
+        }
+    }
+
+    async fn evaluate_value(&self, context: &Context<'a>, expr: &'a Expr) -> Result<Value> {
+        let (filter_context, aggregated) = context;
+
+        evaluate(
+            self.storage,
+            self.ctx,
+            Some(Rc::clone(filter_context)),
+            aggregated.as_ref().map(Rc::clone),
+            expr,
+        )
+        .await?
+        .try_into()
+    }
+}
+
+fn find_windows(expr: &Expr) -> Vec<&AstWindow> {
+    match expr {
+        Expr::Between {
+            expr, low, high, ..
+        } => [expr, low, high]
+            .into_iter()
+            .flat_map(|expr| find_windows(expr))
+            .collect(),
+        Expr::BinaryOp { left, right, .. } => [left, right]
+            .into_iter()
+            .flat_map(|expr| find_windows(expr))
+            .collect(),
+        Expr::UnaryOp { expr, .. }
+        | Expr::Nested(expr)
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr) => find_windows(expr),
+        Expr::Case {
+            operand,
+            when_then,
+            else_result,
+        } => operand
+            .iter()
+            .map(|expr| expr.as_ref())
+            .chain(when_then.iter().flat_map(|(when, then)| [when, then]))
+            .chain(else_result.iter().map(|expr| expr.as_ref()))
+            .flat_map(find_windows)
+            .collect(),
+        Expr::Function(function) => function.as_exprs().flat_map(find_windows).collect(),
+        Expr::Window(window) => vec![window.as_ref()],
+        _ => Vec::new(),
+    }
+}
+
+fn peer_groups(rows: &[usize], order_keys: &[Vec<(Key, Option<bool>)>]) -> Vec<PeerGroup> {
+    let mut peers = Vec::with_capacity(rows.len());
+    let mut start = 0;
+    let mut dense_rank = 0;
+
+    for position in 1..=rows.len() {
+        let is_boundary = position == rows.len()
+            || sort_by(&order_keys[rows[start]], &order_keys[rows[position]]) != Ordering::Equal;
+
+        if is_boundary {
+            let peer = PeerGroup {
+                start,
+                end: position - 1,
+                dense_rank,
+            };
+
+            peers.resize(position, peer);
+            start = position;
+            dense_rank += 1;
+        }
+    }
+
+    peers
+}
+
+async fn aggregate_frame(aggregate: &Aggregate, values: &[Value]) -> Result<Value> {
+    let mut values = values.iter();
+    let aggr_value = match values.next() {
+        Some(value) => AggrValue::new(aggregate, value)?,
+        None => return Ok(AggrValue::empty(aggregate)),
+    };
+
+    values
+        .try_fold(aggr_value, |aggr_value, value| {
+            aggr_value
+                .accumulate(value)
+                .map(|accumulated| accumulated.unwrap_or(aggr_value))
+        })?
+        .export()
+        .await
+}
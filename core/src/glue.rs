@@ -1,9 +1,10 @@
 use {
     crate::{
-        ast::Statement,
-        executor::{execute, Payload},
-        parse_sql::parse,
-        plan::plan,
+        ast::{DataType, Statement},
+        data::Value,
+        executor::{execute_with, ExecuteContext, Limits, Payload, PrepareError},
+        parse_sql::{parse, parse_prepared},
+        plan::{expand_views, fetch_schema_map, param_types, plan, plan_expanded},
         result::Result,
         store::{GStore, GStoreMut},
         translate::translate,
@@ -16,11 +17,33 @@ use {
 
 pub struct Glue<T: GStore + GStoreMut> {
     pub storage: T,
+    pub limits: Limits,
+}
+
+/// Planned statement which can be executed repeatedly with different parameters
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreparedStatement {
+    statement: Statement,
+    param_types: Vec<Option<DataType>>,
+}
+
+impl PreparedStatement {
+    pub fn statement(&self) -> &Statement {
+        &self.statement
+    }
+
+    /// Types of the parameters, `None` when the type cannot be inferred from the statement
+    pub fn param_types(&self) -> &[Option<DataType>] {
+        &self.param_types
+    }
 }
 
 impl<T: GStore + GStoreMut> Glue<T> {
     pub fn new(storage: T) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            limits: Limits::default(),
+        }
     }
 
     pub async fn plan<Sql: AsRef<str>>(&mut self, sql: Sql) -> Result<Vec<Statement>> {
@@ -33,8 +56,59 @@ impl<T: GStore + GStoreMut> Glue<T> {
             .await
     }
 
+    pub async fn prepare<Sql: AsRef<str>>(&mut self, sql: Sql) -> Result<PreparedStatement> {
+        let parsed = parse_prepared(sql)?;
+        let parsed = match parsed.as_slice() {
+            [parsed] => parsed,
+            _ => return Err(PrepareError::NotSingleStatement(parsed.len()).into()),
+        };
+
+        let statement = translate(parsed)?;
+        let statement = expand_views(&self.storage, statement).await?;
+        let schema_map = fetch_schema_map(&self.storage, &statement).await?;
+        let param_types = param_types(&schema_map, &statement);
+        let statement = plan_expanded(&schema_map, statement)?;
+
+        Ok(PreparedStatement {
+            statement,
+            param_types,
+        })
+    }
+
+    pub async fn execute_prepared(
+        &mut self,
+        prepared: &PreparedStatement,
+        params: Vec<Value>,
+    ) -> Result<Payload> {
+        let PreparedStatement {
+            statement,
+            param_types,
+        } = prepared;
+
+        if param_types.len() != params.len() {
+            return Err(
+                PrepareError::ParameterCountMismatch(param_types.len(), params.len()).into(),
+            );
+        }
+
+        let params = params
+            .into_iter()
+            .zip(param_types)
+            .map(|(value, data_type)| match data_type {
+                Some(data_type) => value.cast(data_type),
+                None => Ok(value),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let ctx = ExecuteContext::new(params, self.limits);
+
+        execute_with(&mut self.storage, &ctx, statement).await
+    }
+
     pub async fn execute_stmt(&mut self, statement: &Statement) -> Result<Payload> {
-        execute(&mut self.storage, statement).await
+        let ctx = ExecuteContext::new(Vec::new(), self.limits);
+
+        execute_with(&mut self.storage, &ctx, statement).await
     }
 
     pub async fn execute<Sql: AsRef<str>>(&mut self, sql: Sql) -> Result<Vec<Payload>> {
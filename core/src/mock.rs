@@ -4,7 +4,8 @@ use {
         result::{Error, Result},
         store::{
             AlterTable, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut, Metadata,
-            RowIter, Store, StoreMut, Transaction,
+            RowIter, Sequence, SequenceMut, Store, StoreMut, Transaction, Trigger, TriggerMut,
+            View, ViewMut,
         },
     },
     async_trait::async_trait,
@@ -41,6 +42,24 @@ impl CustomFunction for MockStorage {}
 #[async_trait(?Send)]
 impl CustomFunctionMut for MockStorage {}
 
+#[async_trait(?Send)]
+impl Sequence for MockStorage {}
+
+#[async_trait(?Send)]
+impl SequenceMut for MockStorage {}
+
+#[async_trait(?Send)]
+impl View for MockStorage {}
+
+#[async_trait(?Send)]
+impl ViewMut for MockStorage {}
+
+#[async_trait(?Send)]
+impl Trigger for MockStorage {}
+
+#[async_trait(?Send)]
+impl TriggerMut for MockStorage {}
+
 #[async_trait(?Send)]
 impl Store for MockStorage {
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
@@ -158,17 +177,19 @@ mod tests {
         assert!(block_on(storage.drop_column("Foo", "col", false)).is_err());
 
         // Index & IndexMut
-        assert!(block_on(storage.scan_indexed_data("Foo", "idx_col", None, None)).is_err());
+        assert!(block_on(storage.scan_indexed_data("Foo", "idx_col", None, &[], None)).is_err());
         assert!(block_on(storage.create_index(
             "Foo",
             "idx_col",
-            &OrderByExpr {
+            &[OrderByExpr {
                 expr: Expr::TypedString {
                     data_type: DataType::Boolean,
                     value: "true".to_owned(),
                 },
                 asc: None,
-            },
+            }],
+            false,
+            None,
         ))
         .is_err());
         assert!(block_on(storage.drop_index("Foo", "idx_col")).is_err());
@@ -1,21 +1,666 @@
 use {
-    crate::result::{Error, Result},
+    crate::{
+        ast::{TriggerEvent, TriggerTiming},
+        executor::PrepareError,
+        result::{Error, Result},
+    },
     sqlparser::{
         ast::{
             Assignment as SqlAssignment, ColumnDef as SqlColumnDef, DataType as SqlDataType,
-            Expr as SqlExpr, Ident as SqlIdent, OrderByExpr as SqlOrderByExpr, Query as SqlQuery,
-            SelectItem as SqlSelectItem, Statement as SqlStatement,
+            Expr as SqlExpr, Ident as SqlIdent, ObjectName as SqlObjectName,
+            OperateFunctionArg as SqlOperateFunctionArg, OrderByExpr as SqlOrderByExpr,
+            Query as SqlQuery, SelectItem as SqlSelectItem, Statement as SqlStatement,
         },
         dialect::PostgreSqlDialect,
-        parser::Parser,
-        tokenizer::Tokenizer,
+        keywords::Keyword,
+        parser::{Parser, ParserError},
+        tokenizer::{Token, Tokenizer},
     },
 };
 
 const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
 
-pub fn parse<Sql: AsRef<str>>(sql: Sql) -> Result<Vec<SqlStatement>> {
-    Parser::parse_sql(&DIALECT, sql.as_ref()).map_err(|e| Error::Parser(format!("{:#?}", e)))
+/// Statement parsed from SQL, statements which sqlparser does not support are parsed by
+/// GlueSQL on top of the sqlparser tokens.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum ParsedStatement {
+    Sql(SqlStatement),
+    /// `CREATE [OR REPLACE] TRIGGER <name> { BEFORE | AFTER } <event> [OR <event> ..]
+    /// ON <table> FOR EACH ROW <action>`
+    CreateTrigger {
+        or_replace: bool,
+        name: SqlObjectName,
+        table_name: SqlObjectName,
+        timing: TriggerTiming,
+        events: Vec<TriggerEvent>,
+        action: ParsedTriggerAction,
+    },
+    /// `DROP TRIGGER [IF EXISTS] <name> [, ..]`
+    DropTrigger {
+        if_exists: bool,
+        names: Vec<SqlObjectName>,
+    },
+    /// `CREATE [OR REPLACE] FUNCTION <name>(<args>) [RETURNS <type>] BEGIN .. END` or
+    /// `CREATE [OR REPLACE] FUNCTION <name>(<args>) RETURNS TABLE (<columns>) RETURN <query>`,
+    /// functions with `RETURN <expr>` are parsed by sqlparser
+    CreateFunction {
+        or_replace: bool,
+        name: SqlObjectName,
+        args: Vec<SqlOperateFunctionArg>,
+        body: ParsedFunctionBody,
+    },
+    /// `CREATE [OR REPLACE] PROCEDURE <name>(<args>) BEGIN .. END`
+    CreateProcedure {
+        or_replace: bool,
+        name: SqlObjectName,
+        args: Vec<SqlOperateFunctionArg>,
+        body: Vec<ParsedProcedural>,
+    },
+    /// `CALL <name>(<args>)`
+    Call {
+        name: SqlObjectName,
+        args: Vec<SqlExpr>,
+    },
+    /// `DROP PROCEDURE [IF EXISTS] <name> [, ..]`
+    DropProcedure {
+        if_exists: bool,
+        names: Vec<SqlObjectName>,
+    },
+    /// `CREATE [UNIQUE] INDEX <name> ON <table> (<columns>) WHERE <predicate>`,
+    /// indexes without a predicate are parsed by sqlparser
+    CreatePartialIndex {
+        name: SqlObjectName,
+        table_name: SqlObjectName,
+        columns: Vec<SqlOrderByExpr>,
+        unique: bool,
+        predicate: SqlExpr,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedTriggerAction {
+    /// `EXECUTE FUNCTION <name>(<args>)`
+    Function {
+        name: SqlObjectName,
+        args: Vec<SqlExpr>,
+    },
+    Statement(Box<SqlStatement>),
+    /// `SET NEW.<column> = <expr> [, ..]`
+    SetNew(Vec<(SqlIdent, SqlExpr)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedFunctionBody {
+    Block(Vec<ParsedProcedural>),
+    /// `RETURNS TABLE (<columns>) RETURN <query>`
+    Table {
+        columns: Vec<SqlColumnDef>,
+        query: Box<SqlQuery>,
+    },
+}
+
+/// Statement of a `BEGIN .. END` block
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedProcedural {
+    Declare {
+        name: SqlIdent,
+        data_type: SqlDataType,
+        default: Option<SqlExpr>,
+    },
+    Set {
+        name: SqlIdent,
+        value: SqlExpr,
+    },
+    If {
+        branches: Vec<(SqlExpr, Vec<ParsedProcedural>)>,
+        else_body: Option<Vec<ParsedProcedural>>,
+    },
+    Case {
+        operand: Option<SqlExpr>,
+        branches: Vec<(SqlExpr, Vec<ParsedProcedural>)>,
+        else_body: Option<Vec<ParsedProcedural>>,
+    },
+    While {
+        condition: SqlExpr,
+        body: Vec<ParsedProcedural>,
+    },
+    Loop(Vec<ParsedProcedural>),
+    Exit(Option<SqlExpr>),
+    Continue(Option<SqlExpr>),
+    Return(Option<SqlExpr>),
+    Statement(Box<ParsedStatement>),
+}
+
+pub fn parse<Sql: AsRef<str>>(sql: Sql) -> Result<Vec<ParsedStatement>> {
+    Parser::new(&DIALECT)
+        .try_with_sql(sql.as_ref())
+        .and_then(parse_statements)
+        .map_err(|e| Error::Parser(format!("{:#?}", e)))
+}
+
+/// Parses statements to prepare, positional `?` placeholders are numbered `$1`, `$2`, ..
+/// in the order they appear.
+pub fn parse_prepared<Sql: AsRef<str>>(sql: Sql) -> Result<Vec<ParsedStatement>> {
+    let mut tokens = Tokenizer::new(&DIALECT, sql.as_ref())
+        .tokenize()
+        .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
+    number_placeholders(&mut tokens)?;
+
+    parse_statements(Parser::new(&DIALECT).with_tokens(tokens))
+        .map_err(|e| Error::Parser(format!("{:#?}", e)))
+}
+
+fn number_placeholders(tokens: &mut [Token]) -> Result<()> {
+    let mut positional = 0;
+    let mut numbered = false;
+
+    for token in tokens {
+        let Token::Placeholder(placeholder) = token else {
+            continue;
+        };
+
+        if placeholder == "?" {
+            positional += 1;
+            *placeholder = format!("${positional}");
+        } else if placeholder
+            .strip_prefix('$')
+            .and_then(|index| index.parse::<usize>().ok())
+            .filter(|index| *index > 0)
+            .is_some()
+        {
+            numbered = true;
+        } else {
+            return Err(PrepareError::UnsupportedPlaceholder(placeholder.to_owned()).into());
+        }
+
+        if positional > 0 && numbered {
+            return Err(PrepareError::MixedPlaceholders.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Same as `Parser::parse_statements`, but also parses the statements of `ParsedStatement`
+fn parse_statements(mut parser: Parser) -> Result<Vec<ParsedStatement>, ParserError> {
+    let mut statements = Vec::new();
+    let mut expecting_statement_delimiter = false;
+
+    loop {
+        while parser.consume_token(&Token::SemiColon) {
+            expecting_statement_delimiter = false;
+        }
+
+        match parser.peek_token().token {
+            Token::EOF => break,
+            Token::Word(word) if word.keyword == Keyword::END => break,
+            _ => {}
+        }
+
+        if expecting_statement_delimiter {
+            return parser.expected("end of statement", parser.peek_token());
+        }
+
+        statements.push(parse_statement(&mut parser)?);
+        expecting_statement_delimiter = true;
+    }
+
+    Ok(statements)
+}
+
+fn parse_statement(parser: &mut Parser) -> Result<ParsedStatement, ParserError> {
+    let keyword = |n| match parser.peek_nth_token(n).token {
+        Token::Word(word) => word.keyword,
+        _ => Keyword::NoKeyword,
+    };
+
+    match (keyword(0), keyword(1), keyword(2), keyword(3)) {
+        (Keyword::CREATE, Keyword::TRIGGER, _, _)
+        | (Keyword::CREATE, Keyword::OR, Keyword::REPLACE, Keyword::TRIGGER) => {
+            parse_create_trigger(parser)
+        }
+        (Keyword::DROP, Keyword::TRIGGER, _, _) => parse_drop_trigger(parser),
+        (Keyword::CREATE, Keyword::FUNCTION, _, _) if has_custom_body(parser, 2) => {
+            parse_create_routine(parser)
+        }
+        (Keyword::CREATE, Keyword::OR, Keyword::REPLACE, Keyword::FUNCTION)
+            if has_custom_body(parser, 4) =>
+        {
+            parse_create_routine(parser)
+        }
+        (Keyword::CREATE, Keyword::PROCEDURE, _, _)
+        | (Keyword::CREATE, Keyword::OR, Keyword::REPLACE, Keyword::PROCEDURE) => {
+            parse_create_routine(parser)
+        }
+        (Keyword::CALL, _, _, _) => parse_call(parser),
+        (Keyword::DROP, Keyword::PROCEDURE, _, _) => parse_drop_procedure(parser),
+        (Keyword::CREATE, Keyword::INDEX, _, _)
+        | (Keyword::CREATE, Keyword::UNIQUE, Keyword::INDEX, _) => parse_create_index(parser),
+        _ => parser.parse_statement().map(ParsedStatement::Sql),
+    }
+}
+
+/// Looks ahead whether the function starting from the `n`th token has a `BEGIN .. END` body
+/// or returns a table, skipping its name, arguments and `RETURNS` type
+fn has_custom_body(parser: &Parser, mut n: usize) -> bool {
+    let mut depth = 0;
+
+    loop {
+        match parser.peek_nth_token(n).token {
+            Token::EOF | Token::SemiColon => return false,
+            Token::LParen => depth += 1,
+            Token::RParen => depth -= 1,
+            Token::Word(word) if depth == 0 => match word.keyword {
+                Keyword::BEGIN | Keyword::TABLE => return true,
+                Keyword::RETURN | Keyword::AS | Keyword::LANGUAGE | Keyword::USING => return false,
+                _ => {}
+            },
+            _ => {}
+        }
+
+        n += 1;
+    }
+}
+
+/// Parses `CREATE [OR REPLACE] { FUNCTION | PROCEDURE } <name>(<args>) BEGIN .. END`
+/// and `CREATE [OR REPLACE] FUNCTION <name>(<args>) RETURNS TABLE (<columns>) RETURN <query>`
+fn parse_create_routine(parser: &mut Parser) -> Result<ParsedStatement, ParserError> {
+    parser.expect_keyword(Keyword::CREATE)?;
+    let or_replace = parser.parse_keywords(&[Keyword::OR, Keyword::REPLACE]);
+    let procedure = parser.expect_one_of_keywords(&[Keyword::FUNCTION, Keyword::PROCEDURE])?
+        == Keyword::PROCEDURE;
+    let name = parser.parse_object_name()?;
+
+    parser.expect_token(&Token::LParen)?;
+    let args = match parser.consume_token(&Token::RParen) {
+        true => Vec::new(),
+        false => {
+            let args = parser.parse_comma_separated(parse_function_arg)?;
+            parser.expect_token(&Token::RParen)?;
+
+            args
+        }
+    };
+
+    if !procedure && parser.parse_keywords(&[Keyword::RETURNS, Keyword::TABLE]) {
+        parser.expect_token(&Token::LParen)?;
+        let columns = parser.parse_comma_separated(Parser::parse_column_def)?;
+        parser.expect_token(&Token::RParen)?;
+        parser.expect_keyword(Keyword::RETURN)?;
+        let query = parser.parse_query().map(Box::new)?;
+
+        return Ok(ParsedStatement::CreateFunction {
+            or_replace,
+            name,
+            args,
+            body: ParsedFunctionBody::Table { columns, query },
+        });
+    }
+
+    if !procedure && parser.parse_keyword(Keyword::RETURNS) {
+        parser.parse_data_type()?;
+    }
+
+    parser.expect_keyword(Keyword::BEGIN)?;
+    let body = parse_block(parser, &[])?;
+    parser.expect_keyword(Keyword::END)?;
+
+    Ok(match procedure {
+        true => ParsedStatement::CreateProcedure {
+            or_replace,
+            name,
+            args,
+            body,
+        },
+        false => ParsedStatement::CreateFunction {
+            or_replace,
+            name,
+            args,
+            body: ParsedFunctionBody::Block(body),
+        },
+    })
+}
+
+/// Same as the function argument parser of sqlparser, `[ <name> ] <type> [ DEFAULT <expr> ]`
+fn parse_function_arg(parser: &mut Parser) -> Result<SqlOperateFunctionArg, ParserError> {
+    let mut name = None;
+    let mut data_type = parser.parse_data_type()?;
+    if let SqlDataType::Custom(custom, _) = &data_type {
+        name = Some(custom.0[0].clone());
+        data_type = parser.parse_data_type()?;
+    }
+
+    let default_expr =
+        match parser.parse_keyword(Keyword::DEFAULT) || parser.consume_token(&Token::Eq) {
+            true => Some(parser.parse_expr()?),
+            false => None,
+        };
+
+    Ok(SqlOperateFunctionArg {
+        mode: None,
+        name,
+        data_type,
+        default_expr,
+    })
+}
+
+/// Parses statements each followed by a semicolon, until `END` or one of the `terminators`
+fn parse_block(
+    parser: &mut Parser,
+    terminators: &[&str],
+) -> Result<Vec<ParsedProcedural>, ParserError> {
+    let mut body = Vec::new();
+
+    loop {
+        match parser.peek_token().token {
+            Token::EOF => return parser.expected("END", parser.peek_token()),
+            Token::Word(word)
+                if word.keyword == Keyword::END
+                    || terminators
+                        .iter()
+                        .any(|terminator| word.value.eq_ignore_ascii_case(terminator)) =>
+            {
+                return Ok(body)
+            }
+            _ => {}
+        }
+
+        body.push(parse_procedural(parser)?);
+        parser.expect_token(&Token::SemiColon)?;
+    }
+}
+
+fn parse_procedural(parser: &mut Parser) -> Result<ParsedProcedural, ParserError> {
+    let token = parser.peek_token();
+    let word = match &token.token {
+        Token::Word(word) => word.value.to_uppercase(),
+        _ => String::new(),
+    };
+    let when = |parser: &mut Parser| match parser.parse_keyword(Keyword::WHEN) {
+        true => parser.parse_expr().map(Some),
+        false => Ok(None),
+    };
+    let expect_word = |parser: &mut Parser, expected: &str| {
+        let token = parser.next_token();
+        match &token.token {
+            Token::Word(word) if word.value.eq_ignore_ascii_case(expected) => Ok(()),
+            _ => parser.expected(expected, token),
+        }
+    };
+
+    match word.as_str() {
+        "DECLARE" => {
+            parser.next_token();
+            let name = parser.parse_identifier()?;
+            let data_type = parser.parse_data_type()?;
+            let default = match parser.parse_keyword(Keyword::DEFAULT) {
+                true => Some(parser.parse_expr()?),
+                false => None,
+            };
+
+            Ok(ParsedProcedural::Declare {
+                name,
+                data_type,
+                default,
+            })
+        }
+        "SET" => {
+            parser.next_token();
+            let name = parser.parse_identifier()?;
+            parser.expect_token(&Token::Eq)?;
+            let value = parser.parse_expr()?;
+
+            Ok(ParsedProcedural::Set { name, value })
+        }
+        "IF" => {
+            parser.next_token();
+            let mut branches = Vec::new();
+            let else_body = loop {
+                let condition = parser.parse_expr()?;
+                parser.expect_keyword(Keyword::THEN)?;
+                let body = parse_block(parser, &["ELSEIF", "ELSE"])?;
+                branches.push((condition, body));
+
+                let token = parser.next_token();
+                match &token.token {
+                    Token::Word(word) if word.value.eq_ignore_ascii_case("ELSEIF") => {}
+                    Token::Word(word) if word.keyword == Keyword::ELSE => {
+                        let body = parse_block(parser, &[])?;
+                        parser.expect_keyword(Keyword::END)?;
+
+                        break Some(body);
+                    }
+                    _ => {
+                        parser.prev_token();
+                        parser.expect_keyword(Keyword::END)?;
+
+                        break None;
+                    }
+                }
+            };
+            parser.expect_keyword(Keyword::IF)?;
+
+            Ok(ParsedProcedural::If {
+                branches,
+                else_body,
+            })
+        }
+        "CASE" => {
+            parser.next_token();
+            let operand = match parser.peek_token().token {
+                Token::Word(word) if word.keyword == Keyword::WHEN => None,
+                _ => Some(parser.parse_expr()?),
+            };
+
+            let mut branches = Vec::new();
+            while parser.parse_keyword(Keyword::WHEN) {
+                let condition = parser.parse_expr()?;
+                parser.expect_keyword(Keyword::THEN)?;
+                let body = parse_block(parser, &["WHEN", "ELSE"])?;
+                branches.push((condition, body));
+            }
+
+            if branches.is_empty() {
+                return parser.expected("WHEN", parser.peek_token());
+            }
+
+            let else_body = match parser.parse_keyword(Keyword::ELSE) {
+                true => Some(parse_block(parser, &[])?),
+                false => None,
+            };
+            parser.expect_keywords(&[Keyword::END, Keyword::CASE])?;
+
+            Ok(ParsedProcedural::Case {
+                operand,
+                branches,
+                else_body,
+            })
+        }
+        "WHILE" => {
+            parser.next_token();
+            let condition = parser.parse_expr()?;
+            parser.expect_keyword(Keyword::DO)?;
+            let body = parse_block(parser, &[])?;
+            parser.expect_keyword(Keyword::END)?;
+            expect_word(parser, "WHILE")?;
+
+            Ok(ParsedProcedural::While { condition, body })
+        }
+        "LOOP" => {
+            parser.next_token();
+            let body = parse_block(parser, &[])?;
+            parser.expect_keyword(Keyword::END)?;
+            expect_word(parser, "LOOP")?;
+
+            Ok(ParsedProcedural::Loop(body))
+        }
+        "EXIT" => {
+            parser.next_token();
+
+            when(parser).map(ParsedProcedural::Exit)
+        }
+        "CONTINUE" => {
+            parser.next_token();
+
+            when(parser).map(ParsedProcedural::Continue)
+        }
+        "RETURN" => {
+            parser.next_token();
+
+            match parser.peek_token().token {
+                Token::SemiColon => Ok(ParsedProcedural::Return(None)),
+                _ => parser.parse_expr().map(Some).map(ParsedProcedural::Return),
+            }
+        }
+        _ => parse_statement(parser)
+            .map(Box::new)
+            .map(ParsedProcedural::Statement),
+    }
+}
+
+fn parse_call(parser: &mut Parser) -> Result<ParsedStatement, ParserError> {
+    parser.expect_keyword(Keyword::CALL)?;
+    let name = parser.parse_object_name()?;
+    parser.expect_token(&Token::LParen)?;
+    let args = match parser.consume_token(&Token::RParen) {
+        true => Vec::new(),
+        false => {
+            let args = parser.parse_comma_separated(Parser::parse_expr)?;
+            parser.expect_token(&Token::RParen)?;
+
+            args
+        }
+    };
+
+    Ok(ParsedStatement::Call { name, args })
+}
+
+fn parse_drop_procedure(parser: &mut Parser) -> Result<ParsedStatement, ParserError> {
+    parser.expect_keywords(&[Keyword::DROP, Keyword::PROCEDURE])?;
+    let if_exists = parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+    let names = parser.parse_comma_separated(Parser::parse_object_name)?;
+
+    Ok(ParsedStatement::DropProcedure { if_exists, names })
+}
+
+fn parse_create_index(parser: &mut Parser) -> Result<ParsedStatement, ParserError> {
+    parser.expect_keyword(Keyword::CREATE)?;
+    let unique = parser.parse_keyword(Keyword::UNIQUE);
+    parser.expect_keyword(Keyword::INDEX)?;
+    let statement = parser.parse_create_index(unique)?;
+
+    if !parser.parse_keyword(Keyword::WHERE) {
+        return Ok(ParsedStatement::Sql(statement));
+    }
+
+    let predicate = parser.parse_expr()?;
+    match statement {
+        SqlStatement::CreateIndex {
+            name,
+            table_name,
+            columns,
+            unique,
+            ..
+        } => Ok(ParsedStatement::CreatePartialIndex {
+            name,
+            table_name,
+            columns,
+            unique,
+            predicate,
+        }),
+        _ => parser.expected("CREATE INDEX", parser.peek_token()),
+    }
+}
+
+fn parse_create_trigger(parser: &mut Parser) -> Result<ParsedStatement, ParserError> {
+    parser.expect_keyword(Keyword::CREATE)?;
+    let or_replace = parser.parse_keywords(&[Keyword::OR, Keyword::REPLACE]);
+    parser.expect_keyword(Keyword::TRIGGER)?;
+    let name = parser.parse_object_name()?;
+
+    let token = parser.next_token();
+    let timing = match &token.token {
+        Token::Word(word) if word.value.eq_ignore_ascii_case("BEFORE") => TriggerTiming::Before,
+        Token::Word(word) if word.value.eq_ignore_ascii_case("AFTER") => TriggerTiming::After,
+        _ => return parser.expected("BEFORE or AFTER", token),
+    };
+
+    let mut events = Vec::new();
+    loop {
+        let event = match parser.parse_one_of_keywords(&[
+            Keyword::INSERT,
+            Keyword::UPDATE,
+            Keyword::DELETE,
+        ]) {
+            Some(Keyword::INSERT) => TriggerEvent::Insert,
+            Some(Keyword::UPDATE) => TriggerEvent::Update,
+            Some(_) => TriggerEvent::Delete,
+            None => return parser.expected("INSERT, UPDATE or DELETE", parser.peek_token()),
+        };
+
+        events.push(event);
+
+        if !parser.parse_keyword(Keyword::OR) {
+            break;
+        }
+    }
+
+    parser.expect_keyword(Keyword::ON)?;
+    let table_name = parser.parse_object_name()?;
+    parser.expect_keywords(&[Keyword::FOR, Keyword::EACH, Keyword::ROW])?;
+
+    let action = if parser.parse_keyword(Keyword::SET) {
+        let assignments = parser.parse_comma_separated(|parser| {
+            let token = parser.next_token();
+            if !matches!(&token.token, Token::Word(word) if word.value.eq_ignore_ascii_case("NEW"))
+            {
+                return parser.expected("NEW", token);
+            }
+
+            parser.expect_token(&Token::Period)?;
+            let column = parser.parse_identifier()?;
+            parser.expect_token(&Token::Eq)?;
+            let value = parser.parse_expr()?;
+
+            Ok((column, value))
+        })?;
+
+        ParsedTriggerAction::SetNew(assignments)
+    } else if parser.parse_keywords(&[Keyword::EXECUTE, Keyword::FUNCTION]) {
+        let name = parser.parse_object_name()?;
+        parser.expect_token(&Token::LParen)?;
+        let args = match parser.consume_token(&Token::RParen) {
+            true => Vec::new(),
+            false => {
+                let args = parser.parse_comma_separated(Parser::parse_expr)?;
+                parser.expect_token(&Token::RParen)?;
+
+                args
+            }
+        };
+
+        ParsedTriggerAction::Function { name, args }
+    } else {
+        ParsedTriggerAction::Statement(Box::new(parser.parse_statement()?))
+    };
+
+    Ok(ParsedStatement::CreateTrigger {
+        or_replace,
+        name,
+        table_name,
+        timing,
+        events,
+        action,
+    })
+}
+
+fn parse_drop_trigger(parser: &mut Parser) -> Result<ParsedStatement, ParserError> {
+    parser.expect_keywords(&[Keyword::DROP, Keyword::TRIGGER])?;
+    let if_exists = parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+    let names = parser.parse_comma_separated(Parser::parse_object_name)?;
+
+    Ok(ParsedStatement::DropTrigger { if_exists, names })
 }
 
 macro_rules! generate_parse_fn {
@@ -1,4 +1,4 @@
-use {serde::Serialize, std::fmt::Debug, thiserror::Error as ThisError};
+use {crate::ast::DataType, serde::Serialize, std::fmt::Debug, thiserror::Error as ThisError};
 
 #[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
 pub enum PlanError {
@@ -6,4 +6,18 @@ pub enum PlanError {
     /// situation.
     #[error("column reference {0} is ambiguous, please specify the table name")]
     ColumnReferenceAmbiguous(String),
+
+    /// Error that occurs when operands of `UNION`, `INTERSECT` or `EXCEPT` have different number
+    /// of columns.
+    #[error("each {0} query must have the same number of columns: {1} vs {2}")]
+    SetOperationColumnCountMismatch(String, usize, usize),
+
+    /// Error that occurs when a column of set operation operands has different types.
+    #[error("{operator} types {left} and {right} cannot be matched on column {column}")]
+    SetOperationColumnTypeMismatch {
+        operator: String,
+        column: String,
+        left: DataType,
+        right: DataType,
+    },
 }
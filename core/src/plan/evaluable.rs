@@ -1,8 +1,8 @@
 use {
     super::{context::Context, expr::PlanExpr},
     crate::ast::{
-        Expr, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem, SetExpr, TableAlias,
-        TableFactor, TableWithJoins, Values,
+        Cte, Distinct, Expr, GroupingSets, Join, JoinConstraint, JoinOperator, Query, Select,
+        SelectItem, SetExpr, TableAlias, TableFactor, TableWithJoins, Values, With,
     },
     std::{convert::identity, rc::Rc},
 };
@@ -40,22 +40,23 @@ pub fn check_expr(context: Option<Rc<Context<'_>>>, expr: &Expr) -> bool {
 
 fn check_query(context: Option<Rc<Context<'_>>>, query: &Query) -> bool {
     let Query {
+        with,
         body,
         order_by,
         limit,
         offset,
     } = query;
 
-    let body = match body {
-        SetExpr::Select(select) => check_select(context.as_ref().map(Rc::clone), select),
-        SetExpr::Values(Values(rows)) => rows
-            .iter()
-            .flatten()
-            .map(|expr| check_expr(context.as_ref().map(Rc::clone), expr))
-            .all(identity),
-    };
+    if !with
+        .iter()
+        .flat_map(|With { cte_tables, .. }| cte_tables)
+        .map(|Cte { query, .. }| check_query(context.as_ref().map(Rc::clone), query))
+        .all(identity)
+    {
+        return false;
+    }
 
-    if !body {
+    if !check_set_expr(context.as_ref().map(Rc::clone), body) {
         return false;
     }
 
@@ -75,15 +76,41 @@ fn check_query(context: Option<Rc<Context<'_>>>, query: &Query) -> bool {
         .all(identity)
 }
 
+fn check_set_expr(context: Option<Rc<Context<'_>>>, set_expr: &SetExpr) -> bool {
+    match set_expr {
+        SetExpr::Select(select) => check_select(context, select),
+        SetExpr::Values(Values(rows)) => rows
+            .iter()
+            .flatten()
+            .map(|expr| check_expr(context.as_ref().map(Rc::clone), expr))
+            .all(identity),
+        SetExpr::SetOperation { left, right, .. } => {
+            check_set_expr(context.as_ref().map(Rc::clone), left) && check_set_expr(context, right)
+        }
+    }
+}
+
 fn check_select(context: Option<Rc<Context<'_>>>, select: &Select) -> bool {
     let Select {
+        distinct,
         projection,
         from,
         selection,
         group_by,
+        grouping_sets,
         having,
     } = select;
 
+    if let Some(Distinct::On(exprs)) = distinct {
+        if !exprs
+            .iter()
+            .map(|expr| check_expr(context.as_ref().map(Rc::clone), expr))
+            .all(identity)
+        {
+            return false;
+        }
+    }
+
     if !projection
         .iter()
         .map(|select_item| match select_item {
@@ -116,11 +143,16 @@ fn check_select(context: Option<Rc<Context<'_>>>, select: &Select) -> bool {
 
             match join_operator {
                 JoinOperator::Inner(JoinConstraint::On(expr))
-                | JoinOperator::LeftOuter(JoinConstraint::On(expr)) => {
+                | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+                | JoinOperator::RightOuter(JoinConstraint::On(expr))
+                | JoinOperator::FullOuter(JoinConstraint::On(expr)) => {
                     check_expr(context.as_ref().map(Rc::clone), expr)
                 }
                 JoinOperator::Inner(JoinConstraint::None)
-                | JoinOperator::LeftOuter(JoinConstraint::None) => true,
+                | JoinOperator::LeftOuter(JoinConstraint::None)
+                | JoinOperator::RightOuter(JoinConstraint::None)
+                | JoinOperator::FullOuter(JoinConstraint::None)
+                | JoinOperator::Cross => true,
             }
         })
         .all(identity)
@@ -131,6 +163,7 @@ fn check_select(context: Option<Rc<Context<'_>>>, select: &Select) -> bool {
     selection
         .iter()
         .chain(group_by.iter())
+        .chain(grouping_sets.iter().flat_map(GroupingSets::as_exprs))
         .chain(having.iter())
         .map(|expr| check_expr(context.as_ref().map(Rc::clone), expr))
         .all(identity)
@@ -144,7 +177,8 @@ fn check_table_factor(context: Option<Rc<Context<'_>>>, table_factor: &TableFact
             .unwrap_or_else(|| name),
         TableFactor::Derived { alias, .. }
         | TableFactor::Series { alias, .. }
-        | TableFactor::Dictionary { alias, .. } => &alias.name,
+        | TableFactor::Dictionary { alias, .. }
+        | TableFactor::Function { alias, .. } => &alias.name,
     };
 
     context
@@ -1,18 +1,42 @@
-use crate::ast::{Aggregate, CountArgExpr, Expr};
+use crate::ast::{Aggregate, AggregateFunction, CountArgExpr, Expr};
 
 impl Aggregate {
+    /// Argument of the aggregate function, `None` for `COUNT(*)` and the functions taking
+    /// more than one argument
     pub fn as_expr(&self) -> Option<&Expr> {
-        match self {
-            Aggregate::Count(CountArgExpr::Wildcard) => None,
-            Aggregate::Count(CountArgExpr::Expr(expr))
-            | Aggregate::Sum(expr)
-            | Aggregate::Max(expr)
-            | Aggregate::Min(expr)
-            | Aggregate::Avg(expr)
-            | Aggregate::Variance(expr)
-            | Aggregate::Stdev(expr) => Some(expr),
+        match &self.func {
+            AggregateFunction::Count(CountArgExpr::Wildcard)
+            | AggregateFunction::Grouping(_)
+            | AggregateFunction::StringAgg { .. }
+            | AggregateFunction::JsonObjectAgg { .. } => None,
+            AggregateFunction::Count(CountArgExpr::Expr(expr))
+            | AggregateFunction::Sum(expr)
+            | AggregateFunction::Max(expr)
+            | AggregateFunction::Min(expr)
+            | AggregateFunction::Avg(expr)
+            | AggregateFunction::Variance(expr)
+            | AggregateFunction::Stdev(expr)
+            | AggregateFunction::ArrayAgg(expr) => Some(expr),
         }
     }
+
+    pub fn as_exprs(&self) -> impl Iterator<Item = &Expr> {
+        let args = match &self.func {
+            AggregateFunction::Grouping(exprs) => exprs.iter().collect(),
+            AggregateFunction::StringAgg {
+                expr: left,
+                separator: right,
+            }
+            | AggregateFunction::JsonObjectAgg {
+                key: left,
+                value: right,
+            } => vec![left, right],
+            _ => self.as_expr().into_iter().collect::<Vec<_>>(),
+        };
+        let order_by = self.order_by.iter().map(|order_by| &order_by.expr);
+
+        args.into_iter().chain(order_by).chain(self.filter.as_ref())
+    }
 }
 
 #[cfg(test)]
@@ -61,4 +85,32 @@ mod tests {
         let expected = Expr::Identifier("id".to_owned());
         assert_eq!(actual.as_expr(), Some(&expected));
     }
+
+    #[test]
+    fn as_exprs() {
+        let ident = |name: &str| Expr::Identifier(name.to_owned());
+        let exprs = |aggregate: &Aggregate| aggregate.as_exprs().cloned().collect::<Vec<_>>();
+
+        assert_eq!(exprs(&parse("COUNT(*)")), Vec::new());
+        assert_eq!(exprs(&parse("SUM(DISTINCT id)")), vec![ident("id")]);
+        assert_eq!(
+            exprs(&parse("GROUPING(city, name)")),
+            vec![ident("city"), ident("name")]
+        );
+
+        let actual = Aggregate {
+            filter: Some(ident("paid")),
+            ..parse("SUM(id)")
+        };
+        assert_eq!(exprs(&actual), vec![ident("id"), ident("paid")]);
+
+        assert_eq!(
+            exprs(&parse("STRING_AGG(name, sep ORDER BY id)")),
+            vec![ident("name"), ident("sep"), ident("id")]
+        );
+        assert_eq!(
+            exprs(&parse("JSON_OBJECT_AGG(name, score)")),
+            vec![ident("name"), ident("score")]
+        );
+    }
 }
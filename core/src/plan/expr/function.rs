@@ -194,6 +194,7 @@ impl Function {
             }
             Self::Greatest(exprs) => Exprs::VariableArgs(exprs.iter()),
             Self::Entries(expr) => Exprs::Single([expr].into_iter()),
+            Self::Nextval(expr) | Self::Currval(expr) => Exprs::Single([expr].into_iter()),
         }
     }
 }
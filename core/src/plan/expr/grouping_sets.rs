@@ -0,0 +1,10 @@
+use crate::ast::{Expr, GroupingSets};
+
+impl GroupingSets {
+    pub fn as_exprs(&self) -> impl Iterator<Item = &Expr> {
+        let (GroupingSets::Sets(sets) | GroupingSets::Rollup(sets) | GroupingSets::Cube(sets)) =
+            self;
+
+        sets.iter().flatten()
+    }
+}
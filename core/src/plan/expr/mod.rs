@@ -1,5 +1,7 @@
 mod aggregate;
 mod function;
+mod grouping_sets;
+mod window;
 
 use {
     crate::ast::{Expr, Query},
@@ -22,7 +24,7 @@ pub enum PlanExpr<'a> {
 impl<'a> From<&'a Expr> for PlanExpr<'a> {
     fn from(expr: &'a Expr) -> Self {
         match expr {
-            Expr::Literal(_) | Expr::TypedString { .. } => PlanExpr::None,
+            Expr::Literal(_) | Expr::Placeholder(_) | Expr::TypedString { .. } => PlanExpr::None,
             Expr::Identifier(ident) => PlanExpr::Identifier(ident),
             Expr::CompoundIdentifier { alias, ident } => {
                 PlanExpr::CompoundIdentifier { alias, ident }
@@ -32,10 +34,15 @@ impl<'a> From<&'a Expr> for PlanExpr<'a> {
             | Expr::IsNull(expr)
             | Expr::IsNotNull(expr)
             | Expr::Interval { expr, .. } => PlanExpr::Expr(expr),
-            Expr::Aggregate(aggregate) => match aggregate.as_expr() {
-                Some(expr) => PlanExpr::Expr(expr),
-                None => PlanExpr::None,
-            },
+            Expr::Aggregate(aggregate) => {
+                let exprs = aggregate.as_exprs().collect::<Vec<_>>();
+
+                match exprs.as_slice() {
+                    [] => PlanExpr::None,
+                    [expr] => PlanExpr::Expr(expr),
+                    _ => PlanExpr::MultiExprs(exprs),
+                }
+            }
             Expr::BinaryOp { left, right, .. } => PlanExpr::TwoExprs(left, right),
             Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
                 PlanExpr::TwoExprs(expr, pattern)
@@ -70,6 +77,7 @@ impl<'a> From<&'a Expr> for PlanExpr<'a> {
                 PlanExpr::MultiExprs(exprs)
             }
             Expr::Function(function) => PlanExpr::MultiExprs(function.as_exprs().collect()),
+            Expr::Window(window) => PlanExpr::MultiExprs(window.as_exprs().collect()),
             Expr::Subquery(subquery) | Expr::Exists { subquery, .. } => PlanExpr::Query(subquery),
             Expr::InSubquery {
                 expr,
@@ -230,6 +238,14 @@ mod tests {
         let expected = PlanExpr::MultiExprs(expected.iter().collect());
         test!(actual, expected);
 
+        let actual = expr("SUM(price) OVER (PARTITION BY category ORDER BY id)");
+        let expected = ["price", "category", "id"]
+            .into_iter()
+            .map(expr)
+            .collect::<Vec<_>>();
+        let expected = PlanExpr::MultiExprs(expected.iter().collect());
+        test!(actual, expected);
+
         let actual = Expr::Subquery(Box::new(query("SELECT id FROM Foo")));
         let expected = query("SELECT id FROM Foo");
         let expected = PlanExpr::Query(&expected);
@@ -0,0 +1,101 @@
+use {
+    crate::ast::{
+        Expr, OrderByExpr, Window, WindowFrame, WindowFrameBound, WindowFunction, WindowSpec,
+    },
+    std::iter::once,
+};
+
+impl Window {
+    pub fn as_exprs(&self) -> impl Iterator<Item = &Expr> {
+        let Window { function, spec } = self;
+        let WindowSpec {
+            partition_by,
+            order_by,
+            frame,
+        } = spec;
+
+        let function_exprs = match function {
+            WindowFunction::RowNumber | WindowFunction::Rank | WindowFunction::DenseRank => {
+                Vec::new()
+            }
+            WindowFunction::Lag {
+                expr,
+                offset,
+                default,
+            }
+            | WindowFunction::Lead {
+                expr,
+                offset,
+                default,
+            } => once(expr).chain(offset).chain(default).collect(),
+            WindowFunction::FirstValue(expr) | WindowFunction::LastValue(expr) => vec![expr],
+            WindowFunction::Aggregate(aggregate) => aggregate.as_expr().into_iter().collect(),
+        };
+        let frame_exprs = frame.iter().flat_map(|WindowFrame { start, end, .. }| {
+            [start, end].into_iter().filter_map(|bound| match bound {
+                WindowFrameBound::CurrentRow => None,
+                WindowFrameBound::Preceding(expr) | WindowFrameBound::Following(expr) => {
+                    expr.as_ref()
+                }
+            })
+        });
+
+        function_exprs
+            .into_iter()
+            .chain(partition_by)
+            .chain(order_by.iter().map(|OrderByExpr { expr, .. }| expr))
+            .chain(frame_exprs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::{Expr, Window},
+        parse_sql::parse_expr,
+        translate::translate_expr,
+    };
+
+    fn parse(sql: &str) -> Window {
+        let parsed = parse_expr(sql).unwrap();
+        let expr = translate_expr(&parsed).unwrap();
+
+        match expr {
+            Expr::Window(window) => *window,
+            _ => unreachable!("only for window tests"),
+        }
+    }
+
+    fn exprs(sqls: &[&str]) -> Vec<Expr> {
+        sqls.iter()
+            .map(|sql| translate_expr(&parse_expr(sql).unwrap()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn as_exprs() {
+        let actual = parse("ROW_NUMBER() OVER ()");
+        assert_eq!(actual.as_exprs().count(), 0);
+
+        let actual = parse("RANK() OVER (PARTITION BY category ORDER BY price DESC)");
+        let expected = exprs(&["category", "price"]);
+        assert_eq!(
+            actual.as_exprs().collect::<Vec<_>>(),
+            expected.iter().collect::<Vec<_>>()
+        );
+
+        let actual = parse("LAG(price, 2, 0) OVER (ORDER BY id)");
+        let expected = exprs(&["price", "2", "0", "id"]);
+        assert_eq!(
+            actual.as_exprs().collect::<Vec<_>>(),
+            expected.iter().collect::<Vec<_>>()
+        );
+
+        let actual = parse("COUNT(*) OVER (ORDER BY id ROWS BETWEEN 1 PRECEDING AND n FOLLOWING)");
+        let expected = exprs(&["id", "1", "n"]);
+        assert_eq!(
+            actual.as_exprs().collect::<Vec<_>>(),
+            expected.iter().collect::<Vec<_>>()
+        );
+    }
+}
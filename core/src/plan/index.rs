@@ -1,19 +1,79 @@
 use {
+    super::schema::{scope_ctes, ScopedSchemaMap},
     crate::{
         ast::{
-            AstLiteral, BinaryOperator, Expr, Function, IndexItem, IndexOperator, OrderByExpr,
-            Query, Select, SetExpr, Statement, TableAlias, TableFactor, TableWithJoins,
+            AstLiteral, BinaryOperator, ColumnDef, Cte, DataType, Expr, Function, IndexItem,
+            IndexOperator, OrderByExpr, Query, Select, SetExpr, Statement, TableAlias, TableFactor,
+            TableWithJoins, UnaryOperator, With,
         },
         data::{Schema, SchemaIndex, SchemaIndexOrd, TableError},
         result::{Error, Result},
     },
-    std::collections::HashMap,
-    utils::Vector,
+    bigdecimal::BigDecimal,
+    std::{
+        borrow::Cow,
+        cmp::{Ordering, Reverse},
+        collections::HashMap,
+    },
 };
 
 pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> Result<Statement> {
     match statement {
         Statement::Query(query) => plan_query(schema_map, query).map(Statement::Query),
+        Statement::Update {
+            table_name,
+            assignments,
+            from: Some(from),
+            selection,
+            returning,
+        } => {
+            let select = Select {
+                distinct: None,
+                projection: Vec::new(),
+                from,
+                selection,
+                group_by: Vec::new(),
+                grouping_sets: Vec::new(),
+                having: None,
+            };
+            let Select {
+                from, selection, ..
+            } = plan_joined_select(schema_map, select)?;
+
+            Ok(Statement::Update {
+                table_name,
+                assignments,
+                from: Some(from),
+                selection,
+                returning,
+            })
+        }
+        Statement::Delete {
+            table_name,
+            using: Some(using),
+            selection,
+            returning,
+        } => {
+            let select = Select {
+                distinct: None,
+                projection: Vec::new(),
+                from: using,
+                selection,
+                group_by: Vec::new(),
+                grouping_sets: Vec::new(),
+                having: None,
+            };
+            let Select {
+                from, selection, ..
+            } = plan_joined_select(schema_map, select)?;
+
+            Ok(Statement::Delete {
+                table_name,
+                using: Some(from),
+                selection,
+                returning,
+            })
+        }
         _ => Ok(statement),
     }
 }
@@ -21,51 +81,465 @@ pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> Resul
 struct Indexes(Vec<SchemaIndex>);
 
 impl Indexes {
+    /// Indexes usable with `selection`, a partial index is usable only when every condition of
+    /// its predicate is implied by a condition of `selection`, see [`implies`]
+    fn new(schema: &Schema, selection: Option<&Expr>) -> Self {
+        let Schema {
+            indexes,
+            column_defs,
+            ..
+        } = schema;
+        let column_defs = column_defs.as_deref().unwrap_or_default();
+        let conditions = selection
+            .map(conjuncts)
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(split_between)
+            .collect::<Vec<_>>();
+        let implied = |predicate: &Expr| {
+            conjuncts(predicate)
+                .into_iter()
+                .flat_map(split_between)
+                .all(|expected| {
+                    conditions
+                        .iter()
+                        .any(|condition| implies(column_defs, condition, &expected))
+                })
+        };
+
+        let indexes = indexes
+            .iter()
+            .filter(|SchemaIndex { predicate, .. }| match predicate {
+                Some(predicate) => implied(predicate),
+                None => true,
+            })
+            .cloned()
+            .collect();
+
+        Self(indexes)
+    }
+
     fn find(&self, target: &Expr) -> Option<String> {
         self.0
             .iter()
-            .find(|SchemaIndex { expr, .. }| expr == target)
+            .find(|SchemaIndex { columns, .. }| {
+                matches!(columns.as_slice(), [OrderByExpr { expr, .. }] if expr == target)
+            })
             .map(|SchemaIndex { name, .. }| name.to_owned())
     }
 
-    fn find_ordered(&self, target: &OrderByExpr) -> Option<String> {
+    /// Finds the index scanning rows in the order of the trailing expressions of `order_by`,
+    /// returns its name, the scan direction and the number of the covered expressions.
+    fn find_ordered(&self, order_by: &[OrderByExpr]) -> Option<(String, Option<bool>, usize)> {
         self.0
             .iter()
-            .find(|SchemaIndex { expr, order, .. }| {
-                if expr != &target.expr {
-                    return false;
-                }
+            .filter_map(|index| find_ordered_index(index, order_by))
+            .min_by_key(|(_, _, len)| Reverse(*len))
+    }
+
+    /// Finds the composite index matching the most conditions among `conjuncts`
+    fn find_composite(&self, conjuncts: &[&Expr]) -> Option<CompositeMatch> {
+        self.0
+            .iter()
+            .filter(|SchemaIndex { columns, .. }| columns.len() > 1)
+            .filter_map(|index| match_composite_index(index, conjuncts))
+            .min_by_key(|CompositeMatch { used, .. }| Reverse(used.len()))
+    }
+}
+
+fn find_ordered_index(
+    index: &SchemaIndex,
+    order_by: &[OrderByExpr],
+) -> Option<(String, Option<bool>, usize)> {
+    let SchemaIndex {
+        name,
+        columns,
+        order,
+        ..
+    } = index;
+
+    if let [OrderByExpr { expr, .. }] = columns.as_slice() {
+        let target = order_by.last()?;
+        let matched = expr == &target.expr
+            && matches!(
+                (target.asc, order),
+                (_, SchemaIndexOrd::Both)
+                    | (Some(true), SchemaIndexOrd::Asc)
+                    | (None, SchemaIndexOrd::Asc)
+                    | (Some(false), SchemaIndexOrd::Desc)
+            );
+
+        return matched.then(|| (name.to_owned(), target.asc, 1));
+    }
+
+    // composite index keys are sorted in the direction of each column
+    let is_asc = |OrderByExpr { asc, .. }: &OrderByExpr| asc != &Some(false);
+
+    (1..=columns.len().min(order_by.len()))
+        .rev()
+        .find_map(|len| {
+            let targets = &order_by[order_by.len() - len..];
+            let pairs = || targets.iter().zip(columns);
+
+            if !pairs().all(|(target, column)| target.expr == column.expr) {
+                return None;
+            }
+
+            let forward = pairs().all(|(target, column)| is_asc(target) == is_asc(column));
+            let backward = pairs().all(|(target, column)| is_asc(target) != is_asc(column));
+
+            match order {
+                SchemaIndexOrd::Asc | SchemaIndexOrd::Both if forward => Some((Some(true), len)),
+                SchemaIndexOrd::Desc | SchemaIndexOrd::Both if backward => Some((Some(false), len)),
+                _ => None,
+            }
+        })
+        .map(|(asc, len)| (name.to_owned(), asc, len))
+}
+
+/// Conditions of a selection matched to a composite index
+struct CompositeMatch {
+    index_name: String,
+    /// positions of the matched conditions
+    used: Vec<usize>,
+    /// equality values of the leading columns
+    prefix: Vec<Expr>,
+    /// comparison on the column next to `prefix`
+    cmp: (IndexOperator, Expr),
+}
 
-                matches!(
-                    (target.asc, order),
-                    (_, SchemaIndexOrd::Both)
-                        | (Some(true), SchemaIndexOrd::Asc)
-                        | (None, SchemaIndexOrd::Asc)
-                        | (Some(false), SchemaIndexOrd::Desc)
-                )
+/// Matches equalities on the leading columns followed by a comparison on the next column
+fn match_composite_index(index: &SchemaIndex, conjuncts: &[&Expr]) -> Option<CompositeMatch> {
+    let find = |column: &Expr, used: &[usize], eq_only: bool| {
+        conjuncts
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !used.contains(i))
+            .find_map(|(i, conjunct)| {
+                match_index_cmp(column, conjunct)
+                    .filter(|(op, _)| !eq_only || op == &IndexOperator::Eq)
+                    .map(|cmp| (i, cmp))
             })
-            .map(|SchemaIndex { name, .. }| name.to_owned())
+    };
+
+    let mut used = Vec::new();
+    let mut prefix = Vec::new();
+    for OrderByExpr { expr: column, .. } in &index.columns {
+        match find(column, &used, true) {
+            Some((i, (_, value))) => {
+                used.push(i);
+                prefix.push(value);
+            }
+            None => break,
+        }
+    }
+
+    let cmp = index
+        .columns
+        .get(prefix.len())
+        .and_then(|OrderByExpr { expr: column, .. }| find(column, &used, false));
+    let cmp = match cmp {
+        Some((i, cmp)) => {
+            used.push(i);
+            cmp
+        }
+        None => (IndexOperator::Eq, prefix.pop()?),
+    };
+
+    Some(CompositeMatch {
+        index_name: index.name.to_owned(),
+        used,
+        prefix,
+        cmp,
+    })
+}
+
+/// Matches `<column> <op> <value>` or `<value> <op> <column>` with a stateless value
+fn match_index_cmp(column: &Expr, expr: &Expr) -> Option<(IndexOperator, Expr)> {
+    let (left, op, right) = match expr {
+        Expr::Nested(expr) => return match_index_cmp(column, expr),
+        Expr::BinaryOp { left, op, right } => (left, op, right),
+        _ => return None,
+    };
+    let op = match op {
+        BinaryOperator::Gt => IndexOperator::Gt,
+        BinaryOperator::Lt => IndexOperator::Lt,
+        BinaryOperator::GtEq => IndexOperator::GtEq,
+        BinaryOperator::LtEq => IndexOperator::LtEq,
+        BinaryOperator::Eq => IndexOperator::Eq,
+        _ => return None,
+    };
+    if unnest(left) == column && is_stateless(right) {
+        Some((op, right.as_ref().clone()))
+    } else if unnest(right) == column && is_stateless(left) {
+        Some((op.reverse(), left.as_ref().clone()))
+    } else {
+        None
+    }
+}
+
+/// Whether every row satisfying `condition` satisfies `expected`, it is decided from the
+/// form of both conditions only:
+/// - the same conditions, ignoring parentheses and the side of the constant in comparisons
+/// - comparisons of the same expression to constants, e.g. `x > 10` implies `x >= 5`
+/// - `x IN (..)` implies what each `x = item` implies, and `x = c` implies `x IN (.., c, ..)`
+///
+/// Anything else, e.g. an `OR` or a comparison to a non-constant, implies only itself.
+fn implies(column_defs: &[ColumnDef], condition: &Expr, expected: &Expr) -> bool {
+    if unnest(condition) == unnest(expected) {
+        return true;
+    }
+
+    if let Expr::InList {
+        expr,
+        list,
+        negated: false,
+    } = unnest(condition)
+    {
+        return !list.is_empty()
+            && list.iter().all(|item| {
+                let condition = Expr::BinaryOp {
+                    left: expr.clone(),
+                    op: BinaryOperator::Eq,
+                    right: Box::new(item.clone()),
+                };
+
+                implies(column_defs, &condition, expected)
+            });
+    }
+
+    let (target, op, constant) = match constant_cmp(condition) {
+        Some(cmp) => cmp,
+        None => return false,
+    };
+    let text = is_text_column(column_defs, target);
+
+    match unnest(expected) {
+        Expr::InList {
+            expr,
+            list,
+            negated: false,
+        } => {
+            op == IndexOperator::Eq
+                && unnest(expr) == target
+                && list
+                    .iter()
+                    .any(|item| compare_constants(constant, item, text) == Some(Ordering::Equal))
+        }
+        _ => match constant_cmp(expected) {
+            Some((expected_target, expected_op, expected_constant))
+                if expected_target == target =>
+            {
+                compare_constants(constant, expected_constant, text)
+                    .map(|ordering| bound_implies(op, ordering, expected_op))
+                    .unwrap_or(false)
+            }
+            _ => false,
+        },
+    }
+}
+
+/// Whether `x <op> c1` implies `x <expected_op> c2`, where `ordering` is `c1` compared to `c2`
+fn bound_implies(op: IndexOperator, ordering: Ordering, expected_op: IndexOperator) -> bool {
+    use IndexOperator::*;
+
+    match (&op, &expected_op) {
+        (Eq, _) => expected_op.matches(ordering),
+        (Gt, Gt | GtEq) | (Lt, Lt | LtEq) => ordering == Ordering::Equal || op.matches(ordering),
+        (GtEq, Gt | GtEq) | (LtEq, Lt | LtEq) => expected_op.matches(ordering),
+        _ => false,
+    }
+}
+
+/// Matches `<expr> <op> <constant>` or `<constant> <op> <expr>`, with the constant on the right
+fn constant_cmp(expr: &Expr) -> Option<(&Expr, IndexOperator, &Expr)> {
+    let (left, op, right) = match unnest(expr) {
+        Expr::BinaryOp { left, op, right } => (left, op, right),
+        _ => return None,
+    };
+    let op = match op {
+        BinaryOperator::Gt => IndexOperator::Gt,
+        BinaryOperator::Lt => IndexOperator::Lt,
+        BinaryOperator::GtEq => IndexOperator::GtEq,
+        BinaryOperator::LtEq => IndexOperator::LtEq,
+        BinaryOperator::Eq => IndexOperator::Eq,
+        _ => return None,
+    };
+
+    match (constant(right), constant(left)) {
+        (Some(_), _) => Some((unnest(left), op, right)),
+        (None, Some(_)) => Some((unnest(right), op.reverse(), left)),
+        (None, None) => None,
+    }
+}
+
+/// Compares two number constants, or two string constants when they are compared to a `TEXT`
+/// column, as other columns, e.g. `INTEGER` or `DATE`, read strings as values of their own type
+fn compare_constants(left: &Expr, right: &Expr, text: bool) -> Option<Ordering> {
+    match (constant(left)?, constant(right)?) {
+        (Constant::Number(left), Constant::Number(right)) => Some(left.cmp(&right)),
+        (Constant::Str(left), Constant::Str(right)) if text => Some(left.cmp(right)),
+        _ => None,
+    }
+}
+
+fn is_text_column(column_defs: &[ColumnDef], expr: &Expr) -> bool {
+    match expr {
+        Expr::Identifier(column_name) => column_defs.iter().any(
+            |ColumnDef {
+                 name, data_type, ..
+             }| { name == column_name && data_type == &DataType::Text },
+        ),
+        _ => false,
+    }
+}
+
+enum Constant<'a> {
+    Number(BigDecimal),
+    Str(&'a str),
+}
+
+fn constant(expr: &Expr) -> Option<Constant<'_>> {
+    match unnest(expr) {
+        Expr::Literal(AstLiteral::Number(number)) => Some(Constant::Number(number.clone())),
+        Expr::Literal(AstLiteral::QuotedString(value)) => Some(Constant::Str(value)),
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => match constant(expr)? {
+            Constant::Number(number) => Some(Constant::Number(-number)),
+            Constant::Str(_) => None,
+        },
+        _ => None,
+    }
+}
+
+/// Splits `x BETWEEN low AND high` into `x >= low` and `x <= high`
+fn split_between(expr: &Expr) -> Vec<Cow<'_, Expr>> {
+    match unnest(expr) {
+        Expr::Between {
+            expr,
+            negated: false,
+            low,
+            high,
+        } => [(BinaryOperator::GtEq, low), (BinaryOperator::LtEq, high)]
+            .into_iter()
+            .map(|(op, bound)| {
+                Cow::Owned(Expr::BinaryOp {
+                    left: expr.clone(),
+                    op,
+                    right: bound.clone(),
+                })
+            })
+            .collect(),
+        _ => vec![Cow::Borrowed(expr)],
+    }
+}
+
+fn unnest(mut expr: &Expr) -> &Expr {
+    while let Expr::Nested(inner) = expr {
+        expr = inner;
+    }
+
+    expr
+}
+
+fn conjuncts(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => conjuncts(left)
+            .into_iter()
+            .chain(conjuncts(right))
+            .collect(),
+        Expr::Nested(inner)
+            if matches!(
+                inner.as_ref(),
+                Expr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                }
+            ) =>
+        {
+            conjuncts(inner)
+        }
+        _ => vec![expr],
+    }
+}
+
+fn into_conjuncts(expr: Expr) -> Vec<Expr> {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => into_conjuncts(*left)
+            .into_iter()
+            .chain(into_conjuncts(*right))
+            .collect(),
+        Expr::Nested(inner)
+            if matches!(
+                inner.as_ref(),
+                Expr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                }
+            ) =>
+        {
+            into_conjuncts(*inner)
+        }
+        _ => vec![expr],
     }
 }
 
 fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Query> {
     let Query {
+        with,
         body,
         order_by,
         limit,
         offset,
     } = query;
 
+    let (cte_maps, body_map) = scope_ctes(schema_map, with.as_ref());
+    let with = with.map(|with| plan_with(&cte_maps, with)).transpose()?;
+    let schema_map = body_map.as_ref();
+
     let select = match body {
         SetExpr::Select(select) => select,
         SetExpr::Values(_) => {
             return Ok(Query {
+                with,
                 body,
                 order_by,
                 limit,
                 offset,
             });
         }
+        SetExpr::SetOperation {
+            op,
+            all,
+            left,
+            right,
+        } => {
+            let left = plan_set_expr(schema_map, *left).map(Box::new)?;
+            let right = plan_set_expr(schema_map, *right).map(Box::new)?;
+
+            return Ok(Query {
+                with,
+                body: SetExpr::SetOperation {
+                    op,
+                    all,
+                    left,
+                    right,
+                },
+                order_by,
+                limit,
+                offset,
+            });
+        }
     };
 
     let TableWithJoins { relation, .. } = &select.from;
@@ -73,6 +547,7 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         TableFactor::Table { name, .. } => name,
         TableFactor::Derived { .. } => {
             return Ok(Query {
+                with,
                 body: SetExpr::Select(select),
                 order_by,
                 limit,
@@ -86,13 +561,18 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         TableFactor::Dictionary {
             alias: TableAlias { name, .. },
             ..
+        }
+        | TableFactor::Function {
+            alias: TableAlias { name, .. },
+            ..
         } => name,
     };
 
     let indexes = match schema_map.get(table_name) {
-        Some(Schema { indexes, .. }) => Indexes(indexes.clone()),
+        Some(schema) => Indexes::new(schema, select.selection.as_ref()),
         None => {
             return Ok(Query {
+                with,
                 body: SetExpr::Select(select),
                 order_by,
                 limit,
@@ -101,23 +581,21 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         }
     };
 
-    let index = order_by.last().and_then(|value_expr| {
-        indexes
-            .find_ordered(value_expr)
-            .map(|name| IndexItem::NonClustered {
+    match indexes.find_ordered(&order_by) {
+        Some((name, asc, len)) => {
+            let index = Some(IndexItem::NonClustered {
                 name,
-                asc: value_expr.asc,
+                asc,
+                prefix: Vec::new(),
                 cmp_expr: None,
-            })
-    });
-
-    match index {
-        index if index.is_some() => {
+            });
             let Select {
+                distinct,
                 projection,
                 from,
                 selection,
                 group_by,
+                grouping_sets,
                 having,
             } = *select;
 
@@ -126,7 +604,8 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
                 TableFactor::Table { name, alias, .. } => (name, alias),
                 TableFactor::Derived { .. }
                 | TableFactor::Series { .. }
-                | TableFactor::Dictionary { .. } => {
+                | TableFactor::Dictionary { .. }
+                | TableFactor::Function { .. } => {
                     return Err(Error::Table(TableError::Unreachable));
                 }
             };
@@ -137,24 +616,31 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
             };
 
             let select = Select {
+                distinct,
                 projection,
                 from,
                 selection,
                 group_by,
+                grouping_sets,
                 having,
             };
 
+            let mut order_by = order_by;
+            order_by.truncate(order_by.len() - len);
+
             Ok(Query {
+                with,
                 body: SetExpr::Select(Box::new(select)),
-                order_by: Vector::from(order_by).pop().0.into(),
+                order_by,
                 limit,
                 offset,
             })
         }
-        _ => {
+        None => {
             let select = plan_select(schema_map, &indexes, *select)?;
             let body = SetExpr::Select(Box::new(select));
             let query = Query {
+                with,
                 body,
                 order_by,
                 limit,
@@ -166,16 +652,69 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
     }
 }
 
+fn plan_joined_select(schema_map: &HashMap<String, Schema>, select: Select) -> Result<Select> {
+    let indexes = match &select.from.relation {
+        TableFactor::Table { name, .. } => schema_map.get(name),
+        TableFactor::Derived { .. }
+        | TableFactor::Series { .. }
+        | TableFactor::Dictionary { .. }
+        | TableFactor::Function { .. } => None,
+    };
+
+    match indexes {
+        Some(schema) => {
+            let indexes = Indexes::new(schema, select.selection.as_ref());
+
+            plan_select(schema_map, &indexes, select)
+        }
+        None => Ok(select),
+    }
+}
+
+fn plan_with(cte_maps: &[ScopedSchemaMap], with: With) -> Result<With> {
+    let With {
+        recursive,
+        cte_tables,
+    } = with;
+
+    let cte_tables = cte_tables
+        .into_iter()
+        .zip(cte_maps)
+        .map(|(Cte { alias, query }, schema_map)| {
+            plan_query(schema_map, query).map(|query| Cte { alias, query })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(With {
+        recursive,
+        cte_tables,
+    })
+}
+
+fn plan_set_expr(schema_map: &HashMap<String, Schema>, set_expr: SetExpr) -> Result<SetExpr> {
+    let query = Query {
+        with: None,
+        body: set_expr,
+        order_by: Vec::new(),
+        limit: None,
+        offset: None,
+    };
+
+    plan_query(schema_map, query).map(|Query { body, .. }| body)
+}
+
 fn plan_select(
     schema_map: &HashMap<String, Schema>,
     indexes: &Indexes,
     select: Select,
 ) -> Result<Select> {
     let Select {
+        distinct,
         projection,
         from,
         selection,
         group_by,
+        grouping_sets,
         having,
     } = select;
 
@@ -183,25 +722,52 @@ fn plan_select(
         Some(expr) => expr,
         None => {
             return Ok(Select {
+                distinct,
                 projection,
                 from,
                 selection,
                 group_by,
+                grouping_sets,
                 having,
             });
         }
     };
 
-    match plan_index(schema_map, indexes, selection)? {
+    let planned = match plan_composite_index(indexes, selection, 2) {
+        Planned::Expr(selection) => match plan_index(schema_map, indexes, selection)? {
+            Planned::Expr(selection) => plan_composite_index(indexes, selection, 1),
+            planned => planned,
+        },
+        Planned::IndexedExpr {
+            index_name,
+            index_prefix,
+            index_op,
+            index_value_expr,
+            selection,
+        } => Planned::IndexedExpr {
+            index_name,
+            index_prefix,
+            index_op,
+            index_value_expr,
+            selection: selection
+                .map(|selection| plan_subqueries(schema_map, selection))
+                .transpose()?,
+        },
+    };
+
+    match planned {
         Planned::Expr(selection) => Ok(Select {
+            distinct,
             projection,
             from,
             selection: Some(selection),
             group_by,
+            grouping_sets,
             having,
         }),
         Planned::IndexedExpr {
             index_name,
+            index_prefix,
             index_op,
             index_value_expr,
             selection,
@@ -211,7 +777,8 @@ fn plan_select(
                 TableFactor::Table { name, alias, .. } => (name, alias),
                 TableFactor::Derived { .. }
                 | TableFactor::Series { .. }
-                | TableFactor::Dictionary { .. } => {
+                | TableFactor::Dictionary { .. }
+                | TableFactor::Function { .. } => {
                     return Err(Error::Table(TableError::Unreachable));
                 }
             };
@@ -219,6 +786,7 @@ fn plan_select(
             let index = Some(IndexItem::NonClustered {
                 name: index_name,
                 asc: None,
+                prefix: index_prefix,
                 cmp_expr: Some((index_op, index_value_expr)),
             });
             let from = TableWithJoins {
@@ -227,10 +795,12 @@ fn plan_select(
             };
 
             Ok(Select {
+                distinct,
                 projection,
                 from,
                 selection,
                 group_by,
+                grouping_sets,
                 having,
             })
         }
@@ -240,6 +810,7 @@ fn plan_select(
 enum Planned {
     IndexedExpr {
         index_name: String,
+        index_prefix: Vec<Expr>,
         index_op: IndexOperator,
         index_value_expr: Expr,
         selection: Option<Expr>,
@@ -247,6 +818,48 @@ enum Planned {
     Expr(Expr),
 }
 
+/// Plans a composite index when it matches at least `min_conditions` conditions of `selection`
+fn plan_composite_index(indexes: &Indexes, selection: Expr, min_conditions: usize) -> Planned {
+    let matched = indexes
+        .find_composite(&conjuncts(&selection))
+        .filter(|CompositeMatch { used, .. }| used.len() >= min_conditions);
+    let CompositeMatch {
+        index_name,
+        used,
+        prefix,
+        cmp: (index_op, index_value_expr),
+    } = match matched {
+        Some(matched) => matched,
+        None => return Planned::Expr(selection),
+    };
+
+    let selection = into_conjuncts(selection)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, expr)| (!used.contains(&i)).then_some(expr))
+        .reduce(|left, right| Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOperator::And,
+            right: Box::new(right),
+        });
+
+    Planned::IndexedExpr {
+        index_name,
+        index_prefix: prefix,
+        index_op,
+        index_value_expr,
+        selection,
+    }
+}
+
+/// Plans subqueries of `expr` without planning indexes for it
+fn plan_subqueries(schema_map: &HashMap<String, Schema>, expr: Expr) -> Result<Expr> {
+    match plan_index(schema_map, &Indexes(Vec::new()), expr)? {
+        Planned::Expr(expr) => Ok(expr),
+        Planned::IndexedExpr { .. } => Err(Error::Table(TableError::Unreachable)),
+    }
+}
+
 fn plan_index(
     schema_map: &HashMap<String, Schema>,
     indexes: &Indexes,
@@ -285,6 +898,7 @@ fn plan_index(
                 Planned::Expr(selection) => selection,
                 Planned::IndexedExpr {
                     index_name,
+                    index_prefix,
                     index_value_expr,
                     index_op,
                     selection,
@@ -300,6 +914,7 @@ fn plan_index(
 
                     return Ok(Planned::IndexedExpr {
                         index_name,
+                        index_prefix,
                         index_op,
                         index_value_expr,
                         selection: Some(selection),
@@ -315,6 +930,7 @@ fn plan_index(
                 })),
                 Planned::IndexedExpr {
                     index_name,
+                    index_prefix,
                     index_op,
                     index_value_expr,
                     selection,
@@ -330,6 +946,7 @@ fn plan_index(
 
                     Ok(Planned::IndexedExpr {
                         index_name,
+                        index_prefix,
                         index_value_expr,
                         index_op,
                         selection: Some(selection),
@@ -377,6 +994,7 @@ fn search_is_null(indexes: &Indexes, null: bool, expr: Box<Expr>) -> Planned {
 
             Planned::IndexedExpr {
                 index_name,
+                index_prefix: Vec::new(),
                 index_op,
                 index_value_expr: Expr::Literal(AstLiteral::Null),
                 selection: None,
@@ -406,6 +1024,7 @@ fn search_index_op(
     {
         Planned::IndexedExpr {
             index_name,
+            index_prefix: Vec::new(),
             index_op,
             index_value_expr: *right,
             selection: None,
@@ -416,6 +1035,7 @@ fn search_index_op(
     {
         Planned::IndexedExpr {
             index_name,
+            index_prefix: Vec::new(),
             index_op: index_op.reverse(),
             index_value_expr: *left,
             selection: None,
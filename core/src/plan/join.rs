@@ -1,9 +1,12 @@
 use {
-    super::{context::Context, evaluable::check_expr as check_evaluable, planner::Planner},
+    super::{
+        context::Context, evaluable::check_expr as check_evaluable, planner::Planner,
+        schema::scope_ctes,
+    },
     crate::{
         ast::{
-            BinaryOperator, Expr, Join, JoinConstraint, JoinExecutor, JoinOperator, Query, Select,
-            SetExpr, Statement, TableWithJoins,
+            BinaryOperator, Cte, Expr, Join, JoinConstraint, JoinExecutor, JoinOperator, Query,
+            Select, SetExpr, Statement, TableWithJoins, With,
         },
         data::Schema,
     },
@@ -20,6 +23,60 @@ pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> State
 
             Statement::Query(query)
         }
+        Statement::Update {
+            table_name,
+            assignments,
+            from: Some(from),
+            selection,
+            returning,
+        } => {
+            let select = Select {
+                distinct: None,
+                projection: Vec::new(),
+                from,
+                selection,
+                group_by: Vec::new(),
+                grouping_sets: Vec::new(),
+                having: None,
+            };
+            let Select {
+                from, selection, ..
+            } = planner.select(None, select);
+
+            Statement::Update {
+                table_name,
+                assignments,
+                from: Some(from),
+                selection,
+                returning,
+            }
+        }
+        Statement::Delete {
+            table_name,
+            using: Some(using),
+            selection,
+            returning,
+        } => {
+            let select = Select {
+                distinct: None,
+                projection: Vec::new(),
+                from: using,
+                selection,
+                group_by: Vec::new(),
+                grouping_sets: Vec::new(),
+                having: None,
+            };
+            let Select {
+                from, selection, ..
+            } = planner.select(None, select);
+
+            Statement::Delete {
+                table_name,
+                using: Some(from),
+                selection,
+                returning,
+            }
+        }
         _ => statement,
     }
 }
@@ -31,22 +88,43 @@ struct JoinPlanner<'a> {
 impl<'a> Planner<'a> for JoinPlanner<'a> {
     fn query(&self, outer_context: Option<Rc<Context<'a>>>, query: Query) -> Query {
         let Query {
+            with,
             body,
             order_by,
             limit,
             offset,
         } = query;
 
-        let body = match body {
-            SetExpr::Select(select) => {
-                let select = self.select(outer_context, *select);
-
-                SetExpr::Select(Box::new(select))
-            }
-            SetExpr::Values(_) => body,
+        let (cte_maps, body_map) = scope_ctes(self.schema_map, with.as_ref());
+        let with = with.map(
+            |With {
+                 recursive,
+                 cte_tables,
+             }| {
+                let cte_tables = cte_tables
+                    .into_iter()
+                    .zip(&cte_maps)
+                    .map(|(Cte { alias, query }, schema_map)| {
+                        let planner = JoinPlanner { schema_map };
+                        let query = planner.query(outer_context.as_ref().map(Rc::clone), query);
+
+                        Cte { alias, query }
+                    })
+                    .collect();
+
+                With {
+                    recursive,
+                    cte_tables,
+                }
+            },
+        );
+        let planner = JoinPlanner {
+            schema_map: &body_map,
         };
+        let body = planner.set_expr(outer_context, body);
 
         Query {
+            with,
             body,
             order_by,
             limit,
@@ -60,12 +138,41 @@ impl<'a> Planner<'a> for JoinPlanner<'a> {
 }
 
 impl<'a> JoinPlanner<'a> {
+    fn set_expr(&self, outer_context: Option<Rc<Context<'a>>>, set_expr: SetExpr) -> SetExpr {
+        match set_expr {
+            SetExpr::Select(select) => {
+                let select = self.select(outer_context, *select);
+
+                SetExpr::Select(Box::new(select))
+            }
+            SetExpr::Values(_) => set_expr,
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                let left = self.set_expr(outer_context.as_ref().map(Rc::clone), *left);
+                let right = self.set_expr(outer_context, *right);
+
+                SetExpr::SetOperation {
+                    op,
+                    all,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }
+            }
+        }
+    }
+
     fn select(&self, outer_context: Option<Rc<Context<'a>>>, select: Select) -> Select {
         let Select {
+            distinct,
             projection,
             from,
             selection,
             group_by,
+            grouping_sets,
             having,
         } = select;
 
@@ -73,10 +180,12 @@ impl<'a> JoinPlanner<'a> {
         let selection = selection.map(|expr| self.subquery_expr(outer_context, expr));
 
         Select {
+            distinct,
             projection,
             from,
             selection,
             group_by,
+            grouping_sets,
             having,
         }
     }
@@ -130,13 +239,20 @@ impl<'a> JoinPlanner<'a> {
         enum JoinOp {
             Inner,
             LeftOuter,
+            RightOuter,
+            FullOuter,
         }
 
         let (join_op, expr) = match join_operator {
             JoinOperator::Inner(JoinConstraint::On(expr)) => (JoinOp::Inner, expr),
             JoinOperator::LeftOuter(JoinConstraint::On(expr)) => (JoinOp::LeftOuter, expr),
+            JoinOperator::RightOuter(JoinConstraint::On(expr)) => (JoinOp::RightOuter, expr),
+            JoinOperator::FullOuter(JoinConstraint::On(expr)) => (JoinOp::FullOuter, expr),
             JoinOperator::Inner(JoinConstraint::None)
-            | JoinOperator::LeftOuter(JoinConstraint::None) => {
+            | JoinOperator::LeftOuter(JoinConstraint::None)
+            | JoinOperator::RightOuter(JoinConstraint::None)
+            | JoinOperator::FullOuter(JoinConstraint::None)
+            | JoinOperator::Cross => {
                 let context = self.update_context(inner_context, &relation);
                 let join = Join {
                     relation,
@@ -156,11 +272,15 @@ impl<'a> JoinPlanner<'a> {
             expr,
         );
 
-        let join_operator = match (join_op, expr) {
-            (JoinOp::Inner, Some(expr)) => JoinOperator::Inner(JoinConstraint::On(expr)),
-            (JoinOp::Inner, None) => JoinOperator::Inner(JoinConstraint::None),
-            (JoinOp::LeftOuter, Some(expr)) => JoinOperator::LeftOuter(JoinConstraint::On(expr)),
-            (JoinOp::LeftOuter, None) => JoinOperator::LeftOuter(JoinConstraint::None),
+        let join_constraint = match expr {
+            Some(expr) => JoinConstraint::On(expr),
+            None => JoinConstraint::None,
+        };
+        let join_operator = match join_op {
+            JoinOp::Inner => JoinOperator::Inner(join_constraint),
+            JoinOp::LeftOuter => JoinOperator::LeftOuter(join_constraint),
+            JoinOp::RightOuter => JoinOperator::RightOuter(join_constraint),
+            JoinOp::FullOuter => JoinOperator::FullOuter(join_constraint),
         };
 
         let context = self.update_context(inner_context, &relation);
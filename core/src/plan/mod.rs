@@ -4,24 +4,55 @@ mod evaluable;
 mod expr;
 mod index;
 mod join;
+mod param;
 mod planner;
 mod primary_key;
 mod schema;
 mod validate;
 
-use crate::{ast::Statement, result::Result, store::Store};
+use {
+    crate::{
+        ast::Statement,
+        data::Schema,
+        result::Result,
+        store::{Store, View},
+    },
+    std::collections::HashMap,
+};
 
 pub use {
-    self::validate::validate, error::*, index::plan as plan_index, join::plan as plan_join,
-    primary_key::plan as plan_primary_key, schema::fetch_schema_map,
+    self::validate::{validate, widen_data_type},
+    error::*,
+    index::plan as plan_index,
+    join::plan as plan_join,
+    param::param_types,
+    primary_key::plan as plan_primary_key,
+    schema::{expand_views, fetch_schema_map, read_table_names},
 };
 
-pub async fn plan<T: Store>(storage: &T, statement: Statement) -> Result<Statement> {
+pub async fn plan<T: Store + View>(storage: &T, statement: Statement) -> Result<Statement> {
+    let statement = expand_views(storage, statement).await?;
     let schema_map = fetch_schema_map(storage, &statement).await?;
-    validate(&schema_map, &statement)?;
-    let statement = plan_primary_key(&schema_map, statement);
-    let statement = plan_index(&schema_map, statement)?;
-    let statement = plan_join(&schema_map, statement);
 
-    Ok(statement)
+    plan_expanded(&schema_map, statement)
+}
+
+/// Plans `statement` whose views are already expanded, with the schemas of the tables it reads
+pub fn plan_expanded(
+    schema_map: &HashMap<String, Schema>,
+    statement: Statement,
+) -> Result<Statement> {
+    match statement {
+        Statement::Explain { statement, analyze } => plan_expanded(schema_map, *statement)
+            .map(Box::new)
+            .map(|statement| Statement::Explain { statement, analyze }),
+        _ => {
+            validate(schema_map, &statement)?;
+            let statement = plan_primary_key(schema_map, statement);
+            let statement = plan_index(schema_map, statement)?;
+            let statement = plan_join(schema_map, statement);
+
+            Ok(statement)
+        }
+    }
 }
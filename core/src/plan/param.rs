@@ -0,0 +1,340 @@
+use {
+    super::expr::PlanExpr,
+    crate::{
+        ast::{
+            Assignment, Cte, DataType, Distinct, Expr, GroupingSets, Join, JoinConstraint,
+            JoinOperator, OnConflict, OnConflictAction, OrderByExpr, Query, Select, SelectItem,
+            SetExpr, Statement, TableFactor, TableWithJoins, Values, With,
+        },
+        data::Schema,
+    },
+    std::{collections::HashMap, iter::repeat},
+};
+
+/// Infers the types of the `$n` placeholders of a statement from the columns they are
+/// compared with or assigned to, `None` when the type cannot be inferred.
+pub fn param_types(
+    schema_map: &HashMap<String, Schema>,
+    statement: &Statement,
+) -> Vec<Option<DataType>> {
+    let mut param_types = ParamTypes {
+        schema_map,
+        types: Vec::new(),
+    };
+    param_types.statement(statement);
+
+    param_types.types
+}
+
+struct ParamTypes<'a> {
+    schema_map: &'a HashMap<String, Schema>,
+    types: Vec<Option<DataType>>,
+}
+
+impl<'a> ParamTypes<'a> {
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Query(query) => self.query(query),
+            Statement::Insert {
+                table_name,
+                columns,
+                source,
+                on_conflict,
+                returning,
+            } => {
+                match &source.body {
+                    SetExpr::Values(Values(rows)) => {
+                        let data_types = self.insert_types(table_name, columns);
+
+                        for row in rows {
+                            let data_types = data_types.iter().copied().chain(repeat(None));
+
+                            for (expr, data_type) in row.iter().zip(data_types) {
+                                self.expr(expr, data_type);
+                            }
+                        }
+                    }
+                    _ => self.query(source),
+                }
+
+                if let Some(OnConflict {
+                    action:
+                        OnConflictAction::DoUpdate {
+                            assignments,
+                            selection,
+                        },
+                    ..
+                }) = on_conflict
+                {
+                    self.assignments(table_name, assignments);
+                    self.exprs(selection);
+                }
+
+                self.projection(returning);
+            }
+            Statement::Update {
+                table_name,
+                assignments,
+                from,
+                selection,
+                returning,
+            } => {
+                self.assignments(table_name, assignments);
+
+                if let Some(from) = from {
+                    self.table_with_joins(from);
+                }
+
+                self.exprs(selection);
+                self.projection(returning);
+            }
+            Statement::Delete {
+                using,
+                selection,
+                returning,
+                ..
+            } => {
+                if let Some(using) = using {
+                    self.table_with_joins(using);
+                }
+
+                self.exprs(selection);
+                self.projection(returning);
+            }
+            Statement::CreateTable {
+                source: Some(source),
+                ..
+            } => self.query(source),
+            Statement::Call { args, .. } => self.exprs(args),
+            Statement::Explain { statement, .. } => self.statement(statement),
+            _ => {}
+        }
+    }
+
+    fn assignments(&mut self, table_name: &str, assignments: &[Assignment]) {
+        for Assignment { id, value } in assignments {
+            let data_type = self.table_column_type(table_name, id);
+
+            self.expr(value, data_type);
+        }
+    }
+
+    fn query(&mut self, query: &Query) {
+        let Query {
+            with,
+            body,
+            order_by,
+            limit,
+            offset,
+        } = query;
+
+        if let Some(With { cte_tables, .. }) = with {
+            for Cte { query, .. } in cte_tables {
+                self.query(query);
+            }
+        }
+
+        self.set_expr(body);
+        self.exprs(order_by.iter().map(|OrderByExpr { expr, .. }| expr));
+        self.exprs(limit.iter().chain(offset));
+    }
+
+    fn set_expr(&mut self, set_expr: &SetExpr) {
+        match set_expr {
+            SetExpr::Select(select) => self.select(select),
+            SetExpr::Values(Values(rows)) => self.exprs(rows.iter().flatten()),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.set_expr(left);
+                self.set_expr(right);
+            }
+        }
+    }
+
+    fn select(&mut self, select: &Select) {
+        let Select {
+            distinct,
+            projection,
+            from,
+            selection,
+            group_by,
+            grouping_sets,
+            having,
+        } = select;
+
+        let distinct_on = match distinct {
+            Some(Distinct::On(exprs)) => exprs.as_slice(),
+            Some(Distinct::Distinct) | None => &[],
+        };
+
+        self.projection(projection);
+        self.table_with_joins(from);
+        self.exprs(
+            distinct_on
+                .iter()
+                .chain(selection)
+                .chain(group_by)
+                .chain(grouping_sets.iter().flat_map(GroupingSets::as_exprs))
+                .chain(having),
+        );
+    }
+
+    fn table_with_joins(&mut self, table_with_joins: &TableWithJoins) {
+        let TableWithJoins { relation, joins } = table_with_joins;
+
+        self.table_factor(relation);
+
+        for Join {
+            relation,
+            join_operator,
+            ..
+        } in joins
+        {
+            self.table_factor(relation);
+
+            match join_operator {
+                JoinOperator::Inner(JoinConstraint::On(expr))
+                | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+                | JoinOperator::RightOuter(JoinConstraint::On(expr))
+                | JoinOperator::FullOuter(JoinConstraint::On(expr)) => self.expr(expr, None),
+                JoinOperator::Inner(JoinConstraint::None)
+                | JoinOperator::LeftOuter(JoinConstraint::None)
+                | JoinOperator::RightOuter(JoinConstraint::None)
+                | JoinOperator::FullOuter(JoinConstraint::None)
+                | JoinOperator::Cross => {}
+            }
+        }
+    }
+
+    fn table_factor(&mut self, table_factor: &TableFactor) {
+        match table_factor {
+            TableFactor::Derived { subquery, .. } => self.query(subquery),
+            TableFactor::Series { size, .. } => self.expr(size, None),
+            TableFactor::Function { args, .. } => self.exprs(args),
+            TableFactor::Table { .. } | TableFactor::Dictionary { .. } => {}
+        }
+    }
+
+    fn projection(&mut self, projection: &[SelectItem]) {
+        for select_item in projection {
+            if let SelectItem::Expr { expr, .. } = select_item {
+                self.expr(expr, None);
+            }
+        }
+    }
+
+    fn exprs<'b>(&mut self, exprs: impl IntoIterator<Item = &'b Expr>) {
+        for expr in exprs {
+            self.expr(expr, None);
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr, data_type: Option<&DataType>) {
+        match expr {
+            Expr::Placeholder(placeholder) => {
+                let Some(index) = placeholder
+                    .strip_prefix('$')
+                    .and_then(|index| index.parse::<usize>().ok())
+                else {
+                    return;
+                };
+
+                if self.types.len() < index {
+                    self.types.resize(index, None);
+                }
+
+                let param_type = &mut self.types[index - 1];
+                if param_type.is_none() {
+                    *param_type = data_type.cloned();
+                }
+            }
+            Expr::Nested(expr) => self.expr(expr, data_type),
+            Expr::BinaryOp { left, right, .. } => {
+                let left_type = self.column_type(right);
+                let right_type = self.column_type(left);
+
+                self.expr(left, left_type);
+                self.expr(right, right_type);
+            }
+            Expr::InList { expr, list, .. } => {
+                let data_type = self.column_type(expr);
+
+                self.expr(expr, None);
+                for item in list {
+                    self.expr(item, data_type);
+                }
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                let data_type = self.column_type(expr);
+
+                self.expr(expr, None);
+                self.expr(low, data_type);
+                self.expr(high, data_type);
+            }
+            _ => match PlanExpr::from(expr) {
+                PlanExpr::None | PlanExpr::Identifier(_) | PlanExpr::CompoundIdentifier { .. } => {}
+                PlanExpr::Expr(expr) => self.expr(expr, None),
+                PlanExpr::TwoExprs(expr, expr2) => self.exprs([expr, expr2]),
+                PlanExpr::ThreeExprs(expr, expr2, expr3) => self.exprs([expr, expr2, expr3]),
+                PlanExpr::MultiExprs(exprs) => self.exprs(exprs),
+                PlanExpr::Query(query) => self.query(query),
+                PlanExpr::QueryAndExpr { query, expr } => {
+                    self.expr(expr, None);
+                    self.query(query);
+                }
+            },
+        }
+    }
+
+    /// Finds the column type of `expr` when it is an identifier which is not ambiguous
+    fn column_type(&self, expr: &Expr) -> Option<&'a DataType> {
+        let ident = match expr {
+            Expr::Identifier(ident) | Expr::CompoundIdentifier { ident, .. } => ident,
+            Expr::Nested(expr) => return self.column_type(expr),
+            _ => return None,
+        };
+
+        let mut data_types = self
+            .schema_map
+            .values()
+            .filter_map(|schema| schema.column_defs.as_ref())
+            .flatten()
+            .filter(|column_def| &column_def.name == ident)
+            .map(|column_def| &column_def.data_type);
+
+        let data_type = data_types.next()?;
+        data_types
+            .all(|other| other == data_type)
+            .then_some(data_type)
+    }
+
+    fn table_column_type(&self, table_name: &str, column: &str) -> Option<&'a DataType> {
+        self.schema_map
+            .get(table_name)?
+            .column_defs
+            .as_ref()?
+            .iter()
+            .find(|column_def| column_def.name == column)
+            .map(|column_def| &column_def.data_type)
+    }
+
+    fn insert_types(&self, table_name: &str, columns: &[String]) -> Vec<Option<&'a DataType>> {
+        let column_defs = self
+            .schema_map
+            .get(table_name)
+            .and_then(|schema| schema.column_defs.as_ref());
+
+        match column_defs {
+            Some(column_defs) if columns.is_empty() => column_defs
+                .iter()
+                .map(|column_def| Some(&column_def.data_type))
+                .collect(),
+            Some(_) => columns
+                .iter()
+                .map(|column| self.table_column_type(table_name, column))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use {
     super::context::Context,
     crate::{
-        ast::{ColumnDef, ColumnUniqueOption, Expr, Function, Query, TableAlias, TableFactor},
+        ast::{
+            ColumnDef, ColumnUniqueOption, Cte, Expr, Function, Query, TableAlias, TableFactor,
+            With,
+        },
         data::Schema,
     },
     std::rc::Rc,
@@ -12,11 +15,33 @@ pub trait Planner<'a> {
 
     fn query(&self, outer_context: Option<Rc<Context<'a>>>, query: Query) -> Query;
 
+    fn with(&self, outer_context: Option<Rc<Context<'a>>>, with: With) -> With {
+        let With {
+            recursive,
+            cte_tables,
+        } = with;
+
+        let cte_tables = cte_tables
+            .into_iter()
+            .map(|Cte { alias, query }| {
+                let query = self.query(outer_context.as_ref().map(Rc::clone), query);
+
+                Cte { alias, query }
+            })
+            .collect();
+
+        With {
+            recursive,
+            cte_tables,
+        }
+    }
+
     fn subquery_expr(&self, outer_context: Option<Rc<Context<'a>>>, expr: Expr) -> Expr {
         match expr {
             Expr::Identifier(_)
             | Expr::CompoundIdentifier { .. }
             | Expr::Literal(_)
+            | Expr::Placeholder(_)
             | Expr::TypedString { .. } => expr,
             Expr::IsNull(expr) => Expr::IsNull(Box::new(self.subquery_expr(outer_context, *expr))),
             Expr::IsNotNull(expr) => {
@@ -173,7 +198,7 @@ pub trait Planner<'a> {
                 })),
                 _ => Expr::Function(func),
             },
-            Expr::Aggregate(_) => expr,
+            Expr::Aggregate(_) | Expr::Window(_) => expr,
         }
     }
 
@@ -190,7 +215,8 @@ pub trait Planner<'a> {
             }
             TableFactor::Derived { .. }
             | TableFactor::Series { .. }
-            | TableFactor::Dictionary { .. } => return next,
+            | TableFactor::Dictionary { .. }
+            | TableFactor::Function { .. } => return next,
         };
 
         let column_defs = match self.get_schema(name) {
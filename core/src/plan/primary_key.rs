@@ -1,9 +1,12 @@
 use {
-    super::{context::Context, evaluable::check_expr as check_evaluable, planner::Planner},
+    super::{
+        context::Context, evaluable::check_expr as check_evaluable, planner::Planner,
+        schema::scope_ctes,
+    },
     crate::{
         ast::{
-            BinaryOperator, Expr, IndexItem, Query, Select, SetExpr, Statement, TableFactor,
-            TableWithJoins,
+            BinaryOperator, ColumnDef, Cte, Expr, IndexItem, IndexOperator, Query, Select, SetExpr,
+            Statement, TableAlias, TableFactor, TableWithJoins, With,
         },
         data::Schema,
     },
@@ -19,6 +22,60 @@ pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> State
 
             Statement::Query(query)
         }
+        Statement::Update {
+            table_name,
+            assignments,
+            from: Some(from),
+            selection,
+            returning,
+        } => {
+            let select = Select {
+                distinct: None,
+                projection: Vec::new(),
+                from,
+                selection,
+                group_by: Vec::new(),
+                grouping_sets: Vec::new(),
+                having: None,
+            };
+            let Select {
+                from, selection, ..
+            } = planner.select(None, select);
+
+            Statement::Update {
+                table_name,
+                assignments,
+                from: Some(from),
+                selection,
+                returning,
+            }
+        }
+        Statement::Delete {
+            table_name,
+            using: Some(using),
+            selection,
+            returning,
+        } => {
+            let select = Select {
+                distinct: None,
+                projection: Vec::new(),
+                from: using,
+                selection,
+                group_by: Vec::new(),
+                grouping_sets: Vec::new(),
+                having: None,
+            };
+            let Select {
+                from, selection, ..
+            } = planner.select(None, select);
+
+            Statement::Delete {
+                table_name,
+                using: Some(from),
+                selection,
+                returning,
+            }
+        }
         _ => statement,
     }
 }
@@ -29,16 +86,39 @@ struct PrimaryKeyPlanner<'a> {
 
 impl<'a> Planner<'a> for PrimaryKeyPlanner<'a> {
     fn query(&self, outer_context: Option<Rc<Context<'a>>>, query: Query) -> Query {
-        let body = match query.body {
-            SetExpr::Select(select) => {
-                let select = self.select(outer_context, *select);
+        let (cte_maps, body_map) = scope_ctes(self.schema_map, query.with.as_ref());
+        let with = query.with.map(
+            |With {
+                 recursive,
+                 cte_tables,
+             }| {
+                let cte_tables = cte_tables
+                    .into_iter()
+                    .zip(&cte_maps)
+                    .map(|(Cte { alias, query }, schema_map)| {
+                        let planner = PrimaryKeyPlanner { schema_map };
+                        let query = planner.query(outer_context.as_ref().map(Rc::clone), query);
 
-                SetExpr::Select(Box::new(select))
-            }
-            SetExpr::Values(_) => query.body,
+                        Cte { alias, query }
+                    })
+                    .collect();
+
+                With {
+                    recursive,
+                    cte_tables,
+                }
+            },
+        );
+        let planner = PrimaryKeyPlanner {
+            schema_map: &body_map,
         };
+        let body = planner.set_expr(outer_context, query.body);
 
-        Query { body, ..query }
+        Query {
+            with,
+            body,
+            ..query
+        }
     }
 
     fn get_schema(&self, name: &str) -> Option<&'a Schema> {
@@ -55,6 +135,33 @@ enum PrimaryKey {
 }
 
 impl<'a> PrimaryKeyPlanner<'a> {
+    fn set_expr(&self, outer_context: Option<Rc<Context<'a>>>, set_expr: SetExpr) -> SetExpr {
+        match set_expr {
+            SetExpr::Select(select) => {
+                let select = self.select(outer_context, *select);
+
+                SetExpr::Select(Box::new(select))
+            }
+            SetExpr::Values(_) => set_expr,
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                let left = self.set_expr(outer_context.as_ref().map(Rc::clone), *left);
+                let right = self.set_expr(outer_context, *right);
+
+                SetExpr::SetOperation {
+                    op,
+                    all,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }
+            }
+        }
+    }
+
     fn select(&self, outer_context: Option<Rc<Context<'a>>>, select: Select) -> Select {
         let current_context = self.update_context(None, &select.from.relation);
         let current_context = select
@@ -65,14 +172,31 @@ impl<'a> PrimaryKeyPlanner<'a> {
                 self.update_context(context, &join.relation)
             });
 
-        let (index, selection) = select
-            .selection
-            .map(|expr| self.expr(outer_context, current_context, expr))
-            .map(|primary_key| match primary_key {
-                PrimaryKey::Found { index_item, expr } => (Some(index_item), expr),
-                PrimaryKey::NotFound(expr) => (None, Some(expr)),
-            })
-            .unwrap_or((None, None));
+        let composite_primary_key = match &select.from.relation {
+            TableFactor::Table {
+                name,
+                alias,
+                index: None,
+            } => self.composite_primary_key(name, alias.as_ref()),
+            _ => None,
+        };
+
+        let (index, selection) = match (composite_primary_key, select.selection) {
+            (Some((alias, primary_key)), Some(expr)) => {
+                let (index, expr) = take_composite_primary_key(&alias, &primary_key, expr);
+                let outer_context = Context::concat(current_context, outer_context);
+                let expr = expr.map(|expr| self.subquery_expr(outer_context, expr));
+
+                (index, expr)
+            }
+            (_, selection) => selection
+                .map(|expr| self.expr(outer_context, current_context, expr))
+                .map(|primary_key| match primary_key {
+                    PrimaryKey::Found { index_item, expr } => (Some(index_item), expr),
+                    PrimaryKey::NotFound(expr) => (None, Some(expr)),
+                })
+                .unwrap_or((None, None)),
+        };
 
         if let TableFactor::Table {
             name,
@@ -98,6 +222,31 @@ impl<'a> PrimaryKeyPlanner<'a> {
         }
     }
 
+    /// Returns the alias and the primary key columns of a table with a composite primary key
+    fn composite_primary_key(
+        &self,
+        name: &str,
+        alias: Option<&TableAlias>,
+    ) -> Option<(String, Vec<&'a str>)> {
+        let schema = self.get_schema(name)?;
+        let column_defs = schema.column_defs.as_ref()?;
+        let primary_key = schema
+            .primary_key_indexes()
+            .filter(|indexes| indexes.len() > 1)?
+            .into_iter()
+            .map(|i| {
+                column_defs
+                    .get(i)
+                    .map(|ColumnDef { name, .. }| name.as_str())
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let alias = alias
+            .map(|TableAlias { name, .. }| name.to_owned())
+            .unwrap_or_else(|| name.to_owned());
+
+        Some((alias, primary_key))
+    }
+
     fn expr(
         &self,
         outer_context: Option<Rc<Context<'a>>>,
@@ -213,15 +362,124 @@ impl<'a> PrimaryKeyPlanner<'a> {
     }
 }
 
+/// Takes the equality conditions on the leading columns of a composite primary key,
+/// and a comparison on the column following them, out of the `AND` chain of `expr`,
+/// returning the planned index and the remaining expr.
+fn take_composite_primary_key(
+    alias: &str,
+    primary_key: &[&str],
+    expr: Expr,
+) -> (Option<IndexItem>, Option<Expr>) {
+    fn flatten(expr: Expr, conjuncts: &mut Vec<Expr>) {
+        match expr {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => {
+                flatten(*left, conjuncts);
+                flatten(*right, conjuncts);
+            }
+            _ => conjuncts.push(expr),
+        }
+    }
+
+    let is_key = |column: &str, key: &Expr| match key {
+        Expr::Identifier(ident) => ident == column,
+        Expr::CompoundIdentifier {
+            alias: key_alias,
+            ident,
+        } => key_alias == alias && ident == column,
+        _ => false,
+    };
+    let key_value = |column: &str, expr: &Expr| match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        } => [(left, right), (right, left)]
+            .into_iter()
+            .find(|(key, value)| is_key(column, key) && check_evaluable(None, value))
+            .map(|(_, value)| value.as_ref().clone()),
+        _ => None,
+    };
+    let key_cmp = |column: &str, expr: &Expr| {
+        let (left, op, right) = match expr {
+            Expr::BinaryOp { left, op, right } => (left, op, right),
+            _ => return None,
+        };
+        let op = match op {
+            BinaryOperator::Gt => IndexOperator::Gt,
+            BinaryOperator::Lt => IndexOperator::Lt,
+            BinaryOperator::GtEq => IndexOperator::GtEq,
+            BinaryOperator::LtEq => IndexOperator::LtEq,
+            _ => return None,
+        };
+
+        if is_key(column, left) && check_evaluable(None, right) {
+            Some((op, right.as_ref().clone()))
+        } else if is_key(column, right) && check_evaluable(None, left) {
+            Some((op.reverse(), left.as_ref().clone()))
+        } else {
+            None
+        }
+    };
+
+    let mut conjuncts = Vec::new();
+    flatten(expr, &mut conjuncts);
+
+    let mut exprs = Vec::new();
+    for column in primary_key {
+        let found = conjuncts
+            .iter()
+            .enumerate()
+            .find_map(|(i, conjunct)| key_value(column, conjunct).map(|value| (i, value)));
+
+        match found {
+            Some((i, value)) => {
+                conjuncts.remove(i);
+                exprs.push(value);
+            }
+            None => break,
+        }
+    }
+
+    let cmp_expr = primary_key.get(exprs.len()).and_then(|column| {
+        conjuncts
+            .iter()
+            .enumerate()
+            .find_map(|(i, conjunct)| key_cmp(column, conjunct).map(|cmp| (i, cmp)))
+            .map(|(i, cmp)| {
+                conjuncts.remove(i);
+
+                cmp
+            })
+    });
+
+    let index =
+        (!exprs.is_empty() || cmp_expr.is_some()).then_some(IndexItem::CompositePrimaryKey {
+            full_key: exprs.len() == primary_key.len(),
+            exprs,
+            cmp_expr,
+        });
+    let expr = conjuncts.into_iter().reduce(|left, right| Expr::BinaryOp {
+        left: Box::new(left),
+        op: BinaryOperator::And,
+        right: Box::new(right),
+    });
+
+    (index, expr)
+}
+
 #[cfg(test)]
 mod tests {
     use {
         super::plan as plan_primary_key,
         crate::{
             ast::{
-                AstLiteral, BinaryOperator, Expr, IndexItem, Join, JoinConstraint, JoinExecutor,
-                JoinOperator, Query, Select, SelectItem, SetExpr, Statement, TableFactor,
-                TableWithJoins, Values,
+                Assignment, AstLiteral, BinaryOperator, Expr, IndexItem, IndexOperator, Join,
+                JoinConstraint, JoinExecutor, JoinOperator, Query, Select, SelectItem, SetExpr,
+                Statement, TableFactor, TableWithJoins, Values,
             },
             mock::{run, MockStorage},
             parse_sql::{parse, parse_expr},
@@ -241,6 +499,7 @@ mod tests {
 
     fn select(select: Select) -> Statement {
         Statement::Query(Query {
+            with: None,
             body: SetExpr::Select(Box::new(select)),
             limit: None,
             offset: None,
@@ -266,6 +525,7 @@ mod tests {
         let sql = "SELECT * FROM Player WHERE id = 1;";
         let actual = plan(&storage, sql);
         let expected = select(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -277,6 +537,7 @@ mod tests {
             },
             selection: None,
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         });
         assert_eq!(actual, expected, "primary key in lhs:\n{sql}");
@@ -284,6 +545,7 @@ mod tests {
         let sql = "SELECT * FROM Player WHERE 1 = id;";
         let actual = plan(&storage, sql);
         let expected = select(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -295,6 +557,7 @@ mod tests {
             },
             selection: None,
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         });
         assert_eq!(actual, expected, "primary key in rhs:\n{sql}");
@@ -302,6 +565,7 @@ mod tests {
         let sql = "SELECT * FROM Player WHERE id = 1 AND True;";
         let actual = plan(&storage, sql);
         let expected = select(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -313,6 +577,7 @@ mod tests {
             },
             selection: Some(expr("True")),
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         });
         assert_eq!(actual, expected, "AND binary op:\n{sql}");
@@ -326,6 +591,7 @@ mod tests {
         ";
         let actual = plan(&storage, sql);
         let expected = select(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -337,6 +603,7 @@ mod tests {
             },
             selection: Some(expr("name IS NOT NULL AND True")),
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         });
         assert_eq!(actual, expected, "AND binary op 2:\n{sql}");
@@ -359,6 +626,7 @@ mod tests {
         ";
         let actual = plan(&storage, sql);
         let expected = select(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -370,6 +638,7 @@ mod tests {
             },
             selection: Some(expr("name IS NOT NULL AND (True)")),
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         });
         assert_eq!(actual, expected, "AND binary op 3:\n{sql}");
@@ -391,6 +660,7 @@ mod tests {
         let sql = "SELECT * FROM Player JOIN Badge WHERE Player.id = 1";
         let actual = plan(&storage, sql);
         let expected = select(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -410,6 +680,7 @@ mod tests {
             },
             selection: None,
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         });
         assert_eq!(actual, expected, "basic inner join:\n{sql}");
@@ -417,6 +688,7 @@ mod tests {
         let sql = "SELECT * FROM Player JOIN Badge WHERE Player.id = Badge.user_id";
         let actual = plan(&storage, sql);
         let expected = select(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -436,6 +708,7 @@ mod tests {
             },
             selection: Some(expr("Player.id = Badge.user_id")),
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         });
         assert_eq!(actual, expected, "join but no primary key:\n{sql}");
@@ -448,7 +721,9 @@ mod tests {
         let actual = plan(&storage, sql);
         let expected = {
             let subquery = Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
+                    distinct: None,
                     projection: vec![SelectItem::Wildcard],
                     from: TableWithJoins {
                         relation: TableFactor::Table {
@@ -460,6 +735,7 @@ mod tests {
                     },
                     selection: None,
                     group_by: Vec::new(),
+                    grouping_sets: Vec::new(),
                     having: None,
                 })),
                 limit: None,
@@ -468,6 +744,7 @@ mod tests {
             };
 
             select(Select {
+                distinct: None,
                 projection: vec![SelectItem::Wildcard],
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -483,10 +760,43 @@ mod tests {
                     negated: false,
                 }),
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             })
         };
         assert_eq!(actual, expected, "nested select:\n{sql}");
+
+        let sql = "
+            UPDATE Player SET name = Badge.title
+            FROM Badge
+            WHERE Player.id = 1 AND Player.id = Badge.user_id";
+        let actual = plan(&storage, sql);
+        let expected = Statement::Update {
+            table_name: "Player".to_owned(),
+            assignments: vec![Assignment {
+                id: "name".to_owned(),
+                value: expr("Badge.title"),
+            }],
+            from: Some(TableWithJoins {
+                relation: TableFactor::Table {
+                    name: "Player".to_owned(),
+                    alias: None,
+                    index: Some(IndexItem::PrimaryKey(expr("1"))),
+                },
+                joins: vec![Join {
+                    relation: TableFactor::Table {
+                        name: "Badge".to_owned(),
+                        alias: None,
+                        index: None,
+                    },
+                    join_operator: JoinOperator::Cross,
+                    join_executor: JoinExecutor::NestedLoop,
+                }],
+            }),
+            selection: Some(expr("Player.id = Badge.user_id")),
+            returning: Vec::new(),
+        };
+        assert_eq!(actual, expected, "update from:\n{sql}");
     }
 
     #[test]
@@ -502,7 +812,9 @@ mod tests {
         let actual = plan(&storage, sql);
         let expected = {
             let subquery = Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
+                    distinct: None,
                     projection: vec![SelectItem::Expr {
                         expr: Expr::Identifier("name".to_owned()),
                         label: "name".to_owned(),
@@ -517,6 +829,7 @@ mod tests {
                     },
                     selection: None,
                     group_by: Vec::new(),
+                    grouping_sets: Vec::new(),
                     having: None,
                 })),
                 limit: Some(expr("1")),
@@ -525,6 +838,7 @@ mod tests {
             };
 
             select(Select {
+                distinct: None,
                 projection: vec![SelectItem::Wildcard],
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -540,6 +854,7 @@ mod tests {
                     right: Box::new(Expr::Subquery(Box::new(subquery))),
                 }),
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             })
         };
@@ -553,7 +868,9 @@ mod tests {
         let actual = plan(&storage, sql);
         let expected = {
             let subquery = Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
+                    distinct: None,
                     projection: vec![SelectItem::Expr {
                         expr: Expr::Identifier("id".to_owned()),
                         label: "id".to_owned(),
@@ -568,6 +885,7 @@ mod tests {
                     },
                     selection: Some(expr("id = id")),
                     group_by: Vec::new(),
+                    grouping_sets: Vec::new(),
                     having: None,
                 })),
                 limit: None,
@@ -576,6 +894,7 @@ mod tests {
             };
 
             select(Select {
+                distinct: None,
                 projection: vec![SelectItem::Wildcard],
                 from: TableWithJoins {
                     relation: TableFactor::Table {
@@ -591,6 +910,7 @@ mod tests {
                     negated: false,
                 }),
                 group_by: Vec::new(),
+                grouping_sets: Vec::new(),
                 having: None,
             })
         };
@@ -600,17 +920,20 @@ mod tests {
         let actual = plan(&storage, sql);
         let expected = Statement::Delete {
             table_name: "Player".to_owned(),
+            using: None,
             selection: Some(Expr::BinaryOp {
                 left: Box::new(Expr::Identifier("id".to_owned())),
                 op: BinaryOperator::Eq,
                 right: Box::new(Expr::Literal(AstLiteral::Number(1.into()))),
             }),
+            returning: Vec::new(),
         };
         assert_eq!(actual, expected, "delete statement:\n{sql}");
 
         let sql = "VALUES (1), (2);";
         let actual = plan(&storage, sql);
         let expected = Statement::Query(Query {
+            with: None,
             body: SetExpr::Values(Values(vec![
                 vec![Expr::Literal(AstLiteral::Number(1.into()))],
                 vec![Expr::Literal(AstLiteral::Number(2.into()))],
@@ -624,6 +947,7 @@ mod tests {
         let sql = "SELECT * FROM Player WHERE (name);";
         let actual = plan(&storage, sql);
         let expected = select(Select {
+            distinct: None,
             projection: vec![SelectItem::Wildcard],
             from: TableWithJoins {
                 relation: TableFactor::Table {
@@ -635,8 +959,98 @@ mod tests {
             },
             selection: Some(Expr::Nested(Box::new(expr("name")))),
             group_by: Vec::new(),
+            grouping_sets: Vec::new(),
             having: None,
         });
         assert_eq!(actual, expected, "nested:\n{sql}");
     }
+
+    #[test]
+    fn composite_primary_key() {
+        let storage = run("
+            CREATE TABLE Orders (
+                tenant_id INTEGER,
+                order_id INTEGER,
+                name TEXT,
+                PRIMARY KEY (tenant_id, order_id)
+            );
+        ");
+
+        let plan_select = |index, selection| {
+            select(Select {
+                distinct: None,
+                projection: vec![SelectItem::Wildcard],
+                from: TableWithJoins {
+                    relation: TableFactor::Table {
+                        name: "Orders".to_owned(),
+                        alias: None,
+                        index,
+                    },
+                    joins: Vec::new(),
+                },
+                selection,
+                group_by: Vec::new(),
+                grouping_sets: Vec::new(),
+                having: None,
+            })
+        };
+
+        let sql = "SELECT * FROM Orders WHERE order_id = 2 AND name = 'a' AND 1 = tenant_id;";
+        let actual = plan(&storage, sql);
+        let expected = plan_select(
+            Some(IndexItem::CompositePrimaryKey {
+                exprs: vec![expr("1"), expr("2")],
+                full_key: true,
+                cmp_expr: None,
+            }),
+            Some(expr("name = 'a'")),
+        );
+        assert_eq!(actual, expected, "full key:\n{sql}");
+
+        let sql = "SELECT * FROM Orders WHERE Orders.tenant_id = 1;";
+        let actual = plan(&storage, sql);
+        let expected = plan_select(
+            Some(IndexItem::CompositePrimaryKey {
+                exprs: vec![expr("1")],
+                full_key: false,
+                cmp_expr: None,
+            }),
+            None,
+        );
+        assert_eq!(actual, expected, "key prefix:\n{sql}");
+
+        let sql = "SELECT * FROM Orders WHERE tenant_id = 1 AND order_id > 5;";
+        let actual = plan(&storage, sql);
+        let expected = plan_select(
+            Some(IndexItem::CompositePrimaryKey {
+                exprs: vec![expr("1")],
+                full_key: false,
+                cmp_expr: Some((IndexOperator::Gt, expr("5"))),
+            }),
+            None,
+        );
+        assert_eq!(actual, expected, "key prefix and range:\n{sql}");
+
+        let sql = "SELECT * FROM Orders WHERE 3 >= tenant_id AND name = 'a';";
+        let actual = plan(&storage, sql);
+        let expected = plan_select(
+            Some(IndexItem::CompositePrimaryKey {
+                exprs: Vec::new(),
+                full_key: false,
+                cmp_expr: Some((IndexOperator::LtEq, expr("3"))),
+            }),
+            Some(expr("name = 'a'")),
+        );
+        assert_eq!(actual, expected, "range on leading key column:\n{sql}");
+
+        let sql = "SELECT * FROM Orders WHERE order_id = 2;";
+        let actual = plan(&storage, sql);
+        let expected = plan_select(None, Some(expr("order_id = 2")));
+        assert_eq!(actual, expected, "no leading key column:\n{sql}");
+
+        let sql = "SELECT * FROM Orders WHERE tenant_id = order_id;";
+        let actual = plan(&storage, sql);
+        let expected = plan_select(None, Some(expr("tenant_id = order_id")));
+        assert_eq!(actual, expected, "non-evaluable key value:\n{sql}");
+    }
 }
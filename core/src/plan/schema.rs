@@ -1,19 +1,379 @@
 use {
-    super::expr::PlanExpr,
+    super::{context::Context, expr::PlanExpr, planner::Planner},
     crate::{
         ast::{
-            Expr, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem, SetExpr,
-            Statement, TableFactor, TableWithJoins,
+            Cte, Distinct, Expr, GroupingSets, Join, JoinConstraint, JoinOperator, Query, Select,
+            SelectItem, SetExpr, Statement, TableAlias, TableFactor, TableWithJoins, With,
         },
-        data::Schema,
+        data::{Schema, View},
         result::Result,
-        store::Store,
+        store::{Store, View as ViewStore},
     },
     async_recursion::async_recursion,
     futures::stream::{self, StreamExt, TryStreamExt},
-    std::collections::HashMap,
+    std::{
+        borrow::Cow,
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+        rc::Rc,
+    },
 };
 
+/// Replaces references to views by their queries, as derived tables aliased by the view name.
+/// Only names which are not tables are looked up as views, the names read by the views found
+/// are looked up as well, then the statement is expanded once.
+pub async fn expand_views<T: Store + ViewStore>(
+    storage: &T,
+    statement: Statement,
+) -> Result<Statement> {
+    if !matches!(
+        statement,
+        Statement::Query(_)
+            | Statement::Insert { .. }
+            | Statement::CreateTable {
+                source: Some(_),
+                ..
+            }
+            | Statement::Update { .. }
+            | Statement::Delete { .. }
+            | Statement::Explain { .. }
+    ) {
+        return Ok(statement);
+    }
+
+    let read_tables = RefCell::new(HashSet::new());
+    let planner = ViewPlanner {
+        view_map: &HashMap::new(),
+        expanding: Vec::new(),
+        read_tables: &read_tables,
+    };
+    let statement = planner.statement(statement);
+
+    let mut view_map = HashMap::new();
+    let mut looked_up = HashSet::new();
+    let mut names = read_tables.into_inner().into_iter().collect::<Vec<_>>();
+
+    while let Some(name) = names.pop() {
+        if !looked_up.insert(name.clone()) || storage.fetch_schema(&name).await?.is_some() {
+            continue;
+        }
+
+        if let Some(view) = storage.fetch_view(&name).await? {
+            names.extend(read_table_names(&view.query));
+            view_map.insert(name, view);
+        }
+    }
+
+    if view_map.is_empty() {
+        return Ok(statement);
+    }
+
+    let read_tables = RefCell::new(HashSet::new());
+    let planner = ViewPlanner {
+        view_map: &view_map,
+        expanding: Vec::new(),
+        read_tables: &read_tables,
+    };
+
+    Ok(planner.statement(statement))
+}
+
+/// Names which `query` reads as tables or views, leaving out the CTEs it defines.
+pub fn read_table_names(query: &Query) -> HashSet<String> {
+    let view_map = HashMap::new();
+    let read_tables = RefCell::new(HashSet::new());
+    let planner = ViewPlanner {
+        view_map: &view_map,
+        expanding: Vec::new(),
+        read_tables: &read_tables,
+    };
+
+    planner.query(None, query.clone());
+
+    read_tables.into_inner()
+}
+
+struct ViewPlanner<'a> {
+    view_map: &'a HashMap<String, View>,
+    /// Views being expanded, which are left as they are when referenced by themselves
+    expanding: Vec<&'a str>,
+    /// Names read as tables, which are neither expanded views nor CTEs
+    read_tables: &'a RefCell<HashSet<String>>,
+}
+
+impl<'a> Planner<'a> for ViewPlanner<'a> {
+    fn query(&self, outer_context: Option<Rc<Context<'a>>>, query: Query) -> Query {
+        let Query {
+            with,
+            body,
+            order_by,
+            limit,
+            offset,
+        } = query;
+
+        // CTEs shadow views of the same name, so they are tracked in the context.
+        let with = with.map(|with| self.with(outer_context.as_ref().map(Rc::clone), with));
+        let outer_context = with
+            .iter()
+            .flat_map(|With { cte_tables, .. }| cte_tables)
+            .fold(outer_context, |context, Cte { alias, .. }| {
+                let context = Context::new(alias.name.clone(), Vec::new(), None, context);
+
+                Some(Rc::new(context))
+            });
+        let body = self.set_expr(outer_context, body);
+
+        Query {
+            with,
+            body,
+            order_by,
+            limit,
+            offset,
+        }
+    }
+
+    fn get_schema(&self, _: &str) -> Option<&'a Schema> {
+        None
+    }
+}
+
+impl<'a> ViewPlanner<'a> {
+    fn statement(&self, statement: Statement) -> Statement {
+        match statement {
+            Statement::Query(query) => Statement::Query(self.query(None, query)),
+            Statement::Insert {
+                table_name,
+                columns,
+                source,
+                on_conflict,
+                returning,
+            } => Statement::Insert {
+                table_name,
+                columns,
+                source: self.query(None, source),
+                on_conflict,
+                returning,
+            },
+            Statement::CreateTable {
+                if_not_exists,
+                name,
+                columns,
+                source,
+                engine,
+                foreign_keys,
+                checks,
+                unique_keys,
+            } => Statement::CreateTable {
+                if_not_exists,
+                name,
+                columns,
+                source: source.map(|source| Box::new(self.query(None, *source))),
+                engine,
+                foreign_keys,
+                checks,
+                unique_keys,
+            },
+            Statement::Update {
+                table_name,
+                assignments,
+                from,
+                selection,
+                returning,
+            } => Statement::Update {
+                table_name,
+                assignments,
+                from: from.map(|from| self.table_with_joins(None, from)),
+                selection: selection.map(|expr| self.subquery_expr(None, expr)),
+                returning,
+            },
+            Statement::Delete {
+                table_name,
+                using,
+                selection,
+                returning,
+            } => Statement::Delete {
+                table_name,
+                using: using.map(|using| self.table_with_joins(None, using)),
+                selection: selection.map(|expr| self.subquery_expr(None, expr)),
+                returning,
+            },
+            Statement::Explain { statement, analyze } => Statement::Explain {
+                statement: Box::new(self.statement(*statement)),
+                analyze,
+            },
+            _ => statement,
+        }
+    }
+
+    fn set_expr(&self, outer_context: Option<Rc<Context<'a>>>, set_expr: SetExpr) -> SetExpr {
+        match set_expr {
+            SetExpr::Select(select) => {
+                let select = self.select(outer_context, *select);
+
+                SetExpr::Select(Box::new(select))
+            }
+            SetExpr::Values(_) => set_expr,
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                let left = self.set_expr(outer_context.as_ref().map(Rc::clone), *left);
+                let right = self.set_expr(outer_context, *right);
+
+                SetExpr::SetOperation {
+                    op,
+                    all,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }
+            }
+        }
+    }
+
+    fn select(&self, outer_context: Option<Rc<Context<'a>>>, select: Select) -> Select {
+        let Select {
+            distinct,
+            projection,
+            from,
+            selection,
+            group_by,
+            grouping_sets,
+            having,
+        } = select;
+
+        let subquery_expr = |expr| self.subquery_expr(outer_context.as_ref().map(Rc::clone), expr);
+        let projection = projection
+            .into_iter()
+            .map(|select_item| match select_item {
+                SelectItem::Expr { expr, label } => SelectItem::Expr {
+                    expr: subquery_expr(expr),
+                    label,
+                },
+                SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => select_item,
+            })
+            .collect();
+        let from = self.table_with_joins(outer_context.as_ref().map(Rc::clone), from);
+        let selection = selection.map(subquery_expr);
+        let group_by = group_by.into_iter().map(subquery_expr).collect();
+        let having = having.map(subquery_expr);
+
+        Select {
+            distinct,
+            projection,
+            from,
+            selection,
+            group_by,
+            grouping_sets,
+            having,
+        }
+    }
+
+    fn table_with_joins(
+        &self,
+        outer_context: Option<Rc<Context<'a>>>,
+        table_with_joins: TableWithJoins,
+    ) -> TableWithJoins {
+        let TableWithJoins { relation, joins } = table_with_joins;
+
+        let relation = self.table_factor(outer_context.as_ref().map(Rc::clone), relation);
+        let joins = joins
+            .into_iter()
+            .map(|join| {
+                let Join {
+                    relation,
+                    join_operator,
+                    join_executor,
+                } = join;
+
+                let relation = self.table_factor(outer_context.as_ref().map(Rc::clone), relation);
+                let on = |expr| self.subquery_expr(outer_context.as_ref().map(Rc::clone), expr);
+                let join_operator = match join_operator {
+                    JoinOperator::Inner(JoinConstraint::On(expr)) => {
+                        JoinOperator::Inner(JoinConstraint::On(on(expr)))
+                    }
+                    JoinOperator::LeftOuter(JoinConstraint::On(expr)) => {
+                        JoinOperator::LeftOuter(JoinConstraint::On(on(expr)))
+                    }
+                    JoinOperator::RightOuter(JoinConstraint::On(expr)) => {
+                        JoinOperator::RightOuter(JoinConstraint::On(on(expr)))
+                    }
+                    JoinOperator::FullOuter(JoinConstraint::On(expr)) => {
+                        JoinOperator::FullOuter(JoinConstraint::On(on(expr)))
+                    }
+                    _ => join_operator,
+                };
+
+                Join {
+                    relation,
+                    join_operator,
+                    join_executor,
+                }
+            })
+            .collect();
+
+        TableWithJoins { relation, joins }
+    }
+
+    fn table_factor(
+        &self,
+        outer_context: Option<Rc<Context<'a>>>,
+        table_factor: TableFactor,
+    ) -> TableFactor {
+        match table_factor {
+            TableFactor::Table { name, alias, index } => {
+                let shadowed = outer_context
+                    .as_ref()
+                    .map(|context| context.contains_alias(&name))
+                    .unwrap_or(false);
+                let view = match self.view_map.get(&name) {
+                    _ if shadowed => return TableFactor::Table { name, alias, index },
+                    Some(view) if !self.expanding.contains(&view.view_name.as_str()) => view,
+                    _ => {
+                        self.read_tables.borrow_mut().insert(name.clone());
+
+                        return TableFactor::Table { name, alias, index };
+                    }
+                };
+
+                let planner = ViewPlanner {
+                    view_map: self.view_map,
+                    expanding: self
+                        .expanding
+                        .iter()
+                        .copied()
+                        .chain([view.view_name.as_str()])
+                        .collect(),
+                    read_tables: self.read_tables,
+                };
+                let subquery = planner.query(None, view.query.clone());
+                let alias = match alias {
+                    Some(TableAlias { name, columns }) if columns.is_empty() => TableAlias {
+                        name,
+                        columns: view.columns.clone(),
+                    },
+                    Some(alias) => alias,
+                    None => TableAlias {
+                        name,
+                        columns: view.columns.clone(),
+                    },
+                };
+
+                TableFactor::Derived { subquery, alias }
+            }
+            TableFactor::Derived { subquery, alias } => TableFactor::Derived {
+                subquery: self.query(outer_context, subquery),
+                alias,
+            },
+            TableFactor::Series { .. }
+            | TableFactor::Dictionary { .. }
+            | TableFactor::Function { .. } => table_factor,
+        }
+    }
+}
+
+#[async_recursion(?Send)]
 pub async fn fetch_schema_map<T: Store>(
     storage: &T,
     statement: &Statement,
@@ -47,6 +407,27 @@ pub async fn fetch_schema_map<T: Store>(
 
             Ok(schema_list)
         }
+        Statement::Update {
+            from: Some(from),
+            selection,
+            ..
+        }
+        | Statement::Delete {
+            using: Some(from),
+            selection,
+            ..
+        } => {
+            let schema_list = scan_table_with_joins(storage, from).await?;
+            let schema_list = match selection {
+                Some(expr) => schema_list
+                    .into_iter()
+                    .chain(scan_expr(storage, expr).await?)
+                    .collect(),
+                None => schema_list,
+            };
+
+            Ok(schema_list)
+        }
         Statement::DropTable { names, .. } => {
             stream::iter(names)
                 .filter_map(|table_name| async {
@@ -59,23 +440,72 @@ pub async fn fetch_schema_map<T: Store>(
                 .try_collect()
                 .await
         }
+        Statement::Explain { statement, .. } => fetch_schema_map(storage, statement).await,
         _ => Ok(HashMap::new()),
     }
 }
 
+pub type ScopedSchemaMap<'a> = Cow<'a, HashMap<String, Schema>>;
+
+/// Leaves the tables shadowed by CTEs of the same name out of the schema map, for the body of
+/// each CTE and for the main body of a query. A CTE body sees the CTEs defined before it,
+/// and itself as well under `RECURSIVE`, while the main body sees all of them.
+pub fn scope_ctes<'a>(
+    schema_map: &'a HashMap<String, Schema>,
+    with: Option<&With>,
+) -> (Vec<ScopedSchemaMap<'a>>, ScopedSchemaMap<'a>) {
+    let (recursive, _, names) = cte_names(with);
+    let shadow = |names: &[&str]| {
+        if !names.iter().any(|name| schema_map.contains_key(*name)) {
+            return Cow::Borrowed(schema_map);
+        }
+
+        let schema_map = schema_map
+            .iter()
+            .filter(|(name, _)| !names.contains(&name.as_str()))
+            .map(|(name, schema)| (name.clone(), schema.clone()))
+            .collect();
+
+        Cow::Owned(schema_map)
+    };
+
+    let cte_maps = (0..names.len())
+        .map(|i| shadow(&names[..i + usize::from(recursive)]))
+        .collect();
+
+    (cte_maps, shadow(&names))
+}
+
+/// Returns whether `RECURSIVE` is used, the CTEs and their names
+fn cte_names(with: Option<&With>) -> (bool, &[Cte], Vec<&str>) {
+    let (recursive, cte_tables) = match with {
+        Some(With {
+            recursive,
+            cte_tables,
+        }) => (*recursive, cte_tables.as_slice()),
+        None => (false, [].as_slice()),
+    };
+    let names = cte_tables
+        .iter()
+        .map(|Cte { alias, .. }| alias.name.as_str())
+        .collect();
+
+    (recursive, cte_tables, names)
+}
+
+#[async_recursion(?Send)]
 async fn scan_query<T: Store>(storage: &T, query: &Query) -> Result<HashMap<String, Schema>> {
     let Query {
+        with,
         body,
         limit,
         offset,
         ..
     } = query;
 
-    let schema_list = match body {
-        SetExpr::Select(select) => scan_select(storage, select).await?,
-        SetExpr::Values(_) => HashMap::new(),
-    };
+    let (recursive, cte_tables, names) = cte_names(with.as_ref());
 
+    let schema_list = scan_set_expr(storage, body).await?;
     let schema_list = match (limit, offset) {
         (Some(limit), Some(offset)) => schema_list
             .into_iter()
@@ -88,16 +518,50 @@ async fn scan_query<T: Store>(storage: &T, query: &Query) -> Result<HashMap<Stri
             .collect(),
         (None, None) => schema_list,
     };
+    let mut schema_list = schema_list
+        .into_iter()
+        .filter(|(name, _)| !names.contains(&name.as_str()))
+        .collect::<HashMap<_, _>>();
+
+    // A CTE body reads the tables shadowed by the CTEs defined after it, and by itself
+    // unless `RECURSIVE` is used.
+    for (i, Cte { query, .. }) in cte_tables.iter().enumerate() {
+        let shadowed = &names[..i + usize::from(recursive)];
+        let cte_schema_list = scan_query(storage, query)
+            .await?
+            .into_iter()
+            .filter(|(name, _)| !shadowed.contains(&name.as_str()));
+
+        schema_list.extend(cte_schema_list);
+    }
 
     Ok(schema_list)
 }
 
+#[async_recursion(?Send)]
+async fn scan_set_expr<T: Store>(
+    storage: &T,
+    set_expr: &SetExpr,
+) -> Result<HashMap<String, Schema>> {
+    match set_expr {
+        SetExpr::Select(select) => scan_select(storage, select).await,
+        SetExpr::Values(_) => Ok(HashMap::new()),
+        SetExpr::SetOperation { left, right, .. } => Ok(scan_set_expr(storage, left)
+            .await?
+            .into_iter()
+            .chain(scan_set_expr(storage, right).await?)
+            .collect()),
+    }
+}
+
 async fn scan_select<T: Store>(storage: &T, select: &Select) -> Result<HashMap<String, Schema>> {
     let Select {
+        distinct,
         projection,
         from,
         selection,
         group_by,
+        grouping_sets,
         having,
     } = select;
 
@@ -115,7 +579,16 @@ async fn scan_select<T: Store>(storage: &T, select: &Select) -> Result<HashMap<S
 
     let from = scan_table_with_joins(storage, from).await?;
 
-    let exprs = selection.iter().chain(group_by.iter()).chain(having.iter());
+    let distinct_on = match distinct {
+        Some(Distinct::On(exprs)) => exprs.as_slice(),
+        Some(Distinct::Distinct) | None => &[],
+    };
+    let exprs = distinct_on
+        .iter()
+        .chain(selection.iter())
+        .chain(group_by.iter())
+        .chain(grouping_sets.iter().flat_map(GroupingSets::as_exprs))
+        .chain(having.iter());
 
     Ok(stream::iter(exprs)
         .then(|expr| scan_expr(storage, expr))
@@ -155,13 +628,18 @@ async fn scan_join<T: Store>(storage: &T, join: &Join) -> Result<HashMap<String,
     let schema_list = scan_table_factor(storage, relation).await?;
     let schema_list = match join_operator {
         JoinOperator::Inner(JoinConstraint::On(expr))
-        | JoinOperator::LeftOuter(JoinConstraint::On(expr)) => scan_expr(storage, expr)
+        | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+        | JoinOperator::RightOuter(JoinConstraint::On(expr))
+        | JoinOperator::FullOuter(JoinConstraint::On(expr)) => scan_expr(storage, expr)
             .await?
             .into_iter()
             .chain(schema_list)
             .collect(),
         JoinOperator::Inner(JoinConstraint::None)
-        | JoinOperator::LeftOuter(JoinConstraint::None) => schema_list,
+        | JoinOperator::LeftOuter(JoinConstraint::None)
+        | JoinOperator::RightOuter(JoinConstraint::None)
+        | JoinOperator::FullOuter(JoinConstraint::None)
+        | JoinOperator::Cross => schema_list,
     };
 
     Ok(schema_list)
@@ -182,7 +660,9 @@ async fn scan_table_factor<T: Store>(
             Ok(schema_list)
         }
         TableFactor::Derived { subquery, .. } => scan_query(storage, subquery).await,
-        TableFactor::Series { .. } | TableFactor::Dictionary { .. } => Ok(HashMap::new()),
+        TableFactor::Series { .. }
+        | TableFactor::Dictionary { .. }
+        | TableFactor::Function { .. } => Ok(HashMap::new()),
     }
 }
 
@@ -382,6 +862,52 @@ mod tests {
         test("SELECT * FROM Foo WHERE Foo.id = Lab.foo_id", &["Foo"]);
     }
 
+    #[test]
+    fn cte() {
+        let storage = run("
+            CREATE TABLE Foo (id INTEGER);
+            CREATE TABLE Bar (name TEXT);
+        ");
+        let test = |sql, expected| run_test(&storage, sql, expected);
+
+        test(
+            "WITH Baz AS (SELECT * FROM Foo) SELECT * FROM Baz",
+            &["Foo"],
+        );
+        test(
+            "WITH Baz AS (SELECT * FROM Foo) SELECT * FROM Baz JOIN Bar",
+            &["Bar", "Foo"],
+        );
+
+        // CTE shadows the table of the same name
+        test(
+            "WITH Bar AS (SELECT * FROM Foo) SELECT * FROM Bar",
+            &["Foo"],
+        );
+        test(
+            "SELECT * FROM Foo WHERE id IN (WITH Bar AS (VALUES (1)) SELECT * FROM Bar)",
+            &["Foo"],
+        );
+
+        // CTE body reads the table it shadows, unless RECURSIVE is used
+        test(
+            "WITH Bar AS (SELECT * FROM Bar) SELECT * FROM Bar",
+            &["Bar"],
+        );
+        test(
+            "WITH Baz AS (SELECT * FROM Foo), Foo AS (SELECT * FROM Baz) SELECT * FROM Foo",
+            &["Foo"],
+        );
+        test(
+            "WITH Foo AS (VALUES (1)), Baz AS (SELECT * FROM Foo) SELECT * FROM Baz",
+            &[],
+        );
+        test(
+            "WITH RECURSIVE Bar AS (SELECT * FROM Bar) SELECT * FROM Bar",
+            &[],
+        );
+    }
+
     #[test]
     fn storage_err() {
         let storage = run("
@@ -1,15 +1,19 @@
 use {
-    super::PlanError,
+    super::{schema::scope_ctes, PlanError},
     crate::{
-        ast::{Expr, Join, Query, SelectItem, SetExpr, Statement, TableFactor, TableWithJoins},
-        data::Schema,
+        ast::{
+            Cte, DataType, Expr, Join, Query, Select, SelectItem, SetExpr, Statement, TableAlias,
+            TableFactor, TableWithJoins, Values, With,
+        },
+        data::{get_alias, Schema},
         result::Result,
     },
-    std::{collections::HashMap, rc::Rc},
+    std::{collections::HashMap, iter::once, rc::Rc},
 };
 
 type SchemaMap = HashMap<String, Schema>;
 /// Validate user select column should not be ambiguous
+/// and operands of set operations should have compatible columns
 pub fn validate(schema_map: &SchemaMap, statement: &Statement) -> Result<()> {
     let query = match statement {
         Statement::Query(query) => Some(query),
@@ -19,26 +23,260 @@ pub fn validate(schema_map: &SchemaMap, statement: &Statement) -> Result<()> {
     };
 
     if let Some(query) = query {
-        if let Query {
-            body: SetExpr::Select(select),
-            ..
-        } = query
-        {
+        validate_query(schema_map, query)?;
+    }
+
+    Ok(())
+}
+
+fn validate_query(schema_map: &SchemaMap, query: &Query) -> Result<()> {
+    let Query { with, body, .. } = query;
+    let (cte_maps, body_map) = scope_ctes(schema_map, with.as_ref());
+
+    with.iter()
+        .flat_map(|With { cte_tables, .. }| cte_tables)
+        .zip(&cte_maps)
+        .try_for_each(|(Cte { query, .. }, schema_map)| validate_query(schema_map, query))?;
+
+    validate_set_expr(&body_map, body)
+}
+
+fn validate_set_expr(schema_map: &SchemaMap, set_expr: &SetExpr) -> Result<()> {
+    match set_expr {
+        SetExpr::Select(select) => {
             for select_item in &select.projection {
                 if let SelectItem::Expr {
                     expr: Expr::Identifier(ident),
                     ..
                 } = select_item
                 {
-                    if let Some(context) = contextualize_query(schema_map, query) {
+                    if let Some(context) = contextualize_set_expr(schema_map, set_expr) {
                         context.validate_duplicated(ident)?;
                     }
                 }
             }
+
+            Ok(())
+        }
+        SetExpr::Values(_) => Ok(()),
+        SetExpr::SetOperation {
+            op, left, right, ..
+        } => {
+            validate_set_expr(schema_map, left)?;
+            validate_set_expr(schema_map, right)?;
+
+            let (left, right) = match (
+                fetch_columns(schema_map, left),
+                fetch_columns(schema_map, right),
+            ) {
+                (Some(left), Some(right)) => (left, right),
+                _ => return Ok(()),
+            };
+
+            if left.len() != right.len() {
+                return Err(PlanError::SetOperationColumnCountMismatch(
+                    op.to_string(),
+                    left.len(),
+                    right.len(),
+                )
+                .into());
+            }
+
+            left.into_iter()
+                .zip(right)
+                .try_for_each(|((label, left), (_, right))| match (left, right) {
+                    (Some(left), Some(right)) if widen_data_type(&left, &right).is_none() => {
+                        Err(PlanError::SetOperationColumnTypeMismatch {
+                            operator: op.to_string(),
+                            column: label.to_owned(),
+                            left,
+                            right,
+                        }
+                        .into())
+                    }
+                    _ => Ok(()),
+                })
         }
     }
+}
 
-    Ok(())
+type Columns<'a> = Vec<(&'a str, Option<DataType>)>;
+
+/// Resolves output column labels and, where they can be known from the schema, their types.
+/// Returns `None` when the number of output columns cannot be determined.
+fn fetch_columns<'a>(schema_map: &'a SchemaMap, set_expr: &'a SetExpr) -> Option<Columns<'a>> {
+    match set_expr {
+        SetExpr::Select(select) => {
+            let Select {
+                projection,
+                from: TableWithJoins { relation, joins },
+                ..
+            } = select.as_ref();
+
+            let relations = once(relation)
+                .chain(joins.iter().map(|Join { relation, .. }| relation))
+                .map(|relation| {
+                    let alias = get_alias(relation).as_str();
+
+                    (alias, fetch_relation_columns(schema_map, relation))
+                })
+                .collect::<Vec<_>>();
+
+            projection
+                .iter()
+                .map(|select_item| match select_item {
+                    SelectItem::Expr { expr, label } => {
+                        Some(vec![(label.as_str(), fetch_expr_type(&relations, expr))])
+                    }
+                    SelectItem::QualifiedWildcard(target) => relations
+                        .iter()
+                        .find(|(alias, _)| alias == target)
+                        .and_then(|(_, columns)| columns.clone()),
+                    SelectItem::Wildcard => relations
+                        .iter()
+                        .map(|(_, columns)| columns.clone())
+                        .collect::<Option<Vec<_>>>()
+                        .map(|columns| columns.into_iter().flatten().collect()),
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(|columns| columns.into_iter().flatten().collect())
+        }
+        SetExpr::Values(Values(values_list)) => values_list
+            .first()
+            .map(|values| values.iter().map(|_| ("", None)).collect()),
+        SetExpr::SetOperation { left, right, .. } => {
+            let left = fetch_columns(schema_map, left)?;
+            let right = match fetch_columns(schema_map, right) {
+                Some(right) if right.len() == left.len() => right,
+                _ => return Some(left),
+            };
+
+            left.into_iter()
+                .zip(right)
+                .map(|((label, left), (_, right))| {
+                    let data_type = match (left, right) {
+                        (Some(left), Some(right)) => widen_data_type(&left, &right),
+                        (left, _) => left,
+                    };
+
+                    (label, data_type)
+                })
+                .collect::<Vec<_>>()
+                .into()
+        }
+    }
+}
+
+/// Type which both operand columns of a set operation are read as. Integers widen to a
+/// larger integer, `FLOAT` or `DECIMAL`, and `FLOAT32` widens to `FLOAT`.
+pub fn widen_data_type(left: &DataType, right: &DataType) -> Option<DataType> {
+    use DataType::*;
+
+    fn integer(data_type: &DataType) -> Option<(bool, u32)> {
+        match data_type {
+            Int8 => Some((true, 8)),
+            Int16 => Some((true, 16)),
+            Int32 => Some((true, 32)),
+            Int => Some((true, 64)),
+            Int128 => Some((true, 128)),
+            Uint8 => Some((false, 8)),
+            Uint16 => Some((false, 16)),
+            Uint32 => Some((false, 32)),
+            Uint64 => Some((false, 64)),
+            Uint128 => Some((false, 128)),
+            _ => None,
+        }
+    }
+
+    if left == right {
+        return Some(left.clone());
+    }
+
+    match (integer(left), integer(right)) {
+        (Some((left_signed, left_bits)), Some((right_signed, right_bits))) => {
+            let bits = match (left_signed, right_signed) {
+                (true, false) => left_bits.max(right_bits * 2),
+                (false, true) => right_bits.max(left_bits * 2),
+                _ => left_bits.max(right_bits),
+            };
+
+            let data_type = match (left_signed || right_signed, bits.min(128)) {
+                (true, 8) => Int8,
+                (true, 16) => Int16,
+                (true, 32) => Int32,
+                (true, 64) => Int,
+                (true, _) => Int128,
+                (false, 8) => Uint8,
+                (false, 16) => Uint16,
+                (false, 32) => Uint32,
+                (false, 64) => Uint64,
+                (false, _) => Uint128,
+            };
+
+            Some(data_type)
+        }
+        (Some(_), None) | (None, Some(_)) => match (left, right) {
+            (Float32 | Float, _) | (_, Float32 | Float) => Some(Float),
+            (Decimal, _) | (_, Decimal) => Some(Decimal),
+            _ => None,
+        },
+        (None, None) => match (left, right) {
+            (Float32, Float) | (Float, Float32) => Some(Float),
+            _ => None,
+        },
+    }
+}
+
+fn fetch_relation_columns<'a>(
+    schema_map: &'a SchemaMap,
+    relation: &'a TableFactor,
+) -> Option<Columns<'a>> {
+    match relation {
+        TableFactor::Table { name, .. } => schema_map
+            .get(name)?
+            .column_defs
+            .as_ref()?
+            .iter()
+            .map(|column_def| (column_def.name.as_str(), Some(column_def.data_type.clone())))
+            .collect::<Vec<_>>()
+            .into(),
+        TableFactor::Derived {
+            subquery,
+            alias: TableAlias { columns, .. },
+        } => fetch_columns(schema_map, &subquery.body).map(|labels| {
+            labels
+                .into_iter()
+                .enumerate()
+                .map(|(i, (label, data_type))| match columns.get(i) {
+                    Some(column) => (column.as_str(), data_type),
+                    None => (label, data_type),
+                })
+                .collect()
+        }),
+        TableFactor::Series { .. } => Some(vec![("N", Some(DataType::Int))]),
+        TableFactor::Dictionary { .. } | TableFactor::Function { .. } => None,
+    }
+}
+
+fn fetch_expr_type(relations: &[(&str, Option<Columns>)], expr: &Expr) -> Option<DataType> {
+    let find = |columns: &Option<Columns>, target: &str| {
+        columns
+            .iter()
+            .flatten()
+            .find(|(label, _)| *label == target)
+            .and_then(|(_, data_type)| data_type.clone())
+    };
+
+    match expr {
+        Expr::Identifier(ident) => relations
+            .iter()
+            .find_map(|(_, columns)| find(columns, ident)),
+        Expr::CompoundIdentifier { alias, ident } => relations
+            .iter()
+            .find(|(target, _)| target == alias)
+            .and_then(|(_, columns)| find(columns, ident)),
+        _ => None,
+    }
 }
 
 enum Context<'a> {
@@ -109,9 +347,11 @@ fn get_labels(schema: &Schema) -> Option<Vec<&str>> {
     })
 }
 
-fn contextualize_query<'a>(schema_map: &'a SchemaMap, query: &'a Query) -> Option<Rc<Context<'a>>> {
-    let Query { body, .. } = query;
-    match body {
+fn contextualize_set_expr<'a>(
+    schema_map: &'a SchemaMap,
+    set_expr: &'a SetExpr,
+) -> Option<Rc<Context<'a>>> {
+    match set_expr {
         SetExpr::Select(select) => {
             let TableWithJoins { relation, joins } = &select.from;
             let by_table = contextualize_table_factor(schema_map, relation);
@@ -122,7 +362,7 @@ fn contextualize_query<'a>(schema_map: &'a SchemaMap, query: &'a Query) -> Optio
 
             Context::concat(by_table, by_joins)
         }
-        SetExpr::Values(_) => None,
+        SetExpr::Values(_) | SetExpr::SetOperation { .. } => None,
     }
 }
 
@@ -135,8 +375,10 @@ fn contextualize_table_factor<'a>(
             let schema = schema_map.get(name);
             schema.map(|schema| Rc::from(Context::new(get_labels(schema), None)))
         }
-        TableFactor::Derived { subquery, .. } => contextualize_query(schema_map, subquery),
-        TableFactor::Series { .. } | TableFactor::Dictionary { .. } => None,
+        TableFactor::Derived { subquery, .. } => contextualize_set_expr(schema_map, &subquery.body),
+        TableFactor::Series { .. }
+        | TableFactor::Dictionary { .. }
+        | TableFactor::Function { .. } => None,
     }
     .map(Rc::from)
 }
@@ -159,6 +401,12 @@ mod tests {
                 id INTEGER,
                 name TEXT
             );
+            CREATE TABLE Metrics (
+                small INT8,
+                unsigned UINT64,
+                ratio FLOAT32,
+                amount DECIMAL
+            );
         ");
 
         let cases = [
@@ -173,6 +421,37 @@ mod tests {
                 "CREATE TABLE Ids AS SELECT id FROM Users A JOIN Users B on A.id = B.id",
                 false,
             ),
+            ("SELECT id FROM Users UNION SELECT id FROM Users", true),
+            (
+                "SELECT * FROM Users EXCEPT SELECT id, name FROM Users",
+                true,
+            ),
+            (
+                "SELECT * FROM Users INTERSECT SELECT N, 'a' FROM SERIES(3)",
+                true,
+            ),
+            (
+                "SELECT id FROM Users UNION SELECT id, name FROM Users",
+                false,
+            ),
+            (
+                "SELECT id FROM Users UNION ALL SELECT name FROM Users",
+                false,
+            ),
+            (
+                "SELECT id FROM Users UNION SELECT id FROM Users A JOIN Users B on A.id = B.id",
+                false,
+            ),
+            ("SELECT id FROM Users UNION SELECT small FROM Metrics", true),
+            ("SELECT small FROM Metrics EXCEPT SELECT unsigned FROM Metrics", true),
+            ("SELECT id FROM Users INTERSECT SELECT ratio FROM Metrics", true),
+            ("SELECT amount FROM Metrics UNION SELECT small FROM Metrics", true),
+            ("SELECT ratio FROM Metrics UNION SELECT amount FROM Metrics", false),
+            (
+                "SELECT small FROM Metrics UNION SELECT ratio FROM Metrics UNION SELECT amount FROM Metrics",
+                false,
+            ),
+            ("SELECT name FROM Users UNION SELECT small FROM Metrics", false),
         ];
 
         for (sql, expected) in cases {
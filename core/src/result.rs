@@ -3,12 +3,13 @@ use {serde::Serialize, std::fmt::Debug, thiserror::Error as ThisError};
 pub use crate::{
     ast_builder::AstBuilderError,
     data::{
-        IntervalError, KeyError, LiteralError, RowError, SchemaParseError, StringExtError,
-        TableError, ValueError,
+        IntervalError, KeyError, LiteralError, RowError, SchemaParseError, SequenceError,
+        StringExtError, TableError, ValueError,
     },
     executor::{
         AggregateError, AlterError, EvaluateError, ExecuteError, FetchError, InsertError,
-        SelectError, SortError, UpdateError, ValidateError,
+        PrepareError, ProcedureError, SelectError, SortError, TriggerError, UpdateError,
+        ValidateError, WindowError,
     },
     plan::PlanError,
     store::{AlterTableError, IndexError},
@@ -47,10 +48,18 @@ pub enum Error {
     Aggregate(#[from] AggregateError),
     #[error("sort: {0}")]
     Sort(#[from] SortError),
+    #[error("window: {0}")]
+    Window(#[from] WindowError),
     #[error("insert: {0}")]
     Insert(#[from] InsertError),
     #[error("update: {0}")]
     Update(#[from] UpdateError),
+    #[error("prepare: {0}")]
+    Prepare(#[from] PrepareError),
+    #[error("trigger: {0}")]
+    Trigger(#[from] TriggerError),
+    #[error("procedure: {0}")]
+    Procedure(#[from] ProcedureError),
     #[error("table: {0}")]
     Table(#[from] TableError),
     #[error("validate: {0}")]
@@ -71,6 +80,8 @@ pub enum Error {
     Plan(#[from] PlanError),
     #[error("schema-parse: {0}")]
     Schema(#[from] SchemaParseError),
+    #[error("sequence: {0}")]
+    Sequence(#[from] SequenceError),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -26,6 +26,9 @@ pub enum AlterTableError {
     #[error("Dropping column not found: {0}")]
     DroppingColumnNotFound(String),
 
+    #[error("Altering column not found: {0}")]
+    AlteringColumnNotFound(String),
+
     #[error("Schemaless table does not support ALTER TABLE: {0}")]
     SchemalessTableFound(String),
 }
@@ -65,4 +68,12 @@ pub trait AlterTable {
 
         Err(Error::StorageMsg(msg))
     }
+
+    /// Replaces the definition of the column named `column_def.name`, existing values are
+    /// cast to the new data type and validated against its nullability.
+    async fn alter_column(&mut self, _table_name: &str, _column_def: &ColumnDef) -> Result<()> {
+        let msg = "[Storage] AlterTable::alter_column is not supported".to_owned();
+
+        Err(Error::StorageMsg(msg))
+    }
 }
@@ -1,7 +1,7 @@
 use {
     super::RowIter,
     crate::{
-        ast::{IndexOperator, OrderByExpr},
+        ast::{Expr, IndexOperator, OrderByExpr},
         data::Value,
         result::{Error, Result},
     },
@@ -40,11 +40,14 @@ pub enum IndexError {
 
 #[async_trait(?Send)]
 pub trait Index {
+    /// Scans rows in the index order, `prefix` holds the values of the leading columns of a
+    /// composite index and `cmp_value` compares the column next to them.
     async fn scan_indexed_data(
         &self,
         _table_name: &str,
         _index_name: &str,
         _asc: Option<bool>,
+        _prefix: &[Value],
         _cmp_value: Option<(&IndexOperator, Value)>,
     ) -> Result<RowIter> {
         Err(Error::StorageMsg(
@@ -59,7 +62,9 @@ pub trait IndexMut {
         &mut self,
         _table_name: &str,
         _index_name: &str,
-        _column: &OrderByExpr,
+        _columns: &[OrderByExpr],
+        _unique: bool,
+        _predicate: Option<&Expr>,
     ) -> Result<()> {
         let msg = "[Storage] Index::create_index is not supported".to_owned();
 
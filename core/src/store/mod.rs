@@ -3,17 +3,43 @@ mod data_row;
 mod function;
 mod index;
 mod metadata;
+mod sequence;
 mod transaction;
+mod trigger;
+mod view;
 
-pub trait GStore: Store + Index + Metadata + CustomFunction {}
-impl<S: Store + Index + Metadata + CustomFunction> GStore for S {}
+pub trait GStore: Store + Index + Metadata + CustomFunction + Sequence + View + Trigger {}
+impl<S: Store + Index + Metadata + CustomFunction + Sequence + View + Trigger> GStore for S {}
 
 pub trait GStoreMut:
-    StoreMut + IndexMut + AlterTable + Transaction + CustomFunction + CustomFunctionMut
+    StoreMut
+    + IndexMut
+    + AlterTable
+    + Transaction
+    + CustomFunction
+    + CustomFunctionMut
+    + Sequence
+    + SequenceMut
+    + View
+    + ViewMut
+    + Trigger
+    + TriggerMut
 {
 }
-impl<S: StoreMut + IndexMut + AlterTable + Transaction + CustomFunction + CustomFunctionMut>
-    GStoreMut for S
+impl<
+        S: StoreMut
+            + IndexMut
+            + AlterTable
+            + Transaction
+            + CustomFunction
+            + CustomFunctionMut
+            + Sequence
+            + SequenceMut
+            + View
+            + ViewMut
+            + Trigger
+            + TriggerMut,
+    > GStoreMut for S
 {
 }
 
@@ -23,11 +49,15 @@ pub use {
     function::{CustomFunction, CustomFunctionMut},
     index::{Index, IndexError, IndexMut},
     metadata::{MetaIter, Metadata},
+    sequence::{Sequence, SequenceMut},
     transaction::Transaction,
+    trigger::{Trigger, TriggerMut},
+    view::{View, ViewMut},
 };
 
 use {
     crate::{
+        ast::IndexOperator,
         data::{Key, Schema},
         result::Result,
     },
@@ -46,6 +76,37 @@ pub trait Store {
     async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>>;
 
     async fn scan_data(&self, table_name: &str) -> Result<RowIter>;
+
+    /// Scans rows whose composite primary key starts with `prefix`,
+    /// and whose next key column satisfies `cmp_key` when given.
+    async fn scan_data_with_prefix(
+        &self,
+        table_name: &str,
+        prefix: &[Key],
+        cmp_key: Option<(&IndexOperator, Key)>,
+    ) -> Result<RowIter> {
+        let prefix = prefix.to_vec();
+        let cmp_key = cmp_key.map(|(op, key)| (op.clone(), key));
+        let rows = self
+            .scan_data(table_name)
+            .await?
+            .filter(move |item| match item {
+                Ok((Key::Composite(keys), _)) => {
+                    keys.starts_with(&prefix)
+                        && match &cmp_key {
+                            Some((op, key)) => keys
+                                .get(prefix.len())
+                                .map(|next| op.matches(next.cmp(key)))
+                                .unwrap_or(false),
+                            None => true,
+                        }
+                }
+                Ok(_) => false,
+                Err(_) => true,
+            });
+
+        Ok(Box::new(rows))
+    }
 }
 
 /// By implementing `StoreMut` trait,
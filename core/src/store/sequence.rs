@@ -0,0 +1,51 @@
+use {
+    crate::{
+        data::Sequence as StructSequence,
+        result::{Error, Result},
+    },
+    async_trait::async_trait,
+};
+
+/// By implementing `Sequence` trait, you can call `NEXTVAL` and `CURRVAL`.
+#[async_trait(?Send)]
+pub trait Sequence {
+    async fn fetch_sequence(&self, _name: &str) -> Result<Option<StructSequence>> {
+        Err(Error::StorageMsg(
+            "[Storage] Sequence is not supported".to_owned(),
+        ))
+    }
+
+    async fn fetch_all_sequences(&self) -> Result<Vec<StructSequence>> {
+        Err(Error::StorageMsg(
+            "[Storage] Sequence is not supported".to_owned(),
+        ))
+    }
+
+    /// Advances the sequence and returns its new value, `None` if the sequence does not exist.
+    ///
+    /// `NEXTVAL` is evaluated within queries, so this takes `&self`. Storages supporting
+    /// transactions should make the advance part of the current transaction, so that two
+    /// transactions never receive the same value.
+    async fn next_sequence_value(&self, _name: &str) -> Result<Option<i64>> {
+        Err(Error::StorageMsg(
+            "[Storage] Sequence is not supported".to_owned(),
+        ))
+    }
+}
+
+/// By implementing `SequenceMut` trait, you can run `CREATE SEQUENCE` and `DROP SEQUENCE`
+/// and create tables having identity columns.
+#[async_trait(?Send)]
+pub trait SequenceMut {
+    async fn insert_sequence(&mut self, _sequence: StructSequence) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Sequence is not supported".to_owned(),
+        ))
+    }
+
+    async fn delete_sequence(&mut self, _name: &str) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Sequence is not supported".to_owned(),
+        ))
+    }
+}
@@ -0,0 +1,38 @@
+use {
+    crate::{
+        data::Trigger as StructTrigger,
+        result::{Error, Result},
+    },
+    async_trait::async_trait,
+};
+
+/// By implementing `Trigger` trait, you can fire row-level triggers.
+///
+/// `fetch_all_triggers` is filtered by the table and event of each INSERT, UPDATE and DELETE,
+/// and when a table is renamed or dropped, to move or drop its triggers along with it.
+#[async_trait(?Send)]
+pub trait Trigger {
+    async fn fetch_trigger(&self, _trigger_name: &str) -> Result<Option<StructTrigger>> {
+        Ok(None)
+    }
+
+    async fn fetch_all_triggers(&self) -> Result<Vec<StructTrigger>> {
+        Ok(Vec::new())
+    }
+}
+
+/// By implementing `TriggerMut` trait, you can run `CREATE TRIGGER` and `DROP TRIGGER`.
+#[async_trait(?Send)]
+pub trait TriggerMut {
+    async fn insert_trigger(&mut self, _trigger: StructTrigger) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Trigger is not supported".to_owned(),
+        ))
+    }
+
+    async fn delete_trigger(&mut self, _trigger_name: &str) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Trigger is not supported".to_owned(),
+        ))
+    }
+}
@@ -0,0 +1,38 @@
+use {
+    crate::{
+        data::View as StructView,
+        result::{Error, Result},
+    },
+    async_trait::async_trait,
+};
+
+/// By implementing `View` trait, you can select from views.
+///
+/// `fetch_view` is called while planning for each name a statement reads which is not a table,
+/// and `fetch_all_views` when a table is dropped, to find the views which still read it.
+#[async_trait(?Send)]
+pub trait View {
+    async fn fetch_view(&self, _view_name: &str) -> Result<Option<StructView>> {
+        Ok(None)
+    }
+
+    async fn fetch_all_views(&self) -> Result<Vec<StructView>> {
+        Ok(Vec::new())
+    }
+}
+
+/// By implementing `ViewMut` trait, you can run `CREATE VIEW` and `DROP VIEW`.
+#[async_trait(?Send)]
+pub trait ViewMut {
+    async fn insert_view(&mut self, _view: StructView) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] View is not supported".to_owned(),
+        ))
+    }
+
+    async fn delete_view(&mut self, _view_name: &str) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] View is not supported".to_owned(),
+        ))
+    }
+}
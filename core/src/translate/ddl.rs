@@ -3,14 +3,24 @@ use {
         data_type::translate_data_type, expr::translate_expr, translate_object_name, TranslateError,
     },
     crate::{
-        ast::{AlterTableOperation, ColumnDef, ColumnUniqueOption, OperateFunctionArg},
+        ast::{
+            AlterColumnOperation, AlterTableOperation, AstLiteral, Check, ColumnDef,
+            ColumnUniqueOption, Expr, ForeignKey, Function, OperateFunctionArg, ReferentialAction,
+            SequenceOption, UniqueKey,
+        },
+        data::Sequence,
         result::Result,
     },
     sqlparser::ast::{
+        AlterColumnOperation as SqlAlterColumnOperation,
         AlterTableOperation as SqlAlterTableOperation, ColumnDef as SqlColumnDef,
         ColumnOption as SqlColumnOption, ColumnOptionDef as SqlColumnOptionDef,
-        OperateFunctionArg as SqlOperateFunctionArg,
+        DataType as SqlDataType, GeneratedAs as SqlGeneratedAs, Ident as SqlIdent,
+        MinMaxValue as SqlMinMaxValue, ObjectName as SqlObjectName,
+        OperateFunctionArg as SqlOperateFunctionArg, ReferentialAction as SqlReferentialAction,
+        SequenceOptions as SqlSequenceOptions, TableConstraint as SqlTableConstraint,
     },
+    std::{collections::HashSet, iter},
 };
 
 pub fn translate_alter_table_operation(
@@ -42,6 +52,31 @@ pub fn translate_alter_table_operation(
                 table_name: translate_object_name(table_name)?,
             })
         }
+        SqlAlterTableOperation::AlterColumn { column_name, op } => {
+            let operation = match op {
+                SqlAlterColumnOperation::SetDataType {
+                    data_type,
+                    using: None,
+                } => AlterColumnOperation::SetDataType(translate_data_type(data_type)?),
+                SqlAlterColumnOperation::SetDataType { using: Some(_), .. } => {
+                    return Err(TranslateError::UnsupportedAlterTableOperation(
+                        sql_alter_table_operation.to_string(),
+                    )
+                    .into());
+                }
+                SqlAlterColumnOperation::SetDefault { value } => {
+                    AlterColumnOperation::SetDefault(translate_expr(value)?)
+                }
+                SqlAlterColumnOperation::DropDefault => AlterColumnOperation::DropDefault,
+                SqlAlterColumnOperation::SetNotNull => AlterColumnOperation::SetNotNull,
+                SqlAlterColumnOperation::DropNotNull => AlterColumnOperation::DropNotNull,
+            };
+
+            Ok(AlterTableOperation::AlterColumn {
+                column_name: column_name.value.to_owned(),
+                operation,
+            })
+        }
         _ => Err(TranslateError::UnsupportedAlterTableOperation(
             sql_alter_table_operation.to_string(),
         )
@@ -90,6 +125,361 @@ pub fn translate_column_def(sql_column_def: &SqlColumnDef) -> Result<ColumnDef>
     })
 }
 
+type TableDefinition = (Vec<ColumnDef>, Vec<ForeignKey>, Vec<Check>, Vec<UniqueKey>);
+
+/// Translates columns and table constraints of CREATE TABLE, collecting column-level
+/// `REFERENCES` and `CHECK` options together with the table-level constraints.
+/// A table-level `PRIMARY KEY` or `UNIQUE` on a single column is merged into its column.
+/// `SERIAL` and `GENERATED BY DEFAULT AS IDENTITY` columns get a `NEXTVAL` default on the
+/// sequence named by [`Sequence::identity_name`].
+pub fn translate_table_definition(
+    table_name: &str,
+    sql_column_defs: &[SqlColumnDef],
+    sql_constraints: &[SqlTableConstraint],
+) -> Result<TableDefinition> {
+    let mut column_defs = Vec::with_capacity(sql_column_defs.len());
+    let mut foreign_keys = Vec::new();
+    let mut checks = Vec::new();
+    let mut unique_keys = Vec::new();
+
+    for sql_column_def in sql_column_defs {
+        let (constraint_options, options): (Vec<_>, Vec<_>) =
+            sql_column_def.options.iter().cloned().partition(
+                |SqlColumnOptionDef { option, .. }| {
+                    matches!(
+                        option,
+                        SqlColumnOption::ForeignKey { .. } | SqlColumnOption::Check(_)
+                    )
+                },
+            );
+
+        for SqlColumnOptionDef { name, option } in constraint_options.iter() {
+            match option {
+                SqlColumnOption::ForeignKey {
+                    foreign_table,
+                    referred_columns,
+                    on_delete,
+                    on_update,
+                } => {
+                    let foreign_key = translate_foreign_key(
+                        &option.to_string(),
+                        name.as_ref(),
+                        std::slice::from_ref(&sql_column_def.name),
+                        foreign_table,
+                        referred_columns,
+                        on_delete.as_ref(),
+                        on_update.as_ref(),
+                    )?;
+
+                    foreign_keys.push(foreign_key);
+                }
+                SqlColumnOption::Check(expr) => {
+                    let name = name.as_ref().map(|name| name.value.to_owned());
+                    let generated_name = format!("CK_{}", sql_column_def.name.value);
+
+                    checks.push((name, generated_name, translate_expr(expr)?));
+                }
+                _ => {}
+            }
+        }
+
+        let serial = match &sql_column_def.data_type {
+            SqlDataType::Custom(name, _) => {
+                matches!(
+                    name.to_string().to_uppercase().as_str(),
+                    "SERIAL" | "BIGSERIAL"
+                )
+            }
+            _ => false,
+        };
+        let (identity_options, options): (Vec<_>, Vec<_>) =
+            options
+                .into_iter()
+                .partition(|SqlColumnOptionDef { option, .. }| {
+                    matches!(option, SqlColumnOption::Generated { .. })
+                });
+
+        for SqlColumnOptionDef { option, .. } in identity_options.iter() {
+            let column_name = sql_column_def.name.value.to_owned();
+
+            match option {
+                SqlColumnOption::Generated {
+                    generated_as: SqlGeneratedAs::ExpStored,
+                    ..
+                }
+                | SqlColumnOption::Generated {
+                    generation_expr: Some(_),
+                    ..
+                } => return Err(TranslateError::GeneratedColumnNotSupported(column_name).into()),
+                SqlColumnOption::Generated {
+                    generated_as: SqlGeneratedAs::Always,
+                    ..
+                } => {
+                    return Err(
+                        TranslateError::GeneratedAlwaysIdentityNotSupported(column_name).into(),
+                    )
+                }
+                SqlColumnOption::Generated {
+                    sequence_options: Some(sequence_options),
+                    ..
+                } if !sequence_options.is_empty() => {
+                    return Err(
+                        TranslateError::IdentitySequenceOptionsNotSupported(column_name).into(),
+                    )
+                }
+                _ => {}
+            }
+        }
+
+        let sql_column_def = SqlColumnDef {
+            data_type: match serial {
+                true => SqlDataType::Int(None),
+                false => sql_column_def.data_type.clone(),
+            },
+            options,
+            ..sql_column_def.clone()
+        };
+        let mut column_def = translate_column_def(&sql_column_def)?;
+
+        if serial || !identity_options.is_empty() {
+            if column_def.default.is_some() {
+                return Err(
+                    TranslateError::DefaultOnIdentityColumn(column_def.name.to_owned()).into(),
+                );
+            }
+
+            let sequence_name = Sequence::identity_name(table_name, &column_def.name);
+            let default = Expr::Function(Box::new(Function::Nextval(Expr::Literal(
+                AstLiteral::QuotedString(sequence_name),
+            ))));
+
+            column_def.default = Some(default);
+            column_def.nullable = false;
+        }
+
+        column_defs.push(column_def);
+    }
+
+    for sql_constraint in sql_constraints {
+        match sql_constraint {
+            SqlTableConstraint::ForeignKey {
+                name,
+                columns,
+                foreign_table,
+                referred_columns,
+                on_delete,
+                on_update,
+            } => {
+                let foreign_key = translate_foreign_key(
+                    &sql_constraint.to_string(),
+                    name.as_ref(),
+                    columns,
+                    foreign_table,
+                    referred_columns,
+                    on_delete.as_ref(),
+                    on_update.as_ref(),
+                )?;
+
+                foreign_keys.push(foreign_key);
+            }
+            SqlTableConstraint::Check { name, expr } => {
+                let name = name.as_ref().map(|name| name.value.to_owned());
+                let generated_name = format!("CK_{}", checks.len() + 1);
+
+                checks.push((name, generated_name, translate_expr(expr)?));
+            }
+            SqlTableConstraint::Unique {
+                name,
+                columns,
+                is_primary,
+            } => {
+                let columns = columns
+                    .iter()
+                    .map(|column| column.value.to_owned())
+                    .collect::<Vec<_>>();
+
+                for column in columns.iter() {
+                    let column_def = column_defs
+                        .iter_mut()
+                        .find(|column_def| &column_def.name == column)
+                        .ok_or_else(|| {
+                            TranslateError::ColumnNotFoundInConstraint(column.to_owned())
+                        })?;
+
+                    if *is_primary {
+                        column_def.nullable = false;
+                    }
+
+                    // a column already declared UNIQUE becomes the primary key
+                    if let [_] = columns.as_slice() {
+                        let is_primary = *is_primary
+                            || matches!(
+                                column_def.unique,
+                                Some(ColumnUniqueOption { is_primary: true })
+                            );
+
+                        column_def.unique = Some(ColumnUniqueOption { is_primary });
+                    }
+                }
+
+                if columns.len() > 1 {
+                    let name = match name {
+                        Some(name) => name.value.to_owned(),
+                        None => {
+                            let prefix = if *is_primary { "PK" } else { "UQ" };
+
+                            format!("{prefix}_{}", columns.join("_"))
+                        }
+                    };
+
+                    unique_keys.push(UniqueKey {
+                        name,
+                        columns,
+                        is_primary: *is_primary,
+                    });
+                }
+            }
+            _ => {
+                return Err(
+                    TranslateError::UnsupportedTableConstraint(sql_constraint.to_string()).into(),
+                );
+            }
+        }
+    }
+
+    let primary_key_count = column_defs
+        .iter()
+        .filter(|column_def| {
+            matches!(
+                column_def.unique,
+                Some(ColumnUniqueOption { is_primary: true })
+            )
+        })
+        .count()
+        + unique_keys
+            .iter()
+            .filter(|unique_key| unique_key.is_primary)
+            .count();
+    if primary_key_count > 1 {
+        return Err(TranslateError::MultiplePrimaryKeysNotAllowed.into());
+    }
+
+    Ok((column_defs, foreign_keys, name_checks(checks), unique_keys))
+}
+
+/// Gives each unnamed CHECK its generated name, adding a `_2`, `_3`, ... suffix while
+/// the name is taken by another CHECK of the table.
+fn name_checks(checks: Vec<(Option<String>, String, Expr)>) -> Vec<Check> {
+    let mut names = checks
+        .iter()
+        .filter_map(|(name, ..)| name.clone())
+        .collect::<HashSet<_>>();
+
+    checks
+        .into_iter()
+        .map(|(name, generated_name, expr)| {
+            let name = name.unwrap_or_else(|| {
+                let name = iter::once(generated_name.clone())
+                    .chain((2..).map(|n| format!("{generated_name}_{n}")))
+                    .find(|name| !names.contains(name))
+                    .unwrap_or(generated_name);
+
+                names.insert(name.clone());
+                name
+            });
+
+            Check { name, expr }
+        })
+        .collect()
+}
+
+pub fn translate_sequence_options(
+    sql_sequence_options: &[SqlSequenceOptions],
+) -> Result<Vec<SequenceOption>> {
+    sql_sequence_options
+        .iter()
+        .filter_map(|sql_sequence_option| {
+            let option = match sql_sequence_option {
+                SqlSequenceOptions::IncrementBy(expr, _) => {
+                    translate_expr(expr).map(SequenceOption::IncrementBy)
+                }
+                SqlSequenceOptions::MinValue(SqlMinMaxValue::Empty)
+                | SqlSequenceOptions::MaxValue(SqlMinMaxValue::Empty) => return None,
+                SqlSequenceOptions::MinValue(SqlMinMaxValue::None) => {
+                    Ok(SequenceOption::MinValue(None))
+                }
+                SqlSequenceOptions::MinValue(SqlMinMaxValue::Some(expr)) => {
+                    translate_expr(expr).map(Some).map(SequenceOption::MinValue)
+                }
+                SqlSequenceOptions::MaxValue(SqlMinMaxValue::None) => {
+                    Ok(SequenceOption::MaxValue(None))
+                }
+                SqlSequenceOptions::MaxValue(SqlMinMaxValue::Some(expr)) => {
+                    translate_expr(expr).map(Some).map(SequenceOption::MaxValue)
+                }
+                SqlSequenceOptions::StartWith(expr, _) => {
+                    translate_expr(expr).map(SequenceOption::StartWith)
+                }
+                SqlSequenceOptions::Cycle(no_cycle) => Ok(SequenceOption::Cycle(!no_cycle)),
+                SqlSequenceOptions::Cache(_) => Err(TranslateError::UnsupportedSequenceOption(
+                    sql_sequence_option.to_string().trim_start().to_owned(),
+                )
+                .into()),
+            };
+
+            Some(option)
+        })
+        .collect()
+}
+
+fn translate_foreign_key(
+    sql: &str,
+    name: Option<&SqlIdent>,
+    columns: &[SqlIdent],
+    foreign_table: &SqlObjectName,
+    referred_columns: &[SqlIdent],
+    on_delete: Option<&SqlReferentialAction>,
+    on_update: Option<&SqlReferentialAction>,
+) -> Result<ForeignKey> {
+    let (referencing_column_name, referenced_column_name) = match (columns, referred_columns) {
+        ([column], [referred_column]) => (&column.value, &referred_column.value),
+        (_, []) => return Err(TranslateError::LackOfReferencedColumn(sql.to_owned()).into()),
+        _ => {
+            return Err(TranslateError::CompositeForeignKeyNotSupported(sql.to_owned()).into());
+        }
+    };
+    let referenced_table_name = translate_object_name(foreign_table)?;
+    let name = match name {
+        Some(name) => name.value.to_owned(),
+        None => {
+            format!("FK_{referencing_column_name}-{referenced_table_name}_{referenced_column_name}")
+        }
+    };
+
+    Ok(ForeignKey {
+        name,
+        referencing_column_name: referencing_column_name.to_owned(),
+        referenced_table_name,
+        referenced_column_name: referenced_column_name.to_owned(),
+        on_delete: translate_referential_action(on_delete)?,
+        on_update: translate_referential_action(on_update)?,
+    })
+}
+
+fn translate_referential_action(
+    sql_referential_action: Option<&SqlReferentialAction>,
+) -> Result<ReferentialAction> {
+    match sql_referential_action {
+        None | Some(SqlReferentialAction::NoAction) => Ok(ReferentialAction::NoAction),
+        Some(SqlReferentialAction::Restrict) => Ok(ReferentialAction::Restrict),
+        Some(SqlReferentialAction::Cascade) => Ok(ReferentialAction::Cascade),
+        Some(SqlReferentialAction::SetNull) => Ok(ReferentialAction::SetNull),
+        Some(action @ SqlReferentialAction::SetDefault) => {
+            Err(TranslateError::UnsupportedReferentialAction(action.to_string()).into())
+        }
+    }
+}
+
 pub fn translate_operate_function_arg(arg: &SqlOperateFunctionArg) -> Result<OperateFunctionArg> {
     let name = arg
         .name
@@ -5,11 +5,8 @@ pub enum TranslateError {
     #[error("unimplemented - select on two or more than tables are not supported")]
     TooManyTables,
 
-    #[error("unimplemented - select distinct is not supported")]
-    SelectDistinctNotSupported,
-
-    #[error("unimplemented - composite index is not supported")]
-    CompositeIndexNotSupported,
+    #[error("unimplemented - composite foreign key is not supported: {0}")]
+    CompositeForeignKeyNotSupported(String),
 
     #[error("unimplemented - join on update not supported")]
     JoinOnUpdateNotSupported,
@@ -60,6 +57,36 @@ pub enum TranslateError {
     #[error("qualified wildcard is not supported - COUNT({0})")]
     QualifiedWildcardInCountNotSupported(String),
 
+    #[error("DISTINCT is not supported - COUNT(DISTINCT *)")]
+    DistinctOnWildcardNotSupported,
+
+    #[error("DISTINCT is only supported in aggregate functions: {0}")]
+    DistinctOnNonAggregateFunction(String),
+
+    #[error("ORDER BY is only supported in aggregate functions: {0}")]
+    OrderByOnNonAggregateFunction(String),
+
+    #[error("unsupported ARRAY_AGG - {0}")]
+    UnsupportedArrayAgg(String),
+
+    #[error("FILTER is only supported in aggregate functions: {0}")]
+    FilterOnNonAggregateFunction(String),
+
+    #[error("DISTINCT aggregate is not supported in window functions")]
+    DistinctAggregateInWindowNotSupported,
+
+    #[error("window function requires OVER clause: {0}")]
+    WindowFunctionWithoutOver(String),
+
+    #[error("unsupported window function: {0}")]
+    UnsupportedWindowFunction(String),
+
+    #[error("named window is not supported: {0}")]
+    NamedWindowNotSupported(String),
+
+    #[error("unsupported window frame units: {0}")]
+    UnsupportedWindowFrameUnits(String),
+
     #[error("order by - NULLS (FIRST | LAST) is not supported")]
     OrderByNullsFirstOrLastNotSupported,
 
@@ -108,12 +135,59 @@ pub enum TranslateError {
     #[error("unsupported column option: {0}")]
     UnsupportedColumnOption(String),
 
+    #[error("unsupported sequence option: {0}")]
+    UnsupportedSequenceOption(String),
+
+    #[error("identity column cannot have a DEFAULT: {0}")]
+    DefaultOnIdentityColumn(String),
+
+    #[error("GENERATED ALWAYS AS IDENTITY is not supported, use GENERATED BY DEFAULT: {0}")]
+    GeneratedAlwaysIdentityNotSupported(String),
+
+    #[error("identity sequence options are not supported, create the sequence and use DEFAULT NEXTVAL instead: {0}")]
+    IdentitySequenceOptionsNotSupported(String),
+
+    #[error("generated columns are not supported: {0}")]
+    GeneratedColumnNotSupported(String),
+
+    #[error("unsupported referential action: {0}")]
+    UnsupportedReferentialAction(String),
+
     #[error("unsupported alter table operation: {0}")]
     UnsupportedAlterTableOperation(String),
 
     #[error("unsupported table factor: {0}")]
     UnsupportedTableFactor(String),
 
+    #[error("unsupported trigger action, only INSERT, UPDATE and DELETE are allowed: {0}")]
+    UnsupportedTriggerAction(String),
+
+    #[error(
+        "unsupported statement in function, SQL statements are only allowed in procedures: {0}"
+    )]
+    UnsupportedStatementInFunction(String),
+
+    #[error("procedure cannot return a value")]
+    ReturnValueInProcedure,
+
+    #[error("unsupported ON clause of INSERT: {0}")]
+    UnsupportedOnInsert(String),
+
+    #[error("ON CONFLICT DO UPDATE requires a conflict target column")]
+    LackOfConflictTargetForDoUpdate,
+
+    #[error("foreign key requires a referenced column: {0}")]
+    LackOfReferencedColumn(String),
+
+    #[error("unsupported table constraint: {0}")]
+    UnsupportedTableConstraint(String),
+
+    #[error("column of table constraint not found: {0}")]
+    ColumnNotFoundInConstraint(String),
+
+    #[error("multiple primary keys for a table are not allowed")]
+    MultiplePrimaryKeysNotAllowed,
+
     #[error("Every derived table must have its own alias")]
     LackOfAlias,
 
@@ -10,13 +10,13 @@ use {
         translate_idents, translate_query, TranslateError,
     },
     crate::{
-        ast::{Expr, OrderByExpr},
+        ast::{Aggregate, AggregateFunction, Expr, OrderByExpr},
         result::Result,
         translate::function::translate_trim,
     },
     sqlparser::ast::{
-        DateTimeField as SqlDateTimeField, Expr as SqlExpr, Interval as SqlInterval,
-        OrderByExpr as SqlOrderByExpr,
+        ArrayAgg as SqlArrayAgg, DateTimeField as SqlDateTimeField, Expr as SqlExpr,
+        Interval as SqlInterval, OrderByExpr as SqlOrderByExpr, Value as SqlValue,
     },
 };
 
@@ -101,6 +101,9 @@ pub fn translate_expr(sql_expr: &SqlExpr) -> Result<Expr> {
         }),
         SqlExpr::Extract { field, expr } => translate_extract(field, expr),
         SqlExpr::Nested(expr) => translate_expr(expr).map(Box::new).map(Expr::Nested),
+        SqlExpr::Value(SqlValue::Placeholder(placeholder)) => {
+            Ok(Expr::Placeholder(placeholder.to_owned()))
+        }
         SqlExpr::Value(value) => translate_ast_literal(value).map(Expr::Literal),
         SqlExpr::TypedString { data_type, value } => Ok(Expr::TypedString {
             data_type: translate_data_type(data_type)?,
@@ -178,11 +181,46 @@ pub fn translate_expr(sql_expr: &SqlExpr) -> Result<Expr> {
                 .transpose()?,
         }),
         SqlExpr::Cast { expr, data_type } => translate_cast(expr, data_type),
+        SqlExpr::ArrayAgg(array_agg) => translate_array_agg(array_agg),
+        SqlExpr::AggregateExpressionWithFilter { expr, filter } => match translate_expr(expr)? {
+            Expr::Aggregate(aggregate) => Ok(Expr::Aggregate(Box::new(Aggregate {
+                filter: Some(translate_expr(filter)?),
+                ..*aggregate
+            }))),
+            _ => Err(TranslateError::FilterOnNonAggregateFunction(expr.to_string()).into()),
+        },
 
         _ => Err(TranslateError::UnsupportedExpr(sql_expr.to_string()).into()),
     }
 }
 
+fn translate_array_agg(array_agg: &SqlArrayAgg) -> Result<Expr> {
+    let SqlArrayAgg {
+        distinct,
+        expr,
+        order_by,
+        limit,
+        ..
+    } = array_agg;
+
+    if limit.is_some() {
+        return Err(TranslateError::UnsupportedArrayAgg(array_agg.to_string()).into());
+    }
+
+    let order_by = order_by
+        .iter()
+        .flatten()
+        .map(translate_order_by_expr)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Expr::Aggregate(Box::new(Aggregate {
+        func: AggregateFunction::ArrayAgg(translate_expr(expr)?),
+        distinct: *distinct,
+        filter: None,
+        order_by,
+    })))
+}
+
 pub fn translate_order_by_expr(sql_order_by_expr: &SqlOrderByExpr) -> Result<OrderByExpr> {
     let SqlOrderByExpr {
         expr,
@@ -1,17 +1,22 @@
 use {
     super::{
         ast_literal::{translate_datetime_field, translate_trim_where_field},
-        expr::translate_expr,
+        expr::{translate_expr, translate_order_by_expr},
         translate_data_type, translate_object_name, TranslateError,
     },
     crate::{
-        ast::{Aggregate, CountArgExpr, Expr, Function},
+        ast::{
+            Aggregate, AggregateFunction, CountArgExpr, Expr, Function, OrderByExpr, Window,
+            WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunction, WindowSpec,
+        },
         result::Result,
     },
     sqlparser::ast::{
         DataType, DateTimeField as SqlDateTimeField, Expr as SqlExpr, Function as SqlFunction,
         FunctionArg as SqlFunctionArg, FunctionArgExpr as SqlFunctionArgExpr,
-        TrimWhereField as SqlTrimWhereField,
+        TrimWhereField as SqlTrimWhereField, WindowFrame as SqlWindowFrame,
+        WindowFrameBound as SqlWindowFrameBound, WindowFrameUnits as SqlWindowFrameUnits,
+        WindowSpec as SqlWindowSpec, WindowType as SqlWindowType,
     },
 };
 
@@ -131,17 +136,48 @@ fn translate_function_one_arg<T: FnOnce(Expr) -> Function>(
         .map(Expr::Function)
 }
 
-fn translate_aggregate_one_arg<T: FnOnce(Expr) -> Aggregate>(
+fn translate_aggregate(
+    func: AggregateFunction,
+    distinct: bool,
+    order_by: Vec<OrderByExpr>,
+) -> Expr {
+    Expr::Aggregate(Box::new(Aggregate {
+        func,
+        distinct,
+        filter: None,
+        order_by,
+    }))
+}
+
+fn translate_aggregate_one_arg<
+    T: FnOnce(Expr) -> AggregateFunction,
+    U: FnOnce(AggregateFunction) -> Expr,
+>(
     func: T,
     args: Vec<&SqlExpr>,
     name: String,
+    aggregate: U,
 ) -> Result<Expr> {
     check_len(name, args.len(), 1)?;
 
-    translate_expr(args[0])
-        .map(func)
-        .map(Box::new)
-        .map(Expr::Aggregate)
+    translate_expr(args[0]).map(func).map(aggregate)
+}
+
+fn translate_aggregate_two_args<
+    T: FnOnce(Expr, Expr) -> AggregateFunction,
+    U: FnOnce(AggregateFunction) -> Expr,
+>(
+    func: T,
+    args: Vec<&SqlExpr>,
+    name: String,
+    aggregate: U,
+) -> Result<Expr> {
+    check_len(name, args.len(), 2)?;
+
+    let left = translate_expr(args[0])?;
+    let right = translate_expr(args[1])?;
+
+    Ok(aggregate(func(left, right)))
 }
 
 fn translate_function_trim<T: FnOnce(Expr, Option<Expr>) -> Function>(
@@ -178,8 +214,27 @@ pub fn translate_function_arg_exprs(
 }
 
 pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
-    let SqlFunction { name, args, .. } = sql_function;
+    let SqlFunction {
+        name,
+        args,
+        over,
+        distinct,
+        order_by,
+        ..
+    } = sql_function;
+
+    if let Some(over) = over {
+        return translate_window(sql_function, over);
+    }
+
     let name = translate_object_name(name)?.to_uppercase();
+    let distinct = *distinct;
+    let order_by = order_by
+        .iter()
+        .map(translate_order_by_expr)
+        .collect::<Result<Vec<_>>>()?;
+    let ordered = !order_by.is_empty();
+    let aggregate = |func| translate_aggregate(func, distinct, order_by.clone());
 
     let function_arg_exprs = args
         .iter()
@@ -205,18 +260,55 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
             SqlFunctionArgExpr::Wildcard => CountArgExpr::Wildcard,
         };
 
-        return Ok(Expr::Aggregate(Box::new(Aggregate::Count(count_arg))));
+        if distinct && matches!(count_arg, CountArgExpr::Wildcard) {
+            return Err(TranslateError::DistinctOnWildcardNotSupported.into());
+        }
+
+        return Ok(aggregate(AggregateFunction::Count(count_arg)));
     }
 
     let args = translate_function_arg_exprs(function_arg_exprs)?;
 
+    let aggregate_one_arg = |func: fn(Expr) -> AggregateFunction, args, name| {
+        translate_aggregate_one_arg(func, args, name, aggregate)
+    };
+
     match name.as_str() {
-        "SUM" => translate_aggregate_one_arg(Aggregate::Sum, args, name),
-        "MIN" => translate_aggregate_one_arg(Aggregate::Min, args, name),
-        "MAX" => translate_aggregate_one_arg(Aggregate::Max, args, name),
-        "AVG" => translate_aggregate_one_arg(Aggregate::Avg, args, name),
-        "VARIANCE" => translate_aggregate_one_arg(Aggregate::Variance, args, name),
-        "STDEV" => translate_aggregate_one_arg(Aggregate::Stdev, args, name),
+        "SUM" => aggregate_one_arg(AggregateFunction::Sum, args, name),
+        "MIN" => aggregate_one_arg(AggregateFunction::Min, args, name),
+        "MAX" => aggregate_one_arg(AggregateFunction::Max, args, name),
+        "AVG" => aggregate_one_arg(AggregateFunction::Avg, args, name),
+        "VARIANCE" => aggregate_one_arg(AggregateFunction::Variance, args, name),
+        "STDEV" => aggregate_one_arg(AggregateFunction::Stdev, args, name),
+        "STRING_AGG" => translate_aggregate_two_args(
+            |expr, separator| AggregateFunction::StringAgg { expr, separator },
+            args,
+            name,
+            aggregate,
+        ),
+        "JSON_OBJECT_AGG" => translate_aggregate_two_args(
+            |key, value| AggregateFunction::JsonObjectAgg { key, value },
+            args,
+            name,
+            aggregate,
+        ),
+        _ if distinct => Err(TranslateError::DistinctOnNonAggregateFunction(name).into()),
+        _ if ordered => Err(TranslateError::OrderByOnNonAggregateFunction(name).into()),
+        "GROUPING" => {
+            check_len_min(name, args.len(), 1)?;
+            let exprs = args
+                .into_iter()
+                .map(translate_expr)
+                .collect::<Result<Vec<_>>>()?;
+            Ok(translate_aggregate(
+                AggregateFunction::Grouping(exprs),
+                false,
+                Vec::new(),
+            ))
+        }
+        "ROW_NUMBER" | "RANK" | "DENSE_RANK" | "LAG" | "LEAD" | "FIRST_VALUE" | "LAST_VALUE" => {
+            Err(TranslateError::WindowFunctionWithoutOver(name).into())
+        }
         "COALESCE" => {
             let exprs = args
                 .into_iter()
@@ -631,6 +723,8 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
             let expr = translate_expr(args[0])?;
             Ok(Expr::Function(Box::new(Function::Values(expr))))
         }
+        "NEXTVAL" => translate_function_one_arg(Function::Nextval, args, name),
+        "CURRVAL" => translate_function_one_arg(Function::Currval, args, name),
         _ => {
             let exprs = args
                 .into_iter()
@@ -640,3 +734,174 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
         }
     }
 }
+
+fn translate_window(sql_function: &SqlFunction, over: &SqlWindowType) -> Result<Expr> {
+    let SqlFunction { name, args, .. } = sql_function;
+    let name = translate_object_name(name)?.to_uppercase();
+
+    let spec = match over {
+        SqlWindowType::WindowSpec(spec) => translate_window_spec(spec)?,
+        SqlWindowType::NamedWindow(ident) => {
+            return Err(TranslateError::NamedWindowNotSupported(ident.value.to_owned()).into());
+        }
+    };
+
+    let function = match name.as_str() {
+        "ROW_NUMBER" => {
+            check_len(name, args.len(), 0)?;
+
+            WindowFunction::RowNumber
+        }
+        "RANK" => {
+            check_len(name, args.len(), 0)?;
+
+            WindowFunction::Rank
+        }
+        "DENSE_RANK" => {
+            check_len(name, args.len(), 0)?;
+
+            WindowFunction::DenseRank
+        }
+        "LAG" | "LEAD" => {
+            check_len_range(name.clone(), args.len(), 1, 3)?;
+
+            let mut args = translate_window_args(args)?.into_iter();
+            let expr = args.next().ok_or(TranslateError::LackOfArgs)?;
+            let offset = args.next();
+            let default = args.next();
+
+            match name.as_str() {
+                "LAG" => WindowFunction::Lag {
+                    expr,
+                    offset,
+                    default,
+                },
+                _ => WindowFunction::Lead {
+                    expr,
+                    offset,
+                    default,
+                },
+            }
+        }
+        "FIRST_VALUE" | "LAST_VALUE" => {
+            check_len(name.clone(), args.len(), 1)?;
+
+            let expr = translate_window_args(args)?
+                .into_iter()
+                .next()
+                .ok_or(TranslateError::LackOfArgs)?;
+
+            match name.as_str() {
+                "FIRST_VALUE" => WindowFunction::FirstValue(expr),
+                _ => WindowFunction::LastValue(expr),
+            }
+        }
+        _ => {
+            let sql_function = SqlFunction {
+                over: None,
+                ..sql_function.clone()
+            };
+
+            match translate_function(&sql_function)? {
+                Expr::Aggregate(aggregate) if aggregate.distinct => {
+                    return Err(TranslateError::DistinctAggregateInWindowNotSupported.into());
+                }
+                Expr::Aggregate(aggregate)
+                    if !aggregate.order_by.is_empty()
+                        || matches!(
+                            aggregate.func,
+                            AggregateFunction::StringAgg { .. }
+                                | AggregateFunction::JsonObjectAgg { .. }
+                        ) =>
+                {
+                    return Err(TranslateError::UnsupportedWindowFunction(name).into());
+                }
+                Expr::Aggregate(aggregate) => WindowFunction::Aggregate(*aggregate),
+                _ => return Err(TranslateError::UnsupportedWindowFunction(name).into()),
+            }
+        }
+    };
+
+    Ok(Expr::Window(Box::new(Window { function, spec })))
+}
+
+fn translate_window_args(args: &[SqlFunctionArg]) -> Result<Vec<Expr>> {
+    let function_arg_exprs = args
+        .iter()
+        .map(|arg| match arg {
+            SqlFunctionArg::Named { .. } => {
+                Err(TranslateError::NamedFunctionArgNotSupported.into())
+            }
+            SqlFunctionArg::Unnamed(arg_expr) => Ok(arg_expr),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    translate_function_arg_exprs(function_arg_exprs)?
+        .into_iter()
+        .map(translate_expr)
+        .collect()
+}
+
+fn translate_window_spec(sql_window_spec: &SqlWindowSpec) -> Result<WindowSpec> {
+    let SqlWindowSpec {
+        partition_by,
+        order_by,
+        window_frame,
+    } = sql_window_spec;
+
+    let partition_by = partition_by
+        .iter()
+        .map(translate_expr)
+        .collect::<Result<Vec<_>>>()?;
+    let order_by = order_by
+        .iter()
+        .map(translate_order_by_expr)
+        .collect::<Result<Vec<_>>>()?;
+    let frame = window_frame
+        .as_ref()
+        .map(translate_window_frame)
+        .transpose()?;
+
+    Ok(WindowSpec {
+        partition_by,
+        order_by,
+        frame,
+    })
+}
+
+fn translate_window_frame(sql_window_frame: &SqlWindowFrame) -> Result<WindowFrame> {
+    let SqlWindowFrame {
+        units,
+        start_bound,
+        end_bound,
+    } = sql_window_frame;
+
+    let units = match units {
+        SqlWindowFrameUnits::Rows => WindowFrameUnits::Rows,
+        SqlWindowFrameUnits::Range => WindowFrameUnits::Range,
+        SqlWindowFrameUnits::Groups => {
+            return Err(TranslateError::UnsupportedWindowFrameUnits(units.to_string()).into());
+        }
+    };
+    let start = translate_window_frame_bound(start_bound)?;
+    let end = end_bound
+        .as_ref()
+        .map(translate_window_frame_bound)
+        .transpose()?
+        .unwrap_or(WindowFrameBound::CurrentRow);
+
+    Ok(WindowFrame { units, start, end })
+}
+
+fn translate_window_frame_bound(
+    sql_window_frame_bound: &SqlWindowFrameBound,
+) -> Result<WindowFrameBound> {
+    let translate =
+        |expr: &Option<Box<SqlExpr>>| expr.as_ref().map(|expr| translate_expr(expr)).transpose();
+
+    match sql_window_frame_bound {
+        SqlWindowFrameBound::CurrentRow => Ok(WindowFrameBound::CurrentRow),
+        SqlWindowFrameBound::Preceding(expr) => translate(expr).map(WindowFrameBound::Preceding),
+        SqlWindowFrameBound::Following(expr) => translate(expr).map(WindowFrameBound::Following),
+    }
+}
@@ -5,6 +5,7 @@ mod error;
 mod expr;
 mod function;
 mod operator;
+mod procedural;
 mod query;
 
 pub use self::{
@@ -17,80 +18,267 @@ pub use self::{
 
 use {
     crate::{
-        ast::{Assignment, Statement, Variable},
+        ast::{
+            Assignment, FunctionBody, Join, JoinExecutor, JoinOperator, OnConflict,
+            OnConflictAction, SelectItem, Statement, TableFactor, TableWithJoins, TriggerAction,
+            Variable,
+        },
+        parse_sql::{ParsedFunctionBody, ParsedStatement, ParsedTriggerAction},
         result::Result,
     },
-    ddl::translate_alter_table_operation,
+    ddl::{
+        translate_alter_table_operation, translate_sequence_options, translate_table_definition,
+    },
+    procedural::translate_block,
+    query::translate_table_with_joins,
     sqlparser::ast::{
-        Assignment as SqlAssignment, Ident as SqlIdent, ObjectName as SqlObjectName,
-        ObjectType as SqlObjectType, Statement as SqlStatement, TableFactor, TableWithJoins,
+        Assignment as SqlAssignment, ConflictTarget as SqlConflictTarget, DoUpdate as SqlDoUpdate,
+        Expr as SqlExpr, Ident as SqlIdent, ObjectName as SqlObjectName,
+        ObjectType as SqlObjectType, OnConflict as SqlOnConflict,
+        OnConflictAction as SqlOnConflictAction, OnInsert as SqlOnInsert,
+        OrderByExpr as SqlOrderByExpr, SelectItem as SqlSelectItem, Statement as SqlStatement,
+        TableFactor as SqlTableFactor, TableWithJoins as SqlTableWithJoins,
     },
+    std::iter::once,
 };
 
-pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
+pub fn translate(parsed: &ParsedStatement) -> Result<Statement> {
+    match parsed {
+        ParsedStatement::Sql(sql_statement) => translate_statement(sql_statement),
+        ParsedStatement::CreateTrigger {
+            or_replace,
+            name,
+            table_name,
+            timing,
+            events,
+            action,
+        } => {
+            let action = match action {
+                ParsedTriggerAction::Function { name, args } => TriggerAction::Function {
+                    name: translate_object_name(name)?,
+                    args: args.iter().map(translate_expr).collect::<Result<_>>()?,
+                },
+                ParsedTriggerAction::Statement(sql_statement) => match sql_statement.as_ref() {
+                    SqlStatement::Insert { .. }
+                    | SqlStatement::Update { .. }
+                    | SqlStatement::Delete { .. } => {
+                        let statement = translate_statement(sql_statement)?;
+
+                        TriggerAction::Statement(Box::new(statement))
+                    }
+                    _ => {
+                        return Err(TranslateError::UnsupportedTriggerAction(
+                            sql_statement.to_string(),
+                        )
+                        .into())
+                    }
+                },
+                ParsedTriggerAction::SetNew(assignments) => TriggerAction::SetNew(
+                    assignments
+                        .iter()
+                        .map(|(column, value)| {
+                            Ok(Assignment {
+                                id: column.value.to_owned(),
+                                value: translate_expr(value)?,
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                ),
+            };
+
+            Ok(Statement::CreateTrigger {
+                or_replace: *or_replace,
+                name: translate_object_name(name)?,
+                table_name: translate_object_name(table_name)?,
+                timing: *timing,
+                events: events.clone(),
+                action,
+            })
+        }
+        ParsedStatement::DropTrigger { if_exists, names } => Ok(Statement::DropTrigger {
+            if_exists: *if_exists,
+            names: names
+                .iter()
+                .map(translate_object_name)
+                .collect::<Result<Vec<_>>>()?,
+        }),
+        ParsedStatement::CreateFunction {
+            or_replace,
+            name,
+            args,
+            body,
+        } => Ok(Statement::CreateFunction {
+            or_replace: *or_replace,
+            name: translate_object_name(name)?,
+            args: args
+                .iter()
+                .map(translate_operate_function_arg)
+                .collect::<Result<_>>()?,
+            body: match body {
+                ParsedFunctionBody::Block(body) => {
+                    FunctionBody::Block(translate_block(body, false)?)
+                }
+                ParsedFunctionBody::Table { columns, query } => FunctionBody::Table {
+                    columns: columns
+                        .iter()
+                        .map(translate_column_def)
+                        .collect::<Result<_>>()?,
+                    query: translate_query(query).map(Box::new)?,
+                },
+            },
+        }),
+        ParsedStatement::CreateProcedure {
+            or_replace,
+            name,
+            args,
+            body,
+        } => Ok(Statement::CreateProcedure {
+            or_replace: *or_replace,
+            name: translate_object_name(name)?,
+            args: args
+                .iter()
+                .map(translate_operate_function_arg)
+                .collect::<Result<_>>()?,
+            body: translate_block(body, true)?,
+        }),
+        ParsedStatement::Call { name, args } => Ok(Statement::Call {
+            name: translate_object_name(name)?,
+            args: args.iter().map(translate_expr).collect::<Result<_>>()?,
+        }),
+        ParsedStatement::DropProcedure { if_exists, names } => Ok(Statement::DropProcedure {
+            if_exists: *if_exists,
+            names: names
+                .iter()
+                .map(translate_object_name)
+                .collect::<Result<Vec<_>>>()?,
+        }),
+        ParsedStatement::CreatePartialIndex {
+            name,
+            table_name,
+            columns,
+            unique,
+            predicate,
+        } => translate_create_index(name, table_name, columns, *unique, Some(predicate)),
+    }
+}
+
+fn translate_create_index(
+    name: &SqlObjectName,
+    table_name: &SqlObjectName,
+    columns: &[SqlOrderByExpr],
+    unique: bool,
+    predicate: Option<&SqlExpr>,
+) -> Result<Statement> {
+    let name = translate_object_name(name)?;
+
+    if name.to_uppercase() == "PRIMARY" {
+        return Err(TranslateError::ReservedIndexName(name).into());
+    };
+
+    Ok(Statement::CreateIndex {
+        name,
+        table_name: translate_object_name(table_name)?,
+        columns: columns
+            .iter()
+            .map(translate_order_by_expr)
+            .collect::<Result<Vec<_>>>()?,
+        unique,
+        predicate: predicate.map(translate_expr).transpose()?,
+    })
+}
+
+fn translate_statement(sql_statement: &SqlStatement) -> Result<Statement> {
     match sql_statement {
         SqlStatement::Query(query) => translate_query(query).map(Statement::Query),
         SqlStatement::Insert {
             table_name,
             columns,
             source,
+            on,
+            returning,
             ..
         } => Ok(Statement::Insert {
             table_name: translate_object_name(table_name)?,
             columns: translate_idents(columns),
             source: translate_query(source)?,
+            on_conflict: on.as_ref().map(translate_on_insert).transpose()?,
+            returning: translate_returning(returning.as_deref())?,
         }),
         SqlStatement::Update {
             table,
             assignments,
+            from,
             selection,
-            ..
-        } => Ok(Statement::Update {
-            table_name: translate_table_with_join(table)?,
-            assignments: assignments
-                .iter()
-                .map(translate_assignment)
-                .collect::<Result<_>>()?,
-            selection: selection.as_ref().map(translate_expr).transpose()?,
-        }),
+            returning,
+        } => {
+            let table_name = translate_table_with_join(table)?;
+            let from = from
+                .as_ref()
+                .map(|from| translate_joined_target(&table_name, from))
+                .transpose()?;
+
+            Ok(Statement::Update {
+                table_name,
+                assignments: assignments
+                    .iter()
+                    .map(translate_assignment)
+                    .collect::<Result<_>>()?,
+                from,
+                selection: selection.as_ref().map(translate_expr).transpose()?,
+                returning: translate_returning(returning.as_deref())?,
+            })
+        }
         SqlStatement::Delete {
-            from, selection, ..
+            from,
+            using,
+            selection,
+            returning,
+            ..
         } => {
             let table_name = from
                 .iter()
                 .map(translate_table_with_join)
                 .next()
                 .ok_or(TranslateError::UnreachableEmptyTable)??;
+            let using = match using.as_deref() {
+                Some([using]) => Some(translate_joined_target(&table_name, using)?),
+                Some(_) => return Err(TranslateError::TooManyTables.into()),
+                None => None,
+            };
 
             Ok(Statement::Delete {
                 table_name,
+                using,
                 selection: selection.as_ref().map(translate_expr).transpose()?,
+                returning: translate_returning(returning.as_deref())?,
             })
         }
         SqlStatement::CreateTable {
             if_not_exists,
             name,
             columns,
+            constraints,
             query,
             engine,
             ..
         } => {
-            let columns = columns
-                .iter()
-                .map(translate_column_def)
-                .collect::<Result<Vec<_>>>()?;
-
+            let name = translate_object_name(name)?;
+            let (columns, foreign_keys, checks, unique_keys) =
+                translate_table_definition(&name, columns, constraints)?;
             let columns = (!columns.is_empty()).then_some(columns);
 
             Ok(Statement::CreateTable {
                 if_not_exists: *if_not_exists,
-                name: translate_object_name(name)?,
+                name,
                 columns,
                 source: match query {
                     Some(v) => Some(translate_query(v).map(Box::new)?),
                     None => None,
                 },
                 engine: engine.clone(),
+                foreign_keys,
+                checks,
+                unique_keys,
             })
         }
         SqlStatement::AlterTable {
@@ -122,28 +310,63 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                 .map(|v| translate_object_name(&v.name))
                 .collect::<Result<Vec<_>>>()?,
         }),
+        SqlStatement::CreateView {
+            or_replace,
+            materialized: false,
+            name,
+            columns,
+            query,
+            with_options,
+            cluster_by,
+        } if with_options.is_empty() && cluster_by.is_empty() => Ok(Statement::CreateView {
+            or_replace: *or_replace,
+            name: translate_object_name(name)?,
+            columns: translate_idents(columns),
+            query: translate_query(query).map(Box::new)?,
+        }),
+        SqlStatement::Drop {
+            object_type: SqlObjectType::View,
+            if_exists,
+            names,
+            ..
+        } => Ok(Statement::DropView {
+            if_exists: *if_exists,
+            names: names
+                .iter()
+                .map(translate_object_name)
+                .collect::<Result<Vec<_>>>()?,
+        }),
+        SqlStatement::CreateSequence {
+            temporary: false,
+            if_not_exists,
+            name,
+            data_type: None,
+            sequence_options,
+            owned_by: None,
+        } => Ok(Statement::CreateSequence {
+            if_not_exists: *if_not_exists,
+            name: translate_object_name(name)?,
+            options: translate_sequence_options(sequence_options)?,
+        }),
+        SqlStatement::Drop {
+            object_type: SqlObjectType::Sequence,
+            if_exists,
+            names,
+            ..
+        } => Ok(Statement::DropSequence {
+            if_exists: *if_exists,
+            names: names
+                .iter()
+                .map(translate_object_name)
+                .collect::<Result<Vec<_>>>()?,
+        }),
         SqlStatement::CreateIndex {
             name,
             table_name,
             columns,
+            unique,
             ..
-        } => {
-            if columns.len() > 1 {
-                return Err(TranslateError::CompositeIndexNotSupported.into());
-            }
-
-            let name = translate_object_name(name)?;
-
-            if name.to_uppercase() == "PRIMARY" {
-                return Err(TranslateError::ReservedIndexName(name).into());
-            };
-
-            Ok(Statement::CreateIndex {
-                name,
-                table_name: translate_object_name(table_name)?,
-                column: translate_order_by_expr(&columns[0])?,
-            })
-        }
+        } => translate_create_index(name, table_name, columns, *unique, None),
         SqlStatement::Drop {
             object_type: SqlObjectType::Index,
             names,
@@ -170,6 +393,12 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
         SqlStatement::StartTransaction { .. } => Ok(Statement::StartTransaction),
         SqlStatement::Commit { .. } => Ok(Statement::Commit),
         SqlStatement::Rollback { .. } => Ok(Statement::Rollback),
+        SqlStatement::Explain {
+            statement, analyze, ..
+        } => Ok(Statement::Explain {
+            statement: translate_statement(statement).map(Box::new)?,
+            analyze: *analyze,
+        }),
         SqlStatement::ShowTables {
             filter: None,
             db_name: None,
@@ -181,6 +410,7 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
         SqlStatement::ShowVariable { variable } => match (variable.len(), variable.get(0)) {
             (1, Some(keyword)) => match keyword.value.to_uppercase().as_str() {
                 "VERSION" => Ok(Statement::ShowVariable(Variable::Version)),
+                "VIEWS" => Ok(Statement::ShowVariable(Variable::Views)),
                 v => Err(TranslateError::UnsupportedShowVariableKeyword(v.to_owned()).into()),
             },
             (3, Some(keyword)) => match keyword.value.to_uppercase().as_str() {
@@ -222,11 +452,12 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                 or_replace: *or_replace,
                 name: translate_object_name(name)?,
                 args: args.unwrap_or_default(),
-                return_: params
+                body: params
                     .return_
                     .as_ref()
                     .map(translate_expr)
                     .transpose()?
+                    .map(FunctionBody::Return)
                     .ok_or(TranslateError::UnsupportedEmptyFunctionBody)?,
             })
         }
@@ -234,6 +465,53 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
     }
 }
 
+fn translate_returning(sql_returning: Option<&[SqlSelectItem]>) -> Result<Vec<SelectItem>> {
+    sql_returning
+        .unwrap_or_default()
+        .iter()
+        .map(translate_select_item)
+        .collect()
+}
+
+fn translate_on_insert(sql_on_insert: &SqlOnInsert) -> Result<OnConflict> {
+    let SqlOnConflict {
+        conflict_target,
+        action,
+    } = match sql_on_insert {
+        SqlOnInsert::OnConflict(on_conflict) => on_conflict,
+        _ => {
+            return Err(TranslateError::UnsupportedOnInsert(sql_on_insert.to_string()).into());
+        }
+    };
+
+    let target = match conflict_target {
+        Some(SqlConflictTarget::Columns(columns)) => translate_idents(columns),
+        Some(SqlConflictTarget::OnConstraint(_)) => {
+            return Err(TranslateError::UnsupportedOnInsert(sql_on_insert.to_string()).into());
+        }
+        None => Vec::new(),
+    };
+
+    let action = match action {
+        SqlOnConflictAction::DoNothing => OnConflictAction::DoNothing,
+        SqlOnConflictAction::DoUpdate(_) if target.is_empty() => {
+            return Err(TranslateError::LackOfConflictTargetForDoUpdate.into());
+        }
+        SqlOnConflictAction::DoUpdate(SqlDoUpdate {
+            assignments,
+            selection,
+        }) => OnConflictAction::DoUpdate {
+            assignments: assignments
+                .iter()
+                .map(translate_assignment)
+                .collect::<Result<_>>()?,
+            selection: selection.as_ref().map(translate_expr).transpose()?,
+        },
+    };
+
+    Ok(OnConflict { target, action })
+}
+
 pub fn translate_assignment(sql_assignment: &SqlAssignment) -> Result<Assignment> {
     let SqlAssignment { id, value } = sql_assignment;
 
@@ -253,16 +531,39 @@ pub fn translate_assignment(sql_assignment: &SqlAssignment) -> Result<Assignment
     })
 }
 
-fn translate_table_with_join(table: &TableWithJoins) -> Result<String> {
+fn translate_table_with_join(table: &SqlTableWithJoins) -> Result<String> {
     if !table.joins.is_empty() {
         return Err(TranslateError::JoinOnUpdateNotSupported.into());
     }
     match &table.relation {
-        TableFactor::Table { name, .. } => translate_object_name(name),
+        SqlTableFactor::Table { name, .. } => translate_object_name(name),
         t => Err(TranslateError::UnsupportedTableFactor(t.to_string()).into()),
     }
 }
 
+/// Translates FROM of UPDATE or USING of DELETE into the target table cross joined with them,
+/// so the target table is planned and joined the same way as the leading table of SELECT.
+fn translate_joined_target(
+    table_name: &str,
+    sql_table_with_joins: &SqlTableWithJoins,
+) -> Result<TableWithJoins> {
+    let TableWithJoins { relation, joins } = translate_table_with_joins(sql_table_with_joins)?;
+    let join = Join {
+        relation,
+        join_operator: JoinOperator::Cross,
+        join_executor: JoinExecutor::NestedLoop,
+    };
+
+    Ok(TableWithJoins {
+        relation: TableFactor::Table {
+            name: table_name.to_owned(),
+            alias: None,
+            index: None,
+        },
+        joins: once(join).chain(joins).collect(),
+    })
+}
+
 fn translate_object_name(sql_object_name: &SqlObjectName) -> Result<String> {
     let sql_object_name = &sql_object_name.0;
     if sql_object_name.len() > 1 {
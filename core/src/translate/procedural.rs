@@ -0,0 +1,114 @@
+use {
+    super::{translate, translate_data_type, translate_expr, TranslateError},
+    crate::{
+        ast::{BinaryOperator, Expr, Procedural, ToSql},
+        parse_sql::ParsedProcedural,
+        result::Result,
+    },
+};
+
+/// Translates the statements of a `BEGIN .. END` block, SQL statements are only allowed
+/// in procedures and `RETURN <expr>` only in functions.
+pub fn translate_block(body: &[ParsedProcedural], procedure: bool) -> Result<Vec<Procedural>> {
+    body.iter()
+        .map(|procedural| translate_procedural(procedural, procedure))
+        .collect()
+}
+
+fn translate_procedural(procedural: &ParsedProcedural, procedure: bool) -> Result<Procedural> {
+    let translate_condition = |condition: &Option<_>| condition.as_ref().map(translate_expr);
+
+    match procedural {
+        ParsedProcedural::Declare {
+            name,
+            data_type,
+            default,
+        } => Ok(Procedural::Declare {
+            name: name.value.to_owned(),
+            data_type: translate_data_type(data_type)?,
+            default: default.as_ref().map(translate_expr).transpose()?,
+        }),
+        ParsedProcedural::Set { name, value } => Ok(Procedural::Set {
+            name: name.value.to_owned(),
+            value: translate_expr(value)?,
+        }),
+        ParsedProcedural::If {
+            branches,
+            else_body,
+        } => Ok(Procedural::If {
+            branches: branches
+                .iter()
+                .map(|(condition, body)| {
+                    Ok((
+                        translate_expr(condition)?,
+                        translate_block(body, procedure)?,
+                    ))
+                })
+                .collect::<Result<_>>()?,
+            else_body: else_body
+                .as_ref()
+                .map(|body| translate_block(body, procedure))
+                .transpose()?,
+        }),
+        ParsedProcedural::Case {
+            operand,
+            branches,
+            else_body,
+        } => {
+            let operand = operand.as_ref().map(translate_expr).transpose()?;
+            let branches = branches
+                .iter()
+                .map(|(condition, body)| {
+                    let condition = translate_expr(condition)?;
+                    let condition = match &operand {
+                        Some(operand) => Expr::BinaryOp {
+                            left: Box::new(operand.clone()),
+                            op: BinaryOperator::Eq,
+                            right: Box::new(condition),
+                        },
+                        None => condition,
+                    };
+
+                    Ok((condition, translate_block(body, procedure)?))
+                })
+                .collect::<Result<_>>()?;
+
+            Ok(Procedural::If {
+                branches,
+                else_body: else_body
+                    .as_ref()
+                    .map(|body| translate_block(body, procedure))
+                    .transpose()?,
+            })
+        }
+        ParsedProcedural::While { condition, body } => Ok(Procedural::While {
+            condition: translate_expr(condition)?,
+            body: translate_block(body, procedure)?,
+        }),
+        ParsedProcedural::Loop(body) => translate_block(body, procedure).map(Procedural::Loop),
+        ParsedProcedural::Exit(condition) => translate_condition(condition)
+            .transpose()
+            .map(Procedural::Exit),
+        ParsedProcedural::Continue(condition) => translate_condition(condition)
+            .transpose()
+            .map(Procedural::Continue),
+        ParsedProcedural::Return(Some(_)) if procedure => {
+            Err(TranslateError::ReturnValueInProcedure.into())
+        }
+        ParsedProcedural::Return(expr) => expr
+            .as_ref()
+            .map(translate_expr)
+            .transpose()
+            .map(Procedural::Return),
+        ParsedProcedural::Statement(parsed) => {
+            let statement = translate(parsed)?;
+
+            match procedure {
+                true => Ok(Procedural::Statement(Box::new(statement))),
+                false => {
+                    Err(TranslateError::UnsupportedStatementInFunction(statement.to_sql()).into())
+                }
+            }
+        }
+    }
+}
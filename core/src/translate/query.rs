@@ -5,22 +5,25 @@ use {
     },
     crate::{
         ast::{
-            AstLiteral, Dictionary, Expr, Join, JoinConstraint, JoinExecutor, JoinOperator, Query,
-            Select, SelectItem, SetExpr, TableAlias, TableFactor, TableWithJoins, Values,
+            AstLiteral, Cte, Dictionary, Distinct, Expr, GroupingSets, Join, JoinConstraint,
+            JoinExecutor, JoinOperator, Query, Select, SelectItem, SetExpr, SetOperator,
+            TableAlias, TableFactor, TableWithJoins, Values, With,
         },
         result::Result,
     },
     sqlparser::ast::{
-        Expr as SqlExpr, FunctionArg as SqlFunctionArg, Join as SqlJoin,
-        JoinConstraint as SqlJoinConstraint, JoinOperator as SqlJoinOperator, Query as SqlQuery,
-        Select as SqlSelect, SelectItem as SqlSelectItem, SetExpr as SqlSetExpr,
+        Cte as SqlCte, Distinct as SqlDistinct, Expr as SqlExpr, FunctionArg as SqlFunctionArg,
+        Join as SqlJoin, JoinConstraint as SqlJoinConstraint, JoinOperator as SqlJoinOperator,
+        Query as SqlQuery, Select as SqlSelect, SelectItem as SqlSelectItem, SetExpr as SqlSetExpr,
+        SetOperator as SqlSetOperator, SetQuantifier as SqlSetQuantifier,
         TableAlias as SqlTableAlias, TableFactor as SqlTableFactor,
-        TableWithJoins as SqlTableWithJoins,
+        TableWithJoins as SqlTableWithJoins, With as SqlWith,
     },
 };
 
 pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
     let SqlQuery {
+        with,
         body,
         order_by,
         limit,
@@ -28,6 +31,7 @@ pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
         ..
     } = sql_query;
 
+    let with = with.as_ref().map(translate_with).transpose()?;
     let body = translate_set_expr(body)?;
     let order_by = order_by
         .iter()
@@ -41,6 +45,7 @@ pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
         .transpose()?;
 
     Ok(Query {
+        with,
         body,
         order_by,
         limit,
@@ -48,6 +53,31 @@ pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
     })
 }
 
+fn translate_with(sql_with: &SqlWith) -> Result<With> {
+    let SqlWith {
+        recursive,
+        cte_tables,
+    } = sql_with;
+
+    let cte_tables = cte_tables
+        .iter()
+        .map(|SqlCte { alias, query, .. }| {
+            let SqlTableAlias { name, columns } = alias;
+            let alias = TableAlias {
+                name: name.value.to_owned(),
+                columns: translate_idents(columns),
+            };
+
+            translate_query(query).map(|query| Cte { alias, query })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(With {
+        recursive: *recursive,
+        cte_tables,
+    })
+}
+
 fn translate_set_expr(sql_set_expr: &SqlSetExpr) -> Result<SetExpr> {
     match sql_set_expr {
         SqlSetExpr::Select(select) => translate_select(select).map(Box::new).map(SetExpr::Select),
@@ -57,6 +87,44 @@ fn translate_set_expr(sql_set_expr: &SqlSetExpr) -> Result<SetExpr> {
             .collect::<Result<_>>()
             .map(Values)
             .map(SetExpr::Values),
+        SqlSetExpr::Query(sql_query) => match translate_query(sql_query)? {
+            Query {
+                with: None,
+                body,
+                order_by,
+                limit: None,
+                offset: None,
+            } if order_by.is_empty() => Ok(body),
+            _ => Err(TranslateError::UnsupportedQuerySetExpr(sql_set_expr.to_string()).into()),
+        },
+        SqlSetExpr::SetOperation {
+            op,
+            set_quantifier,
+            left,
+            right,
+        } => {
+            let op = match op {
+                SqlSetOperator::Union => SetOperator::Union,
+                SqlSetOperator::Intersect => SetOperator::Intersect,
+                SqlSetOperator::Except => SetOperator::Except,
+            };
+            let all = match set_quantifier {
+                SqlSetQuantifier::All => true,
+                SqlSetQuantifier::Distinct | SqlSetQuantifier::None => false,
+                SqlSetQuantifier::ByName | SqlSetQuantifier::AllByName => {
+                    return Err(
+                        TranslateError::UnsupportedQuerySetExpr(sql_set_expr.to_string()).into(),
+                    );
+                }
+            };
+
+            Ok(SetExpr::SetOperation {
+                op,
+                all,
+                left: translate_set_expr(left).map(Box::new)?,
+                right: translate_set_expr(right).map(Box::new)?,
+            })
+        }
         _ => Err(TranslateError::UnsupportedQuerySetExpr(sql_set_expr.to_string()).into()),
     }
 }
@@ -76,10 +144,6 @@ fn translate_select(sql_select: &SqlSelect) -> Result<Select> {
         return Err(TranslateError::TooManyTables.into());
     }
 
-    if distinct.is_some() {
-        return Err(TranslateError::SelectDistinctNotSupported.into());
-    }
-
     let from = match from.get(0) {
         Some(sql_table_with_joins) => translate_table_with_joins(sql_table_with_joins)?,
         None => TableWithJoins {
@@ -94,14 +158,50 @@ fn translate_select(sql_select: &SqlSelect) -> Result<Select> {
         },
     };
 
+    let distinct = distinct
+        .as_ref()
+        .map(|distinct| match distinct {
+            SqlDistinct::Distinct => Ok(Distinct::Distinct),
+            SqlDistinct::On(exprs) => exprs
+                .iter()
+                .map(translate_expr)
+                .collect::<Result<_>>()
+                .map(Distinct::On),
+        })
+        .transpose()?;
+
+    let translate_sets = |sets: &[Vec<SqlExpr>]| {
+        sets.iter()
+            .map(|set| set.iter().map(translate_expr).collect::<Result<_>>())
+            .collect::<Result<_>>()
+    };
+    let mut grouping_sets = Vec::new();
+    let mut group_by_exprs = Vec::new();
+
+    for expr in group_by {
+        let sets = match expr {
+            SqlExpr::GroupingSets(sets) => GroupingSets::Sets(translate_sets(sets)?),
+            SqlExpr::Rollup(sets) => GroupingSets::Rollup(translate_sets(sets)?),
+            SqlExpr::Cube(sets) => GroupingSets::Cube(translate_sets(sets)?),
+            _ => {
+                group_by_exprs.push(translate_expr(expr)?);
+                continue;
+            }
+        };
+
+        grouping_sets.push(sets);
+    }
+
     Ok(Select {
+        distinct,
         projection: projection
             .iter()
             .map(translate_select_item)
             .collect::<Result<_>>()?,
         from,
         selection: selection.as_ref().map(translate_expr).transpose()?,
-        group_by: group_by.iter().map(translate_expr).collect::<Result<_>>()?,
+        group_by: group_by_exprs,
+        grouping_sets,
         having: having.as_ref().map(translate_expr).transpose()?,
     })
 }
@@ -135,7 +235,9 @@ pub fn translate_select_item(sql_select_item: &SqlSelectItem) -> Result<SelectIt
     }
 }
 
-fn translate_table_with_joins(sql_table_with_joins: &SqlTableWithJoins) -> Result<TableWithJoins> {
+pub fn translate_table_with_joins(
+    sql_table_with_joins: &SqlTableWithJoins,
+) -> Result<TableWithJoins> {
     let SqlTableWithJoins { relation, joins } = sql_table_with_joins;
 
     Ok(TableWithJoins {
@@ -154,7 +256,7 @@ fn translate_table_alias(alias: &Option<SqlTableAlias>) -> Option<TableAlias> {
 }
 
 fn translate_table_factor(sql_table_factor: &SqlTableFactor) -> Result<TableFactor> {
-    let translate_table_args = |args: &Vec<SqlFunctionArg>| -> Result<Expr> {
+    let translate_table_args = |args: &Vec<SqlFunctionArg>| -> Result<Vec<Expr>> {
         let function_arg_exprs = args
             .iter()
             .map(|arg| match arg {
@@ -165,10 +267,10 @@ fn translate_table_factor(sql_table_factor: &SqlTableFactor) -> Result<TableFact
             })
             .collect::<Result<Vec<_>>>()?;
 
-        match translate_function_arg_exprs(function_arg_exprs)?.get(0) {
-            Some(expr) => Ok(translate_expr(expr)?),
-            None => Err(TranslateError::LackOfArgs.into()),
-        }
+        translate_function_arg_exprs(function_arg_exprs)?
+            .into_iter()
+            .map(translate_expr)
+            .collect::<Result<Vec<_>>>()
     };
 
     match sql_table_factor {
@@ -181,7 +283,10 @@ fn translate_table_factor(sql_table_factor: &SqlTableFactor) -> Result<TableFact
             match (object_name.as_str(), args) {
                 ("SERIES", Some(args)) => Ok(TableFactor::Series {
                     alias: alias_or_name(alias, object_name),
-                    size: translate_table_args(args)?,
+                    size: translate_table_args(args)?
+                        .into_iter()
+                        .next()
+                        .ok_or(TranslateError::LackOfArgs)?,
                 }),
                 ("GLUE_OBJECTS", _) => Ok(TableFactor::Dictionary {
                     dict: Dictionary::GlueObjects,
@@ -199,6 +304,23 @@ fn translate_table_factor(sql_table_factor: &SqlTableFactor) -> Result<TableFact
                     dict: Dictionary::GlueTableColumns,
                     alias: alias_or_name(alias, object_name),
                 }),
+                ("GLUE_TABLE_CONSTRAINTS", _) => Ok(TableFactor::Dictionary {
+                    dict: Dictionary::GlueTableConstraints,
+                    alias: alias_or_name(alias, object_name),
+                }),
+                ("GLUE_VIEWS", _) => Ok(TableFactor::Dictionary {
+                    dict: Dictionary::GlueViews,
+                    alias: alias_or_name(alias, object_name),
+                }),
+                (_, Some(args)) => {
+                    let name = translate_object_name(name)?;
+
+                    Ok(TableFactor::Function {
+                        alias: alias_or_name(alias, name.clone()),
+                        name,
+                        args: translate_table_args(args)?,
+                    })
+                }
                 _ => {
                     Ok(TableFactor::Table {
                         name: translate_object_name(name)?,
@@ -258,6 +380,13 @@ fn translate_join(sql_join: &SqlJoin) -> Result<Join> {
         SqlJoinOperator::LeftOuter(sql_join_constraint) => {
             translate_constraint(sql_join_constraint).map(JoinOperator::LeftOuter)
         }
+        SqlJoinOperator::RightOuter(sql_join_constraint) => {
+            translate_constraint(sql_join_constraint).map(JoinOperator::RightOuter)
+        }
+        SqlJoinOperator::FullOuter(sql_join_constraint) => {
+            translate_constraint(sql_join_constraint).map(JoinOperator::FullOuter)
+        }
+        SqlJoinOperator::CrossJoin => Ok(JoinOperator::Cross),
         _ => {
             Err(TranslateError::UnsupportedJoinOperator(format!("{:?}", sql_join_operator)).into())
         }
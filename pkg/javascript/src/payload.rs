@@ -111,6 +111,27 @@ fn convert_payload(payload: Payload) -> Json {
             })
         }
         Payload::DropFunction => json!({ "type": "DROP FUNCTION" }),
+        Payload::DropSequence => json!({ "type": "DROP SEQUENCE" }),
+        Payload::DropView => json!({ "type": "DROP VIEW" }),
+        Payload::DropTrigger => json!({ "type": "DROP TRIGGER" }),
+        Payload::DropProcedure => json!({ "type": "DROP PROCEDURE" }),
+        Payload::Call => json!({ "type": "CALL" }),
+        Payload::Explain(plan) => json!({
+            "type": "EXPLAIN",
+            "plan": plan
+        }),
+        Payload::ExplainAnalyze(analyzed) => json!({
+            "type": "EXPLAIN ANALYZE",
+            "plan": analyzed.plan,
+            "storage_calls": analyzed.storage_calls,
+            "elapsed": analyzed.elapsed
+        }),
+        Payload::ShowVariable(PayloadVariable::Views(view_names)) => {
+            json!({
+                "type": "SHOW VIEWS",
+                "views": view_names
+            })
+        }
         Payload::ShowVariable(PayloadVariable::Functions(function_names)) => {
             json!({
                 "type": "SHOW FUNCTIONS",
@@ -92,3 +92,9 @@ impl gluesql_core::store::IndexMut for CompositeStorage {}
 impl Metadata for CompositeStorage {}
 impl gluesql_core::store::CustomFunction for CompositeStorage {}
 impl gluesql_core::store::CustomFunctionMut for CompositeStorage {}
+impl gluesql_core::store::View for CompositeStorage {}
+impl gluesql_core::store::ViewMut for CompositeStorage {}
+impl gluesql_core::store::Trigger for CompositeStorage {}
+impl gluesql_core::store::TriggerMut for CompositeStorage {}
+impl gluesql_core::store::Sequence for CompositeStorage {}
+impl gluesql_core::store::SequenceMut for CompositeStorage {}
@@ -3,6 +3,7 @@ use {
     async_trait::async_trait,
     futures::stream::{self, StreamExt, TryStreamExt},
     gluesql_core::{
+        ast::IndexOperator,
         data::{Key, Schema},
         error::Result,
         store::{DataRow, RowIter, Store},
@@ -49,4 +50,16 @@ impl Store for CompositeStorage {
             .scan_data(table_name)
             .await
     }
+
+    async fn scan_data_with_prefix(
+        &self,
+        table_name: &str,
+        prefix: &[Key],
+        cmp_key: Option<(&IndexOperator, Key)>,
+    ) -> Result<RowIter> {
+        self.fetch_storage(table_name)
+            .await?
+            .scan_data_with_prefix(table_name, prefix, cmp_key)
+            .await
+    }
 }
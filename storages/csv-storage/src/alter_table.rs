@@ -0,0 +1,58 @@
+use {
+    crate::{
+        error::{CsvStorageError, ResultExt},
+        CsvStorage,
+    },
+    async_trait::async_trait,
+    gluesql_core::{
+        ast::ColumnDef,
+        data::Schema,
+        error::{AlterTableError, Result},
+        store::{AlterTable, DataRow},
+    },
+    std::{fs::File, io::Write},
+};
+
+#[async_trait(?Send)]
+impl AlterTable for CsvStorage {
+    async fn alter_column(&mut self, table_name: &str, column_def: &ColumnDef) -> Result<()> {
+        let (schema, _) = self
+            .fetch_schema(table_name)?
+            .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_owned()))?;
+        let mut column_defs = schema
+            .column_defs
+            .clone()
+            .ok_or_else(|| AlterTableError::SchemalessTableFound(table_name.to_owned()))?;
+
+        let column_index = column_defs
+            .iter()
+            .position(|ColumnDef { name, .. }| name == &column_def.name)
+            .ok_or_else(|| AlterTableError::AlteringColumnNotFound(column_def.name.to_owned()))?;
+
+        let (columns, rows) = self.scan_data(table_name)?;
+        let rows = rows
+            .map(|item| match item?.1 {
+                DataRow::Vec(mut values) => {
+                    let value = values[column_index].cast(&column_def.data_type)?;
+                    value.validate_null(column_def.nullable)?;
+                    values[column_index] = value;
+
+                    Ok(DataRow::Vec(values))
+                }
+                DataRow::Map(_) => Err(CsvStorageError::UnreachableMapTypeDataRowFound.into()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        column_defs[column_index] = column_def.clone();
+        let schema = Schema {
+            column_defs: Some(column_defs),
+            ..schema
+        };
+
+        let ddl = schema.to_ddl();
+        let mut file = File::create(self.schema_path(table_name)).map_storage_err()?;
+        file.write_all(ddl.as_bytes()).map_storage_err()?;
+
+        self.write(table_name, columns, rows.into_iter().map(Ok))
+    }
+}
@@ -1,3 +1,4 @@
+mod alter_table;
 pub mod error;
 mod store;
 mod store_mut;
@@ -5,13 +6,13 @@ mod store_mut;
 use {
     error::{CsvStorageError, ResultExt},
     gluesql_core::{
-        ast::{ColumnDef, ColumnUniqueOption, DataType},
+        ast::{ColumnDef, DataType},
         data::{Key, Schema, Value},
         error::Result,
         parse_sql::parse_data_type,
         store::{
-            AlterTable, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut, Metadata,
-            RowIter, Transaction,
+            CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut, Metadata, RowIter,
+            Sequence, SequenceMut, Transaction, Trigger, TriggerMut, View, ViewMut,
         },
         translate::translate_data_type,
     },
@@ -66,6 +67,9 @@ impl CsvStorage {
                 column_defs,
                 indexes: Vec::new(),
                 engine: None,
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                unique_keys: Vec::new(),
             };
 
             return Ok(Some((schema, true)));
@@ -128,6 +132,8 @@ impl CsvStorage {
                 .collect::<Vec<_>>())
         };
 
+        let primary_key = schema.primary_key_indexes();
+
         if let Schema {
             column_defs: Some(column_defs),
             ..
@@ -142,8 +148,6 @@ impl CsvStorage {
                 .into_records()
                 .enumerate()
                 .map(move |(index, record)| {
-                    let mut key: Option<Key> = None;
-
                     let values = record
                         .map_storage_err()?
                         .into_iter()
@@ -159,15 +163,16 @@ impl CsvStorage {
                                 data_type => value.cast(data_type)?,
                             };
 
-                            if column_def.unique == Some(ColumnUniqueOption { is_primary: true }) {
-                                key = Key::try_from(&value).map(Some)?;
-                            }
-
                             Ok(value)
                         })
                         .collect::<Result<Vec<Value>>>()?;
 
-                    let key = key.unwrap_or(Key::U64(index as u64));
+                    let key = primary_key
+                        .as_deref()
+                        .map(|indexes| Key::from_row(&values, indexes))
+                        .transpose()?
+                        .flatten()
+                        .unwrap_or(Key::U64(index as u64));
                     let row = DataRow::Vec(values);
 
                     Ok((key, row))
@@ -245,9 +250,14 @@ impl CsvStorage {
     }
 }
 
-impl AlterTable for CsvStorage {}
 impl CustomFunction for CsvStorage {}
 impl CustomFunctionMut for CsvStorage {}
+impl Sequence for CsvStorage {}
+impl SequenceMut for CsvStorage {}
+impl View for CsvStorage {}
+impl ViewMut for CsvStorage {}
+impl Trigger for CsvStorage {}
+impl TriggerMut for CsvStorage {}
 impl Index for CsvStorage {}
 impl IndexMut for CsvStorage {}
 impl Transaction for CsvStorage {}
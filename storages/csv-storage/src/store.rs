@@ -34,6 +34,11 @@ impl Store for CsvStorage {
                     .and_then(OsStr::to_str)
                     .map_storage_err(CsvStorageError::FileNotFound)?;
 
+                // skip *.types.csv and *.tmp.csv files
+                if self.data_path(table_name) != path {
+                    return Ok(None);
+                }
+
                 self.fetch_schema(table_name)?
                     .map(|(schema, _)| schema)
                     .map_storage_err(CsvStorageError::TableDoesNotExist)
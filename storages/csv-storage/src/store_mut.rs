@@ -120,7 +120,7 @@ impl StoreMut for CsvStorage {
 }
 
 impl CsvStorage {
-    fn write<T: Iterator<Item = Result<DataRow>>>(
+    pub(crate) fn write<T: Iterator<Item = Result<DataRow>>>(
         &self,
         table_name: &str,
         columns: Option<Vec<String>>,
@@ -0,0 +1,23 @@
+use {
+    gluesql_core::prelude::{Glue, Payload, PayloadVariable},
+    gluesql_csv_storage::CsvStorage,
+};
+
+#[tokio::test]
+async fn show_tables() {
+    let path = "./tests/samples/";
+    let storage = CsvStorage::new(path).unwrap();
+    let mut glue = Glue::new(storage);
+
+    let actual = glue
+        .execute("SHOW TABLES")
+        .await
+        .map(|mut payloads| payloads.remove(0));
+    let expected = Ok(Payload::ShowVariable(PayloadVariable::Tables(
+        ["Book", "City", "Employee", "Grocery", "Student"]
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect(),
+    )));
+    assert_eq!(actual, expected, "*.types.csv files are not tables");
+}
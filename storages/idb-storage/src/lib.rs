@@ -443,3 +443,9 @@ impl gluesql_core::store::Transaction for IdbStorage {}
 impl Metadata for IdbStorage {}
 impl gluesql_core::store::CustomFunction for IdbStorage {}
 impl gluesql_core::store::CustomFunctionMut for IdbStorage {}
+impl gluesql_core::store::View for IdbStorage {}
+impl gluesql_core::store::ViewMut for IdbStorage {}
+impl gluesql_core::store::Trigger for IdbStorage {}
+impl gluesql_core::store::TriggerMut for IdbStorage {}
+impl gluesql_core::store::Sequence for IdbStorage {}
+impl gluesql_core::store::SequenceMut for IdbStorage {}
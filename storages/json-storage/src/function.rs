@@ -1,7 +1,16 @@
 use {
     super::JsonStorage,
-    gluesql_core::store::{CustomFunction, CustomFunctionMut},
+    gluesql_core::store::{
+        CustomFunction, CustomFunctionMut, Sequence, SequenceMut, Trigger, TriggerMut, View,
+        ViewMut,
+    },
 };
 
 impl CustomFunction for JsonStorage {}
 impl CustomFunctionMut for JsonStorage {}
+impl Sequence for JsonStorage {}
+impl SequenceMut for JsonStorage {}
+impl View for JsonStorage {}
+impl ViewMut for JsonStorage {}
+impl Trigger for JsonStorage {}
+impl TriggerMut for JsonStorage {}
@@ -9,7 +9,6 @@ mod transaction;
 use {
     error::{JsonStorageError, OptionExt, ResultExt},
     gluesql_core::{
-        ast::ColumnUniqueOption,
         data::{value::HashMapJsonExt, Key, Schema},
         error::{Error, Result},
         store::{DataRow, Metadata, RowIter},
@@ -51,7 +50,7 @@ impl JsonStorage {
         }
 
         let schema_path = self.schema_path(table_name);
-        let column_defs = match schema_path.exists() {
+        let (column_defs, foreign_keys, checks, unique_keys) = match schema_path.exists() {
             true => {
                 let mut file = File::open(&schema_path).map_storage_err()?;
                 let mut ddl = String::new();
@@ -64,9 +63,14 @@ impl JsonStorage {
                     ));
                 }
 
-                schema.column_defs
+                (
+                    schema.column_defs,
+                    schema.foreign_keys,
+                    schema.checks,
+                    schema.unique_keys,
+                )
             }
-            false => None,
+            false => (None, Vec::new(), Vec::new(), Vec::new()),
         };
 
         Ok(Some(Schema {
@@ -74,6 +78,9 @@ impl JsonStorage {
             column_defs,
             indexes: vec![],
             engine: None,
+            foreign_keys,
+            checks,
+            unique_keys,
         }))
     }
 
@@ -145,6 +152,7 @@ impl JsonStorage {
         };
 
         let schema2 = schema.clone();
+        let primary_key = schema.primary_key_indexes();
         let rows = jsons.enumerate().map(move |(index, json)| -> Result<_> {
             let json = json?;
             let get_index_key = || index.try_into().map(Key::I64).map_storage_err();
@@ -159,17 +167,13 @@ impl JsonStorage {
                 }
             };
 
-            let mut key: Option<Key> = None;
+            let mut keys = vec![None; primary_key.as_ref().map_or(0, Vec::len)];
             let mut values = Vec::with_capacity(column_defs.len());
-            for column_def in column_defs {
+            for (i, column_def) in column_defs.iter().enumerate() {
                 let value = json.get(&column_def.name).map_storage_err(
                     JsonStorageError::ColumnDoesNotExist(column_def.name.clone()),
                 )?;
 
-                if column_def.unique == Some(ColumnUniqueOption { is_primary: true }) {
-                    key = Some(value.clone().try_into().map_storage_err()?);
-                }
-
                 let value = match value.get_type() {
                     Some(data_type) if data_type != column_def.data_type => {
                         value.cast(&column_def.data_type)?
@@ -177,12 +181,17 @@ impl JsonStorage {
                     Some(_) | None => value.clone(),
                 };
 
+                if let Some(position) = primary_key.iter().flatten().position(|index| *index == i) {
+                    keys[position] = Some(Key::try_from(&value).map_storage_err()?);
+                }
+
                 values.push(value);
             }
 
-            let key = match key {
-                Some(key) => key,
-                None => get_index_key()?,
+            let key = match keys.into_iter().collect::<Option<Vec<_>>>() {
+                Some(mut keys) if keys.len() == 1 => keys.remove(0),
+                Some(keys) if !keys.is_empty() => Key::Composite(keys),
+                Some(_) | None => get_index_key()?,
             };
             let row = DataRow::Vec(values);
 
@@ -5,7 +5,7 @@ use {
     },
     async_trait::async_trait,
     gluesql_core::{
-        data::{Key, Schema},
+        data::{Key, Schema, Value},
         error::Result,
         store::{DataRow, StoreMut},
     },
@@ -155,7 +155,7 @@ where
 }
 
 impl JsonStorage {
-    fn rewrite(&mut self, schema: Schema, rows: Vec<DataRow>) -> Result<()> {
+    pub(crate) fn rewrite(&mut self, schema: Schema, rows: Vec<DataRow>) -> Result<()> {
         let json_path = self.json_path(&schema.table_name);
         let (path, is_json) = match json_path.exists() {
             true => (json_path, true),
@@ -188,7 +188,16 @@ impl JsonStorage {
                 DataRow::Vec(values) => labels
                     .iter()
                     .zip(values.into_iter())
-                    .map(|(key, value)| Ok((key.to_string(), value.try_into()?)))
+                    .map(|(key, value)| {
+                        // JSON numbers are read back as f64, decimals are kept as strings
+                        // and cast back to the column type when scanned
+                        let value = match value {
+                            Value::Decimal(value) => JsonValue::String(value.to_string()),
+                            value => value.try_into()?,
+                        };
+
+                        Ok((key.to_string(), value))
+                    })
                     .collect::<Result<Map<String, JsonValue>>>(),
                 DataRow::Map(hash_map) => hash_map
                     .into_iter()
@@ -165,4 +165,48 @@ impl AlterTable for MemoryStorage {
 
         Ok(())
     }
+
+    async fn alter_column(&mut self, table_name: &str, column_def: &ColumnDef) -> Result<()> {
+        let item = self
+            .items
+            .get_mut(table_name)
+            .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_owned()))?;
+
+        let column_defs = item
+            .schema
+            .column_defs
+            .as_mut()
+            .ok_or_else(|| AlterTableError::SchemalessTableFound(table_name.to_owned()))?;
+
+        let column_index = column_defs
+            .iter()
+            .position(|ColumnDef { name, .. }| name == &column_def.name)
+            .ok_or_else(|| AlterTableError::AlteringColumnNotFound(column_def.name.to_owned()))?;
+
+        let values = item
+            .rows
+            .values()
+            .map(|row| match row {
+                DataRow::Vec(values) => {
+                    let value = values[column_index].cast(&column_def.data_type)?;
+                    value.validate_null(column_def.nullable)?;
+
+                    Ok(value)
+                }
+                DataRow::Map(_) => Err(Error::StorageMsg(
+                    "conflict - alter_column failed: schemaless row found".to_owned(),
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for (row, value) in item.rows.values_mut().zip(values) {
+            if let DataRow::Vec(values) = row {
+                values[column_index] = value;
+            }
+        }
+
+        column_defs[column_index] = column_def.clone();
+
+        Ok(())
+    }
 }
@@ -3,23 +3,33 @@
 mod alter_table;
 mod index;
 mod metadata;
+mod sequence;
 mod transaction;
 
 use {
     async_trait::async_trait,
     gluesql_core::{
+        ast::IndexOperator,
         chrono::Utc,
-        data::{CustomFunction as StructCustomFunction, Key, Schema, Value},
+        data::{
+            CustomFunction as StructCustomFunction, Key, Schema, Trigger as StructTrigger, Value,
+            View as StructView,
+        },
         error::Result,
-        store::{CustomFunction, CustomFunctionMut, DataRow, RowIter, Store, StoreMut},
+        store::{
+            CustomFunction, CustomFunctionMut, DataRow, RowIter, Store, StoreMut, Trigger,
+            TriggerMut, View, ViewMut,
+        },
     },
     serde::{Deserialize, Serialize},
     std::{
         collections::{BTreeMap, HashMap},
-        iter::empty,
+        iter::{empty, once},
     },
 };
 
+pub use sequence::Sequences;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Item {
     pub schema: Schema,
@@ -32,6 +42,12 @@ pub struct MemoryStorage {
     pub items: HashMap<String, Item>,
     pub metadata: HashMap<String, HashMap<String, Value>>,
     pub functions: HashMap<String, StructCustomFunction>,
+    #[serde(default)]
+    pub sequences: Sequences,
+    #[serde(default)]
+    pub views: HashMap<String, StructView>,
+    #[serde(default)]
+    pub triggers: HashMap<String, StructTrigger>,
 }
 
 #[async_trait(?Send)]
@@ -57,6 +73,54 @@ impl CustomFunctionMut for MemoryStorage {
     }
 }
 
+#[async_trait(?Send)]
+impl View for MemoryStorage {
+    async fn fetch_view(&self, view_name: &str) -> Result<Option<StructView>> {
+        Ok(self.views.get(view_name).cloned())
+    }
+
+    async fn fetch_all_views(&self) -> Result<Vec<StructView>> {
+        Ok(self.views.values().cloned().collect())
+    }
+}
+
+#[async_trait(?Send)]
+impl ViewMut for MemoryStorage {
+    async fn insert_view(&mut self, view: StructView) -> Result<()> {
+        self.views.insert(view.view_name.clone(), view);
+        Ok(())
+    }
+
+    async fn delete_view(&mut self, view_name: &str) -> Result<()> {
+        self.views.remove(view_name);
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Trigger for MemoryStorage {
+    async fn fetch_trigger(&self, trigger_name: &str) -> Result<Option<StructTrigger>> {
+        Ok(self.triggers.get(trigger_name).cloned())
+    }
+
+    async fn fetch_all_triggers(&self) -> Result<Vec<StructTrigger>> {
+        Ok(self.triggers.values().cloned().collect())
+    }
+}
+
+#[async_trait(?Send)]
+impl TriggerMut for MemoryStorage {
+    async fn insert_trigger(&mut self, trigger: StructTrigger) -> Result<()> {
+        self.triggers.insert(trigger.name.clone(), trigger);
+        Ok(())
+    }
+
+    async fn delete_trigger(&mut self, trigger_name: &str) -> Result<()> {
+        self.triggers.remove(trigger_name);
+        Ok(())
+    }
+}
+
 #[async_trait(?Send)]
 impl Store for MemoryStorage {
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
@@ -93,6 +157,43 @@ impl Store for MemoryStorage {
 
         Ok(rows)
     }
+
+    async fn scan_data_with_prefix(
+        &self,
+        table_name: &str,
+        prefix: &[Key],
+        cmp_key: Option<(&IndexOperator, Key)>,
+    ) -> Result<RowIter> {
+        let item = match self.items.get(table_name) {
+            Some(item) => item,
+            None => return Ok(Box::new(empty())),
+        };
+
+        let start = match &cmp_key {
+            Some((IndexOperator::Gt | IndexOperator::GtEq | IndexOperator::Eq, key)) => {
+                prefix.iter().cloned().chain(once(key.clone())).collect()
+            }
+            Some((IndexOperator::Lt | IndexOperator::LtEq, _)) | None => prefix.to_vec(),
+        };
+        let rows = item
+            .rows
+            .range(Key::Composite(start)..)
+            .map_while(|(key, row)| match key {
+                Key::Composite(keys) if keys.starts_with(prefix) => Some((key, keys, row)),
+                _ => None,
+            })
+            .filter(|(_, keys, _)| match &cmp_key {
+                Some((op, key)) => keys
+                    .get(prefix.len())
+                    .map(|next| op.matches(next.cmp(key)))
+                    .unwrap_or(false),
+                None => true,
+            })
+            .map(|(key, _, row)| Ok((key.clone(), row.clone())))
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(rows.into_iter()))
+    }
 }
 
 #[async_trait(?Send)]
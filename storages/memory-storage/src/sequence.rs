@@ -0,0 +1,69 @@
+use {
+    crate::MemoryStorage,
+    async_trait::async_trait,
+    gluesql_core::{
+        data::Sequence as StructSequence,
+        error::Result,
+        store::{Sequence, SequenceMut},
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::HashMap,
+        sync::{Mutex, MutexGuard, PoisonError},
+    },
+};
+
+/// `NEXTVAL` advances sequences through `&self`, so they are kept behind a lock.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Sequences(Mutex<HashMap<String, StructSequence>>);
+
+impl Sequences {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, StructSequence>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clone for Sequences {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.lock().clone()))
+    }
+}
+
+#[async_trait(?Send)]
+impl Sequence for MemoryStorage {
+    async fn fetch_sequence(&self, name: &str) -> Result<Option<StructSequence>> {
+        Ok(self.sequences.lock().get(name).cloned())
+    }
+
+    async fn fetch_all_sequences(&self) -> Result<Vec<StructSequence>> {
+        let mut sequences = self.sequences.lock().values().cloned().collect::<Vec<_>>();
+        sequences.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(sequences)
+    }
+
+    async fn next_sequence_value(&self, name: &str) -> Result<Option<i64>> {
+        self.sequences
+            .lock()
+            .get_mut(name)
+            .map(StructSequence::advance)
+            .transpose()
+    }
+}
+
+#[async_trait(?Send)]
+impl SequenceMut for MemoryStorage {
+    async fn insert_sequence(&mut self, sequence: StructSequence) -> Result<()> {
+        self.sequences
+            .lock()
+            .insert(sequence.name.clone(), sequence);
+
+        Ok(())
+    }
+
+    async fn delete_sequence(&mut self, name: &str) -> Result<()> {
+        self.sequences.lock().remove(name);
+
+        Ok(())
+    }
+}
@@ -24,11 +24,18 @@ impl Tester<MemoryStorage> for MemoryTester {
 generate_store_tests!(tokio::test, MemoryTester);
 
 generate_alter_table_tests!(tokio::test, MemoryTester);
+generate_alter_column_tests!(tokio::test, MemoryTester);
 
 generate_metadata_table_tests!(tokio::test, MemoryTester);
 
 generate_custom_function_tests!(tokio::test, MemoryTester);
 
+generate_sequence_tests!(tokio::test, MemoryTester);
+
+generate_view_tests!(tokio::test, MemoryTester);
+
+generate_trigger_tests!(tokio::test, MemoryTester);
+
 macro_rules! exec {
     ($glue: ident $sql: literal) => {
         $glue.execute($sql).await.unwrap();
@@ -63,7 +70,7 @@ async fn memory_storage_index() {
 
     assert_eq!(
         storage
-            .scan_indexed_data("Idx", "hello", None, None)
+            .scan_indexed_data("Idx", "hello", None, &[], None)
             .await
             .map(|_| ()),
         Err(Error::StorageMsg(
@@ -48,4 +48,11 @@ impl AlterTable for SharedMemoryStorage {
             .drop_column(table_name, column_name, if_exists)
             .await
     }
+
+    async fn alter_column(&mut self, table_name: &str, column_def: &ColumnDef) -> Result<()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        database.alter_column(table_name, column_def).await
+    }
 }
@@ -2,7 +2,7 @@ use {
     super::SharedMemoryStorage,
     async_trait::async_trait,
     gluesql_core::{
-        ast::{IndexOperator, OrderByExpr},
+        ast::{Expr, IndexOperator, OrderByExpr},
         data::Value,
         error::{Error, Result},
         store::{Index, IndexMut, RowIter},
@@ -16,6 +16,7 @@ impl Index for SharedMemoryStorage {
         _table_name: &str,
         _index_name: &str,
         _asc: Option<bool>,
+        _prefix: &[Value],
         _cmp_value: Option<(&IndexOperator, Value)>,
     ) -> Result<RowIter> {
         Err(Error::StorageMsg(
@@ -30,7 +31,9 @@ impl IndexMut for SharedMemoryStorage {
         &mut self,
         _table_name: &str,
         _index_name: &str,
-        _column: &OrderByExpr,
+        _columns: &[OrderByExpr],
+        _unique: bool,
+        _predicate: Option<&Expr>,
     ) -> Result<()> {
         Err(Error::StorageMsg(
             "[Shared MemoryStorage] index is not supported".to_owned(),
@@ -7,9 +7,15 @@ mod transaction;
 use {
     async_trait::async_trait,
     gluesql_core::{
-        data::{Key, Schema},
+        ast::IndexOperator,
+        data::{
+            Key, Schema, Sequence as StructSequence, Trigger as StructTrigger, View as StructView,
+        },
         error::Result,
-        store::{DataRow, Metadata, RowIter, Store, StoreMut},
+        store::{
+            DataRow, Metadata, RowIter, Sequence, SequenceMut, Store, StoreMut, Trigger,
+            TriggerMut, View, ViewMut,
+        },
     },
     memory_storage::MemoryStorage,
     std::sync::Arc,
@@ -71,6 +77,20 @@ impl Store for SharedMemoryStorage {
 
         database.scan_data(table_name).await
     }
+
+    async fn scan_data_with_prefix(
+        &self,
+        table_name: &str,
+        prefix: &[Key],
+        cmp_key: Option<(&IndexOperator, Key)>,
+    ) -> Result<RowIter> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database
+            .scan_data_with_prefix(table_name, prefix, cmp_key)
+            .await
+    }
 }
 
 #[async_trait(?Send)]
@@ -111,6 +131,115 @@ impl StoreMut for SharedMemoryStorage {
     }
 }
 
+#[async_trait(?Send)]
+impl Sequence for SharedMemoryStorage {
+    async fn fetch_sequence(&self, name: &str) -> Result<Option<StructSequence>> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database.fetch_sequence(name).await
+    }
+
+    async fn fetch_all_sequences(&self) -> Result<Vec<StructSequence>> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database.fetch_all_sequences().await
+    }
+
+    async fn next_sequence_value(&self, name: &str) -> Result<Option<i64>> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database.next_sequence_value(name).await
+    }
+}
+
+#[async_trait(?Send)]
+impl SequenceMut for SharedMemoryStorage {
+    async fn insert_sequence(&mut self, sequence: StructSequence) -> Result<()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        database.insert_sequence(sequence).await
+    }
+
+    async fn delete_sequence(&mut self, name: &str) -> Result<()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        database.delete_sequence(name).await
+    }
+}
+
+#[async_trait(?Send)]
+impl View for SharedMemoryStorage {
+    async fn fetch_view(&self, view_name: &str) -> Result<Option<StructView>> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database.fetch_view(view_name).await
+    }
+
+    async fn fetch_all_views(&self) -> Result<Vec<StructView>> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database.fetch_all_views().await
+    }
+}
+
+#[async_trait(?Send)]
+impl ViewMut for SharedMemoryStorage {
+    async fn insert_view(&mut self, view: StructView) -> Result<()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        database.insert_view(view).await
+    }
+
+    async fn delete_view(&mut self, view_name: &str) -> Result<()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        database.delete_view(view_name).await
+    }
+}
+
+#[async_trait(?Send)]
+impl Trigger for SharedMemoryStorage {
+    async fn fetch_trigger(&self, trigger_name: &str) -> Result<Option<StructTrigger>> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database.fetch_trigger(trigger_name).await
+    }
+
+    async fn fetch_all_triggers(&self) -> Result<Vec<StructTrigger>> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database.fetch_all_triggers().await
+    }
+}
+
+#[async_trait(?Send)]
+impl TriggerMut for SharedMemoryStorage {
+    async fn insert_trigger(&mut self, trigger: StructTrigger) -> Result<()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        database.insert_trigger(trigger).await
+    }
+
+    async fn delete_trigger(&mut self, trigger_name: &str) -> Result<()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        database.delete_trigger(trigger_name).await
+    }
+}
+
 impl Metadata for SharedMemoryStorage {}
 impl gluesql_core::store::CustomFunction for SharedMemoryStorage {}
 impl gluesql_core::store::CustomFunctionMut for SharedMemoryStorage {}
@@ -24,6 +24,13 @@ impl Tester<SharedMemoryStorage> for SharedMemoryTester {
 generate_store_tests!(tokio::test, SharedMemoryTester);
 
 generate_alter_table_tests!(tokio::test, SharedMemoryTester);
+generate_alter_column_tests!(tokio::test, SharedMemoryTester);
+
+generate_sequence_tests!(tokio::test, SharedMemoryTester);
+
+generate_view_tests!(tokio::test, SharedMemoryTester);
+
+generate_trigger_tests!(tokio::test, SharedMemoryTester);
 
 macro_rules! exec {
     ($glue: ident $sql: literal) => {
@@ -60,7 +67,7 @@ async fn shared_memory_storage_index() {
 
     assert_eq!(
         storage
-            .scan_indexed_data("Idx", "hello", None, None)
+            .scan_indexed_data("Idx", "hello", None, &[], None)
             .await
             .map(|_| ()),
         Err(Error::StorageMsg(
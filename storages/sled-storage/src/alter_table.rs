@@ -51,6 +51,9 @@ impl AlterTable for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
                 ..
             } = old_schema
                 .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_owned()).into())
@@ -61,6 +64,9 @@ impl AlterTable for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
             };
 
             bincode::serialize(&old_snapshot)
@@ -159,6 +165,9 @@ impl AlterTable for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
                 ..
             } = snapshot
                 .get(txid, None)
@@ -207,6 +216,9 @@ impl AlterTable for SledStorage {
                 column_defs: Some(column_defs),
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
             };
             let (snapshot, _) = snapshot.update(txid, schema);
             let value = bincode::serialize(&snapshot)
@@ -259,6 +271,9 @@ impl AlterTable for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
                 ..
             } = schema_snapshot
                 .get(txid, None)
@@ -356,6 +371,9 @@ impl AlterTable for SledStorage {
                 column_defs: Some(column_defs),
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
             };
             let (schema_snapshot, _) = schema_snapshot.update(txid, schema);
             let schema_value = bincode::serialize(&schema_snapshot)
@@ -411,6 +429,9 @@ impl AlterTable for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
                 ..
             } = schema_snapshot
                 .get(txid, None)
@@ -494,6 +515,9 @@ impl AlterTable for SledStorage {
                 column_defs: Some(column_defs),
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
             };
             let (schema_snapshot, _) = schema_snapshot.update(txid, schema);
             let schema_value = bincode::serialize(&schema_snapshot)
@@ -514,4 +538,133 @@ impl AlterTable for SledStorage {
 
         Ok(())
     }
+
+    async fn alter_column(&mut self, table_name: &str, column_def: &ColumnDef) -> Result<()> {
+        let prefix = format!("data/{}/", table_name);
+        let items = self
+            .tree
+            .scan_prefix(prefix.as_bytes())
+            .map(|item| item.map_err(err_into))
+            .collect::<Result<Vec<_>>>()?;
+
+        let state = &self.state;
+        let tx_timeout = self.tx_timeout;
+        let tx_result = self.tree.transaction(move |tree| {
+            let (txid, autocommit) = match lock::acquire(tree, state, tx_timeout)? {
+                LockAcquired::Success { txid, autocommit } => (txid, autocommit),
+                LockAcquired::RollbackAndRetry { lock_txid } => {
+                    return Ok(TxPayload::RollbackAndRetry(lock_txid));
+                }
+            };
+
+            let (schema_key, schema_snapshot) = fetch_schema(tree, table_name)?;
+            let schema_snapshot = schema_snapshot
+                .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_owned()).into())
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let Schema {
+                table_name,
+                column_defs,
+                indexes,
+                engine,
+                foreign_keys,
+                checks,
+                unique_keys,
+                ..
+            } = schema_snapshot
+                .get(txid, None)
+                .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_owned()).into())
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let mut column_defs = column_defs
+                .ok_or_else(|| AlterTableError::SchemalessTableFound(table_name.to_owned()).into())
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let column_index = column_defs
+                .iter()
+                .position(|ColumnDef { name, .. }| name == &column_def.name)
+                .ok_or_else(|| {
+                    AlterTableError::AlteringColumnNotFound(column_def.name.to_owned()).into()
+                })
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            // migrate data
+            for (key, snapshot) in items.iter() {
+                let snapshot: Snapshot<DataRow> = bincode::deserialize(snapshot)
+                    .map_err(err_into)
+                    .map_err(ConflictableTransactionError::Abort)?;
+                let row = match snapshot.clone().extract(txid, None) {
+                    Some(row) => row,
+                    None => {
+                        continue;
+                    }
+                };
+
+                let mut values = match row {
+                    DataRow::Vec(values) => values,
+                    DataRow::Map(_) => {
+                        return Err(Error::StorageMsg(
+                            "conflict - alter_column failed: schemaless row found".to_owned(),
+                        ))
+                        .map_err(ConflictableTransactionError::Abort);
+                    }
+                };
+
+                let value = values[column_index]
+                    .cast(&column_def.data_type)
+                    .and_then(|value| {
+                        value.validate_null(column_def.nullable)?;
+
+                        Ok(value)
+                    })
+                    .map_err(ConflictableTransactionError::Abort)?;
+                values[column_index] = value;
+
+                let (snapshot, _) = snapshot.update(txid, values.into());
+                let snapshot = bincode::serialize(&snapshot)
+                    .map_err(err_into)
+                    .map_err(ConflictableTransactionError::Abort)?;
+
+                tree.insert(key, snapshot)?;
+
+                if !autocommit {
+                    let temp_key = key::temp_data(txid, key);
+
+                    tree.insert(temp_key, key)?;
+                }
+            }
+
+            // update schema
+            column_defs[column_index] = column_def.clone();
+
+            let temp_key = key::temp_schema(txid, &table_name);
+
+            let schema = Schema {
+                table_name,
+                column_defs: Some(column_defs),
+                indexes,
+                engine,
+                foreign_keys,
+                checks,
+                unique_keys,
+            };
+            let (schema_snapshot, _) = schema_snapshot.update(txid, schema);
+            let schema_value = bincode::serialize(&schema_snapshot)
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+            tree.insert(schema_key.as_bytes(), schema_value)?;
+
+            if !autocommit {
+                tree.insert(temp_key, schema_key.as_bytes())?;
+            }
+
+            Ok(TxPayload::Success)
+        });
+
+        if self.check_retry(tx_result)? {
+            self.alter_column(table_name, column_def).await?;
+        }
+
+        Ok(())
+    }
 }
@@ -4,7 +4,11 @@ use {
         lock::{get_txdata_key, Lock, TxData},
         SledStorage, Snapshot,
     },
-    gluesql_core::{data::Schema, error::Result, store::DataRow},
+    gluesql_core::{
+        data::{Schema, Sequence, View},
+        error::Result,
+        store::DataRow,
+    },
     std::time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -108,6 +112,8 @@ impl SledStorage {
         for txid in txids {
             gc_txid!(txid, key::temp_data_prefix(txid), DataRow);
             gc_txid!(txid, key::temp_schema_prefix(txid), Schema);
+            gc_txid!(txid, key::temp_sequence_prefix(txid), Sequence);
+            gc_txid!(txid, key::temp_view_prefix(txid), View);
 
             for (temp_key, data_key) in fetch_keys(key::temp_index_prefix(txid))? {
                 let snapshots: Option<Vec<Snapshot<Vec<u8>>>> = self
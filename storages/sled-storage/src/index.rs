@@ -1,7 +1,7 @@
 use {
     super::{
         err_into,
-        index_sync::{build_index_key, build_index_key_prefix},
+        index_sync::{build_composite_index_key, build_index_key, build_index_key_prefix},
         lock, SledStorage, Snapshot, State,
     },
     async_trait::async_trait,
@@ -9,7 +9,7 @@ use {
         ast::IndexOperator,
         data::{Key, Value},
         error::{Error, IndexError, Result},
-        store::{DataRow, Index, RowIter},
+        store::{DataRow, Index, RowIter, Store},
     },
     iter_enum::{DoubleEndedIterator, Iterator},
     sled::IVec,
@@ -24,8 +24,21 @@ impl Index for SledStorage {
         table_name: &str,
         index_name: &str,
         asc: Option<bool>,
+        prefix: &[Value],
         cmp_value: Option<(&IndexOperator, Value)>,
     ) -> Result<RowIter> {
+        let columns = self
+            .fetch_schema(table_name)
+            .await?
+            .and_then(|schema| {
+                schema
+                    .indexes
+                    .into_iter()
+                    .find(|index| index.name == index_name)
+            })
+            .map(|index| index.columns)
+            .ok_or_else(|| IndexError::IndexNameDoesNotExist(index_name.to_owned()))?;
+
         let data_keys = {
             #[derive(Iterator, DoubleEndedIterator)]
             enum DataIds<I1, I2, I3, I4> {
@@ -38,6 +51,37 @@ impl Index for SledStorage {
             let map = |item: std::result::Result<_, _>| item.map(|(_, v)| v);
 
             match cmp_value {
+                _ if columns.len() > 1 => {
+                    let prefix_key =
+                        build_composite_index_key(table_name, index_name, &columns, prefix)?;
+                    let (lower, upper) = match cmp_value {
+                        None => (prefix_key.clone(), upper_bound(&prefix_key)),
+                        Some((op, value)) => {
+                            let values = prefix.iter().cloned().chain(once(value));
+                            let key = build_composite_index_key(
+                                table_name,
+                                index_name,
+                                &columns,
+                                &values.collect::<Vec<_>>(),
+                            )?;
+                            // keys of a descending column are sorted in reverse
+                            let op = match columns.get(prefix.len()) {
+                                Some(column) if column.asc == Some(false) => op.clone().reverse(),
+                                _ => op.clone(),
+                            };
+
+                            match op {
+                                IndexOperator::Eq => (key.clone(), upper_bound(&key)),
+                                IndexOperator::Gt => (upper_bound(&key), upper_bound(&prefix_key)),
+                                IndexOperator::GtEq => (key, upper_bound(&prefix_key)),
+                                IndexOperator::Lt => (prefix_key, key),
+                                IndexOperator::LtEq => (prefix_key, upper_bound(&key)),
+                            }
+                        }
+                    };
+
+                    DataIds::Range(self.tree.range(lower..upper).map(map))
+                }
                 None => {
                     let prefix = build_index_key_prefix(table_name, index_name);
 
@@ -154,3 +198,17 @@ impl Index for SledStorage {
         })
     }
 }
+
+/// Smallest key greater than every key starting with `prefix`
+fn upper_bound(prefix: &[u8]) -> Vec<u8> {
+    let mut upper = prefix.to_vec();
+
+    while let Some(byte) = upper.pop() {
+        if byte < u8::MAX {
+            upper.push(byte + 1);
+            break;
+        }
+    }
+
+    upper
+}
@@ -10,7 +10,7 @@ use {
     async_io::block_on,
     async_trait::async_trait,
     gluesql_core::{
-        ast::OrderByExpr,
+        ast::{Expr, OrderByExpr},
         chrono::Utc,
         data::{Schema, SchemaIndex, SchemaIndexOrd},
         error::{Error, IndexError, Result},
@@ -43,7 +43,9 @@ impl IndexMut for SledStorage {
         &mut self,
         table_name: &str,
         index_name: &str,
-        column: &OrderByExpr,
+        columns: &[OrderByExpr],
+        unique: bool,
+        predicate: Option<&Expr>,
     ) -> Result<()> {
         let rows = self
             .scan_data(table_name)
@@ -60,8 +62,6 @@ impl IndexMut for SledStorage {
                 }
             };
 
-            let index_expr = &column.expr;
-
             let (schema_key, schema_snapshot) = fetch_schema(tree, table_name)?;
             let schema_snapshot = schema_snapshot
                 .ok_or_else(|| IndexError::TableNotFound(table_name.to_owned()).into())
@@ -72,6 +72,9 @@ impl IndexMut for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
                 ..
             } = schema
                 .ok_or_else(|| IndexError::ConflictTableNotFound(table_name.to_owned()).into())
@@ -84,8 +87,10 @@ impl IndexMut for SledStorage {
 
             let index = SchemaIndex {
                 name: index_name.to_owned(),
-                expr: index_expr.clone(),
+                columns: columns.to_vec(),
                 order: SchemaIndexOrd::Both,
+                unique,
+                predicate: predicate.cloned(),
                 created: Utc::now().naive_utc(),
             };
 
@@ -99,6 +104,9 @@ impl IndexMut for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
             };
 
             let index_sync = IndexSync::from_schema(tree, txid, &schema);
@@ -130,7 +138,8 @@ impl IndexMut for SledStorage {
         });
 
         if self.check_retry(tx_result)? {
-            self.create_index(table_name, index_name, column).await?;
+            self.create_index(table_name, index_name, columns, unique, predicate)
+                .await?;
         }
 
         Ok(())
@@ -162,6 +171,9 @@ impl IndexMut for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
                 ..
             } = schema
                 .ok_or_else(|| IndexError::ConflictTableNotFound(table_name.to_owned()).into())
@@ -184,6 +196,9 @@ impl IndexMut for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                foreign_keys,
+                checks,
+                unique_keys,
             };
 
             let index_sync = IndexSync::from_schema(tree, txid, &schema);
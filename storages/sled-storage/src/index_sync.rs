@@ -1,8 +1,11 @@
 use {
     super::{err_into, fetch_schema, key, Snapshot},
     gluesql_core::{
-        ast::Expr,
-        data::schema::{Schema, SchemaIndex},
+        ast::OrderByExpr,
+        data::{
+            schema::{Schema, SchemaIndex},
+            Key,
+        },
         error::{Error, IndexError, Result},
         executor::evaluate_stateless,
         prelude::Value,
@@ -103,22 +106,12 @@ impl<'a> IndexSync<'a> {
         data_key: &IVec,
         row: &DataRow,
     ) -> ConflictableTransactionResult<(), Error> {
-        let SchemaIndex {
-            name: index_name,
-            expr: index_expr,
-            ..
-        } = index;
+        let index_key =
+            evaluate_index_key(self.table_name, index, self.columns.as_deref(), row).await?;
 
-        let index_key = &evaluate_index_key(
-            self.table_name,
-            index_name,
-            index_expr,
-            self.columns.as_deref(),
-            row,
-        )
-        .await?;
-
-        self.insert_index_data(index_key, data_key)?;
+        if let Some(index_key) = index_key {
+            self.insert_index_data(&index_key, data_key)?;
+        }
 
         Ok(())
     }
@@ -130,32 +123,21 @@ impl<'a> IndexSync<'a> {
         new_row: &DataRow,
     ) -> ConflictableTransactionResult<(), Error> {
         for index in self.indexes.iter() {
-            let SchemaIndex {
-                name: index_name,
-                expr: index_expr,
-                ..
-            } = index;
-
-            let old_index_key = &evaluate_index_key(
-                self.table_name,
-                index_name,
-                index_expr,
-                self.columns.as_deref(),
-                old_row,
-            )
-            .await?;
-
-            let new_index_key = &evaluate_index_key(
-                self.table_name,
-                index_name,
-                index_expr,
-                self.columns.as_deref(),
-                new_row,
-            )
-            .await?;
-
-            self.delete_index_data(old_index_key, data_key)?;
-            self.insert_index_data(new_index_key, data_key)?;
+            let old_index_key =
+                evaluate_index_key(self.table_name, index, self.columns.as_deref(), old_row)
+                    .await?;
+
+            let new_index_key =
+                evaluate_index_key(self.table_name, index, self.columns.as_deref(), new_row)
+                    .await?;
+
+            if let Some(old_index_key) = old_index_key {
+                self.delete_index_data(&old_index_key, data_key)?;
+            }
+
+            if let Some(new_index_key) = new_index_key {
+                self.insert_index_data(&new_index_key, data_key)?;
+            }
         }
 
         Ok(())
@@ -179,22 +161,12 @@ impl<'a> IndexSync<'a> {
         data_key: &IVec,
         row: &DataRow,
     ) -> ConflictableTransactionResult<(), Error> {
-        let SchemaIndex {
-            name: index_name,
-            expr: index_expr,
-            ..
-        } = index;
+        let index_key =
+            evaluate_index_key(self.table_name, index, self.columns.as_deref(), row).await?;
 
-        let index_key = &evaluate_index_key(
-            self.table_name,
-            index_name,
-            index_expr,
-            self.columns.as_deref(),
-            row,
-        )
-        .await?;
-
-        self.delete_index_data(index_key, data_key)?;
+        if let Some(index_key) = index_key {
+            self.delete_index_data(&index_key, data_key)?;
+        }
 
         Ok(())
     }
@@ -267,22 +239,55 @@ impl<'a> IndexSync<'a> {
     }
 }
 
+/// Index key of the row, `None` when the row does not match the predicate of a partial index
 async fn evaluate_index_key(
     table_name: &str,
-    index_name: &str,
-    index_expr: &Expr,
+    index: &SchemaIndex,
     columns: Option<&[String]>,
     row: &DataRow,
-) -> ConflictableTransactionResult<Vec<u8>, Error> {
-    let context = Some(row.as_context(columns));
-    let evaluated = evaluate_stateless(context, index_expr)
-        .await
-        .map_err(ConflictableTransactionError::Abort)?;
-    let value: Value = evaluated
-        .try_into()
+) -> ConflictableTransactionResult<Option<Vec<u8>>, Error> {
+    let SchemaIndex {
+        name: index_name,
+        columns: index_columns,
+        predicate,
+        ..
+    } = index;
+
+    if let Some(predicate) = predicate {
+        let context = Some(row.as_context(columns));
+        let evaluated = evaluate_stateless(context, predicate)
+            .await
+            .map_err(ConflictableTransactionError::Abort)?;
+        let matched = match evaluated.is_null() {
+            true => Ok(false),
+            false => bool::try_from(evaluated),
+        }
         .map_err(ConflictableTransactionError::Abort)?;
 
-    build_index_key(table_name, index_name, value).map_err(ConflictableTransactionError::Abort)
+        if !matched {
+            return Ok(None);
+        }
+    }
+
+    let mut values = Vec::with_capacity(index_columns.len());
+    for expr in index.exprs() {
+        let context = Some(row.as_context(columns));
+        let evaluated = evaluate_stateless(context, expr)
+            .await
+            .map_err(ConflictableTransactionError::Abort)?;
+        let value: Value = evaluated
+            .try_into()
+            .map_err(ConflictableTransactionError::Abort)?;
+
+        values.push(value);
+    }
+
+    match <[Value; 1]>::try_from(values) {
+        Ok([value]) => build_index_key(table_name, index_name, value),
+        Err(values) => build_composite_index_key(table_name, index_name, index_columns, &values),
+    }
+    .map(Some)
+    .map_err(ConflictableTransactionError::Abort)
 }
 
 pub fn build_index_key_prefix(table_name: &str, index_name: &str) -> Vec<u8> {
@@ -295,3 +300,25 @@ pub fn build_index_key(table_name: &str, index_name: &str, value: Value) -> Resu
         .chain(value.to_cmp_be_bytes()?)
         .collect::<Vec<_>>())
 }
+
+/// Key of a composite index, or its leading part when fewer `values` than `columns` are given.
+/// Each value is an escaped and terminated segment, inverted for a descending column.
+pub fn build_composite_index_key(
+    table_name: &str,
+    index_name: &str,
+    columns: &[OrderByExpr],
+    values: &[Value],
+) -> Result<Vec<u8>> {
+    let mut key = build_index_key_prefix(table_name, index_name);
+
+    for (OrderByExpr { asc, .. }, value) in columns.iter().zip(values) {
+        let segment = Key::Composite(vec![Key::try_from(value)?]).to_cmp_be_bytes()?;
+
+        match asc {
+            Some(false) => key.extend(segment.into_iter().map(|byte| !byte)),
+            _ => key.extend(segment),
+        }
+    }
+
+    Ok(key)
+}
@@ -3,6 +3,8 @@ use sled::IVec;
 const TEMP_DATA: &str = "temp_data/";
 const TEMP_SCHEMA: &str = "temp_schema/";
 const TEMP_INDEX: &str = "temp_index/";
+const TEMP_SEQUENCE: &str = "temp_sequence/";
+const TEMP_VIEW: &str = "temp_view/";
 
 pub fn data_prefix(table_name: &str) -> String {
     format!("data/{table_name}/")
@@ -39,6 +41,14 @@ pub fn temp_index_prefix(txid: u64) -> IVec {
     IVec::from_iter(prefix!(txid, TEMP_INDEX))
 }
 
+pub fn temp_sequence_prefix(txid: u64) -> IVec {
+    IVec::from_iter(prefix!(txid, TEMP_SEQUENCE))
+}
+
+pub fn temp_view_prefix(txid: u64) -> IVec {
+    IVec::from_iter(prefix!(txid, TEMP_VIEW))
+}
+
 pub fn temp_data(txid: u64, data_key: &IVec) -> IVec {
     IVec::from_iter(prefix!(txid, TEMP_DATA).chain(data_key.iter().copied()))
 }
@@ -54,3 +64,11 @@ pub fn temp_schema(txid: u64, table_name: &str) -> IVec {
 pub fn temp_index(txid: u64, index_key: &[u8]) -> IVec {
     IVec::from_iter(prefix!(txid, TEMP_INDEX).chain(index_key.iter().copied()))
 }
+
+pub fn temp_sequence(txid: u64, name: &str) -> IVec {
+    IVec::from_iter(prefix!(txid, TEMP_SEQUENCE).chain(name.as_bytes().iter().copied()))
+}
+
+pub fn temp_view(txid: u64, view_name: &str) -> IVec {
+    IVec::from_iter(prefix!(txid, TEMP_VIEW).chain(view_name.as_bytes().iter().copied()))
+}
@@ -8,10 +8,12 @@ mod index_mut;
 mod index_sync;
 mod key;
 mod lock;
+mod sequence;
 mod snapshot;
 mod store;
 mod store_mut;
 mod transaction;
+mod view;
 
 // re-export
 pub use sled;
@@ -148,3 +150,5 @@ fn fetch_schema(
 impl Metadata for SledStorage {}
 impl gluesql_core::store::CustomFunction for SledStorage {}
 impl gluesql_core::store::CustomFunctionMut for SledStorage {}
+impl gluesql_core::store::Trigger for SledStorage {}
+impl gluesql_core::store::TriggerMut for SledStorage {}
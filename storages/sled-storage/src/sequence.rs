@@ -0,0 +1,160 @@
+use {
+    super::{
+        err_into, key,
+        lock::{self, LockAcquired},
+        tx_err_into, SledStorage, Snapshot, State,
+    },
+    async_trait::async_trait,
+    gluesql_core::{
+        data::Sequence as StructSequence,
+        error::Result,
+        store::{Sequence, SequenceMut},
+    },
+    sled::transaction::ConflictableTransactionError,
+};
+
+impl SledStorage {
+    const SEQUENCE_PREFIX: &str = "sequence/";
+
+    /// Replaces the sequence by the one `update` returns within the current transaction, so
+    /// that ROLLBACK restores it. The newest version is read rather than the one visible to
+    /// the transaction, as sequence values must never be handed out twice.
+    fn update_sequence<T>(
+        &self,
+        name: &str,
+        update: &dyn Fn(Option<StructSequence>) -> Result<(Option<StructSequence>, T)>,
+    ) -> Result<T> {
+        let state = &self.state;
+        let tx_timeout = self.tx_timeout;
+
+        let tx_result = self.tree.transaction(move |tree| {
+            let txid = match lock::acquire(tree, state, tx_timeout)? {
+                LockAcquired::Success { txid, .. } => txid,
+                LockAcquired::RollbackAndRetry { lock_txid } => {
+                    return Ok(Err(lock_txid));
+                }
+            };
+
+            let key = format!("{}{name}", SledStorage::SEQUENCE_PREFIX);
+            let temp_key = key::temp_sequence(txid, name);
+
+            let snapshot: Option<Snapshot<StructSequence>> = tree
+                .get(key.as_bytes())?
+                .map(|v| bincode::deserialize(&v))
+                .transpose()
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let sequence = snapshot.as_ref().and_then(Snapshot::latest);
+            let found = sequence.is_some();
+            let (sequence, output) =
+                update(sequence).map_err(ConflictableTransactionError::Abort)?;
+
+            let snapshot = match (snapshot, sequence) {
+                (Some(snapshot), Some(sequence)) => snapshot.update(txid, sequence).0,
+                (None, Some(sequence)) => Snapshot::new(txid, sequence),
+                (Some(snapshot), None) if found => snapshot.delete(txid).0,
+                (_, None) => {
+                    return Ok(Ok(output));
+                }
+            };
+            let snapshot = bincode::serialize(&snapshot)
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            tree.insert(key.as_bytes(), snapshot)?;
+            tree.insert(temp_key, key.as_bytes())?;
+
+            Ok(Ok(output))
+        });
+
+        match tx_result.map_err(tx_err_into)? {
+            Ok(output) => Ok(output),
+            Err(lock_txid) => {
+                self.rollback_txid(lock_txid)?;
+                self.tree
+                    .transaction(move |tree| lock::release(tree, lock_txid))
+                    .map_err(tx_err_into)?;
+
+                self.update_sequence(name, update)
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Sequence for SledStorage {
+    async fn fetch_sequence(&self, name: &str) -> Result<Option<StructSequence>> {
+        let (txid, created_at, temp) = match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => (txid, created_at, false),
+            State::Idle => lock::register(&self.tree, self.id_offset)
+                .map(|(txid, created_at)| (txid, created_at, true))?,
+        };
+        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+
+        let key = format!("{}{name}", SledStorage::SEQUENCE_PREFIX);
+        let sequence = self
+            .tree
+            .get(key.as_bytes())
+            .map_err(err_into)?
+            .map(|v| bincode::deserialize(&v))
+            .transpose()
+            .map_err(err_into)?
+            .and_then(|snapshot: Snapshot<StructSequence>| snapshot.extract(txid, lock_txid));
+
+        if temp {
+            lock::unregister(&self.tree, txid)?;
+        }
+
+        Ok(sequence)
+    }
+
+    async fn fetch_all_sequences(&self) -> Result<Vec<StructSequence>> {
+        let (txid, created_at) = match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => (txid, created_at),
+            State::Idle => lock::register(&self.tree, self.id_offset)?,
+        };
+        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+
+        self.tree
+            .scan_prefix(SledStorage::SEQUENCE_PREFIX)
+            .map(move |item| {
+                let (_, value) = item.map_err(err_into)?;
+                let snapshot: Snapshot<StructSequence> =
+                    bincode::deserialize(&value).map_err(err_into)?;
+                let sequence = snapshot.extract(txid, lock_txid);
+
+                Ok(sequence)
+            })
+            .filter_map(|result| result.transpose())
+            .collect::<Result<Vec<_>>>()
+    }
+
+    async fn next_sequence_value(&self, name: &str) -> Result<Option<i64>> {
+        self.update_sequence(name, &|sequence| match sequence {
+            Some(mut sequence) => {
+                let value = sequence.advance()?;
+
+                Ok((Some(sequence), Some(value)))
+            }
+            None => Ok((None, None)),
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl SequenceMut for SledStorage {
+    async fn insert_sequence(&mut self, sequence: StructSequence) -> Result<()> {
+        let name = sequence.name.clone();
+
+        self.update_sequence(&name, &|_| Ok((Some(sequence.clone()), ())))
+    }
+
+    async fn delete_sequence(&mut self, name: &str) -> Result<()> {
+        self.update_sequence(name, &|_| Ok((None, ())))
+    }
+}
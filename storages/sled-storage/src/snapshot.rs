@@ -122,6 +122,14 @@ impl<T: Clone> Snapshot<T> {
         None
     }
 
+    /// Newest version which is not deleted, for the holder of the lock.
+    pub fn latest(&self) -> Option<T> {
+        self.0
+            .first()
+            .filter(|item| item.deleted_by.is_none())
+            .map(|item| item.data.clone())
+    }
+
     pub fn gc(self, txid: u64) -> Option<Self> {
         let items = self
             .0
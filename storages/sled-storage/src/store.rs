@@ -2,15 +2,47 @@ use {
     super::{err_into, key, lock, SledStorage, Snapshot, State},
     async_trait::async_trait,
     gluesql_core::{
+        ast::IndexOperator,
         data::{Key, Schema},
         error::{Error, Result},
         store::{DataRow, RowIter, Store},
     },
-    std::str,
+    std::{cmp::Ordering, iter::once, str},
 };
 
 impl SledStorage {
     const SCHEMA_PREFIX: &str = "schema/";
+
+    fn scan_data_by_key_prefix(&self, table_name: &str, key_prefix: Vec<u8>) -> Result<RowIter> {
+        let (txid, created_at) = match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => (txid, created_at),
+            State::Idle => {
+                return Err(Error::StorageMsg(
+                    "conflict - scan_data failed, lock does not exist".to_owned(),
+                ));
+            }
+        };
+        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+
+        let prefix_len = key::data_prefix(table_name).len();
+        let result_set = self
+            .tree
+            .scan_prefix(key::data(table_name, key_prefix))
+            .map(move |item| {
+                let (key, value) = item.map_err(err_into)?;
+                let key = key.subslice(prefix_len, key.len() - prefix_len).to_vec();
+                let snapshot: Snapshot<DataRow> = bincode::deserialize(&value).map_err(err_into)?;
+                let row = snapshot.extract(txid, lock_txid);
+                let item = row.map(|row| (Key::Bytea(key), row));
+
+                Ok(item)
+            })
+            .filter_map(|item| item.transpose());
+
+        Ok(Box::new(result_set))
+    }
 }
 
 #[async_trait(?Send)]
@@ -93,34 +125,41 @@ impl Store for SledStorage {
     }
 
     async fn scan_data(&self, table_name: &str) -> Result<RowIter> {
-        let (txid, created_at) = match self.state {
-            State::Transaction {
-                txid, created_at, ..
-            } => (txid, created_at),
-            State::Idle => {
-                return Err(Error::StorageMsg(
-                    "conflict - scan_data failed, lock does not exist".to_owned(),
-                ));
+        self.scan_data_by_key_prefix(table_name, Vec::new())
+    }
+
+    async fn scan_data_with_prefix(
+        &self,
+        table_name: &str,
+        prefix: &[Key],
+        cmp_key: Option<(&IndexOperator, Key)>,
+    ) -> Result<RowIter> {
+        let key_prefix = Key::Composite(prefix.to_vec()).to_cmp_be_bytes()?;
+        let rows = self.scan_data_by_key_prefix(table_name, key_prefix)?;
+        let (op, bound) = match cmp_key {
+            Some((op, key)) => {
+                let keys = prefix.iter().cloned().chain(once(key)).collect();
+
+                (op.clone(), Key::Composite(keys).to_cmp_be_bytes()?)
             }
+            None => return Ok(rows),
         };
-        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
 
-        let prefix = key::data_prefix(table_name);
-        let prefix_len = prefix.len();
-        let result_set = self
-            .tree
-            .scan_prefix(prefix.as_bytes())
-            .map(move |item| {
-                let (key, value) = item.map_err(err_into)?;
-                let key = key.subslice(prefix_len, key.len() - prefix_len).to_vec();
-                let snapshot: Snapshot<DataRow> = bincode::deserialize(&value).map_err(err_into)?;
-                let row = snapshot.extract(txid, lock_txid);
-                let item = row.map(|row| (Key::Bytea(key), row));
+        // each element of an encoded composite key is terminated, so the next key column
+        // compares as the raw bytes following the prefix
+        let rows = rows.filter(move |item| match item {
+            Ok((Key::Bytea(key), _)) => {
+                let ordering = match key.starts_with(&bound) {
+                    true => Ordering::Equal,
+                    false => key.as_slice().cmp(bound.as_slice()),
+                };
 
-                Ok(item)
-            })
-            .filter_map(|item| item.transpose());
+                op.matches(ordering)
+            }
+            Ok(_) => false,
+            Err(_) => true,
+        });
 
-        Ok(Box::new(result_set))
+        Ok(Box::new(rows))
     }
 }
@@ -0,0 +1,136 @@
+use {
+    super::{
+        err_into, key,
+        lock::{self, LockAcquired},
+        tx_err_into, SledStorage, Snapshot, State,
+    },
+    async_trait::async_trait,
+    gluesql_core::{
+        data::View as StructView,
+        error::Result,
+        store::{View, ViewMut},
+    },
+    sled::transaction::ConflictableTransactionError,
+};
+
+impl SledStorage {
+    const VIEW_PREFIX: &str = "view/";
+
+    /// Replaces the view by `view` within the current transaction, so that ROLLBACK restores it
+    fn update_view(&self, view_name: &str, view: Option<&StructView>) -> Result<()> {
+        let state = &self.state;
+        let tx_timeout = self.tx_timeout;
+
+        let tx_result = self.tree.transaction(move |tree| {
+            let txid = match lock::acquire(tree, state, tx_timeout)? {
+                LockAcquired::Success { txid, .. } => txid,
+                LockAcquired::RollbackAndRetry { lock_txid } => {
+                    return Ok(Err(lock_txid));
+                }
+            };
+
+            let key = format!("{}{view_name}", SledStorage::VIEW_PREFIX);
+            let temp_key = key::temp_view(txid, view_name);
+
+            let snapshot: Option<Snapshot<StructView>> = tree
+                .get(key.as_bytes())?
+                .map(|v| bincode::deserialize(&v))
+                .transpose()
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let snapshot = match (snapshot, view) {
+                (Some(snapshot), Some(view)) => snapshot.update(txid, view.clone()).0,
+                (None, Some(view)) => Snapshot::new(txid, view.clone()),
+                (Some(snapshot), None) => snapshot.delete(txid).0,
+                (None, None) => {
+                    return Ok(Ok(()));
+                }
+            };
+            let snapshot = bincode::serialize(&snapshot)
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            tree.insert(key.as_bytes(), snapshot)?;
+            tree.insert(temp_key, key.as_bytes())?;
+
+            Ok(Ok(()))
+        });
+
+        match tx_result.map_err(tx_err_into)? {
+            Ok(()) => Ok(()),
+            Err(lock_txid) => {
+                self.rollback_txid(lock_txid)?;
+                self.tree
+                    .transaction(move |tree| lock::release(tree, lock_txid))
+                    .map_err(tx_err_into)?;
+
+                self.update_view(view_name, view)
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl View for SledStorage {
+    async fn fetch_view(&self, view_name: &str) -> Result<Option<StructView>> {
+        let (txid, created_at, temp) = match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => (txid, created_at, false),
+            State::Idle => lock::register(&self.tree, self.id_offset)
+                .map(|(txid, created_at)| (txid, created_at, true))?,
+        };
+        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+
+        let key = format!("{}{view_name}", SledStorage::VIEW_PREFIX);
+        let view = self
+            .tree
+            .get(key.as_bytes())
+            .map_err(err_into)?
+            .map(|v| bincode::deserialize(&v))
+            .transpose()
+            .map_err(err_into)?
+            .and_then(|snapshot: Snapshot<StructView>| snapshot.extract(txid, lock_txid));
+
+        if temp {
+            lock::unregister(&self.tree, txid)?;
+        }
+
+        Ok(view)
+    }
+
+    async fn fetch_all_views(&self) -> Result<Vec<StructView>> {
+        let (txid, created_at) = match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => (txid, created_at),
+            State::Idle => lock::register(&self.tree, self.id_offset)?,
+        };
+        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+
+        self.tree
+            .scan_prefix(SledStorage::VIEW_PREFIX)
+            .map(move |item| {
+                let (_, value) = item.map_err(err_into)?;
+                let snapshot: Snapshot<StructView> =
+                    bincode::deserialize(&value).map_err(err_into)?;
+                let view = snapshot.extract(txid, lock_txid);
+
+                Ok(view)
+            })
+            .filter_map(|result| result.transpose())
+            .collect::<Result<Vec<_>>>()
+    }
+}
+
+#[async_trait(?Send)]
+impl ViewMut for SledStorage {
+    async fn insert_view(&mut self, view: StructView) -> Result<()> {
+        self.update_view(&view.view_name, Some(&view))
+    }
+
+    async fn delete_view(&mut self, view_name: &str) -> Result<()> {
+        self.update_view(view_name, None)
+    }
+}
@@ -90,7 +90,10 @@ generate_store_tests!(tokio::test, SledTester);
 generate_index_tests!(tokio::test, SledTester);
 generate_transaction_tests!(tokio::test, SledTester);
 generate_alter_table_tests!(tokio::test, SledTester);
+generate_alter_column_tests!(tokio::test, SledTester);
 generate_alter_table_index_tests!(tokio::test, SledTester);
 generate_transaction_alter_table_tests!(tokio::test, SledTester);
 generate_transaction_index_tests!(tokio::test, SledTester);
 generate_metadata_index_tests!(tokio::test, SledTester);
+generate_sequence_tests!(tokio::test, SledTester);
+generate_view_tests!(tokio::test, SledTester);
@@ -175,6 +175,39 @@ async fn sled_transaction_schema_mut() {
     );
 }
 
+#[tokio::test]
+async fn sled_transaction_view_mut() {
+    let path = &format!("{}/transaction_view_mut", PATH_PREFIX);
+    fs::remove_dir_all(path).unwrap_or(());
+
+    let storage = SledStorage::new(path).unwrap();
+    let mut glue = Glue::new(storage);
+
+    exec!(glue "CREATE TABLE Sample (id INTEGER);");
+    exec!(glue "INSERT INTO Sample VALUES (1), (2);");
+
+    exec!(glue "BEGIN;");
+    exec!(glue "CREATE VIEW Big AS SELECT id FROM Sample WHERE id > 1;");
+    test!(
+        glue "SELECT * FROM Big;",
+        Ok(select!(id I64; 2))
+    );
+    exec!(glue "ROLLBACK;");
+    test!(
+        glue "SELECT * FROM Big;",
+        Err(FetchError::TableNotFound("Big".to_owned()).into())
+    );
+
+    exec!(glue "CREATE VIEW Big AS SELECT id FROM Sample WHERE id > 1;");
+    exec!(glue "BEGIN;");
+    exec!(glue "DROP VIEW Big;");
+    exec!(glue "ROLLBACK;");
+    test!(
+        glue "SELECT * FROM Big;",
+        Ok(select!(id I64; 2))
+    );
+}
+
 #[tokio::test]
 async fn sled_transaction_data_mut() {
     let path = &format!("{}/transaction_data_mut", PATH_PREFIX);
@@ -5,7 +5,6 @@ use {
     async_trait::async_trait,
     gloo_storage::{errors::StorageError, LocalStorage, SessionStorage, Storage},
     gluesql_core::{
-        ast::ColumnUniqueOption,
         data::{Key, Schema},
         error::{Error, Result},
         store::{DataRow, Metadata, RowIter, Store, StoreMut},
@@ -116,17 +115,8 @@ impl Store for WebStorage {
         let path = format!("{}/{}", DATA_PATH, table_name);
         let mut rows = self.get::<Vec<(Key, DataRow)>>(path)?.unwrap_or_default();
 
-        match self.get(format!("{}/{}", SCHEMA_PATH, table_name))? {
-            Some(Schema {
-                column_defs: Some(column_defs),
-                ..
-            }) if column_defs.iter().any(|column_def| {
-                matches!(
-                    column_def.unique,
-                    Some(ColumnUniqueOption { is_primary: true })
-                )
-            }) =>
-            {
+        match self.get::<Schema>(format!("{}/{}", SCHEMA_PATH, table_name))? {
+            Some(schema) if schema.primary_key_indexes().is_some() => {
                 rows.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
             }
             _ => {}
@@ -209,3 +199,9 @@ impl gluesql_core::store::Transaction for WebStorage {}
 impl Metadata for WebStorage {}
 impl gluesql_core::store::CustomFunction for WebStorage {}
 impl gluesql_core::store::CustomFunctionMut for WebStorage {}
+impl gluesql_core::store::View for WebStorage {}
+impl gluesql_core::store::ViewMut for WebStorage {}
+impl gluesql_core::store::Trigger for WebStorage {}
+impl gluesql_core::store::TriggerMut for WebStorage {}
+impl gluesql_core::store::Sequence for WebStorage {}
+impl gluesql_core::store::SequenceMut for WebStorage {}
@@ -0,0 +1,138 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AggregateError, TranslateError},
+        prelude::Value::{self, *},
+    },
+};
+
+test_case!(collect, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Player (
+            id INTEGER,
+            team TEXT,
+            name TEXT NULL,
+            score INTEGER,
+        );
+    ",
+    )
+    .await;
+    g.run(
+        "
+        INSERT INTO Player (id, team, name, score) VALUES
+            (1, 'Red',  'Alice', 30),
+            (2, 'Red',    'Bob', 10),
+            (3, 'Red',   'Carl', 20),
+            (4, 'Blue',  'Dave', 40),
+            (5, 'Blue',    NULL, 50);
+    ",
+    )
+    .await;
+
+    let s = |v: &str| Str(v.to_owned());
+    let l = |v: Vec<Value>| List(v);
+    let m = |v: &str| Value::parse_json_map(v).unwrap();
+    let test_cases = [
+        (
+            "SELECT team, ARRAY_AGG(id ORDER BY score DESC) AS ids
+            FROM Player
+            GROUP BY team
+            ORDER BY team",
+            select_with_null!(
+                team      | ids;
+                s("Blue")   l(vec![I64(5), I64(4)]);
+                s("Red")    l(vec![I64(1), I64(3), I64(2)])
+            ),
+        ),
+        (
+            "SELECT ARRAY_AGG(name ORDER BY id) AS names FROM Player",
+            select_with_null!(
+                names;
+                l(vec![s("Alice"), s("Bob"), s("Carl"), s("Dave"), Null])
+            ),
+        ),
+        (
+            "SELECT ARRAY_AGG(DISTINCT team ORDER BY team) AS teams FROM Player",
+            select_with_null!(
+                teams;
+                l(vec![s("Blue"), s("Red")])
+            ),
+        ),
+        (
+            "SELECT team, STRING_AGG(name, ', ' ORDER BY name DESC) AS names
+            FROM Player
+            GROUP BY team
+            ORDER BY team",
+            select_with_null!(
+                team      | names;
+                s("Blue")   s("Dave");
+                s("Red")    s("Carl, Bob, Alice")
+            ),
+        ),
+        (
+            "SELECT STRING_AGG(name, '-' ORDER BY team, score) AS names FROM Player",
+            select_with_null!(
+                names;
+                s("Dave-Bob-Carl-Alice")
+            ),
+        ),
+        (
+            "SELECT
+                team,
+                STRING_AGG(name, ', ') FILTER (WHERE score > 35) AS names,
+                ARRAY_AGG(id) FILTER (WHERE score > 35) AS ids
+            FROM Player
+            GROUP BY team
+            ORDER BY team",
+            select_with_null!(
+                team      | names      | ids;
+                s("Blue")   s("Dave")    l(vec![I64(4), I64(5)]);
+                s("Red")    Null         Null
+            ),
+        ),
+        (
+            "SELECT team, JSON_OBJECT_AGG(name, score) AS scores
+            FROM Player
+            WHERE name IS NOT NULL
+            GROUP BY team
+            ORDER BY team",
+            select_with_null!(
+                team      | scores;
+                s("Blue")   m(r#"{"Dave": 40}"#);
+                s("Red")    m(r#"{"Alice": 30, "Bob": 10, "Carl": 20}"#)
+            ),
+        ),
+        (
+            "SELECT JSON_OBJECT_AGG(team, name ORDER BY score) AS captains
+            FROM Player
+            WHERE name IS NOT NULL",
+            select_with_null!(
+                captains;
+                m(r#"{"Red": "Alice", "Blue": "Dave"}"#)
+            ),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, Ok(expected)).await;
+    }
+
+    g.test(
+        "SELECT JSON_OBJECT_AGG(name, score) FROM Player",
+        Err(AggregateError::NullJsonObjectAggKey.into()),
+    )
+    .await;
+    g.test(
+        "SELECT UPPER(name ORDER BY id) FROM Player",
+        Err(TranslateError::OrderByOnNonAggregateFunction("UPPER".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "SELECT STRING_AGG(name, ', ') OVER () FROM Player",
+        Err(TranslateError::UnsupportedWindowFunction("STRING_AGG".to_owned()).into()),
+    )
+    .await;
+});
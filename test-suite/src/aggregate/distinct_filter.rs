@@ -0,0 +1,113 @@
+use {
+    crate::*,
+    gluesql_core::{error::TranslateError, prelude::Value::*},
+};
+
+test_case!(distinct_filter, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Orders (
+            id INTEGER,
+            customer TEXT,
+            price INTEGER NULL,
+            paid BOOLEAN,
+        );
+    ",
+    )
+    .await;
+    g.run(
+        "
+        INSERT INTO Orders (id, customer, price, paid) VALUES
+            (1, 'Alice',   10,  TRUE),
+            (2, 'Alice',   10, FALSE),
+            (3, 'Alice',   30,  TRUE),
+            (4,   'Bob',   20, FALSE),
+            (5,   'Bob', NULL, FALSE),
+            (6, 'Carol',   20,  TRUE);
+    ",
+    )
+    .await;
+
+    let s = |v: &str| Str(v.to_owned());
+    let test_cases = [
+        (
+            "SELECT
+                COUNT(DISTINCT price) AS prices,
+                SUM(DISTINCT price) AS total,
+                AVG(DISTINCT price) AS average
+            FROM Orders
+            WHERE price IS NOT NULL",
+            select!(
+                prices | total   | average;
+                I64    | I64     | F64;
+                3        60        20.0
+            ),
+        ),
+        (
+            "SELECT customer, COUNT(DISTINCT price) AS prices, COUNT(price) AS total
+            FROM Orders
+            GROUP BY customer
+            ORDER BY customer",
+            select!(
+                customer         | prices | total;
+                Str              | I64    | I64;
+                "Alice".to_owned()  2        3;
+                "Bob".to_owned()    1        1;
+                "Carol".to_owned()  1        1
+            ),
+        ),
+        (
+            "SELECT
+                COUNT(*) FILTER (WHERE paid) AS paid_orders,
+                SUM(price) FILTER (WHERE paid) AS paid_total,
+                SUM(price) FILTER (WHERE NOT paid AND price IS NOT NULL) AS unpaid_total
+            FROM Orders",
+            select!(
+                paid_orders | paid_total | unpaid_total;
+                I64         | I64        | I64;
+                3             60           30
+            ),
+        ),
+        (
+            "SELECT
+                customer,
+                COUNT(*) FILTER (WHERE paid) AS paid_orders,
+                SUM(price) FILTER (WHERE paid) AS paid_total,
+                COUNT(DISTINCT price) FILTER (WHERE price > 10) AS prices
+            FROM Orders
+            GROUP BY customer
+            ORDER BY customer",
+            select_with_null!(
+                customer   | paid_orders | paid_total | prices;
+                s("Alice")   I64(2)        I64(40)      I64(1);
+                s("Bob")     I64(0)        Null         I64(1);
+                s("Carol")   I64(1)        I64(20)      I64(1)
+            ),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, Ok(expected)).await;
+    }
+
+    let error_cases = [
+        (
+            "SELECT COUNT(DISTINCT *) FROM Orders",
+            TranslateError::DistinctOnWildcardNotSupported,
+        ),
+        (
+            "SELECT UPPER(DISTINCT customer) FROM Orders",
+            TranslateError::DistinctOnNonAggregateFunction("UPPER".to_owned()),
+        ),
+        (
+            "SELECT SUM(DISTINCT price) OVER () FROM Orders",
+            TranslateError::DistinctAggregateInWindowNotSupported,
+        ),
+    ];
+
+    for (sql, error) in error_cases {
+        g.test(sql, Err(error.into())).await;
+    }
+});
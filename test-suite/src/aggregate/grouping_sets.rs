@@ -0,0 +1,168 @@
+use {
+    crate::*,
+    gluesql_core::{error::AggregateError, prelude::Value::*},
+};
+
+test_case!(grouping_sets, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Sales (
+            id INTEGER,
+            region TEXT,
+            city TEXT,
+            amount INTEGER,
+        );
+    ",
+    )
+    .await;
+    g.run(
+        "
+        INSERT INTO Sales (id, region, city, amount) VALUES
+            (1, 'East', 'Boston', 10),
+            (2, 'East',    'NYC', 20),
+            (3, 'West',     'LA', 30),
+            (4, 'West',     'LA',  5),
+            (5, 'West',     'SF', 40);
+    ",
+    )
+    .await;
+    g.run("CREATE TABLE Target (region TEXT, goal INTEGER);")
+        .await;
+    g.run("INSERT INTO Target VALUES ('East', 25), ('West', 50);")
+        .await;
+
+    let s = |v: &str| Str(v.to_owned());
+    let test_cases = [
+        (
+            "SELECT region, city, SUM(amount) AS total
+            FROM Sales
+            GROUP BY ROLLUP (region, city)
+            ORDER BY region, city",
+            select_with_null!(
+                region    | city        | total;
+                s("East")   s("Boston")   I64(10);
+                s("East")   s("NYC")      I64(20);
+                s("East")   Null          I64(30);
+                s("West")   s("LA")       I64(35);
+                s("West")   s("SF")       I64(40);
+                s("West")   Null          I64(75);
+                Null        Null          I64(105)
+            ),
+        ),
+        (
+            "SELECT region, city, COUNT(*) AS cnt, GROUPING(region, city) AS level
+            FROM Sales
+            GROUP BY CUBE (region, city)
+            ORDER BY GROUPING(region, city), region, city",
+            select_with_null!(
+                region    | city        | cnt    | level;
+                s("East")   s("Boston")   I64(1)   I64(0);
+                s("East")   s("NYC")      I64(1)   I64(0);
+                s("West")   s("LA")       I64(2)   I64(0);
+                s("West")   s("SF")       I64(1)   I64(0);
+                s("East")   Null          I64(2)   I64(1);
+                s("West")   Null          I64(3)   I64(1);
+                Null        s("Boston")   I64(1)   I64(2);
+                Null        s("LA")       I64(2)   I64(2);
+                Null        s("NYC")      I64(1)   I64(2);
+                Null        s("SF")       I64(1)   I64(2);
+                Null        Null          I64(5)   I64(3)
+            ),
+        ),
+        (
+            "SELECT region, city, SUM(amount) AS total
+            FROM Sales
+            GROUP BY GROUPING SETS ((region), (city), ())
+            HAVING SUM(amount) > 30
+            ORDER BY region, city",
+            select_with_null!(
+                region    | city    | total;
+                s("West")   Null      I64(75);
+                Null        s("LA")   I64(35);
+                Null        s("SF")   I64(40);
+                Null        Null      I64(105)
+            ),
+        ),
+        (
+            "SELECT region, city, SUM(amount) AS total
+            FROM Sales
+            GROUP BY region, ROLLUP (city)
+            ORDER BY region, city",
+            select_with_null!(
+                region    | city        | total;
+                s("East")   s("Boston")   I64(10);
+                s("East")   s("NYC")      I64(20);
+                s("East")   Null          I64(30);
+                s("West")   s("LA")       I64(35);
+                s("West")   s("SF")       I64(40);
+                s("West")   Null          I64(75)
+            ),
+        ),
+        (
+            "SELECT S.region, COUNT(*) AS cnt, GROUPING(S.region) AS level
+            FROM Sales S
+            GROUP BY ROLLUP (S.region)
+            ORDER BY S.region",
+            select_with_null!(
+                region    | cnt    | level;
+                s("East")   I64(2)   I64(0);
+                s("West")   I64(3)   I64(0);
+                Null        I64(5)   I64(1)
+            ),
+        ),
+        (
+            "SELECT UPPER(region) AS r, SUM(amount) AS total
+            FROM Sales
+            GROUP BY ROLLUP (UPPER(region))
+            ORDER BY r",
+            select_with_null!(
+                r         | total;
+                s("EAST")   I64(30);
+                s("WEST")   I64(75);
+                Null        I64(105)
+            ),
+        ),
+        (
+            "SELECT amount / 10 AS bucket, COUNT(*) AS cnt
+            FROM Sales
+            GROUP BY ROLLUP (amount / 10)
+            ORDER BY bucket",
+            select_with_null!(
+                bucket | cnt;
+                I64(0)   I64(1);
+                I64(1)   I64(1);
+                I64(2)   I64(1);
+                I64(3)   I64(1);
+                I64(4)   I64(1);
+                Null     I64(5)
+            ),
+        ),
+        (
+            "SELECT S.id, S.amount AS sold, goal
+            FROM Sales S
+            JOIN Target ON Target.region = S.region
+            WHERE S.id <= 2
+            GROUP BY S.id, S.amount, ROLLUP (goal)
+            ORDER BY S.id, goal",
+            select_with_null!(
+                id     | sold    | goal;
+                I64(1)   I64(10)   I64(25);
+                I64(1)   I64(10)   Null;
+                I64(2)   I64(20)   I64(25);
+                I64(2)   I64(20)   Null
+            ),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, Ok(expected)).await;
+    }
+
+    g.test(
+        "SELECT GROUPING(city) FROM Sales GROUP BY ROLLUP (region)",
+        Err(AggregateError::GroupingArgNotInGroupBy(r#""city""#.to_owned()).into()),
+    )
+    .await;
+});
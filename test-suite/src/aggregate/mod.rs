@@ -1,7 +1,10 @@
 pub mod avg;
+pub mod collect;
 pub mod count;
+pub mod distinct_filter;
 pub mod error;
 pub mod group_by;
+pub mod grouping_sets;
 pub mod max;
 pub mod min;
 pub mod stdev;
@@ -2,10 +2,11 @@ use {
     crate::*,
     gluesql_core::{
         ast::*,
-        data::Value::*,
-        error::{AlterError, AlterTableError, EvaluateError, TranslateError},
+        data::{Literal, Value::*},
+        error::{AlterError, AlterTableError, EvaluateError, TranslateError, ValueError},
         prelude::Payload,
     },
+    std::borrow::Cow,
 };
 
 test_case!(alter_table_rename, {
@@ -173,3 +174,115 @@ test_case!(alter_table_add_drop, {
         g.test(sql, expected).await;
     }
 });
+
+test_case!(alter_table_alter_column, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Foo (id INTEGER PRIMARY KEY, num TEXT, flag BOOLEAN);",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Foo VALUES (1, '10', TRUE), (2, '20', NULL);",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN num TYPE INTEGER",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "SELECT id, num + 1 AS num FROM Foo",
+            Ok(select!(id | num; I64 | I64; 1 11; 2 21)),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN flag SET NOT NULL",
+            Err(ValueError::NullValueOnNotNullField.into()),
+        ),
+        (
+            "UPDATE Foo SET flag = FALSE WHERE id = 2",
+            Ok(Payload::Update(1)),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN flag SET NOT NULL",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "INSERT INTO Foo VALUES (3, 30, NULL)",
+            Err(ValueError::NullValueOnNotNullField.into()),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN flag DROP NOT NULL",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "INSERT INTO Foo VALUES (3, 30, NULL)",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN num SET DEFAULT 5",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "INSERT INTO Foo (id, flag) VALUES (4, TRUE)",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN num DROP DEFAULT",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "INSERT INTO Foo (id, flag) VALUES (5, TRUE)",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "SELECT id, num FROM Foo WHERE id > 2",
+            Ok(select_with_null!(
+                id     | num;
+                I64(3)   I64(30);
+                I64(4)   I64(5);
+                I64(5)   Null
+            )),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN id TYPE TEXT",
+            Err(AlterError::DataTypeChangeOnUniqueColumn("id".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Rate (a TEXT, b INTEGER, UNIQUE (a, b));",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Rate VALUES ('1', 1), ('01', 1);",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "ALTER TABLE Rate ALTER COLUMN a TYPE INTEGER",
+            Err(AlterError::ColumnReferencedByUniqueKey {
+                column_name: "a".to_owned(),
+                unique_key: "UQ_a_b".to_owned(),
+            }
+            .into()),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN id DROP NOT NULL",
+            Err(AlterError::DropNotNullOnPrimaryKey("id".to_owned()).into()),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN hello SET NOT NULL",
+            Err(AlterTableError::AlteringColumnNotFound("hello".to_owned()).into()),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN num SET DEFAULT 'abc'",
+            Err(ValueError::IncompatibleLiteralForDataType {
+                data_type: DataType::Int,
+                literal: format!("{:?}", Literal::Text(Cow::Owned("abc".to_owned()))),
+            }
+            .into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
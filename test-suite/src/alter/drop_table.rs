@@ -59,8 +59,8 @@ CREATE TABLE DropTable (
             Ok(select!(id | num | name)),
         ),
         (
-            "DROP VIEW DropTable;",
-            Err(TranslateError::UnsupportedStatement("DROP VIEW DropTable".to_owned()).into()),
+            "DROP SCHEMA DropTable;",
+            Err(TranslateError::UnsupportedStatement("DROP SCHEMA DropTable".to_owned()).into()),
         ),
         (
             "
@@ -3,7 +3,7 @@ mod create_table;
 mod drop_indexed;
 mod drop_table;
 
-pub use alter_table::{alter_table_add_drop, alter_table_rename};
+pub use alter_table::{alter_table_add_drop, alter_table_alter_column, alter_table_rename};
 pub use create_table::create_table;
 pub use drop_indexed::{drop_indexed_column, drop_indexed_table};
 pub use drop_table::drop_table;
@@ -0,0 +1,193 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, EvaluateError, ValidateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(check_constraint, {
+    let g = get_tester!();
+
+    g.run(
+        "
+CREATE TABLE Product (
+    id INTEGER PRIMARY KEY,
+    price INTEGER CHECK (price > 0),
+    discount INTEGER,
+    CONSTRAINT discount_range CHECK (discount <= price)
+);",
+    )
+    .await;
+
+    g.named_test(
+        "insert rows satisfying checks, NULL passes",
+        "INSERT INTO Product VALUES (1, 100, 10), (2, 50, NULL);",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+
+    g.named_test(
+        "insert violating column check",
+        "INSERT INTO Product VALUES (3, 0, 0);",
+        Err(ValidateError::CheckConstraintViolated("CK_price".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "insert violating table check",
+        "INSERT INTO Product VALUES (3, 10, 20);",
+        Err(ValidateError::CheckConstraintViolated("discount_range".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "update violating table check",
+        "UPDATE Product SET discount = 200 WHERE id = 1;",
+        Err(ValidateError::CheckConstraintViolated("discount_range".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "update satisfying checks",
+        "UPDATE Product SET price = price - 10;",
+        Ok(Payload::Update(2)),
+    )
+    .await;
+
+    g.named_test(
+        "upsert violating column check",
+        "INSERT INTO Product VALUES (1, 5, 0) ON CONFLICT (id) DO UPDATE SET price = -1;",
+        Err(ValidateError::CheckConstraintViolated("CK_price".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "upsert satisfying checks",
+        "INSERT INTO Product VALUES (1, 5, 0) ON CONFLICT (id) DO UPDATE SET discount = 50;",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.test(
+        "SELECT * FROM Product ORDER BY id;",
+        Ok(select_with_null!(
+            id     | price   | discount;
+            I64(1)   I64(90)   I64(50);
+            I64(2)   I64(40)   Null
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "drop column used by check",
+        "ALTER TABLE Product DROP COLUMN discount;",
+        Err(AlterError::ColumnReferencedByCheck {
+            column_name: "discount".to_owned(),
+            check: "discount_range".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.run("CREATE TABLE Span (low INTEGER, high INTEGER, CHECK (low < high));")
+        .await;
+    g.named_test(
+        "unnamed table check",
+        "INSERT INTO Span VALUES (2, 1);",
+        Err(ValidateError::CheckConstraintViolated("CK_1".to_owned()).into()),
+    )
+    .await;
+
+    g.run(
+        "
+CREATE TABLE Digit (
+    num INTEGER CHECK (num >= 0) CHECK (num < 10),
+    CHECK (num <> 5),
+    CONSTRAINT CK_3 CHECK (num <> 7)
+);",
+    )
+    .await;
+    g.named_test(
+        "generated check names do not collide",
+        "SELECT CONSTRAINT_NAME FROM GLUE_TABLE_CONSTRAINTS WHERE TABLE_NAME = 'Digit';",
+        Ok(select!(
+            CONSTRAINT_NAME
+            Str;
+            "CK_num".to_owned();
+            "CK_num_2".to_owned();
+            "CK_3_2".to_owned();
+            "CK_3".to_owned()
+        )),
+    )
+    .await;
+    g.named_test(
+        "second column check",
+        "INSERT INTO Digit VALUES (10);",
+        Err(ValidateError::CheckConstraintViolated("CK_num_2".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "unnamed table check next to a named CK_3",
+        "INSERT INTO Digit VALUES (5);",
+        Err(ValidateError::CheckConstraintViolated("CK_3_2".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "check on unknown column fails at create",
+        "CREATE TABLE Unknown (num INTEGER CHECK (amount > 0));",
+        Err(EvaluateError::ValueNotFound("amount".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "check which is NOT NULL on NULLs is accepted at create",
+        "CREATE TABLE Required (num INTEGER, CHECK (num IS NOT NULL));",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.run("CREATE TABLE Team (id INTEGER PRIMARY KEY, code INTEGER UNIQUE);")
+        .await;
+    g.run(
+        "
+CREATE TABLE Member (
+    id INTEGER PRIMARY KEY,
+    team_id INTEGER REFERENCES Team (code) ON DELETE SET NULL ON UPDATE CASCADE,
+    CHECK (team_id IS NOT NULL),
+    CHECK (team_id < 10)
+);",
+    )
+    .await;
+    g.run("INSERT INTO Team VALUES (1, 1);").await;
+    g.run("INSERT INTO Member VALUES (1, 1);").await;
+    g.named_test(
+        "set null cascade violating check",
+        "DELETE FROM Team;",
+        Err(ValidateError::CheckConstraintViolated("CK_1".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "update cascade violating check",
+        "UPDATE Team SET code = 20;",
+        Err(ValidateError::CheckConstraintViolated("CK_2".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "rejected cascades leave rows untouched",
+        "SELECT Team.code, Member.team_id FROM Team JOIN Member ON Team.code = Member.team_id;",
+        Ok(select!(code | team_id; I64 | I64; 1 1)),
+    )
+    .await;
+
+    g.test(
+        "SELECT CONSTRAINT_NAME, CONSTRAINT_TYPE, DEFINITION FROM GLUE_TABLE_CONSTRAINTS WHERE TABLE_NAME = 'Product';",
+        Ok(select!(
+            CONSTRAINT_NAME               | CONSTRAINT_TYPE     | DEFINITION
+            Str                           | Str                 | Str;
+            "CK_price".to_owned()           "CHECK".to_owned()    "CHECK (price > 0)".to_owned();
+            "discount_range".to_owned()     "CHECK".to_owned()    "CHECK (discount <= price)".to_owned()
+        )),
+    )
+    .await;
+});
@@ -0,0 +1,294 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::{DataType, IndexItem, IndexOperator},
+        data::Key,
+        error::{AlterError, TranslateError, UpdateError, ValidateError},
+        parse_sql::parse_expr,
+        prelude::{Payload, Value::*},
+        translate::translate_expr,
+    },
+};
+
+test_case!(composite_key, {
+    let g = get_tester!();
+
+    let expr = |sql: &str| translate_expr(&parse_expr(sql).unwrap()).unwrap();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Foo (id INTEGER, PRIMARY KEY (id, uid));",
+            Err(TranslateError::ColumnNotFoundInConstraint("uid".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Foo (id INTEGER PRIMARY KEY, num INTEGER, PRIMARY KEY (id, num));",
+            Err(TranslateError::MultiplePrimaryKeysNotAllowed.into()),
+        ),
+        (
+            "CREATE TABLE Foo (id INTEGER, rate FLOAT, UNIQUE (id, rate));",
+            Err(
+                AlterError::UnsupportedDataTypeForUniqueColumn("rate".to_owned(), DataType::Float)
+                    .into(),
+            ),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+
+    g.run(
+        "
+CREATE TABLE Orders (
+    tenant_id INTEGER,
+    order_id INTEGER,
+    code TEXT,
+    memo TEXT,
+    PRIMARY KEY (tenant_id, order_id),
+    CONSTRAINT uq_code UNIQUE (tenant_id, code)
+);",
+    )
+    .await;
+
+    g.named_test(
+        "insert rows sharing a part of the primary key",
+        "INSERT INTO Orders VALUES (1, 1, 'a', 'x'), (1, 2, 'b', 'y'), (2, 1, 'a', 'z'), (2, 2, NULL, NULL);",
+        Ok(Payload::Insert(4)),
+    )
+    .await;
+
+    g.named_test(
+        "insert duplicate primary key",
+        "INSERT INTO Orders VALUES (1, 2, 'c', NULL);",
+        Err(
+            ValidateError::DuplicateEntryOnPrimaryKeyField(Key::Composite(vec![
+                Key::I64(1),
+                Key::I64(2),
+            ]))
+            .into(),
+        ),
+    )
+    .await;
+
+    g.named_test(
+        "insert duplicate primary key within the inserted rows",
+        "INSERT INTO Orders VALUES (3, 1, 'a', NULL), (3, 1, 'b', NULL);",
+        Err(
+            ValidateError::DuplicateEntryOnPrimaryKeyField(Key::Composite(vec![
+                Key::I64(3),
+                Key::I64(1),
+            ]))
+            .into(),
+        ),
+    )
+    .await;
+
+    g.named_test(
+        "insert duplicate unique key",
+        "INSERT INTO Orders VALUES (2, 3, 'a', NULL);",
+        Err(ValidateError::DuplicateEntryOnUniqueKey {
+            unique_key: "uq_code".to_owned(),
+            key: Key::Composite(vec![Key::I64(2), Key::Str("a".to_owned())]),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "unique key having NULL is never a duplicate",
+        "INSERT INTO Orders VALUES (2, 3, NULL, NULL);",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT order_id, memo FROM Orders WHERE order_id = 1 AND tenant_id = 2",
+        Ok(select!(
+            order_id | memo
+            I64      | Str;
+            1          "z".to_owned()
+        )),
+        vec![IndexItem::CompositePrimaryKey {
+            exprs: vec![expr("2"), expr("1")],
+            full_key: true,
+            cmp_expr: None,
+        }],
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT order_id, code FROM Orders WHERE tenant_id = 1 AND memo IS NOT NULL",
+        Ok(select!(
+            order_id | code
+            I64      | Str;
+            1          "a".to_owned();
+            2          "b".to_owned()
+        )),
+        vec![IndexItem::CompositePrimaryKey {
+            exprs: vec![expr("1")],
+            full_key: false,
+            cmp_expr: None,
+        }],
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT tenant_id, order_id FROM Orders WHERE tenant_id = 2 AND order_id > 1",
+        Ok(select!(
+            tenant_id | order_id
+            I64       | I64;
+            2           2;
+            2           3
+        )),
+        vec![IndexItem::CompositePrimaryKey {
+            exprs: vec![expr("2")],
+            full_key: false,
+            cmp_expr: Some((IndexOperator::Gt, expr("1"))),
+        }],
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT tenant_id, order_id FROM Orders WHERE 2 > tenant_id",
+        Ok(select!(
+            tenant_id | order_id
+            I64       | I64;
+            1           1;
+            1           2
+        )),
+        vec![IndexItem::CompositePrimaryKey {
+            exprs: Vec::new(),
+            full_key: false,
+            cmp_expr: Some((IndexOperator::Lt, expr("2"))),
+        }],
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT order_id FROM Orders WHERE tenant_id = 2 AND order_id <= NULL",
+        Ok(Payload::Select {
+            labels: vec!["order_id".to_owned()],
+            rows: Vec::new(),
+        }),
+        vec![IndexItem::CompositePrimaryKey {
+            exprs: vec![expr("2")],
+            full_key: false,
+            cmp_expr: Some((IndexOperator::LtEq, expr("NULL"))),
+        }],
+    )
+    .await;
+
+    g.named_test(
+        "update primary key column",
+        "UPDATE Orders SET order_id = 5 WHERE tenant_id = 1;",
+        Err(UpdateError::UpdateOnPrimaryKeyNotSupported("order_id".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "update into duplicate unique key",
+        "UPDATE Orders SET code = 'a' WHERE tenant_id = 1 AND order_id = 2;",
+        Err(ValidateError::DuplicateEntryOnUniqueKey {
+            unique_key: "uq_code".to_owned(),
+            key: Key::Composite(vec![Key::I64(1), Key::Str("a".to_owned())]),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "update by full primary key",
+        "UPDATE Orders SET memo = 'w' WHERE tenant_id = 2 AND order_id = 2;",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+
+    g.named_test(
+        "upsert on composite conflict target",
+        "INSERT INTO Orders VALUES (1, 1, 'c', NULL) ON CONFLICT (order_id, tenant_id) DO UPDATE SET memo = 'v';",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.named_test(
+        "upsert on unique key",
+        "INSERT INTO Orders VALUES (1, 9, 'b', NULL) ON CONFLICT (tenant_id, code) DO NOTHING;",
+        Ok(Payload::Insert(0)),
+    )
+    .await;
+
+    g.named_test(
+        "delete by primary key prefix",
+        "DELETE FROM Orders WHERE tenant_id = 2;",
+        Ok(Payload::Delete(3)),
+    )
+    .await;
+
+    g.test(
+        "SELECT * FROM Orders;",
+        Ok(select_with_null!(
+            tenant_id | order_id | code                | memo;
+            I64(1)      I64(1)     Str("a".to_owned())   Str("v".to_owned());
+            I64(1)      I64(2)     Str("b".to_owned())   Str("y".to_owned())
+        )),
+    )
+    .await;
+
+    g.test(
+        "SELECT CONSTRAINT_NAME, CONSTRAINT_TYPE, DEFINITION FROM GLUE_TABLE_CONSTRAINTS WHERE TABLE_NAME = 'Orders';",
+        Ok(select!(
+            CONSTRAINT_NAME                   | CONSTRAINT_TYPE          | DEFINITION
+            Str                               | Str                      | Str;
+            "PK_tenant_id_order_id".to_owned()  "PRIMARY KEY".to_owned()   "PRIMARY KEY (tenant_id, order_id)".to_owned();
+            "uq_code".to_owned()                "UNIQUE".to_owned()        "UNIQUE (tenant_id, code)".to_owned()
+        )),
+    )
+    .await;
+
+    g.test(
+        "SELECT INDEX_NAME, EXPRESSION FROM GLUE_INDEXES WHERE TABLE_NAME = 'Orders';",
+        Ok(select!(
+            INDEX_NAME            | EXPRESSION
+            Str                   | Str;
+            "PRIMARY".to_owned()    "tenant_id, order_id".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "drop column used by unique key",
+        "ALTER TABLE Orders DROP COLUMN code;",
+        Err(AlterError::ColumnReferencedByUniqueKey {
+            column_name: "code".to_owned(),
+            unique_key: "uq_code".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.run("CREATE TABLE Pair (a INTEGER, b INTEGER, PRIMARY KEY (a));")
+        .await;
+    g.run("INSERT INTO Pair VALUES (1, 1);").await;
+    g.named_test(
+        "table-level primary key on a single column",
+        "INSERT INTO Pair VALUES (1, 2);",
+        Err(ValidateError::DuplicateEntryOnPrimaryKeyField(Key::I64(1)).into()),
+    )
+    .await;
+
+    g.run("CREATE TABLE Single (a INTEGER UNIQUE, b INTEGER, PRIMARY KEY (a));")
+        .await;
+    g.run("INSERT INTO Single VALUES (1, 1);").await;
+    g.named_test(
+        "table-level primary key on a unique column",
+        "INSERT INTO Single VALUES (1, 2);",
+        Err(ValidateError::DuplicateEntryOnPrimaryKeyField(Key::I64(1)).into()),
+    )
+    .await;
+    g.test_idx(
+        "SELECT b FROM Single WHERE a = 1",
+        Ok(select!(b; I64; 1)),
+        vec![IndexItem::PrimaryKey(expr("1"))],
+    )
+    .await;
+});
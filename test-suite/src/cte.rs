@@ -0,0 +1,182 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{FetchError, SelectError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(cte, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Employee (id INTEGER, name TEXT, manager_id INTEGER NULL)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Employee VALUES
+                (1, 'Alice', NULL),
+                (2, 'Bob', 1),
+                (3, 'Carol', 1),
+                (4, 'Dave', 2),
+                (5, 'Eve', 4);",
+            Ok(Payload::Insert(5)),
+        ),
+        (
+            "WITH Boss AS (SELECT id, name FROM Employee WHERE manager_id IS NULL)
+            SELECT name FROM Boss",
+            Ok(select!(name; Str; "Alice".to_owned())),
+        ),
+        (
+            "WITH Pair AS (SELECT id FROM Employee WHERE id <= 3)
+            SELECT A.id AS a, B.id AS b FROM Pair AS A JOIN Pair AS B ON A.id < B.id
+            ORDER BY a, b",
+            Ok(select!(
+                a   | b
+                I64 | I64;
+                1     2;
+                1     3;
+                2     3
+            )),
+        ),
+        (
+            "WITH
+                Numbers(x) AS (SELECT id FROM Employee),
+                Scaled AS (SELECT x * 10 AS y FROM Numbers WHERE x > 3)
+            SELECT * FROM Scaled",
+            Ok(select!(y; I64; 40; 50)),
+        ),
+        (
+            "WITH Managers AS (SELECT manager_id FROM Employee WHERE manager_id IS NOT NULL)
+            SELECT name FROM Employee WHERE id IN (SELECT manager_id FROM Managers) ORDER BY id",
+            Ok(select!(
+                name
+                Str;
+                "Alice".to_owned();
+                "Bob".to_owned();
+                "Dave".to_owned()
+            )),
+        ),
+        (
+            "WITH Employee(id) AS (VALUES (100)) SELECT * FROM Employee",
+            Ok(select!(id; I64; 100)),
+        ),
+        (
+            "WITH Employee AS (SELECT id, name FROM Employee WHERE id > 3)
+            SELECT name FROM Employee ORDER BY id",
+            Ok(select!(name; Str; "Dave".to_owned(); "Eve".to_owned())),
+        ),
+        (
+            "CREATE TABLE Team (id INTEGER PRIMARY KEY, name TEXT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Team VALUES (1, 'Core'), (2, 'Web'), (3, 'Docs')",
+            Ok(Payload::Insert(3)),
+        ),
+        (
+            "WITH Team AS (SELECT id, name FROM Team WHERE id > 1)
+            SELECT Team.name FROM Team WHERE id = 2",
+            Ok(select!(name; Str; "Web".to_owned())),
+        ),
+        (
+            "WITH Team AS (SELECT id + 10 AS id, name FROM Team WHERE id = 1)
+            SELECT name FROM Team WHERE id = 11",
+            Ok(select!(name; Str; "Core".to_owned())),
+        ),
+        (
+            "SELECT * FROM (WITH Third AS (SELECT id, name FROM Employee WHERE id = 3)
+            SELECT * FROM Third) AS Sub",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                3     "Carol".to_owned()
+            )),
+        ),
+        (
+            "WITH RECURSIVE Counter(n) AS (
+                SELECT 1
+                UNION ALL
+                SELECT n + 1 FROM Counter WHERE n < 5
+            )
+            SELECT n FROM Counter",
+            Ok(select!(n; I64; 1; 2; 3; 4; 5)),
+        ),
+        (
+            "WITH RECURSIVE Subordinate AS (
+                SELECT id, name FROM Employee WHERE id = 2
+                UNION ALL
+                SELECT Employee.id, Employee.name
+                FROM Employee JOIN Subordinate ON Employee.manager_id = Subordinate.id
+            )
+            SELECT id, name FROM Subordinate ORDER BY id",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                2     "Bob".to_owned();
+                4     "Dave".to_owned();
+                5     "Eve".to_owned()
+            )),
+        ),
+        (
+            "WITH RECURSIVE Chain(id) AS (
+                SELECT id FROM Employee WHERE id = 2
+                UNION
+                SELECT id FROM Employee WHERE manager_id IN (SELECT id FROM Chain)
+            )
+            SELECT id FROM Chain ORDER BY id",
+            Ok(select!(id; I64; 2; 4; 5)),
+        ),
+        (
+            "WITH RECURSIVE Cycle(n) AS (
+                VALUES (0)
+                UNION
+                SELECT (n + 1) % 3 FROM Cycle
+            )
+            SELECT n FROM Cycle ORDER BY n",
+            Ok(select!(n; I64; 0; 1; 2)),
+        ),
+        (
+            "WITH RECURSIVE Counter(n) AS (
+                SELECT 1
+                UNION ALL
+                SELECT n + 1 FROM Counter WHERE n < 10
+                ORDER BY n DESC
+                LIMIT 3
+            )
+            SELECT n FROM Counter",
+            Ok(select!(n; I64; 10; 9; 8)),
+        ),
+        (
+            "WITH Single(a, b) AS (SELECT id FROM Employee) SELECT * FROM Single",
+            Err(FetchError::TooManyColumnAliases("Single".to_owned(), 1, 2).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+
+    g.get_glue().limits.max_recursion = 10;
+    g.test(
+        "WITH RECURSIVE Counter(n) AS (
+            SELECT 1
+            UNION ALL
+            SELECT n + 1 FROM Counter WHERE n < 10
+        )
+        SELECT COUNT(*) AS cnt FROM Counter",
+        Ok(select!(cnt; I64; 10)),
+    )
+    .await;
+    g.test(
+        "WITH RECURSIVE Forever(n) AS (
+            SELECT 1
+            UNION ALL
+            SELECT n FROM Forever
+        )
+        SELECT n FROM Forever",
+        Err(SelectError::RecursionLimitExceeded("Forever".to_owned(), 10).into()),
+    )
+    .await;
+});
@@ -0,0 +1,129 @@
+use {crate::*, gluesql_core::prelude::*, Value::*};
+
+test_case!(distinct, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Item (
+                id INTEGER,
+                category TEXT,
+                price INTEGER
+            )",
+            Payload::Create,
+        ),
+        (
+            "INSERT INTO Item VALUES
+                (1, 'fruit', 30),
+                (2, 'fruit', 10),
+                (3, 'meat', 50),
+                (4, 'fruit', 10),
+                (5, 'meat', 20),
+                (6, NULL, 20),
+                (7, NULL, 20);",
+            Payload::Insert(7),
+        ),
+        (
+            "SELECT DISTINCT category FROM Item",
+            select_with_null!(
+                category;
+                Str("fruit".to_owned());
+                Str("meat".to_owned());
+                Null
+            ),
+        ),
+        (
+            "SELECT DISTINCT category, price FROM Item ORDER BY category, price",
+            select_with_null!(
+                category                 | price;
+                Str("fruit".to_owned())    I64(10);
+                Str("fruit".to_owned())    I64(30);
+                Str("meat".to_owned())     I64(20);
+                Str("meat".to_owned())     I64(50);
+                Null                       I64(20)
+            ),
+        ),
+        (
+            "SELECT DISTINCT price FROM Item ORDER BY price DESC LIMIT 2 OFFSET 1",
+            select!(price; I64; 30; 20),
+        ),
+        (
+            "SELECT DISTINCT ON (category) category, id FROM Item ORDER BY category, price, id",
+            select_with_null!(
+                category                 | id;
+                Str("fruit".to_owned())    I64(2);
+                Str("meat".to_owned())     I64(5);
+                Null                       I64(6)
+            ),
+        ),
+        (
+            "SELECT DISTINCT ON (price % 20) id, price FROM Item ORDER BY price % 20, id DESC",
+            select!(
+                id  | price;
+                I64 | I64;
+                7     20;
+                4     10
+            ),
+        ),
+        (
+            "SELECT DISTINCT COUNT(*) AS cnt FROM Item GROUP BY category",
+            select!(cnt; I64; 3; 2),
+        ),
+        (
+            "SELECT DISTINCT ON (COUNT(*)) category, COUNT(*) AS cnt
+            FROM Item GROUP BY category ORDER BY COUNT(*), category",
+            select!(
+                category          | cnt;
+                Str               | I64;
+                "meat".to_owned()   2;
+                "fruit".to_owned()  3
+            ),
+        ),
+        (
+            "SELECT DISTINCT * FROM (VALUES (1), (1), (2)) AS Sub",
+            select!(column1; I64; 1; 2),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, Ok(expected)).await;
+    }
+
+    g.run("CREATE TABLE Bag (id INTEGER, items LIST, attrs MAP)")
+        .await;
+    g.run(
+        r#"
+INSERT INTO Bag VALUES
+    (1, '[1, 2]', '{"a": 1, "b": [true]}'),
+    (2, '[1, 2]', '{"b": [true], "a": 1}'),
+    (3, '[2, 1]', '{"a": 2}'),
+    (4, NULL, NULL);
+"#,
+    )
+    .await;
+
+    let l = |s: &str| Value::parse_json_list(s).unwrap();
+    let m = |s: &str| Value::parse_json_map(s).unwrap();
+
+    g.named_test(
+        "SELECT DISTINCT over LIST",
+        "SELECT DISTINCT items FROM Bag",
+        Ok(select_with_null!(items; l("[1, 2]"); l("[2, 1]"); Null)),
+    )
+    .await;
+    g.named_test(
+        "COUNT DISTINCT over LIST and MAP",
+        "SELECT COUNT(DISTINCT items) AS items, COUNT(DISTINCT attrs) AS attrs FROM Bag",
+        Ok(select!(items | attrs; I64 | I64; 2 2)),
+    )
+    .await;
+    g.named_test(
+        "ARRAY_AGG DISTINCT over MAP",
+        "SELECT ARRAY_AGG(DISTINCT attrs) AS attrs FROM Bag WHERE id < 4",
+        Ok(select_with_null!(
+            attrs;
+            List(vec![m(r#"{"a": 1, "b": [true]}"#), m(r#"{"a": 2}"#)])
+        )),
+    )
+    .await;
+});
@@ -0,0 +1,298 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::ExecuteError,
+        executor::{AnalyzedPlan, PlanNode, PlanStats, StorageCalls},
+        prelude::{Payload, Value::*},
+    },
+    std::time::Duration,
+};
+
+fn plan_tree(payload: Payload) -> String {
+    match payload {
+        Payload::Explain(plan) => plan.to_string(),
+        payload => panic!("unexpected payload: {payload:?}"),
+    }
+}
+
+/// Analyzed plan tree with the timings zeroed, so that only row counts are compared
+fn analyzed_tree(payload: Payload) -> (String, StorageCalls) {
+    fn zero_elapsed(node: PlanNode) -> PlanNode {
+        let stats = node.stats.map(|stats| PlanStats {
+            elapsed: Duration::ZERO,
+            ..stats
+        });
+        let children = node.children.into_iter().map(zero_elapsed).collect();
+
+        PlanNode {
+            children,
+            stats,
+            ..node
+        }
+    }
+
+    match payload {
+        Payload::ExplainAnalyze(AnalyzedPlan {
+            plan,
+            storage_calls,
+            ..
+        }) => (zero_elapsed(plan).to_string(), storage_calls),
+        payload => panic!("unexpected payload: {payload:?}"),
+    }
+}
+
+fn calls(scan_data: usize, scan_indexed_data: usize, fetch_data: usize) -> StorageCalls {
+    StorageCalls {
+        scan_data,
+        scan_indexed_data,
+        fetch_data,
+    }
+}
+
+test_case!(explain, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Player (id INTEGER PRIMARY KEY, name TEXT, team_id INTEGER)")
+        .await;
+    g.run("CREATE TABLE Team (id INTEGER, name TEXT)").await;
+    g.run("INSERT INTO Player VALUES (1, 'Taehoon', 1), (2, 'Mike', 2)")
+        .await;
+    g.run("INSERT INTO Team VALUES (1, 'Glue'), (2, 'SQL')")
+        .await;
+
+    g.test(
+        "EXPLAIN SELECT * FROM Player",
+        Ok(Payload::Explain(PlanNode::new(
+            "Table Scan",
+            Some("Player".to_owned()),
+            Vec::new(),
+        ))),
+    )
+    .await;
+
+    let test_cases = [
+        (
+            "EXPLAIN SELECT name FROM Player WHERE id = 1",
+            "Primary Key Lookup: Player, KEY = 1\n",
+        ),
+        (
+            "EXPLAIN SELECT name FROM Player WHERE name = 'Mike' ORDER BY id DESC LIMIT 1 OFFSET 1",
+            "\
+Limit: LIMIT 1 OFFSET 1
+└─ Sort: id DESC
+   └─ Filter: name = 'Mike'
+      └─ Table Scan: Player
+",
+        ),
+        (
+            "EXPLAIN SELECT team_id, COUNT(*) FROM Player GROUP BY team_id HAVING COUNT(*) > 0",
+            "\
+Aggregate: GROUP BY team_id HAVING COUNT(*) > 0
+└─ Table Scan: Player
+",
+        ),
+        (
+            "EXPLAIN SELECT DISTINCT p.name FROM Player p JOIN Team t ON p.team_id = t.id",
+            "\
+Distinct
+└─ Hash Join: INNER, HASH KEY t.id = p.team_id
+   ├─ Table Scan: Player AS p
+   └─ Table Scan: Team AS t
+",
+        ),
+        (
+            "EXPLAIN SELECT * FROM Player p LEFT JOIN Team t ON p.team_id > t.id",
+            "\
+Nested Loop Join: LEFT OUTER, ON p.team_id > t.id
+├─ Table Scan: Player AS p
+└─ Table Scan: Team AS t
+",
+        ),
+        (
+            "EXPLAIN SELECT * FROM (SELECT id FROM Team) AS Sub UNION ALL SELECT 1",
+            "\
+Union: ALL
+├─ Subquery: Sub
+│  └─ Table Scan: Team
+└─ Series: Series (1)
+",
+        ),
+        (
+            "EXPLAIN WITH Player AS (SELECT id, name FROM Player WHERE id = 1)
+            SELECT name FROM Player WHERE id = 1",
+            "\
+With
+├─ CTE: Player
+│  └─ Primary Key Lookup: Player, KEY = 1
+└─ Filter: id = 1
+   └─ Table Scan: Player
+",
+        ),
+        (
+            "EXPLAIN INSERT INTO Team SELECT id + 10, name FROM Team",
+            "\
+Insert: Team
+└─ Table Scan: Team
+",
+        ),
+        (
+            "EXPLAIN UPDATE Player SET name = 'Noname' WHERE team_id = 2",
+            "\
+Update: Player
+└─ Filter: team_id = 2
+   └─ Table Scan: Player
+",
+        ),
+        (
+            "EXPLAIN DELETE FROM Team",
+            "\
+Delete: Team
+└─ Table Scan: Team
+",
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        assert_eq!(plan_tree(g.run(sql).await), expected, "[EXPLAIN] {sql}");
+    }
+
+    g.test(
+        "EXPLAIN DROP TABLE Team",
+        Err(ExecuteError::UnsupportedExplain.into()),
+    )
+    .await;
+
+    g.test("SELECT COUNT(*) FROM Team", Ok(select!("COUNT(*)" I64; 2)))
+        .await;
+});
+
+test_case!(explain_analyze, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Player (id INTEGER PRIMARY KEY, name TEXT, team_id INTEGER)")
+        .await;
+    g.run("CREATE TABLE Team (id INTEGER, name TEXT)").await;
+    g.run("INSERT INTO Player VALUES (1, 'Taehoon', 1), (2, 'Mike', 2), (3, 'Jorno', 2)")
+        .await;
+    g.run("INSERT INTO Team VALUES (1, 'Glue'), (2, 'SQL')")
+        .await;
+
+    let test_cases = [
+        (
+            "EXPLAIN ANALYZE SELECT name FROM Player WHERE id = 1",
+            "Primary Key Lookup: Player, KEY = 1 (rows: 1, time: 0ns)\n",
+            calls(0, 0, 1),
+        ),
+        (
+            "EXPLAIN ANALYZE SELECT name FROM Player WHERE team_id = 2 ORDER BY id DESC LIMIT 1",
+            "\
+Limit: LIMIT 1 (rows: 1 -> 1, time: 0ns)
+└─ Sort: id DESC (rows: 2 -> 1, time: 0ns)
+   └─ Filter: team_id = 2 (rows: 3 -> 2, time: 0ns)
+      └─ Table Scan: Player (rows: 3, time: 0ns)
+",
+            calls(1, 0, 0),
+        ),
+        (
+            "EXPLAIN ANALYZE SELECT team_id, COUNT(*) FROM Player GROUP BY team_id",
+            "\
+Aggregate: GROUP BY team_id (rows: 3 -> 2, time: 0ns)
+└─ Table Scan: Player (rows: 3, time: 0ns)
+",
+            calls(1, 0, 0),
+        ),
+        (
+            "EXPLAIN ANALYZE SELECT DISTINCT t.name FROM Player p JOIN Team t ON p.team_id = t.id",
+            "\
+Distinct (rows: 3 -> 2, time: 0ns)
+└─ Hash Join: INNER, HASH KEY t.id = p.team_id (rows: 3 -> 3, time: 0ns)
+   ├─ Table Scan: Player AS p (rows: 3, time: 0ns)
+   └─ Table Scan: Team AS t
+",
+            calls(2, 0, 0),
+        ),
+        (
+            "EXPLAIN ANALYZE SELECT id FROM Team UNION SELECT team_id FROM Player",
+            "\
+Union (rows: 5 -> 2, time: 0ns)
+├─ Table Scan: Team (rows: 2, time: 0ns)
+└─ Table Scan: Player (rows: 3, time: 0ns)
+",
+            calls(2, 0, 0),
+        ),
+        (
+            "EXPLAIN ANALYZE SELECT id FROM Team UNION ALL SELECT id FROM Player WHERE team_id = 2
+            EXCEPT SELECT team_id FROM Player",
+            "\
+Except (rows: 7 -> 1, time: 0ns)
+├─ Union: ALL (rows: 4 -> 4, time: 0ns)
+│  ├─ Table Scan: Team (rows: 2, time: 0ns)
+│  └─ Filter: team_id = 2 (rows: 3 -> 2, time: 0ns)
+│     └─ Table Scan: Player (rows: 3, time: 0ns)
+└─ Table Scan: Player (rows: 3, time: 0ns)
+",
+            calls(3, 0, 0),
+        ),
+    ];
+
+    for (sql, expected_tree, expected_calls) in test_cases {
+        let (tree, storage_calls) = analyzed_tree(g.run(sql).await);
+
+        assert_eq!(tree, expected_tree, "[EXPLAIN ANALYZE] {sql}");
+        assert_eq!(storage_calls, expected_calls, "[EXPLAIN ANALYZE] {sql}");
+    }
+
+    g.test(
+        "EXPLAIN ANALYZE DELETE FROM Team",
+        Err(ExecuteError::UnsupportedExplainAnalyze.into()),
+    )
+    .await;
+
+    g.test("SELECT COUNT(*) FROM Team", Ok(select!("COUNT(*)" I64; 2)))
+        .await;
+});
+
+test_case!(explain_index, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Orders (id INTEGER, customer_id INTEGER, amount INTEGER)")
+        .await;
+    g.run("CREATE INDEX idx_amount ON Orders (amount)").await;
+    g.run("CREATE INDEX idx_customer ON Orders (customer_id, amount DESC)")
+        .await;
+
+    let test_cases = [
+        (
+            "EXPLAIN SELECT id FROM Orders WHERE amount > 100",
+            "Index Scan: Orders USING idx_amount, KEY > 100\n",
+        ),
+        (
+            "EXPLAIN SELECT id FROM Orders WHERE customer_id = 1 AND amount <= 10 AND id > 0",
+            "\
+Filter: id > 0
+└─ Index Scan: Orders USING idx_customer, PREFIX = (1), KEY <= 10
+",
+        ),
+        (
+            "EXPLAIN SELECT id FROM Orders ORDER BY amount DESC",
+            "Index Scan: Orders USING idx_amount, DESC\n",
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        assert_eq!(plan_tree(g.run(sql).await), expected, "[EXPLAIN] {sql}");
+    }
+
+    g.run("INSERT INTO Orders VALUES (1, 1, 50), (2, 1, 150), (3, 2, 200)")
+        .await;
+
+    let (tree, storage_calls) = analyzed_tree(
+        g.run("EXPLAIN ANALYZE SELECT id FROM Orders WHERE amount > 100")
+            .await,
+    );
+    assert_eq!(
+        tree,
+        "Index Scan: Orders USING idx_amount, KEY > 100 (rows: 2, time: 0ns)\n"
+    );
+    assert_eq!(storage_calls, calls(0, 1, 0));
+});
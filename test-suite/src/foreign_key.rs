@@ -0,0 +1,260 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::DataType,
+        error::{AlterError, TranslateError, ValidateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(foreign_key, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Parent (id INTEGER PRIMARY KEY, code TEXT UNIQUE, name TEXT);")
+        .await;
+
+    let test_cases = [
+        (
+            "CREATE TABLE Child (parent_id INTEGER REFERENCES Unknown (id));",
+            Err(AlterError::ReferencedTableNotFound("Unknown".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Child (parent_id INTEGER REFERENCES Parent (uid));",
+            Err(AlterError::ReferencedColumnNotFound("uid".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Child (parent_name TEXT REFERENCES Parent (name));",
+            Err(AlterError::ReferencedColumnNotUnique("name".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Child (parent_id TEXT REFERENCES Parent (id));",
+            Err(AlterError::ForeignKeyDataTypeMismatch {
+                foreign_key: "FK_parent_id-Parent_id".to_owned(),
+                referencing: DataType::Text,
+                referenced: DataType::Int,
+            }
+            .into()),
+        ),
+        (
+            "CREATE TABLE Child (parent_id INTEGER NOT NULL REFERENCES Parent (id) ON DELETE SET NULL);",
+            Err(AlterError::SetNullOnNotNullColumn("FK_parent_id-Parent_id".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Child (parent_code TEXT PRIMARY KEY REFERENCES Parent (code) ON UPDATE CASCADE);",
+            Err(AlterError::UpdateActionOnPrimaryKeyColumn(
+                "FK_parent_code-Parent_code".to_owned(),
+            )
+            .into()),
+        ),
+        (
+            "CREATE TABLE Child (parent_code TEXT REFERENCES Parent (code) ON UPDATE CASCADE, seq INTEGER, PRIMARY KEY (parent_code, seq));",
+            Err(AlterError::UpdateActionOnPrimaryKeyColumn(
+                "FK_parent_code-Parent_code".to_owned(),
+            )
+            .into()),
+        ),
+        (
+            "CREATE TABLE Child (id INTEGER, FOREIGN KEY (parent_id) REFERENCES Parent (id));",
+            Err(AlterError::ReferencingColumnNotFound("parent_id".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Child (a INTEGER, b TEXT, FOREIGN KEY (a, b) REFERENCES Parent (id, code));",
+            Err(TranslateError::CompositeForeignKeyNotSupported(
+                "FOREIGN KEY (a, b) REFERENCES Parent(id, code)".to_owned(),
+            )
+            .into()),
+        ),
+        (
+            "CREATE TABLE Child (parent_id INTEGER REFERENCES Parent (id) ON DELETE SET DEFAULT);",
+            Err(TranslateError::UnsupportedReferentialAction("SET DEFAULT".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+
+    g.run(
+        "
+CREATE TABLE Child (
+    id INTEGER PRIMARY KEY,
+    parent_id INTEGER REFERENCES Parent (id) ON DELETE CASCADE,
+    parent_code TEXT,
+    CONSTRAINT fk_code FOREIGN KEY (parent_code) REFERENCES Parent (code)
+        ON DELETE SET NULL ON UPDATE CASCADE
+);",
+    )
+    .await;
+    g.run("INSERT INTO Parent VALUES (1, 'A', 'Alpha'), (2, 'B', 'Beta'), (3, 'C', 'Gamma');")
+        .await;
+
+    g.named_test(
+        "insert with matching and NULL references",
+        "INSERT INTO Child VALUES (1, 1, 'A'), (2, 1, NULL), (3, NULL, 'B'), (4, 3, 'C');",
+        Ok(Payload::Insert(4)),
+    )
+    .await;
+
+    g.named_test(
+        "insert with missing referenced value",
+        "INSERT INTO Child VALUES (5, 9, NULL);",
+        Err(ValidateError::ReferencedValueNotFound {
+            foreign_key: "FK_parent_id-Parent_id".to_owned(),
+            value: I64(9),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "update with missing referenced value",
+        "UPDATE Child SET parent_code = 'Z' WHERE id = 1;",
+        Err(ValidateError::ReferencedValueNotFound {
+            foreign_key: "fk_code".to_owned(),
+            value: Str("Z".to_owned()),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "update referenced column cascades",
+        "UPDATE Parent SET code = 'AA' WHERE id = 1;",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+
+    g.named_test(
+        "delete referenced rows cascades and sets null",
+        "DELETE FROM Parent WHERE id < 3;",
+        Ok(Payload::Delete(2)),
+    )
+    .await;
+
+    g.test(
+        "SELECT * FROM Child ORDER BY id;",
+        Ok(select_with_null!(
+            id     | parent_id | parent_code;
+            I64(3)   Null        Null;
+            I64(4)   I64(3)      Str("C".to_owned())
+        )),
+    )
+    .await;
+
+    g.run("CREATE TABLE Tag (id INTEGER PRIMARY KEY);").await;
+    g.run("CREATE TABLE ItemTag (item_id INTEGER, tag_id INTEGER REFERENCES Tag (id) ON DELETE RESTRICT);")
+        .await;
+    g.run("INSERT INTO Tag VALUES (1), (2);").await;
+    g.run("INSERT INTO ItemTag VALUES (100, 1);").await;
+
+    g.named_test(
+        "delete restricted by referencing row",
+        "DELETE FROM Tag;",
+        Err(ValidateError::ReferencingRowExists {
+            foreign_key: "FK_tag_id-Tag_id".to_owned(),
+            referencing_table_name: "ItemTag".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.count("SELECT * FROM Tag", 2).await;
+    g.test("DELETE FROM Tag WHERE id = 2;", Ok(Payload::Delete(1)))
+        .await;
+
+    g.run(
+        "CREATE TABLE Employee (id INTEGER PRIMARY KEY, manager_id INTEGER REFERENCES Employee (id) ON DELETE SET NULL);",
+    )
+    .await;
+    g.named_test(
+        "self-referencing rows in a single insert",
+        "INSERT INTO Employee VALUES (1, NULL), (2, 1), (3, 2);",
+        Ok(Payload::Insert(3)),
+    )
+    .await;
+    g.run("DELETE FROM Employee WHERE id = 1;").await;
+    g.test(
+        "SELECT * FROM Employee ORDER BY id;",
+        Ok(select_with_null!(
+            id     | manager_id;
+            I64(2)   Null;
+            I64(3)   I64(2)
+        )),
+    )
+    .await;
+
+    g.run("CREATE TABLE Person (id INTEGER PRIMARY KEY);").await;
+    g.run(
+        "
+CREATE TABLE Review (
+    id INTEGER PRIMARY KEY,
+    author_id INTEGER REFERENCES Person (id) ON DELETE SET NULL,
+    reviewer_id INTEGER REFERENCES Person (id) ON DELETE SET NULL,
+    approver_id INTEGER REFERENCES Person (id) ON DELETE RESTRICT
+);",
+    )
+    .await;
+    g.run("INSERT INTO Person VALUES (1), (2), (3);").await;
+    g.run("INSERT INTO Review VALUES (1, 1, 1, 3), (2, 2, 1, 2);")
+        .await;
+
+    g.named_test(
+        "restrict of another foreign key on a row set to null",
+        "DELETE FROM Person WHERE id = 2;",
+        Err(ValidateError::ReferencingRowExists {
+            foreign_key: "FK_approver_id-Person_id".to_owned(),
+            referencing_table_name: "Review".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.named_test(
+        "every foreign key on a row sets null",
+        "DELETE FROM Person WHERE id = 1;",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+    g.test(
+        "SELECT * FROM Review ORDER BY id;",
+        Ok(select_with_null!(
+            id     | author_id | reviewer_id | approver_id;
+            I64(1)   Null        Null          I64(3);
+            I64(2)   I64(2)      Null          I64(2)
+        )),
+    )
+    .await;
+
+    g.test(
+        "SELECT * FROM GLUE_TABLE_CONSTRAINTS WHERE TABLE_NAME = 'Child';",
+        Ok(select!(
+            TABLE_NAME | CONSTRAINT_NAME | CONSTRAINT_TYPE | COLUMN_NAME | DEFINITION
+            Str        | Str             | Str             | Str         | Str;
+            "Child".to_owned()  "FK_parent_id-Parent_id".to_owned()  "FOREIGN KEY".to_owned()  "parent_id".to_owned()  "REFERENCES Parent (id) ON DELETE CASCADE ON UPDATE NO ACTION".to_owned();
+            "Child".to_owned()  "fk_code".to_owned()  "FOREIGN KEY".to_owned()  "parent_code".to_owned()  "REFERENCES Parent (code) ON DELETE SET NULL ON UPDATE CASCADE".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "drop column used by foreign key",
+        "ALTER TABLE Child DROP COLUMN parent_code;",
+        Err(AlterError::ColumnReferencedByForeignKey {
+            column_name: "parent_code".to_owned(),
+            foreign_key: "fk_code".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "drop referenced table",
+        "DROP TABLE Tag;",
+        Err(AlterError::TableReferencedByForeignKey {
+            table_name: "Tag".to_owned(),
+            foreign_key: "FK_tag_id-Tag_id".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.test("DROP TABLE ItemTag, Tag;", Ok(Payload::DropTable))
+        .await;
+});
@@ -2,7 +2,7 @@ use {
     crate::*,
     gluesql_core::{
         ast::IndexOperator::*,
-        error::{AlterError, IndexError, TranslateError},
+        error::{AlterError, IndexError, TranslateError, ValueError},
         prelude::{Payload, Value::*},
     },
 };
@@ -318,8 +318,8 @@ test_case!(basic, {
     .await;
 
     g.test(
-        "CREATE INDEX idx_com ON Test (id, num)",
-        Err(TranslateError::CompositeIndexNotSupported.into()),
+        "CREATE INDEX idx_com ON Test (id, a.b)",
+        Err(AlterError::UnsupportedIndexExpr(expr("a.b")).into()),
     )
     .await;
 
@@ -358,4 +358,25 @@ test_case!(basic, {
         Err(IndexError::IndexNameDoesNotExist("idx_aaa".to_owned()).into()),
     )
     .await;
+
+    g.run("CREATE TABLE Code (id INTEGER, code TEXT)").await;
+    g.run("CREATE INDEX idx_code ON Code (code)").await;
+    g.run("INSERT INTO Code VALUES (1, '10'), (2, 'x')").await;
+
+    g.test(
+        "ALTER TABLE Code ALTER COLUMN code TYPE INTEGER",
+        Err(ValueError::ImpossibleCast.into()),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id, code FROM Code WHERE code = '10'",
+        Ok(select!(
+            id  | code
+            I64 | Str;
+            1     "10".to_owned()
+        )),
+        idx!(idx_code, Eq, "'10'"),
+    )
+    .await;
 });
@@ -0,0 +1,312 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::IndexOperator::*,
+        data::Key,
+        error::{AlterError, InsertError, ValidateError},
+        prelude::*,
+    },
+    Value::*,
+};
+
+test_case!(composite, {
+    let g = get_tester!();
+
+    g.run(
+        "
+CREATE TABLE Orders (
+    id INTEGER PRIMARY KEY,
+    customer_id INTEGER,
+    created_at INTEGER,
+    amount INTEGER
+)",
+    )
+    .await;
+
+    g.run(
+        "
+        INSERT INTO Orders VALUES
+            (1, 1, 3, 100),
+            (2, 1, 7, 300),
+            (3, 2, 5, 200),
+            (4, 1, 5, 400),
+            (5, 2, 1, 500),
+            (6, 3, 7, 600);
+    ",
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_customer ON Orders (customer_id, created_at DESC)",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id, created_at FROM Orders WHERE customer_id = 1 AND created_at > 3",
+        Ok(select!(
+            id  | created_at
+            I64 | I64;
+            2     7;
+            4     5
+        )),
+        idx!(idx_customer, ["1"], Gt, "3"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE 5 >= created_at AND customer_id = 1",
+        Ok(select!(
+            id
+            I64;
+            4;
+            1
+        )),
+        idx!(idx_customer, ["1"], LtEq, "5"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE created_at = 5 AND amount > 300 AND (customer_id = 1)",
+        Ok(select!(id I64; 4)),
+        idx!(idx_customer, ["1"], Eq, "5"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id, created_at FROM Orders WHERE customer_id = 2",
+        Ok(select!(
+            id  | created_at
+            I64 | I64;
+            3     5;
+            5     1
+        )),
+        idx!(idx_customer, Eq, "2"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE created_at = 7",
+        Ok(select!(
+            id
+            I64;
+            2;
+            6
+        )),
+        idx!(),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders ORDER BY customer_id, created_at DESC",
+        Ok(select!(
+            id
+            I64;
+            2; 4; 1; 3; 5; 6
+        )),
+        idx!(idx_customer, ASC),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders ORDER BY customer_id DESC, created_at ASC",
+        Ok(select!(
+            id
+            I64;
+            6; 5; 3; 1; 4; 2
+        )),
+        idx!(idx_customer, DESC),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE amount < 500 ORDER BY amount DESC, customer_id",
+        Ok(select!(
+            id
+            I64;
+            4; 2; 3; 1
+        )),
+        idx!(idx_customer, ASC),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders ORDER BY customer_id, created_at",
+        Ok(select!(
+            id
+            I64;
+            1; 4; 2; 5; 3; 6
+        )),
+        idx!(),
+    )
+    .await;
+
+    g.run("UPDATE Orders SET created_at = 9 WHERE id = 1").await;
+    g.run("DELETE FROM Orders WHERE id = 4").await;
+
+    g.test_idx(
+        "SELECT id, created_at FROM Orders WHERE customer_id = 1 AND created_at >= 7",
+        Ok(select!(
+            id  | created_at
+            I64 | I64;
+            1     9;
+            2     7
+        )),
+        idx!(idx_customer, ["1"], GtEq, "7"),
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_wrong ON Orders (customer_id, amount.value)",
+        Err(AlterError::UnsupportedIndexExpr(expr("amount.value")).into()),
+    )
+    .await;
+});
+
+test_case!(unique, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Users (id INTEGER, org INTEGER, email TEXT)")
+        .await;
+    g.run(
+        "INSERT INTO Users VALUES (1, 1, 'a@glue'), (2, 1, 'b@glue'), (3, 2, 'a@glue'), (4, 2, NULL)",
+    )
+    .await;
+
+    let test_cases = [
+        (
+            "CREATE UNIQUE INDEX idx_email ON Users (email)",
+            Err(ValidateError::DuplicateEntryOnUniqueIndex {
+                index_name: "idx_email".to_owned(),
+                key: Key::Str("a@glue".to_owned()),
+            }
+            .into()),
+        ),
+        (
+            "CREATE UNIQUE INDEX idx_org_email ON Users (org, email)",
+            Ok(Payload::CreateIndex),
+        ),
+        (
+            "CREATE UNIQUE INDEX idx_id ON Users (id)",
+            Ok(Payload::CreateIndex),
+        ),
+        (
+            "INSERT INTO Users VALUES (5, 1, 'a@glue')",
+            Err(ValidateError::DuplicateEntryOnUniqueIndex {
+                index_name: "idx_org_email".to_owned(),
+                key: Key::Composite(vec![Key::I64(1), Key::Str("a@glue".to_owned())]),
+            }
+            .into()),
+        ),
+        (
+            "INSERT INTO Users VALUES (5, 3, 'c@glue'), (6, 3, 'c@glue')",
+            Err(ValidateError::DuplicateEntryOnUniqueIndex {
+                index_name: "idx_org_email".to_owned(),
+                key: Key::Composite(vec![Key::I64(3), Key::Str("c@glue".to_owned())]),
+            }
+            .into()),
+        ),
+        (
+            "INSERT INTO Users VALUES (5, 2, NULL), (6, 3, 'b@glue')",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "INSERT INTO Users VALUES (1, 3, 'd@glue')",
+            Err(ValidateError::DuplicateEntryOnUniqueIndex {
+                index_name: "idx_id".to_owned(),
+                key: Key::I64(1),
+            }
+            .into()),
+        ),
+        (
+            "UPDATE Users SET email = 'b@glue' WHERE id = 1",
+            Err(ValidateError::DuplicateEntryOnUniqueIndex {
+                index_name: "idx_org_email".to_owned(),
+                key: Key::Composite(vec![Key::I64(1), Key::Str("b@glue".to_owned())]),
+            }
+            .into()),
+        ),
+        (
+            "UPDATE Users SET email = 'c@glue' WHERE id = 1",
+            Ok(Payload::Update(1)),
+        ),
+        (
+            "SELECT id, email FROM Users WHERE org = 1 AND email = 'c@glue'",
+            Ok(select!(
+                id  | email
+                I64 | Str;
+                1     "c@glue".to_owned()
+            )),
+        ),
+        (
+            "INSERT INTO Users VALUES (1, 9, 'z@glue') ON CONFLICT (id) DO UPDATE SET email = 'x@glue'",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "INSERT INTO Users VALUES (7, 1, 'x@glue') ON CONFLICT (email, org) DO NOTHING",
+            Ok(Payload::Insert(0)),
+        ),
+        (
+            "INSERT INTO Users VALUES (7, 1, 'y@glue') ON CONFLICT (org) DO NOTHING",
+            Err(InsertError::ConflictTargetNotUnique("org".to_owned()).into()),
+        ),
+        (
+            "SELECT id, org, email FROM Users WHERE id = 1",
+            Ok(select!(
+                id  | org | email
+                I64 | I64 | Str;
+                1     1     "x@glue".to_owned()
+            )),
+        ),
+        (
+            "ALTER TABLE Users ALTER COLUMN org TYPE FLOAT",
+            Err(AlterError::DataTypeChangeOnUniqueColumn("org".to_owned()).into()),
+        ),
+        (
+            "SELECT INDEX_NAME, EXPRESSION, UNIQUENESS FROM GLUE_INDEXES",
+            Ok(select!(
+                INDEX_NAME                | EXPRESSION               | UNIQUENESS
+                Str                       | Str                      | Bool;
+                "idx_org_email".to_owned()  "org, email".to_owned()    true;
+                "idx_id".to_owned()         "id".to_owned()            true
+            )),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+
+    g.run("CREATE TABLE Team (id INTEGER PRIMARY KEY, code TEXT UNIQUE)")
+        .await;
+    g.run(
+        "CREATE TABLE Seat (id INTEGER PRIMARY KEY, team_code TEXT REFERENCES Team (code) ON UPDATE CASCADE, num INTEGER)",
+    )
+    .await;
+    g.run("CREATE UNIQUE INDEX idx_late_seat ON Seat (num) WHERE team_code > 'm'")
+        .await;
+    g.run("INSERT INTO Team VALUES (1, 'a'), (2, 'n')").await;
+    g.run("INSERT INTO Seat VALUES (1, 'a', 1), (2, 'n', 1)")
+        .await;
+
+    g.named_test(
+        "cascaded update is validated against unique indexes",
+        "UPDATE Team SET code = 'z' WHERE id = 1",
+        Err(ValidateError::DuplicateEntryOnUniqueIndex {
+            index_name: "idx_late_seat".to_owned(),
+            key: Key::I64(1),
+        }
+        .into()),
+    )
+    .await;
+    g.test(
+        "SELECT id, team_code FROM Seat",
+        Ok(select!(
+            id  | team_code
+            I64 | Str;
+            1     "a".to_owned();
+            2     "n".to_owned()
+        )),
+    )
+    .await;
+});
@@ -1,19 +1,23 @@
 mod and;
 mod basic;
+mod composite;
 mod expr;
 mod nested;
 mod null;
 mod order_by;
+mod partial;
 mod showindexes;
 mod value;
 
 pub use {
     and::and,
     basic::basic,
+    composite::{composite, unique},
     expr::expr,
     nested::nested,
     null::null,
     order_by::{order_by, order_by_multi},
+    partial::partial,
     showindexes::showindexes,
     value::value,
 };
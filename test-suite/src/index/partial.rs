@@ -0,0 +1,290 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::IndexOperator::*,
+        data::Key,
+        error::{AlterError, ValidateError},
+        prelude::*,
+    },
+    Value::*,
+};
+
+test_case!(partial, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Orders (id INTEGER PRIMARY KEY, customer_id INTEGER, status TEXT)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Orders VALUES
+            (1, 1, 'active'),
+            (2, 1, 'archived'),
+            (3, 2, 'active'),
+            (4, 2, 'archived'),
+            (5, 3, 'archived'),
+            (6, 3, 'active');
+    ",
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_active ON Orders (customer_id) WHERE status = 'active'",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE customer_id = 1 AND status = 'active'",
+        Ok(select!(id I64; 1)),
+        idx!(idx_active, Eq, "1"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE (status = 'active') AND customer_id > 1",
+        Ok(select!(
+            id
+            I64;
+            3;
+            6
+        )),
+        idx!(idx_active, Gt, "1"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE customer_id = 1",
+        Ok(select!(
+            id
+            I64;
+            1;
+            2
+        )),
+        idx!(),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE customer_id = 1 AND status = 'archived'",
+        Ok(select!(id I64; 2)),
+        idx!(),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE status = 'active' ORDER BY customer_id DESC",
+        Ok(select!(
+            id
+            I64;
+            6;
+            3;
+            1
+        )),
+        idx!(idx_active, DESC),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders ORDER BY customer_id DESC",
+        Ok(select!(
+            id
+            I64;
+            5; 6; 3; 4; 1; 2
+        )),
+        idx!(),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE customer_id = 1 AND 'active' = status",
+        Ok(select!(id I64; 1)),
+        idx!(idx_active, Eq, "1"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE customer_id = 1 AND status IN ('active')",
+        Ok(select!(id I64; 1)),
+        idx!(idx_active, Eq, "1"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE customer_id = 1 AND status IN ('active', 'archived')",
+        Ok(select!(
+            id
+            I64;
+            1;
+            2
+        )),
+        idx!(),
+    )
+    .await;
+
+    g.run("UPDATE Orders SET status = 'active' WHERE id = 4")
+        .await;
+    g.run("UPDATE Orders SET status = 'archived' WHERE id = 3")
+        .await;
+    g.run("DELETE FROM Orders WHERE id = 6").await;
+
+    g.test_idx(
+        "SELECT id FROM Orders WHERE status = 'active' AND customer_id >= 2",
+        Ok(select!(id I64; 4)),
+        idx!(idx_active, GtEq, "2"),
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_wrong ON Orders (customer_id) WHERE status IN (SELECT status FROM Orders)",
+        Err(AlterError::UnsupportedIndexPredicate(expr(
+            "status IN (SELECT status FROM Orders)",
+        ))
+        .into()),
+    )
+    .await;
+
+    g.test(
+        "CREATE UNIQUE INDEX idx_unique_active ON Orders (customer_id) WHERE status = 'active'",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.test(
+        "INSERT INTO Orders VALUES (7, 1, 'archived'), (8, 3, 'active')",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+
+    g.test(
+        "INSERT INTO Orders VALUES (9, 2, 'active')",
+        Err(ValidateError::DuplicateEntryOnUniqueIndex {
+            index_name: "idx_unique_active".to_owned(),
+            key: Key::I64(2),
+        }
+        .into()),
+    )
+    .await;
+
+    g.test(
+        "UPDATE Orders SET status = 'active' WHERE id = 7",
+        Err(ValidateError::DuplicateEntryOnUniqueIndex {
+            index_name: "idx_unique_active".to_owned(),
+            key: Key::I64(1),
+        }
+        .into()),
+    )
+    .await;
+
+    g.run("CREATE TABLE Score (id INTEGER PRIMARY KEY, score INTEGER, grp INTEGER, tag INTEGER)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Score VALUES
+            (1, 3, 1, 1),
+            (2, 7, 1, 2),
+            (3, 9, 1, 1),
+            (4, 12, 2, 2),
+            (5, 11, 1, 1);
+    ",
+    )
+    .await;
+    g.run("CREATE INDEX idx_high ON Score (grp) WHERE score > 5")
+        .await;
+    g.run("CREATE INDEX idx_listed ON Score (tag) WHERE score IN (3, 7, 9)")
+        .await;
+
+    let implied = [
+        ("grp = 1 AND score > 10", vec![5]),
+        ("grp = 1 AND 10 < score", vec![5]),
+        ("grp = 1 AND score >= 6", vec![2, 3, 5]),
+        ("grp = 1 AND score = 7", vec![2]),
+        ("grp = 1 AND score BETWEEN 6 AND 8", vec![2]),
+        ("grp = 1 AND score IN (7, 9)", vec![2, 3]),
+    ];
+
+    for (selection, ids) in implied {
+        g.test_idx(
+            &format!("SELECT id FROM Score WHERE {selection}"),
+            Ok(Payload::Select {
+                labels: vec!["id".to_owned()],
+                rows: ids.into_iter().map(|id| vec![I64(id)]).collect(),
+            }),
+            idx!(idx_high, Eq, "1"),
+        )
+        .await;
+    }
+
+    let not_implied = [
+        ("grp = 1 AND score > 4", vec![2, 3, 5]),
+        ("grp = 1 AND score >= 5", vec![2, 3, 5]),
+        ("grp = 1 AND score IN (3, 9)", vec![1, 3]),
+        ("grp = 1 AND (score > 10 OR score > 20)", vec![5]),
+        ("grp = 1 AND score > id", vec![1, 2, 3, 5]),
+    ];
+
+    for (selection, ids) in not_implied {
+        g.test_idx(
+            &format!("SELECT id FROM Score WHERE {selection}"),
+            Ok(Payload::Select {
+                labels: vec!["id".to_owned()],
+                rows: ids.into_iter().map(|id| vec![I64(id)]).collect(),
+            }),
+            idx!(),
+        )
+        .await;
+    }
+
+    g.test_idx(
+        "SELECT id FROM Score WHERE tag = 1 AND score = 9",
+        Ok(select!(id I64; 3)),
+        idx!(idx_listed, Eq, "1"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Score WHERE tag = 2 AND score IN (7, 3)",
+        Ok(select!(id I64; 2)),
+        idx!(idx_listed, Eq, "2"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Score WHERE tag = 1 AND score = 11",
+        Ok(select!(id I64; 5)),
+        idx!(),
+    )
+    .await;
+
+    g.run("CREATE TABLE Shift (id INTEGER PRIMARY KEY, starts TIME, grp INTEGER)")
+        .await;
+    g.run("INSERT INTO Shift VALUES (1, '08:00:00', 1), (2, '09:30:00', 1), (3, '11:00:00', 1)")
+        .await;
+    g.run("CREATE INDEX idx_late_shift ON Shift (grp) WHERE starts > '10:00:00'")
+        .await;
+    g.test_idx(
+        "SELECT id FROM Shift WHERE grp = 1 AND starts > '9:00:00'",
+        Ok(select!(id I64; 2; 3)),
+        idx!(),
+    )
+    .await;
+
+    g.run("CREATE TABLE Label (id INTEGER PRIMARY KEY, name TEXT, grp INTEGER)")
+        .await;
+    g.run("INSERT INTO Label VALUES (1, 'apple', 1), (2, 'melon', 1), (3, 'pear', 1)")
+        .await;
+    g.run("CREATE INDEX idx_late ON Label (grp) WHERE name > 'm'")
+        .await;
+    g.test_idx(
+        "SELECT id FROM Label WHERE grp = 1 AND name > 'n'",
+        Ok(select!(id I64; 3)),
+        idx!(idx_late, Eq, "1"),
+    )
+    .await;
+
+    g.run("ALTER TABLE Orders DROP COLUMN status").await;
+    g.test(
+        "SELECT INDEX_NAME FROM GLUE_INDEXES WHERE TABLE_NAME = 'Orders'",
+        Ok(select!(INDEX_NAME Str; "PRIMARY".to_owned())),
+    )
+    .await;
+});
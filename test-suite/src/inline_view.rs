@@ -247,9 +247,14 @@ test_case!(inline_view, {
             Err(TranslateError::TooManyTables.into()),
         ),
         (
-            // unsupported select distinct
-            "SELECT DISTINCT id FROM OuterTable",
-            Err(TranslateError::SelectDistinctNotSupported.into()),
+            // inline view with select distinct
+            "SELECT * FROM (SELECT DISTINCT name FROM InnerTable) AS InlineView",
+            Ok(select!(
+                name
+                Str;
+                "GLUE".to_owned();
+                "SQL".to_owned()
+            )),
         ),
         (
             // inline view subquery + join with inline view
@@ -273,8 +273,8 @@ test_case!(project, {
             TranslateError::UnsupportedJoinConstraint("USING".to_owned()).into(),
         ),
         (
-            "SELECT * FROM TableA CROSS JOIN TableA as A;",
-            TranslateError::UnsupportedJoinOperator("CrossJoin".to_owned()).into(),
+            "SELECT * FROM TableA CROSS APPLY TableA as A;",
+            TranslateError::UnsupportedJoinOperator("CrossApply".to_owned()).into(),
         ),
         (
             "SELECT id FROM Users JOIN Testers ON Users.id = Testers.id;",
@@ -303,3 +303,150 @@ test_case!(project, {
         g.test(sql, Err(error)).await;
     }
 });
+
+test_case!(outer_join, {
+    let g = get_tester!();
+
+    let create_sqls: [&str; 2] = [
+        "
+        CREATE TABLE Player (
+            id INTEGER,
+            name TEXT
+        );
+    ",
+        "
+        CREATE TABLE Item (
+            id INTEGER,
+            quantity INTEGER,
+            player_id INTEGER,
+        );
+    ",
+    ];
+
+    for sql in create_sqls {
+        g.run(sql).await;
+    }
+
+    let insert_sqls = [
+        "
+        INSERT INTO Player (id, name) VALUES
+            (1, 'Taehoon'),
+            (2,    'Mike'),
+            (3,   'Jorno'),
+            (4,   'Berry'),
+            (5,    'Hwan');
+        ",
+        "
+        INSERT INTO Item (id, quantity, player_id) VALUES
+            (101, 1, 1),
+            (102, 4, 2),
+            (103, 9, 4),
+            (104, 2, 7);
+        ",
+    ];
+
+    for insert_sql in insert_sqls {
+        g.run(insert_sql).await;
+    }
+
+    let test_cases = [
+        (
+            "SELECT p.id, i.id FROM Player p RIGHT JOIN Item i ON p.id = i.player_id",
+            select_with_null!(
+                id     | id;
+                I64(1)   I64(101);
+                I64(2)   I64(102);
+                I64(4)   I64(103);
+                Null     I64(104)
+            ),
+        ),
+        (
+            "
+            SELECT p.id, i.id FROM Player p
+            RIGHT OUTER JOIN Item i ON p.id = i.player_id OR i.player_id = 0
+            ",
+            select_with_null!(
+                id     | id;
+                I64(1)   I64(101);
+                I64(2)   I64(102);
+                I64(4)   I64(103);
+                Null     I64(104)
+            ),
+        ),
+        (
+            "SELECT p.id, i.id FROM Player p FULL JOIN Item i ON p.id = i.player_id",
+            select_with_null!(
+                id     | id;
+                I64(1)   I64(101);
+                I64(2)   I64(102);
+                I64(3)   Null;
+                I64(4)   I64(103);
+                I64(5)   Null;
+                Null     I64(104)
+            ),
+        ),
+        (
+            "
+            SELECT p.id, i.id FROM Player p
+            FULL OUTER JOIN Item i ON p.id = i.player_id AND i.quantity > 1
+            ",
+            select_with_null!(
+                id     | id;
+                I64(1)   Null;
+                I64(2)   I64(102);
+                I64(3)   Null;
+                I64(4)   I64(103);
+                I64(5)   Null;
+                Null     I64(101);
+                Null     I64(104)
+            ),
+        ),
+        (
+            "
+            SELECT p.id, i.id FROM Player p
+            FULL JOIN Item i ON p.id = i.player_id
+            WHERE p.id IS NULL OR i.id IS NULL
+            ",
+            select_with_null!(
+                id     | id;
+                I64(3)   Null;
+                I64(5)   Null;
+                Null     I64(104)
+            ),
+        ),
+        (
+            "SELECT p.id, i.id FROM Player p CROSS JOIN Item i WHERE p.id = 2",
+            select!(
+                id  | id
+                I64 | I64;
+                2     101;
+                2     102;
+                2     103;
+                2     104
+            ),
+        ),
+        (
+            "SELECT COUNT(*) FROM Player CROSS JOIN Item",
+            select!("COUNT(*)"; I64; 20),
+        ),
+        (
+            "
+            SELECT p.id, i.id, q.id FROM Player p
+            CROSS JOIN Item i
+            RIGHT JOIN Player q ON q.id = i.player_id AND p.id = 1
+            ",
+            select_with_null!(
+                id     | id       | id;
+                I64(1)   I64(101)   I64(1);
+                I64(1)   I64(102)   I64(2);
+                I64(1)   I64(103)   I64(4);
+                Null     Null       I64(3);
+                Null     Null       I64(5)
+            ),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, Ok(expected)).await;
+    }
+});
@@ -8,15 +8,21 @@ pub mod basic;
 pub mod bitwise_and;
 pub mod bitwise_shift;
 pub mod case;
+pub mod check_constraint;
 pub mod column_alias;
+pub mod composite_key;
 pub mod concat;
+pub mod cte;
 pub mod custom_function;
 pub mod data_type;
 pub mod default;
 pub mod delete;
 pub mod dictionary;
 pub mod dictionary_index;
+pub mod distinct;
+pub mod explain;
 pub mod filter;
+pub mod foreign_key;
 pub mod function;
 pub mod index;
 pub mod inline_view;
@@ -30,18 +36,29 @@ pub mod nested_select;
 pub mod nullable;
 pub mod order_by;
 pub mod ordering;
+pub mod prepared;
 pub mod primary_key;
+pub mod procedure;
 pub mod project;
+pub mod returning;
 pub mod schemaless;
+pub mod sequence;
 pub mod series;
+pub mod set_operation;
 pub mod show_columns;
 pub mod synthesize;
+pub mod table_function;
 pub mod transaction;
+pub mod trigger;
 pub mod type_match;
 pub mod unary_operator;
 pub mod update;
+pub mod update_delete_join;
+pub mod upsert;
 pub mod validate;
 pub mod values;
+pub mod view;
+pub mod window;
 
 pub mod tester;
 
@@ -69,13 +86,27 @@ macro_rules! generate_store_tests {
             };
         }
         glue!(update, update::update);
+        glue!(update_delete_join, update_delete_join::update_delete_join);
+        glue!(upsert, upsert::upsert);
+        glue!(foreign_key, foreign_key::foreign_key);
+        glue!(check_constraint, check_constraint::check_constraint);
+        glue!(composite_key, composite_key::composite_key);
         glue!(insert, insert::insert);
         glue!(delete, delete::delete);
         glue!(basic, basic::basic);
         glue!(bitwise_and, bitwise_and::bitwise_and);
         glue!(aggregate_avg, aggregate::avg::avg);
+        glue!(aggregate_collect, aggregate::collect::collect);
         glue!(aggregate_count, aggregate::count::count);
+        glue!(
+            aggregate_distinct_filter,
+            aggregate::distinct_filter::distinct_filter
+        );
         glue!(aggregate_group_by, aggregate::group_by::group_by);
+        glue!(
+            aggregate_grouping_sets,
+            aggregate::grouping_sets::grouping_sets
+        );
         glue!(aggregate_max, aggregate::max::max);
         glue!(aggregate_min, aggregate::min::min);
         glue!(aggregate_stdev, aggregate::stdev::stdev);
@@ -92,6 +123,12 @@ macro_rules! generate_store_tests {
         glue!(drop_table, alter::drop_table);
         glue!(default, default::default);
         glue!(limit, limit::limit);
+        glue!(distinct, distinct::distinct);
+        glue!(set_operation, set_operation::set_operation);
+        glue!(window, window::window);
+        glue!(cte, cte::cte);
+        glue!(explain, explain::explain);
+        glue!(explain_analyze, explain::explain_analyze);
         glue!(like_ilike, like_ilike::like_ilike);
         glue!(filter, filter::filter);
         glue!(inline_view, inline_view::inline_view);
@@ -154,9 +191,12 @@ macro_rules! generate_store_tests {
         glue!(function_entries, function::entries::entries);
         glue!(join, join::join);
         glue!(join_project, join::project);
+        glue!(join_outer, join::outer_join);
         glue!(migrate, migrate::migrate);
         glue!(nested_select, nested_select::nested_select);
         glue!(primary_key, primary_key::primary_key);
+        glue!(prepared, prepared::prepared);
+        glue!(returning, returning::returning);
         glue!(series, series::series);
         glue!(nullable, nullable::nullable);
         glue!(nullable_text, nullable::nullable_text);
@@ -292,6 +332,19 @@ macro_rules! generate_alter_table_tests {
     };
 }
 
+#[macro_export]
+macro_rules! generate_alter_column_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(alter_table_alter_column, alter::alter_table_alter_column);
+    };
+}
+
 #[macro_export]
 macro_rules! generate_custom_function_tests {
     ($test: meta, $storage: ident) => {
@@ -302,6 +355,54 @@ macro_rules! generate_custom_function_tests {
         }
 
         glue!(function_custom, custom_function::custom);
+        glue!(function_trigger, trigger::function);
+        glue!(function_block, procedure::function_block);
+        glue!(function_procedure, procedure::procedure);
+        glue!(function_nesting, procedure::nesting);
+        glue!(function_table, table_function::table_function);
+    };
+}
+
+#[macro_export]
+macro_rules! generate_sequence_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(sequence, sequence::sequence);
+        glue!(sequence_identity, sequence::identity);
+    };
+}
+
+#[macro_export]
+macro_rules! generate_view_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(view, view::view);
+    };
+}
+
+#[macro_export]
+macro_rules! generate_trigger_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(trigger, trigger::trigger);
+        glue!(trigger_nesting, trigger::nesting);
+        glue!(trigger_foreign_key, trigger::foreign_key);
+        glue!(trigger_set_new, trigger::set_new);
     };
 }
 
@@ -316,6 +417,9 @@ macro_rules! generate_index_tests {
 
         glue!(index_basic, index::basic);
         glue!(index_and, index::and);
+        glue!(index_composite, index::composite);
+        glue!(index_unique, index::unique);
+        glue!(index_partial, index::partial);
         glue!(index_nested, index::nested);
         glue!(index_null, index::null);
         glue!(index_expr, index::expr);
@@ -324,6 +428,7 @@ macro_rules! generate_index_tests {
         glue!(index_order_by_multi, index::order_by_multi);
         glue!(showindexes, index::showindexes);
         glue!(dictionary_index, dictionary_index::ditionary_index);
+        glue!(explain_index, explain::explain_index);
     };
 }
 
@@ -342,6 +447,7 @@ macro_rules! generate_transaction_tests {
             transaction::create_drop_table
         );
         glue!(transaction_dictionary, transaction::dictionary);
+        glue!(transaction_sequence, transaction::sequence);
     };
 }
 
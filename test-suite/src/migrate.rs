@@ -56,11 +56,9 @@ test_case!(migrate, {
             TranslateError::UnsupportedBinaryOperator("^".to_owned()).into(),
         ),
         (
-            "SELECT * FROM Test UNION SELECT * FROM Test;",
-            TranslateError::UnsupportedQuerySetExpr(
-                "SELECT * FROM Test UNION SELECT * FROM Test".to_owned(),
-            )
-            .into(),
+            "(SELECT * FROM Test LIMIT 1) UNION SELECT * FROM Test;",
+            TranslateError::UnsupportedQuerySetExpr("(SELECT * FROM Test LIMIT 1)".to_owned())
+                .into(),
         ),
         (
             "SELECT * FROM Test WHERE noname = 1;",
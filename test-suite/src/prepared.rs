@@ -0,0 +1,209 @@
+use {
+    crate::*,
+    gluesql_core::{
+        data::Interval as I,
+        error::{EvaluateError, PrepareError, ValueError},
+        prelude::{DataType, Payload, Value::*},
+    },
+    rust_decimal::Decimal as D,
+    std::{collections::HashMap, str::FromStr},
+};
+
+test_case!(prepared, {
+    let glue = get_glue!();
+
+    glue.execute("CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT, price FLOAT)")
+        .await
+        .unwrap();
+
+    let insert = glue
+        .prepare("INSERT INTO Item VALUES ($1, $2, $3)")
+        .await
+        .unwrap();
+    assert_eq!(
+        insert.param_types(),
+        &[
+            Some(DataType::Int),
+            Some(DataType::Text),
+            Some(DataType::Float)
+        ]
+    );
+
+    for (id, name, price) in [(1, "Apple", 1.5), (2, "Banana", 0.5), (3, "Cherry", 3.0)] {
+        let actual = glue
+            .execute_prepared(&insert, vec![I64(id), Str(name.to_owned()), F64(price)])
+            .await;
+        assert_eq!(actual, Ok(Payload::Insert(1)), "insert {name}");
+    }
+
+    let name = "Durian'); DROP TABLE Item; --".to_owned();
+    let actual = glue
+        .execute_prepared(
+            &insert,
+            vec![Str("4".to_owned()), Str(name.clone()), I64(10)],
+        )
+        .await;
+    assert_eq!(actual, Ok(Payload::Insert(1)), "cast parameters");
+
+    let actual = glue
+        .execute_prepared(&insert, vec![Str("five".to_owned()), Null, Null])
+        .await;
+    assert_eq!(
+        actual,
+        Err(ValueError::ImpossibleCast.into()),
+        "parameter cannot be cast"
+    );
+
+    let select = glue
+        .prepare("SELECT id, name FROM Item WHERE price > ? AND id < ?")
+        .await
+        .unwrap();
+    let actual = glue.execute_prepared(&select, vec![F64(1.0), I64(4)]).await;
+    let expected = select!(
+        id  | name
+        I64 | Str;
+        1     "Apple".to_owned();
+        3     "Cherry".to_owned()
+    );
+    assert_eq!(actual, Ok(expected), "select");
+
+    let actual = glue.execute_prepared(&select, vec![F64(0.1), I64(3)]).await;
+    let expected = select!(
+        id  | name
+        I64 | Str;
+        1     "Apple".to_owned();
+        2     "Banana".to_owned()
+    );
+    assert_eq!(actual, Ok(expected), "select again with other parameters");
+
+    let actual = glue.execute_prepared(&select, vec![F64(1.0)]).await;
+    assert_eq!(
+        actual,
+        Err(PrepareError::ParameterCountMismatch(2, 1).into()),
+        "wrong number of parameters"
+    );
+
+    let select = glue
+        .prepare("SELECT name FROM Item WHERE id = $1")
+        .await
+        .unwrap();
+    let actual = glue.execute_prepared(&select, vec![I64(4)]).await;
+    assert_eq!(actual, Ok(select!(name; Str; name)), "primary key lookup");
+
+    let update = glue
+        .prepare("UPDATE Item SET price = $2 WHERE id = $1 OR name = $3")
+        .await
+        .unwrap();
+    let actual = glue
+        .execute_prepared(&update, vec![I64(2), F64(0.75), Str("Cherry".to_owned())])
+        .await;
+    assert_eq!(actual, Ok(Payload::Update(2)), "update");
+
+    let delete = glue
+        .prepare("DELETE FROM Item WHERE price = $1")
+        .await
+        .unwrap();
+    let actual = glue.execute_prepared(&delete, vec![F64(0.75)]).await;
+    assert_eq!(actual, Ok(Payload::Delete(2)), "delete");
+
+    let actual = glue.execute("SELECT COUNT(*) FROM Item").await;
+    assert_eq!(
+        actual,
+        Ok(vec![select!("COUNT(*)"; I64; 2)]),
+        "remaining rows"
+    );
+
+    let actual = glue.prepare("SELECT $1, ?").await;
+    assert_eq!(
+        actual.map(|_| ()),
+        Err(PrepareError::MixedPlaceholders.into()),
+        "mixed placeholders"
+    );
+
+    let actual = glue.prepare("SELECT 1; SELECT 2;").await;
+    assert_eq!(
+        actual.map(|_| ()),
+        Err(PrepareError::NotSingleStatement(2).into()),
+        "multiple statements"
+    );
+
+    let actual = glue.execute("SELECT name FROM Item WHERE price > $1").await;
+    assert_eq!(
+        actual,
+        Err(EvaluateError::UnboundPlaceholder("$1".to_owned()).into()),
+        "placeholder without parameters"
+    );
+
+    glue.execute("CREATE TABLE Lossless (amount DECIMAL, span INTERVAL, tags LIST, attrs MAP)")
+        .await
+        .unwrap();
+
+    let amount = D::from_str("12345678901234567890.123456789").unwrap();
+    let span = I::Microsecond(1_500_000);
+    let tags = List(vec![I64(1), Str("a".to_owned())]);
+    let attrs = Map(HashMap::from([("checked".to_owned(), Bool(true))]));
+
+    let insert = glue
+        .prepare("INSERT INTO Lossless VALUES ($1, $2, $3, $4)")
+        .await
+        .unwrap();
+    let actual = glue
+        .execute_prepared(
+            &insert,
+            vec![Decimal(amount), Interval(span), tags.clone(), attrs.clone()],
+        )
+        .await;
+    assert_eq!(
+        actual,
+        Ok(Payload::Insert(1)),
+        "insert values without literals"
+    );
+
+    let select = glue
+        .prepare("SELECT $1 AS amount, $2 AS span, $3 AS tags, $4 AS attrs")
+        .await
+        .unwrap();
+    let actual = glue
+        .execute_prepared(
+            &select,
+            vec![Decimal(amount), Interval(span), tags.clone(), attrs.clone()],
+        )
+        .await;
+    let expected = Payload::Select {
+        labels: vec![
+            "amount".to_owned(),
+            "span".to_owned(),
+            "tags".to_owned(),
+            "attrs".to_owned(),
+        ],
+        rows: vec![vec![
+            Decimal(amount),
+            Interval(span),
+            tags.clone(),
+            attrs.clone(),
+        ]],
+    };
+    assert_eq!(actual, Ok(expected), "parameters are bound without loss");
+
+    let select = glue
+        .prepare("SELECT * FROM Lossless WHERE amount = $1 AND span = $2")
+        .await
+        .unwrap();
+    let actual = glue
+        .execute_prepared(&select, vec![Decimal(amount), Interval(span)])
+        .await;
+    let expected = Payload::Select {
+        labels: vec![
+            "amount".to_owned(),
+            "span".to_owned(),
+            "tags".to_owned(),
+            "attrs".to_owned(),
+        ],
+        rows: vec![vec![Decimal(amount), Interval(span), tags, attrs]],
+    };
+    assert_eq!(
+        actual,
+        Ok(expected),
+        "bound values are stored and compared without loss"
+    );
+});
@@ -0,0 +1,314 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, EvaluateError, ExecuteError, ProcedureError, TranslateError},
+        prelude::{Payload, PayloadVariable, Value::*},
+    },
+};
+
+test_case!(function_block, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE FUNCTION fib(n INTEGER) RETURNS INTEGER BEGIN
+                DECLARE a INTEGER DEFAULT 0;
+                DECLARE b INTEGER DEFAULT 1;
+                DECLARE i INTEGER DEFAULT 0;
+                WHILE i < n DO
+                    SET b = a + b;
+                    SET a = b - a;
+                    SET i = i + 1;
+                END WHILE;
+                RETURN a;
+            END",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT fib(1) AS a, fib(10) AS b",
+            Ok(select!(a | b I64 | I64; 1 55)),
+        ),
+        (
+            "CREATE FUNCTION grade(score INTEGER) BEGIN
+                IF score >= 90 THEN
+                    RETURN 'A';
+                ELSEIF score >= 80 THEN
+                    RETURN 'B';
+                ELSE
+                    RETURN 'C';
+                END IF;
+            END",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT grade(95) AS a, grade(85) AS b, grade(10) AS c",
+            Ok(select!(
+                a               | b               | c
+                Str             | Str             | Str;
+                "A".to_owned()    "B".to_owned()    "C".to_owned()
+            )),
+        ),
+        (
+            "CREATE FUNCTION day_name(n INTEGER) BEGIN
+                CASE n
+                    WHEN 0 THEN RETURN 'Sun';
+                    WHEN 6 THEN RETURN 'Sat';
+                    ELSE RETURN 'Weekday';
+                END CASE;
+            END",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT day_name(0) AS a, day_name(3) AS b",
+            Ok(select!(
+                a                 | b
+                Str               | Str;
+                "Sun".to_owned()    "Weekday".to_owned()
+            )),
+        ),
+        (
+            "CREATE FUNCTION sum_odd(n INTEGER) BEGIN
+                DECLARE total INTEGER DEFAULT 0;
+                DECLARE i INTEGER DEFAULT 0;
+                LOOP
+                    SET i = i + 1;
+                    EXIT WHEN i > n;
+                    CONTINUE WHEN i % 2 = 0;
+                    SET total = total + i;
+                END LOOP;
+                RETURN total;
+            END",
+            Ok(Payload::Create),
+        ),
+        ("SELECT sum_odd(5) AS r", Ok(select!(r I64; 9))),
+        (
+            "CREATE FUNCTION nothing() BEGIN DECLARE a INTEGER; END",
+            Ok(Payload::Create),
+        ),
+        ("SELECT nothing() AS r", Ok(select_with_null!(r; Null))),
+        (
+            "CREATE FUNCTION undeclared() BEGIN SET a = 1; END",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT undeclared() AS r",
+            Err(ProcedureError::VariableNotDeclared("a".to_owned()).into()),
+        ),
+        (
+            "CREATE FUNCTION leave() BEGIN EXIT; END",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT leave() AS r",
+            Err(ProcedureError::OutsideLoop("EXIT".to_owned()).into()),
+        ),
+        (
+            "CREATE FUNCTION clear() BEGIN DELETE FROM Foo; END",
+            Err(
+                TranslateError::UnsupportedStatementInFunction(r#"DELETE FROM "Foo";"#.to_owned())
+                    .into(),
+            ),
+        ),
+        (
+            "SHOW FUNCTIONS",
+            Ok(Payload::ShowVariable(PayloadVariable::Functions(vec![
+                "day_name(n: INT)".to_owned(),
+                "fib(n: INT)".to_owned(),
+                "grade(score: INT)".to_owned(),
+                "leave()".to_owned(),
+                "nothing()".to_owned(),
+                "sum_odd(n: INT)".to_owned(),
+                "undeclared()".to_owned(),
+            ]))),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
+
+test_case!(procedure, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Account (id INTEGER, balance INTEGER)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Account VALUES (1, 100), (2, 50)",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "CREATE TABLE Transfer (src INTEGER, dst INTEGER, amount INTEGER)",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE PROCEDURE transfer(src_id INTEGER, dst_id INTEGER, amount INTEGER DEFAULT 10)
+            BEGIN
+                UPDATE Account SET balance = balance - amount WHERE id = src_id;
+                UPDATE Account SET balance = balance + amount WHERE id = dst_id;
+                INSERT INTO Transfer VALUES (src_id, dst_id, amount);
+            END",
+            Ok(Payload::Create),
+        ),
+        ("CALL transfer(1, 2, 30)", Ok(Payload::Call)),
+        ("CALL transfer(2, 1)", Ok(Payload::Call)),
+        (
+            "SELECT id, balance FROM Account",
+            Ok(select!(
+                id  | balance
+                I64 | I64;
+                1     80;
+                2     70
+            )),
+        ),
+        (
+            "SELECT * FROM Transfer",
+            Ok(select!(
+                src | dst | amount
+                I64 | I64 | I64;
+                1     2     30;
+                2     1     10
+            )),
+        ),
+        (
+            "CALL transfer(1)",
+            Err(EvaluateError::FunctionArgsLengthNotWithinRange {
+                name: "transfer".to_owned(),
+                expected_minimum: 2,
+                expected_maximum: 3,
+                found: 1,
+            }
+            .into()),
+        ),
+        ("CREATE TABLE Num (num INTEGER)", Ok(Payload::Create)),
+        (
+            "CREATE PROCEDURE fill(n INTEGER) BEGIN
+                DECLARE i INTEGER DEFAULT 1;
+                WHILE i <= n DO
+                    IF i % 2 = 1 THEN
+                        INSERT INTO Num VALUES (i);
+                    END IF;
+                    SET i = i + 1;
+                END WHILE;
+            END",
+            Ok(Payload::Create),
+        ),
+        ("CALL fill(5)", Ok(Payload::Call)),
+        ("SELECT num FROM Num", Ok(select!(num I64; 1; 3; 5))),
+        (
+            "CREATE PROCEDURE fill(n INTEGER) BEGIN DELETE FROM Num; END",
+            Err(AlterError::ProcedureAlreadyExists("fill".to_owned()).into()),
+        ),
+        (
+            "CREATE OR REPLACE PROCEDURE fill() BEGIN CALL transfer(1, 2); RETURN; END",
+            Ok(Payload::Create),
+        ),
+        ("CALL fill()", Ok(Payload::Call)),
+        (
+            "SELECT id, balance FROM Account",
+            Ok(select!(
+                id  | balance
+                I64 | I64;
+                1     70;
+                2     80
+            )),
+        ),
+        (
+            "CREATE PROCEDURE deposit(id INTEGER, amount INTEGER) BEGIN
+                UPDATE Account SET balance = balance + amount WHERE id = deposit.id;
+            END",
+            Ok(Payload::Create),
+        ),
+        ("CALL deposit(2, 5)", Ok(Payload::Call)),
+        (
+            "SELECT id, balance FROM Account",
+            Ok(select!(
+                id  | balance
+                I64 | I64;
+                1     70;
+                2     85
+            )),
+        ),
+        (
+            "CREATE FUNCTION fill() RETURN 1",
+            Err(AlterError::FunctionAlreadyExists("fill".to_owned()).into()),
+        ),
+        (
+            "CREATE PROCEDURE answer() BEGIN RETURN 42; END",
+            Err(TranslateError::ReturnValueInProcedure.into()),
+        ),
+        (
+            "SELECT fill() AS r",
+            Err(EvaluateError::UnsupportedFunction("FILL".to_owned()).into()),
+        ),
+        ("CREATE FUNCTION one() RETURN 1", Ok(Payload::Create)),
+        (
+            "CALL one()",
+            Err(ProcedureError::ProcedureNotFound("one".to_owned()).into()),
+        ),
+        (
+            "SHOW FUNCTIONS",
+            Ok(Payload::ShowVariable(PayloadVariable::Functions(vec![
+                "one()".to_owned(),
+            ]))),
+        ),
+        (
+            "DROP PROCEDURE one",
+            Err(AlterError::ProcedureNotFound("one".to_owned()).into()),
+        ),
+        (
+            "DROP FUNCTION fill",
+            Err(AlterError::FunctionNotFound("fill".to_owned()).into()),
+        ),
+        (
+            "DROP PROCEDURE IF EXISTS one, fill, transfer, deposit",
+            Ok(Payload::DropProcedure),
+        ),
+        (
+            "CALL fill()",
+            Err(ProcedureError::ProcedureNotFound("fill".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
+
+test_case!(nesting, {
+    let g = get_tester!();
+
+    g.get_glue().limits.max_nesting_depth = 5;
+
+    let test_cases = [
+        (
+            "CREATE FUNCTION fact(n INTEGER) RETURNS INTEGER BEGIN
+                IF n <= 1 THEN
+                    RETURN 1;
+                END IF;
+                RETURN n * fact(n - 1);
+            END",
+            Ok(Payload::Create),
+        ),
+        ("SELECT fact(5) AS f", Ok(select!(f I64; 120))),
+        (
+            "SELECT fact(6) AS f",
+            Err(ExecuteError::NestingDepthExceeded("function fact".to_owned()).into()),
+        ),
+        (
+            "CREATE PROCEDURE forever() BEGIN CALL forever(); END",
+            Ok(Payload::Create),
+        ),
+        (
+            "CALL forever()",
+            Err(ExecuteError::NestingDepthExceeded("procedure forever".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
@@ -0,0 +1,90 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::DataType,
+        error::FetchError,
+        prelude::{
+            Payload,
+            Value::{self, *},
+        },
+    },
+    serde_json::json,
+};
+
+test_case!(returning, {
+    let g = get_tester!();
+
+    g.run(
+        "
+CREATE TABLE Item (
+    id UUID DEFAULT GENERATE_UUID(),
+    name TEXT,
+    price INTEGER
+);",
+    )
+    .await;
+
+    g.type_match(
+        "INSERT INTO Item (name, price) VALUES ('Apple', 100) RETURNING id, name;",
+        &[DataType::Uuid, DataType::Text],
+    )
+    .await;
+
+    g.named_test(
+        "insert returning expressions",
+        "INSERT INTO Item (name, price) VALUES ('Banana', 200), ('Cherry', 300) RETURNING name, price * 2 AS double;",
+        Ok(select!(
+            name                      | double
+            Str                       | I64;
+            "Banana".to_owned()         400;
+            "Cherry".to_owned()         600
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "update returning post-update rows",
+        "UPDATE Item SET price = price + 50 WHERE price >= 200 RETURNING name, price;",
+        Ok(select!(
+            name                      | price
+            Str                       | I64;
+            "Banana".to_owned()         250;
+            "Cherry".to_owned()         350
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "delete returning deleted rows",
+        "DELETE FROM Item WHERE name = 'Apple' RETURNING Item.price;",
+        Ok(select!(price I64; 100)),
+    )
+    .await;
+
+    g.named_test(
+        "returning without affected rows",
+        "DELETE FROM Item WHERE price > 1000 RETURNING name;",
+        Ok(Payload::Select {
+            labels: vec!["name".to_owned()],
+            rows: vec![],
+        }),
+    )
+    .await;
+
+    g.named_test(
+        "returning qualified wildcard of other table",
+        "UPDATE Item SET price = 0 RETURNING Foo.*;",
+        Err(FetchError::TableAliasNotFound("Foo".to_owned()).into()),
+    )
+    .await;
+
+    g.count("SELECT * FROM Item WHERE price = 0", 0).await;
+
+    g.run("CREATE TABLE Logs;").await;
+    g.named_test(
+        "schemaless returning",
+        r#"INSERT INTO Logs VALUES ('{"id": 1, "message": "Hello"}') RETURNING *;"#,
+        Ok(select_map![json!({"id": 1, "message": "Hello"})]),
+    )
+    .await;
+});
@@ -82,6 +82,11 @@ test_case!(error, {
         Err(InsertError::MapTypeValueRequired("100".to_owned()).into()),
     )
     .await;
+    g.test(
+        r#"INSERT INTO Item VALUES ('{ "id": 100 }') ON CONFLICT DO NOTHING;"#,
+        Err(InsertError::OnConflictOnSchemalessTableNotSupported("Item".to_owned()).into()),
+    )
+    .await;
     g.test(
         "SELECT id FROM Item WHERE id IN (SELECT * FROM Item)",
         Err(EvaluateError::SchemalessProjectionForInSubQuery.into()),
@@ -0,0 +1,248 @@
+use {
+    crate::*,
+    gluesql_core::{
+        data::SequenceError,
+        error::{EvaluateError, TranslateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(sequence, {
+    let g = get_tester!();
+
+    let test_cases = [
+        ("CREATE SEQUENCE Seq", Ok(Payload::Create)),
+        (
+            "CREATE SEQUENCE Seq",
+            Err(SequenceError::AlreadyExists("Seq".to_owned()).into()),
+        ),
+        ("CREATE SEQUENCE IF NOT EXISTS Seq", Ok(Payload::Create)),
+        (
+            "SELECT CURRVAL('Seq') AS v",
+            Err(SequenceError::CurrentValueNotDefined("Seq".to_owned()).into()),
+        ),
+        ("SELECT NEXTVAL('Seq') AS v", Ok(select!(v I64; 1))),
+        ("SELECT NEXTVAL('Seq') AS v", Ok(select!(v I64; 2))),
+        ("SELECT CURRVAL('Seq') AS v", Ok(select!(v I64; 2))),
+        ("SELECT NEXTVAL(NULL) AS v", Ok(select_with_null!(v; Null))),
+        (
+            "SELECT NEXTVAL(1) AS v",
+            Err(EvaluateError::FunctionRequiresStringValue("NEXTVAL".to_owned()).into()),
+        ),
+        (
+            "SELECT NEXTVAL('Nothing') AS v",
+            Err(SequenceError::NotFound("Nothing".to_owned()).into()),
+        ),
+        (
+            "CREATE SEQUENCE Cyc INCREMENT BY 2 MINVALUE 1 MAXVALUE 5 CYCLE",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT NEXTVAL('Cyc') AS a, NEXTVAL('Cyc') AS b, NEXTVAL('Cyc') AS c, NEXTVAL('Cyc') AS d",
+            Ok(select!(
+                a   | b   | c   | d
+                I64 | I64 | I64 | I64;
+                1     3     5     1
+            )),
+        ),
+        (
+            "CREATE SEQUENCE Lim MAXVALUE 4 START WITH 3",
+            Ok(Payload::Create),
+        ),
+        ("SELECT NEXTVAL('Lim') AS v", Ok(select!(v I64; 3))),
+        ("SELECT NEXTVAL('Lim') AS v", Ok(select!(v I64; 4))),
+        (
+            "SELECT NEXTVAL('Lim') AS v",
+            Err(SequenceError::MaxValueReached("Lim".to_owned()).into()),
+        ),
+        (
+            "CREATE SEQUENCE Zero INCREMENT BY 0",
+            Err(SequenceError::ZeroIncrement("Zero".to_owned()).into()),
+        ),
+        (
+            "CREATE SEQUENCE Range MINVALUE 5 MAXVALUE 1",
+            Err(SequenceError::InvalidRange {
+                name: "Range".to_owned(),
+                min_value: 5,
+                max_value: 1,
+            }
+            .into()),
+        ),
+        (
+            "CREATE SEQUENCE Start MAXVALUE 5 START WITH 10",
+            Err(SequenceError::StartOutOfRange {
+                name: "Start".to_owned(),
+                start: 10,
+            }
+            .into()),
+        ),
+        (
+            "CREATE SEQUENCE Cached CACHE 10",
+            Err(TranslateError::UnsupportedSequenceOption("CACHE 10".to_owned()).into()),
+        ),
+        (
+            "DROP SEQUENCE Nothing",
+            Err(SequenceError::NotFound("Nothing".to_owned()).into()),
+        ),
+        ("DROP SEQUENCE IF EXISTS Nothing", Ok(Payload::DropSequence)),
+        ("DROP SEQUENCE Cyc, Lim", Ok(Payload::DropSequence)),
+        (
+            "SELECT NEXTVAL('Cyc') AS v",
+            Err(SequenceError::NotFound("Cyc".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
+
+test_case!(identity, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Item (id SERIAL, name TEXT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item (name) VALUES ('a'), ('b')",
+            Ok(Payload::Insert(2)),
+        ),
+        ("INSERT INTO Item VALUES (10, 'c')", Ok(Payload::Insert(1))),
+        (
+            "INSERT INTO Item (name) VALUES ('d')",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "SELECT id, name FROM Item",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                1     "a".to_owned();
+                2     "b".to_owned();
+                10    "c".to_owned();
+                3     "d".to_owned()
+            )),
+        ),
+        ("SELECT CURRVAL('Item_id_seq') AS v", Ok(select!(v I64; 3))),
+        (
+            "DROP SEQUENCE Item_id_seq",
+            Err(SequenceError::OwnedByTable {
+                name: "Item_id_seq".to_owned(),
+                table_name: "Item".to_owned(),
+            }
+            .into()),
+        ),
+        (
+            "CREATE TABLE Other (id INT GENERATED BY DEFAULT AS IDENTITY, num INT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Other (num) VALUES (5), (7)",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "SELECT id, num FROM Other",
+            Ok(select!(
+                id  | num
+                I64 | I64;
+                1     5;
+                2     7
+            )),
+        ),
+        (
+            "CREATE TABLE Defaulted (id SERIAL DEFAULT 1)",
+            Err(TranslateError::DefaultOnIdentityColumn("id".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Always (id INT GENERATED ALWAYS AS IDENTITY)",
+            Err(TranslateError::GeneratedAlwaysIdentityNotSupported("id".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Options (id INT GENERATED BY DEFAULT AS IDENTITY (START WITH 10))",
+            Err(TranslateError::IdentitySequenceOptionsNotSupported("id".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Stored (num INT, twice INT GENERATED ALWAYS AS (num * 2) STORED)",
+            Err(TranslateError::GeneratedColumnNotSupported("twice".to_owned()).into()),
+        ),
+        ("CREATE SEQUENCE Shared START WITH 100", Ok(Payload::Create)),
+        (
+            "CREATE TABLE SharedA (id INT DEFAULT NEXTVAL('Shared'), num INT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TABLE SharedB (id INT DEFAULT NEXTVAL('Shared'), num INT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO SharedA (num) VALUES (1)",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "INSERT INTO SharedB VALUES (NEXTVAL('Shared'), 2)",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "SELECT id, num FROM SharedB",
+            Ok(select!(id | num I64 | I64; 101 2)),
+        ),
+        (
+            "CREATE TABLE Missing (id INT DEFAULT NEXTVAL('Missing_id_sequence'))",
+            Err(SequenceError::NotFound("Missing_id_sequence".to_owned()).into()),
+        ),
+        ("ALTER TABLE Item RENAME TO Thing", Ok(Payload::AlterTable)),
+        (
+            "DROP SEQUENCE Item_id_seq",
+            Err(SequenceError::OwnedByTable {
+                name: "Item_id_seq".to_owned(),
+                table_name: "Thing".to_owned(),
+            }
+            .into()),
+        ),
+        (
+            "INSERT INTO Thing (name) VALUES ('e')",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "SELECT id FROM Thing WHERE name = 'e'",
+            Ok(select!(id I64; 4)),
+        ),
+        ("ALTER TABLE Other DROP COLUMN id", Ok(Payload::AlterTable)),
+        (
+            "SELECT NEXTVAL('Other_id_seq') AS v",
+            Err(SequenceError::NotFound("Other_id_seq".to_owned()).into()),
+        ),
+        ("DROP TABLE Thing", Ok(Payload::DropTable)),
+        (
+            "SELECT NEXTVAL('Item_id_seq') AS v",
+            Err(SequenceError::NotFound("Item_id_seq".to_owned()).into()),
+        ),
+        (
+            "DROP SEQUENCE Shared",
+            Err(SequenceError::UsedByColumnDefault {
+                name: "Shared".to_owned(),
+                table_name: "SharedA".to_owned(),
+                column_name: "id".to_owned(),
+            }
+            .into()),
+        ),
+        ("DROP TABLE SharedA", Ok(Payload::DropTable)),
+        (
+            "DROP SEQUENCE Shared",
+            Err(SequenceError::UsedByColumnDefault {
+                name: "Shared".to_owned(),
+                table_name: "SharedB".to_owned(),
+                column_name: "id".to_owned(),
+            }
+            .into()),
+        ),
+        ("DROP TABLE SharedB", Ok(Payload::DropTable)),
+        ("DROP SEQUENCE Shared", Ok(Payload::DropSequence)),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
@@ -0,0 +1,166 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::DataType,
+        error::PlanError,
+        prelude::{Payload, Value::*},
+    },
+    rust_decimal::Decimal as D,
+};
+
+test_case!(set_operation, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Foo (id INTEGER, name TEXT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TABLE Bar (id INTEGER, name TEXT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Foo VALUES (1, 'a'), (2, 'b'), (2, 'b'), (3, 'c')",
+            Ok(Payload::Insert(4)),
+        ),
+        (
+            "INSERT INTO Bar VALUES (2, 'b'), (3, 'c'), (3, 'c'), (4, 'd')",
+            Ok(Payload::Insert(4)),
+        ),
+        (
+            "SELECT id FROM Foo UNION SELECT id FROM Bar",
+            Ok(select!(id; I64; 1; 2; 3; 4)),
+        ),
+        (
+            "SELECT id FROM Foo UNION ALL SELECT id FROM Bar",
+            Ok(select!(id; I64; 1; 2; 2; 3; 2; 3; 3; 4)),
+        ),
+        (
+            "SELECT id FROM Foo INTERSECT SELECT id FROM Bar",
+            Ok(select!(id; I64; 2; 3)),
+        ),
+        (
+            "SELECT id FROM Foo INTERSECT ALL SELECT id FROM Bar",
+            Ok(select!(id; I64; 2; 3)),
+        ),
+        (
+            "SELECT * FROM Bar INTERSECT ALL SELECT * FROM Bar WHERE id = 3",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                3     "c".to_owned();
+                3     "c".to_owned()
+            )),
+        ),
+        (
+            "SELECT id FROM Foo EXCEPT SELECT id FROM Bar",
+            Ok(select!(id; I64; 1)),
+        ),
+        (
+            "SELECT id FROM Foo EXCEPT ALL SELECT id FROM Bar WHERE id = 2",
+            Ok(select!(id; I64; 1; 2; 3)),
+        ),
+        (
+            "SELECT id, name FROM Foo UNION SELECT id AS bar_id, name FROM Bar ORDER BY id DESC",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                4     "d".to_owned();
+                3     "c".to_owned();
+                2     "b".to_owned();
+                1     "a".to_owned()
+            )),
+        ),
+        (
+            "SELECT id FROM Foo UNION ALL SELECT id FROM Bar ORDER BY id LIMIT 3 OFFSET 2",
+            Ok(select!(id; I64; 2; 2; 3)),
+        ),
+        (
+            "SELECT id FROM Foo UNION SELECT id FROM Bar EXCEPT SELECT id FROM Foo WHERE id > 1",
+            Ok(select!(id; I64; 1; 4)),
+        ),
+        (
+            "SELECT id FROM Foo UNION (SELECT id FROM Bar EXCEPT SELECT id FROM Foo WHERE id > 1)",
+            Ok(select!(id; I64; 1; 2; 3; 4)),
+        ),
+        (
+            "SELECT id FROM Foo INTERSECT VALUES (1), (3), (5)",
+            Ok(select!(id; I64; 1; 3)),
+        ),
+        (
+            "SELECT * FROM (SELECT id FROM Foo UNION SELECT id FROM Bar) AS Sub WHERE id > 2",
+            Ok(select!(id; I64; 3; 4)),
+        ),
+        (
+            "SELECT name FROM Foo WHERE id IN (SELECT id FROM Bar EXCEPT SELECT 3)",
+            Ok(select!(name; Str; "b".to_owned(); "b".to_owned())),
+        ),
+        ("CREATE TABLE Baz (id INTEGER)", Ok(Payload::Create)),
+        (
+            "INSERT INTO Baz SELECT id FROM Foo INTERSECT SELECT id FROM Bar",
+            Ok(Payload::Insert(2)),
+        ),
+        ("SELECT id FROM Baz", Ok(select!(id; I64; 2; 3))),
+        (
+            "SELECT id FROM Foo UNION SELECT id, name FROM Bar",
+            Err(PlanError::SetOperationColumnCountMismatch("UNION".to_owned(), 1, 2).into()),
+        ),
+        (
+            "SELECT * FROM Foo EXCEPT SELECT name, id FROM Bar",
+            Err(PlanError::SetOperationColumnTypeMismatch {
+                operator: "EXCEPT".to_owned(),
+                column: "id".to_owned(),
+                left: DataType::Int,
+                right: DataType::Text,
+            }
+            .into()),
+        ),
+        (
+            "CREATE TABLE Small (id INT8, ratio FLOAT32, amount DECIMAL)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Small VALUES (1, 1.0, 1.5), (5, 2.5, 5)",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "SELECT id FROM Small UNION SELECT id FROM Foo",
+            Ok(select!(id; I64; 1; 5; 2; 3)),
+        ),
+        (
+            "SELECT id FROM Foo INTERSECT SELECT id FROM Small",
+            Ok(select!(id; I64; 1)),
+        ),
+        (
+            "SELECT id FROM Foo EXCEPT SELECT id FROM Small",
+            Ok(select!(id; I64; 2; 3)),
+        ),
+        (
+            "SELECT id FROM Foo INTERSECT SELECT ratio FROM Small",
+            Ok(select!(id; F64; 1.0)),
+        ),
+        (
+            "SELECT id FROM Small INTERSECT SELECT amount FROM Small",
+            Ok(select!(id; Decimal; D::from(5))),
+        ),
+        (
+            "SELECT ratio FROM Small UNION SELECT amount FROM Small",
+            Err(PlanError::SetOperationColumnTypeMismatch {
+                operator: "UNION".to_owned(),
+                column: "ratio".to_owned(),
+                left: DataType::Float32,
+                right: DataType::Decimal,
+            }
+            .into()),
+        ),
+        (
+            "VALUES (1) UNION VALUES (1, 2)",
+            Err(PlanError::SetOperationColumnCountMismatch("UNION".to_owned(), 1, 2).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
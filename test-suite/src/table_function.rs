@@ -0,0 +1,124 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{EvaluateError, FetchError, ValueError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(table_function, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT, price INTEGER)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES (1, 'apple', 300), (2, 'banana', 150), (3, 'cherry', 900)",
+            Ok(Payload::Insert(3)),
+        ),
+        (
+            "CREATE FUNCTION cheap_items(max_price INTEGER, label TEXT DEFAULT 'cheap')
+            RETURNS TABLE (id INTEGER, name TEXT, tag TEXT)
+            RETURN SELECT id, name, label FROM Item WHERE price <= max_price ORDER BY id",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT * FROM cheap_items(300, 'sale')",
+            Ok(select!(
+                id  | name                  | tag
+                I64 | Str                   | Str;
+                1     "apple".to_owned()      "sale".to_owned();
+                2     "banana".to_owned()     "sale".to_owned()
+            )),
+        ),
+        (
+            "SELECT c.name, c.tag FROM cheap_items(200) AS c",
+            Ok(select!(
+                name                | tag
+                Str                 | Str;
+                "banana".to_owned()   "cheap".to_owned()
+            )),
+        ),
+        (
+            "SELECT c.n FROM cheap_items(1000) AS c(i, n) WHERE c.i > 1",
+            Ok(select!(
+                n
+                Str;
+                "banana".to_owned();
+                "cherry".to_owned()
+            )),
+        ),
+        (
+            "SELECT Item.id, c.name
+            FROM Item
+            JOIN cheap_items(Item.price) AS c ON c.id < Item.id
+            ORDER BY Item.id, c.name",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                3     "apple".to_owned();
+                3     "banana".to_owned()
+            )),
+        ),
+        (
+            "CREATE FUNCTION numbers(n INTEGER) RETURNS TABLE (num FLOAT)
+            RETURN SELECT N FROM SERIES(n)",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT SUM(num) AS total FROM numbers(4)",
+            Ok(select!(total F64; 10.0)),
+        ),
+        (
+            "CREATE FUNCTION pair() RETURNS TABLE (a INTEGER NOT NULL, b INTEGER)
+            RETURN VALUES (1, 2), (NULL, 3)",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT * FROM pair()",
+            Err(ValueError::NullValueOnNotNullField.into()),
+        ),
+        (
+            "CREATE OR REPLACE FUNCTION pair() RETURNS TABLE (a INTEGER)
+            RETURN VALUES (1, 2)",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT * FROM pair()",
+            Err(FetchError::TableFunctionColumnsMismatch("pair".to_owned(), 1, 2).into()),
+        ),
+        (
+            "SELECT * FROM cheap_items()",
+            Err(EvaluateError::FunctionArgsLengthNotWithinRange {
+                name: "cheap_items".to_owned(),
+                expected_minimum: 1,
+                expected_maximum: 2,
+                found: 0,
+            }
+            .into()),
+        ),
+        (
+            "SELECT cheap_items(100) AS c",
+            Err(EvaluateError::TableFunctionInExpression("cheap_items".to_owned()).into()),
+        ),
+        ("CREATE FUNCTION one() RETURN 1", Ok(Payload::Create)),
+        (
+            "SELECT * FROM one()",
+            Err(FetchError::TableFunctionNotFound("one".to_owned()).into()),
+        ),
+        (
+            "SELECT * FROM missing(1)",
+            Err(FetchError::TableFunctionNotFound("missing".to_owned()).into()),
+        ),
+        (
+            "DROP FUNCTION cheap_items, numbers, pair, one",
+            Ok(Payload::DropFunction),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
@@ -14,6 +14,26 @@ macro_rules! idx {
         vec![gluesql_core::ast::IndexItem::NonClustered {
             name: stringify_label!($name).to_owned(),
             asc: None,
+            prefix: Vec::new(),
+            cmp_expr: Some((
+                $op,
+                gluesql_core::translate::translate_expr(
+                    &gluesql_core::parse_sql::parse_expr($sql_expr).unwrap(),
+                )
+                .unwrap(),
+            )),
+        }]
+    };
+    ($name: path, [ $( $prefix: literal ),* ], $op: path, $sql_expr: literal) => {
+        vec![gluesql_core::ast::IndexItem::NonClustered {
+            name: stringify_label!($name).to_owned(),
+            asc: None,
+            prefix: vec![$(
+                gluesql_core::translate::translate_expr(
+                    &gluesql_core::parse_sql::parse_expr($prefix).unwrap(),
+                )
+                .unwrap()
+            ),*],
             cmp_expr: Some((
                 $op,
                 gluesql_core::translate::translate_expr(
@@ -27,6 +47,7 @@ macro_rules! idx {
         vec![gluesql_core::ast::IndexItem::NonClustered {
             name: stringify_label!($name).to_owned(),
             asc: None,
+            prefix: Vec::new(),
             cmp_expr: None,
         }]
     };
@@ -34,6 +55,7 @@ macro_rules! idx {
         vec![gluesql_core::ast::IndexItem::NonClustered {
             name: stringify_label!($name).to_owned(),
             asc: Some(true),
+            prefix: Vec::new(),
             cmp_expr: None,
         }]
     };
@@ -41,6 +63,7 @@ macro_rules! idx {
         vec![gluesql_core::ast::IndexItem::NonClustered {
             name: stringify_label!($name).to_owned(),
             asc: Some(false),
+            prefix: Vec::new(),
             cmp_expr: None,
         }]
     };
@@ -2,10 +2,12 @@ mod alter_table;
 mod basic;
 mod dictionary;
 mod index;
+mod sequence;
 mod table;
 
 pub use alter_table::*;
 pub use basic::basic;
 pub use dictionary::dictionary;
 pub use index::*;
+pub use sequence::sequence;
 pub use table::*;
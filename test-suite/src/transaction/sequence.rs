@@ -0,0 +1,63 @@
+use {
+    crate::*,
+    gluesql_core::{data::SequenceError, prelude::Value::*},
+};
+
+test_case!(sequence, {
+    let g = get_tester!();
+
+    // CREATE && ROLLBACK
+    g.run("BEGIN;").await;
+    g.run("CREATE SEQUENCE Seq;").await;
+    g.test("SELECT NEXTVAL('Seq') AS v;", Ok(select!(v I64; 1)))
+        .await;
+    g.run("ROLLBACK;").await;
+    g.test(
+        "SELECT NEXTVAL('Seq') AS v;",
+        Err(SequenceError::NotFound("Seq".to_owned()).into()),
+    )
+    .await;
+
+    // CREATE && COMMIT
+    g.run("BEGIN;").await;
+    g.run("CREATE SEQUENCE Seq;").await;
+    g.test("SELECT NEXTVAL('Seq') AS v;", Ok(select!(v I64; 1)))
+        .await;
+    g.run("COMMIT;").await;
+    g.test("SELECT CURRVAL('Seq') AS v;", Ok(select!(v I64; 1)))
+        .await;
+
+    // NEXTVAL && ROLLBACK
+    g.run("BEGIN;").await;
+    g.test("SELECT NEXTVAL('Seq') AS v;", Ok(select!(v I64; 2)))
+        .await;
+    g.test("SELECT NEXTVAL('Seq') AS v;", Ok(select!(v I64; 3)))
+        .await;
+    g.run("ROLLBACK;").await;
+    g.test("SELECT NEXTVAL('Seq') AS v;", Ok(select!(v I64; 2)))
+        .await;
+
+    // identity column && ROLLBACK
+    g.run("CREATE TABLE Item (id SERIAL, name TEXT);").await;
+    g.run("BEGIN;").await;
+    g.run("INSERT INTO Item (name) VALUES ('a'), ('b');").await;
+    g.run("ROLLBACK;").await;
+    g.run("INSERT INTO Item (name) VALUES ('c');").await;
+    g.test(
+        "SELECT id, name FROM Item;",
+        Ok(select!(id | name I64 | Str; 1 "c".to_owned())),
+    )
+    .await;
+
+    // DROP TABLE && ROLLBACK
+    g.run("BEGIN;").await;
+    g.run("DROP TABLE Item;").await;
+    g.test(
+        "SELECT CURRVAL('Item_id_seq') AS v;",
+        Err(SequenceError::NotFound("Item_id_seq".to_owned()).into()),
+    )
+    .await;
+    g.run("ROLLBACK;").await;
+    g.test("SELECT CURRVAL('Item_id_seq') AS v;", Ok(select!(v I64; 1)))
+        .await;
+});
@@ -0,0 +1,372 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, ExecuteError, TranslateError, TriggerError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(trigger, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TABLE Audit (action TEXT, item_id INTEGER, name TEXT)",
+            Ok(Payload::Create),
+        ),
+        ("CREATE TABLE Counter (num INTEGER)", Ok(Payload::Create)),
+        ("INSERT INTO Counter VALUES (0)", Ok(Payload::Insert(1))),
+        (
+            "CREATE TRIGGER audit_insert AFTER INSERT ON Item FOR EACH ROW
+            INSERT INTO Audit VALUES ('insert', NEW.id, NEW.name)",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TRIGGER audit_insert AFTER INSERT ON Item FOR EACH ROW
+            INSERT INTO Audit VALUES ('insert', NEW.id, NEW.name)",
+            Err(AlterError::TriggerAlreadyExists("audit_insert".to_owned()).into()),
+        ),
+        (
+            "CREATE TRIGGER count_insert AFTER INSERT ON Item FOR EACH ROW
+            UPDATE Counter SET num = num + 1",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TRIGGER count_delete AFTER DELETE ON Item FOR EACH ROW
+            UPDATE Counter SET num = num - 1",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TRIGGER audit_change AFTER UPDATE OR DELETE ON Item FOR EACH ROW
+            INSERT INTO Audit VALUES ('change', OLD.id, OLD.name)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES (1, 'apple'), (2, 'banana')",
+            Ok(Payload::Insert(2)),
+        ),
+        ("SELECT num FROM Counter", Ok(select!(num I64; 2))),
+        (
+            "UPDATE Item SET name = 'cherry' WHERE id = 2",
+            Ok(Payload::Update(1)),
+        ),
+        ("DELETE FROM Item WHERE id = 1", Ok(Payload::Delete(1))),
+        ("SELECT num FROM Counter", Ok(select!(num I64; 1))),
+        (
+            "SELECT * FROM Audit",
+            Ok(select!(
+                action              | item_id | name
+                Str                 | I64     | Str;
+                "insert".to_owned()   1         "apple".to_owned();
+                "insert".to_owned()   2         "banana".to_owned();
+                "change".to_owned()   2         "banana".to_owned();
+                "change".to_owned()   1         "apple".to_owned()
+            )),
+        ),
+        (
+            "CREATE OR REPLACE TRIGGER audit_change AFTER UPDATE ON Item FOR EACH ROW
+            INSERT INTO Audit VALUES ('update', NEW.id, NEW.name)",
+            Ok(Payload::Create),
+        ),
+        ("UPDATE Item SET name = 'durian'", Ok(Payload::Update(1))),
+        (
+            "SELECT * FROM Audit WHERE action = 'update'",
+            Ok(select!(
+                action              | item_id | name
+                Str                 | I64     | Str;
+                "update".to_owned()   2         "durian".to_owned()
+            )),
+        ),
+        (
+            "CREATE TRIGGER audit_insert_old AFTER INSERT ON Item FOR EACH ROW
+            INSERT INTO Audit VALUES ('insert', OLD.id, OLD.name)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES (3, 'egg')",
+            Err(TriggerError::RowNotAvailable("OLD".to_owned()).into()),
+        ),
+        ("DROP TRIGGER audit_insert_old", Ok(Payload::DropTrigger)),
+        (
+            "CREATE TRIGGER audit_wrong AFTER INSERT ON Item FOR EACH ROW
+            INSERT INTO Audit VALUES ('insert', NEW.wrong, NEW.name)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES (4, 'fig')",
+            Err(TriggerError::ColumnNotFound("NEW.wrong".to_owned()).into()),
+        ),
+        ("DROP TRIGGER audit_wrong", Ok(Payload::DropTrigger)),
+        (
+            "CREATE TRIGGER audit_select AFTER INSERT ON Item FOR EACH ROW SELECT * FROM Audit",
+            Err(TranslateError::UnsupportedTriggerAction("SELECT * FROM Audit".to_owned()).into()),
+        ),
+        (
+            "CREATE TRIGGER audit_missing AFTER INSERT ON Missing FOR EACH ROW
+            DELETE FROM Audit",
+            Err(AlterError::TableNotFound("Missing".to_owned()).into()),
+        ),
+        (
+            "DROP TRIGGER audit_missing",
+            Err(AlterError::TriggerNotFound("audit_missing".to_owned()).into()),
+        ),
+        (
+            "DROP TRIGGER IF EXISTS audit_missing",
+            Ok(Payload::DropTrigger),
+        ),
+        (
+            "DROP TRIGGER audit_insert, count_insert, count_delete, audit_change",
+            Ok(Payload::DropTrigger),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
+
+test_case!(function, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Item (id INTEGER, name TEXT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TRIGGER check_id BEFORE INSERT OR UPDATE ON Item FOR EACH ROW
+            EXECUTE FUNCTION is_valid(NEW.id)",
+            Err(AlterError::FunctionNotFound("is_valid".to_owned()).into()),
+        ),
+        (
+            "CREATE FUNCTION is_valid(n INTEGER) RETURN n > 0",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TRIGGER check_id BEFORE INSERT OR UPDATE ON Item FOR EACH ROW
+            EXECUTE FUNCTION is_valid(NEW.id)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES (1, 'a'), (-1, 'b'), (2, 'c')",
+            Ok(Payload::Insert(2)),
+        ),
+        ("UPDATE Item SET id = id - 1", Ok(Payload::Update(1))),
+        (
+            "SELECT id, name FROM Item",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                1     "a".to_owned();
+                1     "c".to_owned()
+            )),
+        ),
+        (
+            "CREATE FUNCTION is_kept(name TEXT) RETURN name <> 'c'",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TRIGGER keep_row BEFORE DELETE ON Item FOR EACH ROW
+            EXECUTE FUNCTION is_kept(OLD.name)",
+            Ok(Payload::Create),
+        ),
+        ("DELETE FROM Item", Ok(Payload::Delete(1))),
+        (
+            "SELECT id, name FROM Item",
+            Ok(select!(id | name I64 | Str; 1 "c".to_owned())),
+        ),
+        ("DROP TABLE Item", Ok(Payload::DropTable)),
+        (
+            "DROP TRIGGER check_id",
+            Err(AlterError::TriggerNotFound("check_id".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
+
+test_case!(nesting, {
+    let g = get_tester!();
+
+    g.get_glue().limits.max_nesting_depth = 5;
+
+    let test_cases = [
+        (
+            "CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TRIGGER next_item AFTER INSERT ON Item FOR EACH ROW
+            INSERT INTO Item SELECT NEW.id + 1, NEW.name WHERE NEW.id < 5",
+            Ok(Payload::Create),
+        ),
+        ("INSERT INTO Item VALUES (1, 'a')", Ok(Payload::Insert(1))),
+        (
+            "SELECT COUNT(*), MAX(id) FROM Item",
+            Ok(select!("COUNT(*)" | "MAX(id)" I64 | I64; 5 5)),
+        ),
+        (
+            "CREATE OR REPLACE TRIGGER next_item AFTER INSERT ON Item FOR EACH ROW
+            INSERT INTO Item VALUES (NEW.id + 1, NEW.name)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES (100, 'b')",
+            Err(ExecuteError::NestingDepthExceeded("trigger next_item".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
+
+test_case!(foreign_key, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Parent (id INTEGER PRIMARY KEY, code INTEGER UNIQUE)",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TABLE Child (
+                id INTEGER PRIMARY KEY,
+                parent_code INTEGER REFERENCES Parent (code) ON DELETE CASCADE ON UPDATE CASCADE
+            )",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TABLE Audit (action TEXT, id INTEGER)",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TRIGGER audit_delete AFTER DELETE ON Child FOR EACH ROW
+            INSERT INTO Audit VALUES ('delete', OLD.id)",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TRIGGER audit_update AFTER UPDATE ON Child FOR EACH ROW
+            INSERT INTO Audit VALUES ('update', OLD.id)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Parent VALUES (1, 1), (2, 2)",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "INSERT INTO Child VALUES (1, 1), (2, 2)",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "UPDATE Parent SET code = 3 WHERE id = 1",
+            Ok(Payload::Update(1)),
+        ),
+        ("DELETE FROM Parent WHERE id = 2", Ok(Payload::Delete(1))),
+        (
+            "SELECT id, parent_code FROM Child",
+            Ok(select!(id | parent_code I64 | I64; 1 3)),
+        ),
+        // changes made by foreign key actions do not fire triggers
+        (
+            "SELECT * FROM Audit",
+            Ok(Payload::Select {
+                labels: vec!["action".to_owned(), "id".to_owned()],
+                rows: Vec::new(),
+            }),
+        ),
+        ("DELETE FROM Child", Ok(Payload::Delete(1))),
+        (
+            "SELECT * FROM Audit",
+            Ok(select!(action | id Str | I64; "delete".to_owned() 1)),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
+
+test_case!(set_new, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT, price INTEGER CHECK (price >= 0))",
+            Ok(Payload::Create),
+        ),
+        ("CREATE TABLE Audit (name TEXT, price INTEGER)", Ok(Payload::Create)),
+        (
+            "CREATE TRIGGER clamp_price BEFORE INSERT OR UPDATE ON Item FOR EACH ROW
+            SET NEW.name = UPPER(NEW.name),
+                NEW.price = CASE WHEN NEW.price < 0 THEN 0 ELSE NEW.price END",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TRIGGER audit_insert AFTER INSERT ON Item FOR EACH ROW
+            INSERT INTO Audit VALUES (NEW.name, NEW.price)",
+            Ok(Payload::Create),
+        ),
+        // BEFORE triggers fire before the CHECK constraint is validated
+        (
+            "INSERT INTO Item VALUES (1, 'apple', -10), (2, 'banana', 5)",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "UPDATE Item SET price = -1 WHERE id = 2",
+            Ok(Payload::Update(1)),
+        ),
+        (
+            "INSERT INTO Item VALUES (2, 'cherry', -3), (3, 'fig', -2)
+            ON CONFLICT (id) DO UPDATE SET price = EXCLUDED.price",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "SELECT * FROM Item",
+            Ok(select!(
+                id  | name                | price
+                I64 | Str                 | I64;
+                1     "APPLE".to_owned()    0;
+                2     "BANANA".to_owned()   0;
+                3     "FIG".to_owned()      0
+            )),
+        ),
+        (
+            "SELECT * FROM Audit",
+            Ok(select!(
+                name                | price
+                Str                 | I64;
+                "APPLE".to_owned()    0;
+                "BANANA".to_owned()   5;
+                "FIG".to_owned()      0
+            )),
+        ),
+        (
+            "CREATE TRIGGER set_after AFTER INSERT ON Item FOR EACH ROW SET NEW.price = 0",
+            Err(AlterError::TriggerSetNewNotBefore("set_after".to_owned()).into()),
+        ),
+        (
+            "CREATE TRIGGER set_delete BEFORE UPDATE OR DELETE ON Item FOR EACH ROW
+            SET NEW.price = 0",
+            Err(AlterError::TriggerSetNewNotBefore("set_delete".to_owned()).into()),
+        ),
+        (
+            "CREATE TRIGGER set_wrong BEFORE INSERT ON Item FOR EACH ROW SET NEW.wrong = 0",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES (4, 'grape', 1)",
+            Err(TriggerError::ColumnNotFound("NEW.wrong".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
@@ -0,0 +1,128 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::TranslateError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(update_delete_join, {
+    let g = get_tester!();
+
+    g.run(
+        "
+CREATE TABLE Item (
+    id INTEGER PRIMARY KEY,
+    name TEXT,
+    price INTEGER
+);",
+    )
+    .await;
+    g.run(
+        "
+CREATE TABLE PriceChange (
+    item_id INTEGER,
+    supplier_id INTEGER,
+    price INTEGER
+);",
+    )
+    .await;
+    g.run("CREATE TABLE Supplier (id INTEGER, name TEXT);")
+        .await;
+    g.run(
+        "
+INSERT INTO Item VALUES
+    (1, 'Apple', 100),
+    (2, 'Banana', 200),
+    (3, 'Cherry', 300),
+    (4, 'Durian', 400);",
+    )
+    .await;
+    g.run("INSERT INTO PriceChange VALUES (1, 10, 150), (3, 20, 350), (5, 10, 550);")
+        .await;
+    g.run("INSERT INTO Supplier VALUES (10, 'Farm'), (20, 'Market');")
+        .await;
+
+    g.named_test(
+        "update from joined table",
+        "
+        UPDATE Item SET price = PriceChange.price
+        FROM PriceChange
+        WHERE Item.id = PriceChange.item_id;
+        ",
+        Ok(Payload::Update(2)),
+    )
+    .await;
+
+    g.test(
+        "SELECT * FROM Item ORDER BY id;",
+        Ok(select!(
+            id  | name                | price
+            I64 | Str                 | I64;
+            1     "Apple".to_owned()    150;
+            2     "Banana".to_owned()   200;
+            3     "Cherry".to_owned()   350;
+            4     "Durian".to_owned()   400
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "update from joins with primary key on target table",
+        "
+        UPDATE Item SET name = Item.name || '@' || Supplier.name
+        FROM PriceChange
+        INNER JOIN Supplier ON Supplier.id = PriceChange.supplier_id
+        WHERE Item.id = 3 AND Item.id = PriceChange.item_id
+        RETURNING id, name;
+        ",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            3     "Cherry@Market".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "update from without matching rows",
+        "
+        UPDATE Item SET price = 0
+        FROM Supplier
+        WHERE Item.price = Supplier.id;
+        ",
+        Ok(Payload::Update(0)),
+    )
+    .await;
+
+    g.named_test(
+        "delete using joined table",
+        "
+        DELETE FROM Item
+        USING PriceChange
+        WHERE Item.id = PriceChange.item_id AND PriceChange.supplier_id = 10
+        RETURNING name;
+        ",
+        Ok(select!(name Str; "Apple".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "delete using multiple tables",
+        "DELETE FROM Item USING PriceChange, Supplier WHERE Item.id = PriceChange.item_id;",
+        Err(TranslateError::TooManyTables.into()),
+    )
+    .await;
+
+    g.test(
+        "SELECT id, name FROM Item ORDER BY id;",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            2     "Banana".to_owned();
+            3     "Cherry@Market".to_owned();
+            4     "Durian".to_owned()
+        )),
+    )
+    .await;
+});
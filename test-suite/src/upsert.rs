@@ -0,0 +1,125 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{InsertError, TranslateError, ValidateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(upsert, {
+    let g = get_tester!();
+
+    g.run(
+        "
+CREATE TABLE Item (
+    id INTEGER PRIMARY KEY,
+    name TEXT UNIQUE,
+    price INTEGER,
+    stock INTEGER NULL
+);",
+    )
+    .await;
+    g.run("INSERT INTO Item VALUES (1, 'Apple', 100, 10), (2, 'Banana', 200, 20);")
+        .await;
+
+    g.named_test(
+        "do nothing on primary key conflict",
+        "INSERT INTO Item VALUES (1, 'Cherry', 300, 30), (3, 'Durian', 400, 40) ON CONFLICT (id) DO NOTHING;",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.named_test(
+        "do nothing without conflict target",
+        "INSERT INTO Item VALUES (4, 'Apple', 500, 50) ON CONFLICT DO NOTHING;",
+        Ok(Payload::Insert(0)),
+    )
+    .await;
+
+    g.named_test(
+        "do update with excluded row",
+        "
+        INSERT INTO Item VALUES (1, 'Apple', 150, 5), (2, 'Banana', 250, 5)
+        ON CONFLICT (id) DO UPDATE
+        SET price = EXCLUDED.price, stock = stock + EXCLUDED.stock
+        WHERE EXCLUDED.price > 200;
+        ",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.named_test(
+        "do update on unique column conflict",
+        "
+        INSERT INTO Item VALUES (5, 'Durian', 450, NULL), (6, 'Eggplant', 600, 60)
+        ON CONFLICT (name) DO UPDATE SET price = EXCLUDED.price, stock = EXCLUDED.stock;
+        ",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+
+    g.test(
+        "SELECT * FROM Item ORDER BY id;",
+        Ok(select_with_null!(
+            id     | name                       | price     | stock;
+            I64(1)   Str("Apple".to_owned())      I64(100)    I64(10);
+            I64(2)   Str("Banana".to_owned())     I64(250)    I64(25);
+            I64(3)   Str("Durian".to_owned())     I64(450)    Null;
+            I64(6)   Str("Eggplant".to_owned())   I64(600)    I64(60)
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "updated value still validated against unique columns",
+        "INSERT INTO Item VALUES (1, 'Apple', 0, 0) ON CONFLICT (id) DO UPDATE SET name = 'Banana';",
+        Err(ValidateError::DuplicateEntryOnUniqueField(
+            Str("Banana".to_owned()),
+            "name".to_owned(),
+        )
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "conflict target must be unique",
+        "INSERT INTO Item VALUES (7, 'Fig', 700, 70) ON CONFLICT (price) DO NOTHING;",
+        Err(InsertError::ConflictTargetNotUnique("price".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "conflict target column not found",
+        "INSERT INTO Item VALUES (7, 'Fig', 700, 70) ON CONFLICT (weight) DO NOTHING;",
+        Err(InsertError::WrongColumnName("weight".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "do update requires conflict target",
+        "INSERT INTO Item VALUES (7, 'Fig', 700, 70) ON CONFLICT DO UPDATE SET price = 0;",
+        Err(TranslateError::LackOfConflictTargetForDoUpdate.into()),
+    )
+    .await;
+
+    g.run("CREATE TABLE Tag (id INTEGER, code TEXT, hits INTEGER, UNIQUE (code));")
+        .await;
+    g.run("INSERT INTO Tag VALUES (1, 'sql', 1);").await;
+
+    g.named_test(
+        "do update on single column unique key conflict",
+        "INSERT INTO Tag VALUES (2, 'sql', 1) ON CONFLICT (code) DO UPDATE SET hits = hits + 1;",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.test(
+        "SELECT * FROM Tag;",
+        Ok(select!(
+            id  | code             | hits
+            I64 | Str              | I64;
+            1     "sql".to_owned()   2
+        )),
+    )
+    .await;
+});
@@ -0,0 +1,167 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, FetchError},
+        prelude::{Payload, PayloadVariable, Value::*},
+    },
+};
+
+test_case!(view, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Person (id INTEGER, name TEXT, age INTEGER)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Person VALUES (1, 'Alice', 31), (2, 'Bob', 17), (3, 'Carol', 45)",
+            Ok(Payload::Insert(3)),
+        ),
+        (
+            "CREATE TABLE Pet (owner_id INTEGER, pet TEXT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Pet VALUES (1, 'cat'), (3, 'dog')",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "CREATE VIEW Adult AS SELECT id, name FROM Person WHERE age >= 18",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT * FROM Adult",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                1     "Alice".to_owned();
+                3     "Carol".to_owned()
+            )),
+        ),
+        (
+            "SELECT name FROM Adult WHERE id = 3",
+            Ok(select!(name Str; "Carol".to_owned())),
+        ),
+        (
+            "SELECT Adult.name, Pet.pet FROM Adult JOIN Pet ON Adult.id = Pet.owner_id",
+            Ok(select!(
+                name               | pet
+                Str                | Str;
+                "Alice".to_owned()   "cat".to_owned();
+                "Carol".to_owned()   "dog".to_owned()
+            )),
+        ),
+        (
+            "SELECT a.name FROM Adult a WHERE a.id = 1",
+            Ok(select!(name Str; "Alice".to_owned())),
+        ),
+        (
+            "SELECT name FROM Person WHERE id IN (SELECT id FROM Adult)",
+            Ok(select!(
+                name
+                Str;
+                "Alice".to_owned();
+                "Carol".to_owned()
+            )),
+        ),
+        (
+            "CREATE VIEW Adult AS SELECT id FROM Person",
+            Err(AlterError::ViewAlreadyExists("Adult".to_owned()).into()),
+        ),
+        (
+            "CREATE VIEW Person AS SELECT 1",
+            Err(AlterError::TableAlreadyExists("Person".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Adult (id INTEGER)",
+            Err(AlterError::ViewAlreadyExists("Adult".to_owned()).into()),
+        ),
+        (
+            "CREATE VIEW Missing AS SELECT * FROM Nothing",
+            Err(FetchError::TableNotFound("Nothing".to_owned()).into()),
+        ),
+        (
+            "CREATE VIEW TooMany (a, b, c) AS SELECT id, name FROM Person",
+            Err(FetchError::TooManyColumnAliases("TooMany".to_owned(), 2, 3).into()),
+        ),
+        (
+            "CREATE VIEW Named (person_id, person_name) AS SELECT id, name FROM Person",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT person_name FROM Named WHERE person_id = 2",
+            Ok(select!(person_name Str; "Bob".to_owned())),
+        ),
+        (
+            "CREATE VIEW Senior AS SELECT name FROM Adult WHERE id > 1",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT * FROM Senior",
+            Ok(select!(name Str; "Carol".to_owned())),
+        ),
+        (
+            "CREATE OR REPLACE VIEW Senior AS SELECT name FROM Person WHERE age > 40",
+            Ok(Payload::Create),
+        ),
+        (
+            "SELECT * FROM Senior",
+            Ok(select!(name Str; "Carol".to_owned())),
+        ),
+        (
+            "WITH Adult AS (SELECT name FROM Person WHERE id = 2) SELECT * FROM Adult",
+            Ok(select!(name Str; "Bob".to_owned())),
+        ),
+        (
+            "SHOW VIEWS",
+            Ok(Payload::ShowVariable(PayloadVariable::Views(vec![
+                "Adult".to_owned(),
+                "Named".to_owned(),
+                "Senior".to_owned(),
+            ]))),
+        ),
+        (
+            "SELECT VIEW_NAME FROM GLUE_VIEWS",
+            Ok(select!(
+                VIEW_NAME
+                Str;
+                "Adult".to_owned();
+                "Named".to_owned();
+                "Senior".to_owned()
+            )),
+        ),
+        ("DROP VIEW Named, Senior", Ok(Payload::DropView)),
+        (
+            "DROP VIEW Senior",
+            Err(AlterError::ViewNotFound("Senior".to_owned()).into()),
+        ),
+        ("DROP VIEW IF EXISTS Senior", Ok(Payload::DropView)),
+        (
+            "SHOW VIEWS",
+            Ok(Payload::ShowVariable(PayloadVariable::Views(vec![
+                "Adult".to_owned()
+            ]))),
+        ),
+        (
+            "CREATE VIEW Shadow AS WITH Pet AS (SELECT 1 AS n) SELECT n FROM Pet",
+            Ok(Payload::Create),
+        ),
+        (
+            "DROP TABLE Person",
+            Err(AlterError::TableReadByView {
+                table_name: "Person".to_owned(),
+                view_name: "Adult".to_owned(),
+            }
+            .into()),
+        ),
+        ("DROP TABLE Pet", Ok(Payload::DropTable)),
+        ("SELECT * FROM Shadow", Ok(select!(n I64; 1))),
+        ("DROP VIEW Adult", Ok(Payload::DropView)),
+        ("DROP TABLE Person", Ok(Payload::DropTable)),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
@@ -0,0 +1,238 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{EvaluateError, TranslateError, WindowError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(window, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Item (id INTEGER, category TEXT, price INTEGER)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES
+                (1, 'fruit', 30),
+                (2, 'fruit', 10),
+                (3, 'meat', 50),
+                (4, 'fruit', 10),
+                (5, 'meat', 20),
+                (6, NULL, 20);",
+            Ok(Payload::Insert(6)),
+        ),
+        (
+            "SELECT id, ROW_NUMBER() OVER (ORDER BY price, id) AS rn FROM Item ORDER BY id",
+            Ok(select!(
+                id  | rn
+                I64 | I64;
+                1     5;
+                2     1;
+                3     6;
+                4     2;
+                5     3;
+                6     4
+            )),
+        ),
+        (
+            "SELECT
+                id,
+                RANK() OVER (ORDER BY price) AS rnk,
+                DENSE_RANK() OVER (ORDER BY price) AS dense
+            FROM Item ORDER BY id",
+            Ok(select!(
+                id  | rnk | dense
+                I64 | I64 | I64;
+                1     5     3;
+                2     1     1;
+                3     6     4;
+                4     1     1;
+                5     3     2;
+                6     3     2
+            )),
+        ),
+        (
+            "SELECT id, ROW_NUMBER() OVER (PARTITION BY category ORDER BY price DESC, id) AS rn
+            FROM Item ORDER BY id",
+            Ok(select!(
+                id  | rn
+                I64 | I64;
+                1     1;
+                2     2;
+                3     1;
+                4     3;
+                5     2;
+                6     1
+            )),
+        ),
+        (
+            "SELECT
+                id,
+                LAG(price) OVER (ORDER BY id) AS prev,
+                LEAD(price, 2, 0) OVER (ORDER BY id) AS next
+            FROM Item ORDER BY id",
+            Ok(select_with_null!(
+                id     | prev    | next;
+                I64(1)   Null      I64(50);
+                I64(2)   I64(30)   I64(10);
+                I64(3)   I64(10)   I64(20);
+                I64(4)   I64(50)   I64(20);
+                I64(5)   I64(10)   I64(0);
+                I64(6)   I64(20)   I64(0)
+            )),
+        ),
+        (
+            "SELECT id, price - LAG(price, 1, price) OVER (ORDER BY id) AS diff FROM Item ORDER BY id",
+            Ok(select_with_null!(
+                id     | diff;
+                I64(1)   I64(0);
+                I64(2)   I64(-20);
+                I64(3)   I64(40);
+                I64(4)   I64(-40);
+                I64(5)   I64(10);
+                I64(6)   I64(0)
+            )),
+        ),
+        (
+            "SELECT id, SUM(price) OVER (ORDER BY id) AS total FROM Item ORDER BY id",
+            Ok(select!(
+                id  | total
+                I64 | I64;
+                1     30;
+                2     40;
+                3     90;
+                4     100;
+                5     120;
+                6     140
+            )),
+        ),
+        (
+            "SELECT id, SUM(price) OVER (PARTITION BY category) AS total FROM Item ORDER BY id",
+            Ok(select!(
+                id  | total
+                I64 | I64;
+                1     50;
+                2     50;
+                3     70;
+                4     50;
+                5     70;
+                6     20
+            )),
+        ),
+        (
+            "SELECT id, SUM(price) OVER (ORDER BY price) AS total FROM Item ORDER BY id",
+            Ok(select!(
+                id  | total
+                I64 | I64;
+                1     90;
+                2     20;
+                3     140;
+                4     20;
+                5     60;
+                6     60
+            )),
+        ),
+        (
+            "SELECT
+                id,
+                SUM(price) OVER (ORDER BY id ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) AS total
+            FROM Item ORDER BY id",
+            Ok(select!(
+                id  | total
+                I64 | I64;
+                1     40;
+                2     90;
+                3     70;
+                4     80;
+                5     50;
+                6     40
+            )),
+        ),
+        (
+            "SELECT
+                id,
+                COUNT(*) OVER (ORDER BY price RANGE BETWEEN 10 PRECEDING AND CURRENT ROW) AS cnt
+            FROM Item ORDER BY id",
+            Ok(select!(
+                id  | cnt
+                I64 | I64;
+                1     3;
+                2     2;
+                3     1;
+                4     2;
+                5     4;
+                6     4
+            )),
+        ),
+        (
+            "SELECT
+                id,
+                FIRST_VALUE(id) OVER (PARTITION BY category ORDER BY price, id) AS first,
+                LAST_VALUE(id) OVER (
+                    PARTITION BY category ORDER BY price, id
+                    ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING
+                ) AS last
+            FROM Item ORDER BY id",
+            Ok(select!(
+                id  | first | last
+                I64 | I64   | I64;
+                1     2       1;
+                2     2       1;
+                3     5       3;
+                4     2       1;
+                5     5       3;
+                6     6       6
+            )),
+        ),
+        (
+            "SELECT category, SUM(price) AS total, RANK() OVER (ORDER BY SUM(price) DESC) AS rnk
+            FROM Item GROUP BY category ORDER BY rnk",
+            Ok(select_with_null!(
+                category                 | total   | rnk;
+                Str("meat".to_owned())     I64(70)   I64(1);
+                Str("fruit".to_owned())    I64(50)   I64(2);
+                Null                       I64(20)   I64(3)
+            )),
+        ),
+        (
+            "SELECT id FROM Item ORDER BY ROW_NUMBER() OVER (ORDER BY price DESC, id)",
+            Ok(select!(id; I64; 3; 1; 5; 6; 2; 4)),
+        ),
+        (
+            "SELECT id FROM Item WHERE ROW_NUMBER() OVER () > 1",
+            Err(EvaluateError::WindowFunctionNotAllowed("ROW_NUMBER() OVER ()".to_owned()).into()),
+        ),
+        (
+            "SELECT ROW_NUMBER() FROM Item",
+            Err(TranslateError::WindowFunctionWithoutOver("ROW_NUMBER".to_owned()).into()),
+        ),
+        (
+            "SELECT UPPER(category) OVER () FROM Item",
+            Err(TranslateError::UnsupportedWindowFunction("UPPER".to_owned()).into()),
+        ),
+        (
+            "SELECT COUNT(*) OVER (ORDER BY id GROUPS 1 PRECEDING) FROM Item",
+            Err(TranslateError::UnsupportedWindowFrameUnits("GROUPS".to_owned()).into()),
+        ),
+        (
+            "SELECT SUM(price) OVER (ORDER BY id ROWS BETWEEN UNBOUNDED FOLLOWING AND CURRENT ROW)
+            FROM Item",
+            Err(WindowError::UnboundedFollowingFrameStart.into()),
+        ),
+        (
+            "SELECT SUM(price) OVER (ORDER BY id ROWS -1 PRECEDING) FROM Item",
+            Err(WindowError::NegativeFrameOffset(-1).into()),
+        ),
+        (
+            "SELECT COUNT(*) OVER (ORDER BY id, price RANGE 1 PRECEDING) FROM Item",
+            Err(WindowError::RangeOffsetRequiresSingleOrderBy.into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});